@@ -492,6 +492,34 @@ describe('attachment receipt mapping', () => {
       },
     });
   });
+
+  it('maps a DOCX paste receipt onto the document images in order', async () => {
+    const { editor, importer } = createImporter();
+    const data = new Uint8Array([0x50, 0x4b]);
+    const first = file('image1.png', 'image/png');
+    const second = file('image2.jpeg', 'image/jpeg');
+    editor.updateEventsImpl = () => {
+      const message = editor.messages.at(-1);
+      if (message?.type !== 'clipboard' || message.op.type !== 'paste_docx') return [];
+      editor.externalElements.push(external('node-a', 'image'), external('node-b', 'image'));
+      return [receipt(message.op.request_id, ['node-a', 'node-b'])];
+    };
+
+    expect(
+      importer.importDocx(
+        data,
+        [
+          { file: first, kind: 'image' },
+          { file: second, kind: 'image' },
+        ],
+        { onFailure: vi.fn() },
+      ),
+    ).toBe(true);
+    expect(editor.messages[0]).toMatchObject({ type: 'clipboard', op: { type: 'paste_docx', data } });
+    expect([...editor.inflightImages.keys()]).toEqual(['node-a', 'node-b']);
+    await waitForIdle(editor);
+    expect(upload.uploadImageFile.mock.calls.map(([input]) => input)).toEqual([first, second]);
+  });
 });
 
 describe('attachment target lifecycle', () => {
@@ -356,6 +356,39 @@ export class EditorAttachmentImporter {
     return this.#reserveAndSchedule(editor, mapped, onFailure);
   }
 
+  importDocx(
+    data: Uint8Array,
+    items: readonly AttachmentImportItem[],
+    {
+      onFailure,
+    }: {
+      onFailure: AttachmentImportFailureHandler;
+    },
+  ): boolean {
+    const editor = this.#editableEditor();
+    if (!editor) return false;
+
+    const requestId = crypto.randomUUID();
+    const enqueue = () => {
+      editor.enqueue({ type: 'clipboard', op: { type: 'paste_docx', request_id: requestId, data } });
+    };
+    if (items.length === 0) {
+      editor.updateNow(enqueue);
+      return true;
+    }
+
+    const nodeIds = this.#collectReceipt(editor, requestId, enqueue);
+    if (!nodeIds || !this.#isExactMapping(editor, nodeIds, items.length)) return false;
+
+    const mapped: MappedAttachment[] = [];
+    for (const [index, item] of items.entries()) {
+      const nodeId = nodeIds[index];
+      if (nodeId === undefined) return false;
+      mapped.push({ item, nodeId, removeOnFailure: true });
+    }
+    return this.#reserveAndSchedule(editor, mapped, onFailure);
+  }
+
   canReusePlaceholder(nodeId: string, kind: AttachmentPlaceholderKind): boolean {
     const editor = this.#ctx.editor;
     return editor !== undefined && this.#isEditorCurrent(editor) && this.#isAvailable(editor, nodeId, kind);
@@ -1,8 +1,14 @@
 import { beforeEach, describe, expect, it, vi } from 'vitest';
 import { deferPasteShortcutDuringComposition, handlePaste, requestPaste } from './clipboard';
-import type { Message } from '@typie/editor-ffi/browser';
+import type { DocxAttachment, Message } from '@typie/editor-ffi/browser';
 import type { AttachmentImportFailureHandler, AttachmentImportItem } from '../attachment-importer';
 
+const docxAttachments = vi.hoisted(() => vi.fn<(data: Uint8Array) => DocxAttachment[]>());
+
+vi.mock('$lib/wasm-ffi.svelte', () => ({
+  wasm: { docx_attachments: docxAttachments },
+}));
+
 type FakeEditor = {
   enqueue: ReturnType<typeof vi.fn<(message: Message) => void>>;
   scrollIntoView: ReturnType<typeof vi.fn>;
@@ -29,13 +35,17 @@ const createContext = (importAccepted = true) => {
   const importAtSelection = vi.fn<
     (items: readonly AttachmentImportItem[], options: { onFailure: AttachmentImportFailureHandler }) => boolean
   >(() => importAccepted);
+  const importDocx = vi.fn<
+    (data: Uint8Array, items: readonly AttachmentImportItem[], options: { onFailure: AttachmentImportFailureHandler }) => boolean
+  >(() => importAccepted);
   return {
     ctx: {
       editor,
-      attachmentImporter: { importAtSelection },
+      attachmentImporter: { importAtSelection, importDocx },
     },
     editor,
     importAtSelection,
+    importDocx,
     revealAdmissions,
   };
 };
@@ -201,6 +211,26 @@ describe('native paste arbitration', () => {
     expect(event.preventDefault).toHaveBeenCalledOnce();
     expect(editor.scrollIntoView).not.toHaveBeenCalled();
   });
+
+  it('pastes a single Word document through the DOCX import with its images as attachments', async () => {
+    const docx = new File(['PK'], 'report.docx', {
+      type: 'application/vnd.openxmlformats-officedocument.wordprocessingml.document',
+    });
+    docxAttachments.mockReturnValue([{ name: 'image1.png', content_type: 'image/png', data: new Uint8Array([1, 2]) }]);
+    const { ctx, importAtSelection, importDocx } = createContext();
+    const event = pasteEvent(clipboardData({ text: 'ignored', items: [transferItem(docx)] }));
+
+    handlePaste(ctx as never, event, vi.fn());
+
+    await vi.waitFor(() => {
+      expect(importDocx).toHaveBeenCalledOnce();
+    });
+    expect(importAtSelection).not.toHaveBeenCalled();
+    expect(event.preventDefault).toHaveBeenCalledOnce();
+    const [data, items] = importDocx.mock.calls[0] ?? [];
+    expect(data).toEqual(new Uint8Array([0x50, 0x4b]));
+    expect(items?.map(({ file, kind }) => [file.name, file.type, kind])).toEqual([['image1.png', 'image/png', 'image']]);
+  });
 });
 
 describe('composition paste fallback', () => {
@@ -1,3 +1,5 @@
+import { wasm } from '$lib/wasm-ffi.svelte';
+import type { DocxAttachment } from '@typie/editor-ffi/browser';
 import type { AttachmentImportFailureHandler, AttachmentImportItem } from '../attachment-importer';
 import type { Editor, EditorContext } from '../editor.svelte';
 import type { ImeTextInput } from '../input/ime-context';
@@ -74,6 +76,10 @@ const toImportItem = (file: File): AttachmentImportItem => ({
   kind: file.type.startsWith('image/') ? 'image' : 'file',
 });
 
+const DOCX_TYPE = 'application/vnd.openxmlformats-officedocument.wordprocessingml.document';
+
+const isDocx = (file: File): boolean => file.type === DOCX_TYPE || file.name.toLowerCase().endsWith('.docx');
+
 const filesFromTransfer = (data: DataTransfer): File[] => {
   const fileItems = [...data.items].filter((item) => item.kind === 'file');
   const files = fileItems.map((item) => item.getAsFile());
@@ -100,6 +106,27 @@ const scrollAfterPaste = (editor: Editor): void => {
   editor.scrollIntoView({ target: { type: 'current_selection_head' }, policy: 'typewriter' });
 };
 
+const pasteDocx = async (ctx: EditorContext, file: File, onFailure: AttachmentImportFailureHandler): Promise<void> => {
+  const editor = ctx.editor;
+  if (!editor) return;
+
+  const data = new Uint8Array(await file.arrayBuffer());
+  if (ctx.editor !== editor || editor.terminal || editor.readOnly) return;
+
+  let attachments: DocxAttachment[];
+  try {
+    attachments = wasm.docx_attachments(data);
+  } catch (err) {
+    console.error('Failed to read pasted Word document:', err);
+    return;
+  }
+  const items = attachments.map<AttachmentImportItem>((attachment) => ({
+    file: new File([attachment.data], attachment.name, { type: attachment.content_type }),
+    kind: 'image',
+  }));
+  if (ctx.attachmentImporter.importDocx(data, items, { onFailure })) scrollAfterPaste(editor);
+};
+
 const paste = (
   ctx: EditorContext,
   {
@@ -123,6 +150,11 @@ const paste = (
     });
     return true;
   }
+  const [first] = files;
+  if (files.length === 1 && first && isDocx(first)) {
+    void pasteDocx(ctx, first, onFailure);
+    return true;
+  }
   if (files.length > 0) {
     const imported = ctx.attachmentImporter.importAtSelection(files.map(toImportItem), { onFailure });
     if (imported) scrollAfterPaste(editor);
@@ -0,0 +1,21 @@
+use std::path::PathBuf;
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    if args.len() < 3 {
+        eprintln!("Usage: python <library-path> <package-dir>");
+        std::process::exit(1);
+    }
+
+    let library_path = PathBuf::from(&args[1]);
+    let package_dir = PathBuf::from(&args[2]);
+
+    let metas = editor_bindgen::reader::read_ffi_meta(&library_path);
+    eprintln!("Found {} FFI types", metas.len());
+
+    let interfaces = editor_bindgen::reader::read_ffi_interfaces(&library_path);
+    eprintln!("Found {} FFI interfaces", interfaces.len());
+
+    editor_bindgen::python::generate_all(&metas, &interfaces, &package_dir);
+    eprintln!("Generated Python package in {}", package_dir.display());
+}
@@ -0,0 +1,17 @@
+use heck::{
+    ToKebabCase, ToLowerCamelCase, ToShoutyKebabCase, ToShoutySnakeCase, ToSnakeCase,
+    ToUpperCamelCase,
+};
+
+/// Applies a serde `rename_all` strategy to a Rust identifier.
+pub(crate) fn apply_rename(name: &str, strategy: Option<&str>) -> String {
+    match strategy {
+        Some("snake_case") => name.to_snake_case(),
+        Some("camelCase") => name.to_lower_camel_case(),
+        Some("PascalCase") => name.to_upper_camel_case(),
+        Some("SCREAMING_SNAKE_CASE") => name.to_shouty_snake_case(),
+        Some("kebab-case") => name.to_kebab_case(),
+        Some("SCREAMING-KEBAB-CASE") => name.to_shouty_kebab_case(),
+        _ => name.to_string(),
+    }
+}
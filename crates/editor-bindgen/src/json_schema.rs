@@ -0,0 +1,417 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use serde_json::{Map, Value, json};
+
+use crate::case::apply_rename;
+use crate::meta::{FfiField, FfiKind, FfiMeta, FfiVariant};
+
+const DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+struct Context<'a> {
+    meta_map: HashMap<&'a str, &'a FfiMeta>,
+    defs: BTreeMap<String, Value>,
+    queue: Vec<String>,
+}
+
+/// Renders a JSON Schema (draft 2020-12) whose root is `root`, with one
+/// `$defs` entry per `#[ffi]` type reachable from it. Every named type must
+/// have metadata: a public schema that silently widened to "anything" would
+/// defeat its purpose, so unresolved names panic.
+pub fn generate(metas: &[FfiMeta], root: &str) -> String {
+    let mut ctx = Context {
+        meta_map: metas.iter().map(|m| (m.name.as_str(), m)).collect(),
+        defs: BTreeMap::new(),
+        queue: vec![root.to_owned()],
+    };
+    while let Some(name) = ctx.queue.pop() {
+        if ctx.defs.contains_key(&name) {
+            continue;
+        }
+        let meta = *ctx
+            .meta_map
+            .get(name.as_str())
+            .unwrap_or_else(|| panic!("`{name}` has no #[ffi] metadata"));
+        let schema = ctx.type_def(meta);
+        ctx.defs.insert(name, schema);
+    }
+
+    let schema = json!({
+        "$schema": DIALECT,
+        "title": root,
+        "$ref": format!("#/$defs/{root}"),
+        "$defs": ctx.defs,
+    });
+    let mut out = serde_json::to_string_pretty(&schema).expect("schema serializes");
+    out.push('\n');
+    out
+}
+
+impl<'a> Context<'a> {
+    fn type_def(&mut self, meta: &'a FfiMeta) -> Value {
+        let generics: HashSet<&str> = meta.generics.iter().map(String::as_str).collect();
+        match &meta.kind {
+            FfiKind::Custom { target } => self.type_schema(target, &generics),
+            FfiKind::Struct { fields } => {
+                self.object(None, fields, meta.serde_rename_all.as_deref(), &generics)
+            }
+            FfiKind::Enum {
+                variants,
+                serde_tag,
+                ..
+            } => self.enum_def(meta, variants, serde_tag.as_deref(), &generics),
+        }
+    }
+
+    /// Mirrors serde's enum representations, as the Python generator does:
+    /// unit-only enums are strings, `serde(tag)` enums are internally tagged
+    /// objects (single-struct tuple variants flatten their fields), and the
+    /// rest are externally tagged.
+    fn enum_def(
+        &mut self,
+        meta: &'a FfiMeta,
+        variants: &'a [FfiVariant],
+        serde_tag: Option<&str>,
+        generics: &HashSet<&str>,
+    ) -> Value {
+        let rename_all = meta.serde_rename_all.as_deref();
+        let serial = |name: &str| apply_rename(name, rename_all);
+
+        if serde_tag.is_none()
+            && variants
+                .iter()
+                .all(|v| matches!(v, FfiVariant::Unit { .. }))
+        {
+            let names: Vec<_> = variants.iter().map(|v| serial(variant_name(v))).collect();
+            return json!({ "type": "string", "enum": names });
+        }
+
+        let one_of = variants
+            .iter()
+            .map(|variant| {
+                let value = serial(variant_name(variant));
+                match (serde_tag, variant) {
+                    (Some(tag), FfiVariant::Unit { .. }) => {
+                        self.object(Some((tag, &value)), &[], None, generics)
+                    }
+                    (
+                        Some(tag),
+                        FfiVariant::Struct {
+                            fields,
+                            serde_rename_all,
+                            ..
+                        },
+                    ) => self.object(
+                        Some((tag, &value)),
+                        fields,
+                        serde_rename_all.as_deref().or(rename_all),
+                        generics,
+                    ),
+                    (Some(tag), FfiVariant::Tuple { tys, .. }) => {
+                        match self.flattened_struct(tys) {
+                            Some((fields, inner_rename)) => {
+                                self.object(Some((tag, &value)), fields, inner_rename, generics)
+                            }
+                            None => self.object(Some((tag, &value)), &[], None, generics),
+                        }
+                    }
+                    (None, FfiVariant::Unit { .. }) => json!({ "const": value }),
+                    (
+                        None,
+                        FfiVariant::Struct {
+                            fields,
+                            serde_rename_all,
+                            ..
+                        },
+                    ) => {
+                        let payload = self.object(
+                            None,
+                            fields,
+                            serde_rename_all.as_deref().or(rename_all),
+                            generics,
+                        );
+                        externally_tagged(&value, payload)
+                    }
+                    (None, FfiVariant::Tuple { tys, .. }) => {
+                        let payload = match tys.as_slice() {
+                            [single] => self.type_schema(single, generics),
+                            _ => {
+                                let items: Vec<_> = tys
+                                    .iter()
+                                    .map(|ty| self.type_schema(ty, generics))
+                                    .collect();
+                                json!({
+                                    "type": "array",
+                                    "prefixItems": items,
+                                    "items": false,
+                                })
+                            }
+                        };
+                        externally_tagged(&value, payload)
+                    }
+                }
+            })
+            .collect::<Vec<_>>();
+        json!({ "oneOf": one_of })
+    }
+
+    fn flattened_struct(&self, tys: &[String]) -> Option<(&'a [FfiField], Option<&'a str>)> {
+        let [single] = tys else {
+            return None;
+        };
+        let meta = self.meta_map.get(single.as_str())?;
+        match &meta.kind {
+            FfiKind::Struct { fields } => Some((fields, meta.serde_rename_all.as_deref())),
+            _ => None,
+        }
+    }
+
+    /// Fields with a serde default, or of `Option` type, may be omitted.
+    fn object(
+        &mut self,
+        tag: Option<(&str, &str)>,
+        fields: &[FfiField],
+        rename_all: Option<&str>,
+        generics: &HashSet<&str>,
+    ) -> Value {
+        let mut properties = Map::new();
+        let mut required = Vec::new();
+        if let Some((key, value)) = tag {
+            properties.insert(key.to_owned(), json!({ "const": value }));
+            required.push(key.to_owned());
+        }
+        for field in fields {
+            let key = field
+                .serde_rename
+                .clone()
+                .unwrap_or_else(|| apply_rename(&field.name, rename_all));
+            properties.insert(key.clone(), self.type_schema(&field.ty, generics));
+            if !field.has_serde_default && !field.ty.starts_with("Option<") {
+                required.push(key);
+            }
+        }
+        let mut schema = json!({ "type": "object", "properties": properties });
+        if !required.is_empty() {
+            schema["required"] = json!(required);
+        }
+        schema
+    }
+
+    fn type_schema(&mut self, rust_ty: &str, generics: &HashSet<&str>) -> Value {
+        let parsed: syn::Type = syn::parse_str(rust_ty).expect("failed to parse type");
+        self.syn_type_schema(&parsed, generics)
+    }
+
+    fn syn_type_schema(&mut self, ty: &syn::Type, generics: &HashSet<&str>) -> Value {
+        let type_path = match ty {
+            syn::Type::Path(type_path) => type_path,
+            syn::Type::Tuple(tuple) if tuple.elems.is_empty() => return json!({ "type": "null" }),
+            _ => panic!("unsupported type in FFI metadata: {}", quote::quote!(#ty)),
+        };
+        let last = type_path.path.segments.last().expect("empty path");
+        let ident = last.ident.to_string();
+        let args: Vec<&syn::Type> = match &last.arguments {
+            syn::PathArguments::AngleBracketed(args) => args
+                .args
+                .iter()
+                .filter_map(|a| match a {
+                    syn::GenericArgument::Type(ty) => Some(ty),
+                    _ => None,
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+
+        match (ident.as_str(), args.as_slice()) {
+            ("bool", []) => json!({ "type": "boolean" }),
+            ("u8" | "u16" | "u32" | "u64" | "usize", []) => {
+                json!({ "type": "integer", "minimum": 0 })
+            }
+            ("i8" | "i16" | "i32" | "i64" | "isize", []) => json!({ "type": "integer" }),
+            ("f32" | "f64", []) => json!({ "type": "number" }),
+            ("String" | "char", []) => json!({ "type": "string" }),
+            (name, []) if generics.contains(name) => json!({}),
+            ("Option", [inner]) => json!({
+                "anyOf": [self.syn_type_schema(inner, generics), { "type": "null" }],
+            }),
+            ("Vec" | "Vector" | "HashSet" | "BTreeSet" | "SmallVec", [inner]) => json!({
+                "type": "array",
+                "items": self.syn_type_schema(inner, generics),
+            }),
+            ("HashMap" | "BTreeMap", [key, value]) => {
+                let mut schema = json!({
+                    "type": "object",
+                    "additionalProperties": self.syn_type_schema(value, generics),
+                });
+                let key = self.syn_type_schema(key, generics);
+                if key != json!({ "type": "string" }) {
+                    schema["propertyNames"] = key;
+                }
+                schema
+            }
+            ("Box" | "Arc", [inner]) => self.syn_type_schema(inner, generics),
+            // Generic `#[ffi]` types (`Tri<T>`) are declared once, with open parameters.
+            (name, _) if self.meta_map.contains_key(name) => {
+                self.queue.push(ident.clone());
+                json!({ "$ref": format!("#/$defs/{ident}") })
+            }
+            _ => panic!("`{ident}` has no #[ffi] metadata"),
+        }
+    }
+}
+
+fn externally_tagged(variant: &str, payload: Value) -> Value {
+    json!({
+        "type": "object",
+        "properties": { variant: payload },
+        "required": [variant],
+        "additionalProperties": false,
+    })
+}
+
+fn variant_name(variant: &FfiVariant) -> &str {
+    match variant {
+        FfiVariant::Unit { name }
+        | FfiVariant::Tuple { name, .. }
+        | FfiVariant::Struct { name, .. } => name,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(name: &str, ty: &str, default: bool) -> FfiField {
+        FfiField {
+            name: name.into(),
+            serde_rename: None,
+            ty: ty.into(),
+            has_serde_default: default,
+            ffi_default_override: None,
+        }
+    }
+
+    fn schema(metas: &[FfiMeta], root: &str) -> Value {
+        serde_json::from_str(&generate(metas, root)).unwrap()
+    }
+
+    #[test]
+    fn structs_require_fields_without_defaults() {
+        let metas = vec![FfiMeta {
+            name: "Image".into(),
+            serde_rename_all: None,
+            kind: FfiKind::Struct {
+                fields: vec![
+                    field("id", "Option<String>", false),
+                    field("proportion", "u32", true),
+                    field("tags", "Vec<String>", false),
+                ],
+            },
+            generics: Vec::new(),
+        }];
+
+        let schema = schema(&metas, "Image");
+        assert_eq!(schema["$ref"], "#/$defs/Image");
+        let image = &schema["$defs"]["Image"];
+        assert_eq!(image["required"], json!(["tags"]));
+        assert_eq!(
+            image["properties"]["id"],
+            json!({ "anyOf": [{ "type": "string" }, { "type": "null" }] })
+        );
+        assert_eq!(image["properties"]["tags"]["items"]["type"], "string");
+    }
+
+    #[test]
+    fn tagged_enums_become_one_of_with_const_tags() {
+        let metas = vec![
+            FfiMeta {
+                name: "Mark".into(),
+                serde_rename_all: Some("snake_case".into()),
+                kind: FfiKind::Enum {
+                    variants: vec![
+                        FfiVariant::Unit {
+                            name: "Bold".into(),
+                        },
+                        FfiVariant::Struct {
+                            name: "FontSize".into(),
+                            fields: vec![field("value", "u32", false)],
+                            serde_rename_all: None,
+                        },
+                        FfiVariant::Tuple {
+                            name: "Link".into(),
+                            tys: vec!["LinkMark".into()],
+                        },
+                    ],
+                    serde_tag: Some("type".into()),
+                    default_variant: None,
+                },
+                generics: Vec::new(),
+            },
+            FfiMeta {
+                name: "LinkMark".into(),
+                serde_rename_all: None,
+                kind: FfiKind::Struct {
+                    fields: vec![field("href", "String", false)],
+                },
+                generics: Vec::new(),
+            },
+        ];
+
+        let schema = schema(&metas, "Mark");
+        let variants = schema["$defs"]["Mark"]["oneOf"].as_array().unwrap();
+        assert_eq!(variants[0]["properties"]["type"]["const"], "bold");
+        assert_eq!(variants[1]["properties"]["type"]["const"], "font_size");
+        assert_eq!(variants[1]["required"], json!(["type", "value"]));
+        assert_eq!(variants[2]["required"], json!(["type", "href"]));
+    }
+
+    #[test]
+    fn unit_enums_are_string_enums_and_only_reachable_types_are_defined() {
+        let metas = vec![
+            FfiMeta {
+                name: "Alignment".into(),
+                serde_rename_all: Some("snake_case".into()),
+                kind: FfiKind::Enum {
+                    variants: vec![
+                        FfiVariant::Unit {
+                            name: "Left".into(),
+                        },
+                        FfiVariant::Unit {
+                            name: "Right".into(),
+                        },
+                    ],
+                    serde_tag: None,
+                    default_variant: None,
+                },
+                generics: Vec::new(),
+            },
+            FfiMeta {
+                name: "Unused".into(),
+                serde_rename_all: None,
+                kind: FfiKind::Struct { fields: Vec::new() },
+                generics: Vec::new(),
+            },
+        ];
+
+        let schema = schema(&metas, "Alignment");
+        assert_eq!(
+            schema["$defs"]["Alignment"],
+            json!({ "type": "string", "enum": ["left", "right"] })
+        );
+        assert!(schema["$defs"].get("Unused").is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "`Missing` has no #[ffi] metadata")]
+    fn unresolved_types_are_rejected() {
+        let metas = vec![FfiMeta {
+            name: "Holder".into(),
+            serde_rename_all: None,
+            kind: FfiKind::Struct {
+                fields: vec![field("inner", "Missing", false)],
+            },
+            generics: Vec::new(),
+        }];
+
+        generate(&metas, "Holder");
+    }
+}
@@ -1,11 +1,9 @@
 use std::collections::{HashMap, HashSet};
 use std::path::Path;
 
-use heck::{
-    ToKebabCase, ToLowerCamelCase, ToShoutyKebabCase, ToShoutySnakeCase, ToSnakeCase,
-    ToUpperCamelCase,
-};
+use heck::ToLowerCamelCase;
 
+use crate::case::apply_rename;
 use crate::meta::{FfiField, FfiKind, FfiMeta, FfiVariant};
 
 const PACKAGE: &str = "co.typie.editor.ffi";
@@ -84,18 +82,6 @@ fn build_custom_type_map(metas: &[FfiMeta]) -> HashMap<String, String> {
         .collect()
 }
 
-fn apply_rename(name: &str, strategy: Option<&str>) -> String {
-    match strategy {
-        Some("snake_case") => name.to_snake_case(),
-        Some("camelCase") => name.to_lower_camel_case(),
-        Some("PascalCase") => name.to_upper_camel_case(),
-        Some("SCREAMING_SNAKE_CASE") => name.to_shouty_snake_case(),
-        Some("kebab-case") => name.to_kebab_case(),
-        Some("SCREAMING-KEBAB-CASE") => name.to_shouty_kebab_case(),
-        _ => name.to_string(),
-    }
-}
-
 fn apply_field_rename(field: &FfiField, strategy: Option<&str>) -> String {
     field
         .serde_rename
@@ -1,5 +1,12 @@
 pub mod meta;
 
+#[cfg(feature = "schema")]
+mod case;
+#[cfg(feature = "schema")]
+pub mod json_schema;
+#[cfg(feature = "schema")]
+pub mod reader;
+
 #[cfg(feature = "bin")]
 pub mod kotlin;
 #[cfg(feature = "bin")]
@@ -11,6 +18,6 @@ pub mod kotlin_jna;
 #[cfg(feature = "bin")]
 pub mod objc;
 #[cfg(feature = "bin")]
-pub mod reader;
+pub mod python;
 #[cfg(feature = "bin")]
 pub mod swift;
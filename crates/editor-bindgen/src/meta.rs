@@ -10,6 +10,15 @@ pub struct FfiMeta {
     pub generics: Vec<String>,
 }
 
+impl FfiMeta {
+    /// Decodes an `FFI_META_*` static in-process: a little-endian `u32`
+    /// payload length followed by the bitcode payload.
+    pub fn decode_static(bytes: &[u8]) -> Option<Self> {
+        let (len, payload) = bytes.split_first_chunk::<4>()?;
+        bitcode::decode(payload.get(..u32::from_le_bytes(*len) as usize)?).ok()
+    }
+}
+
 #[derive(Debug, Clone, Encode, Decode)]
 pub enum FfiKind {
     Struct {
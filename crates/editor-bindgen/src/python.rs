@@ -0,0 +1,674 @@
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use crate::case::apply_rename;
+use crate::meta::{
+    FfiField, FfiInterface, FfiKind, FfiMeta, FfiMethod, FfiParamType, FfiReturnType,
+    FfiScalarParam, FfiScalarReturn, FfiVariant,
+};
+
+const HEADER: &str = "# This file is auto-generated by editor-bindgen. Do not edit.\n";
+
+struct CodegenContext<'a> {
+    custom_types: HashMap<String, String>,
+    meta_map: HashMap<&'a str, &'a FfiMeta>,
+}
+
+/// Writes `types.py` (the JSON shape of every `#[ffi]` type as `TypedDict`s and
+/// aliases) and `__init__.py` (one wrapper class per exported interface) into
+/// the package directory that holds the pyo3 `_native` module.
+pub fn generate_all(metas: &[FfiMeta], interfaces: &[FfiInterface], output_dir: &Path) {
+    let ctx = CodegenContext {
+        custom_types: metas
+            .iter()
+            .filter_map(|m| match &m.kind {
+                FfiKind::Custom { target } => Some((m.name.clone(), target.clone())),
+                _ => None,
+            })
+            .collect(),
+        meta_map: metas.iter().map(|m| (m.name.as_str(), m)).collect(),
+    };
+
+    std::fs::create_dir_all(output_dir).expect("failed to create output directory");
+    std::fs::write(output_dir.join("types.py"), generate_types(metas, &ctx))
+        .expect("failed to write types.py");
+    std::fs::write(
+        output_dir.join("__init__.py"),
+        generate_module(interfaces, &ctx),
+    )
+    .expect("failed to write __init__.py");
+}
+
+fn generate_types(metas: &[FfiMeta], ctx: &CodegenContext) -> String {
+    let mut metas: Vec<&FfiMeta> = metas.iter().collect();
+    metas.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let mut out = String::new();
+    out.push_str(HEADER);
+    out.push('\n');
+    out.push_str("from typing import Any, Literal, NotRequired, TypeAlias, TypedDict, Union\n");
+
+    for meta in metas {
+        out.push_str("\n\n");
+        let generics: HashSet<&str> = meta.generics.iter().map(String::as_str).collect();
+        match &meta.kind {
+            FfiKind::Custom { target } => {
+                out.push_str(&format!(
+                    "{}: TypeAlias = \"{}\"\n",
+                    meta.name,
+                    map_type(target, &generics, ctx)
+                ));
+            }
+            FfiKind::Struct { fields } => {
+                out.push_str(&typed_dict(
+                    &meta.name,
+                    None,
+                    fields,
+                    meta.serde_rename_all.as_deref(),
+                    &generics,
+                    ctx,
+                ));
+            }
+            FfiKind::Enum {
+                variants,
+                serde_tag,
+                ..
+            } => out.push_str(&generate_enum(
+                meta,
+                variants,
+                serde_tag.as_deref(),
+                &generics,
+                ctx,
+            )),
+        }
+    }
+    out
+}
+
+/// Mirrors serde's enum representations: unit-only enums are string literals,
+/// `serde(tag)` enums are internally tagged objects (single-struct tuple
+/// variants flatten their fields, as in the Kotlin sealed classes), and the
+/// rest are externally tagged `{"variant": payload}` objects.
+fn generate_enum(
+    meta: &FfiMeta,
+    variants: &[FfiVariant],
+    serde_tag: Option<&str>,
+    generics: &HashSet<&str>,
+    ctx: &CodegenContext,
+) -> String {
+    let rename_all = meta.serde_rename_all.as_deref();
+    let serial = |name: &str| apply_rename(name, rename_all);
+
+    if serde_tag.is_none()
+        && variants
+            .iter()
+            .all(|v| matches!(v, FfiVariant::Unit { .. }))
+    {
+        let literals = variants
+            .iter()
+            .map(|v| format!("'{}'", serial(variant_name(v))))
+            .collect::<Vec<_>>()
+            .join(", ");
+        return format!("{}: TypeAlias = \"Literal[{}]\"\n", meta.name, literals);
+    }
+
+    let mut out = String::new();
+    let mut members = Vec::new();
+    for variant in variants {
+        let class_name = format!("{}{}", meta.name, variant_name(variant));
+        let tag_value = serial(variant_name(variant));
+        let shape = match (serde_tag, variant) {
+            (None, FfiVariant::Unit { .. }) => {
+                members.push(format!("Literal['{tag_value}']"));
+                continue;
+            }
+            (Some(tag), FfiVariant::Unit { .. }) => typed_dict(
+                &class_name,
+                Some((tag, &tag_value)),
+                &[],
+                None,
+                generics,
+                ctx,
+            ),
+            (
+                Some(tag),
+                FfiVariant::Struct {
+                    fields,
+                    serde_rename_all,
+                    ..
+                },
+            ) => typed_dict(
+                &class_name,
+                Some((tag, &tag_value)),
+                fields,
+                serde_rename_all.as_deref().or(rename_all),
+                generics,
+                ctx,
+            ),
+            (Some(tag), FfiVariant::Tuple { tys, .. }) => {
+                match flattened_struct(tys, ctx) {
+                    Some((fields, inner_rename)) => typed_dict(
+                        &class_name,
+                        Some((tag, &tag_value)),
+                        fields,
+                        inner_rename,
+                        generics,
+                        ctx,
+                    ),
+                    // serde rejects internally tagged non-object payloads; keep the
+                    // tag so callers can still dispatch.
+                    None => typed_dict(
+                        &class_name,
+                        Some((tag, &tag_value)),
+                        &[],
+                        None,
+                        generics,
+                        ctx,
+                    ),
+                }
+            }
+            (
+                None,
+                FfiVariant::Struct {
+                    fields,
+                    serde_rename_all,
+                    ..
+                },
+            ) => {
+                let payload = format!("{class_name}Payload");
+                let mut shape = typed_dict(
+                    &payload,
+                    None,
+                    fields,
+                    serde_rename_all.as_deref().or(rename_all),
+                    generics,
+                    ctx,
+                );
+                shape.push('\n');
+                shape.push_str(&format!(
+                    "{class_name} = TypedDict(\"{class_name}\", {{\"{tag_value}\": \"{payload}\"}})\n"
+                ));
+                shape
+            }
+            (None, FfiVariant::Tuple { tys, .. }) => {
+                let payload = match tys.as_slice() {
+                    [single] => map_type(single, generics, ctx),
+                    _ => format!(
+                        "tuple[{}]",
+                        tys.iter()
+                            .map(|ty| map_type(ty, generics, ctx))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ),
+                };
+                format!(
+                    "{class_name} = TypedDict(\"{class_name}\", {{\"{tag_value}\": \"{payload}\"}})\n"
+                )
+            }
+        };
+        out.push_str(&shape);
+        out.push('\n');
+        members.push(class_name);
+    }
+    out.push_str(&format!(
+        "{}: TypeAlias = \"Union[{}]\"\n",
+        meta.name,
+        members.join(", ")
+    ));
+    out
+}
+
+fn flattened_struct<'a>(
+    tys: &[String],
+    ctx: &'a CodegenContext,
+) -> Option<(&'a [FfiField], Option<&'a str>)> {
+    let [single] = tys else {
+        return None;
+    };
+    let meta = ctx.meta_map.get(single.as_str())?;
+    match &meta.kind {
+        FfiKind::Struct { fields } => Some((fields, meta.serde_rename_all.as_deref())),
+        _ => None,
+    }
+}
+
+fn variant_name(variant: &FfiVariant) -> &str {
+    match variant {
+        FfiVariant::Unit { name }
+        | FfiVariant::Tuple { name, .. }
+        | FfiVariant::Struct { name, .. } => name,
+    }
+}
+
+/// Functional `TypedDict` syntax, so serde keys that are not Python
+/// identifiers (`type`, kebab-case) still work. Annotations are strings and
+/// resolve lazily, which keeps declaration order irrelevant.
+fn typed_dict(
+    name: &str,
+    tag: Option<(&str, &str)>,
+    fields: &[FfiField],
+    rename_all: Option<&str>,
+    generics: &HashSet<&str>,
+    ctx: &CodegenContext,
+) -> String {
+    let mut entries = Vec::new();
+    if let Some((key, value)) = tag {
+        entries.push(format!("\"{key}\": \"Literal['{value}']\""));
+    }
+    for field in fields {
+        let key = field
+            .serde_rename
+            .clone()
+            .unwrap_or_else(|| apply_rename(&field.name, rename_all));
+        let ty = map_type(&field.ty, generics, ctx);
+        let ty = if field.has_serde_default {
+            format!("NotRequired[{ty}]")
+        } else {
+            ty
+        };
+        entries.push(format!("\"{key}\": \"{ty}\""));
+    }
+    format!(
+        "{name} = TypedDict(\"{name}\", {{{}}})\n",
+        entries.join(", ")
+    )
+}
+
+fn map_type(rust_ty: &str, generics: &HashSet<&str>, ctx: &CodegenContext) -> String {
+    let parsed: syn::Type = syn::parse_str(rust_ty).expect("failed to parse type");
+    map_syn_type(&parsed, generics, ctx)
+}
+
+fn map_syn_type(ty: &syn::Type, generics: &HashSet<&str>, ctx: &CodegenContext) -> String {
+    let type_path = match ty {
+        syn::Type::Path(type_path) => type_path,
+        syn::Type::Tuple(tuple) if tuple.elems.is_empty() => return "None".into(),
+        _ => panic!("unsupported type in FFI metadata: {}", quote::quote!(#ty)),
+    };
+    let segments: Vec<_> = type_path.path.segments.iter().collect();
+    let last = segments.last().expect("empty path");
+    let ident = last.ident.to_string();
+
+    let args: Vec<&syn::Type> = match &last.arguments {
+        syn::PathArguments::AngleBracketed(args) => args
+            .args
+            .iter()
+            .filter_map(|a| match a {
+                syn::GenericArgument::Type(ty) => Some(ty),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    if args.is_empty() {
+        if let Some(target) = ctx.custom_types.get(&ident) {
+            return map_type(target, generics, ctx);
+        }
+        return match ident.as_str() {
+            "bool" => "bool".into(),
+            "u8" | "u16" | "u32" | "u64" | "usize" | "i8" | "i16" | "i32" | "i64" | "isize" => {
+                "int".into()
+            }
+            "f32" | "f64" => "float".into(),
+            "String" | "char" => "str".into(),
+            _ if generics.contains(ident.as_str()) => "Any".into(),
+            _ if ctx.meta_map.contains_key(ident.as_str()) => ident,
+            _ => "Any".into(),
+        };
+    }
+
+    match (ident.as_str(), args.as_slice()) {
+        ("Option", [inner]) => format!("Union[{}, None]", map_syn_type(inner, generics, ctx)),
+        ("Vec" | "Vector" | "HashSet" | "BTreeSet" | "SmallVec", [inner]) => {
+            format!("list[{}]", map_syn_type(inner, generics, ctx))
+        }
+        ("HashMap" | "BTreeMap", [key, value]) => format!(
+            "dict[{}, {}]",
+            map_syn_type(key, generics, ctx),
+            map_syn_type(value, generics, ctx)
+        ),
+        ("Box" | "Arc", [inner]) => map_syn_type(inner, generics, ctx),
+        // Generic `#[ffi]` types (`Tri<T>`) are declared with `Any` parameters.
+        _ if ctx.meta_map.contains_key(ident.as_str()) => ident,
+        _ => "Any".into(),
+    }
+}
+
+fn generate_module(interfaces: &[FfiInterface], ctx: &CodegenContext) -> String {
+    let mut interfaces: Vec<&FfiInterface> = interfaces.iter().collect();
+    interfaces.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let mut out = String::new();
+    out.push_str(HEADER);
+    out.push('\n');
+    out.push_str("from __future__ import annotations\n");
+    out.push('\n');
+    out.push_str("import json\n");
+    out.push_str("from typing import Any, Optional\n");
+    out.push('\n');
+    out.push_str("from . import _native\n");
+    out.push_str("from .types import *  # noqa: F403\n");
+
+    for iface in interfaces {
+        out.push_str("\n\n");
+        out.push_str(&generate_class(iface, ctx));
+    }
+    out
+}
+
+fn generate_class(iface: &FfiInterface, ctx: &CodegenContext) -> String {
+    let mut out = String::new();
+    out.push_str(&format!("class {}:\n", iface.name));
+    out.push_str(&format!("    _inner: _native.{}\n", iface.name));
+    out.push('\n');
+    out.push_str("    @classmethod\n");
+    out.push_str(&format!(
+        "    def _wrap(cls, inner: _native.{}) -> {}:\n",
+        iface.name, iface.name
+    ));
+    out.push_str("        obj = cls.__new__(cls)\n");
+    out.push_str("        obj._inner = inner\n");
+    out.push_str("        return obj\n");
+
+    for method in &iface.methods {
+        out.push('\n');
+        out.push_str(&generate_method(iface, method, ctx));
+    }
+    out
+}
+
+fn generate_method(iface: &FfiInterface, method: &FfiMethod, ctx: &CodegenContext) -> String {
+    assert!(
+        !method.is_async,
+        "async FFI methods are not supported in Python bindings: {}::{}",
+        iface.name, method.name
+    );
+    let params = method
+        .params
+        .iter()
+        .map(|p| format!(", {}: {}", p.name, param_annotation(&p.ty, ctx)))
+        .collect::<String>();
+    let args = method
+        .params
+        .iter()
+        .map(|p| lower_param(&p.name, &p.ty))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let mut out = String::new();
+    if method.is_constructor {
+        out.push_str(&format!("    def __init__(self{params}) -> None:\n"));
+        out.push_str(&format!(
+            "        self._inner = _native.{}({args})\n",
+            iface.name
+        ));
+        return out;
+    }
+    out.push_str(&format!(
+        "    def {}(self{params}) -> {}:\n",
+        method.name,
+        return_annotation(&method.return_type, ctx)
+    ));
+    let call = format!("self._inner.{}({args})", method.name);
+    match lift_return(&method.return_type, "result") {
+        Some(lifted) => {
+            out.push_str(&format!("        result = {call}\n"));
+            out.push_str(&format!("        return {lifted}\n"));
+        }
+        None => out.push_str(&format!("        return {call}\n")),
+    }
+    out
+}
+
+fn scalar_annotation(ty: &str, ctx: &CodegenContext) -> String {
+    let ty = strip_generic_args(ty);
+    if ctx.meta_map.contains_key(ty) || is_primitive(ty) {
+        map_type(ty, &HashSet::new(), ctx)
+    } else {
+        ty.to_string()
+    }
+}
+
+fn param_annotation(ty: &FfiParamType, ctx: &CodegenContext) -> String {
+    match ty {
+        FfiParamType::Primitive(t) | FfiParamType::Complex(t) | FfiParamType::Owned(t) => {
+            scalar_annotation(t, ctx)
+        }
+        FfiParamType::Vec(FfiScalarParam::Primitive(t)) if t == "u8" => "bytes".into(),
+        FfiParamType::Vec(FfiScalarParam::Primitive(t) | FfiScalarParam::Complex(t)) => {
+            format!("list[{}]", scalar_annotation(t, ctx))
+        }
+        FfiParamType::Option(FfiScalarParam::Primitive(t) | FfiScalarParam::Complex(t)) => {
+            format!("Optional[{}]", scalar_annotation(t, ctx))
+        }
+    }
+}
+
+fn return_annotation(ty: &FfiReturnType, ctx: &CodegenContext) -> String {
+    let scalar = |s: &FfiScalarReturn| match s {
+        FfiScalarReturn::Primitive(t) | FfiScalarReturn::Complex(t) | FfiScalarReturn::Owned(t) => {
+            scalar_annotation(t, ctx)
+        }
+    };
+    match ty {
+        FfiReturnType::Unit => "None".into(),
+        FfiReturnType::Primitive(t) | FfiReturnType::Complex(t) | FfiReturnType::Owned(t) => {
+            scalar_annotation(t, ctx)
+        }
+        FfiReturnType::Vec(FfiScalarReturn::Primitive(t)) if t == "u8" => "bytes".into(),
+        FfiReturnType::Vec(s) => format!("list[{}]", scalar(s)),
+        FfiReturnType::Option(s) => format!("Optional[{}]", scalar(s)),
+    }
+}
+
+/// `Complex<T>` crosses as JSON text (the pyo3 export rewrites it to `String`),
+/// objects as their `_native` handle.
+fn lower_param(name: &str, ty: &FfiParamType) -> String {
+    match ty {
+        FfiParamType::Complex(_) => format!("json.dumps({name})"),
+        FfiParamType::Owned(_) => format!("{name}._inner"),
+        FfiParamType::Vec(FfiScalarParam::Complex(_)) => {
+            format!("[json.dumps(v) for v in {name}]")
+        }
+        FfiParamType::Option(FfiScalarParam::Complex(_)) => {
+            format!("None if {name} is None else json.dumps({name})")
+        }
+        _ => name.to_string(),
+    }
+}
+
+fn lift_return(ty: &FfiReturnType, var: &str) -> Option<String> {
+    Some(match ty {
+        FfiReturnType::Complex(_) => format!("json.loads({var})"),
+        FfiReturnType::Owned(t) => format!("{}._wrap({var})", strip_generic_args(t)),
+        FfiReturnType::Vec(FfiScalarReturn::Primitive(t)) if t == "u8" => format!("bytes({var})"),
+        FfiReturnType::Vec(FfiScalarReturn::Complex(_)) => {
+            format!("[json.loads(v) for v in {var}]")
+        }
+        FfiReturnType::Vec(FfiScalarReturn::Owned(t)) => {
+            format!("[{}._wrap(v) for v in {var}]", strip_generic_args(t))
+        }
+        FfiReturnType::Option(FfiScalarReturn::Complex(_)) => {
+            format!("None if {var} is None else json.loads({var})")
+        }
+        FfiReturnType::Option(FfiScalarReturn::Owned(t)) => format!(
+            "None if {var} is None else {}._wrap({var})",
+            strip_generic_args(t)
+        ),
+        _ => return None,
+    })
+}
+
+fn strip_generic_args(ty: &str) -> &str {
+    ty.split_once('<').map_or(ty, |(head, _)| head)
+}
+
+fn is_primitive(ty: &str) -> bool {
+    matches!(
+        ty,
+        "bool"
+            | "u8"
+            | "u16"
+            | "u32"
+            | "u64"
+            | "usize"
+            | "i8"
+            | "i16"
+            | "i32"
+            | "i64"
+            | "isize"
+            | "f32"
+            | "f64"
+            | "String"
+            | "char"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::meta::FfiParam;
+
+    fn field(name: &str, ty: &str, default: bool) -> FfiField {
+        FfiField {
+            name: name.into(),
+            serde_rename: None,
+            ty: ty.into(),
+            has_serde_default: default,
+            ffi_default_override: None,
+        }
+    }
+
+    fn context(metas: &[FfiMeta]) -> CodegenContext<'_> {
+        CodegenContext {
+            custom_types: HashMap::from([("Dot".to_string(), "String".to_string())]),
+            meta_map: metas.iter().map(|m| (m.name.as_str(), m)).collect(),
+        }
+    }
+
+    #[test]
+    fn map_types_follow_json_shapes() {
+        let metas = [FfiMeta {
+            name: "Block".into(),
+            kind: FfiKind::Struct { fields: vec![] },
+            serde_rename_all: None,
+            generics: vec![],
+        }];
+        let ctx = context(&metas);
+        let none = HashSet::new();
+        assert_eq!(map_type("Option<u32>", &none, &ctx), "Union[int, None]");
+        assert_eq!(map_type("Vec<Dot>", &none, &ctx), "list[str]");
+        assert_eq!(map_type("BTreeSet<Block>", &none, &ctx), "list[Block]");
+        assert_eq!(
+            map_type("HashMap<String, f64>", &none, &ctx),
+            "dict[str, float]"
+        );
+        assert_eq!(map_type("Unknown", &none, &ctx), "Any");
+        assert_eq!(map_type("T", &HashSet::from(["T"]), &ctx), "Any");
+    }
+
+    #[test]
+    fn struct_fields_use_serde_names_and_defaults() {
+        let metas = [FfiMeta {
+            name: "Stats".into(),
+            kind: FfiKind::Struct {
+                fields: vec![
+                    field("word_count", "u32", false),
+                    field("note", "String", true),
+                ],
+            },
+            serde_rename_all: Some("camelCase".into()),
+            generics: vec![],
+        }];
+        let out = generate_types(&metas, &context(&metas));
+        assert!(out.contains(
+            "Stats = TypedDict(\"Stats\", {\"wordCount\": \"int\", \"note\": \"NotRequired[str]\"})"
+        ));
+    }
+
+    #[test]
+    fn enums_follow_serde_representation() {
+        let metas = [
+            FfiMeta {
+                name: "Align".into(),
+                kind: FfiKind::Enum {
+                    variants: vec![
+                        FfiVariant::Unit {
+                            name: "Left".into(),
+                        },
+                        FfiVariant::Unit {
+                            name: "Right".into(),
+                        },
+                    ],
+                    serde_tag: None,
+                    default_variant: None,
+                },
+                serde_rename_all: Some("snake_case".into()),
+                generics: vec![],
+            },
+            FfiMeta {
+                name: "Node".into(),
+                kind: FfiKind::Enum {
+                    variants: vec![FfiVariant::Struct {
+                        name: "Text".into(),
+                        fields: vec![field("text", "String", false)],
+                        serde_rename_all: None,
+                    }],
+                    serde_tag: Some("type".into()),
+                    default_variant: None,
+                },
+                serde_rename_all: Some("snake_case".into()),
+                generics: vec![],
+            },
+        ];
+        let out = generate_types(&metas, &context(&metas));
+        assert!(out.contains("Align: TypeAlias = \"Literal['left', 'right']\""));
+        assert!(out.contains(
+            "NodeText = TypedDict(\"NodeText\", {\"type\": \"Literal['text']\", \"text\": \"str\"})"
+        ));
+        assert!(out.contains("Node: TypeAlias = \"Union[NodeText]\""));
+    }
+
+    #[test]
+    fn methods_marshal_complex_and_bytes() {
+        let metas = [FfiMeta {
+            name: "PlainDoc".into(),
+            kind: FfiKind::Struct { fields: vec![] },
+            serde_rename_all: None,
+            generics: vec![],
+        }];
+        let ctx = context(&metas);
+        let iface = FfiInterface {
+            name: "Corpus".into(),
+            methods: vec![
+                FfiMethod {
+                    name: "create".into(),
+                    is_async: false,
+                    is_constructor: true,
+                    params: vec![FfiParam {
+                        name: "icu_data".into(),
+                        ty: FfiParamType::Vec(FfiScalarParam::Primitive("u8".into())),
+                    }],
+                    return_type: FfiReturnType::Owned("Corpus".into()),
+                },
+                FfiMethod {
+                    name: "to_changesets".into(),
+                    is_async: false,
+                    is_constructor: false,
+                    params: vec![FfiParam {
+                        name: "plain".into(),
+                        ty: FfiParamType::Complex("PlainDoc".into()),
+                    }],
+                    return_type: FfiReturnType::Vec(FfiScalarReturn::Primitive("u8".into())),
+                },
+            ],
+        };
+        let out = generate_class(&iface, &ctx);
+        assert!(out.contains("    def __init__(self, icu_data: bytes) -> None:\n"));
+        assert!(out.contains("        self._inner = _native.Corpus(icu_data)\n"));
+        assert!(out.contains("    def to_changesets(self, plain: PlainDoc) -> bytes:\n"));
+        assert!(out.contains("        result = self._inner.to_changesets(json.dumps(plain))\n"));
+        assert!(out.contains("        return bytes(result)\n"));
+    }
+}
@@ -1,13 +1,37 @@
+use object::read::archive::ArchiveFile;
 use object::{Object, ObjectSection, ObjectSymbol, ObjectSymbolTable};
 use std::path::Path;
 
 use crate::meta::{FfiInterface, FfiMeta};
 
+/// The `FFI_META_*` statics of a built library, or of every object in an
+/// rlib archive.
 pub fn read_ffi_meta(path: &Path) -> Vec<FfiMeta> {
     let data = std::fs::read(path).expect("failed to read binary");
-    let file = object::File::parse(&*data).expect("failed to parse binary");
     let mut result = Vec::new();
 
+    match ArchiveFile::parse(&*data) {
+        Ok(archive) => {
+            for member in archive.members().flatten() {
+                // Archive members that are not objects, such as an rlib's
+                // metadata, carry no statics.
+                if let Ok(bytes) = member.data(&*data)
+                    && let Ok(file) = object::File::parse(bytes)
+                {
+                    collect_ffi_meta(&file, &mut result);
+                }
+            }
+        }
+        Err(_) => {
+            let file = object::File::parse(&*data).expect("failed to parse binary");
+            collect_ffi_meta(&file, &mut result);
+        }
+    }
+
+    result
+}
+
+fn collect_ffi_meta(file: &object::File, result: &mut Vec<FfiMeta>) {
     if let Some(symbol_table) = file.symbol_table() {
         for symbol in symbol_table.symbols() {
             let name = match symbol.name() {
@@ -51,8 +75,6 @@ pub fn read_ffi_meta(path: &Path) -> Vec<FfiMeta> {
             }
         }
     }
-
-    result
 }
 
 pub fn read_ffi_interfaces(path: &Path) -> Vec<FfiInterface> {
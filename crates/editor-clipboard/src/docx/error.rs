@@ -0,0 +1,11 @@
+#[derive(Debug, thiserror::Error)]
+pub enum DocxError {
+    #[error(transparent)]
+    Zip(#[from] editor_zip::ZipError),
+
+    #[error("missing package part `{0}`")]
+    MissingPart(String),
+
+    #[error("malformed xml in `{part}`: {message}")]
+    Xml { part: String, message: String },
+}
@@ -0,0 +1,6 @@
+mod error;
+pub mod parse;
+mod xml;
+
+pub use error::DocxError;
+pub use parse::{DocxAttachment, DocxImport, from_docx};
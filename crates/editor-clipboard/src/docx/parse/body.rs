@@ -0,0 +1,543 @@
+use editor_model::{
+    Fragment, Modifier, PlainBulletListNode, PlainColumnBreakNode, PlainHardBreakNode,
+    PlainImageNode, PlainListItemNode, PlainNode, PlainOrderedListNode, PlainPageBreakNode,
+    PlainParagraphNode, PlainTabNode, PlainTableCellNode, PlainTableNode, PlainTableRowNode,
+    PlainTextNode, TableBorderStyle,
+};
+use editor_resource::Resource;
+use roxmltree::Node;
+
+use crate::docx::parse::DocxAttachment;
+use crate::docx::parse::numbering::{ListKind, Numbering};
+use crate::docx::parse::package::{Package, Relationships};
+use crate::docx::parse::props::{DEFAULT_HALF_POINTS, ParaProps, RunProps};
+use crate::docx::parse::styles::Styles;
+use crate::docx::xml::{child_val, descendant, is_w, r_attr, w_attr, w_child, w_val};
+use crate::html::parse::normalize::color;
+
+/// Twips per CSS pixel at 96 dpi.
+const TWIPS_PER_PX: u32 = 15;
+
+/// Converts the body of the main document part. Images are emitted as
+/// placeholders in document order, with their bytes in `attachments`.
+pub(super) struct Walker<'a> {
+    pub(super) package: &'a Package<'a>,
+    pub(super) rels: &'a Relationships,
+    pub(super) styles: &'a Styles,
+    pub(super) numbering: &'a Numbering,
+    pub(super) resource: &'a Resource,
+    pub(super) attachments: Vec<DocxAttachment>,
+}
+
+/// Where a block container sits; page and column breaks are only valid in
+/// paragraphs directly under the root.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Scope {
+    Root,
+    Nested,
+}
+
+impl Walker<'_> {
+    pub(super) fn body(&mut self, body: Node) -> Vec<Fragment> {
+        self.blocks(body, Scope::Root)
+    }
+
+    fn blocks(&mut self, container: Node, scope: Scope) -> Vec<Fragment> {
+        let mut out = Vec::new();
+        let mut lists = ListStack::default();
+        self.collect_blocks(container, scope, &mut out, &mut lists);
+        lists.flush(&mut out);
+        out
+    }
+
+    fn collect_blocks(
+        &mut self,
+        container: Node,
+        scope: Scope,
+        out: &mut Vec<Fragment>,
+        lists: &mut ListStack,
+    ) {
+        for child in container.children().filter(Node::is_element) {
+            if is_w(child, "p") {
+                let paragraph = self.paragraph(child, scope);
+                match paragraph.list {
+                    Some((kind, level)) => lists.push(kind, level, paragraph.blocks, out),
+                    None => {
+                        lists.flush(out);
+                        if paragraph.page_break_before && scope == Scope::Root {
+                            break_after_last(out);
+                        }
+                        out.extend(paragraph.blocks);
+                    }
+                }
+            } else if is_w(child, "tbl") {
+                lists.flush(out);
+                out.extend(self.table(child));
+            } else if is_w(child, "sdt") {
+                if let Some(content) = w_child(child, "sdtContent") {
+                    self.collect_blocks(content, scope, out, lists);
+                }
+            } else if is_w(child, "customXml") || is_w(child, "ins") || is_w(child, "moveTo") {
+                self.collect_blocks(child, scope, out, lists);
+            }
+        }
+    }
+
+    fn paragraph(&mut self, p: Node, scope: Scope) -> ParagraphOut {
+        let ppr = w_child(p, "pPr");
+        let (mut para, run) = self
+            .styles
+            .paragraph(ppr.and_then(|ppr| child_val(ppr, "pStyle")));
+        if let Some(ppr) = ppr {
+            para.overlay(&ParaProps::parse(ppr));
+        }
+        let list = para.num_id.as_deref().and_then(|num_id| {
+            let level = para.level.unwrap_or(0);
+            Some((self.numbering.kind(num_id, level)?, level))
+        });
+        let block = para.modifiers(run.half_points.unwrap_or(DEFAULT_HALF_POINTS));
+
+        // The paragraph mark's own formatting is what an empty paragraph
+        // carries for the text typed into it.
+        let mut mark = run.clone();
+        if let Some(rpr) = ppr.and_then(|ppr| w_child(ppr, "rPr")) {
+            mark.overlay(&RunProps::parse(rpr));
+        }
+        let breaks = scope == Scope::Root && list.is_none();
+        let mut builder = ParagraphBuilder::new(block, breaks);
+        self.inlines(p, &run, &[], &mut builder);
+        ParagraphOut {
+            list,
+            page_break_before: para.page_break_before == Some(true),
+            blocks: builder.finish(mark.modifiers(self.resource)),
+        }
+    }
+
+    fn inlines(
+        &mut self,
+        container: Node,
+        run: &RunProps,
+        inherited: &[Modifier],
+        builder: &mut ParagraphBuilder,
+    ) {
+        for child in container.children().filter(Node::is_element) {
+            if is_w(child, "r") {
+                self.run(child, run, inherited, builder);
+            } else if is_w(child, "hyperlink") {
+                let href = r_attr(child, "id").and_then(|id| self.rels.external(id));
+                self.linked(child, href, run, inherited, builder);
+            } else if is_w(child, "fldSimple") {
+                let href = w_attr(child, "instr").and_then(hyperlink_instruction);
+                self.linked(child, href, run, inherited, builder);
+            } else if is_w(child, "sdt") {
+                if let Some(content) = w_child(child, "sdtContent") {
+                    self.inlines(content, run, inherited, builder);
+                }
+            } else if ["ins", "moveTo", "smartTag", "customXml", "dir", "bdo"]
+                .iter()
+                .any(|local| is_w(child, local))
+            {
+                self.inlines(child, run, inherited, builder);
+            }
+        }
+    }
+
+    fn linked(
+        &mut self,
+        container: Node,
+        href: Option<&str>,
+        run: &RunProps,
+        inherited: &[Modifier],
+        builder: &mut ParagraphBuilder,
+    ) {
+        match href {
+            Some(href) => {
+                let mut inherited = inherited.to_vec();
+                inherited.push(Modifier::Link {
+                    href: href.to_owned(),
+                });
+                self.inlines(container, run, &inherited, builder);
+            }
+            None => self.inlines(container, run, inherited, builder),
+        }
+    }
+
+    fn run(
+        &mut self,
+        r: Node,
+        paragraph_run: &RunProps,
+        inherited: &[Modifier],
+        builder: &mut ParagraphBuilder,
+    ) {
+        let rpr = w_child(r, "rPr");
+        let mut props = paragraph_run.clone();
+        if let Some(style) = rpr.and_then(|rpr| child_val(rpr, "rStyle")) {
+            props.overlay(&self.styles.character(style));
+        }
+        if let Some(rpr) = rpr {
+            props.overlay(&RunProps::parse(rpr));
+        }
+        if props.hidden == Some(true) {
+            return;
+        }
+        let mut modifiers = props.modifiers(self.resource);
+        modifiers.extend_from_slice(inherited);
+
+        for child in r.children().filter(Node::is_element) {
+            let local = child.tag_name().name();
+            if is_w(child, "t") {
+                builder.text(child.text().unwrap_or_default(), &modifiers);
+            } else if is_w(child, "tab") || is_w(child, "ptab") {
+                builder.inline(PlainNode::Tab(PlainTabNode::default()));
+            } else if is_w(child, "br") {
+                match w_attr(child, "type") {
+                    Some("page") => {
+                        builder.page_break(PlainNode::PageBreak(PlainPageBreakNode::default()))
+                    }
+                    Some("column") => {
+                        builder.page_break(PlainNode::ColumnBreak(PlainColumnBreakNode::default()))
+                    }
+                    _ => builder.inline(PlainNode::HardBreak(PlainHardBreakNode::default())),
+                }
+            } else if is_w(child, "cr") {
+                builder.inline(PlainNode::HardBreak(PlainHardBreakNode::default()));
+            } else if is_w(child, "noBreakHyphen") {
+                builder.text("\u{2011}", &modifiers);
+            } else if is_w(child, "ruby") {
+                self.ruby(child, paragraph_run, inherited, builder);
+            } else if is_w(child, "drawing")
+                || is_w(child, "pict")
+                || is_w(child, "object")
+                || local == "AlternateContent"
+            {
+                self.image(child, builder);
+            }
+        }
+    }
+
+    fn ruby(
+        &mut self,
+        ruby: Node,
+        paragraph_run: &RunProps,
+        inherited: &[Modifier],
+        builder: &mut ParagraphBuilder,
+    ) {
+        let annotation: String = w_child(ruby, "rt")
+            .map(|rt| {
+                rt.descendants()
+                    .filter(|node| is_w(*node, "t"))
+                    .filter_map(|t| t.text())
+                    .collect()
+            })
+            .unwrap_or_default();
+        let Some(base) = w_child(ruby, "rubyBase") else {
+            return;
+        };
+        let mut inherited = inherited.to_vec();
+        if !annotation.is_empty() {
+            inherited.push(Modifier::Ruby { text: annotation });
+        }
+        self.inlines(base, paragraph_run, &inherited, builder);
+    }
+
+    fn image(&mut self, node: Node, builder: &mut ParagraphBuilder) {
+        let id = descendant(node, "blip")
+            .and_then(|blip| r_attr(blip, "embed"))
+            .or_else(|| descendant(node, "imagedata").and_then(|data| r_attr(data, "id")));
+        let Some(part) = id.and_then(|id| self.rels.internal(id)) else {
+            return;
+        };
+        // A dangling or unreadable image is dropped rather than failing the
+        // whole import; the placeholder is only emitted with its bytes.
+        let Ok(Some(data)) = self.package.read(part) else {
+            return;
+        };
+        self.attachments.push(DocxAttachment {
+            name: part.rsplit('/').next().unwrap_or(part).to_owned(),
+            content_type: self.package.content_type(part),
+            data,
+        });
+        builder.block(Fragment::leaf(PlainNode::Image(PlainImageNode {
+            id: None,
+            ..Default::default()
+        })));
+    }
+
+    fn table(&mut self, tbl: Node) -> Option<Fragment> {
+        let border_style = w_child(tbl, "tblPr")
+            .and_then(|pr| w_child(pr, "tblBorders"))
+            .and_then(border_style)
+            .unwrap_or_default();
+        let mut rows = Vec::new();
+        for tr in tbl.children().filter(|node| is_w(*node, "tr")) {
+            let mut cells = Vec::new();
+            for tc in tr.children().filter(|node| is_w(*node, "tc")) {
+                let span = self.cell(tc, &mut cells);
+                // The model has no spans; padding keeps the grid rectangular.
+                for _ in 1..span {
+                    cells.push(empty_cell(None));
+                }
+            }
+            if !cells.is_empty() {
+                rows.push(
+                    Fragment::leaf(PlainNode::TableRow(PlainTableRowNode::default()))
+                        .with_children(cells),
+                );
+            }
+        }
+        (!rows.is_empty()).then(|| {
+            Fragment::leaf(PlainNode::Table(PlainTableNode {
+                border_style,
+                ..Default::default()
+            }))
+            .with_children(rows)
+        })
+    }
+
+    /// Appends the cell and returns how many grid columns it spans.
+    fn cell(&mut self, tc: Node, cells: &mut Vec<Fragment>) -> u32 {
+        let tcpr = w_child(tc, "tcPr");
+        let span = tcpr
+            .and_then(|pr| child_val(pr, "gridSpan"))
+            .and_then(|span| span.parse().ok())
+            .unwrap_or(1u32)
+            .clamp(1, 64);
+        let col_width = tcpr
+            .and_then(|pr| w_child(pr, "tcW"))
+            .filter(|w| matches!(w_attr(*w, "type"), None | Some("dxa")))
+            .and_then(|w| w_attr(w, "w")?.parse::<u32>().ok())
+            .filter(|&twips| twips > 0)
+            .map(|twips| (twips / span / TWIPS_PER_PX).max(1));
+        let background = tcpr
+            .and_then(|pr| w_child(pr, "shd"))
+            .and_then(|shd| w_attr(shd, "fill"))
+            .filter(|fill| fill.len() == 6 && fill.bytes().all(|b| b.is_ascii_hexdigit()))
+            .and_then(|fill| color::normalize_background_color(&format!("#{fill}"), self.resource))
+            .filter(|m| !matches!(m, Modifier::BackgroundColor { value } if value == "none"));
+        // Continuation cells of a vertical merge repeat nothing.
+        let continued = tcpr
+            .and_then(|pr| w_child(pr, "vMerge"))
+            .is_some_and(|merge| w_val(merge).is_none_or(|val| val == "continue"));
+
+        let mut cell = empty_cell(col_width);
+        if !continued {
+            let children = self.blocks(tc, Scope::Nested);
+            if !children.is_empty() {
+                cell.children = children;
+            }
+        }
+        cell.modifiers.extend(background);
+        cells.push(cell);
+        span
+    }
+}
+
+struct ParagraphOut {
+    list: Option<(ListKind, u8)>,
+    page_break_before: bool,
+    blocks: Vec<Fragment>,
+}
+
+/// Collects a paragraph's inline content. Images and page breaks split the
+/// paragraph, so one `w:p` may yield several blocks.
+struct ParagraphBuilder {
+    block: Vec<Modifier>,
+    breaks: bool,
+    inline: Vec<Fragment>,
+    out: Vec<Fragment>,
+}
+
+impl ParagraphBuilder {
+    fn new(block: Vec<Modifier>, breaks: bool) -> Self {
+        Self {
+            block,
+            breaks,
+            inline: Vec::new(),
+            out: Vec::new(),
+        }
+    }
+
+    fn text(&mut self, text: &str, modifiers: &[Modifier]) {
+        if !text.is_empty() {
+            self.inline.push(
+                Fragment::leaf(PlainNode::Text(PlainTextNode {
+                    text: text.to_owned(),
+                }))
+                .with_modifiers(modifiers.to_vec()),
+            );
+        }
+    }
+
+    fn inline(&mut self, node: PlainNode) {
+        self.inline.push(Fragment::leaf(node));
+    }
+
+    /// A page or column break ends the paragraph it appears in.
+    fn page_break(&mut self, node: PlainNode) {
+        if self.breaks {
+            self.inline.push(Fragment::leaf(node));
+            self.close();
+        }
+    }
+
+    fn block(&mut self, fragment: Fragment) {
+        if !self.inline.is_empty() {
+            self.close();
+        }
+        self.out.push(fragment);
+    }
+
+    fn close(&mut self) {
+        let children = std::mem::take(&mut self.inline);
+        self.out.push(
+            Fragment::leaf(PlainNode::Paragraph(PlainParagraphNode::default()))
+                .with_modifiers(self.block.clone())
+                .with_children(children),
+        );
+    }
+
+    fn finish(mut self, carry: Vec<Modifier>) -> Vec<Fragment> {
+        if !self.inline.is_empty() {
+            self.close();
+        } else if self.out.is_empty() {
+            self.close();
+            if let Some(paragraph) = self.out.last_mut() {
+                paragraph.carry = carry;
+            }
+        }
+        self.out
+    }
+}
+
+struct OpenList {
+    level: u8,
+    kind: ListKind,
+    list: Fragment,
+}
+
+/// Rebuilds nested lists from flat numbered paragraphs. A deeper level nests
+/// in the last item of the level above it.
+#[derive(Default)]
+struct ListStack {
+    open: Vec<OpenList>,
+}
+
+impl ListStack {
+    fn push(&mut self, kind: ListKind, level: u8, blocks: Vec<Fragment>, out: &mut Vec<Fragment>) {
+        while self
+            .open
+            .last()
+            .is_some_and(|top| top.level > level || (top.level == level && top.kind != kind))
+        {
+            self.close_top(out);
+        }
+        let item = list_item(blocks);
+        match self.open.last_mut() {
+            Some(top) if top.level == level => top.list.children.push(item),
+            _ => {
+                let node = match kind {
+                    ListKind::Bullet => PlainNode::BulletList(PlainBulletListNode::default()),
+                    ListKind::Ordered => PlainNode::OrderedList(PlainOrderedListNode::default()),
+                };
+                self.open.push(OpenList {
+                    level,
+                    kind,
+                    list: Fragment::leaf(node).with_children(vec![item]),
+                });
+            }
+        }
+    }
+
+    fn close_top(&mut self, out: &mut Vec<Fragment>) {
+        let Some(top) = self.open.pop() else {
+            return;
+        };
+        let parent_item = self
+            .open
+            .last_mut()
+            .and_then(|parent| parent.list.children.last_mut());
+        match parent_item {
+            Some(item) => item.children.push(top.list),
+            None => out.push(top.list),
+        }
+    }
+
+    fn flush(&mut self, out: &mut Vec<Fragment>) {
+        while !self.open.is_empty() {
+            self.close_top(out);
+        }
+    }
+}
+
+fn list_item(mut blocks: Vec<Fragment>) -> Fragment {
+    // A list item starts with a paragraph; an image-only paragraph gets an
+    // empty one in front.
+    if !blocks
+        .first()
+        .is_some_and(|block| matches!(block.node, PlainNode::Paragraph(_)))
+    {
+        blocks.insert(
+            0,
+            Fragment::leaf(PlainNode::Paragraph(PlainParagraphNode::default())),
+        );
+    }
+    Fragment::leaf(PlainNode::ListItem(PlainListItemNode::default())).with_children(blocks)
+}
+
+fn empty_cell(col_width: Option<u32>) -> Fragment {
+    Fragment::leaf(PlainNode::TableCell(PlainTableCellNode {
+        col_width,
+        background_color: None,
+    }))
+    .with_children(vec![Fragment::leaf(PlainNode::Paragraph(
+        PlainParagraphNode::default(),
+    ))])
+}
+
+/// `pageBreakBefore` becomes a break at the end of the previous paragraph,
+/// which is where the model keeps page breaks.
+fn break_after_last(out: &mut [Fragment]) {
+    let Some(last) = out.last_mut() else {
+        return;
+    };
+    if !matches!(last.node, PlainNode::Paragraph(_))
+        || last.children.last().is_some_and(|child| {
+            matches!(
+                child.node,
+                PlainNode::PageBreak(_) | PlainNode::ColumnBreak(_)
+            )
+        })
+    {
+        return;
+    }
+    last.children.push(Fragment::leaf(PlainNode::PageBreak(
+        PlainPageBreakNode::default(),
+    )));
+}
+
+fn border_style(borders: Node) -> Option<TableBorderStyle> {
+    let style = borders
+        .children()
+        .filter(Node::is_element)
+        .find_map(w_val)?;
+    Some(match style {
+        "nil" | "none" => TableBorderStyle::None,
+        "dashed" | "dashSmallGap" | "dotDash" | "dotDotDash" => TableBorderStyle::Dashed,
+        "dotted" => TableBorderStyle::Dotted,
+        _ => TableBorderStyle::Solid,
+    })
+}
+
+/// The target of a `HYPERLINK "url"` field instruction. Switches such as
+/// `\l` (a bookmark within the document) have no external target.
+fn hyperlink_instruction(instr: &str) -> Option<&str> {
+    let rest = instr.trim().strip_prefix("HYPERLINK")?.trim_start();
+    if rest.starts_with('\\') {
+        return None;
+    }
+    let target = match rest.strip_prefix('"') {
+        Some(quoted) => quoted.split('"').next()?,
+        None => rest.split_whitespace().next()?,
+    };
+    (!target.is_empty()).then_some(target)
+}
@@ -0,0 +1,424 @@
+mod body;
+mod numbering;
+mod package;
+mod props;
+mod styles;
+
+use editor_macros::ffi;
+use editor_resource::Resource;
+use serde::{Deserialize, Serialize};
+
+use crate::docx::DocxError;
+use crate::docx::xml::{self, w_child};
+use crate::html::parse::schema_normalize;
+use crate::slice::Slice;
+
+use body::Walker;
+use numbering::Numbering;
+use package::Package;
+use styles::Styles;
+
+/// A converted document. Each image became an `Image` placeholder without an
+/// id; `attachments` holds their bytes in the placeholders' document order,
+/// for the host to upload before inserting the slice.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DocxImport {
+    pub slice: Slice,
+    pub attachments: Vec<DocxAttachment>,
+}
+
+#[ffi]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DocxAttachment {
+    /// File name of the media part, e.g. `image1.png`.
+    pub name: String,
+    pub content_type: String,
+    #[serde(with = "serde_bytes")]
+    #[cfg_attr(feature = "wasm", tsify(type = "Uint8Array"))]
+    pub data: Vec<u8>,
+}
+
+/// Converts a WordprocessingML package. Formatting the model cannot express
+/// is dropped; fonts and colors go through the same normalization as pasted
+/// HTML.
+pub fn from_docx(bytes: &[u8], resource: &Resource) -> Result<DocxImport, DocxError> {
+    let package = Package::open(bytes)?;
+    let document_part = package.main_document()?;
+    let rels = package.relationships(&document_part)?;
+
+    let styles = match rels.target_of_type("styles") {
+        Some(part) => match package.read_text(part)? {
+            Some(text) => Styles::parse(&xml::parse(part, &text)?),
+            None => Styles::default(),
+        },
+        None => Styles::default(),
+    };
+    let numbering = match rels.target_of_type("numbering") {
+        Some(part) => match package.read_text(part)? {
+            Some(text) => Numbering::parse(&xml::parse(part, &text)?),
+            None => Numbering::default(),
+        },
+        None => Numbering::default(),
+    };
+
+    let text = package
+        .read_text(&document_part)?
+        .ok_or_else(|| DocxError::MissingPart(document_part.clone()))?;
+    let document = xml::parse(&document_part, &text)?;
+    let mut walker = Walker {
+        package: &package,
+        rels: &rels,
+        styles: &styles,
+        numbering: &numbering,
+        resource,
+        attachments: Vec::new(),
+    };
+    let blocks = match w_child(document.root_element(), "body") {
+        Some(body) => walker.body(body),
+        None => Vec::new(),
+    };
+    Ok(DocxImport {
+        slice: Slice::new(schema_normalize::normalize(blocks), 0, 0),
+        attachments: walker.attachments,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use editor_model::{Alignment, Fragment, Modifier, PlainNode, TableBorderStyle};
+    use editor_resource::{
+        FontFamily, FontFamilySource, FontWeight, ResourceSource, prepare_fonts,
+    };
+    use editor_zip::{ZipError, ZipWriter};
+
+    use super::*;
+
+    const W_NS: &str = "http://schemas.openxmlformats.org/wordprocessingml/2006/main";
+    const R_NS: &str = "http://schemas.openxmlformats.org/officeDocument/2006/relationships";
+
+    fn document(body: &str) -> String {
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:document xmlns:w="{W_NS}" xmlns:r="{R_NS}"
+  xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main"
+  xmlns:wp="http://schemas.openxmlformats.org/drawingml/2006/wordprocessingDrawing">
+<w:body>{body}</w:body></w:document>"#
+        )
+    }
+
+    fn archive(entries: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut zip = ZipWriter::new();
+        for &(name, data) in entries {
+            zip.deflated(name, data);
+        }
+        zip.finish()
+    }
+
+    fn package(body: &str, extra: &[(&str, &[u8])]) -> Vec<u8> {
+        let content_types = r#"<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types">
+<Default Extension="png" ContentType="image/png"/>
+<Default Extension="xml" ContentType="application/xml"/>
+</Types>"#;
+        let package_rels = r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+<Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="word/document.xml"/>
+</Relationships>"#;
+        let document_rels = r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+<Relationship Id="rIdStyles" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/styles" Target="styles.xml"/>
+<Relationship Id="rIdNumbering" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/numbering" Target="numbering.xml"/>
+<Relationship Id="rIdImage" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/image" Target="media/image1.png"/>
+<Relationship Id="rIdLink" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/hyperlink" Target="https://example.com/" TargetMode="External"/>
+</Relationships>"#;
+        let document = document(body);
+        let mut entries: Vec<(&str, &[u8])> = vec![
+            ("[Content_Types].xml", content_types.as_bytes()),
+            ("_rels/.rels", package_rels.as_bytes()),
+            ("word/document.xml", document.as_bytes()),
+            ("word/_rels/document.xml.rels", document_rels.as_bytes()),
+        ];
+        entries.extend_from_slice(extra);
+        archive(&entries)
+    }
+
+    fn import(body: &str, extra: &[(&str, &[u8])]) -> DocxImport {
+        from_docx(&package(body, extra), &Resource::new_test()).unwrap()
+    }
+
+    fn texts(fragment: &Fragment) -> Vec<(String, Vec<Modifier>)> {
+        fragment
+            .children
+            .iter()
+            .filter_map(|child| match &child.node {
+                PlainNode::Text(text) => Some((text.text.clone(), child.modifiers.clone())),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn runs_keep_their_formatting() {
+        let import = import(
+            r#"<w:p>
+  <w:r><w:rPr><w:b/><w:i/></w:rPr><w:t>bold</w:t></w:r>
+  <w:r><w:rPr><w:u w:val="single"/><w:strike/><w:sz w:val="32"/></w:rPr><w:t xml:space="preserve"> under</w:t></w:r>
+  <w:r><w:rPr><w:b w:val="0"/><w:vanish/></w:rPr><w:t>hidden</w:t></w:r>
+  <w:hyperlink r:id="rIdLink"><w:r><w:t>link</w:t></w:r></w:hyperlink>
+  <w:r><w:tab/><w:t>after</w:t><w:br/></w:r>
+</w:p>"#,
+            &[],
+        );
+        let [paragraph] = import.slice.content.as_slice() else {
+            panic!("expected one paragraph: {:?}", import.slice.content);
+        };
+        assert_eq!(
+            texts(paragraph),
+            vec![
+                ("bold".into(), vec![Modifier::Italic, Modifier::Bold]),
+                (
+                    " under".into(),
+                    vec![
+                        Modifier::Underline,
+                        Modifier::Strikethrough,
+                        Modifier::FontSize { value: 1600 },
+                    ],
+                ),
+                (
+                    "link".into(),
+                    vec![Modifier::Link {
+                        href: "https://example.com/".into(),
+                    }],
+                ),
+                ("after".into(), vec![]),
+            ]
+        );
+        assert!(matches!(paragraph.children[3].node, PlainNode::Tab(_)));
+        assert!(matches!(
+            paragraph.children.last().unwrap().node,
+            PlainNode::HardBreak(_)
+        ));
+    }
+
+    #[test]
+    fn runs_map_color_font_spacing_and_ruby() {
+        let mut source = ResourceSource::new_test();
+        source
+            .set_fonts(prepare_fonts(vec![FontFamily {
+                name: "Pretendard".into(),
+                source: FontFamilySource::User,
+                weights: vec![FontWeight {
+                    value: 400,
+                    hash: "h_pretendard".into(),
+                }],
+            }]))
+            .expect("font families must change resources");
+        let body = r#"<w:p>
+  <w:r><w:rPr><w:color w:val="c00000"/><w:shd w:val="clear" w:fill="FFFF00"/></w:rPr><w:t>tinted</w:t></w:r>
+  <w:r><w:rPr><w:rFonts w:ascii="Arial" w:hAnsi="Arial" w:eastAsia="Pretendard"/><w:sz w:val="20"/><w:spacing w:val="40"/></w:rPr><w:t>spaced</w:t></w:r>
+  <w:r><w:ruby><w:rubyPr/><w:rt><w:r><w:t>かん</w:t></w:r></w:rt><w:rubyBase><w:r><w:rPr><w:b/></w:rPr><w:t>漢</w:t></w:r></w:rubyBase></w:ruby></w:r>
+</w:p>"#;
+        let import = from_docx(
+            &package(body, &[]),
+            &Resource::from_snapshot(source.snapshot()),
+        )
+        .unwrap();
+        let [paragraph] = import.slice.content.as_slice() else {
+            panic!("expected one paragraph: {:?}", import.slice.content);
+        };
+        assert_eq!(
+            texts(paragraph),
+            vec![
+                (
+                    "tinted".into(),
+                    vec![
+                        Modifier::TextColor {
+                            value: "red".into(),
+                        },
+                        Modifier::BackgroundColor {
+                            value: "yellow".into(),
+                        },
+                    ],
+                ),
+                (
+                    "spaced".into(),
+                    vec![
+                        Modifier::FontSize { value: 1000 },
+                        Modifier::FontFamily {
+                            value: "Pretendard".into(),
+                        },
+                        // 2pt of tracking on a 10pt run.
+                        Modifier::LetterSpacing { value: 20 },
+                    ],
+                ),
+                (
+                    "漢".into(),
+                    vec![
+                        Modifier::Bold,
+                        Modifier::Ruby {
+                            text: "かん".into(),
+                        },
+                    ],
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn paragraph_styles_resolve_through_based_on() {
+        let styles = format!(
+            r#"<w:styles xmlns:w="{W_NS}">
+<w:docDefaults><w:rPrDefault><w:rPr><w:sz w:val="20"/></w:rPr></w:rPrDefault></w:docDefaults>
+<w:style w:type="paragraph" w:styleId="Base"><w:pPr><w:jc w:val="center"/></w:pPr></w:style>
+<w:style w:type="paragraph" w:styleId="Heading1"><w:basedOn w:val="Base"/>
+  <w:pPr><w:spacing w:line="360" w:lineRule="auto"/></w:pPr><w:rPr><w:b/><w:sz w:val="40"/></w:rPr></w:style>
+</w:styles>"#
+        );
+        let import = import(
+            r#"<w:p><w:pPr><w:pStyle w:val="Heading1"/><w:ind w:firstLineChars="100"/></w:pPr>
+  <w:r><w:t>Title</w:t></w:r></w:p>
+<w:p><w:r><w:t>Body</w:t></w:r></w:p>"#,
+            &[("word/styles.xml", styles.as_bytes())],
+        );
+        let [heading, body] = import.slice.content.as_slice() else {
+            panic!("expected two paragraphs: {:?}", import.slice.content);
+        };
+        assert_eq!(
+            heading.modifiers,
+            vec![
+                Modifier::Alignment {
+                    value: Alignment::Center,
+                },
+                Modifier::LineHeight { value: 150 },
+                Modifier::ParagraphIndent { value: 100 },
+            ]
+        );
+        assert_eq!(
+            texts(heading),
+            vec![(
+                "Title".into(),
+                vec![Modifier::FontSize { value: 2000 }, Modifier::Bold],
+            )]
+        );
+        assert!(body.modifiers.is_empty());
+        assert_eq!(
+            texts(body),
+            vec![("Body".into(), vec![Modifier::FontSize { value: 1000 }])]
+        );
+    }
+
+    #[test]
+    fn numbered_paragraphs_become_nested_lists() {
+        let numbering = format!(
+            r#"<w:numbering xmlns:w="{W_NS}">
+<w:abstractNum w:abstractNumId="0">
+  <w:lvl w:ilvl="0"><w:numFmt w:val="bullet"/></w:lvl>
+  <w:lvl w:ilvl="1"><w:numFmt w:val="decimal"/></w:lvl>
+</w:abstractNum>
+<w:num w:numId="1"><w:abstractNumId w:val="0"/></w:num>
+</w:numbering>"#
+        );
+        let item = |level: u8, text: &str| {
+            format!(
+                r#"<w:p><w:pPr><w:numPr><w:ilvl w:val="{level}"/><w:numId w:val="1"/></w:numPr></w:pPr><w:r><w:t>{text}</w:t></w:r></w:p>"#
+            )
+        };
+        let body = [
+            item(0, "a"),
+            item(1, "a.1"),
+            item(1, "a.2"),
+            item(0, "b"),
+            "<w:p><w:r><w:t>after</w:t></w:r></w:p>".to_owned(),
+        ]
+        .concat();
+        let import = import(&body, &[("word/numbering.xml", numbering.as_bytes())]);
+
+        let [list, after] = import.slice.content.as_slice() else {
+            panic!(
+                "expected a list and a paragraph: {:?}",
+                import.slice.content
+            );
+        };
+        assert!(matches!(list.node, PlainNode::BulletList(_)));
+        assert!(matches!(after.node, PlainNode::Paragraph(_)));
+        let [first, second] = list.children.as_slice() else {
+            panic!("expected two items: {:?}", list.children);
+        };
+        let [_, nested] = first.children.as_slice() else {
+            panic!("expected a nested list: {:?}", first.children);
+        };
+        assert!(matches!(nested.node, PlainNode::OrderedList(_)));
+        assert_eq!(nested.children.len(), 2);
+        assert_eq!(second.children.len(), 1);
+    }
+
+    #[test]
+    fn tables_and_images_become_blocks() {
+        let image = b"\x89PNG\r\n\x1a\nfake";
+        let import = import(
+            r#"<w:p><w:r><w:t>before</w:t></w:r>
+  <w:r><w:drawing><wp:inline><wp:docPr id="1" name="Picture 1" descr="A chart"/>
+    <a:graphic><a:graphicData><a:blip r:embed="rIdImage"/></a:graphicData></a:graphic>
+  </wp:inline></w:drawing></w:r>
+  <w:r><w:t>after</w:t><w:br w:type="page"/></w:r></w:p>
+<w:tbl><w:tblPr><w:tblBorders><w:top w:val="dashed"/></w:tblBorders></w:tblPr>
+  <w:tr>
+    <w:tc><w:tcPr><w:tcW w:w="1500" w:type="dxa"/><w:shd w:fill="FF0000"/></w:tcPr><w:p><w:r><w:t>cell</w:t></w:r></w:p></w:tc>
+    <w:tc><w:tcPr><w:gridSpan w:val="2"/></w:tcPr><w:p/></w:tc>
+  </w:tr>
+</w:tbl>"#,
+            &[("word/media/image1.png", image)],
+        );
+
+        let [before, picture, after, table] = import.slice.content.as_slice() else {
+            panic!("unexpected blocks: {:?}", import.slice.content);
+        };
+        assert_eq!(texts(before), vec![("before".into(), vec![])]);
+        let PlainNode::Image(picture) = &picture.node else {
+            panic!("expected an image: {picture:?}");
+        };
+        assert_eq!(picture.id, None);
+        assert!(matches!(
+            after.children.last().unwrap().node,
+            PlainNode::PageBreak(_)
+        ));
+        assert_eq!(
+            import.attachments,
+            vec![DocxAttachment {
+                name: "image1.png".into(),
+                content_type: "image/png".into(),
+                data: image.to_vec(),
+            }]
+        );
+
+        let PlainNode::Table(node) = &table.node else {
+            panic!("expected a table: {table:?}");
+        };
+        assert_eq!(node.border_style, TableBorderStyle::Dashed);
+        let [row] = table.children.as_slice() else {
+            panic!("expected one row: {:?}", table.children);
+        };
+        // The spanning cell is padded out to the grid.
+        assert_eq!(row.children.len(), 3);
+        let PlainNode::TableCell(cell) = &row.children[0].node else {
+            panic!("expected a cell");
+        };
+        assert_eq!(cell.col_width, Some(100));
+        assert!(
+            row.children[0]
+                .modifiers
+                .iter()
+                .any(|m| matches!(m, Modifier::BackgroundColor { .. }))
+        );
+    }
+
+    #[test]
+    fn rejects_packages_without_a_document() {
+        let bytes = archive(&[("[Content_Types].xml", b"<Types/>")]);
+        assert!(matches!(
+            from_docx(&bytes, &Resource::new_test()),
+            Err(DocxError::MissingPart(part)) if part == "word/document.xml"
+        ));
+        assert!(matches!(
+            from_docx(b"plain text", &Resource::new_test()),
+            Err(DocxError::Zip(ZipError::NotZip))
+        ));
+    }
+}
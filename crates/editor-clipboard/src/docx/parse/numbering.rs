@@ -0,0 +1,67 @@
+use std::collections::HashMap;
+
+use roxmltree::Document;
+
+use crate::docx::xml::{child_val, is_w, w_attr};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum ListKind {
+    Bullet,
+    Ordered,
+}
+
+/// `numbering.xml`, reduced to whether each list level is bulleted.
+#[derive(Default)]
+pub(super) struct Numbering {
+    /// `w:num` id to `w:abstractNum` id.
+    nums: HashMap<String, String>,
+    levels: HashMap<(String, u8), ListKind>,
+}
+
+impl Numbering {
+    pub(super) fn parse(doc: &Document) -> Self {
+        let mut numbering = Self::default();
+        for node in doc.root_element().children() {
+            if is_w(node, "abstractNum") {
+                let Some(abstract_id) = w_attr(node, "abstractNumId") else {
+                    continue;
+                };
+                for level in node.children().filter(|child| is_w(*child, "lvl")) {
+                    let Some(ilvl) = w_attr(level, "ilvl").and_then(|v| v.parse().ok()) else {
+                        continue;
+                    };
+                    let kind = match child_val(level, "numFmt") {
+                        Some("bullet" | "none") => ListKind::Bullet,
+                        _ => ListKind::Ordered,
+                    };
+                    numbering
+                        .levels
+                        .insert((abstract_id.to_owned(), ilvl), kind);
+                }
+            } else if is_w(node, "num")
+                && let (Some(num_id), Some(abstract_id)) =
+                    (w_attr(node, "numId"), child_val(node, "abstractNumId"))
+            {
+                numbering
+                    .nums
+                    .insert(num_id.to_owned(), abstract_id.to_owned());
+            }
+        }
+        numbering
+    }
+
+    /// The list a numbered paragraph belongs to. `numId` 0 removes numbering
+    /// a style would otherwise apply.
+    pub(super) fn kind(&self, num_id: &str, level: u8) -> Option<ListKind> {
+        if num_id == "0" {
+            return None;
+        }
+        let abstract_id = self.nums.get(num_id)?;
+        Some(
+            self.levels
+                .get(&(abstract_id.clone(), level))
+                .copied()
+                .unwrap_or(ListKind::Bullet),
+        )
+    }
+}
@@ -0,0 +1,202 @@
+use std::collections::HashMap;
+
+use editor_zip::ZipArchive;
+use roxmltree::Node;
+
+use crate::docx::DocxError;
+use crate::docx::xml;
+
+const DEFAULT_DOCUMENT: &str = "word/document.xml";
+
+/// An OPC package: the ZIP archive plus its content types. Part names are
+/// kept without the leading `/`.
+pub(super) struct Package<'a> {
+    archive: ZipArchive<'a>,
+    defaults: HashMap<String, String>,
+    overrides: HashMap<String, String>,
+}
+
+impl<'a> Package<'a> {
+    pub(super) fn open(bytes: &'a [u8]) -> Result<Self, DocxError> {
+        let mut package = Self {
+            archive: ZipArchive::new(bytes)?,
+            defaults: HashMap::new(),
+            overrides: HashMap::new(),
+        };
+        if let Some(text) = package.read_text("[Content_Types].xml")? {
+            let doc = xml::parse("[Content_Types].xml", &text)?;
+            for node in doc.root_element().children().filter(Node::is_element) {
+                let content_type = node.attribute("ContentType").unwrap_or_default();
+                match node.tag_name().name() {
+                    "Default" => {
+                        if let Some(extension) = node.attribute("Extension") {
+                            package
+                                .defaults
+                                .insert(extension.to_ascii_lowercase(), content_type.to_owned());
+                        }
+                    }
+                    "Override" => {
+                        if let Some(part) = node.attribute("PartName") {
+                            package.overrides.insert(
+                                part.trim_start_matches('/').to_ascii_lowercase(),
+                                content_type.to_owned(),
+                            );
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        Ok(package)
+    }
+
+    pub(super) fn read(&self, part: &str) -> Result<Option<Vec<u8>>, DocxError> {
+        Ok(self.archive.read(part)?)
+    }
+
+    pub(super) fn read_text(&self, part: &str) -> Result<Option<String>, DocxError> {
+        let Some(bytes) = self.read(part)? else {
+            return Ok(None);
+        };
+        let bytes = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(&bytes);
+        String::from_utf8(bytes.to_vec())
+            .map(Some)
+            .map_err(|err| DocxError::Xml {
+                part: part.to_owned(),
+                message: err.to_string(),
+            })
+    }
+
+    pub(super) fn content_type(&self, part: &str) -> String {
+        let part = part.to_ascii_lowercase();
+        if let Some(content_type) = self.overrides.get(&part) {
+            return content_type.clone();
+        }
+        part.rsplit_once('.')
+            .and_then(|(_, extension)| self.defaults.get(extension))
+            .cloned()
+            .unwrap_or_else(|| "application/octet-stream".to_owned())
+    }
+
+    /// The main document part named by the package relationships.
+    pub(super) fn main_document(&self) -> Result<String, DocxError> {
+        let rels = self.relationships("")?;
+        let part = rels
+            .target_of_type("officeDocument")
+            .unwrap_or(DEFAULT_DOCUMENT)
+            .to_owned();
+        Ok(part)
+    }
+
+    /// Relationships of `source`, or of the package itself when empty.
+    pub(super) fn relationships(&self, source: &str) -> Result<Relationships, DocxError> {
+        let (dir, name) = source.rsplit_once('/').unwrap_or(("", source));
+        let rels_part = if dir.is_empty() {
+            format!("_rels/{name}.rels")
+        } else {
+            format!("{dir}/_rels/{name}.rels")
+        };
+        let mut rels = Relationships::default();
+        let Some(text) = self.read_text(&rels_part)? else {
+            return Ok(rels);
+        };
+        let doc = xml::parse(&rels_part, &text)?;
+        for node in doc.root_element().children().filter(Node::is_element) {
+            let (Some(id), Some(kind), Some(target)) = (
+                node.attribute("Id"),
+                node.attribute("Type"),
+                node.attribute("Target"),
+            ) else {
+                continue;
+            };
+            let external = node.attribute("TargetMode") == Some("External");
+            let target = if external {
+                target.to_owned()
+            } else {
+                resolve(dir, target)
+            };
+            rels.entries.insert(
+                id.to_owned(),
+                Relationship {
+                    kind: kind.rsplit('/').next().unwrap_or_default().to_owned(),
+                    target,
+                    external,
+                },
+            );
+        }
+        Ok(rels)
+    }
+}
+
+#[derive(Default)]
+pub(super) struct Relationships {
+    entries: HashMap<String, Relationship>,
+}
+
+struct Relationship {
+    /// Last segment of the relationship type URI, e.g. `styles`.
+    kind: String,
+    target: String,
+    external: bool,
+}
+
+impl Relationships {
+    pub(super) fn target_of_type(&self, kind: &str) -> Option<&str> {
+        let mut matching: Vec<_> = self
+            .entries
+            .iter()
+            .filter(|(_, rel)| rel.kind == kind && !rel.external)
+            .collect();
+        // Ids are unordered in a map; pick deterministically among duplicates.
+        matching.sort_by_key(|(id, _)| *id);
+        matching.first().map(|(_, rel)| rel.target.as_str())
+    }
+
+    pub(super) fn external(&self, id: &str) -> Option<&str> {
+        self.entries
+            .get(id)
+            .filter(|rel| rel.external)
+            .map(|rel| rel.target.as_str())
+    }
+
+    pub(super) fn internal(&self, id: &str) -> Option<&str> {
+        self.entries
+            .get(id)
+            .filter(|rel| !rel.external)
+            .map(|rel| rel.target.as_str())
+    }
+}
+
+/// Resolves a relationship target against the source part's directory.
+fn resolve(dir: &str, target: &str) -> String {
+    let mut segments: Vec<&str> = match target.strip_prefix('/') {
+        Some(_) => Vec::new(),
+        None => dir.split('/').filter(|s| !s.is_empty()).collect(),
+    };
+    for segment in target.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                segments.pop();
+            }
+            segment => segments.push(segment),
+        }
+    }
+    segments.join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn targets_resolve_against_the_source_directory() {
+        assert_eq!(resolve("word", "media/image1.png"), "word/media/image1.png");
+        assert_eq!(
+            resolve("word", "../customXml/item.xml"),
+            "customXml/item.xml"
+        );
+        assert_eq!(resolve("word", "/word/styles.xml"), "word/styles.xml");
+        assert_eq!(resolve("", "word/document.xml"), "word/document.xml");
+    }
+}
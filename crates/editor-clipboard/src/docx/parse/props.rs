@@ -0,0 +1,290 @@
+//! Run (`w:rPr`) and paragraph (`w:pPr`) properties. Every field is optional
+//! so that style layers can be overlaid: a later layer only replaces what it
+//! sets.
+
+use editor_model::{Alignment, Modifier, TextDirection};
+use editor_resource::Resource;
+use roxmltree::Node;
+
+use crate::docx::xml::{child_on_off, child_val, w_attr, w_child, w_val};
+use crate::html::parse::normalize::normalize_modifier;
+use crate::html::parse::resolve_weight::resolve_font_weight;
+
+/// Word's size when nothing sets one, in half-points.
+pub(super) const DEFAULT_HALF_POINTS: u32 = 20;
+
+#[derive(Debug, Clone, Default)]
+pub(super) struct RunProps {
+    pub bold: Option<bool>,
+    pub italic: Option<bool>,
+    pub underline: Option<bool>,
+    pub strike: Option<bool>,
+    /// `RRGGBB` without the `#`.
+    pub color: Option<String>,
+    pub background: Option<String>,
+    pub half_points: Option<u32>,
+    pub font: Option<String>,
+    /// Twips.
+    pub spacing: Option<i32>,
+    pub hidden: Option<bool>,
+}
+
+impl RunProps {
+    pub(super) fn parse(rpr: Node) -> Self {
+        let strike = match (child_on_off(rpr, "strike"), child_on_off(rpr, "dstrike")) {
+            (None, None) => None,
+            (a, b) => Some(a.unwrap_or(false) || b.unwrap_or(false)),
+        };
+        let background = w_child(rpr, "highlight")
+            .and_then(w_val)
+            .and_then(highlight_color)
+            .map(str::to_owned)
+            .or_else(|| w_child(rpr, "shd").and_then(|shd| hex_color(w_attr(shd, "fill")?)));
+        Self {
+            bold: child_on_off(rpr, "b"),
+            italic: child_on_off(rpr, "i"),
+            underline: child_val(rpr, "u")
+                .map(|val| val != "none")
+                .or_else(|| w_child(rpr, "u").map(|_| true)),
+            strike,
+            color: w_child(rpr, "color").and_then(|color| hex_color(w_val(color)?)),
+            background,
+            half_points: child_val(rpr, "sz").and_then(|sz| sz.parse().ok()),
+            font: w_child(rpr, "rFonts").and_then(font_list),
+            spacing: child_val(rpr, "spacing").and_then(|spacing| spacing.parse().ok()),
+            hidden: child_on_off(rpr, "vanish"),
+        }
+    }
+
+    pub(super) fn overlay(&mut self, other: &Self) {
+        set(&mut self.bold, &other.bold);
+        set(&mut self.italic, &other.italic);
+        set(&mut self.underline, &other.underline);
+        set(&mut self.strike, &other.strike);
+        set(&mut self.color, &other.color);
+        set(&mut self.background, &other.background);
+        set(&mut self.half_points, &other.half_points);
+        set(&mut self.font, &other.font);
+        set(&mut self.spacing, &other.spacing);
+        set(&mut self.hidden, &other.hidden);
+    }
+
+    pub(super) fn modifiers(&self, resource: &Resource) -> Vec<Modifier> {
+        let mut raw = Vec::new();
+        if self.bold == Some(true) {
+            raw.push(Modifier::Bold);
+        }
+        if self.italic == Some(true) {
+            raw.push(Modifier::Italic);
+        }
+        if self.underline == Some(true) {
+            raw.push(Modifier::Underline);
+        }
+        if self.strike == Some(true) {
+            raw.push(Modifier::Strikethrough);
+        }
+        if let Some(color) = &self.color {
+            raw.push(Modifier::TextColor {
+                value: format!("#{color}"),
+            });
+        }
+        if let Some(color) = &self.background {
+            raw.push(Modifier::BackgroundColor {
+                value: format!("#{color}"),
+            });
+        }
+        if let Some(half_points) = self.half_points {
+            // Half-points to pt x 100.
+            raw.push(Modifier::FontSize {
+                value: half_points * 50,
+            });
+        }
+        if let Some(font) = &self.font {
+            raw.push(Modifier::FontFamily {
+                value: font.clone(),
+            });
+        }
+        if let Some(twips) = self.spacing.filter(|&twips| twips != 0) {
+            // A twip is 1/20pt and the font size is in half-points, so
+            // em x 100 = twips / 20 / (half_points / 2) x 100.
+            let half_points = self.half_points.unwrap_or(DEFAULT_HALF_POINTS).max(1) as i32;
+            raw.push(Modifier::LetterSpacing {
+                value: twips * 10 / half_points,
+            });
+        }
+        let normalized = raw
+            .into_iter()
+            .filter_map(|m| normalize_modifier(m, resource))
+            .collect();
+        resolve_font_weight(normalized, &[], resource)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(super) enum LineSpacing {
+    /// 240ths of a line.
+    Auto(u32),
+    /// Twips, for `exact` and `atLeast`.
+    Fixed(u32),
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(super) enum FirstLineIndent {
+    Twips(i32),
+    /// Hundredths of a character.
+    Chars(i32),
+}
+
+#[derive(Debug, Clone, Default)]
+pub(super) struct ParaProps {
+    pub alignment: Option<Alignment>,
+    pub line: Option<LineSpacing>,
+    pub first_line: Option<FirstLineIndent>,
+    pub rtl: Option<bool>,
+    pub num_id: Option<String>,
+    pub level: Option<u8>,
+    pub page_break_before: Option<bool>,
+}
+
+impl ParaProps {
+    pub(super) fn parse(ppr: Node) -> Self {
+        let alignment = child_val(ppr, "jc").and_then(|jc| match jc {
+            "left" | "start" => Some(Alignment::Left),
+            "center" => Some(Alignment::Center),
+            "right" | "end" => Some(Alignment::Right),
+            "both" | "distribute" => Some(Alignment::Justify),
+            _ => None,
+        });
+        let line = w_child(ppr, "spacing").and_then(|spacing| {
+            let line = w_attr(spacing, "line")?.parse().ok()?;
+            Some(match w_attr(spacing, "lineRule") {
+                Some("exact" | "atLeast") => LineSpacing::Fixed(line),
+                _ => LineSpacing::Auto(line),
+            })
+        });
+        let first_line = w_child(ppr, "ind").and_then(|ind| {
+            if let Some(chars) = w_attr(ind, "firstLineChars").and_then(|v| v.parse().ok()) {
+                return Some(FirstLineIndent::Chars(chars));
+            }
+            if let Some(twips) = w_attr(ind, "firstLine").and_then(|v| v.parse().ok()) {
+                return Some(FirstLineIndent::Twips(twips));
+            }
+            // A hanging indent has no first-line counterpart in the model.
+            w_attr(ind, "hanging").map(|_| FirstLineIndent::Twips(0))
+        });
+        let numbering = w_child(ppr, "numPr");
+        Self {
+            alignment,
+            line,
+            first_line,
+            rtl: child_on_off(ppr, "bidi"),
+            num_id: numbering
+                .and_then(|num| child_val(num, "numId"))
+                .map(str::to_owned),
+            level: numbering
+                .and_then(|num| child_val(num, "ilvl"))
+                .and_then(|level| level.parse().ok()),
+            page_break_before: child_on_off(ppr, "pageBreakBefore"),
+        }
+    }
+
+    pub(super) fn overlay(&mut self, other: &Self) {
+        set(&mut self.alignment, &other.alignment);
+        set(&mut self.line, &other.line);
+        set(&mut self.first_line, &other.first_line);
+        set(&mut self.rtl, &other.rtl);
+        set(&mut self.num_id, &other.num_id);
+        set(&mut self.level, &other.level);
+        set(&mut self.page_break_before, &other.page_break_before);
+    }
+
+    /// Block modifiers; `half_points` is the paragraph's font size, against
+    /// which fixed lengths are converted to em.
+    pub(super) fn modifiers(&self, half_points: u32) -> Vec<Modifier> {
+        let half_points = half_points.max(1);
+        let mut out = Vec::new();
+        if let Some(value) = self.alignment {
+            out.push(Modifier::Alignment { value });
+        }
+        if let Some(line) = self.line {
+            let value = match line {
+                LineSpacing::Auto(line) => line * 100 / 240,
+                // Twips over the font size in twips (half_points x 10).
+                LineSpacing::Fixed(twips) => twips * 10 / half_points,
+            };
+            out.push(Modifier::LineHeight {
+                value: value.clamp(50, 400),
+            });
+        }
+        let indent = match self.first_line {
+            Some(FirstLineIndent::Chars(chars)) => chars,
+            Some(FirstLineIndent::Twips(twips)) => twips * 10 / half_points as i32,
+            None => 0,
+        };
+        if indent > 0 {
+            out.push(Modifier::ParagraphIndent {
+                value: indent.clamp(0, 400) as u32,
+            });
+        }
+        if self.rtl == Some(true) {
+            out.push(Modifier::Direction {
+                value: TextDirection::Rtl,
+            });
+        }
+        out
+    }
+}
+
+fn set<T: Clone>(slot: &mut Option<T>, value: &Option<T>) {
+    if value.is_some() {
+        slot.clone_from(value);
+    }
+}
+
+fn hex_color(value: &str) -> Option<String> {
+    (value.len() == 6 && value.bytes().all(|b| b.is_ascii_hexdigit()))
+        .then(|| value.to_ascii_uppercase())
+}
+
+/// `ST_HighlightColor` names as hex.
+fn highlight_color(name: &str) -> Option<&'static str> {
+    Some(match name {
+        "black" => "000000",
+        "blue" => "0000FF",
+        "cyan" => "00FFFF",
+        "green" => "00FF00",
+        "magenta" => "FF00FF",
+        "red" => "FF0000",
+        "yellow" => "FFFF00",
+        "white" => "FFFFFF",
+        "darkBlue" => "000080",
+        "darkCyan" => "008080",
+        "darkGreen" => "008000",
+        "darkMagenta" => "800080",
+        "darkRed" => "800000",
+        "darkYellow" => "808000",
+        "darkGray" => "808080",
+        "lightGray" => "C0C0C0",
+        _ => return None,
+    })
+}
+
+/// `w:rFonts` as a CSS-style family list, East Asian face first since that is
+/// the one the editor's fonts are chosen for.
+fn font_list(fonts: Node) -> Option<String> {
+    let mut families: Vec<&str> = Vec::new();
+    for attr in ["eastAsia", "ascii", "hAnsi"] {
+        if let Some(family) = w_attr(fonts, attr).filter(|f| !f.is_empty())
+            && !families.contains(&family)
+        {
+            families.push(family);
+        }
+    }
+    (!families.is_empty()).then(|| {
+        families
+            .iter()
+            .map(|family| format!("\"{family}\""))
+            .collect::<Vec<_>>()
+            .join(", ")
+    })
+}
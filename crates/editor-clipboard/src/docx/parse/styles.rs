@@ -0,0 +1,101 @@
+use std::collections::HashMap;
+
+use roxmltree::Document;
+
+use crate::docx::parse::props::{ParaProps, RunProps};
+use crate::docx::xml::{child_val, is_w, w_attr, w_child};
+
+/// `basedOn` chains longer than this are treated as cyclic.
+const MAX_STYLE_DEPTH: usize = 16;
+
+/// `styles.xml`: document defaults and named styles, flattened on lookup.
+#[derive(Default)]
+pub(super) struct Styles {
+    run_defaults: RunProps,
+    para_defaults: ParaProps,
+    default_paragraph: Option<String>,
+    styles: HashMap<String, Style>,
+}
+
+struct Style {
+    based_on: Option<String>,
+    run: RunProps,
+    para: ParaProps,
+}
+
+impl Styles {
+    pub(super) fn parse(doc: &Document) -> Self {
+        let root = doc.root_element();
+        let mut styles = Self::default();
+        if let Some(defaults) = w_child(root, "docDefaults") {
+            if let Some(rpr) = w_child(defaults, "rPrDefault").and_then(|d| w_child(d, "rPr")) {
+                styles.run_defaults = RunProps::parse(rpr);
+            }
+            if let Some(ppr) = w_child(defaults, "pPrDefault").and_then(|d| w_child(d, "pPr")) {
+                styles.para_defaults = ParaProps::parse(ppr);
+            }
+        }
+        for node in root.children().filter(|node| is_w(*node, "style")) {
+            let Some(id) = w_attr(node, "styleId") else {
+                continue;
+            };
+            if w_attr(node, "type") == Some("paragraph")
+                && w_attr(node, "default").is_some_and(|v| matches!(v, "1" | "true" | "on"))
+            {
+                styles.default_paragraph = Some(id.to_owned());
+            }
+            styles.styles.insert(
+                id.to_owned(),
+                Style {
+                    based_on: child_val(node, "basedOn").map(str::to_owned),
+                    run: w_child(node, "rPr")
+                        .map(RunProps::parse)
+                        .unwrap_or_default(),
+                    para: w_child(node, "pPr")
+                        .map(ParaProps::parse)
+                        .unwrap_or_default(),
+                },
+            );
+        }
+        styles
+    }
+
+    /// Paragraph and paragraph-mark run properties of a paragraph style, on
+    /// top of the document defaults.
+    pub(super) fn paragraph(&self, style_id: Option<&str>) -> (ParaProps, RunProps) {
+        let mut para = self.para_defaults.clone();
+        let mut run = self.run_defaults.clone();
+        let style_id = style_id.or(self.default_paragraph.as_deref());
+        for style in self.chain(style_id) {
+            para.overlay(&style.para);
+            run.overlay(&style.run);
+        }
+        (para, run)
+    }
+
+    /// Run properties a character style adds over its paragraph.
+    pub(super) fn character(&self, style_id: &str) -> RunProps {
+        let mut run = RunProps::default();
+        for style in self.chain(Some(style_id)) {
+            run.overlay(&style.run);
+        }
+        run
+    }
+
+    /// The style and its ancestors, base first.
+    fn chain(&self, style_id: Option<&str>) -> Vec<&Style> {
+        let mut chain = Vec::new();
+        let mut next = style_id;
+        while let Some(id) = next
+            && chain.len() < MAX_STYLE_DEPTH
+        {
+            let Some(style) = self.styles.get(id) else {
+                break;
+            };
+            chain.push(style);
+            next = style.based_on.as_deref();
+        }
+        chain.reverse();
+        chain
+    }
+}
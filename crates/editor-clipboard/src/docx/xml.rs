@@ -0,0 +1,72 @@
+//! Namespace-aware lookups for WordprocessingML. Both the transitional and
+//! the strict namespaces are accepted.
+
+use roxmltree::Node;
+
+const W: [&str; 2] = [
+    "http://schemas.openxmlformats.org/wordprocessingml/2006/main",
+    "http://purl.oclc.org/ooxml/wordprocessingml/main",
+];
+const R: [&str; 2] = [
+    "http://schemas.openxmlformats.org/officeDocument/2006/relationships",
+    "http://purl.oclc.org/ooxml/officeDocument/relationships",
+];
+
+pub(crate) fn is_w(node: Node, local: &str) -> bool {
+    node.is_element()
+        && node.tag_name().name() == local
+        && node
+            .tag_name()
+            .namespace()
+            .is_some_and(|ns| W.contains(&ns))
+}
+
+pub(crate) fn w_child<'a, 'input>(node: Node<'a, 'input>, local: &str) -> Option<Node<'a, 'input>> {
+    node.children().find(|child| is_w(*child, local))
+}
+
+pub(crate) fn w_attr<'a>(node: Node<'a, '_>, local: &str) -> Option<&'a str> {
+    W.iter().find_map(|ns| node.attribute((*ns, local)))
+}
+
+pub(crate) fn r_attr<'a>(node: Node<'a, '_>, local: &str) -> Option<&'a str> {
+    R.iter().find_map(|ns| node.attribute((*ns, local)))
+}
+
+pub(crate) fn w_val<'a>(node: Node<'a, '_>) -> Option<&'a str> {
+    w_attr(node, "val")
+}
+
+/// `w:val` of the `local` child, if the child is present.
+pub(crate) fn child_val<'a>(node: Node<'a, '_>, local: &str) -> Option<&'a str> {
+    w_child(node, local).and_then(w_val)
+}
+
+/// An `ST_OnOff` toggle: a bare element means on.
+pub(crate) fn on_off(node: Node) -> bool {
+    !matches!(w_val(node), Some("0" | "false" | "off"))
+}
+
+pub(crate) fn child_on_off(node: Node, local: &str) -> Option<bool> {
+    w_child(node, local).map(on_off)
+}
+
+/// The first descendant with this local name, whatever its namespace, for
+/// DrawingML and VML parts nested in a run.
+pub(crate) fn descendant<'a, 'input>(
+    node: Node<'a, 'input>,
+    local: &str,
+) -> Option<Node<'a, 'input>> {
+    node.descendants()
+        .find(|d| d.is_element() && d.tag_name().name() == local)
+}
+
+pub(crate) fn parse<'input>(
+    part: &str,
+    text: &'input str,
+) -> Result<roxmltree::Document<'input>, crate::docx::DocxError> {
+    roxmltree::Document::parse(text).map_err(|err| crate::docx::DocxError::Xml {
+        part: part.to_owned(),
+        message: err.to_string(),
+    })
+}
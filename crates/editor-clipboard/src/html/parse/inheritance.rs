@@ -14,6 +14,7 @@ pub fn is_inheritable(ty: ModifierType) -> bool {
         | ModifierType::LetterSpacing
         | ModifierType::Link => true,
         ModifierType::Alignment
+        | ModifierType::Direction
         | ModifierType::LineHeight
         | ModifierType::BlockGap
         | ModifierType::ParagraphIndent
@@ -25,6 +26,7 @@ pub fn is_block_level(ty: ModifierType) -> bool {
     matches!(
         ty,
         ModifierType::Alignment
+            | ModifierType::Direction
             | ModifierType::LineHeight
             | ModifierType::BlockGap
             | ModifierType::ParagraphIndent
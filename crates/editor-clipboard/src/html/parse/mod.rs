@@ -846,6 +846,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn dir_attribute_lands_on_block() {
+        let s = Slice::from_html(r#"<p dir="rtl">שלום</p>"#, &Resource::new_test());
+        let p = s
+            .content
+            .iter()
+            .find(|f| matches!(f.node, PlainNode::Paragraph(_)))
+            .unwrap();
+        assert!(p.modifiers.contains(&Modifier::Direction {
+            value: editor_model::TextDirection::Rtl
+        }));
+        let t = find_text(&s).unwrap();
+        assert!(
+            !t.modifiers
+                .iter()
+                .any(|m| matches!(m, Modifier::Direction { .. }))
+        );
+    }
+
     #[test]
     fn important_ignored_inline() {
         let s = Slice::from_html(
@@ -312,10 +312,36 @@ fn build_modifier_rules() -> Vec<ModifierParseRule> {
             vec![Modifier::Alignment { value: align }]
         },
     });
+    rules.push(ModifierParseRule {
+        matcher: ModifierMatcher::StyleProperty("direction"),
+        priority: 50,
+        parse: |_, ctx| ctx.value.and_then(parse_direction).into_iter().collect(),
+    });
+    rules.push(ModifierParseRule {
+        matcher: ModifierMatcher::DataAttr("dir"),
+        priority: 50,
+        parse: |elem, _| {
+            elem.value()
+                .attr("dir")
+                .and_then(parse_direction)
+                .into_iter()
+                .collect()
+        },
+    });
 
     rules
 }
 
+fn parse_direction(value: &str) -> Option<Modifier> {
+    let value = match value.trim().to_lowercase().as_str() {
+        "auto" => editor_model::TextDirection::Auto,
+        "ltr" => editor_model::TextDirection::Ltr,
+        "rtl" => editor_model::TextDirection::Rtl,
+        _ => return None,
+    };
+    Some(Modifier::Direction { value })
+}
+
 pub fn try_parse_node(elem: &ElementRef, rules: &[NodeParseRule]) -> Option<PlainNode> {
     let tag = elem.value().name();
     for rule in rules {
@@ -113,6 +113,7 @@ fn serialize_forest(fragments: &[Fragment], resource: &Resource, out: &mut Strin
             PlainNode::PageBreak(_) => {
                 out.push_str(r#"<div style="page-break-after:always"></div>"#)
             }
+            PlainNode::ColumnBreak(_) => out.push_str(r#"<div style="break-after:column"></div>"#),
             PlainNode::HorizontalRule(_) => out.push_str("<hr>"),
             PlainNode::Root(_) => push_children(&mut tasks, &fragment.children),
             PlainNode::Unknown => {}
@@ -144,7 +145,9 @@ fn variant_str<T: serde::Serialize>(v: &T) -> String {
         .unwrap_or_default()
 }
 
-fn serialize_text(text: &str, modifiers: &[Modifier], resource: &Resource, out: &mut String) {
+/// Writes one text run as inline markup: structural modifiers become elements
+/// and the rest a styled `<span>`.
+pub fn serialize_text(text: &str, modifiers: &[Modifier], resource: &Resource, out: &mut String) {
     let escaped = html_escape(text);
 
     let (structural, style_pairs) = split_modifiers(modifiers, resource);
@@ -196,6 +199,11 @@ fn css_color(value: &str, token_prefix: &str, resource: &Resource) -> String {
     }
 }
 
+/// The CSS declarations [`serialize_text`] puts on a run's `<span>`.
+pub fn style_declarations(modifiers: &[Modifier], resource: &Resource) -> Vec<String> {
+    split_modifiers(modifiers, resource).1
+}
+
 fn split_modifiers<'m>(
     mods: &'m [Modifier],
     resource: &Resource,
@@ -245,7 +253,7 @@ fn open_close_for(m: &Modifier) -> (String, String) {
     }
 }
 
-fn html_escape(s: &str) -> String {
+pub fn html_escape(s: &str) -> String {
     s.replace('&', "&amp;")
         .replace('<', "&lt;")
         .replace('>', "&gt;")
@@ -0,0 +1,63 @@
+use std::env;
+use std::fs;
+use std::process;
+
+use editor_codec::{EnvelopeDump, dump_stream, load_stream, validate_stream};
+
+const USAGE: &str = "Usage:
+  editor-codec-dump check <file>...
+  editor-codec-dump dump <file>
+  editor-codec-dump load <json> <out>";
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    match args.as_slice() {
+        [cmd, files @ ..] if cmd == "check" && !files.is_empty() => check(files),
+        [cmd, file] if cmd == "dump" => dump(file),
+        [cmd, json, out] if cmd == "load" => load(json, out),
+        _ => {
+            eprintln!("{USAGE}");
+            process::exit(1);
+        }
+    }
+}
+
+fn check(files: &[String]) {
+    let mut failed = false;
+    for path in files {
+        match validate_stream(&read_file(path)) {
+            Ok(count) => println!("{path}: ok ({count} envelopes)"),
+            Err(fault) => {
+                println!("{path}: {fault}");
+                failed = true;
+            }
+        }
+    }
+    if failed {
+        process::exit(1);
+    }
+}
+
+fn dump(path: &str) {
+    let dumps = dump_stream(&read_file(path)).unwrap_or_else(|f| fail(&format!("{path}: {f}")));
+    let json = serde_json::to_string_pretty(&dumps).expect("dump serializes");
+    println!("{json}");
+}
+
+fn load(json_path: &str, out_path: &str) {
+    let json = fs::read_to_string(json_path)
+        .unwrap_or_else(|e| fail(&format!("cannot read {json_path}: {e}")));
+    let dumps: Vec<EnvelopeDump> =
+        serde_json::from_str(&json).unwrap_or_else(|e| fail(&format!("{json_path}: {e}")));
+    let bytes = load_stream(&dumps).unwrap_or_else(|e| fail(&format!("{json_path}: {e}")));
+    fs::write(out_path, bytes).unwrap_or_else(|e| fail(&format!("cannot write {out_path}: {e}")));
+}
+
+fn read_file(path: &str) -> Vec<u8> {
+    fs::read(path).unwrap_or_else(|e| fail(&format!("cannot read {path}: {e}")))
+}
+
+fn fail(msg: &str) -> ! {
+    eprintln!("ERROR: {msg}");
+    process::exit(1)
+}
@@ -1,11 +1,11 @@
-use editor_crdt::Dot;
+use editor_crdt::{ContentHash, Dot};
 
 use crate::ctx::{CollectCtx, DecCtx, EncCtx, read_dot, read_preamble, write_dot, write_preamble};
 use crate::durable::Durable;
-use crate::envelope::{Envelope, PayloadKind, unwrap_one, wrap};
+use crate::envelope::{Envelope, OPTIONAL_CHANGESET_HASHES, PayloadKind, unwrap_one, wrap};
 use crate::error::{CodecResult, Corruption, EncodeInvariant};
 use crate::framing::{FrameReader, expect_consumed, write_frame};
-use crate::primitives::{read_u8, write_u8};
+use crate::primitives::{read_u8, take, write_u8};
 use crate::types::op::DurableOp;
 use crate::varint::{read_varint, write_varint};
 
@@ -28,6 +28,14 @@ pub struct BundleChangeset {
     pub records: Vec<BundleRecord>,
 }
 
+/// 디코드된 bundle body. `hashes`는 `OPTIONAL_CHANGESET_HASHES` envelope에서만
+/// `Some`이며 `changesets`와 같은 순서·길이다.
+pub(crate) struct BundleBody {
+    pub(crate) ctx: DecCtx,
+    pub(crate) changesets: Vec<BundleChangeset>,
+    pub(crate) hashes: Option<Vec<ContentHash>>,
+}
+
 const MARKER_GENESIS: u8 = 0;
 const MARKER_IMPLICIT: u8 = 1;
 const MARKER_EXPLICIT: u8 = 2;
@@ -103,7 +111,11 @@ fn write_parents(
     Ok(())
 }
 
-fn encode_bundle_body(css: &[BundleChangeset], ctx: &EncCtx, out: &mut Vec<u8>) -> CodecResult<()> {
+pub(crate) fn encode_bundle_body(
+    css: &[BundleChangeset],
+    ctx: &EncCtx,
+    out: &mut Vec<u8>,
+) -> CodecResult<()> {
     write_varint(css.len() as u64, out);
     let mut prev_cs_last: Option<Dot> = None;
     for cs in css {
@@ -140,6 +152,22 @@ fn encode_bundle_body(css: &[BundleChangeset], ctx: &EncCtx, out: &mut Vec<u8>)
 }
 
 pub fn encode_bundle(css: &[BundleChangeset]) -> CodecResult<Vec<u8>> {
+    encode_bundle_with_hashes(css, None)
+}
+
+/// `encode_bundle` + changeset별 content hash 섹션(`OPTIONAL_CHANGESET_HASHES`).
+/// `hashes[i]`는 `css[i]`의 hash — 코덱은 값을 계산·검증하지 않고 운반만 한다.
+pub fn encode_bundle_hashed(
+    css: &[BundleChangeset],
+    hashes: &[ContentHash],
+) -> CodecResult<Vec<u8>> {
+    encode_bundle_with_hashes(css, Some(hashes))
+}
+
+fn encode_bundle_with_hashes(
+    css: &[BundleChangeset],
+    hashes: Option<&[ContentHash]>,
+) -> CodecResult<Vec<u8>> {
     let mut cc = CollectCtx::new();
     collect_bundle(css, &mut cc)?;
     let (actors, baselines) = cc.finalize();
@@ -147,7 +175,56 @@ pub fn encode_bundle(css: &[BundleChangeset]) -> CodecResult<Vec<u8>> {
     let mut body = Vec::new();
     write_preamble(&actors, &baselines, &mut body)?;
     encode_bundle_body(css, &ctx, &mut body)?;
-    wrap(&Envelope::new(PayloadKind::ChangesetBundle, body))
+    let mut envelope = Envelope::new(PayloadKind::ChangesetBundle, body);
+    if let Some(hashes) = hashes {
+        write_hash_section(css.len(), hashes, &mut envelope.body)?;
+        envelope.optional_features |= OPTIONAL_CHANGESET_HASHES;
+    }
+    wrap(&envelope)
+}
+
+pub(crate) fn write_hash_section(
+    cs_count: usize,
+    hashes: &[ContentHash],
+    out: &mut Vec<u8>,
+) -> CodecResult<()> {
+    if hashes.len() != cs_count {
+        return Err(EncodeInvariant::ChangesetHashCount {
+            changesets: cs_count,
+            hashes: hashes.len(),
+        }
+        .into());
+    }
+    write_varint(hashes.len() as u64, out);
+    for hash in hashes {
+        out.extend_from_slice(&hash.0);
+    }
+    Ok(())
+}
+
+/// bit가 없으면 섹션이 없는 것 — 읽지 않고 `None`.
+pub(crate) fn read_hash_section(
+    input: &mut &[u8],
+    optional_features: u64,
+    cs_count: usize,
+) -> CodecResult<Option<Vec<ContentHash>>> {
+    if optional_features & OPTIONAL_CHANGESET_HASHES == 0 {
+        return Ok(None);
+    }
+    let count = read_varint(input)?;
+    if count != cs_count as u64 {
+        return Err(Corruption::ChangesetHashCount {
+            expected: cs_count as u64,
+            actual: count,
+        }
+        .into());
+    }
+    let mut hashes = Vec::with_capacity(cs_count);
+    for _ in 0..count {
+        let bytes = take(input, 32)?;
+        hashes.push(ContentHash(bytes.try_into().expect("32 bytes")));
+    }
+    Ok(Some(hashes))
 }
 
 fn read_parents(input: &mut &[u8], implicit: Option<Dot>, ctx: &DecCtx) -> CodecResult<Vec<Dot>> {
@@ -222,7 +299,10 @@ fn decode_record(
     })
 }
 
-fn decode_bundle_changesets(input: &mut &[u8], ctx: &DecCtx) -> CodecResult<Vec<BundleChangeset>> {
+pub(crate) fn decode_bundle_changesets(
+    input: &mut &[u8],
+    ctx: &DecCtx,
+) -> CodecResult<Vec<BundleChangeset>> {
     let cs_count = read_varint(input)?;
     let mut css = Vec::new();
     let mut prev_cs_last: Option<Dot> = None;
@@ -245,24 +325,30 @@ fn decode_bundle_changesets(input: &mut &[u8], ctx: &DecCtx) -> CodecResult<Vec<
     Ok(css)
 }
 
-fn decode_bundle_body_with_ctx(input: &mut &[u8]) -> CodecResult<(DecCtx, Vec<BundleChangeset>)> {
+fn decode_bundle_body_with_ctx(
+    input: &mut &[u8],
+    optional_features: u64,
+) -> CodecResult<BundleBody> {
     let ctx = read_preamble(input)?;
-    let css = decode_bundle_changesets(input, &ctx)?;
+    let changesets = decode_bundle_changesets(input, &ctx)?;
+    let hashes = read_hash_section(input, optional_features, changesets.len())?;
     expect_consumed(input)?;
-    Ok((ctx, css))
+    Ok(BundleBody {
+        ctx,
+        changesets,
+        hashes,
+    })
 }
 
 pub fn decode_bundle(bytes: &[u8]) -> CodecResult<Vec<BundleChangeset>> {
-    decode_bundle_with_ctx(bytes).map(|(_, css)| css)
+    decode_bundle_with_ctx(bytes).map(|b| b.changesets)
 }
 
 /// Body-level decode of an **already-parsed** envelope — for callers that got
 /// `envelope` from their own `unwrap`/`unwrap_one` call and would otherwise
 /// re-parse the same header a second time via `decode_bundle_with_ctx(bytes)`
 /// (e.g. a stream walker that needs the envelope boundary before it can decode).
-pub(crate) fn decode_bundle_from_envelope(
-    envelope: &Envelope,
-) -> CodecResult<(DecCtx, Vec<BundleChangeset>)> {
+pub(crate) fn decode_bundle_from_envelope(envelope: &Envelope) -> CodecResult<BundleBody> {
     if envelope.payload_kind != PayloadKind::ChangesetBundle {
         return Err(Corruption::UnexpectedPayloadKind {
             kind: envelope.payload_kind as u8,
@@ -270,10 +356,10 @@ pub(crate) fn decode_bundle_from_envelope(
         .into());
     }
     let mut body = &envelope.body[..];
-    decode_bundle_body_with_ctx(&mut body)
+    decode_bundle_body_with_ctx(&mut body, envelope.optional_features)
 }
 
-pub(crate) fn decode_bundle_with_ctx(bytes: &[u8]) -> CodecResult<(DecCtx, Vec<BundleChangeset>)> {
+pub(crate) fn decode_bundle_with_ctx(bytes: &[u8]) -> CodecResult<BundleBody> {
     let envelope = crate::envelope::unwrap(bytes)?;
     decode_bundle_from_envelope(&envelope)
 }
@@ -290,7 +376,7 @@ pub fn decode_bundle_stream(bytes: &[u8]) -> CodecResult<Vec<BundleChangeset>> {
             .into());
         }
         let mut body = &envelope.body[..];
-        all.extend(decode_bundle_body_with_ctx(&mut body)?.1);
+        all.extend(decode_bundle_body_with_ctx(&mut body, envelope.optional_features)?.changesets);
     }
     Ok(all)
 }
@@ -303,7 +389,7 @@ pub fn bundle_contains_unknown(bytes: &[u8]) -> CodecResult<bool> {
     if bytes.is_empty() {
         return Ok(false);
     }
-    let (_, css) = decode_bundle_with_ctx(bytes)?;
+    let css = decode_bundle_with_ctx(bytes)?.changesets;
     Ok(css.iter().any(|cs| {
         cs.records.iter().any(|r| match &r.payload {
             RecordPayload::Preserved(_) => true,
@@ -328,9 +414,7 @@ pub fn bundle_stream_contains_unknown(bytes: &[u8]) -> CodecResult<bool> {
             .into());
         }
         let mut body = &envelope.body[..];
-        let ctx = read_preamble(&mut body)?;
-        let css = decode_bundle_changesets(&mut body, &ctx)?;
-        expect_consumed(body)?;
+        let css = decode_bundle_body_with_ctx(&mut body, envelope.optional_features)?.changesets;
         any |= css.iter().any(|cs| {
             cs.records.iter().any(|r| match &r.payload {
                 RecordPayload::Preserved(_) => true,
@@ -344,7 +428,8 @@ pub fn bundle_stream_contains_unknown(bytes: &[u8]) -> CodecResult<bool> {
 /// 값 재인코드 없이 changeset 단위로 쪼갠다 — 각 산출은 원본 preamble을 그대로
 /// 복사하고 해당 changeset만 다시 프레이밍한다. 첫(유일) changeset이 되므로
 /// cs_parents의 Implicit은 write_parents가 자동으로 Explicit으로 승격한다
-/// (prev_cs_last가 항상 None으로 시작하기 때문).
+/// (prev_cs_last가 항상 None으로 시작하기 때문). content hash 섹션이 있으면 각
+/// 산출이 자기 changeset의 hash 하나를 이어받는다.
 pub fn split_bundle_bytes(bytes: &[u8]) -> CodecResult<Vec<Vec<u8>>> {
     let mut input = bytes;
     let mut outputs = Vec::new();
@@ -361,16 +446,18 @@ pub fn split_bundle_bytes(bytes: &[u8]) -> CodecResult<Vec<Vec<u8>>> {
         let ctx = read_preamble(&mut body)?;
         let preamble_bytes = &envelope.body[..before - body.len()];
         let css = decode_bundle_changesets(&mut body, &ctx)?;
+        let hashes = read_hash_section(&mut body, envelope.optional_features, css.len())?;
         expect_consumed(body)?;
 
         let enc_ctx = EncCtx::from_parts(&ctx.actors, ctx.baselines.clone())?;
-        for cs in &css {
-            let mut out_body = preamble_bytes.to_vec();
-            encode_bundle_body(std::slice::from_ref(cs), &enc_ctx, &mut out_body)?;
-            outputs.push(wrap(&Envelope::new(
-                PayloadKind::ChangesetBundle,
-                out_body,
-            ))?);
+        for (i, cs) in css.iter().enumerate() {
+            let mut out = Envelope::new(PayloadKind::ChangesetBundle, preamble_bytes.to_vec());
+            encode_bundle_body(std::slice::from_ref(cs), &enc_ctx, &mut out.body)?;
+            if let Some(hashes) = &hashes {
+                write_hash_section(1, &hashes[i..=i], &mut out.body)?;
+                out.optional_features |= OPTIONAL_CHANGESET_HASHES;
+            }
+            outputs.push(wrap(&out)?);
         }
     }
     Ok(outputs)
@@ -507,7 +594,7 @@ mod tests {
         body[cs_count_end] = MARKER_IMPLICIT;
         let mut input = &body[..];
         assert!(matches!(
-            decode_bundle_body_with_ctx(&mut input).map(|(_, css)| css),
+            decode_bundle_body_with_ctx(&mut input, 0).map(|b| b.changesets),
             Err(CodecError::Corruption(
                 Corruption::ImplicitPrevWithoutPredecessor
             ))
@@ -564,7 +651,7 @@ mod tests {
         .unwrap();
         let mut input = &body[..];
         assert!(matches!(
-            decode_bundle_body_with_ctx(&mut input),
+            decode_bundle_body_with_ctx(&mut input, 0).map(|b| b.changesets),
             Err(CodecError::Corruption(
                 Corruption::NonCanonicalParentsMarker
             ))
@@ -587,6 +674,59 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn changeset_hashes_ride_the_optional_section() {
+        let css = vec![
+            BundleChangeset {
+                records: vec![rec(1, 0, vec![], 'a')],
+            },
+            BundleChangeset {
+                records: vec![rec(1, 1, vec![Dot::new(1, 0)], 'b')],
+            },
+        ];
+        let hashes = [ContentHash([1; 32]), ContentHash([2; 32])];
+        let bytes = encode_bundle_hashed(&css, &hashes).unwrap();
+        let body = decode_bundle_with_ctx(&bytes).unwrap();
+        assert_eq!(body.changesets, css);
+        assert_eq!(body.hashes.as_deref(), Some(&hashes[..]));
+        assert_eq!(
+            decode_bundle_with_ctx(&encode_bundle(&css).unwrap())
+                .unwrap()
+                .hashes,
+            None
+        );
+
+        let parts = split_bundle_bytes(&bytes).unwrap();
+        assert_eq!(
+            decode_bundle_with_ctx(&parts[1]).unwrap().hashes,
+            Some(vec![hashes[1]])
+        );
+
+        assert!(matches!(
+            encode_bundle_hashed(&css, &hashes[..1]),
+            Err(CodecError::Encode(EncodeInvariant::ChangesetHashCount {
+                changesets: 2,
+                hashes: 1
+            }))
+        ));
+    }
+
+    #[test]
+    fn hash_section_is_trailing_bytes_without_the_bit() {
+        let css = vec![BundleChangeset {
+            records: vec![rec(1, 0, vec![], 'a')],
+        }];
+        let bytes = encode_bundle_hashed(&css, &[ContentHash([1; 32])]).unwrap();
+        let envelope = crate::envelope::unwrap(&bytes).unwrap();
+        let mut input = &envelope.body[..];
+        assert!(matches!(
+            decode_bundle_body_with_ctx(&mut input, 0).map(|b| b.changesets),
+            Err(CodecError::Corruption(Corruption::TrailingBytes {
+                remaining: 33
+            }))
+        ));
+    }
+
     #[test]
     fn empty_bundle_round_trips() {
         let bytes = encode_bundle(&[]).unwrap();
@@ -613,7 +753,7 @@ mod tests {
         write_varint(0, &mut body);
         let mut input = &body[..];
         assert!(matches!(
-            decode_bundle_body_with_ctx(&mut input),
+            decode_bundle_body_with_ctx(&mut input, 0).map(|b| b.changesets),
             Err(CodecError::Corruption(Corruption::EmptyChangesetOps))
         ));
     }
@@ -4,7 +4,7 @@ use std::collections::hash_map::Entry;
 use editor_crdt::Dot;
 
 use crate::bundle::decode_bundle_from_envelope;
-use crate::convert::{ReencodableChangesets, changesets_from_ctx_and_bundles, encode_changesets};
+use crate::convert::{ReencodableChangesets, changesets_from_body, encode_changesets};
 use crate::envelope::unwrap_one;
 use crate::error::{CodecError, CodecResult, Corruption};
 
@@ -33,8 +33,7 @@ pub fn consolidate_stream(bytes: &[u8]) -> CodecResult<Option<Consolidation>> {
             Err(e) => return Err(e),
         };
         let taken = before.len() - input.len();
-        let (ctx, bundles) = decode_bundle_from_envelope(&envelope)?;
-        let decoded = changesets_from_ctx_and_bundles(ctx, bundles)?;
+        let decoded = changesets_from_body(decode_bundle_from_envelope(&envelope)?)?;
         match decoded.into_reencodable() {
             Ok(r) => parts.push(r),
             Err(_) => break,
@@ -1,12 +1,13 @@
-use editor_crdt::{Changeset, Dot, ListOp, Op};
+use editor_crdt::{Changeset, ContentHash, Dot, ListOp, Op};
 use editor_model::{
     AliasOp, AliasRun, Alignment, AtomLeaf, EditOp, LayoutMode, Modifier, ModifierAttrOp,
-    ModifierType, NodeAttr, NodeAttrOp, NodeType, SeqClass, SeqItem, SpanOp, alias_op_is_valid,
-    classify,
+    ModifierType, NodeAttr, NodeAttrOp, NodeType, SeqClass, SeqItem, SpanOp, TextDirection,
+    alias_op_is_valid, classify,
 };
 
 use crate::bundle::{
-    BundleChangeset, BundleRecord, RecordPayload, decode_bundle_with_ctx, encode_bundle,
+    BundleBody, BundleChangeset, BundleRecord, RecordPayload, decode_bundle_with_ctx,
+    encode_bundle, encode_bundle_hashed,
 };
 use crate::ctx::EncCtx;
 use crate::durable::Durable;
@@ -42,6 +43,7 @@ impl ReencodableChangesets {
 
 pub struct Decoded {
     changesets: Vec<Changeset<EditOp>>,
+    content_hashes: Vec<Option<ContentHash>>,
     lossless: bool,
 }
 
@@ -50,6 +52,12 @@ impl Decoded {
         self.lossless
     }
 
+    /// `changesets`와 같은 순서의 전송측 content hash — hash 섹션이 없던 envelope의
+    /// changeset은 `None`. 코덱은 운반만 하고, 대조는 그래프에 적재한 뒤 호출측 몫이다.
+    pub fn content_hashes(&self) -> &[Option<ContentHash>] {
+        &self.content_hashes
+    }
+
     pub fn into_graph_input(self) -> Vec<Changeset<EditOp>> {
         self.changesets
     }
@@ -92,6 +100,7 @@ fn to_durable_node_type(nt: NodeType) -> DurableNodeType {
         NodeType::HorizontalRule => DurableNodeType::HorizontalRule,
         NodeType::PageBreak => DurableNodeType::PageBreak,
         NodeType::Tab => DurableNodeType::Tab,
+        NodeType::ColumnBreak => DurableNodeType::ColumnBreak,
     }
 }
 
@@ -104,6 +113,8 @@ fn to_durable_layout(m: &LayoutMode) -> DurableLayoutMode {
             page_margin_bottom,
             page_margin_left,
             page_margin_right,
+            columns,
+            column_gap,
         } => DurableLayoutMode::Paginated {
             page_width: *page_width,
             page_height: *page_height,
@@ -111,12 +122,28 @@ fn to_durable_layout(m: &LayoutMode) -> DurableLayoutMode {
             page_margin_bottom: *page_margin_bottom,
             page_margin_left: *page_margin_left,
             page_margin_right: *page_margin_right,
+            columns: *columns,
+            column_gap: *column_gap,
             tail: no_tail(),
         },
         LayoutMode::Continuous { max_width } => DurableLayoutMode::Continuous {
             max_width: *max_width,
             tail: no_tail(),
         },
+        LayoutMode::Manuscript {
+            columns,
+            rows,
+            cell_size,
+            row_gap,
+            page_margin,
+        } => DurableLayoutMode::Manuscript {
+            columns: *columns,
+            rows: *rows,
+            cell_size: *cell_size,
+            row_gap: *row_gap,
+            page_margin: *page_margin,
+            tail: no_tail(),
+        },
     }
 }
 
@@ -218,6 +245,7 @@ fn to_durable_attr(attr: &NodeAttr) -> DurableAttr {
         NodeAttr::HardBreak { attr } => match *attr {},
         NodeAttr::PageBreak { attr } => match *attr {},
         NodeAttr::Tab { attr } => match *attr {},
+        NodeAttr::ColumnBreak { attr } => match *attr {},
     }
 }
 
@@ -244,6 +272,11 @@ fn to_durable_modifier(m: &Modifier) -> DurableModifier {
             Alignment::Right => DurableAlignment::Right,
             Alignment::Justify => DurableAlignment::Justify,
         }),
+        Modifier::Direction { value } => DurableModifier::Direction(match value {
+            TextDirection::Auto => DurableTextDirection::Auto,
+            TextDirection::Ltr => DurableTextDirection::Ltr,
+            TextDirection::Rtl => DurableTextDirection::Rtl,
+        }),
     }
 }
 
@@ -265,6 +298,7 @@ fn to_durable_kind(k: &ModifierType) -> DurableModifierKind {
         ModifierType::BlockGap => DurableModifierKind::BlockGap,
         ModifierType::ParagraphIndent => DurableModifierKind::ParagraphIndent,
         ModifierType::Alignment => DurableModifierKind::Alignment,
+        ModifierType::Direction => DurableModifierKind::Direction,
     }
 }
 
@@ -414,9 +448,21 @@ pub fn to_durable_op(op: &EditOp) -> CodecResult<DurableOp> {
 }
 
 pub fn encode_changesets(css: ReencodableChangesets) -> CodecResult<Vec<u8>> {
-    let css = css.0;
+    encode_bundle(&to_bundles(&css.0)?)
+}
+
+/// `encode_changesets` + `OPTIONAL_CHANGESET_HASHES` 섹션. `hashes[i]`는 `css`의 i번째
+/// changeset hash(`OpGraph::changeset_hash::<DurableDigest>`) — 이 bit를 모르는 리더는 결과를 거부한다.
+pub fn encode_changesets_hashed(
+    css: ReencodableChangesets,
+    hashes: &[ContentHash],
+) -> CodecResult<Vec<u8>> {
+    encode_bundle_hashed(&to_bundles(&css.0)?, hashes)
+}
+
+fn to_bundles(css: &[Changeset<EditOp>]) -> CodecResult<Vec<BundleChangeset>> {
     let mut bundles = Vec::with_capacity(css.len());
-    for cs in &css {
+    for cs in css {
         let mut records = Vec::with_capacity(cs.ops.len());
         for op in &cs.ops {
             records.push(BundleRecord {
@@ -428,7 +474,7 @@ pub fn encode_changesets(css: ReencodableChangesets) -> CodecResult<Vec<u8>> {
         }
         bundles.push(BundleChangeset { records });
     }
-    encode_bundle(&bundles)
+    Ok(bundles)
 }
 
 pub fn changesets_contain_unknown(css: &[Changeset<EditOp>]) -> bool {
@@ -484,6 +530,7 @@ fn from_durable_node_type(nt: &DurableNodeType) -> Result<NodeType, Unrepresenta
         DurableNodeType::HorizontalRule => NodeType::HorizontalRule,
         DurableNodeType::PageBreak => NodeType::PageBreak,
         DurableNodeType::Tab => NodeType::Tab,
+        DurableNodeType::ColumnBreak => NodeType::ColumnBreak,
         DurableNodeType::Unknown(_) => return Err(Unrepresentable),
     })
 }
@@ -497,6 +544,8 @@ fn from_durable_layout(m: &DurableLayoutMode) -> Result<LayoutMode, Unrepresenta
             page_margin_bottom,
             page_margin_left,
             page_margin_right,
+            columns,
+            column_gap,
             tail,
         } => {
             if !tail.0.is_empty() {
@@ -509,6 +558,8 @@ fn from_durable_layout(m: &DurableLayoutMode) -> Result<LayoutMode, Unrepresenta
                 page_margin_bottom: *page_margin_bottom,
                 page_margin_left: *page_margin_left,
                 page_margin_right: *page_margin_right,
+                columns: *columns,
+                column_gap: *column_gap,
             }
         }
         DurableLayoutMode::Continuous { max_width, tail } => {
@@ -519,6 +570,25 @@ fn from_durable_layout(m: &DurableLayoutMode) -> Result<LayoutMode, Unrepresenta
                 max_width: *max_width,
             }
         }
+        DurableLayoutMode::Manuscript {
+            columns,
+            rows,
+            cell_size,
+            row_gap,
+            page_margin,
+            tail,
+        } => {
+            if !tail.0.is_empty() {
+                return Err(Unrepresentable);
+            }
+            LayoutMode::Manuscript {
+                columns: *columns,
+                rows: *rows,
+                cell_size: *cell_size,
+                row_gap: *row_gap,
+                page_margin: *page_margin,
+            }
+        }
         DurableLayoutMode::Unknown(_) => return Err(Unrepresentable),
     })
 }
@@ -610,6 +680,14 @@ fn from_durable_modifier(m: &DurableModifier) -> Result<Modifier, Unrepresentabl
                 DurableAlignment::Unknown(_) => return Err(Unrepresentable),
             },
         },
+        DurableModifier::Direction(d) => Modifier::Direction {
+            value: match d {
+                DurableTextDirection::Auto => TextDirection::Auto,
+                DurableTextDirection::Ltr => TextDirection::Ltr,
+                DurableTextDirection::Rtl => TextDirection::Rtl,
+                DurableTextDirection::Unknown(_) => return Err(Unrepresentable),
+            },
+        },
         DurableModifier::Unknown(_) => return Err(Unrepresentable),
     })
 }
@@ -632,6 +710,7 @@ fn from_durable_kind(k: &DurableModifierKind) -> Result<ModifierType, Unrepresen
         DurableModifierKind::BlockGap => ModifierType::BlockGap,
         DurableModifierKind::ParagraphIndent => ModifierType::ParagraphIndent,
         DurableModifierKind::Alignment => ModifierType::Alignment,
+        DurableModifierKind::Direction => ModifierType::Direction,
         DurableModifierKind::Unknown(_) => return Err(Unrepresentable),
     })
 }
@@ -996,18 +1075,23 @@ fn from_durable_op(
 }
 
 pub fn decode_changesets(bytes: &[u8]) -> CodecResult<Decoded> {
-    let (ctx, bundles) = decode_bundle_with_ctx(bytes)?;
-    changesets_from_ctx_and_bundles(ctx, bundles)
+    changesets_from_body(decode_bundle_with_ctx(bytes)?)
 }
 
 /// Shared by `decode_changesets` (parses `bytes` itself) and callers that
 /// already parsed the envelope for their own purposes (e.g. a stream walker
 /// that needs the envelope boundary) and would otherwise re-parse the same
 /// header a second time to reach this point.
-pub(crate) fn changesets_from_ctx_and_bundles(
-    ctx: crate::ctx::DecCtx,
-    bundles: Vec<BundleChangeset>,
-) -> CodecResult<Decoded> {
+pub(crate) fn changesets_from_body(body: BundleBody) -> CodecResult<Decoded> {
+    let BundleBody {
+        ctx,
+        changesets: bundles,
+        hashes,
+    } = body;
+    let content_hashes = match hashes {
+        Some(hashes) => hashes.into_iter().map(Some).collect(),
+        None => vec![None; bundles.len()],
+    };
     let enc = EncCtx::from_parts(&ctx.actors, ctx.baselines.clone())?;
     let mut lossless = true;
     let changesets = bundles
@@ -1048,6 +1132,7 @@ pub(crate) fn changesets_from_ctx_and_bundles(
         .collect::<CodecResult<Vec<_>>>()?;
     Ok(Decoded {
         changesets,
+        content_hashes,
         lossless,
     })
 }
@@ -1055,6 +1140,7 @@ pub(crate) fn changesets_from_ctx_and_bundles(
 pub fn decode_changeset_stream(bytes: &[u8]) -> CodecResult<Decoded> {
     let mut input = bytes;
     let mut changesets = Vec::new();
+    let mut content_hashes = Vec::new();
     let mut lossless = true;
     while !input.is_empty() {
         let before = input;
@@ -1063,9 +1149,11 @@ pub fn decode_changeset_stream(bytes: &[u8]) -> CodecResult<Decoded> {
         let mut decoded = decode_changesets(&before[..consumed])?;
         lossless &= decoded.lossless;
         changesets.append(&mut decoded.changesets);
+        content_hashes.append(&mut decoded.content_hashes);
     }
     Ok(Decoded {
         changesets,
+        content_hashes,
         lossless,
     })
 }
@@ -0,0 +1,119 @@
+use editor_crdt::{ContentDigest, ListOp};
+use editor_model::{EditOp, SeqItem};
+
+use crate::convert::to_durable_op;
+use crate::ctx::{CollectCtx, EncCtx, write_preamble};
+use crate::durable::Durable;
+use crate::error::CodecResult;
+use crate::types::DurableOp;
+use crate::varint::write_varint;
+
+const DURABLE: u8 = 0;
+const UNKNOWN_OP: u8 = 1;
+const UNKNOWN_ITEM: u8 = 2;
+const UNENCODABLE: u8 = 3;
+
+/// `OpGraph::changeset_hash`/`heads_hash`의 `EditOp` digest — 저장/전송과 같은
+/// durable 인코딩이라 스키마 진화 규약이 hash 안정성도 함께 보장한다.
+///
+/// op 하나를 자기 dot만으로 모은 독립 ctx(preamble 포함)로 인코딩하므로 같은 op는
+/// 어느 bundle에서 왔든 같은 바이트가 된다. 보존된 `Unknown` payload는 원 bundle
+/// ctx 상대 바이트를 그대로 쓴다 — 태그를 아는 리더는 같은 op를 다르게 hash하므로,
+/// hash는 태그 집합이 같은 replica끼리만 비교된다.
+pub struct DurableDigest;
+
+impl ContentDigest<EditOp> for DurableDigest {
+    fn digest_into(payload: &EditOp, out: &mut Vec<u8>) {
+        if let Ok(durable) = to_durable_op(payload)
+            && let Ok(bytes) = standalone_bytes(&durable)
+        {
+            out.push(DURABLE);
+            out.extend_from_slice(&bytes);
+            return;
+        }
+        match payload {
+            EditOp::Unknown { bytes } => {
+                out.push(UNKNOWN_OP);
+                out.extend_from_slice(bytes);
+            }
+            EditOp::Seq(ListOp::Ins {
+                pos,
+                item: SeqItem::Unknown { tag, bytes },
+            }) => {
+                out.push(UNKNOWN_ITEM);
+                write_varint(*pos as u64, out);
+                write_varint(*tag, out);
+                out.extend_from_slice(bytes);
+            }
+            // 인코더가 거부하는 op(잘못된 alias 등)는 어떤 envelope로도 전송되지
+            // 않으므로 hash를 비교할 상대가 없다.
+            _ => out.push(UNENCODABLE),
+        }
+    }
+}
+
+fn standalone_bytes(op: &DurableOp) -> CodecResult<Vec<u8>> {
+    let mut cc = CollectCtx::new();
+    op.collect(&mut cc);
+    let (actors, baselines) = cc.finalize();
+    let ctx = EncCtx::from_parts(&actors, baselines.clone())?;
+    let mut out = Vec::new();
+    write_preamble(&actors, &baselines, &mut out)?;
+    op.encode(&ctx, &mut out)?;
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use editor_crdt::{Changeset, Dot, Op, OpGraph};
+    use editor_model::{EditOp, SeqItem};
+
+    use super::*;
+
+    fn ins(id: Dot, parents: Vec<Dot>, pos: usize, ch: char) -> Op<EditOp> {
+        Op {
+            id,
+            parents,
+            payload: EditOp::Seq(ListOp::Ins {
+                pos,
+                item: SeqItem::Char(ch),
+            }),
+        }
+    }
+
+    #[test]
+    fn digest_survives_encode_and_decode() {
+        let root = ins(Dot::new(5, 0), vec![], 0, 'a');
+        let undel = Op {
+            id: Dot::new(9, 3),
+            parents: vec![root.id],
+            payload: EditOp::Seq(ListOp::Undel { del: root.id }),
+        };
+        let alone = OpGraph::from_changesets(vec![
+            Changeset {
+                ops: vec![root.clone()],
+            },
+            Changeset {
+                ops: vec![undel.clone()],
+            },
+        ])
+        .unwrap();
+        let bytes = crate::encode_changesets(crate::ReencodableChangesets::from_local_ops(
+            alone.changesets_as_vec(),
+        ))
+        .unwrap();
+        let decoded =
+            OpGraph::from_changesets(crate::decode_changesets(&bytes).unwrap().into_graph_input())
+                .unwrap();
+        assert_eq!(
+            decoded.heads_hash::<DurableDigest>(),
+            alone.heads_hash::<DurableDigest>()
+        );
+
+        let mut other = Vec::new();
+        DurableDigest::digest_into(&ins(Dot::new(5, 0), vec![], 0, 'b').payload, &mut other);
+        let mut same = Vec::new();
+        DurableDigest::digest_into(&root.payload, &mut same);
+        assert_ne!(same, other);
+    }
+}
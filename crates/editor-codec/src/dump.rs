@@ -0,0 +1,483 @@
+//! 코덱 스트림의 사람용 덤프와 그 무손실 역변환.
+//!
+//! `dump_stream`은 스트림의 envelope마다 [`EnvelopeDump`]를 만든다 — 헤더 필드,
+//! preamble의 actor 테이블, `DurableOp`까지 디코드한 changeset 레코드. 리더가
+//! 해석하지 못하는 바이트(`Preserved` 레코드, `record_tail`,
+//! `UnknownTail`/`UnknownPayload`)는 hex로 운반한다. `load_stream`은 덤프를 다시
+//! wire 바이트로 바꾸며, 손대지 않은 덤프는 입력을 바이트 동일하게 재현한다.
+//!
+//! op 안의 dot은 덤프된 preamble 기준으로 재인코딩하므로, 편집한 덤프가 언급하는
+//! 모든 dot을 actor 테이블이 계속 덮어야 한다.
+
+use editor_crdt::{ContentHash, Dot};
+use serde::{Deserialize, Serialize};
+
+use crate::bundle::{
+    BundleChangeset, BundleRecord, RecordPayload, decode_bundle_changesets, encode_bundle_body,
+    read_hash_section, write_hash_section,
+};
+use crate::ctx::{EncCtx, read_dot, read_preamble, write_dot, write_preamble};
+use crate::envelope::{
+    Envelope, OPTIONAL_CHANGESET_HASHES, PayloadKind, StoredBody, compress_body, unwrap_one_stored,
+    wrap_stored,
+};
+use crate::error::{CodecError, CodecResult, EncodeInvariant};
+use crate::framing::{expect_consumed, hex_bytes};
+use crate::types::op::DurableOp;
+use crate::varint::{read_varint, write_varint};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EnvelopeDump {
+    /// 덤프한 스트림에서 envelope의 바이트 오프셋. 참고용이며 load는 무시한다.
+    #[serde(default)]
+    pub offset: usize,
+    pub epoch: u64,
+    pub required_features: u64,
+    pub optional_features: u64,
+    pub compressed: bool,
+    pub payload: PayloadDump,
+    /// `payload` 재인코딩으로 재현되지 않는 원본 envelope 바이트(다른 라이터의 zstd
+    /// 출력, 기본값으로 생략된 필드)일 때만 보관한다. 덤프의 나머지가 그대로면
+    /// `load`는 이를 그대로 방출한다.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "hex_bytes::option"
+    )]
+    pub verbatim: Option<Vec<u8>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum PayloadDump {
+    ChangesetBundle {
+        actors: Vec<u64>,
+        baselines: Vec<u64>,
+        changesets: Vec<ChangesetDump>,
+    },
+    Dots {
+        actors: Vec<u64>,
+        baselines: Vec<u64>,
+        dots: Vec<Dot>,
+    },
+    Snapshot {
+        #[serde(with = "hex_bytes")]
+        body: Vec<u8>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChangesetDump {
+    /// 첫 레코드 id의 actor와 clock. 참고용이며 load는 무시한다.
+    pub actor: u64,
+    pub clock: u64,
+    pub parents: Vec<Dot>,
+    pub records: Vec<RecordDump>,
+    /// changeset-hashes 섹션에서 운반한 hash. load는 envelope의
+    /// `optional_features`에 해당 bit가 있을 때에만 쓰며, 그때는 모든 changeset에
+    /// 있어야 한다.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_hash: Option<ContentHash>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordDump {
+    pub id: Dot,
+    /// 첫 레코드에는 없다 — changeset의 parents를 따른다.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parents: Option<Vec<Dot>>,
+    pub payload: RecordPayloadDump,
+    #[serde(default, skip_serializing_if = "Vec::is_empty", with = "hex_bytes")]
+    pub record_tail: Vec<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecordPayloadDump {
+    Op(DurableOp),
+    /// 이 리더가 모르는 op의 payload와 tail, 저장된 그대로.
+    Preserved(#[serde(with = "hex_bytes")] Vec<u8>),
+}
+
+/// 스트림 덤프·검증 중 처음 만난 오류와 입력 속 위치.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error(
+    "envelope {envelope} (at byte {envelope_offset}): {error} at byte {offset}{}",
+    .body_offset.map(|o| format!(" (decompressed body byte {o})")).unwrap_or_default()
+)]
+pub struct StreamFault {
+    pub envelope: usize,
+    pub envelope_offset: usize,
+    /// 디코더가 멈춘 입력 위치. 압축 body 안이면 저장 body의 시작이고, 그때
+    /// `body_offset`이 압축 해제된 바이트 안을 가리킨다.
+    pub offset: usize,
+    pub body_offset: Option<usize>,
+    pub error: CodecError,
+}
+
+pub fn dump_stream(bytes: &[u8]) -> Result<Vec<EnvelopeDump>, StreamFault> {
+    let mut input = bytes;
+    let mut dumps = Vec::new();
+    while !input.is_empty() {
+        let start = bytes.len() - input.len();
+        let fault = |offset: usize, body_offset: Option<usize>, error: CodecError| StreamFault {
+            envelope: dumps.len(),
+            envelope_offset: start,
+            offset,
+            body_offset,
+            error,
+        };
+        let before = input;
+        let (envelope, stored) =
+            unwrap_one_stored(&mut input).map_err(|e| fault(bytes.len() - input.len(), None, e))?;
+        let original = &before[..before.len() - input.len()];
+        let body_start = bytes.len() - input.len() - stored.bytes.len();
+
+        let payload = dump_payload(&envelope).map_err(|(at, e)| {
+            if stored.compressed {
+                fault(body_start, Some(at), e)
+            } else {
+                fault(body_start + at, None, e)
+            }
+        })?;
+        let mut dump = EnvelopeDump {
+            offset: start,
+            epoch: envelope.epoch,
+            required_features: envelope.required_features,
+            optional_features: envelope.optional_features,
+            compressed: stored.compressed,
+            payload,
+            verbatim: None,
+        };
+        if encode_envelope(&dump).ok().as_deref() != Some(original) {
+            dump.verbatim = Some(original.to_vec());
+        }
+        dumps.push(dump);
+    }
+    Ok(dumps)
+}
+
+/// `dump_stream`과 같은 순회를 결과를 남기지 않고 한다 — envelope 수를 돌려준다.
+pub fn validate_stream(bytes: &[u8]) -> Result<usize, StreamFault> {
+    dump_stream(bytes).map(|dumps| dumps.len())
+}
+
+pub fn load_stream(dumps: &[EnvelopeDump]) -> CodecResult<Vec<u8>> {
+    let mut out = Vec::new();
+    for dump in dumps {
+        match dump.verbatim.as_deref() {
+            Some(verbatim) if is_unedited(dump, verbatim) => out.extend_from_slice(verbatim),
+            _ => out.extend_from_slice(&encode_envelope(dump)?),
+        }
+    }
+    Ok(out)
+}
+
+fn is_unedited(dump: &EnvelopeDump, verbatim: &[u8]) -> bool {
+    let Ok(dumps) = dump_stream(verbatim) else {
+        return false;
+    };
+    let [original] = dumps.as_slice() else {
+        return false;
+    };
+    original.epoch == dump.epoch
+        && original.required_features == dump.required_features
+        && original.optional_features == dump.optional_features
+        && original.compressed == dump.compressed
+        && original.payload == dump.payload
+}
+
+fn encode_envelope(dump: &EnvelopeDump) -> CodecResult<Vec<u8>> {
+    let (payload_kind, body) = encode_payload(&dump.payload, dump.optional_features)?;
+    let envelope = Envelope {
+        payload_kind,
+        epoch: dump.epoch,
+        required_features: dump.required_features,
+        optional_features: dump.optional_features,
+        body,
+    };
+    let compressed = dump.compressed.then(|| compress_body(&envelope.body));
+    let stored = StoredBody {
+        compressed: dump.compressed,
+        bytes: compressed.as_deref().unwrap_or(&envelope.body),
+    };
+    wrap_stored(&envelope, stored)
+}
+
+/// envelope body 디코드. 오류는 디코더가 멈춘 body 오프셋을 담는다.
+fn dump_payload(envelope: &Envelope) -> Result<PayloadDump, (usize, CodecError)> {
+    let body = &envelope.body[..];
+    let mut input = body;
+    let result = match envelope.payload_kind {
+        PayloadKind::ChangesetBundle => dump_bundle(&mut input, envelope.optional_features),
+        PayloadKind::Dots => dump_dots(&mut input),
+        PayloadKind::Snapshot => Ok(PayloadDump::Snapshot {
+            body: body.to_vec(),
+        }),
+    };
+    result.map_err(|e| (body.len() - input.len(), e))
+}
+
+fn dump_bundle(input: &mut &[u8], optional_features: u64) -> CodecResult<PayloadDump> {
+    let ctx = read_preamble(input)?;
+    let css = decode_bundle_changesets(input, &ctx)?;
+    let hashes = read_hash_section(input, optional_features, css.len())?;
+    expect_consumed(input)?;
+    let changesets = css
+        .into_iter()
+        .enumerate()
+        .map(|(i, cs)| {
+            let first = &cs.records[0];
+            ChangesetDump {
+                actor: first.id.actor,
+                clock: first.id.clock,
+                parents: first.parents.clone(),
+                records: cs
+                    .records
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, r)| RecordDump {
+                        id: r.id,
+                        parents: (i > 0).then_some(r.parents),
+                        payload: match r.payload {
+                            RecordPayload::Known(op) => RecordPayloadDump::Op(op),
+                            RecordPayload::Preserved(bytes) => RecordPayloadDump::Preserved(bytes),
+                        },
+                        record_tail: r.record_tail,
+                    })
+                    .collect(),
+                content_hash: hashes.as_ref().map(|h| h[i]),
+            }
+        })
+        .collect();
+    Ok(PayloadDump::ChangesetBundle {
+        actors: ctx.actors,
+        baselines: ctx.baselines,
+        changesets,
+    })
+}
+
+fn dump_dots(input: &mut &[u8]) -> CodecResult<PayloadDump> {
+    let ctx = read_preamble(input)?;
+    let count = read_varint(input)?;
+    let mut dots = Vec::with_capacity((count as usize).min(input.len()));
+    for _ in 0..count {
+        dots.push(read_dot(input, &ctx)?);
+    }
+    expect_consumed(input)?;
+    Ok(PayloadDump::Dots {
+        actors: ctx.actors,
+        baselines: ctx.baselines,
+        dots,
+    })
+}
+
+fn encode_payload(
+    payload: &PayloadDump,
+    optional_features: u64,
+) -> CodecResult<(PayloadKind, Vec<u8>)> {
+    let mut body = Vec::new();
+    match payload {
+        PayloadDump::ChangesetBundle {
+            actors,
+            baselines,
+            changesets,
+        } => {
+            let ctx = EncCtx::from_parts(actors, baselines.clone())?;
+            write_preamble(actors, baselines, &mut body)?;
+            let css: Vec<BundleChangeset> = changesets.iter().map(bundle_changeset).collect();
+            encode_bundle_body(&css, &ctx, &mut body)?;
+            if optional_features & OPTIONAL_CHANGESET_HASHES != 0 {
+                let hashes: Vec<ContentHash> =
+                    changesets.iter().filter_map(|cs| cs.content_hash).collect();
+                if hashes.len() != changesets.len() {
+                    return Err(EncodeInvariant::ChangesetHashCount {
+                        changesets: changesets.len(),
+                        hashes: hashes.len(),
+                    }
+                    .into());
+                }
+                write_hash_section(css.len(), &hashes, &mut body)?;
+            }
+            Ok((PayloadKind::ChangesetBundle, body))
+        }
+        PayloadDump::Dots {
+            actors,
+            baselines,
+            dots,
+        } => {
+            let ctx = EncCtx::from_parts(actors, baselines.clone())?;
+            write_preamble(actors, baselines, &mut body)?;
+            write_varint(dots.len() as u64, &mut body);
+            for dot in dots {
+                write_dot(dot, &ctx, &mut body)?;
+            }
+            Ok((PayloadKind::Dots, body))
+        }
+        PayloadDump::Snapshot { body } => Ok((PayloadKind::Snapshot, body.clone())),
+    }
+}
+
+fn bundle_changeset(cs: &ChangesetDump) -> BundleChangeset {
+    BundleChangeset {
+        records: cs
+            .records
+            .iter()
+            .enumerate()
+            .map(|(i, r)| BundleRecord {
+                id: r.id,
+                parents: if i == 0 {
+                    cs.parents.clone()
+                } else {
+                    r.parents.clone().unwrap_or_default()
+                },
+                payload: match &r.payload {
+                    RecordPayloadDump::Op(op) => RecordPayload::Known(op.clone()),
+                    RecordPayloadDump::Preserved(bytes) => RecordPayload::Preserved(bytes.clone()),
+                },
+                record_tail: r.record_tail.clone(),
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Corruption;
+    use crate::bundle::{encode_bundle, encode_bundle_hashed, encode_dots};
+    use crate::envelope::wrap;
+    use crate::types::item::DurableItem;
+
+    fn record(id: Dot, parents: Vec<Dot>, ch: char) -> BundleRecord {
+        BundleRecord {
+            id,
+            parents,
+            payload: RecordPayload::Known(DurableOp::SeqIns {
+                pos: 0,
+                item: DurableItem::Char(ch),
+            }),
+            record_tail: Vec::new(),
+        }
+    }
+
+    fn sample_stream() -> Vec<u8> {
+        let a = Dot::new(7, 0);
+        let b = Dot::new(7, 1);
+        let mut stream = encode_bundle(&[BundleChangeset {
+            records: vec![record(a, vec![], 'h'), record(b, vec![a], 'i')],
+        }])
+        .unwrap();
+        stream.extend(encode_dots(&[a, Dot::new(9, 4)]).unwrap());
+        // 압축 경로까지 덮도록 임계값을 넘는 body
+        let long: Vec<BundleRecord> = (0..200)
+            .map(|i| {
+                let parents = if i == 0 {
+                    vec![]
+                } else {
+                    vec![Dot::new(3, i - 1)]
+                };
+                record(Dot::new(3, i), parents, 'x')
+            })
+            .collect();
+        stream.extend(encode_bundle(&[BundleChangeset { records: long }]).unwrap());
+        stream
+    }
+
+    #[test]
+    fn json_round_trip_is_byte_identical() {
+        let stream = sample_stream();
+        let dumps = dump_stream(&stream).unwrap();
+        assert_eq!(dumps.len(), 3);
+        assert!(dumps[2].compressed);
+        assert!(dumps.iter().all(|d| d.verbatim.is_none()));
+
+        let json = serde_json::to_string_pretty(&dumps).unwrap();
+        let reloaded: Vec<EnvelopeDump> = serde_json::from_str(&json).unwrap();
+        assert_eq!(load_stream(&reloaded).unwrap(), stream);
+    }
+
+    #[test]
+    fn edited_dump_reencodes() {
+        let stream = sample_stream();
+        let mut dumps = dump_stream(&stream).unwrap();
+        let PayloadDump::ChangesetBundle { changesets, .. } = &mut dumps[0].payload else {
+            panic!("first envelope is a bundle");
+        };
+        changesets[0].records[1].payload = RecordPayloadDump::Op(DurableOp::SeqIns {
+            pos: 1,
+            item: DurableItem::Char('!'),
+        });
+
+        let bytes = load_stream(&dumps).unwrap();
+        assert_eq!(dump_stream(&bytes).unwrap()[0].payload, dumps[0].payload);
+    }
+
+    #[test]
+    fn foreign_compression_is_kept_verbatim() {
+        // 이 라이터가 고르지 않았을 저장 형태: raw 블록만으로 된 zstd 프레임
+        let envelope = Envelope::new(PayloadKind::Snapshot, vec![b'x'; 1_000]);
+        let stored = ruzstd::encoding::compress_to_vec(
+            envelope.body.as_slice(),
+            ruzstd::encoding::CompressionLevel::Uncompressed,
+        );
+        let bytes = wrap_stored(
+            &envelope,
+            StoredBody {
+                compressed: true,
+                bytes: &stored,
+            },
+        )
+        .unwrap();
+        assert_ne!(bytes, wrap(&envelope).unwrap());
+
+        let dumps = dump_stream(&bytes).unwrap();
+        assert!(dumps[0].verbatim.is_some());
+        assert_eq!(load_stream(&dumps).unwrap(), bytes);
+    }
+
+    #[test]
+    fn changeset_hashes_survive_dump_and_load() {
+        let a = Dot::new(7, 0);
+        let hash = ContentHash([0xab; 32]);
+        let bytes = encode_bundle_hashed(
+            &[BundleChangeset {
+                records: vec![record(a, vec![], 'h')],
+            }],
+            &[hash],
+        )
+        .unwrap();
+        let mut dumps = dump_stream(&bytes).unwrap();
+        assert_eq!(load_stream(&dumps).unwrap(), bytes);
+        let PayloadDump::ChangesetBundle { changesets, .. } = &mut dumps[0].payload else {
+            panic!("bundle envelope");
+        };
+        assert_eq!(changesets[0].content_hash, Some(hash));
+
+        changesets[0].content_hash = None;
+        assert!(matches!(
+            load_stream(&dumps),
+            Err(CodecError::Encode(EncodeInvariant::ChangesetHashCount {
+                changesets: 1,
+                hashes: 0
+            }))
+        ));
+    }
+
+    #[test]
+    fn fault_reports_the_envelope_and_byte_offset() {
+        let mut stream = sample_stream();
+        let second = dump_stream(&stream).unwrap()[1].offset;
+        let last = stream.len() - 1;
+        stream[last] ^= 0x01;
+
+        let fault = validate_stream(&stream).unwrap_err();
+        assert_eq!(fault.envelope, 2);
+        assert!(fault.envelope_offset > second);
+        assert_eq!(fault.offset, stream.len());
+        assert_eq!(
+            fault.error,
+            CodecError::Corruption(Corruption::ChecksumMismatch)
+        );
+    }
+}
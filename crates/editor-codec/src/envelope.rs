@@ -7,6 +7,10 @@ use crate::varint::{read_varint, write_varint};
 pub const MAGIC: u8 = 0xC2;
 pub const FORMAT_VERSION: u8 = 1;
 pub const SUPPORTED_REQUIRED_FEATURES: u64 = 0;
+/// changeset-bundle body 끝에 changeset별 content hash 섹션(개수 varint + 32바이트씩)이
+/// 붙어 있음. hash는 검증 전용이라 디코드 결과를 바꾸지 않지만, 이 bit 이전 리더는 섹션을
+/// TrailingBytes로 거부한다 — 수신측이 이 bit를 안다고 확인된 경로에서만 방출할 것.
+pub const OPTIONAL_CHANGESET_HASHES: u64 = 1;
 pub const COMPRESSION_THRESHOLD_BYTES: usize = 256;
 pub const FLAG_COMPRESSED: u8 = 0b0000_0001;
 pub const KNOWN_FLAGS: u8 = FLAG_COMPRESSED;
@@ -52,9 +56,64 @@ impl Envelope {
     }
 }
 
+/// 헤더가 선언한 저장 형태 그대로의 body — 압축 여부와 체크섬이 덮는 저장 바이트.
+/// 덤프/재적재처럼 원본 바이트를 그대로 재방출해야 하는 경로용.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StoredBody<'a> {
+    pub compressed: bool,
+    pub bytes: &'a [u8],
+}
+
 pub fn wrap(envelope: &Envelope) -> CodecResult<Vec<u8>> {
-    // 라이터 불변식: 현재 라이터가 표현할 수 없는 값은 쓰기 전에 거부한다 —
-    // 자기 리더가 못 여는 envelope를 생산하는 경로를 타입 수준에서 봉쇄
+    check_writable(envelope)?;
+    if envelope.body.len() >= COMPRESSION_THRESHOLD_BYTES {
+        let compressed = compress_body(&envelope.body);
+        if compressed.len() < envelope.body.len() {
+            return Ok(write_envelope(
+                envelope,
+                StoredBody {
+                    compressed: true,
+                    bytes: &compressed,
+                },
+            ));
+        }
+    }
+    Ok(write_envelope(
+        envelope,
+        StoredBody {
+            compressed: false,
+            bytes: &envelope.body,
+        },
+    ))
+}
+
+/// `wrap`의 압축 휴리스틱 대신 `stored`를 저장 형태로 그대로 쓴다 — 다른 라이터(또는
+/// 다른 zstd 구현)가 만든 envelope를 바이트 동일하게 재방출하는 용도. `stored`가
+/// `envelope.body`를 표기하지 않으면 거부한다: 자기 리더가 다르게 읽을 envelope는
+/// 쓰지 않는다는 라이터 불변식은 `wrap`과 같다.
+pub fn wrap_stored(envelope: &Envelope, stored: StoredBody<'_>) -> CodecResult<Vec<u8>> {
+    check_writable(envelope)?;
+    writer_body_cap(stored.bytes.len() as u64)?;
+    let represents_body = if stored.compressed {
+        decompress_body(stored.bytes, envelope.body.len() as u64).ok()
+            == Some(envelope.body.clone())
+    } else {
+        stored.bytes == envelope.body.as_slice()
+    };
+    if !represents_body {
+        return Err(EncodeInvariant::StoredBodyMismatch.into());
+    }
+    Ok(write_envelope(envelope, stored))
+}
+
+/// `wrap`이 쓰는 것과 같은 설정의 zstd 압축.
+pub fn compress_body(body: &[u8]) -> Vec<u8> {
+    ruzstd::encoding::compress_to_vec(body, ruzstd::encoding::CompressionLevel::Fastest)
+}
+
+// 라이터 불변식: 현재 라이터가 표현할 수 없는 값은 쓰기 전에 거부한다 —
+// 자기 리더가 못 여는 envelope를 생산하는 경로를 타입 수준에서 봉쇄
+fn check_writable(envelope: &Envelope) -> CodecResult<()> {
     let unsupported = envelope.required_features & !SUPPORTED_REQUIRED_FEATURES;
     if unsupported != 0 {
         return Err(EncodeInvariant::UnsupportedRequiredFeatures { bits: unsupported }.into());
@@ -65,50 +124,43 @@ pub fn wrap(envelope: &Envelope) -> CodecResult<Vec<u8>> {
         }
         .into());
     }
-    writer_body_cap(envelope.body.len() as u64)?;
-
-    let (flags, stored_body, raw_len): (u8, Vec<u8>, Option<u64>) =
-        if envelope.body.len() >= COMPRESSION_THRESHOLD_BYTES {
-            let compressed = ruzstd::encoding::compress_to_vec(
-                envelope.body.as_slice(),
-                ruzstd::encoding::CompressionLevel::Fastest,
-            );
-            if compressed.len() < envelope.body.len() {
-                (
-                    FLAG_COMPRESSED,
-                    compressed,
-                    Some(envelope.body.len() as u64),
-                )
-            } else {
-                (0, envelope.body.clone(), None)
-            }
-        } else {
-            (0, envelope.body.clone(), None)
-        };
+    writer_body_cap(envelope.body.len() as u64)
+}
 
-    let mut out = Vec::with_capacity(stored_body.len() + 32);
+fn write_envelope(envelope: &Envelope, stored: StoredBody<'_>) -> Vec<u8> {
+    let mut out = Vec::with_capacity(stored.bytes.len() + 32);
     out.push(MAGIC);
     out.push(FORMAT_VERSION);
     write_varint(envelope.required_features, &mut out);
     write_varint(envelope.optional_features, &mut out);
     write_varint(envelope.epoch, &mut out);
     out.push(envelope.payload_kind as u8);
+    let flags = if stored.compressed {
+        FLAG_COMPRESSED
+    } else {
+        0
+    };
     out.push(flags);
-    write_varint(stored_body.len() as u64, &mut out);
-    if let Some(raw) = raw_len {
-        write_varint(raw, &mut out);
+    write_varint(stored.bytes.len() as u64, &mut out);
+    if stored.compressed {
+        write_varint(envelope.body.len() as u64, &mut out);
     }
 
     // 체크섬 = checksum 필드 자신을 제외한 전체 (헤더 프리픽스 + 저장 body)
     let mut hasher = xxhash_rust::xxh3::Xxh3::new();
     hasher.update(&out);
-    hasher.update(&stored_body);
+    hasher.update(stored.bytes);
     out.extend_from_slice(&hasher.digest().to_le_bytes());
-    out.extend_from_slice(&stored_body);
-    Ok(out)
+    out.extend_from_slice(stored.bytes);
+    out
 }
 
 pub fn unwrap_one(input: &mut &[u8]) -> CodecResult<Envelope> {
+    unwrap_one_stored(input).map(|(envelope, _)| envelope)
+}
+
+/// `unwrap_one`과 같되 저장 형태(압축 여부·저장 바이트)도 함께 돌려준다.
+pub fn unwrap_one_stored<'a>(input: &mut &'a [u8]) -> CodecResult<(Envelope, StoredBody<'a>)> {
     let original = *input;
     let magic = read_u8(input)?;
     if magic != MAGIC {
@@ -188,55 +240,62 @@ pub fn unwrap_one(input: &mut &[u8]) -> CodecResult<Envelope> {
     let payload_kind =
         PayloadKind::from_u8(kind_byte).ok_or(Fenced::PayloadKind { got: kind_byte })?;
 
-    let body = if let Some(raw) = raw_len {
-        let mut cursor = stored_body;
-        // ruzstd 0.8.3의 window 상한(100 MiB) 가드는 state가 이미 있는 reset 경로에만
-        // 있고 최초 init 경로에는 없다. seed 프레임으로 state를 선점해 실제 입력이
-        // 반드시 가드된 reset 경로를 타게 한다 — 적대적 window 선언(최대 2^41)의
-        // 무상한 할당 차단.
-        let seed =
-            ruzstd::encoding::compress_to_vec(&[][..], ruzstd::encoding::CompressionLevel::Fastest);
-        let mut frame_decoder = ruzstd::decoding::FrameDecoder::new();
-        frame_decoder
-            .init(seed.as_slice())
-            .map_err(|e| Corruption::Zstd(format!("{e:?}")))?;
-        let decoder =
-            ruzstd::decoding::StreamingDecoder::new_with_decoder(&mut cursor, frame_decoder)
-                .map_err(|e| Corruption::Zstd(format!("{e:?}")))?;
-        let mut body = Vec::new();
-        // raw_len + 1로 유계 읽기: 출력 할당·CPU가 선언값에 묶인다
-        let mut limited = decoder.take(raw + 1);
-        limited
-            .read_to_end(&mut body)
-            .map_err(|e| Corruption::Zstd(format!("{e:?}")))?;
-        if body.len() as u64 != raw {
-            return Err(Corruption::RawLenMismatch {
-                declared: raw,
-                actual: body.len() as u64,
-            }
-            .into());
-        }
-        // 정확 소비: 프레임 뒤 잉여 바이트/추가 프레임은 체크섬이 유효해도 거부 —
-        // 동일 payload의 복수 표기(밀수 채널)를 봉쇄
-        drop(limited);
-        if !cursor.is_empty() {
-            return Err(Corruption::TrailingBytes {
-                remaining: cursor.len(),
-            }
-            .into());
-        }
-        body
-    } else {
-        stored_body.to_vec()
+    let body = match raw_len {
+        Some(raw) => decompress_body(stored_body, raw)?,
+        None => stored_body.to_vec(),
     };
 
-    Ok(Envelope {
-        payload_kind,
-        epoch,
-        required_features,
-        optional_features,
-        body,
-    })
+    Ok((
+        Envelope {
+            payload_kind,
+            epoch,
+            required_features,
+            optional_features,
+            body,
+        },
+        StoredBody {
+            compressed: raw_len.is_some(),
+            bytes: stored_body,
+        },
+    ))
+}
+
+fn decompress_body(stored_body: &[u8], raw: u64) -> CodecResult<Vec<u8>> {
+    let mut cursor = stored_body;
+    // ruzstd 0.8.3의 window 상한(100 MiB) 가드는 state가 이미 있는 reset 경로에만
+    // 있고 최초 init 경로에는 없다. seed 프레임으로 state를 선점해 실제 입력이
+    // 반드시 가드된 reset 경로를 타게 한다 — 적대적 window 선언(최대 2^41)의
+    // 무상한 할당 차단.
+    let seed = compress_body(&[]);
+    let mut frame_decoder = ruzstd::decoding::FrameDecoder::new();
+    frame_decoder
+        .init(seed.as_slice())
+        .map_err(|e| Corruption::Zstd(format!("{e:?}")))?;
+    let decoder = ruzstd::decoding::StreamingDecoder::new_with_decoder(&mut cursor, frame_decoder)
+        .map_err(|e| Corruption::Zstd(format!("{e:?}")))?;
+    let mut body = Vec::new();
+    // raw_len + 1로 유계 읽기: 출력 할당·CPU가 선언값에 묶인다
+    let mut limited = decoder.take(raw + 1);
+    limited
+        .read_to_end(&mut body)
+        .map_err(|e| Corruption::Zstd(format!("{e:?}")))?;
+    if body.len() as u64 != raw {
+        return Err(Corruption::RawLenMismatch {
+            declared: raw,
+            actual: body.len() as u64,
+        }
+        .into());
+    }
+    // 정확 소비: 프레임 뒤 잉여 바이트/추가 프레임은 체크섬이 유효해도 거부 —
+    // 동일 payload의 복수 표기(밀수 채널)를 봉쇄
+    drop(limited);
+    if !cursor.is_empty() {
+        return Err(Corruption::TrailingBytes {
+            remaining: cursor.len(),
+        }
+        .into());
+    }
+    Ok(body)
 }
 
 pub fn unwrap(bytes: &[u8]) -> CodecResult<Envelope> {
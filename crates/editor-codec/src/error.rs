@@ -99,6 +99,9 @@ pub enum Corruption {
 
     #[error("changesets share first dot {dot} but differ")]
     DivergentDuplicate { dot: editor_crdt::Dot },
+
+    #[error("changeset hash section lists {actual} hashes for {expected} changesets")]
+    ChangesetHashCount { expected: u64, actual: u64 },
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
@@ -160,6 +163,12 @@ pub enum EncodeInvariant {
 
     #[error("alias run violates creation-boundary/domain invariants")]
     InvalidAliasOp,
+
+    #[error("stored envelope body does not decode to the declared body")]
+    StoredBodyMismatch,
+
+    #[error("{hashes} content hashes supplied for {changesets} changesets")]
+    ChangesetHashCount { changesets: usize, hashes: usize },
 }
 
 pub type CodecResult<T> = Result<T, CodecError>;
@@ -1,3 +1,5 @@
+use serde::{Deserialize, Serialize};
+
 use crate::error::{CodecResult, Corruption};
 use crate::primitives::read_len_prefixed;
 use crate::varint::{read_varint, write_varint};
@@ -5,16 +7,49 @@ use crate::varint::{read_varint, write_varint};
 /// 계약: 내부에 preamble-상대 Dot 인코딩이 있을 수 있으므로 **원본 preamble 컨텍스트
 /// 안에서만** 재방출 가능 — 다른 번들로의 이식 금지. 구조적 봉인은 번들 계층(Plan 4)이
 /// 소유 스코프 타입으로 강제한다.
-#[derive(Debug, Clone, PartialEq, Eq, Default)]
-pub struct UnknownTail(pub Vec<u8>);
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct UnknownTail(#[serde(with = "hex_bytes")] pub Vec<u8>);
 
 /// 계약: `UnknownTail`과 동일 — 원본 preamble 컨텍스트 밖으로 이식 금지.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct UnknownPayload {
     pub tag: u64,
+    #[serde(with = "hex_bytes")]
     pub bytes: Vec<u8>,
 }
 
+/// 보존 바이트의 사람용 표기(소문자 hex) — JSON 덤프가 불투명 바이트를 무손실로 왕복.
+pub(crate) mod hex_bytes {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(bytes: &[u8], s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_str(&hex::encode(bytes))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Vec<u8>, D::Error> {
+        let text = String::deserialize(d)?;
+        hex::decode(text).map_err(serde::de::Error::custom)
+    }
+
+    pub mod option {
+        use serde::{Deserialize, Deserializer, Serializer};
+
+        pub fn serialize<S: Serializer>(bytes: &Option<Vec<u8>>, s: S) -> Result<S::Ok, S::Error> {
+            match bytes {
+                Some(bytes) => super::serialize(bytes, s),
+                None => s.serialize_none(),
+            }
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Option<Vec<u8>>, D::Error> {
+            Option::<String>::deserialize(d)?
+                .map(|text| hex::decode(text).map_err(serde::de::Error::custom))
+                .transpose()
+        }
+    }
+}
+
 pub fn write_frame(
     out: &mut Vec<u8>,
     f: impl FnOnce(&mut Vec<u8>) -> CodecResult<()>,
@@ -13,7 +13,8 @@
 //!   node type·dot-앵커 오버레이 op·새 item 종류(1-슬롯 계약 하).
 //! - 예 → required feature bit 의무: 위치 산술 개입 seq op(예: Move),
 //!   replay 규칙 변경, baseline/epoch.
-//! - optional bit는 관측용 자유.
+//! - optional bit는 관측용 자유. 단 body에 섹션을 덧붙이는 bit
+//!   (`OPTIONAL_CHANGESET_HASHES`)는 구 리더가 거부하므로 방출이 opt-in이다.
 //! - 새 attr(및 그 payload 값 타입)는 Dot-free 폐쇄 안에 있어야 한다 — attr 바이트의
 //!   ctx-독립(무손실 런타임 캐리어·자유 재인코딩)이 이 성질에 기댄다. 스키마 테스트
 //!   attr_type_universe_is_dot_free가 기계 강제한다.
@@ -28,6 +29,8 @@ pub mod bundle;
 pub mod consolidate;
 pub mod convert;
 pub mod ctx;
+pub mod digest;
+pub mod dump;
 pub mod durable;
 pub mod envelope;
 pub mod error;
@@ -45,6 +48,8 @@ pub use bundle::{
 pub use consolidate::{Consolidation, consolidate_stream};
 pub use convert::{
     Decoded, ReencodableChangesets, changesets_contain_unknown, decode_changeset_stream,
-    decode_changesets, encode_changesets,
+    decode_changesets, encode_changesets, encode_changesets_hashed,
 };
+pub use digest::DurableDigest;
+pub use dump::{EnvelopeDump, StreamFault, dump_stream, load_stream, validate_stream};
 pub use error::{CodecError, CodecResult, Corruption, EncodeInvariant, Fenced};
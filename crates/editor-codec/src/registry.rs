@@ -11,6 +11,7 @@ pub fn all_type_schemas() -> Vec<TypeSchema> {
         DurableHorizontalRuleVariant::schema(),
         DurableLayoutMode::schema(),
         DurableTableBorderStyle::schema(),
+        DurableTextDirection::schema(),
         DurableModifier::schema(),
         DurableModifierKind::schema(),
         DurableAttr::schema(),
@@ -1,6 +1,7 @@
 use editor_codec_macros::Durable;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Durable)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Durable, Serialize, Deserialize)]
 #[durable(closed)]
 pub enum DurableBias {
     #[durable(n(0))]
@@ -9,7 +10,7 @@ pub enum DurableBias {
     After,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Durable)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Durable, Serialize, Deserialize)]
 #[durable(frozen)]
 pub struct DurableAnchor {
     pub id: editor_crdt::Dot,
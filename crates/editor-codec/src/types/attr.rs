@@ -1,4 +1,5 @@
 use editor_codec_macros::Durable;
+use serde::{Deserialize, Serialize};
 
 use crate::framing::UnknownPayload;
 use crate::types::values::{
@@ -6,7 +7,7 @@ use crate::types::values::{
     DurableLayoutMode, DurableTableBorderStyle,
 };
 
-#[derive(Debug, Clone, PartialEq, Eq, Durable)]
+#[derive(Debug, Clone, PartialEq, Eq, Durable, Serialize, Deserialize)]
 #[durable(open)]
 pub enum DurableAttr {
     #[durable(n(0))]
@@ -1,10 +1,11 @@
 use editor_codec_macros::Durable;
 use editor_crdt::Dot;
+use serde::{Deserialize, Serialize};
 
 use crate::framing::{UnknownPayload, UnknownTail};
 use crate::types::attr::DurableAttr;
 
-#[derive(Debug, Clone, PartialEq, Eq, Durable)]
+#[derive(Debug, Clone, PartialEq, Eq, Durable, Serialize, Deserialize)]
 #[durable(open)]
 pub enum DurableNodeType {
     #[durable(n(0))]
@@ -51,6 +52,8 @@ pub enum DurableNodeType {
     PageBreak,
     #[durable(n(21))]
     Tab,
+    #[durable(n(22))]
+    ColumnBreak,
     #[durable(unknown)]
     Unknown(UnknownPayload),
 }
@@ -79,13 +82,14 @@ impl DurableNodeType {
             | DurableNodeType::HardBreak
             | DurableNodeType::HorizontalRule
             | DurableNodeType::PageBreak
-            | DurableNodeType::Tab => false,
+            | DurableNodeType::Tab
+            | DurableNodeType::ColumnBreak => false,
             DurableNodeType::Unknown(_) => true,
         }
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Durable)]
+#[derive(Debug, Clone, PartialEq, Eq, Durable, Serialize, Deserialize)]
 #[durable(open)]
 pub enum DurableItem {
     #[durable(n(0))]
@@ -12,5 +12,5 @@ pub use modifier::{DurableModifier, DurableModifierKind};
 pub use op::{DurableAliasRun, DurableOp};
 pub use values::{
     DurableAlignment, DurableBlockquoteVariant, DurableCalloutVariant,
-    DurableHorizontalRuleVariant, DurableLayoutMode, DurableTableBorderStyle,
+    DurableHorizontalRuleVariant, DurableLayoutMode, DurableTableBorderStyle, DurableTextDirection,
 };
@@ -1,9 +1,10 @@
 use editor_codec_macros::Durable;
+use serde::{Deserialize, Serialize};
 
 use crate::framing::UnknownPayload;
-use crate::types::values::DurableAlignment;
+use crate::types::values::{DurableAlignment, DurableTextDirection};
 
-#[derive(Debug, Clone, PartialEq, Eq, Durable)]
+#[derive(Debug, Clone, PartialEq, Eq, Durable, Serialize, Deserialize)]
 #[durable(open)]
 pub enum DurableModifier {
     #[durable(n(0))]
@@ -50,6 +51,9 @@ pub enum DurableModifier {
     #[durable(n(15))]
     #[durable(frozen)]
     Alignment(DurableAlignment),
+    #[durable(n(16))]
+    #[durable(frozen)]
+    Direction(DurableTextDirection),
     #[durable(unknown)]
     Unknown(UnknownPayload),
 }
@@ -73,12 +77,13 @@ impl DurableModifier {
             | DurableModifier::BlockGap(_)
             | DurableModifier::ParagraphIndent(_) => false,
             DurableModifier::Alignment(a) => a.contains_ctx_unknown(),
+            DurableModifier::Direction(d) => d.contains_ctx_unknown(),
             DurableModifier::Unknown(_) => true,
         }
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Durable)]
+#[derive(Debug, Clone, PartialEq, Eq, Durable, Serialize, Deserialize)]
 #[durable(open)]
 pub enum DurableModifierKind {
     #[durable(n(0))]
@@ -113,6 +118,8 @@ pub enum DurableModifierKind {
     ParagraphIndent,
     #[durable(n(15))]
     Alignment,
+    #[durable(n(16))]
+    Direction,
     #[durable(unknown)]
     Unknown(UnknownPayload),
 }
@@ -135,7 +142,8 @@ impl DurableModifierKind {
             | DurableModifierKind::LineHeight
             | DurableModifierKind::BlockGap
             | DurableModifierKind::ParagraphIndent
-            | DurableModifierKind::Alignment => false,
+            | DurableModifierKind::Alignment
+            | DurableModifierKind::Direction => false,
             DurableModifierKind::Unknown(_) => true,
         }
     }
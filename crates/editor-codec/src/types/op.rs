@@ -1,5 +1,6 @@
 use editor_codec_macros::Durable;
 use editor_crdt::Dot;
+use serde::{Deserialize, Serialize};
 
 use crate::framing::{UnknownPayload, UnknownTail};
 use crate::types::anchor::DurableAnchor;
@@ -7,7 +8,7 @@ use crate::types::attr::DurableAttr;
 use crate::types::item::DurableItem;
 use crate::types::modifier::{DurableModifier, DurableModifierKind};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Durable)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Durable, Serialize, Deserialize)]
 #[durable(frozen)]
 pub struct DurableAliasRun {
     pub old_start: Dot,
@@ -15,7 +16,7 @@ pub struct DurableAliasRun {
     pub new_start: Dot,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Durable)]
+#[derive(Debug, Clone, PartialEq, Eq, Durable, Serialize, Deserialize)]
 #[durable(open)]
 pub enum DurableOp {
     #[durable(n(0))]
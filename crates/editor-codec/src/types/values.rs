@@ -1,8 +1,9 @@
 use editor_codec_macros::Durable;
+use serde::{Deserialize, Serialize};
 
 use crate::framing::UnknownPayload;
 
-#[derive(Debug, Clone, PartialEq, Eq, Durable)]
+#[derive(Debug, Clone, PartialEq, Eq, Durable, Serialize, Deserialize)]
 #[durable(open)]
 pub enum DurableLayoutMode {
     #[durable(n(0))]
@@ -13,6 +14,10 @@ pub enum DurableLayoutMode {
         page_margin_bottom: u32,
         page_margin_left: u32,
         page_margin_right: u32,
+        #[durable(default = "1")]
+        columns: u32,
+        #[durable(default)]
+        column_gap: u32,
         tail: crate::framing::UnknownTail,
     },
     #[durable(n(1))]
@@ -20,6 +25,15 @@ pub enum DurableLayoutMode {
         max_width: u32,
         tail: crate::framing::UnknownTail,
     },
+    #[durable(n(2))]
+    Manuscript {
+        columns: u32,
+        rows: u32,
+        cell_size: u32,
+        row_gap: u32,
+        page_margin: u32,
+        tail: crate::framing::UnknownTail,
+    },
     #[durable(unknown)]
     Unknown(UnknownPayload),
 }
@@ -29,12 +43,13 @@ impl DurableLayoutMode {
         match self {
             DurableLayoutMode::Paginated { tail, .. } => !tail.0.is_empty(),
             DurableLayoutMode::Continuous { tail, .. } => !tail.0.is_empty(),
+            DurableLayoutMode::Manuscript { tail, .. } => !tail.0.is_empty(),
             DurableLayoutMode::Unknown(_) => true,
         }
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Durable)]
+#[derive(Debug, Clone, PartialEq, Eq, Durable, Serialize, Deserialize)]
 #[durable(open)]
 pub enum DurableBlockquoteVariant {
     #[durable(n(0))]
@@ -61,7 +76,7 @@ impl DurableBlockquoteVariant {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Durable)]
+#[derive(Debug, Clone, PartialEq, Eq, Durable, Serialize, Deserialize)]
 #[durable(open)]
 pub enum DurableCalloutVariant {
     #[durable(n(0))]
@@ -88,7 +103,7 @@ impl DurableCalloutVariant {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Durable)]
+#[derive(Debug, Clone, PartialEq, Eq, Durable, Serialize, Deserialize)]
 #[durable(open)]
 pub enum DurableHorizontalRuleVariant {
     #[durable(n(0))]
@@ -130,7 +145,7 @@ impl DurableHorizontalRuleVariant {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Durable)]
+#[derive(Debug, Clone, PartialEq, Eq, Durable, Serialize, Deserialize)]
 #[durable(open)]
 pub enum DurableTableBorderStyle {
     #[durable(n(0))]
@@ -157,7 +172,7 @@ impl DurableTableBorderStyle {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Durable)]
+#[derive(Debug, Clone, PartialEq, Eq, Durable, Serialize, Deserialize)]
 #[durable(open)]
 pub enum DurableAlignment {
     #[durable(n(0))]
@@ -183,3 +198,27 @@ impl DurableAlignment {
         }
     }
 }
+
+#[derive(Debug, Clone, PartialEq, Eq, Durable, Serialize, Deserialize)]
+#[durable(open)]
+pub enum DurableTextDirection {
+    #[durable(n(0))]
+    Auto,
+    #[durable(n(1))]
+    Ltr,
+    #[durable(n(2))]
+    Rtl,
+    #[durable(unknown)]
+    Unknown(UnknownPayload),
+}
+
+impl DurableTextDirection {
+    pub fn contains_ctx_unknown(&self) -> bool {
+        match self {
+            DurableTextDirection::Auto | DurableTextDirection::Ltr | DurableTextDirection::Rtl => {
+                false
+            }
+            DurableTextDirection::Unknown(_) => true,
+        }
+    }
+}
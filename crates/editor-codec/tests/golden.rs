@@ -223,6 +223,8 @@ fn corpus() -> Vec<Fixture> {
                 page_margin_bottom: 40,
                 page_margin_left: 30,
                 page_margin_right: 30,
+                columns: 1,
+                column_gap: 0,
                 tail: UnknownTail(Vec::new()),
             }),
             redecode::<DurableLayoutMode>,
@@ -235,6 +237,18 @@ fn corpus() -> Vec<Fixture> {
             }),
             redecode::<DurableLayoutMode>,
         ),
+        (
+            "DurableLayoutMode::Manuscript",
+            bytes(&DurableLayoutMode::Manuscript {
+                columns: 20,
+                rows: 10,
+                cell_size: 32,
+                row_gap: 12,
+                page_margin: 48,
+                tail: UnknownTail(Vec::new()),
+            }),
+            redecode::<DurableLayoutMode>,
+        ),
         (
             "DurableLayoutMode::Unknown",
             bytes(&DurableLayoutMode::Unknown(UnknownPayload {
@@ -272,6 +286,30 @@ fn corpus() -> Vec<Fixture> {
             })),
             redecode::<DurableTableBorderStyle>,
         ),
+        // ----- DurableTextDirection (open) -----
+        (
+            "DurableTextDirection::Auto",
+            bytes(&DurableTextDirection::Auto),
+            redecode::<DurableTextDirection>,
+        ),
+        (
+            "DurableTextDirection::Ltr",
+            bytes(&DurableTextDirection::Ltr),
+            redecode::<DurableTextDirection>,
+        ),
+        (
+            "DurableTextDirection::Rtl",
+            bytes(&DurableTextDirection::Rtl),
+            redecode::<DurableTextDirection>,
+        ),
+        (
+            "DurableTextDirection::Unknown",
+            bytes(&DurableTextDirection::Unknown(UnknownPayload {
+                tag: 89,
+                bytes: vec![25, 26],
+            })),
+            redecode::<DurableTextDirection>,
+        ),
         // ----- DurableModifier (open) -----
         (
             "DurableModifier::Bold",
@@ -353,6 +391,11 @@ fn corpus() -> Vec<Fixture> {
             bytes(&DurableModifier::Alignment(DurableAlignment::Center)),
             redecode::<DurableModifier>,
         ),
+        (
+            "DurableModifier::Direction",
+            bytes(&DurableModifier::Direction(DurableTextDirection::Rtl)),
+            redecode::<DurableModifier>,
+        ),
         (
             "DurableModifier::Unknown",
             bytes(&DurableModifier::Unknown(UnknownPayload {
@@ -442,6 +485,11 @@ fn corpus() -> Vec<Fixture> {
             bytes(&DurableModifierKind::Alignment),
             redecode::<DurableModifierKind>,
         ),
+        (
+            "DurableModifierKind::Direction",
+            bytes(&DurableModifierKind::Direction),
+            redecode::<DurableModifierKind>,
+        ),
         (
             "DurableModifierKind::Unknown",
             bytes(&DurableModifierKind::Unknown(UnknownPayload {
@@ -648,6 +696,11 @@ fn corpus() -> Vec<Fixture> {
             bytes(&DurableNodeType::Tab),
             redecode::<DurableNodeType>,
         ),
+        (
+            "DurableNodeType::ColumnBreak",
+            bytes(&DurableNodeType::ColumnBreak),
+            redecode::<DurableNodeType>,
+        ),
         (
             "DurableNodeType::Unknown",
             bytes(&DurableNodeType::Unknown(UnknownPayload {
@@ -882,3 +935,21 @@ fn golden_round_trips() {
         assert_eq!(redecode(&bytes), bytes, "{label} decode-reencode 항등 실패");
     }
 }
+
+#[test]
+fn pre_column_paginated_layout_decodes_as_single_column() {
+    // 컬럼 필드 추가 이전 v1 바이트 — 누락된 후행 필드는 기본값으로 채워진다.
+    let v1 = [0x00, 0x08, 0xa0, 0x06, 0xb0, 0x09, 0x28, 0x28, 0x1e, 0x1e];
+    let mut slice = &v1[..];
+    let decoded = DurableLayoutMode::decode(&dec(), &mut slice).unwrap();
+    assert!(slice.is_empty());
+    assert!(matches!(
+        decoded,
+        DurableLayoutMode::Paginated {
+            page_width: 800,
+            columns: 1,
+            column_gap: 0,
+            ..
+        }
+    ));
+}
@@ -19,7 +19,10 @@ fn envelope_lock() -> EnvelopeLock {
             ("snapshot".to_owned(), 2),
         ]),
         required_features: std::collections::BTreeMap::new(),
-        optional_features: std::collections::BTreeMap::new(),
+        optional_features: std::collections::BTreeMap::from([(
+            "changeset-hashes".to_owned(),
+            envelope::OPTIONAL_CHANGESET_HASHES,
+        )]),
     }
 }
 
@@ -1,10 +1,12 @@
-use editor_model::{ChildView, NodeType};
+use editor_model::ChildView;
 use editor_state::{Position, Selection};
 use editor_transaction::Transaction;
 
 use crate::helpers::remove_atom_leaf;
 use crate::{CommandError, CommandResult};
 
+/// Removes the paragraph's terminal break (page or column) when the caret sits
+/// right before it or right after it.
 pub fn delete_page_break_forward(tr: &mut Transaction) -> CommandResult {
     let Some(selection) = tr.selection() else {
         return Ok(false);
@@ -15,29 +17,29 @@ pub fn delete_page_break_forward(tr: &mut Transaction) -> CommandResult {
 
     let pos = selection.head;
 
-    let (children_count, last_is_page_break) = {
+    let (children_count, last_is_terminal_break) = {
         let view = tr.state().view();
         let node = view
             .node(pos.node)
             .ok_or(CommandError::NodeNotFound(pos.node))?;
-        let last_is_page_break = matches!(
+        let last_is_terminal_break = matches!(
             node.last_child(),
-            Some(ChildView::Leaf(l)) if l.node_type() == NodeType::PageBreak
+            Some(ChildView::Leaf(l)) if l.node_type().is_terminal_break()
         );
-        (node.children().count(), last_is_page_break)
+        (node.children().count(), last_is_terminal_break)
     };
 
-    if !last_is_page_break {
+    if !last_is_terminal_break {
         return Ok(false);
     }
-    let page_break_index = children_count - 1;
+    let break_index = children_count - 1;
 
     if pos.offset + 1 == children_count {
-        remove_atom_leaf(tr, pos.node, page_break_index)?;
+        remove_atom_leaf(tr, pos.node, break_index)?;
         Ok(true)
     } else if pos.offset == children_count {
         let new_offset = pos.offset - 1;
-        remove_atom_leaf(tr, pos.node, page_break_index)?;
+        remove_atom_leaf(tr, pos.node, break_index)?;
         tr.set_selection(Some(Selection::collapsed(Position {
             node: pos.node,
             offset: new_offset,
@@ -80,6 +82,30 @@ mod tests {
         assert_state_eq!(&actual, &expected);
     }
 
+    #[test]
+    fn at_text_end_before_column_break_removes_marker() {
+        let (initial, _t1) = state! {
+            doc {
+                root {
+                    t1: paragraph { text("a") column_break }
+                    paragraph { text("b") }
+                }
+            }
+            selection: (t1, 1)
+        };
+        let (actual, ..) = transact!(initial, |tr| delete_page_break_forward(&mut tr));
+        let (expected, _t1) = state! {
+            doc {
+                root {
+                    t1: paragraph { text("a") }
+                    paragraph { text("b") }
+                }
+            }
+            selection: (t1, 1)
+        };
+        assert_state_eq!(&actual, &expected);
+    }
+
     #[test]
     fn at_paragraph_offset_before_page_break_removes_marker() {
         let (initial, ..) = state! {
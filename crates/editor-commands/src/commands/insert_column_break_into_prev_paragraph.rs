@@ -0,0 +1,80 @@
+use editor_model::{PlainColumnBreakNode, PlainNode};
+use editor_transaction::Transaction;
+
+use crate::CommandResult;
+use crate::helpers::insert_terminal_break_into_prev_paragraph;
+
+pub fn insert_column_break_into_prev_paragraph(tr: &mut Transaction) -> CommandResult {
+    insert_terminal_break_into_prev_paragraph(
+        tr,
+        PlainNode::ColumnBreak(PlainColumnBreakNode::default()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use editor_macros::state;
+
+    use super::*;
+    use crate::test_utils::*;
+
+    #[test]
+    fn inserts_into_prev_paragraph_with_text() {
+        let (initial, ..) = state! {
+            doc {
+                root {
+                    paragraph { text("hello") }
+                    p1: paragraph { text("world") }
+                }
+            }
+            selection: (p1, 0)
+        };
+        let (actual, ..) = transact!(initial, |tr| insert_column_break_into_prev_paragraph(
+            &mut tr
+        ));
+        let (expected, ..) = state! {
+            doc {
+                root {
+                    paragraph { text("hello") column_break {} }
+                    p1: paragraph { text("world") }
+                }
+            }
+            selection: (p1, 0)
+        };
+        assert_state_eq!(&actual, &expected);
+    }
+
+    #[test]
+    fn prev_already_has_page_break_returns_false() {
+        let (initial, ..) = state! {
+            doc {
+                root {
+                    paragraph { text("hello") page_break {} }
+                    p1: paragraph { text("world") }
+                }
+            }
+            selection: (p1, 0)
+        };
+        transact_fail!(initial, |tr| insert_column_break_into_prev_paragraph(
+            &mut tr
+        ));
+    }
+
+    #[test]
+    fn current_paragraph_not_root_child_returns_false() {
+        let (initial, ..) = state! {
+            doc {
+                root {
+                    blockquote {
+                        paragraph { text("a") }
+                        p1: paragraph { text("b") }
+                    }
+                }
+            }
+            selection: (p1, 0)
+        };
+        transact_fail!(initial, |tr| insert_column_break_into_prev_paragraph(
+            &mut tr
+        ));
+    }
+}
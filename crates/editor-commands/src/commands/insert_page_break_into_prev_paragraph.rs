@@ -1,53 +1,14 @@
-use editor_model::{ChildView, Node};
+use editor_model::{PlainNode, PlainPageBreakNode};
 use editor_transaction::Transaction;
 
-use crate::helpers::{
-    find_ancestor_textblock, insert_terminal_page_break_into_root_paragraph, prev_sibling,
-};
-use crate::{CommandError, CommandResult};
+use crate::CommandResult;
+use crate::helpers::insert_terminal_break_into_prev_paragraph;
 
 pub fn insert_page_break_into_prev_paragraph(tr: &mut Transaction) -> CommandResult {
-    let Some(selection) = tr.selection() else {
-        return Ok(false);
-    };
-    if selection.anchor != selection.head {
-        return Ok(false);
-    }
-    let pos = selection.head;
-
-    let prev_id = {
-        let view = tr.state().view();
-        let Some(paragraph_id) = find_ancestor_textblock(&view, pos.node) else {
-            return Ok(false);
-        };
-        let paragraph = view
-            .node(paragraph_id)
-            .ok_or(CommandError::NodeNotFound(paragraph_id))?;
-
-        if !matches!(paragraph.node(), Node::Paragraph(_)) {
-            return Ok(false);
-        }
-        if paragraph
-            .parent()
-            .is_none_or(|parent| parent.id() != view.root().unwrap().id())
-        {
-            return Ok(false);
-        }
-
-        paragraph
-            .parent()
-            .ok_or(CommandError::NoParent(paragraph_id))?;
-        let prev = match prev_sibling(&paragraph) {
-            Some(ChildView::Block(prev)) => prev,
-            _ => return Ok(false),
-        };
-        if !matches!(prev.node(), Node::Paragraph(_)) {
-            return Ok(false);
-        }
-        prev.id()
-    };
-
-    insert_terminal_page_break_into_root_paragraph(tr, prev_id)
+    insert_terminal_break_into_prev_paragraph(
+        tr,
+        PlainNode::PageBreak(PlainPageBreakNode::default()),
+    )
 }
 
 #[cfg(test)]
@@ -41,7 +41,7 @@ pub fn join_paragraph_backward(tr: &mut Transaction) -> CommandResult {
         let raw_child_count = prev.children().count();
         let has_trailing_page_break = matches!(
             prev.last_child(),
-            Some(ChildView::Leaf(l)) if l.node_type() == NodeType::PageBreak
+            Some(ChildView::Leaf(l)) if l.node_type().is_terminal_break()
         );
         let page_break_index = has_trailing_page_break.then(|| raw_child_count - 1);
         let prev_child_count = raw_child_count - usize::from(has_trailing_page_break);
@@ -42,7 +42,7 @@ pub fn join_paragraph_forward(tr: &mut Transaction) -> CommandResult {
         }
         let has_trailing_page_break = matches!(
             node.last_child(),
-            Some(ChildView::Leaf(l)) if l.node_type() == NodeType::PageBreak
+            Some(ChildView::Leaf(l)) if l.node_type().is_terminal_break()
         );
         let page_break_index = has_trailing_page_break.then(|| child_count - 1);
         (pos.node, page_break_index)
@@ -66,7 +66,7 @@ pub fn lift_paragraph_forward(tr: &mut Transaction) -> CommandResult {
 
         let last_is_pb = matches!(
             node.last_child(),
-            Some(ChildView::Leaf(l)) if l.node_type() == NodeType::PageBreak
+            Some(ChildView::Leaf(l)) if l.node_type().is_terminal_break()
         );
 
         (
@@ -98,7 +98,7 @@ pub fn lift_paragraph_forward(tr: &mut Transaction) -> CommandResult {
             view.node(paragraph_id).and_then(|target| {
                 let count = target.children().count();
                 match target.last_child() {
-                    Some(ChildView::Leaf(l)) if l.node_type() == NodeType::PageBreak => {
+                    Some(ChildView::Leaf(l)) if l.node_type().is_terminal_break() => {
                         Some(count - 1)
                     }
                     _ => None,
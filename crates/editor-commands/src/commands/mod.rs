@@ -17,6 +17,7 @@ mod edit_modifier;
 mod edit_modifier_in_selection;
 mod ensure_paragraph;
 mod fill_cell_rect_with_slice;
+mod insert_column_break_into_prev_paragraph;
 mod insert_fragment;
 mod insert_hard_break;
 mod insert_page_break_into_prev_paragraph;
@@ -52,6 +53,7 @@ mod move_paragraph_backward_into_prev_list;
 mod move_table_axis;
 mod normalize_selected_blocks_in_blockquote;
 mod normalize_selected_blocks_in_callout;
+mod normalize_typography;
 mod paste_cells_into_cell_rect;
 mod replace_range_with_text;
 mod replace_selection_with_text;
@@ -115,6 +117,7 @@ pub use edit_modifier::edit_modifier;
 pub use edit_modifier_in_selection::edit_modifier_in_selection;
 pub use ensure_paragraph::ensure_paragraph;
 pub(crate) use fill_cell_rect_with_slice::apply_cell_fill_plan;
+pub use insert_column_break_into_prev_paragraph::insert_column_break_into_prev_paragraph;
 pub use insert_fragment::insert_fragment;
 pub use insert_hard_break::insert_hard_break;
 pub use insert_page_break_into_prev_paragraph::insert_page_break_into_prev_paragraph;
@@ -152,6 +155,7 @@ pub use move_paragraph_backward_into_prev_list::move_paragraph_backward_into_pre
 pub use move_table_axis::move_table_axis;
 pub use normalize_selected_blocks_in_blockquote::normalize_selected_blocks_in_blockquote;
 pub use normalize_selected_blocks_in_callout::normalize_selected_blocks_in_callout;
+pub use normalize_typography::{QuoteLocale, normalize_typography};
 pub(crate) use paste_cells_into_cell_rect::apply_table_grid_plan;
 pub use replace_range_with_text::replace_range_with_text;
 pub use replace_selection_with_text::replace_selection_with_text;
@@ -36,7 +36,7 @@ pub fn move_next_paragraph_forward_into_list(tr: &mut Transaction) -> CommandRes
             .ok_or(CommandError::NoParent(source.id()))?
             .id();
         let trailing_page_break_offset = match source.last_child() {
-            Some(ChildView::Leaf(leaf)) if leaf.node_type() == NodeType::PageBreak => {
+            Some(ChildView::Leaf(leaf)) if leaf.node_type().is_terminal_break() => {
                 Some(source.children().count() - 1)
             }
             _ => None,
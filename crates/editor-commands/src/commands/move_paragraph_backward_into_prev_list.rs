@@ -42,7 +42,7 @@ pub fn move_paragraph_backward_into_prev_list(tr: &mut Transaction) -> CommandRe
             .ok_or(CommandError::NoParent(source.id()))?
             .id();
         let trailing_page_break_offset = match source.last_child() {
-            Some(ChildView::Leaf(leaf)) if leaf.node_type() == NodeType::PageBreak => {
+            Some(ChildView::Leaf(leaf)) if leaf.node_type().is_terminal_break() => {
                 Some(source.children().count() - 1)
             }
             _ => None,
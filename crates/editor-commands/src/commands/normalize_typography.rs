@@ -0,0 +1,488 @@
+use editor_crdt::Dot;
+use editor_model::{ChildView, NodeView};
+use editor_resource::{Resource, TextReplacementRule};
+use editor_state::{Position, Selection, blocks_in_range, replacement_paint};
+use editor_transaction::Transaction;
+
+use super::try_text_replacement::{
+    apply_empty_replacement_carry, char_boundary_at_or_after, match_rule,
+};
+use crate::CommandError;
+use crate::helpers::{apply_inline_modifiers, child_leaf_dots};
+
+/// How [`normalize_typography`] pairs straight quotes into curly ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuoteLocale {
+    /// A quote after a space or opening bracket opens; any other closes.
+    /// `'` inside a word or before a decade (`'90s`) is an apostrophe.
+    English,
+    /// Quotes alternate open/close, since Korean attaches particles right
+    /// after a closing quote (`“안녕”이라고`). `'` inside a Latin word is
+    /// still an apostrophe.
+    Korean,
+}
+
+/// Replays the text replacement rules and smart-quote pairing over the
+/// selection, or over the whole document with `whole_document`, as if each
+/// run of text had just been typed. Replacements keep the modifiers of the
+/// text they replace. Returns the number of replacements made.
+pub fn normalize_typography(
+    tr: &mut Transaction,
+    resource: &Resource,
+    locale: QuoteLocale,
+    whole_document: bool,
+) -> Result<usize, CommandError> {
+    let selection = tr.selection();
+    let windows = {
+        let view = tr.view();
+        let mut windows = Vec::new();
+        if whole_document {
+            if let Some(root) = view.root() {
+                collect_windows(&root, &mut windows);
+            }
+        } else {
+            let Some(selection) = selection.filter(|s| !s.is_collapsed()) else {
+                return Ok(0);
+            };
+            let Some(resolved) = selection.resolve(&view) else {
+                return Ok(0);
+            };
+            let (from, to) = (resolved.from().position(), resolved.to().position());
+            for block in blocks_in_range(&resolved) {
+                if !block.spec().is_textblock() {
+                    continue;
+                }
+                let start = if block.id() == from.node {
+                    from.offset
+                } else {
+                    0
+                };
+                let end = if block.id() == to.node {
+                    to.offset
+                } else {
+                    block.child_count()
+                };
+                windows.push((block.id(), start..end));
+            }
+        }
+        windows
+    };
+
+    let rules = resource.text_replacement_rules();
+    let mut replacements = 0;
+    let mut edits: Vec<(Dot, Vec<Edit>)> = Vec::new();
+    {
+        let view = tr.view();
+        for (block, window) in windows {
+            let Some(node) = view.node(block) else {
+                continue;
+            };
+            let mut block_edits = Vec::new();
+            for (run_start, run) in text_runs(&node, window) {
+                let (run_edits, count) = replay(&run, rules, locale);
+                replacements += count;
+                block_edits.extend(run_edits.into_iter().map(|edit| Edit {
+                    start: run_start + edit.start,
+                    end: run_start + edit.end,
+                    text: edit.text,
+                }));
+            }
+            if !block_edits.is_empty() {
+                edits.push((block, block_edits));
+            }
+        }
+    }
+    if edits.is_empty() {
+        return Ok(0);
+    }
+
+    for (block, block_edits) in &edits {
+        // Back to front, so the offsets of the edits still to come hold.
+        for edit in block_edits.iter().rev() {
+            apply_edit(tr, *block, edit)?;
+        }
+    }
+
+    if let Some(selection) = selection {
+        let map = |position: Position| match edits.iter().find(|(b, _)| *b == position.node) {
+            Some((_, block_edits)) => Position {
+                offset: map_offset(position.offset, block_edits),
+                ..position
+            },
+            None => position,
+        };
+        tr.set_selection(Some(Selection::new(
+            map(selection.anchor),
+            map(selection.head),
+        )))?;
+    }
+    Ok(replacements)
+}
+
+/// Replaces child slots `start..end` of a block with `text`.
+struct Edit {
+    start: usize,
+    end: usize,
+    text: String,
+}
+
+fn collect_windows(node: &NodeView<'_>, out: &mut Vec<(Dot, std::ops::Range<usize>)>) {
+    if node.spec().is_textblock() {
+        out.push((node.id(), 0..node.child_count()));
+        return;
+    }
+    for child in node.child_blocks() {
+        collect_windows(&child, out);
+    }
+}
+
+/// Maximal runs of character leaves within `window`, with their first slot.
+/// Atoms, hard breaks included, end a run, as they reset the text a typed
+/// replacement can see.
+fn text_runs(node: &NodeView<'_>, window: std::ops::Range<usize>) -> Vec<(usize, Vec<char>)> {
+    let mut runs = Vec::new();
+    let mut current: Option<(usize, Vec<char>)> = None;
+    for (slot, child) in node.children().enumerate() {
+        if !window.contains(&slot) {
+            continue;
+        }
+        let ch = match child {
+            ChildView::Leaf(leaf) => leaf.as_char(),
+            ChildView::Block(_) => None,
+        };
+        match ch {
+            Some(ch) => current.get_or_insert_with(|| (slot, Vec::new())).1.push(ch),
+            None => runs.extend(current.take()),
+        }
+    }
+    runs.extend(current);
+    runs
+}
+
+/// Types `text` one character at a time through smart quotes and the
+/// replacement rules, then diffs the result against `text`. Returns the
+/// edits, in order, and how many replacements fired.
+fn replay(text: &[char], rules: &[TextReplacementRule], locale: QuoteLocale) -> (Vec<Edit>, usize) {
+    let mut typed = String::new();
+    // Index into `text` of each typed char; `None` for substituted ones.
+    let mut origins: Vec<Option<usize>> = Vec::new();
+    let mut quotes = OpenQuotes::default();
+    let mut count = 0;
+
+    for (i, &ch) in text.iter().enumerate() {
+        let prev = typed.chars().next_back();
+        let out = match ch {
+            '"' | '\'' => smart_quote(locale, ch, prev, &text[i + 1..], &mut quotes),
+            _ => {
+                quotes.observe(ch);
+                ch
+            }
+        };
+        origins.push((out == ch).then_some(i));
+        count += usize::from(out != ch);
+        typed.push(out);
+        count += apply_rules(rules, &mut typed, &mut origins);
+    }
+
+    let mut edits = Vec::new();
+    let mut next_source = 0;
+    let mut inserted = String::new();
+    for (ch, origin) in typed.chars().zip(&origins) {
+        match origin {
+            Some(source) => {
+                if *source != next_source || !inserted.is_empty() {
+                    edits.push(Edit {
+                        start: next_source,
+                        end: *source,
+                        text: std::mem::take(&mut inserted),
+                    });
+                }
+                next_source = source + 1;
+            }
+            None => inserted.push(ch),
+        }
+    }
+    if next_source != text.len() || !inserted.is_empty() {
+        edits.push(Edit {
+            start: next_source,
+            end: text.len(),
+            text: inserted,
+        });
+    }
+    (edits, count)
+}
+
+/// The rule loop of `try_text_replacement`, run against the typed prefix.
+fn apply_rules(
+    rules: &[TextReplacementRule],
+    typed: &mut String,
+    origins: &mut Vec<Option<usize>>,
+) -> usize {
+    let mut count = 0;
+    let mut search_start_byte = 0;
+    while search_start_byte < typed.len() {
+        search_start_byte = char_boundary_at_or_after(typed, search_start_byte);
+        let Some((start, matched, substitute, _)) = match_rule(rules, typed, search_start_byte)
+        else {
+            break;
+        };
+        // Hard breaks end a run, so a substitute cannot introduce one here.
+        if substitute == matched || substitute.contains('\n') {
+            break;
+        }
+        origins.truncate(typed[..start].chars().count());
+        typed.truncate(start);
+        origins.extend(substitute.chars().map(|_| None));
+        typed.push_str(&substitute);
+        count += 1;
+        search_start_byte = start + substitute.len();
+    }
+    count
+}
+
+#[derive(Default)]
+struct OpenQuotes {
+    double: bool,
+    single: bool,
+}
+
+impl OpenQuotes {
+    /// Tracks curly quotes already in the text, so alternation stays in step.
+    fn observe(&mut self, ch: char) {
+        match ch {
+            '\u{201C}' => self.double = true,
+            '\u{201D}' => self.double = false,
+            '\u{2018}' => self.single = true,
+            '\u{2019}' => self.single = false,
+            _ => {}
+        }
+    }
+}
+
+fn smart_quote(
+    locale: QuoteLocale,
+    quote: char,
+    prev: Option<char>,
+    rest: &[char],
+    open: &mut OpenQuotes,
+) -> char {
+    let next = rest.first().copied();
+    let (opening, closing) = if quote == '"' {
+        ('\u{201C}', '\u{201D}')
+    } else {
+        ('\u{2018}', '\u{2019}')
+    };
+    if quote == '\'' && is_apostrophe(locale, prev, next, rest) {
+        return closing;
+    }
+    let after_space = prev.is_none_or(opens_quote_after);
+    let is_open = if quote == '"' {
+        &mut open.double
+    } else {
+        &mut open.single
+    };
+    let opens = match locale {
+        QuoteLocale::English => after_space,
+        QuoteLocale::Korean => after_space || !*is_open,
+    };
+    *is_open = opens;
+    if opens { opening } else { closing }
+}
+
+fn is_apostrophe(
+    locale: QuoteLocale,
+    prev: Option<char>,
+    next: Option<char>,
+    rest: &[char],
+) -> bool {
+    let (Some(prev), Some(next)) = (prev, next) else {
+        return prev.is_none_or(char::is_whitespace) && is_decade(rest);
+    };
+    match locale {
+        QuoteLocale::English => {
+            (prev.is_alphanumeric() && next.is_alphabetic())
+                || (prev.is_whitespace() && is_decade(rest))
+        }
+        QuoteLocale::Korean => prev.is_ascii_alphanumeric() && next.is_ascii_alphabetic(),
+    }
+}
+
+/// `90s` or `90` after an elided century, but not a quoted `1984`.
+fn is_decade(rest: &[char]) -> bool {
+    matches!(
+        rest,
+        [a, b] | [a, b, 's' | ' ', ..] if a.is_ascii_digit() && b.is_ascii_digit()
+    )
+}
+
+fn opens_quote_after(c: char) -> bool {
+    c.is_whitespace()
+        || matches!(
+            c,
+            '(' | '[' | '{' | '<' | '/' | '-' | '\u{2013}' | '\u{2014}' | '\u{201C}' | '\u{2018}'
+        )
+}
+
+fn apply_edit(tr: &mut Transaction, block: Dot, edit: &Edit) -> Result<(), CommandError> {
+    // A pure insertion takes the paint of the character before it, as if typed.
+    let paint_from = if edit.start == edit.end {
+        edit.start.saturating_sub(1)
+    } else {
+        edit.start
+    };
+    let paint = replacement_paint(
+        &tr.state().projected,
+        Position::new(block, paint_from),
+        Position::new(block, edit.end),
+    );
+    if edit.end > edit.start {
+        tr.remove_text(block, edit.start, edit.end - edit.start)?;
+    }
+    if edit.text.is_empty() {
+        return apply_empty_replacement_carry(tr, block, paint.as_deref());
+    }
+    tr.insert_text(block, edit.start, &edit.text)?;
+    if let Some(paint) = paint {
+        let dots = child_leaf_dots(tr, block, edit.start, edit.text.chars().count());
+        apply_inline_modifiers(tr, &dots, &paint)?;
+    }
+    Ok(())
+}
+
+/// Where `offset` lands once `edits` (in order) are applied. An offset inside
+/// a replaced span is clamped into the replacement.
+fn map_offset(offset: usize, edits: &[Edit]) -> usize {
+    let mut mapped = offset;
+    for edit in edits {
+        let inserted = edit.text.chars().count();
+        if edit.end <= offset {
+            mapped = mapped + inserted - (edit.end - edit.start);
+        } else if edit.start < offset {
+            return mapped - (offset - edit.start) + (offset - edit.start).min(inserted);
+        } else {
+            break;
+        }
+    }
+    mapped
+}
+
+#[cfg(test)]
+mod tests {
+    use editor_macros::state;
+    use editor_resource::{RawTextReplacementRule, ResourceSource, prepare_text_replacement_rules};
+
+    use super::*;
+    use crate::test_utils::*;
+
+    fn resource_with_rules(rules: &[(&str, &str)]) -> Resource {
+        let mut source = ResourceSource::new_test();
+        let rules = rules
+            .iter()
+            .map(|(pattern, substitute)| RawTextReplacementRule {
+                id: (*pattern).into(),
+                match_pattern: (*pattern).into(),
+                substitute: (*substitute).into(),
+                regex: false,
+            })
+            .collect();
+        source.set_text_replacement_rules(prepare_text_replacement_rules(rules));
+        Resource::from_snapshot(source.snapshot())
+    }
+
+    fn typographic_rules() -> Resource {
+        resource_with_rules(&[("--", "\u{2014}"), ("...", "\u{2026}")])
+    }
+
+    fn normalize(
+        initial: editor_state::State,
+        resource: &Resource,
+        locale: QuoteLocale,
+        whole_document: bool,
+    ) -> (editor_state::State, usize) {
+        let mut tr = Transaction::new(&initial);
+        let count = normalize_typography(&mut tr, resource, locale, whole_document).unwrap();
+        (tr.commit().0, count)
+    }
+
+    #[test]
+    fn replaces_rules_and_pairs_english_quotes_across_document() {
+        let (initial, ..) = state! {
+            doc { root {
+                p1: paragraph { text("\"Don't\" -- he said...") }
+                p2: paragraph { text("back in the '90s") }
+            } }
+            selection: (p1, 0)
+        };
+        let (actual, count) = normalize(initial, &typographic_rules(), QuoteLocale::English, true);
+        let (expected, ..) = state! {
+            doc { root {
+                p1: paragraph { text("\u{201C}Don\u{2019}t\u{201D} \u{2014} he said\u{2026}") }
+                p2: paragraph { text("back in the \u{2019}90s") }
+            } }
+            selection: (p1, 0)
+        };
+        assert_state_eq!(&actual, &expected);
+        assert_eq!(count, 6);
+    }
+
+    #[test]
+    fn korean_quotes_close_before_particles() {
+        let (initial, ..) = state! {
+            doc { root { p1: paragraph { text("그는\"안녕\"이라고 '속으로' 말했다") } } }
+            selection: (p1, 0)
+        };
+        let (actual, count) = normalize(initial, &typographic_rules(), QuoteLocale::Korean, true);
+        let (expected, ..) = state! {
+            doc { root { p1: paragraph {
+                text("그는\u{201C}안녕\u{201D}이라고 \u{2018}속으로\u{2019} 말했다")
+            } } }
+            selection: (p1, 0)
+        };
+        assert_state_eq!(&actual, &expected);
+        assert_eq!(count, 4);
+    }
+
+    #[test]
+    fn replacements_keep_the_replaced_text_modifiers() {
+        let (initial, ..) = state! {
+            doc { root { p1: paragraph { text("a") text("--") [bold] text("b") } } }
+            selection: (p1, 4)
+        };
+        let (actual, count) = normalize(initial, &typographic_rules(), QuoteLocale::English, true);
+        let (expected, ..) = state! {
+            doc { root { p1: paragraph { text("a") text("\u{2014}") [bold] text("b") } } }
+            selection: (p1, 3)
+        };
+        assert_state_eq!(&actual, &expected);
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn selection_scope_leaves_text_outside_untouched() {
+        let (initial, ..) = state! {
+            doc { root { p1: paragraph { text("a--b c--d") } } }
+            selection: (p1, 5) -> (p1, 9)
+        };
+        let (actual, count) = normalize(initial, &typographic_rules(), QuoteLocale::English, false);
+        let (expected, ..) = state! {
+            doc { root { p1: paragraph { text("a--b c\u{2014}d") } } }
+            selection: (p1, 5) -> (p1, 8)
+        };
+        assert_state_eq!(&actual, &expected);
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn already_typographic_text_is_left_alone() {
+        let (initial, ..) = state! {
+            doc { root { p1: paragraph { text("\u{201C}hi\u{201D} \u{2014} ok") } } }
+            selection: (p1, 0) -> (p1, 9)
+        };
+        let mut tr = Transaction::new(&initial);
+        let count =
+            normalize_typography(&mut tr, &typographic_rules(), QuoteLocale::English, false)
+                .unwrap();
+        assert_eq!(count, 0);
+        assert!(!tr.doc_changed());
+    }
+}
@@ -106,7 +106,7 @@ pub fn try_text_replacement(tr: &mut Transaction, resource: &Resource) -> Comman
 // paragraph prefix — only the start of the match itself is limited.
 const BACKTRACKING_START_WINDOW: usize = 256;
 
-fn match_rule(
+pub(super) fn match_rule(
     rules: &[TextReplacementRule],
     text_before: &str,
     search_start_byte: usize,
@@ -155,7 +155,7 @@ fn match_rule(
     None
 }
 
-fn char_boundary_at_or_after(text: &str, mut byte: usize) -> usize {
+pub(super) fn char_boundary_at_or_after(text: &str, mut byte: usize) -> usize {
     while byte < text.len() && !text.is_char_boundary(byte) {
         byte += 1;
     }
@@ -336,7 +336,7 @@ fn insert_text_at_cursor(
     Ok(true)
 }
 
-fn apply_empty_replacement_carry(
+pub(super) fn apply_empty_replacement_carry(
     tr: &mut Transaction,
     block: Dot,
     paint: Option<&[Modifier]>,
@@ -511,7 +511,7 @@ fn virtual_merge_after_delete(
         let trailing = from
             .children
             .last()
-            .is_some_and(|child| child.node_type == NodeType::PageBreak)
+            .is_some_and(|child| child.node_type.is_terminal_break())
             .then(|| from.children.last().and_then(|child| child.id))
             .flatten();
         if trailing.is_some() {
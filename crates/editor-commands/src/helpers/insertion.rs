@@ -3,15 +3,15 @@ use std::collections::{BTreeMap, BTreeSet};
 use editor_common::StrExt;
 use editor_crdt::Dot;
 use editor_model::{
-    ChildView, Modifier, ModifierType, Node, NodeType, PlainHardBreakNode, PlainNode,
-    PlainPageBreakNode, PlainTabNode, PlainTextNode, Subtree,
+    ChildView, Modifier, ModifierType, Node, PlainHardBreakNode, PlainNode, PlainTabNode,
+    PlainTextNode, Subtree,
 };
 use editor_state::{
     Affinity, PendingModifiers, Position, Selection, modifier_applies_to_textblock_child,
 };
 use editor_transaction::{Step, Transaction};
 
-use crate::helpers::resolve_effective_modifiers;
+use crate::helpers::{find_ancestor_textblock, prev_sibling, resolve_effective_modifiers};
 use crate::{CommandError, CommandResult};
 
 /// Remove the child at full child-slot `index` of `parent` (a block OR a
@@ -338,10 +338,15 @@ pub(crate) fn materialize_caret_block(tr: &mut Transaction) -> Result<(), Comman
     Ok(())
 }
 
-pub(crate) fn insert_terminal_page_break_into_root_paragraph(
+/// Append `leaf` (a terminal break — see `NodeType::is_terminal_break`) to the
+/// trailing slot of the Root-level paragraph `paragraph_id`. A paragraph that
+/// already ends in a terminal break is left untouched.
+pub(crate) fn insert_terminal_break_into_root_paragraph(
     tr: &mut Transaction,
     paragraph_id: Dot,
+    leaf: PlainNode,
 ) -> CommandResult {
+    debug_assert!(leaf.as_type().is_terminal_break());
     let insert_index = {
         let view = tr.state().view();
         let Some(paragraph) = view.node(paragraph_id) else {
@@ -357,21 +362,67 @@ pub(crate) fn insert_terminal_page_break_into_root_paragraph(
             return Ok(false);
         }
         if paragraph.children().any(
-            |child| matches!(child, ChildView::Leaf(leaf) if leaf.node_type() == NodeType::PageBreak),
+            |child| matches!(child, ChildView::Leaf(leaf) if leaf.node_type().is_terminal_break()),
         ) {
             return Ok(false);
         }
         paragraph.children().count()
     };
 
-    tr.insert_subtree(
-        paragraph_id,
-        insert_index,
-        Subtree::leaf(PlainNode::PageBreak(PlainPageBreakNode::default())),
-    )?;
+    tr.insert_subtree(paragraph_id, insert_index, Subtree::leaf(leaf))?;
     Ok(true)
 }
 
+/// Terminate the Root-level paragraph preceding the caret's paragraph with
+/// `leaf`. Runs after a split, so the caret sits at the head of the new
+/// paragraph and the break lands at the tail of the one before it.
+pub(crate) fn insert_terminal_break_into_prev_paragraph(
+    tr: &mut Transaction,
+    leaf: PlainNode,
+) -> CommandResult {
+    let Some(selection) = tr.selection() else {
+        return Ok(false);
+    };
+    if selection.anchor != selection.head {
+        return Ok(false);
+    }
+    let pos = selection.head;
+
+    let prev_id = {
+        let view = tr.state().view();
+        let Some(paragraph_id) = find_ancestor_textblock(&view, pos.node) else {
+            return Ok(false);
+        };
+        let paragraph = view
+            .node(paragraph_id)
+            .ok_or(CommandError::NodeNotFound(paragraph_id))?;
+
+        if !matches!(paragraph.node(), Node::Paragraph(_)) {
+            return Ok(false);
+        }
+        if paragraph
+            .parent()
+            .is_none_or(|parent| parent.id() != view.root().unwrap().id())
+        {
+            return Ok(false);
+        }
+
+        paragraph
+            .parent()
+            .ok_or(CommandError::NoParent(paragraph_id))?;
+        let prev = match prev_sibling(&paragraph) {
+            Some(ChildView::Block(prev)) => prev,
+            _ => return Ok(false),
+        };
+        if !matches!(prev.node(), Node::Paragraph(_)) {
+            return Ok(false);
+        }
+        prev.id()
+    };
+
+    insert_terminal_break_into_root_paragraph(tr, prev_id, leaf)
+}
+
 fn caret_paint(tr: &Transaction, block: Dot, offset: usize) -> Vec<Modifier> {
     let pending_modifiers = tr.pending_modifiers().clone();
     resolve_effective_modifiers(&tr.state().projected, block, offset, &pending_modifiers)
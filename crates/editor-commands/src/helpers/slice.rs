@@ -578,6 +578,7 @@ fn inline_output_node_types(fragments: &[Fragment]) -> Vec<NodeType> {
             PlainNode::HardBreak(_) => output.push(NodeType::HardBreak),
             PlainNode::Tab(_) => output.push(NodeType::Tab),
             PlainNode::PageBreak(_) => output.push(NodeType::PageBreak),
+            PlainNode::ColumnBreak(_) => output.push(NodeType::ColumnBreak),
             _ => {}
         }
     }
@@ -1792,7 +1793,7 @@ impl TextblockSplicePlan {
             }
         } else if nodes
             .last()
-            .is_some_and(|node| node.node_type == NodeType::PageBreak)
+            .is_some_and(|node| node.node_type.is_terminal_break())
         {
             SliceOutputRelation::AfterTerminalPageBreak
         } else {
@@ -1858,7 +1859,7 @@ fn inline_output_end_affinity(fragments: &[Fragment]) -> Affinity {
         .find_map(|fragment| match &fragment.node {
             PlainNode::Text(text) if !text.text.is_empty() => Some(Affinity::Upstream),
             PlainNode::HardBreak(_) | PlainNode::Tab(_) => Some(Affinity::Downstream),
-            PlainNode::PageBreak(_) => Some(Affinity::Upstream),
+            PlainNode::PageBreak(_) | PlainNode::ColumnBreak(_) => Some(Affinity::Upstream),
             _ => None,
         })
         .unwrap_or(Affinity::Downstream)
@@ -1974,7 +1975,7 @@ pub(crate) fn plan_textblock_splice_target(
             || blocks[0]
                 .children
                 .last()
-                .is_some_and(|child| child.node.as_type() == NodeType::PageBreak));
+                .is_some_and(|child| child.node.as_type().is_terminal_break()));
     let merge_end_emits = merge_end
         && blocks
             .last()
@@ -2111,7 +2112,7 @@ fn insert_blocks_in_textblock(
         let inline = first.children.to_vec();
         let insertable_inline = inline
             .last()
-            .is_some_and(|fragment| fragment.node.as_type() == NodeType::PageBreak)
+            .is_some_and(|fragment| fragment.node.as_type().is_terminal_break())
             .then(|| &inline[..inline.len() - 1])
             .unwrap_or(&inline);
         let position = position_at_end_of_block(tr, left_id)?;
@@ -2130,7 +2131,7 @@ fn insert_blocks_in_textblock(
                 .node(left_id)
                 .and_then(|paragraph| paragraph.last_child())
                 .and_then(|child| match child {
-                    ChildView::Leaf(leaf) if leaf.node_type() == NodeType::PageBreak => {
+                    ChildView::Leaf(leaf) if leaf.node_type().is_terminal_break() => {
                         Some(leaf.dot())
                     }
                     _ => None,
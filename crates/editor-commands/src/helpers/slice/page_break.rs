@@ -3,17 +3,17 @@ use editor_model::{Fragment, NodeType};
 use editor_transaction::Transaction;
 
 use crate::CommandResult;
-use crate::helpers::insert_terminal_page_break_into_root_paragraph;
+use crate::helpers::insert_terminal_break_into_root_paragraph;
 
 pub(super) fn insert_terminal_page_break_from_edge(
     tr: &mut Transaction,
     paragraph_id: Dot,
     fragments: &[Fragment],
 ) -> CommandResult {
-    if !fragments.last().is_some_and(is_page_break_fragment) {
+    let Some(terminal) = fragments.last().filter(|f| is_page_break_fragment(f)) else {
         return Ok(false);
-    }
-    insert_terminal_page_break_into_root_paragraph(tr, paragraph_id)
+    };
+    insert_terminal_break_into_root_paragraph(tr, paragraph_id, terminal.node.clone())
 }
 
 pub(super) fn paragraph_ends_with_page_break(fragment: &Fragment) -> bool {
@@ -21,6 +21,8 @@ pub(super) fn paragraph_ends_with_page_break(fragment: &Fragment) -> bool {
         && fragment.children.last().is_some_and(is_page_break_fragment)
 }
 
+/// Page and column breaks share the paragraph's trailing slot, so the slice
+/// planner treats both as the same terminal output.
 fn is_page_break_fragment(fragment: &Fragment) -> bool {
-    fragment.node.as_type() == NodeType::PageBreak
+    fragment.node.as_type().is_terminal_break()
 }
@@ -193,7 +193,7 @@ fn try_place_slice_at_frontier(
                 .or_else(|| {
                     let has_meaningful_top_level = slice.content.iter().any(|fragment| {
                         is_insertable_inline_fragment(fragment)
-                            || fragment.node.as_type() == editor_model::NodeType::PageBreak
+                            || fragment.node.as_type().is_terminal_break()
                             || !editor_model::Schema::node_spec(fragment.node.as_type()).inline
                     });
                     if !has_meaningful_top_level {
@@ -300,7 +300,7 @@ fn plan_hoisted_block_insertion(
 ) -> Option<HoistedBlockInsertionPlan> {
     let has_meaningful_content = slice.content.iter().any(|fragment| {
         is_insertable_inline_fragment(fragment)
-            || fragment.node.as_type() == editor_model::NodeType::PageBreak
+            || fragment.node.as_type().is_terminal_break()
             || !editor_model::Schema::node_spec(fragment.node.as_type()).inline
     });
     if !has_meaningful_content {
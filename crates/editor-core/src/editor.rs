@@ -1,8 +1,10 @@
 use editor_clipboard::Slice;
 use editor_commands::CommandError;
-use editor_common::{HistoryTag, Movement, time::Duration};
+use editor_common::{HistoryTag, Movement, Rect, time::Duration};
 use editor_crdt::{Changeset, CrdtError, Dot, Op};
-use editor_model::{EditOp, ModifierState, ModifierType, NodeView, PlainDoc, PlainNode};
+use editor_model::{EditOp, ModifierState, ModifierType, NodeType, NodeView, PlainDoc, PlainNode};
+use editor_renderer::vector::svg::{SvgRegion, write_svg};
+use editor_renderer::vector::types::{VectorDocument, VectorLink, VectorOutlineItem, VectorPage};
 use editor_renderer::{Mark, MarkData, RenderSink, Renderer, damage::IRect};
 #[cfg(any(test, feature = "test-utils"))]
 use editor_resource::ThemeVariant;
@@ -16,7 +18,7 @@ use editor_transaction::{Effect, HistoryMeta, MergeKind, StepError, Transaction}
 use editor_view::{GapPhantom, PageRect, PendingOverlay, View, Viewport};
 use hashbrown::{HashMap, HashSet};
 use std::cell::OnceCell;
-use std::collections::VecDeque;
+use std::collections::{BTreeMap, VecDeque};
 use std::sync::{Arc, Mutex};
 use strum::IntoEnumIterator;
 
@@ -27,7 +29,11 @@ use crate::event::{EditorEvent, FontData};
 use crate::handle;
 use crate::ime::{Ime, ImeRange};
 use crate::message::*;
+use crate::recording::SessionRecorder;
+use crate::spellcheck::SpellcheckState;
 use crate::state_field::StateField;
+use crate::statistics::{StatisticsOptions, StatisticsState, WritingStatistics};
+use crate::telemetry::{Telemetry, TickTrace};
 use crate::tick::{
     CommandOutcome, CommandRejection, QueueEntry, RequestId, RequestOutcome, Revision, TickResult,
 };
@@ -236,6 +242,36 @@ pub(crate) fn nearest_insertable_flat_probe(
     nearest_insertable_flat(doc, total, offset)
 }
 
+/// Folds are the document's only titled structure, so they make up the export
+/// outline. Folds without a laid-out box (inside a collapsed fold) are skipped
+/// and their nested folds move up a level.
+fn fold_outline(view: &View, node: NodeView<'_>) -> Vec<VectorOutlineItem> {
+    let mut items = Vec::new();
+    for block in node.child_blocks() {
+        let children = fold_outline(view, block);
+        if block.node_type() != NodeType::Fold {
+            items.extend(children);
+            continue;
+        }
+        let Some(rect) = view.node_box_rects(&[block.id()]).into_iter().next() else {
+            items.extend(children);
+            continue;
+        };
+        let title = block
+            .child_blocks()
+            .find(|b| b.node_type() == NodeType::FoldTitle)
+            .map(|title| title.inline_text().trim().to_string())
+            .unwrap_or_default();
+        items.push(VectorOutlineItem {
+            title,
+            page_idx: rect.page_idx,
+            y: rect.rect.y,
+            children,
+        });
+    }
+    items
+}
+
 /// Snapshot of the transient (non-document) editor state recorded with each undo
 /// entry so undo/redo restore the caret.
 ///
@@ -347,11 +383,13 @@ pub struct Editor {
     pub(crate) renderer: Renderer,
     pub(crate) resource: Arc<Mutex<Resource>>,
     pub(crate) tracked_ranges: TrackedRangeRegistry,
+    pub(crate) spellcheck: SpellcheckState,
+    statistics: Mutex<StatisticsState>,
 
     // drag-and-drop state
     pub(crate) dnd: DndState,
 
-    focused: bool,
+    pub(crate) focused: bool,
     /// Monotonic counter bumped whenever a change can alter rendered page pixels
     /// beyond the selection overlay (doc edits, layout/reflow, font, theme).
     /// Selection-only changes intentionally do NOT bump it, so `page_render_signature`
@@ -393,6 +431,12 @@ pub struct Editor {
     // page rendered in the same epoch so a multi-page repaint resolves the
     // registry once instead of once per page.
     tracked_decoration_marks_cache: TrackedDecorationMarksCache,
+    recorder: Option<SessionRecorder>,
+    // `None` unless the host enabled tick tracing; every probe checks it first.
+    telemetry: Option<Box<Telemetry>>,
+    // Pins the undo-coalescing clock while replaying a recording so history
+    // merges the same way it did live.
+    pub(crate) replay_clock: Option<Instant>,
 }
 
 #[derive(Clone, Copy)]
@@ -410,6 +454,8 @@ impl Editor {
             renderer: Renderer::new(Arc::clone(&resource)),
             resource,
             tracked_ranges: TrackedRangeRegistry::new(),
+            spellcheck: SpellcheckState::default(),
+            statistics: Mutex::new(StatisticsState::default()),
             dnd: DndState::default(),
             focused: false,
             render_epoch: 0,
@@ -434,6 +480,9 @@ impl Editor {
             ime_window_anchor: None,
             selection_mark_rects_cache: Mutex::new(None),
             tracked_decoration_marks_cache: Mutex::new(None),
+            recorder: None,
+            telemetry: None,
+            replay_clock: None,
         }
     }
 
@@ -453,6 +502,12 @@ impl Editor {
         &mut self.tracked_ranges
     }
 
+    /// Dictionary corrections for the misspelling range `id`, best first.
+    /// Misspellings live in the [`crate::SPELLCHECK_GROUP`] tracked-range group.
+    pub fn spelling_suggestions(&self, id: &str) -> Vec<String> {
+        crate::spellcheck::suggestions(self, id)
+    }
+
     /// Dirty-block-scoped re-verification of text-sensitive tracked ranges.
     /// Content-only dirt re-checks just the ranges indexed under the dirtied
     /// blocks; structural or full dirt re-checks every sensitive range, since
@@ -539,6 +594,9 @@ impl Editor {
     }
 
     pub fn receive_remote_changeset(&mut self, changeset: Changeset<EditOp>) {
+        if let Some(recorder) = self.recorder.as_mut() {
+            recorder.remote(recorder.stamp(self.revision), &changeset);
+        }
         self.queue.push_back(QueueEntry::Remote(changeset));
     }
 
@@ -692,6 +750,23 @@ impl Editor {
         (doc, selection)
     }
 
+    /// Document and selection statistics, leaving out whatever `options`
+    /// excludes. Only blocks edited since the last call are re-segmented.
+    pub fn writing_statistics(
+        &self,
+        options: StatisticsOptions,
+    ) -> (WritingStatistics, WritingStatistics) {
+        let segmenters = Arc::clone(self.resource.lock().unwrap().segmenters());
+        let doc = crate::statistics::document(
+            &self.state,
+            &mut self.statistics.lock().unwrap(),
+            &segmenters,
+            options,
+        );
+        let selection = crate::statistics::selection(&self.state, &segmenters, options);
+        (doc, selection)
+    }
+
     pub fn interactive_hit_test(
         &self,
         page_idx: usize,
@@ -739,6 +814,14 @@ impl Editor {
         self.view.cursor_hit_rects(&self.state)
     }
 
+    pub fn accessibility_tree(&mut self) -> editor_view::AccessibilityTree {
+        self.view.accessibility_tree(&self.state)
+    }
+
+    pub fn accessibility_update(&mut self) -> editor_view::AccessibilityUpdate {
+        self.view.accessibility_update(&self.state)
+    }
+
     pub fn interactive_regions(&self) -> Vec<editor_view::InteractiveRegion> {
         self.view.interactive_regions(&self.state)
     }
@@ -1010,11 +1093,17 @@ impl Editor {
             .next_request_id
             .checked_add(1)
             .expect("request id overflow");
+        if let Some(recorder) = self.recorder.as_mut() {
+            recorder.request(recorder.stamp(self.revision), id, &messages);
+        }
         self.queue.push_back(QueueEntry::Request { id, messages });
         Ok(id)
     }
 
     pub fn receive_resource_update(&mut self, update: crate::ResourceUpdate) {
+        if let Some(recorder) = self.recorder.as_mut() {
+            recorder.resource(recorder.stamp(self.revision), update.notices());
+        }
         if let Some(QueueEntry::Resource(previous)) = self.queue.back_mut() {
             previous.coalesce(update);
         } else {
@@ -1027,7 +1116,12 @@ impl Editor {
         if item_count == 0 {
             return Ok(None);
         }
-        self.tick_prefix(item_count)
+        let stamp = self.recorder.as_ref().map(|r| r.stamp(self.revision));
+        let result = self.tick_prefix(item_count);
+        if let (Some(recorder), Some(stamp)) = (self.recorder.as_mut(), stamp) {
+            recorder.tick(stamp, None, result.as_ref().map(Option::as_ref));
+        }
+        result
     }
 
     pub fn tick_through(&mut self, request_id: RequestId) -> Result<TickResult, EditorError> {
@@ -1037,12 +1131,36 @@ impl Editor {
             .rposition(|entry| matches!(entry, QueueEntry::Request { id, .. } if *id == request_id))
             .map(|index| index + 1)
             .ok_or(EditorError::RequestNotQueued { request_id })?;
-        Ok(self
-            .tick_prefix(item_count)?
-            .expect("a prefix ending at a queued request must produce a tick result"))
+        let stamp = self.recorder.as_ref().map(|r| r.stamp(self.revision));
+        let result = self.tick_prefix(item_count).map(|result| {
+            result.expect("a prefix ending at a queued request must produce a tick result")
+        });
+        if let (Some(recorder), Some(stamp)) = (self.recorder.as_mut(), stamp) {
+            recorder.tick(stamp, Some(request_id), result.as_ref().map(Some));
+        }
+        result
     }
 
     fn tick_prefix(&mut self, item_count: usize) -> Result<Option<TickResult>, EditorError> {
+        if let Some(telemetry) = self.telemetry.as_mut() {
+            telemetry.begin();
+            // Drop layout work done between ticks (viewport changes, lazy queries).
+            self.view.take_layout_stats();
+        }
+        let result = self.tick_prefix_inner(item_count);
+        if let Some(telemetry) = self.telemetry.as_mut() {
+            match &result {
+                Ok(result) => telemetry.finish(
+                    result.as_ref().map(|r| r.revision),
+                    self.view.take_layout_stats(),
+                ),
+                Err(_) => telemetry.abandon(),
+            }
+        }
+        result
+    }
+
+    fn tick_prefix_inner(&mut self, item_count: usize) -> Result<Option<TickResult>, EditorError> {
         let mut entries: VecDeque<_> = self.queue.drain(..item_count).collect();
         let mut request_outcomes = Vec::new();
 
@@ -1058,10 +1176,51 @@ impl Editor {
         }))
     }
 
+    /// Starts keeping a trace of the last `capacity` ticks, replacing any
+    /// traces already collected.
+    pub fn enable_telemetry(&mut self, capacity: usize) {
+        self.telemetry = Some(Box::new(Telemetry::new(capacity)));
+        self.view.set_collect_layout_stats(true);
+    }
+
+    pub fn disable_telemetry(&mut self) {
+        self.telemetry = None;
+        self.view.set_collect_layout_stats(false);
+    }
+
+    pub fn is_telemetry_enabled(&self) -> bool {
+        self.telemetry.is_some()
+    }
+
+    /// Drains collected tick traces oldest first, with the number evicted
+    /// since the last drain. Empty while telemetry is disabled.
+    pub fn take_tick_traces(&mut self) -> (Vec<TickTrace>, u32) {
+        self.telemetry
+            .as_mut()
+            .map_or_else(Default::default, |telemetry| telemetry.drain())
+    }
+
     pub fn revision(&self) -> Revision {
         self.revision
     }
 
+    /// Starts logging every inbound call for `replay_session`, replacing any
+    /// recording in progress. See `recording` for what replays exactly.
+    pub fn start_recording(&mut self) -> Result<(), EditorError> {
+        self.recorder = Some(SessionRecorder::start(self)?);
+        Ok(())
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.recorder.is_some()
+    }
+
+    /// Stops recording and returns the encoded session.
+    pub fn finish_recording(&mut self) -> Result<Vec<u8>, EditorError> {
+        let recorder = self.recorder.take().ok_or(EditorError::NotRecording)?;
+        recorder.finish(self)
+    }
+
     fn process_tick_entries(
         &mut self,
         entries: &mut VecDeque<QueueEntry>,
@@ -1108,10 +1267,17 @@ impl Editor {
         }
 
         self.reconcile_pending_layout(&mut changes);
-        let tracked_ranges_went_stale = changes
-            .tracked_text_dirty
-            .take()
-            .is_some_and(|dirty| self.reverify_tracked_text(&dirty));
+        let tracked_text_dirty = changes.tracked_text_dirty.take();
+        let tracked_ranges_went_stale = tracked_text_dirty
+            .as_ref()
+            .is_some_and(|dirty| self.reverify_tracked_text(dirty));
+        let misspellings_changed = crate::spellcheck::recheck(self, tracked_text_dirty.as_ref());
+        if let Some(dirty) = &tracked_text_dirty {
+            self.statistics.get_mut().unwrap().mark_dirty(dirty);
+        }
+        if misspellings_changed {
+            self.invalidate_render();
+        }
 
         let effects = std::mem::take(&mut self.pending_effects);
         if !effects.is_empty() {
@@ -1148,7 +1314,7 @@ impl Editor {
             fields.insert(StateField::Placeholder);
         }
 
-        if tracked_ranges_went_stale {
+        if tracked_ranges_went_stale || misspellings_changed {
             fields.insert(StateField::TrackedRanges);
         }
 
@@ -1229,9 +1395,16 @@ impl Editor {
         let mut ime_failed = false;
         let mut applied = false;
         while let Some(entry) = entries.pop_front() {
+            let span = self
+                .telemetry
+                .as_ref()
+                .and_then(|telemetry| telemetry.span_start())
+                .map(|start| (start, entry.span_kind()));
+            let mut count = 1;
             match entry {
                 QueueEntry::Request { id, messages } => {
                     applied = true;
+                    count = messages.len();
                     let mut command_outcomes = Vec::with_capacity(messages.len());
                     self.process_request_messages(
                         messages,
@@ -1258,6 +1431,7 @@ impl Editor {
                             batch.push(changeset);
                         }
                     }
+                    count = batch.len();
                     applied |= self.apply_remote_changesets(batch)?;
                 }
                 QueueEntry::SetDocument(plain) => {
@@ -1271,6 +1445,9 @@ impl Editor {
                     self.apply_template_fragment(template)?;
                 }
             }
+            if let (Some((start, kind)), Some(telemetry)) = (span, self.telemetry.as_mut()) {
+                telemetry.span_end(start, kind, count);
+            }
         }
         Ok((ime_failed, applied))
     }
@@ -1376,6 +1553,14 @@ impl Editor {
     /// Reconciles the retained layout with the current state. This may run before
     /// geometry-dependent commands, so irreversible tick-final work stays out.
     fn reconcile_pending_layout(&mut self, changes: &mut TickChanges) {
+        let started = self.telemetry.is_some().then(Instant::now);
+        self.reconcile_pending_layout_inner(changes);
+        if let (Some(started), Some(telemetry)) = (started, self.telemetry.as_mut()) {
+            telemetry.record_layout(started.elapsed());
+        }
+    }
+
+    fn reconcile_pending_layout_inner(&mut self, changes: &mut TickChanges) {
         crate::font::flush_font_loads(self);
 
         let layout_dirty = self.view.take_layout_dirty(&mut self.state);
@@ -1534,8 +1719,8 @@ impl Editor {
     /// - `focused` — the selection mark's color depends on it.
     /// - the selection mark rects on this page — the only mark that moves without
     ///   bumping the epoch (the drag hot path).
-    /// - `theme.variant()` / `font_generation()` — hashed directly since
-    ///   `set_theme_variant`/`set_fonts` mutate resource without bumping the epoch.
+    /// - `theme` / `font_generation()` — hashed directly since
+    ///   `set_theme`/`set_fonts` mutate resource without bumping the epoch.
     pub fn page_render_signature(&self, page_idx: u32) -> u64 {
         use std::hash::{Hash, Hasher};
         let mut hasher = std::collections::hash_map::DefaultHasher::new();
@@ -1550,7 +1735,7 @@ impl Editor {
             }
         }
         let res = self.resource.lock().unwrap();
-        res.theme().variant().hash(&mut hasher);
+        res.theme().hash(&mut hasher);
         res.font_registry.font_generation().hash(&mut hasher);
         drop(res);
         hasher.finish()
@@ -1651,6 +1836,114 @@ impl Editor {
             .export_page_vector(&doc, &self.view, page_idx as usize, scale_factor)
     }
 
+    /// Every page as a glyph-mode vector display list at layout scale, plus the
+    /// link areas and a fold-title outline, for whole-document exporters.
+    pub fn export_vector_document(&mut self) -> VectorDocument {
+        let doc = self.state.view();
+        let pages = (0..self.view.pages().len())
+            .map(|idx| {
+                self.renderer
+                    .export_page_glyph_vector(&doc, &self.view, idx, 1.0)
+            })
+            .collect();
+        let outline = doc
+            .root()
+            .map(|root| fold_outline(&self.view, root))
+            .unwrap_or_default();
+        VectorDocument {
+            pages,
+            links: self.vector_links(),
+            outline,
+        }
+    }
+
+    /// One page as a standalone SVG document with text drawn as outlines.
+    pub fn export_page_svg(&mut self, page_idx: u32) -> String {
+        let doc = self.state.view();
+        let page_idx = page_idx as usize;
+        let page = self
+            .renderer
+            .export_page_outline_vector(&doc, &self.view, page_idx, 1.0);
+        let links: Vec<VectorLink> = self
+            .vector_links()
+            .into_iter()
+            .filter(|link| link.page_idx == page_idx)
+            .collect();
+        let rect = Rect::from_xywh(0.0, 0.0, page.width, page.height);
+        write_svg(&[SvgRegion {
+            page: &page,
+            links: &links,
+            rect,
+        }])
+    }
+
+    /// The area the selection covers on each page it touches, stacked into one
+    /// SVG document. `None` when nothing is selected.
+    pub fn export_selection_svg(&mut self) -> Option<String> {
+        let mut bounds: BTreeMap<usize, (f32, f32, f32, f32)> = BTreeMap::new();
+        for PageRect { page_idx, rect, .. } in self.selection_hit_rects() {
+            if rect.width <= 0.0 || rect.height <= 0.0 {
+                continue;
+            }
+            let entry =
+                bounds
+                    .entry(page_idx)
+                    .or_insert((rect.x, rect.y, rect.right(), rect.bottom()));
+            entry.0 = entry.0.min(rect.x);
+            entry.1 = entry.1.min(rect.y);
+            entry.2 = entry.2.max(rect.right());
+            entry.3 = entry.3.max(rect.bottom());
+        }
+        if bounds.is_empty() {
+            return None;
+        }
+
+        let doc = self.state.view();
+        let links = self.vector_links();
+        let pages: Vec<(VectorPage, Vec<VectorLink>, Rect)> = bounds
+            .into_iter()
+            .map(|(page_idx, (left, top, right, bottom))| {
+                let page = self
+                    .renderer
+                    .export_page_outline_vector(&doc, &self.view, page_idx, 1.0);
+                let page_links = links
+                    .iter()
+                    .filter(|link| link.page_idx == page_idx)
+                    .cloned()
+                    .collect();
+                (
+                    page,
+                    page_links,
+                    Rect::from_xywh(left, top, right - left, bottom - top),
+                )
+            })
+            .collect();
+        let regions: Vec<SvgRegion<'_>> = pages
+            .iter()
+            .map(|(page, links, rect)| SvgRegion {
+                page,
+                links,
+                rect: *rect,
+            })
+            .collect();
+        Some(write_svg(&regions))
+    }
+
+    fn vector_links(&self) -> Vec<VectorLink> {
+        self.view
+            .link_rects()
+            .into_iter()
+            .flat_map(|link| {
+                let href = link.href;
+                link.rects.into_iter().map(move |rect| VectorLink {
+                    page_idx: link.page_idx,
+                    rect,
+                    href: href.clone(),
+                })
+            })
+            .collect()
+    }
+
     fn process_message(
         &mut self,
         msg: Message,
@@ -1685,6 +1978,7 @@ impl Editor {
             Message::History { op } => handle::handle_history_op(self, op)?,
             Message::System { event } => handle::handle_system_event(self, event)?,
             Message::TrackedRange { op } => handle::handle_tracked_range_op(self, op)?,
+            Message::Typography { op } => handle::handle_typography_op(self, op)?,
         }
         Ok(())
     }
@@ -1770,6 +2064,7 @@ impl Editor {
         // pre-transaction view.
         let undoable = !recorded.is_empty() || transient_fields_changed(&self.state, &state);
         let merge = typing_run(meta.merge, &self.state, &state);
+        let now = self.replay_clock.unwrap_or_else(Instant::now);
 
         match meta.history {
             HistoryMeta::Skip if undoable => self.undo_history.invalidate_last_tag(),
@@ -1781,7 +2076,7 @@ impl Editor {
                     transient: capture_transient(&self.state),
                     merge,
                 },
-                Instant::now(),
+                now,
             ),
             HistoryMeta::Tagged { tag } if undoable => self.undo_history.record(
                 UndoEntry {
@@ -1790,7 +2085,7 @@ impl Editor {
                     transient: capture_transient(&self.state),
                     merge: RecordMerge::Isolated,
                 },
-                Instant::now(),
+                now,
             ),
             _ => self.undo_history.clear_last_tag(),
         }
@@ -2155,6 +2450,9 @@ impl Editor {
 
     pub fn set_doc(&mut self, plain: PlainDoc) -> Result<(), EditorError> {
         ensure_plain_doc_root(&plain)?;
+        if let Some(recorder) = self.recorder.as_mut() {
+            recorder.set_document(recorder.stamp(self.revision), &plain);
+        }
         self.queue.push_back(QueueEntry::SetDocument(plain));
         Ok(())
     }
@@ -2176,6 +2474,9 @@ impl Editor {
 
     pub fn insert_template_fragment(&mut self, template: PlainDoc) -> Result<(), EditorError> {
         ensure_plain_doc_root(&template)?;
+        if let Some(recorder) = self.recorder.as_mut() {
+            recorder.insert_template(recorder.stamp(self.revision), &template);
+        }
         self.queue.push_back(QueueEntry::InsertTemplate(template));
         Ok(())
     }
@@ -2446,6 +2747,8 @@ impl Editor {
             renderer: Renderer::new(Arc::clone(&resource)),
             resource,
             tracked_ranges: TrackedRangeRegistry::new(),
+            spellcheck: SpellcheckState::default(),
+            statistics: Mutex::new(StatisticsState::default()),
             dnd: DndState::default(),
             focused: false,
             render_epoch: 0,
@@ -2470,6 +2773,9 @@ impl Editor {
             ime_window_anchor: None,
             selection_mark_rects_cache: Mutex::new(None),
             tracked_decoration_marks_cache: Mutex::new(None),
+            recorder: None,
+            telemetry: None,
+            replay_clock: None,
         };
         // Lay out the view once so the first `tick()` reconciles clean (matches the
         // production `run_initialize` path); otherwise every test's first tick would
@@ -2514,7 +2820,7 @@ impl Editor {
     }
 
     #[cfg(any(test, feature = "test-utils"))]
-    pub(crate) fn theme_variant(&self) -> ThemeVariant {
+    pub(crate) fn theme_variant(&self) -> Option<ThemeVariant> {
         self.resource.lock().unwrap().theme().variant()
     }
 }
@@ -6169,6 +6475,105 @@ mod tests {
         );
     }
 
+    #[test]
+    fn export_vector_document_collects_links_and_fold_outline() {
+        let (initial, outer, _p1) = state! {
+            doc {
+                root {
+                    outer: fold {
+                        fold_title { text(" Outer ") }
+                        fold_content {
+                            fold {
+                                fold_title { text("Inner") }
+                                fold_content { paragraph { text("body") } }
+                            }
+                        }
+                    }
+                    _p1: paragraph { text("site") [link(href: "https://a.com".to_string())] }
+                }
+            }
+            selection: none
+        };
+        let mut editor = Editor::new_test(initial);
+        editor.apply(Message::System {
+            event: crate::message::SystemEvent::Initialize,
+        });
+
+        let document = editor.export_vector_document();
+        assert_eq!(document.pages.len(), editor.view().pages().len());
+        assert!(
+            document
+                .links
+                .iter()
+                .any(|link| link.href == "https://a.com" && link.page_idx == 0)
+        );
+        assert_eq!(document.outline.len(), 1);
+        assert_eq!(document.outline[0].title, "Outer");
+        assert!(
+            document.outline[0].children.is_empty(),
+            "a collapsed fold hides its nested folds"
+        );
+
+        editor.set_fold_expanded(outer, true);
+        let document = editor.export_vector_document();
+        let titles: Vec<&str> = document.outline[0]
+            .children
+            .iter()
+            .map(|item| item.title.as_str())
+            .collect();
+        assert_eq!(titles, ["Inner"]);
+        assert!(document.outline[0].children[0].y > document.outline[0].y);
+    }
+
+    #[test]
+    fn export_page_svg_overlays_text_and_links() {
+        let (initial, _p1) = state! {
+            doc {
+                root {
+                    _p1: paragraph { text("site") [link(href: "https://a.com/?a&b".to_string())] }
+                }
+            }
+            selection: none
+        };
+        let mut editor = Editor::new_test(initial);
+        editor.apply(Message::System {
+            event: crate::message::SystemEvent::Initialize,
+        });
+
+        let svg = editor.export_page_svg(0);
+        assert!(svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg""#));
+        assert!(svg.contains(r#"<a href="https://a.com/?a&amp;b">"#));
+        assert!(svg.contains(">site</text>"));
+        assert_eq!(svg, editor.export_page_svg(0), "same input, same bytes");
+    }
+
+    #[test]
+    fn export_selection_svg_crops_to_the_selection() {
+        let (initial, _p1) = state! {
+            doc { root { p1: paragraph { text("hello world") } } }
+            selection: (p1, 0) -> (p1, 5)
+        };
+        let mut editor = Editor::new_test(initial);
+        editor.apply(Message::System {
+            event: crate::message::SystemEvent::Initialize,
+        });
+
+        let svg = editor.export_selection_svg().expect("a range is selected");
+        let page_width = editor.view().pages()[0].size.width;
+        assert!(svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg""#));
+        assert!(!svg.contains(&format!(r#"width="{page_width}""#)));
+
+        let (collapsed, _p1) = state! {
+            doc { root { p1: paragraph { text("hello") } } }
+            selection: (p1, 2)
+        };
+        let mut editor = Editor::new_test(collapsed);
+        editor.apply(Message::System {
+            event: crate::message::SystemEvent::Initialize,
+        });
+        assert!(editor.export_selection_svg().is_none());
+    }
+
     fn fold_editor_with_unit_selection() -> (Editor, editor_crdt::Dot, editor_crdt::Dot) {
         let (initial, root, fold_node, _p1) = state! {
             doc {
@@ -6676,6 +7081,8 @@ mod tests {
                         page_margin_bottom: 80,
                         page_margin_left: 60,
                         page_margin_right: 60,
+                        columns: 1,
+                        column_gap: 0,
                     }
                 ) {
                     p2: paragraph { text("Body") }
@@ -6701,6 +7108,8 @@ mod tests {
                 page_margin_bottom: 80,
                 page_margin_left: 60,
                 page_margin_right: 60,
+                columns: 1,
+                column_gap: 0,
             }
         );
     }
@@ -7434,6 +7843,8 @@ mod tests {
                         page_margin_bottom: 20,
                         page_margin_left: 20,
                         page_margin_right: 20,
+                        columns: 1,
+                        column_gap: 0,
                     },
                 }),
                 modifiers: root_font_modifiers(),
@@ -14,4 +14,16 @@ pub enum EditorError {
 
     #[error("request is not queued: {request_id:?}")]
     RequestNotQueued { request_id: crate::RequestId },
+
+    #[error("the editor is not recording")]
+    NotRecording,
+
+    #[error("session recording failed: {msg}")]
+    RecordingFailed { msg: String },
+
+    #[error("malformed session recording: {msg}")]
+    MalformedRecording { msg: String },
+
+    #[error("replay diverged at entry {entry}: {detail}")]
+    ReplayDiverged { entry: usize, detail: String },
 }
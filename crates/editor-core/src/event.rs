@@ -68,4 +68,9 @@ pub enum EditorEvent {
         request_id: String,
         node_ids: Vec<Dot>,
     },
+    /// Answers [`crate::TypographyOp::Normalize`], including when nothing
+    /// needed replacing.
+    TypographyNormalized {
+        replacements: u32,
+    },
 }
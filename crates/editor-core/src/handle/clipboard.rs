@@ -1,8 +1,11 @@
 use editor_clipboard::{PayloadSource, Slice};
 use editor_commands::{self as commands};
 use editor_common::HistoryTag;
+use editor_crdt::Dot;
+use editor_model::{ChildView, DocView, NodeType};
 use editor_state::{ResolvedPosition, ResolvedPositionFlatExt, Selection, StableSelection};
 use editor_transaction::HistoryMeta;
+use hashbrown::HashSet;
 
 use crate::editor::Editor;
 use crate::error::EditorError;
@@ -77,6 +80,36 @@ pub fn handle_clipboard_op(editor: &mut Editor, op: ClipboardOp) -> Result<(), E
             }
             Ok(())
         }
+        ClipboardOp::PasteDocx { request_id, data } => {
+            let import = {
+                let resource = editor.resource.lock().unwrap();
+                editor_clipboard::docx::from_docx(&data, &resource)
+            }
+            .map_err(|err| commands::CommandError::InvalidArgument(err.to_string()))?;
+            let before: HashSet<Dot> = image_ids(&editor.state().view()).into_iter().collect();
+            let mut pasted = false;
+            editor.transact_observable(|tr| {
+                pasted = commands::insert_slice(
+                    tr,
+                    import.slice,
+                    commands::types::SliceProvenance::Formatted,
+                )?;
+                Ok(())
+            })?;
+            if pasted && !import.attachments.is_empty() {
+                // Placeholders are the only images the paste creates, and the
+                // walk visits them in document order.
+                let node_ids = image_ids(&editor.state().view())
+                    .into_iter()
+                    .filter(|id| !before.contains(id))
+                    .collect();
+                editor.push_event(EditorEvent::AttachmentPlaceholdersInserted {
+                    request_id,
+                    node_ids,
+                });
+            }
+            Ok(())
+        }
         ClipboardOp::RepasteAsText => {
             let Some(HistoryTag::PasteHtml { plain_text, start }) = editor.last_history_tag()
             else {
@@ -174,6 +207,18 @@ pub fn handle_clipboard_op(editor: &mut Editor, op: ClipboardOp) -> Result<(), E
     }
 }
 
+fn image_ids(view: &DocView) -> Vec<Dot> {
+    let Some(root) = view.root() else {
+        return Vec::new();
+    };
+    root.descendants()
+        .filter_map(|child| match child {
+            ChildView::Leaf(leaf) if leaf.node_type() == NodeType::Image => Some(leaf.dot()),
+            _ => None,
+        })
+        .collect()
+}
+
 fn repaste_structural_as_text(editor: &mut Editor, plain_slice: Slice) -> Result<(), EditorError> {
     let Some(paste_entry) = editor.undo_history.last_entry().cloned() else {
         return Ok(());
@@ -262,6 +307,94 @@ mod tests {
         );
     }
 
+    fn docx(body: &str) -> Vec<u8> {
+        let mut zip = editor_zip::ZipWriter::new();
+        zip.deflated(
+            "_rels/.rels",
+            br#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+<Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="word/document.xml"/>
+</Relationships>"#,
+        );
+        zip.deflated(
+            "word/document.xml",
+            format!(
+                r#"<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"
+  xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships"
+  xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main"><w:body>{body}</w:body></w:document>"#
+            )
+            .as_bytes(),
+        );
+        zip.deflated(
+            "word/_rels/document.xml.rels",
+            br#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+<Relationship Id="rIdImage" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/image" Target="media/image1.png"/>
+</Relationships>"#,
+        );
+        zip.stored("word/media/image1.png", b"png");
+        zip.finish()
+    }
+
+    #[test]
+    fn paste_docx_reports_image_placeholders_in_document_order() {
+        let (state, existing) = state! {
+            doc { root {
+                p1: paragraph { text("hello") }
+                existing: image
+            } }
+            selection: (p1, 5)
+        };
+        let drawing =
+            r#"<w:p><w:r><w:drawing><a:blip r:embed="rIdImage"/></w:drawing></w:r></w:p>"#;
+        let mut editor = Editor::new_test(state);
+        let events = editor.apply(Message::Clipboard {
+            op: ClipboardOp::PasteDocx {
+                request_id: "docx-paste".into(),
+                data: docx(&format!(
+                    "<w:p><w:r><w:t>from word</w:t></w:r></w:p>{drawing}{drawing}"
+                )),
+            },
+        });
+
+        let view = editor.state().view();
+        let images = image_ids(&view);
+        assert_eq!(images.len(), 3);
+        assert_eq!(images.last(), Some(&existing));
+        let receipts: Vec<_> = events
+            .iter()
+            .filter_map(|event| match event {
+                EditorEvent::AttachmentPlaceholdersInserted {
+                    request_id,
+                    node_ids,
+                } if request_id == "docx-paste" => Some(node_ids.clone()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(receipts, vec![images[..2].to_vec()]);
+        assert!(
+            view.root()
+                .unwrap()
+                .child_blocks()
+                .any(|block| block.inline_text().contains("from word"))
+        );
+    }
+
+    #[test]
+    fn paste_docx_rejects_bytes_that_are_not_a_package() {
+        let (state, ..) = state! {
+            doc { root { p1: paragraph { text("hello") } } }
+            selection: (p1, 5)
+        };
+        assert_apply_preserves_state(
+            state,
+            Message::Clipboard {
+                op: ClipboardOp::PasteDocx {
+                    request_id: "docx-paste".into(),
+                    data: b"not a zip".to_vec(),
+                },
+            },
+        );
+    }
+
     #[test]
     fn deeply_nested_proprietary_metadata_reaches_no_fit_without_stack_overflow() {
         const CHILD_ENV: &str = "TYPIE_DEEP_SLICE_CLIPBOARD_CHILD";
@@ -116,6 +116,28 @@ pub fn handle_insertion_op(editor: &mut Editor, op: InsertionOp) -> Result<(), E
                     tr.clear_pending_format()?;
                 }
             }
+            InsertionOp::Break {
+                kind: Break::Column,
+            } => {
+                let applied = commands::chain!(
+                    tr,
+                    |tr| commands::first!(
+                        tr,
+                        commands::materialize_gap_paragraph(),
+                        commands::insert_paragraph_after_unit_selection(),
+                        |tr| commands::chain!(
+                            tr,
+                            commands::optional!(commands::ensure_paragraph()),
+                            commands::optional!(commands::delete_selection()),
+                        ),
+                    ),
+                    commands::split_root_paragraph(),
+                    commands::insert_column_break_into_prev_paragraph(),
+                )?;
+                if applied {
+                    tr.clear_pending_format()?;
+                }
+            }
             InsertionOp::Fragment { fragment } => {
                 commands::chain!(
                     tr,
@@ -839,6 +861,28 @@ mod tests {
         assert_state_eq!(editor.state(), &expected);
     }
 
+    #[test]
+    fn insert_break_column() {
+        let (state, ..) = state! {
+            doc { root { p1: paragraph { text("hello") } } }
+            selection: (p1, 3)
+        };
+        let mut editor = Editor::new_test(state);
+        editor.apply(Message::Insertion {
+            op: InsertionOp::Break {
+                kind: Break::Column,
+            },
+        });
+        let (expected, ..) = state! {
+            doc { root {
+                paragraph { text("hel") column_break {} }
+                p2: paragraph { text("lo") }
+            } }
+            selection: (p2, 0)
+        };
+        assert_state_eq!(editor.state(), &expected);
+    }
+
     #[test]
     fn insert_break_page_in_list_is_an_applied_noop() {
         let (state, ..) = state! {
@@ -16,6 +16,7 @@ mod selection;
 mod system;
 mod text_input;
 mod tracked_range;
+mod typography;
 mod view;
 
 pub use block::handle_block_op;
@@ -39,4 +40,5 @@ pub use selection::handle_selection_op;
 pub use system::handle_system_event;
 pub use text_input::handle_flat_ime_ops;
 pub use tracked_range::handle_tracked_range_op;
+pub use typography::handle_typography_op;
 pub use view::handle_view_op;
@@ -22,7 +22,7 @@ pub fn handle_system_event(editor: &mut Editor, event: SystemEvent) -> Result<()
             Ok(())
         }
 
-        SystemEvent::ThemeVariantChanged => {
+        SystemEvent::ThemeChanged => {
             editor.invalidate_render();
             Ok(())
         }
@@ -54,25 +54,41 @@ pub fn handle_system_event(editor: &mut Editor, event: SystemEvent) -> Result<()
 
         SystemEvent::FontsChanged => {
             editor.reresolve_fonts()?;
-            editor.view.clear_measure_cache();
-            let changed = editor.view.invalidate(&editor.state);
-            if changed {
-                editor.push_event(EditorEvent::StateChanged {
-                    fields: vec![
-                        StateField::Cursor,
-                        StateField::PageSizes,
-                        StateField::ExternalElements,
-                        StateField::TableOverlays,
-                        StateField::Placeholder,
-                    ],
-                });
-                editor.invalidate_render();
-            }
+            remeasure(editor);
+            Ok(())
+        }
+
+        SystemEvent::HyphenationChanged => {
+            remeasure(editor);
+            Ok(())
+        }
+
+        SystemEvent::SpellcheckChanged => {
+            editor.spellcheck.invalidate();
             Ok(())
         }
     }
 }
 
+/// Drops every measured line so the document is laid out again under the
+/// changed resources.
+fn remeasure(editor: &mut Editor) {
+    editor.view.clear_measure_cache();
+    let changed = editor.view.invalidate(&editor.state);
+    if changed {
+        editor.push_event(EditorEvent::StateChanged {
+            fields: vec![
+                StateField::Cursor,
+                StateField::PageSizes,
+                StateField::ExternalElements,
+                StateField::TableOverlays,
+                StateField::Placeholder,
+            ],
+        });
+        editor.invalidate_render();
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::sync::{Arc, Mutex};
@@ -194,7 +210,7 @@ mod tests {
 
         let mut editor = Editor::new_test(state);
         let events = editor.apply(Message::System {
-            event: SystemEvent::ThemeVariantChanged,
+            event: SystemEvent::ThemeChanged,
         });
 
         assert_eq!(events, vec![EditorEvent::RenderInvalidated]);
@@ -1509,6 +1525,8 @@ mod tests {
                         page_margin_bottom: 20,
                         page_margin_left: 20,
                         page_margin_right: 20,
+                        columns: 1,
+                        column_gap: 0,
                     }
                 ) [font_family("TestFont".to_string()), font_weight(400)] {
                     p1: paragraph { text("hello") }
@@ -0,0 +1,131 @@
+use std::sync::Arc;
+
+use editor_commands::{self as commands};
+
+use crate::editor::Editor;
+use crate::error::EditorError;
+use crate::event::EditorEvent;
+use crate::message::*;
+
+pub fn handle_typography_op(editor: &mut Editor, op: TypographyOp) -> Result<(), EditorError> {
+    match op {
+        TypographyOp::Normalize {
+            locale,
+            whole_document,
+        } => {
+            let resource = Arc::clone(&editor.resource);
+            let resource = resource.lock().unwrap();
+            let mut replacements = 0;
+            editor.transact_observable(|tr| {
+                replacements = commands::normalize_typography(
+                    tr,
+                    &resource,
+                    quote_locale(locale),
+                    whole_document,
+                )?;
+                Ok(())
+            })?;
+            editor.push_event(EditorEvent::TypographyNormalized {
+                replacements: replacements as u32,
+            });
+        }
+    }
+    Ok(())
+}
+
+fn quote_locale(locale: QuoteLocale) -> commands::QuoteLocale {
+    match locale {
+        QuoteLocale::English => commands::QuoteLocale::English,
+        QuoteLocale::Korean => commands::QuoteLocale::Korean,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use editor_macros::state;
+    use editor_resource::{
+        RawTextReplacementRule, Resource, ResourceSource, prepare_text_replacement_rules,
+    };
+
+    use super::*;
+
+    fn editor_with_dash_rule(state: editor_state::State) -> Editor {
+        let mut source = ResourceSource::new_test();
+        source
+            .set_text_replacement_rules(prepare_text_replacement_rules(vec![
+                RawTextReplacementRule {
+                    id: "dash".into(),
+                    match_pattern: "--".into(),
+                    substitute: "\u{2014}".into(),
+                    regex: false,
+                },
+            ]))
+            .expect("text replacement rules must change resources");
+        let resource = Arc::new(Mutex::new(Resource::from_snapshot(source.snapshot())));
+        Editor::new_test_with_resource(state, resource)
+    }
+
+    fn normalize(whole_document: bool) -> Message {
+        Message::Typography {
+            op: TypographyOp::Normalize {
+                locale: QuoteLocale::English,
+                whole_document,
+            },
+        }
+    }
+
+    fn replacements(events: &[EditorEvent]) -> Option<u32> {
+        events.iter().find_map(|event| match event {
+            EditorEvent::TypographyNormalized { replacements } => Some(*replacements),
+            _ => None,
+        })
+    }
+
+    fn text(editor: &Editor) -> Vec<String> {
+        let view = editor.state().view();
+        view.root()
+            .unwrap()
+            .child_blocks()
+            .map(|block| block.inline_text())
+            .collect()
+    }
+
+    #[test]
+    fn normalizes_whole_document_as_one_undo_step() {
+        let (initial, ..) = state! {
+            doc { root {
+                p1: paragraph { text("\"a\" -- b") }
+                p2: paragraph { text("it's c--d") }
+            } }
+            selection: (p1, 0)
+        };
+        let mut editor = editor_with_dash_rule(initial);
+
+        let events = editor.apply(normalize(true));
+        assert_eq!(replacements(&events), Some(5));
+        assert_eq!(
+            text(&editor),
+            ["\u{201C}a\u{201D} \u{2014} b", "it\u{2019}s c\u{2014}d"]
+        );
+
+        editor.apply(Message::History {
+            op: HistoryOp::Undo,
+        });
+        assert_eq!(text(&editor), ["\"a\" -- b", "it's c--d"]);
+    }
+
+    #[test]
+    fn reports_zero_without_a_history_entry() {
+        let (initial, ..) = state! {
+            doc { root { p1: paragraph { text("plain text") } } }
+            selection: (p1, 0) -> (p1, 5)
+        };
+        let mut editor = editor_with_dash_rule(initial);
+
+        let events = editor.apply(normalize(false));
+        assert_eq!(replacements(&events), Some(0));
+        assert!(!editor.undo_history.can_undo());
+    }
+}
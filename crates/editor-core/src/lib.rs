@@ -9,8 +9,13 @@ mod font;
 mod handle;
 mod ime;
 mod message;
+mod recording;
 mod search;
+mod spellcheck;
 mod state_field;
+mod statistics;
+mod telemetry;
+mod text_walk;
 mod tick;
 mod tracked_range;
 
@@ -30,7 +35,11 @@ pub use event::*;
 pub use handle::*;
 pub use ime::*;
 pub use message::*;
+pub use recording::{RECORDING_MAGIC, RECORDING_VERSION, ReplaySummary, replay_session};
 pub use search::find_matches;
+pub use spellcheck::SPELLCHECK_GROUP;
 pub use state_field::*;
+pub use statistics::{StatisticsOptions, WritingStatistics};
+pub use telemetry::{EditorTelemetry, FrameTrace, TickSpan, TickSpanKind, TickTrace, TraceRing};
 pub use tick::*;
 pub use tracked_range::*;
@@ -104,6 +104,7 @@ pub enum Break {
     Line,
     Paragraph,
     Page,
+    Column,
 }
 
 #[ffi]
@@ -311,7 +312,19 @@ pub enum ViewOp {
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum ClipboardOp {
-    Paste { html: Option<String>, text: String },
+    Paste {
+        html: Option<String>,
+        text: String,
+    },
+    /// A Word document on the clipboard. Its images are inserted as
+    /// placeholders and reported through `AttachmentPlaceholdersInserted`
+    /// under `request_id`, in the order `docx_attachments` returns their bytes.
+    PasteDocx {
+        request_id: String,
+        #[serde(with = "serde_bytes")]
+        #[cfg_attr(feature = "wasm", tsify(type = "Uint8Array"))]
+        data: Vec<u8>,
+    },
     RepasteAsText,
     Cut,
 }
@@ -353,7 +366,7 @@ pub enum SystemEvent {
         focused: bool,
     },
     #[ffi(skip)]
-    ThemeVariantChanged,
+    ThemeChanged,
     #[ffi(skip)]
     FontBaseLoaded {
         family: String,
@@ -376,6 +389,10 @@ pub enum SystemEvent {
     },
     #[ffi(skip)]
     FontsChanged,
+    #[ffi(skip)]
+    HyphenationChanged,
+    #[ffi(skip)]
+    SpellcheckChanged,
 }
 
 #[ffi]
@@ -471,6 +488,27 @@ pub enum TrackedRangeOp {
     },
 }
 
+#[ffi]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QuoteLocale {
+    English,
+    Korean,
+}
+
+#[ffi]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TypographyOp {
+    /// Runs the text replacement rules and smart quotes over the selection,
+    /// or the whole document, as one undoable edit.
+    Normalize {
+        locale: QuoteLocale,
+        #[serde(default)]
+        whole_document: bool,
+    },
+}
+
 #[ffi]
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
@@ -491,6 +529,7 @@ pub enum Message {
     History { op: HistoryOp },
     System { event: SystemEvent },
     TrackedRange { op: TrackedRangeOp },
+    Typography { op: TypographyOp },
 }
 
 #[cfg(test)]
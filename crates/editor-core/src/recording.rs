@@ -0,0 +1,406 @@
+//! Session recording and headless replay of the editor message loop.
+//!
+//! A recording is the magic and a version varint followed by length-prefixed
+//! frames: a header frame carrying the state
+//! the recording started from, then one frame per inbound call (`enqueue_request`,
+//! `receive_remote_changeset`, `receive_resource_update`, `set_doc`,
+//! `insert_template_fragment`, `tick`, `tick_through`) stamped with the
+//! revision it was made at and the time since recording started, and a final
+//! frame with the resulting heads, document and selection. Changesets travel in the
+//! bundle encoding; every other payload is JSON so recordings stay readable
+//! across message-enum growth.
+//!
+//! Replay rebuilds the starting state with the recorded actor, feeds the same
+//! calls to a fresh editor with its undo clock pinned to the recorded times,
+//! and fails with `ReplayDiverged` at the first tick whose result differs.
+//!
+//! Replay is exact only for recordings started on an editor with no undo
+//! history, tracked ranges or queued entries, since none of those are
+//! captured. Resource contents (fonts, dictionaries, themes) are not captured
+//! either: the replayer must supply the same resources, and resource updates
+//! replay their notices against its current snapshot.
+
+use editor_codec::primitives::{read_bool, read_len_prefixed, write_bool};
+use editor_codec::varint::{read_varint, write_varint};
+use editor_codec::{ReencodableChangesets, decode_changesets, encode_changesets};
+use editor_common::time::{Duration, Instant};
+use editor_crdt::{Changeset, Dot, OpGraph};
+use editor_macros::ffi;
+use editor_model::{EditOp, PlainDoc};
+use editor_resource::Resource;
+use editor_state::{ProjectedState, Selection, State};
+use editor_view::Viewport;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+
+use crate::editor::Editor;
+use crate::error::EditorError;
+use crate::message::{Message, SystemEvent};
+use crate::tick::{RequestId, ResourceUpdate, Revision, TickResult};
+
+pub const RECORDING_MAGIC: &[u8; 4] = b"ERSR";
+pub const RECORDING_VERSION: u64 = 1;
+
+const TAG_REQUEST: u64 = 0;
+const TAG_REMOTE: u64 = 1;
+const TAG_RESOURCE: u64 = 2;
+const TAG_SET_DOCUMENT: u64 = 3;
+const TAG_INSERT_TEMPLATE: u64 = 4;
+const TAG_TICK: u64 = 5;
+const TAG_FINISH: u64 = 6;
+
+#[ffi]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReplaySummary {
+    /// Inbound calls replayed, not counting the final state check.
+    pub entries: u32,
+    pub ticks: u32,
+    pub revision: Revision,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Header {
+    actor: u64,
+    viewport: Viewport,
+    selection: Option<Selection>,
+    focused: bool,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct FinalState {
+    heads: Vec<Dot>,
+    doc: PlainDoc,
+    selection: Option<Selection>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum TickOutcome {
+    Ticked { result: Option<TickResult> },
+    Failed { error: String },
+}
+
+impl TickOutcome {
+    fn of(result: Result<Option<&TickResult>, &EditorError>) -> Self {
+        match result {
+            Ok(result) => Self::Ticked {
+                result: result.cloned(),
+            },
+            Err(error) => Self::Failed {
+                error: error.to_string(),
+            },
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub(crate) struct Stamp {
+    revision: Revision,
+    elapsed: Duration,
+}
+
+pub(crate) struct SessionRecorder {
+    started: Instant,
+    out: Vec<u8>,
+    // The first encoding failure poisons the recording; it is reported by
+    // `finish` rather than by the inbound call that hit it.
+    error: Option<String>,
+}
+
+impl SessionRecorder {
+    pub(crate) fn start(editor: &Editor) -> Result<Self, EditorError> {
+        let state = editor.state();
+        let header = Header {
+            actor: state.graph().actor(),
+            viewport: *editor.view().viewport(),
+            selection: state.selection,
+            focused: editor.focused,
+        };
+        let changesets = ReencodableChangesets::from_local_ops(state.graph().changesets_as_vec());
+        let mut body = Vec::new();
+        write_json(&header, &mut body).map_err(|msg| EditorError::RecordingFailed { msg })?;
+        let bundle =
+            encode_changesets(changesets).map_err(|error| EditorError::RecordingFailed {
+                msg: error.to_string(),
+            })?;
+        write_bytes(&bundle, &mut body);
+        let mut out = RECORDING_MAGIC.to_vec();
+        write_varint(RECORDING_VERSION, &mut out);
+        write_bytes(&body, &mut out);
+        Ok(Self {
+            started: Instant::now(),
+            out,
+            error: None,
+        })
+    }
+
+    pub(crate) fn stamp(&self, revision: Revision) -> Stamp {
+        Stamp {
+            revision,
+            elapsed: self.started.elapsed(),
+        }
+    }
+
+    pub(crate) fn request(&mut self, stamp: Stamp, id: RequestId, messages: &[Message]) {
+        self.entry(stamp, TAG_REQUEST, |body| {
+            write_varint(id.value, body);
+            write_json(messages, body)
+        });
+    }
+
+    pub(crate) fn remote(&mut self, stamp: Stamp, changeset: &Changeset<EditOp>) {
+        let changesets = ReencodableChangesets::from_local_ops(vec![changeset.clone()]);
+        self.entry(stamp, TAG_REMOTE, |body| {
+            let bundle = encode_changesets(changesets).map_err(|error| error.to_string())?;
+            write_bytes(&bundle, body);
+            Ok(())
+        });
+    }
+
+    pub(crate) fn resource(&mut self, stamp: Stamp, notices: &[SystemEvent]) {
+        self.entry(stamp, TAG_RESOURCE, |body| write_json(notices, body));
+    }
+
+    pub(crate) fn set_document(&mut self, stamp: Stamp, plain: &PlainDoc) {
+        self.entry(stamp, TAG_SET_DOCUMENT, |body| write_json(plain, body));
+    }
+
+    pub(crate) fn insert_template(&mut self, stamp: Stamp, template: &PlainDoc) {
+        self.entry(stamp, TAG_INSERT_TEMPLATE, |body| {
+            write_json(template, body)
+        });
+    }
+
+    pub(crate) fn tick(
+        &mut self,
+        stamp: Stamp,
+        through: Option<RequestId>,
+        result: Result<Option<&TickResult>, &EditorError>,
+    ) {
+        let outcome = TickOutcome::of(result);
+        self.entry(stamp, TAG_TICK, |body| {
+            write_bool(through.is_some(), body);
+            if let Some(id) = through {
+                write_varint(id.value, body);
+            }
+            write_json(&outcome, body)
+        });
+    }
+
+    pub(crate) fn finish(mut self, editor: &Editor) -> Result<Vec<u8>, EditorError> {
+        let final_state = final_state(editor);
+        self.entry(self.stamp(editor.revision()), TAG_FINISH, |body| {
+            write_json(&final_state, body)
+        });
+        match self.error {
+            Some(msg) => Err(EditorError::RecordingFailed { msg }),
+            None => Ok(self.out),
+        }
+    }
+
+    fn entry(
+        &mut self,
+        stamp: Stamp,
+        tag: u64,
+        f: impl FnOnce(&mut Vec<u8>) -> Result<(), String>,
+    ) {
+        if self.error.is_some() {
+            return;
+        }
+        let mut body = Vec::new();
+        write_varint(tag, &mut body);
+        write_varint(stamp.revision.get(), &mut body);
+        write_varint(stamp.elapsed.as_micros() as u64, &mut body);
+        match f(&mut body) {
+            Ok(()) => write_bytes(&body, &mut self.out),
+            Err(msg) => self.error = Some(msg),
+        }
+    }
+}
+
+enum Entry {
+    Request {
+        id: RequestId,
+        messages: Vec<Message>,
+    },
+    Remote(Vec<Changeset<EditOp>>),
+    Resource(Vec<SystemEvent>),
+    SetDocument(PlainDoc),
+    InsertTemplate(PlainDoc),
+    Tick {
+        through: Option<RequestId>,
+        outcome: TickOutcome,
+    },
+    Finish(FinalState),
+}
+
+/// Replays `recording` against a fresh editor sharing `resource`, returning
+/// the replayed editor once every tick and the final state matched.
+pub fn replay_session(
+    recording: &[u8],
+    resource: Arc<Mutex<Resource>>,
+) -> Result<(Editor, ReplaySummary), EditorError> {
+    let mut input = recording;
+    let magic = take(&mut input, RECORDING_MAGIC.len())?;
+    if magic != RECORDING_MAGIC {
+        return Err(malformed("not a session recording"));
+    }
+    let version = read_varint(&mut input).map_err(malformed)?;
+    if version != RECORDING_VERSION {
+        return Err(malformed(format!(
+            "unsupported recording version {version}"
+        )));
+    }
+
+    let mut frame = read_len_prefixed(&mut input).map_err(malformed)?;
+    let header: Header = read_json(&mut frame)?;
+    let changesets = decode_changesets(read_len_prefixed(&mut frame).map_err(malformed)?)
+        .map_err(malformed)?
+        .into_graph_input();
+    let (graph, dropped) = OpGraph::with_actor(header.actor).receive_changesets_ordered(changesets);
+    if !dropped.is_empty() {
+        return Err(malformed("starting document has unresolved dependencies"));
+    }
+    let projected = ProjectedState::from_graph(graph).map_err(|e| malformed(format!("{e:?}")))?;
+    let state = State::new(projected, header.selection);
+
+    let mut editor = Editor::new(state, header.viewport, Arc::clone(&resource));
+    editor.focused = header.focused;
+    let base = Instant::now();
+    let mut summary = ReplaySummary::default();
+
+    loop {
+        if input.is_empty() {
+            return Err(malformed("recording ends without a final state"));
+        }
+        let index = summary.entries as usize;
+        let mut frame = read_len_prefixed(&mut input).map_err(malformed)?;
+        let tag = read_varint(&mut frame).map_err(malformed)?;
+        let revision = Revision {
+            value: read_varint(&mut frame).map_err(malformed)?,
+        };
+        let elapsed = Duration::from_micros(read_varint(&mut frame).map_err(malformed)?);
+        let entry = read_entry(tag, &mut frame)?;
+
+        diverged(index, "revision", &revision, &editor.revision())?;
+        editor.replay_clock = Some(base + elapsed);
+        match entry {
+            Entry::Request { id, messages } => {
+                let replayed = editor.enqueue_request(messages)?;
+                diverged(index, "request id", &id, &replayed)?;
+            }
+            Entry::Remote(changesets) => {
+                for changeset in changesets {
+                    editor.receive_remote_changeset(changeset);
+                }
+            }
+            Entry::Resource(notices) => {
+                let snapshot = Arc::clone(resource.lock().unwrap().snapshot());
+                editor.receive_resource_update(ResourceUpdate::new(snapshot, notices));
+            }
+            Entry::SetDocument(plain) => editor.set_doc(plain)?,
+            Entry::InsertTemplate(template) => editor.insert_template_fragment(template)?,
+            Entry::Tick { through, outcome } => {
+                let replayed = match through {
+                    None => TickOutcome::of(editor.tick().as_ref().map(Option::as_ref)),
+                    Some(id) => TickOutcome::of(editor.tick_through(id).as_ref().map(Some)),
+                };
+                diverged(index, "tick", &outcome, &replayed)?;
+                summary.ticks += 1;
+            }
+            Entry::Finish(expected) => {
+                if !input.is_empty() {
+                    return Err(malformed("trailing bytes after the final state"));
+                }
+                diverged(index, "final state", &expected, &final_state(&editor))?;
+                editor.replay_clock = None;
+                summary.revision = editor.revision();
+                return Ok((editor, summary));
+            }
+        }
+        summary.entries += 1;
+    }
+}
+
+fn read_entry(tag: u64, frame: &mut &[u8]) -> Result<Entry, EditorError> {
+    Ok(match tag {
+        TAG_REQUEST => Entry::Request {
+            id: RequestId::new(read_varint(frame).map_err(malformed)?),
+            messages: read_json(frame)?,
+        },
+        TAG_REMOTE => Entry::Remote(
+            decode_changesets(read_len_prefixed(frame).map_err(malformed)?)
+                .map_err(malformed)?
+                .into_graph_input(),
+        ),
+        TAG_RESOURCE => Entry::Resource(read_json(frame)?),
+        TAG_SET_DOCUMENT => Entry::SetDocument(read_json(frame)?),
+        TAG_INSERT_TEMPLATE => Entry::InsertTemplate(read_json(frame)?),
+        TAG_TICK => {
+            let through = if read_bool(frame).map_err(malformed)? {
+                Some(RequestId::new(read_varint(frame).map_err(malformed)?))
+            } else {
+                None
+            };
+            Entry::Tick {
+                through,
+                outcome: read_json(frame)?,
+            }
+        }
+        TAG_FINISH => Entry::Finish(read_json(frame)?),
+        tag => return Err(malformed(format!("unknown entry tag {tag}"))),
+    })
+}
+
+fn final_state(editor: &Editor) -> FinalState {
+    let mut heads = editor.current_heads();
+    heads.sort();
+    FinalState {
+        heads,
+        doc: editor.state().to_plain(),
+        selection: editor.state().selection,
+    }
+}
+
+fn diverged<T: PartialEq + Debug>(
+    entry: usize,
+    what: &str,
+    expected: &T,
+    replayed: &T,
+) -> Result<(), EditorError> {
+    if expected == replayed {
+        return Ok(());
+    }
+    Err(EditorError::ReplayDiverged {
+        entry,
+        detail: format!("{what}: recorded {expected:?}, replayed {replayed:?}"),
+    })
+}
+
+fn write_bytes(bytes: &[u8], out: &mut Vec<u8>) {
+    write_varint(bytes.len() as u64, out);
+    out.extend_from_slice(bytes);
+}
+
+fn write_json<T: Serialize + ?Sized>(value: &T, out: &mut Vec<u8>) -> Result<(), String> {
+    let json = serde_json::to_vec(value).map_err(|error| error.to_string())?;
+    write_bytes(&json, out);
+    Ok(())
+}
+
+fn read_json<T: DeserializeOwned>(input: &mut &[u8]) -> Result<T, EditorError> {
+    let bytes = read_len_prefixed(input).map_err(malformed)?;
+    serde_json::from_slice(bytes).map_err(malformed)
+}
+
+fn take<'a>(input: &mut &'a [u8], n: usize) -> Result<&'a [u8], EditorError> {
+    editor_codec::primitives::take(input, n).map_err(malformed)
+}
+
+fn malformed(msg: impl ToString) -> EditorError {
+    EditorError::MalformedRecording {
+        msg: msg.to_string(),
+    }
+}
@@ -0,0 +1,195 @@
+use editor_crdt::Dot;
+use editor_state::{LayoutDirty, Position, Selection, StableSelection};
+use hashbrown::{HashMap, HashSet};
+use std::sync::Arc;
+
+use crate::editor::Editor;
+use crate::text_walk::{block_text, collect_textblocks};
+use crate::tracked_range::{TrackedRange, TrackedRangeId};
+
+/// Tracked-range group holding the misspellings found by the resource
+/// spellchecker. Each range's metadata is the misspelled word.
+pub const SPELLCHECK_GROUP: &str = "misspelling";
+
+const MAX_SUGGESTIONS: usize = 8;
+
+/// Misspelling ranges per checked textblock, so a tick only re-checks the
+/// blocks its edits dirtied.
+#[derive(Debug)]
+pub(crate) struct SpellcheckState {
+    by_block: HashMap<Dot, Vec<TrackedRangeId>>,
+    checked: HashSet<Dot>,
+    next_id: u64,
+    recheck_all: bool,
+}
+
+impl Default for SpellcheckState {
+    /// Nothing is checked yet, so the first tick with a dictionary checks
+    /// the whole document.
+    fn default() -> Self {
+        Self {
+            by_block: HashMap::new(),
+            checked: HashSet::new(),
+            next_id: 0,
+            recheck_all: true,
+        }
+    }
+}
+
+impl SpellcheckState {
+    /// Re-checks every block on the next tick; the dictionary changed.
+    pub(crate) fn invalidate(&mut self) {
+        self.recheck_all = true;
+    }
+}
+
+/// Brings [`SPELLCHECK_GROUP`] up to date with the blocks `dirty` touched.
+/// Returns whether any range was added or removed.
+pub(crate) fn recheck(editor: &mut Editor, dirty: Option<&LayoutDirty>) -> bool {
+    let (checker, segmenters) = {
+        let resource = editor.resource.lock().unwrap();
+        (
+            resource.spellchecker().cloned(),
+            Arc::clone(resource.segmenters()),
+        )
+    };
+    let Some(checker) = checker else {
+        if std::mem::take(&mut editor.spellcheck).checked.is_empty() {
+            return false;
+        }
+        return !editor
+            .tracked_ranges
+            .clear_group(SPELLCHECK_GROUP)
+            .is_empty();
+    };
+
+    let full = std::mem::take(&mut editor.spellcheck.recheck_all)
+        || dirty.is_some_and(LayoutDirty::is_full);
+    let state = &editor.state;
+    let view = state.view();
+    let spellcheck = &mut editor.spellcheck;
+    let registry = &mut editor.tracked_ranges;
+    let mut changed = false;
+
+    let mut blocks = Vec::new();
+    let mut structural_changed = full;
+    if full {
+        if let Some(root) = view.root() {
+            collect_textblocks(&root, &mut blocks);
+        }
+    } else if let Some(LayoutDirty::Incremental {
+        content,
+        structural,
+    }) = dirty
+    {
+        blocks.extend(
+            content
+                .iter()
+                .copied()
+                .filter(|&dot| view.node(dot).is_some_and(|n| n.spec().is_textblock())),
+        );
+        // Moved blocks keep their leaves and so their ranges; only blocks
+        // that were never checked (splits, pastes, inserts) need a pass.
+        let mut inserted = Vec::new();
+        for &dot in structural {
+            if let Some(node) = view.node(dot) {
+                collect_textblocks(&node, &mut inserted);
+            }
+        }
+        blocks.extend(
+            inserted
+                .into_iter()
+                .filter(|dot| !spellcheck.checked.contains(dot)),
+        );
+        structural_changed = !structural.is_empty();
+    }
+
+    if structural_changed {
+        let removed: Vec<Dot> = spellcheck
+            .checked
+            .iter()
+            .copied()
+            .filter(|&dot| view.node(dot).is_none_or(|n| !n.spec().is_textblock()))
+            .collect();
+        for dot in removed {
+            spellcheck.checked.remove(&dot);
+            for id in spellcheck.by_block.remove(&dot).unwrap_or_default() {
+                changed |= registry.remove(&id).is_some();
+            }
+        }
+    }
+
+    blocks.sort_unstable();
+    blocks.dedup();
+    for block in blocks {
+        let Some(node) = view.node(block) else {
+            continue;
+        };
+        let text = block_text(&node);
+        let found = checker.misspellings(&text, &segmenters.word);
+        spellcheck.checked.insert(block);
+
+        let existing = spellcheck.by_block.remove(&block).unwrap_or_default();
+        let mut located: Vec<_> = existing
+            .iter()
+            .filter_map(|id| registry.get(id)?.locate(state))
+            .filter(|sel| sel.anchor.node == block && sel.head.node == block)
+            .map(|sel| {
+                let (a, b) = (sel.anchor.offset, sel.head.offset);
+                a.min(b)..a.max(b)
+            })
+            .collect();
+        located.sort_unstable_by_key(|range| range.start);
+        if located.len() == existing.len() && located == found {
+            if !existing.is_empty() {
+                spellcheck.by_block.insert(block, existing);
+            }
+            continue;
+        }
+
+        for id in &existing {
+            changed |= registry.remove(id).is_some();
+        }
+        let chars: Vec<char> = text.chars().collect();
+        let mut ids = Vec::with_capacity(found.len());
+        for range in found {
+            let id = format!("{SPELLCHECK_GROUP}:{}", spellcheck.next_id);
+            spellcheck.next_id += 1;
+            let selection = Selection::new(
+                Position::new(block, range.start),
+                Position::new(block, range.end),
+            );
+            registry.add(TrackedRange::new(
+                id.clone(),
+                SPELLCHECK_GROUP.into(),
+                StableSelection::capture(&selection, &view),
+                chars[range].iter().collect(),
+                false,
+                state,
+            ));
+            ids.push(id);
+            changed = true;
+        }
+        if !ids.is_empty() {
+            spellcheck.by_block.insert(block, ids);
+        }
+    }
+    changed
+}
+
+/// Corrections for the misspelling tracked as `id`, best first. Empty for
+/// ranges outside [`SPELLCHECK_GROUP`] or when no dictionary is loaded.
+pub(crate) fn suggestions(editor: &Editor, id: &str) -> Vec<String> {
+    let Some(range) = editor
+        .tracked_ranges
+        .get(id)
+        .filter(|range| range.group == SPELLCHECK_GROUP)
+    else {
+        return Vec::new();
+    };
+    let resource = editor.resource.lock().unwrap();
+    resource
+        .spellchecker()
+        .map(|checker| checker.suggest(&range.metadata, MAX_SUGGESTIONS))
+        .unwrap_or_default()
+}
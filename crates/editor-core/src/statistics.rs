@@ -0,0 +1,197 @@
+use editor_crdt::Dot;
+use editor_macros::ffi;
+use editor_model::{DocView, NodeType, NodeView};
+use editor_resource::{MANUSCRIPT_ROWS, TextSegmenters, TextStatistics, count_statistics};
+use editor_state::{LayoutDirty, State, blocks_in_range};
+use hashbrown::{HashMap, HashSet};
+use serde::{Deserialize, Serialize};
+
+use crate::text_walk::{block_text, collect_textblocks};
+
+/// Silent reading pace behind [`WritingStatistics::reading_seconds`].
+const READING_WORDS_PER_MINUTE: u32 = 230;
+
+/// Content left out of [`WritingStatistics`], e.g. for contests that only
+/// count body text.
+#[ffi]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct StatisticsOptions {
+    #[serde(default)]
+    pub exclude_fold_content: bool,
+    #[serde(default)]
+    pub exclude_tables: bool,
+    #[serde(default)]
+    pub exclude_blockquotes: bool,
+}
+
+#[ffi]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct WritingStatistics {
+    pub words: u32,
+    pub sentences: u32,
+    /// Textblocks with any visible text.
+    pub paragraphs: u32,
+    /// 200-cell 원고지 pages, each paragraph starting on a fresh line.
+    pub manuscript_pages: u32,
+    pub reading_seconds: u32,
+}
+
+impl WritingStatistics {
+    fn from_blocks(blocks: impl IntoIterator<Item = TextStatistics>) -> Self {
+        let mut out = Self::default();
+        let mut manuscript_lines = 0;
+        for block in blocks {
+            if block.is_empty() {
+                continue;
+            }
+            out.words += block.words;
+            out.sentences += block.sentences;
+            out.paragraphs += 1;
+            manuscript_lines += block.manuscript_lines;
+        }
+        out.manuscript_pages = manuscript_lines.div_ceil(MANUSCRIPT_ROWS);
+        out.reading_seconds = (out.words * 60).div_ceil(READING_WORDS_PER_MINUTE);
+        out
+    }
+}
+
+/// Per-textblock tallies, refreshed on demand for the blocks ticks dirtied
+/// since the last query.
+#[derive(Debug)]
+pub(crate) struct StatisticsState {
+    by_block: HashMap<Dot, TextStatistics>,
+    dirty: HashSet<Dot>,
+    structural_changed: bool,
+    recount_all: bool,
+}
+
+impl Default for StatisticsState {
+    fn default() -> Self {
+        Self {
+            by_block: HashMap::new(),
+            dirty: HashSet::new(),
+            structural_changed: false,
+            recount_all: true,
+        }
+    }
+}
+
+impl StatisticsState {
+    pub(crate) fn mark_dirty(&mut self, dirty: &LayoutDirty) {
+        match dirty {
+            LayoutDirty::Full => self.recount_all = true,
+            LayoutDirty::Incremental {
+                content,
+                structural,
+            } => {
+                self.dirty.extend(content.iter().copied());
+                self.dirty.extend(structural.iter().copied());
+                self.structural_changed |= !structural.is_empty();
+            }
+        }
+    }
+
+    fn refresh(&mut self, view: &DocView<'_>, segmenters: &TextSegmenters) {
+        let mut blocks = Vec::new();
+        if std::mem::take(&mut self.recount_all) {
+            self.by_block.clear();
+            self.dirty.clear();
+            self.structural_changed = false;
+            if let Some(root) = view.root() {
+                collect_textblocks(&root, &mut blocks);
+            }
+        } else {
+            // Structural dots cover inserted and moved subtrees; recounting
+            // every textblock under them also picks up splits and pastes.
+            for dot in self.dirty.drain() {
+                if let Some(node) = view.node(dot) {
+                    collect_textblocks(&node, &mut blocks);
+                }
+            }
+            if std::mem::take(&mut self.structural_changed) {
+                self.by_block
+                    .retain(|&dot, _| view.node(dot).is_some_and(|n| n.spec().is_textblock()));
+            }
+        }
+        for block in blocks {
+            if let Some(node) = view.node(block) {
+                self.by_block
+                    .insert(block, count_statistics(&statistics_text(&node), segmenters));
+            }
+        }
+    }
+}
+
+/// Whole-document statistics, recounting only blocks edited since the last
+/// call.
+pub(crate) fn document(
+    state: &State,
+    cache: &mut StatisticsState,
+    segmenters: &TextSegmenters,
+    options: StatisticsOptions,
+) -> WritingStatistics {
+    let view = state.view();
+    cache.refresh(&view, segmenters);
+    WritingStatistics::from_blocks(cache.by_block.iter().filter_map(|(&dot, &stats)| {
+        let node = view.node(dot)?;
+        (!excluded(&node, options)).then_some(stats)
+    }))
+}
+
+/// Statistics for the selected text, counting partly selected blocks by
+/// their selected part. Zero for a collapsed or missing selection.
+pub(crate) fn selection(
+    state: &State,
+    segmenters: &TextSegmenters,
+    options: StatisticsOptions,
+) -> WritingStatistics {
+    let view = state.view();
+    let Some(resolved) = state
+        .selection
+        .filter(|s| !s.is_collapsed())
+        .and_then(|s| s.resolve(&view))
+    else {
+        return WritingStatistics::default();
+    };
+    let (from, to) = (resolved.from().position(), resolved.to().position());
+    let blocks = blocks_in_range(&resolved)
+        .into_iter()
+        .filter(|block| block.spec().is_textblock() && !excluded(block, options))
+        .map(|block| {
+            let start = if block.id() == from.node {
+                from.offset
+            } else {
+                0
+            };
+            let end = if block.id() == to.node {
+                to.offset
+            } else {
+                block.child_count()
+            };
+            let text: String = statistics_text(&block)
+                .chars()
+                .skip(start)
+                .take(end.saturating_sub(start))
+                .collect();
+            count_statistics(&text, segmenters)
+        })
+        .collect::<Vec<_>>();
+    WritingStatistics::from_blocks(blocks)
+}
+
+fn excluded(node: &NodeView<'_>, options: StatisticsOptions) -> bool {
+    node.ancestors().any(|ancestor| match ancestor.node_type() {
+        NodeType::FoldContent => options.exclude_fold_content,
+        NodeType::Table => options.exclude_tables,
+        NodeType::Blockquote => options.exclude_blockquotes,
+        _ => false,
+    })
+}
+
+/// [`block_text`] with atoms read as spaces, so a hard break or inline
+/// image separates words and an image alone is not a paragraph.
+fn statistics_text(node: &NodeView<'_>) -> String {
+    block_text(node).replace('\u{FFFC}', " ")
+}
@@ -0,0 +1,233 @@
+//! Opt-in tick tracing. While disabled the editor holds no telemetry state and
+//! the tick path never reads the clock; while enabled each tick that drains the
+//! queue appends one [`TickTrace`] to a bounded ring the host drains with
+//! `Editor::take_tick_traces`.
+
+use std::collections::VecDeque;
+
+use editor_common::time::{Duration, Instant};
+use editor_macros::ffi;
+use editor_view::LayoutStats;
+use serde::{Deserialize, Serialize};
+
+use crate::tick::Revision;
+
+/// Spans kept per tick; a burst of queued requests beyond this is only counted.
+const MAX_SPANS_PER_TICK: usize = 64;
+
+#[ffi]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TickSpanKind {
+    Request,
+    Resource,
+    Remote,
+    SetDocument,
+    InsertTemplate,
+}
+
+/// One drained queue entry. `micros` is wall time including any layout the
+/// entry forced for geometry-dependent commands.
+#[ffi]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TickSpan {
+    pub kind: TickSpanKind,
+    pub micros: u64,
+    /// Messages for a request, changesets for a coalesced remote batch, else 1.
+    pub count: u32,
+}
+
+/// Exclusive phase durations of one tick; together they sum to `total_micros`.
+#[ffi]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TickTrace {
+    /// The revision the tick published, or `None` when it changed nothing.
+    pub revision: Option<Revision>,
+    pub total_micros: u64,
+    /// Local requests, templates and resource updates.
+    pub apply_micros: u64,
+    /// Remote changesets and document replacement.
+    pub projection_micros: u64,
+    /// Measuring and layout reconciliation, pagination excluded.
+    pub layout_micros: u64,
+    pub pagination_micros: u64,
+    /// Tracked ranges, spellcheck, statistics, effects and events.
+    pub publish_micros: u64,
+    pub blocks_remeasured: u32,
+    pub pages_repaginated: u32,
+    pub spans: Vec<TickSpan>,
+    pub dropped_spans: u32,
+}
+
+/// Rasterization of one page, recorded by the host-facing render path.
+#[ffi]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FrameTrace {
+    pub page: u32,
+    pub revision: Revision,
+    /// Display-list build under the editor lock.
+    pub display_list_micros: u64,
+    pub raster_micros: u64,
+    pub damage_rects: u32,
+    /// Damaged device pixels, overlapping rects counted once per rect.
+    pub damage_area: u64,
+}
+
+#[ffi]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EditorTelemetry {
+    pub ticks: Vec<TickTrace>,
+    pub frames: Vec<FrameTrace>,
+    /// Traces evicted from the rings since the last drain.
+    pub dropped: u32,
+}
+
+/// Fixed-capacity FIFO that evicts the oldest trace when full.
+#[derive(Debug)]
+pub struct TraceRing<T> {
+    capacity: usize,
+    items: VecDeque<T>,
+    dropped: u32,
+}
+
+impl<T> TraceRing<T> {
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            capacity,
+            items: VecDeque::with_capacity(capacity),
+            dropped: 0,
+        }
+    }
+
+    pub fn push(&mut self, item: T) {
+        if self.items.len() == self.capacity {
+            self.items.pop_front();
+            self.dropped = self.dropped.saturating_add(1);
+        }
+        self.items.push_back(item);
+    }
+
+    /// Drains the ring, returning the traces oldest first and the eviction count.
+    pub fn drain(&mut self) -> (Vec<T>, u32) {
+        (
+            self.items.drain(..).collect(),
+            std::mem::take(&mut self.dropped),
+        )
+    }
+}
+
+pub(crate) struct Telemetry {
+    ring: TraceRing<TickTrace>,
+    active: Option<ActiveTick>,
+}
+
+struct ActiveTick {
+    started: Instant,
+    apply: Duration,
+    projection: Duration,
+    layout: Duration,
+    spans: Vec<TickSpan>,
+    dropped_spans: u32,
+}
+
+#[derive(Clone, Copy)]
+pub(crate) struct SpanStart {
+    started: Instant,
+    layout_before: Duration,
+}
+
+impl Telemetry {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            ring: TraceRing::new(capacity),
+            active: None,
+        }
+    }
+
+    pub(crate) fn begin(&mut self) {
+        self.active = Some(ActiveTick {
+            started: Instant::now(),
+            apply: Duration::ZERO,
+            projection: Duration::ZERO,
+            layout: Duration::ZERO,
+            spans: Vec::new(),
+            dropped_spans: 0,
+        });
+    }
+
+    /// A failed tick publishes nothing, so its partial trace is discarded.
+    pub(crate) fn abandon(&mut self) {
+        self.active = None;
+    }
+
+    pub(crate) fn span_start(&self) -> Option<SpanStart> {
+        let active = self.active.as_ref()?;
+        Some(SpanStart {
+            started: Instant::now(),
+            layout_before: active.layout,
+        })
+    }
+
+    pub(crate) fn span_end(&mut self, start: SpanStart, kind: TickSpanKind, count: usize) {
+        let Some(active) = self.active.as_mut() else {
+            return;
+        };
+        let elapsed = start.started.elapsed();
+        // Layout forced mid-entry is already attributed to the layout phase.
+        let own = elapsed.saturating_sub(active.layout - start.layout_before);
+        match kind {
+            TickSpanKind::Remote | TickSpanKind::SetDocument => active.projection += own,
+            TickSpanKind::Request | TickSpanKind::Resource | TickSpanKind::InsertTemplate => {
+                active.apply += own
+            }
+        }
+        if active.spans.len() < MAX_SPANS_PER_TICK {
+            active.spans.push(TickSpan {
+                kind,
+                micros: micros(elapsed),
+                count: u32::try_from(count).unwrap_or(u32::MAX),
+            });
+        } else {
+            active.dropped_spans = active.dropped_spans.saturating_add(1);
+        }
+    }
+
+    pub(crate) fn record_layout(&mut self, elapsed: Duration) {
+        if let Some(active) = self.active.as_mut() {
+            active.layout += elapsed;
+        }
+    }
+
+    pub(crate) fn finish(&mut self, revision: Option<Revision>, layout: LayoutStats) {
+        let Some(active) = self.active.take() else {
+            return;
+        };
+        let total = active.started.elapsed();
+        let publish = total
+            .saturating_sub(active.apply)
+            .saturating_sub(active.projection)
+            .saturating_sub(active.layout);
+        self.ring.push(TickTrace {
+            revision,
+            total_micros: micros(total),
+            apply_micros: micros(active.apply),
+            projection_micros: micros(active.projection),
+            layout_micros: micros(active.layout.saturating_sub(layout.pagination)),
+            pagination_micros: micros(layout.pagination),
+            publish_micros: micros(publish),
+            blocks_remeasured: layout.blocks_remeasured,
+            pages_repaginated: layout.pages_repaginated,
+            spans: active.spans,
+            dropped_spans: active.dropped_spans,
+        });
+    }
+
+    pub(crate) fn drain(&mut self) -> (Vec<TickTrace>, u32) {
+        self.ring.drain()
+    }
+}
+
+fn micros(duration: Duration) -> u64 {
+    u64::try_from(duration.as_micros()).unwrap_or(u64::MAX)
+}
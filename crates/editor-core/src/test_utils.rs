@@ -21,7 +21,7 @@ pub struct EditorSnapshot {
     fold_states: HashMap<Dot, bool>,
     preferred_x: Option<f32>,
     focused: bool,
-    theme_variant: ThemeVariant,
+    theme_variant: Option<ThemeVariant>,
     page_sizes: Vec<Size>,
     tracked_ranges: TrackedRangeRegistry,
     tracked_decoration_groups: HashMap<String, GroupDecoration>,
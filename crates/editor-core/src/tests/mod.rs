@@ -10,8 +10,12 @@ mod perf_modifier;
 mod perf_span_boundary;
 mod perf_tracked_resolve;
 mod perf_tracked_spellcheck;
+mod recording;
 mod set_doc;
+mod spellcheck;
 mod state_changed_tracked_ranges;
+mod statistics;
+mod telemetry;
 mod tracked_decoration_integration;
 mod tracked_range_hit_test;
 mod tracked_range_integration;
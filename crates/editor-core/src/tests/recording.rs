@@ -0,0 +1,95 @@
+use std::sync::{Arc, Mutex};
+
+use editor_macros::state;
+use editor_resource::Resource;
+use editor_state::State;
+use editor_view::Viewport;
+
+use crate::editor::Editor;
+use crate::error::EditorError;
+use crate::message::*;
+use crate::recording::replay_session;
+
+fn editor(state: State) -> Editor {
+    let resource = Arc::new(Mutex::new(Resource::new_test()));
+    Editor::new(state, Viewport::new(800.0, 600.0, 1.0), resource)
+}
+
+fn type_text(text: &str) -> Vec<Message> {
+    vec![Message::Insertion {
+        op: InsertionOp::Text { text: text.into() },
+    }]
+}
+
+fn record_typing() -> (Editor, Vec<u8>) {
+    let (initial, _p1) = state! {
+        doc { root {
+            p1: paragraph { text("Hello") }
+        } }
+        selection: (p1, 5)
+    };
+    let mut editor = editor(initial);
+    editor.start_recording().unwrap();
+    editor.enqueue_request(type_text(" zq")).unwrap();
+    editor.tick().unwrap();
+    let id = editor.enqueue_request(type_text("!")).unwrap();
+    editor.tick_through(id).unwrap();
+    let recording = editor.finish_recording().unwrap();
+    (editor, recording)
+}
+
+#[test]
+fn replay_reproduces_ticks_and_final_state() {
+    let (editor, recording) = record_typing();
+    assert!(!editor.is_recording());
+
+    let resource = Arc::new(Mutex::new(Resource::new_test()));
+    let (replayed, summary) = replay_session(&recording, resource).unwrap();
+
+    assert_eq!(summary.entries, 4);
+    assert_eq!(summary.ticks, 2);
+    assert_eq!(summary.revision, editor.revision());
+    assert_eq!(replayed.state().to_plain(), editor.state().to_plain());
+    assert_eq!(replayed.state().selection, editor.state().selection);
+}
+
+#[test]
+fn replay_reports_divergence_from_a_tampered_request() {
+    let (_, mut recording) = record_typing();
+    let at = recording
+        .windows(2)
+        .position(|window| window == b"zq")
+        .unwrap();
+    recording[at + 1] = b'x';
+
+    let resource = Arc::new(Mutex::new(Resource::new_test()));
+    let err = replay_session(&recording, resource).unwrap_err();
+    assert!(matches!(err, EditorError::ReplayDiverged { .. }), "{err}");
+}
+
+#[test]
+fn replay_rejects_truncated_recordings() {
+    let (_, recording) = record_typing();
+
+    let resource = Arc::new(Mutex::new(Resource::new_test()));
+    let err = replay_session(&recording[..recording.len() - 1], resource).unwrap_err();
+    assert!(
+        matches!(err, EditorError::MalformedRecording { .. }),
+        "{err}"
+    );
+}
+
+#[test]
+fn finish_without_start_is_an_error() {
+    let (initial, _p1) = state! {
+        doc { root {
+            p1: paragraph { text("") }
+        } }
+        selection: (p1, 0)
+    };
+    let mut editor = editor(initial);
+    assert!(matches!(
+        editor.finish_recording(),
+        Err(EditorError::NotRecording)
+    ));
+}
@@ -0,0 +1,148 @@
+use std::sync::{Arc, Mutex};
+
+use editor_macros::state;
+use editor_resource::{Resource, ResourceSource, compress_zstd, prepare_spellchecker};
+use editor_state::{Position, Selection, State};
+
+use crate::editor::Editor;
+use crate::event::EditorEvent;
+use crate::message::*;
+use crate::spellcheck::SPELLCHECK_GROUP;
+use crate::state_field::StateField;
+
+const AFF: &str = "SET UTF-8\nTRY esianrtolcdugmphbyfvkwz\n";
+const DIC: &str = "3\nhello\nworld\nthe\n";
+
+fn editor_with_dictionary(state: State) -> (Editor, ResourceSource) {
+    let mut source = ResourceSource::new_test();
+    let prepared = prepare_spellchecker(
+        &compress_zstd(AFF.as_bytes()),
+        &compress_zstd(DIC.as_bytes()),
+    )
+    .unwrap();
+    source
+        .set_spellchecker(Some(prepared))
+        .expect("installing a dictionary must change resources");
+    let resource = Arc::new(Mutex::new(Resource::from_snapshot(source.snapshot())));
+    let mut editor = Editor::new_test_with_resource(state, resource);
+    editor.apply(Message::System {
+        event: SystemEvent::Initialize,
+    });
+    (editor, source)
+}
+
+/// `(id, word)` for every misspelling, in id order.
+fn misspellings(editor: &Editor) -> Vec<(String, String)> {
+    let mut found: Vec<_> = editor
+        .tracked_ranges()
+        .iter_group(SPELLCHECK_GROUP)
+        .map(|range| (range.id.clone(), range.metadata.clone()))
+        .collect();
+    found.sort();
+    found
+}
+
+fn set_selection(editor: &mut Editor, selection: Selection) {
+    editor.apply(Message::Selection {
+        op: SelectionOp::Set { selection },
+    });
+}
+
+fn type_text(editor: &mut Editor, text: &str) -> Vec<EditorEvent> {
+    editor.apply(Message::Insertion {
+        op: InsertionOp::Text { text: text.into() },
+    })
+}
+
+fn tracked_ranges_changed(events: &[EditorEvent]) -> bool {
+    events.iter().any(|event| {
+        matches!(event, EditorEvent::StateChanged { fields } if fields.contains(&StateField::TrackedRanges))
+    })
+}
+
+#[test]
+fn initial_check_tracks_misspelled_words() {
+    let (initial, p1) = state! {
+        doc { root { p1: paragraph { text("hello wrold") } } }
+        selection: (p1, 0)
+    };
+    let (editor, _source) = editor_with_dictionary(initial);
+
+    let found = misspellings(&editor);
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].1, "wrold");
+    let range = editor.tracked_ranges().get(&found[0].0).unwrap();
+    assert_eq!(
+        range.locate(editor.state()),
+        Some(Selection::new(Position::new(p1, 6), Position::new(p1, 11)))
+    );
+    assert!(
+        editor
+            .spelling_suggestions(&found[0].0)
+            .contains(&"world".to_owned())
+    );
+}
+
+#[test]
+fn edits_recheck_only_the_dirty_block() {
+    let (initial, _p1, p2) = state! {
+        doc { root { p1: paragraph { text("hello wrold") } p2: paragraph { text("hello") } } }
+        selection: (p2, 5)
+    };
+    let (mut editor, _source) = editor_with_dictionary(initial);
+    let before = misspellings(&editor);
+    assert_eq!(before.len(), 1);
+
+    let events = type_text(&mut editor, " teh");
+    assert!(tracked_ranges_changed(&events));
+    let after = misspellings(&editor);
+    assert_eq!(after.len(), 2);
+    assert!(
+        after.contains(&before[0]),
+        "p1's range must survive untouched"
+    );
+    assert!(after.iter().any(|(_, word)| word == "teh"));
+
+    set_selection(
+        &mut editor,
+        Selection::new(Position::new(p2, 6), Position::new(p2, 9)),
+    );
+    type_text(&mut editor, "the");
+    assert_eq!(misspellings(&editor), before);
+}
+
+#[test]
+fn typing_without_changing_misspellings_keeps_ranges_quiet() {
+    let (initial, _p1) = state! {
+        doc { root { p1: paragraph { text("hello wrold") } } }
+        selection: (p1, 5)
+    };
+    let (mut editor, _source) = editor_with_dictionary(initial);
+    let before = misspellings(&editor);
+
+    let events = type_text(&mut editor, " the");
+    assert!(!tracked_ranges_changed(&events));
+    assert_eq!(misspellings(&editor), before);
+}
+
+#[test]
+fn clearing_the_dictionary_drops_misspellings() {
+    let (initial, _p1) = state! {
+        doc { root { p1: paragraph { text("hello wrold") } } }
+        selection: (p1, 0)
+    };
+    let (mut editor, mut source) = editor_with_dictionary(initial);
+    let id = misspellings(&editor)[0].0.clone();
+
+    let snapshot = source
+        .set_spellchecker(None)
+        .expect("clearing an installed dictionary must change resources");
+    editor.receive_resource_update(crate::ResourceUpdate::new(
+        snapshot,
+        vec![SystemEvent::SpellcheckChanged],
+    ));
+    editor.tick().unwrap().unwrap();
+
+    assert!(misspellings(&editor).is_empty());
+    assert!(editor.spelling_suggestions(&id).is_empty());
+}
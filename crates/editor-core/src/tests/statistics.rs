@@ -0,0 +1,137 @@
+use editor_macros::state;
+use editor_state::{Position, Selection};
+
+use crate::editor::Editor;
+use crate::message::*;
+use crate::statistics::{StatisticsOptions, WritingStatistics};
+
+fn document(editor: &Editor, options: StatisticsOptions) -> WritingStatistics {
+    editor.writing_statistics(options).0
+}
+
+fn type_text(editor: &mut Editor, text: &str) {
+    editor.apply(Message::Insertion {
+        op: InsertionOp::Text { text: text.into() },
+    });
+}
+
+#[test]
+fn counts_words_sentences_and_paragraphs() {
+    let (initial, _p1) = state! {
+        doc { root {
+            p1: paragraph { text("Hello world. How are you?") }
+            paragraph { text("   ") }
+            paragraph { text("안녕하세요 반갑습니다.") }
+        } }
+        selection: (p1, 0)
+    };
+    let editor = Editor::new_test(initial);
+
+    let (doc, selection) = editor.writing_statistics(StatisticsOptions::default());
+    assert_eq!(doc.words, 7);
+    assert_eq!(doc.sentences, 3);
+    assert_eq!(doc.paragraphs, 2);
+    assert_eq!(doc.manuscript_pages, 1);
+    assert_eq!(doc.reading_seconds, 2);
+    assert_eq!(selection, WritingStatistics::default());
+}
+
+#[test]
+fn manuscript_pages_start_each_paragraph_on_a_new_line() {
+    let (initial, _p1) = state! {
+        doc { root {
+            p1: paragraph { text("가나다") }
+            paragraph { text("가나다") }
+            paragraph { text("가나다") }
+            paragraph { text("가나다") }
+            paragraph { text("가나다") }
+            paragraph { text("가나다") }
+            paragraph { text("가나다") }
+            paragraph { text("가나다") }
+            paragraph { text("가나다") }
+            paragraph { text("가나다") }
+            paragraph { text("라") }
+        } }
+        selection: (p1, 0)
+    };
+    let editor = Editor::new_test(initial);
+
+    assert_eq!(
+        document(&editor, StatisticsOptions::default()).manuscript_pages,
+        2
+    );
+}
+
+#[test]
+fn options_exclude_folds_tables_and_blockquotes() {
+    let (initial, _p1) = state! {
+        doc { root {
+            p1: paragraph { text("one") }
+            fold { fold_title { text("two") } fold_content { paragraph { text("three") } } }
+            table { table_row { table_cell { paragraph { text("four") } } } }
+            blockquote { paragraph { text("five") } }
+        } }
+        selection: (p1, 0)
+    };
+    let editor = Editor::new_test(initial);
+
+    assert_eq!(document(&editor, StatisticsOptions::default()).words, 5);
+    let body_only = StatisticsOptions {
+        exclude_fold_content: true,
+        exclude_tables: true,
+        exclude_blockquotes: true,
+    };
+    let doc = document(&editor, body_only);
+    assert_eq!(doc.words, 2, "the fold title still counts");
+    assert_eq!(doc.paragraphs, 2);
+}
+
+#[test]
+fn selection_counts_only_the_selected_part_of_each_block() {
+    let (initial, p1, p2) = state! {
+        doc { root {
+            p1: paragraph { text("alpha beta gamma") }
+            p2: paragraph { text("delta epsilon") }
+        } }
+        selection: (p1, 0)
+    };
+    let mut editor = Editor::new_test(initial);
+    editor.apply(Message::Selection {
+        op: SelectionOp::Set {
+            selection: Selection::new(Position::new(p1, 6), Position::new(p2, 5)),
+        },
+    });
+
+    let (doc, selection) = editor.writing_statistics(StatisticsOptions::default());
+    assert_eq!(doc.words, 5);
+    assert_eq!(selection.words, 3);
+    assert_eq!(selection.paragraphs, 2);
+}
+
+#[test]
+fn edits_keep_document_statistics_current() {
+    let (initial, _p1, _p2) = state! {
+        doc { root {
+            p1: paragraph { text("one two") }
+            p2: paragraph { text("three") }
+        } }
+        selection: (p2, 5)
+    };
+    let mut editor = Editor::new_test(initial);
+    assert_eq!(document(&editor, StatisticsOptions::default()).words, 3);
+
+    type_text(&mut editor, " four. Five");
+    let doc = document(&editor, StatisticsOptions::default());
+    assert_eq!(doc.words, 5);
+    assert_eq!(doc.sentences, 3);
+
+    editor.apply(Message::Insertion {
+        op: InsertionOp::Break {
+            kind: Break::Paragraph,
+        },
+    });
+    type_text(&mut editor, "six");
+    let doc = document(&editor, StatisticsOptions::default());
+    assert_eq!(doc.words, 6);
+    assert_eq!(doc.paragraphs, 3);
+}
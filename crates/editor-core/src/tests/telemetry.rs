@@ -0,0 +1,82 @@
+use editor_macros::state;
+
+use crate::editor::Editor;
+use crate::message::*;
+use crate::telemetry::TickSpanKind;
+
+fn editor() -> Editor {
+    let (initial, _p1) = state! {
+        doc { root {
+            p1: paragraph { text("Hello") }
+        } }
+        selection: (p1, 5)
+    };
+    Editor::new_test(initial)
+}
+
+fn type_text(editor: &mut Editor, text: &str) {
+    editor.apply(Message::Insertion {
+        op: InsertionOp::Text { text: text.into() },
+    });
+}
+
+#[test]
+fn disabled_editor_collects_nothing() {
+    let mut editor = editor();
+    type_text(&mut editor, "!");
+
+    assert!(!editor.is_telemetry_enabled());
+    assert_eq!(editor.take_tick_traces(), (Vec::new(), 0));
+}
+
+#[test]
+fn typing_traces_one_request_span_and_remeasures_the_block() {
+    let mut editor = editor();
+    editor.enable_telemetry(8);
+    type_text(&mut editor, "!");
+
+    let (traces, dropped) = editor.take_tick_traces();
+    assert_eq!(dropped, 0);
+    let [trace] = traces.as_slice() else {
+        panic!("expected one trace, got {traces:?}");
+    };
+    assert_eq!(trace.revision, Some(editor.revision()));
+    assert_eq!(trace.spans.len(), 1);
+    assert_eq!(trace.spans[0].kind, TickSpanKind::Request);
+    assert_eq!(trace.spans[0].count, 1);
+    assert!(trace.blocks_remeasured >= 1);
+    let phases = trace.apply_micros
+        + trace.projection_micros
+        + trace.layout_micros
+        + trace.pagination_micros
+        + trace.publish_micros;
+    assert!(phases <= trace.total_micros);
+
+    assert_eq!(editor.take_tick_traces(), (Vec::new(), 0));
+}
+
+#[test]
+fn ring_keeps_the_newest_ticks_and_counts_evictions() {
+    let mut editor = editor();
+    editor.enable_telemetry(2);
+    for text in ["a", "b", "c"] {
+        type_text(&mut editor, text);
+    }
+
+    let (traces, dropped) = editor.take_tick_traces();
+    assert_eq!(dropped, 1);
+    let revisions: Vec<_> = traces.iter().filter_map(|t| t.revision).collect();
+    assert_eq!(revisions.len(), 2);
+    assert_eq!(revisions.last(), Some(&editor.revision()));
+    assert!(revisions[0] < revisions[1]);
+}
+
+#[test]
+fn disabling_drops_collected_traces() {
+    let mut editor = editor();
+    editor.enable_telemetry(4);
+    type_text(&mut editor, "!");
+    editor.disable_telemetry();
+
+    assert_eq!(editor.take_tick_traces(), (Vec::new(), 0));
+}
@@ -0,0 +1,26 @@
+//! Plain-text views of textblocks shared by the spellcheck and statistics
+//! passes.
+
+use editor_crdt::Dot;
+use editor_model::{ChildView, NodeView};
+
+pub(crate) fn collect_textblocks(node: &NodeView<'_>, out: &mut Vec<Dot>) {
+    if node.spec().is_textblock() {
+        out.push(node.id());
+        return;
+    }
+    for child in node.child_blocks() {
+        collect_textblocks(&child, out);
+    }
+}
+
+/// One char per child slot, so char offsets are positions in the block.
+/// Atoms read as U+FFFC, which never joins a word segment.
+pub(crate) fn block_text(node: &NodeView<'_>) -> String {
+    node.children()
+        .map(|child| match child {
+            ChildView::Leaf(leaf) => leaf.as_char().unwrap_or('\u{FFFC}'),
+            ChildView::Block(_) => '\u{FFFC}',
+        })
+        .collect()
+}
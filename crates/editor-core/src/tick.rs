@@ -5,6 +5,7 @@ use editor_resource::{ResourceRevision, ResourceSnapshot};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
+use crate::telemetry::TickSpanKind;
 use crate::{EditorEvent, Message, SystemEvent};
 
 #[ffi]
@@ -135,6 +136,18 @@ pub(crate) enum QueueEntry {
     InsertTemplate(PlainDoc),
 }
 
+impl QueueEntry {
+    pub(crate) fn span_kind(&self) -> TickSpanKind {
+        match self {
+            Self::Request { .. } => TickSpanKind::Request,
+            Self::Resource(_) => TickSpanKind::Resource,
+            Self::Remote(_) => TickSpanKind::Remote,
+            Self::SetDocument(_) => TickSpanKind::SetDocument,
+            Self::InsertTemplate(_) => TickSpanKind::InsertTemplate,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::BTreeMap;
@@ -199,7 +212,7 @@ mod tests {
         let snapshot = source
             .set_theme_variant(ThemeVariant::DarkBlack)
             .expect("theme changes");
-        super::ResourceUpdate::new(snapshot, vec![crate::SystemEvent::ThemeVariantChanged])
+        super::ResourceUpdate::new(snapshot, vec![crate::SystemEvent::ThemeChanged])
     }
 
     fn text(editor: &Editor) -> String {
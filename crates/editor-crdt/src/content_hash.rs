@@ -0,0 +1,172 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use sha2::{Digest, Sha256};
+
+use crate::{Dot, Op};
+
+/// SHA-256 content address of a sealed changeset. Covers every op's id,
+/// parents and payload digest plus the hashes of the changesets that own the
+/// ops' out-of-changeset parents, so two changesets hash equal only when their
+/// whole causal histories do.
+///
+/// Serializes as 64 lowercase hex digits.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ContentHash(pub [u8; 32]);
+
+impl fmt::Display for ContentHash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in self.0 {
+            write!(f, "{byte:02x}")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for ContentHash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ContentHash({self})")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("content hash must be 64 hex digits")]
+pub struct ParseContentHashError;
+
+impl FromStr for ContentHash {
+    type Err = ParseContentHashError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.len() != 64 || !s.is_ascii() {
+            return Err(ParseContentHashError);
+        }
+        let mut out = [0u8; 32];
+        for (i, byte) in out.iter_mut().enumerate() {
+            *byte =
+                u8::from_str_radix(&s[i * 2..i * 2 + 2], 16).map_err(|_| ParseContentHashError)?;
+        }
+        Ok(Self(out))
+    }
+}
+
+impl Serialize for ContentHash {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for ContentHash {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Self::from_str(&s).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Stable byte form of `P` payloads, fed into [`ContentHash`]. Implemented by
+/// the layer that owns `P`'s durable encoding rather than by `P` itself:
+/// replicas must produce identical bytes for equal payloads regardless of
+/// platform, process or build, which only a versioned wire format guarantees.
+/// A graph memoizes changeset hashes on first request, so each payload type
+/// must be hashed through exactly one digest.
+pub trait ContentDigest<P> {
+    fn digest_into(payload: &P, out: &mut Vec<u8>);
+}
+
+fn digest_dot(dot: &Dot, out: &mut Vec<u8>) {
+    out.extend_from_slice(&dot.actor.to_le_bytes());
+    out.extend_from_slice(&dot.clock.to_le_bytes());
+}
+
+const CHANGESET_DOMAIN: &[u8] = b"editor-crdt/changeset/v1";
+const HEADS_DOMAIN: &[u8] = b"editor-crdt/heads/v1";
+
+pub(crate) fn changeset_hash<P, D: ContentDigest<P>>(
+    ops: &[&Op<P>],
+    mut parent_hashes: Vec<ContentHash>,
+) -> ContentHash {
+    let mut buf = Vec::new();
+    let mut hasher = Sha256::new();
+    hasher.update(CHANGESET_DOMAIN);
+    hasher.update((ops.len() as u64).to_le_bytes());
+    for op in ops {
+        buf.clear();
+        digest_dot(&op.id, &mut buf);
+        buf.extend_from_slice(&(op.parents.len() as u64).to_le_bytes());
+        for parent in &op.parents {
+            digest_dot(parent, &mut buf);
+        }
+        let payload_start = buf.len();
+        D::digest_into(&op.payload, &mut buf);
+        let payload_len = (buf.len() - payload_start) as u64;
+        hasher.update(&buf[..payload_start]);
+        hasher.update(payload_len.to_le_bytes());
+        hasher.update(&buf[payload_start..]);
+    }
+    parent_hashes.sort_unstable();
+    parent_hashes.dedup();
+    hasher.update((parent_hashes.len() as u64).to_le_bytes());
+    for hash in &parent_hashes {
+        hasher.update(hash.0);
+    }
+    ContentHash(hasher.finalize().into())
+}
+
+pub(crate) fn heads_hash(hashes: impl IntoIterator<Item = ContentHash>) -> ContentHash {
+    let mut hashes: Vec<ContentHash> = hashes.into_iter().collect();
+    hashes.sort_unstable();
+    hashes.dedup();
+    let mut hasher = Sha256::new();
+    hasher.update(HEADS_DOMAIN);
+    hasher.update((hashes.len() as u64).to_le_bytes());
+    for hash in &hashes {
+        hasher.update(hash.0);
+    }
+    ContentHash(hasher.finalize().into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hex_round_trips_through_serde() {
+        let hash = heads_hash([]);
+        let json = serde_json::to_string(&hash).unwrap();
+        assert_eq!(json.len(), 66);
+        assert_eq!(serde_json::from_str::<ContentHash>(&json).unwrap(), hash);
+        assert!("zz".parse::<ContentHash>().is_err());
+    }
+
+    #[test]
+    fn heads_hash_ignores_order_and_duplicates() {
+        let a = ContentHash([1; 32]);
+        let b = ContentHash([2; 32]);
+        assert_eq!(heads_hash([a, b]), heads_hash([b, a, b]));
+        assert_ne!(heads_hash([a]), heads_hash([a, b]));
+    }
+
+    /// Raw UTF-8 with no framing of its own.
+    struct Utf8;
+
+    impl ContentDigest<String> for Utf8 {
+        fn digest_into(payload: &String, out: &mut Vec<u8>) {
+            out.extend_from_slice(payload.as_bytes());
+        }
+    }
+
+    #[test]
+    fn payload_digest_is_length_delimited() {
+        let op = |clock, payload: &str| Op {
+            id: Dot::new(1, clock),
+            parents: vec![],
+            payload: payload.to_string(),
+        };
+        let joined = [op(0, "ab"), op(1, "")];
+        let split = [op(0, "a"), op(1, "b")];
+        assert_ne!(
+            changeset_hash::<_, Utf8>(&[&joined[0], &joined[1]], vec![]),
+            changeset_hash::<_, Utf8>(&[&split[0], &split[1]], vec![]),
+        );
+    }
+}
@@ -1,6 +1,7 @@
 editor_macros::preamble!();
 
 pub mod changeset;
+pub mod content_hash;
 pub mod dot;
 mod dot_map;
 pub use dot_map::DotMap;
@@ -15,6 +16,7 @@ pub mod sync;
 pub mod to_plain;
 
 pub use changeset::Changeset;
+pub use content_hash::{ContentDigest, ContentHash};
 pub use dot::{Dot, Dots, OpDot};
 pub use error::CrdtError;
 pub use lwwreg::{LwwReg, LwwRegOp};
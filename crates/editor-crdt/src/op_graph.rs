@@ -1,7 +1,10 @@
+use std::sync::OnceLock;
+
 use editor_macros::ffi;
 use hashbrown::{HashMap, HashSet};
 use serde::{Deserialize, Serialize};
 
+use crate::content_hash::{self, ContentDigest, ContentHash};
 use crate::dot_map::DotMap;
 use crate::{CrdtError, Dot, FastSet};
 
@@ -26,13 +29,26 @@ fn child_remove(set: &mut ChildSet, dot: &Dot) {
 /// Sealed-changeset descriptor: the member dots, in op order, compressed as
 /// consecutive-clock runs. Storing the full `Changeset` duplicated every
 /// op's payload and parents in memory; the wire/FFI `Changeset` is
-/// materialized from `ops` on demand instead.
-#[derive(Debug, Clone, PartialEq, Eq)]
+/// materialized from `ops` on demand instead. `hash` memoizes the content
+/// hash the first time anyone asks for it, so sealing and receiving never pay
+/// for SHA-256 on graphs whose peers did not negotiate hashes.
+#[derive(Debug, Clone)]
 pub struct ChangesetRef {
     runs: smallvec::SmallVec<[(Dot, u32); 2]>,
     len: u32,
+    hash: OnceLock<ContentHash>,
+}
+
+// Manual impls: the memoized hash is derived state, so whether it has been
+// computed yet must not affect equality.
+impl PartialEq for ChangesetRef {
+    fn eq(&self, other: &Self) -> bool {
+        self.runs == other.runs && self.len == other.len
+    }
 }
 
+impl Eq for ChangesetRef {}
+
 impl ChangesetRef {
     fn from_ops<P>(ops: &[Op<P>]) -> Self {
         let mut runs: smallvec::SmallVec<[(Dot, u32); 2]> = smallvec::SmallVec::new();
@@ -50,6 +66,7 @@ impl ChangesetRef {
         ChangesetRef {
             runs,
             len: ops.len() as u32,
+            hash: OnceLock::new(),
         }
     }
 
@@ -65,6 +82,14 @@ impl ChangesetRef {
         self.runs.first().map(|(d, _)| *d)
     }
 
+    fn contains(&self, dot: &Dot) -> bool {
+        self.runs.iter().any(|(start, len)| {
+            dot.actor == start.actor
+                && dot.clock >= start.clock
+                && dot.clock - start.clock < u64::from(*len)
+        })
+    }
+
     pub fn dots(&self) -> impl Iterator<Item = Dot> + '_ {
         self.runs.iter().flat_map(|(start, len)| {
             (0..u64::from(*len)).map(move |i| Dot::new(start.actor, start.clock + i))
@@ -89,11 +114,12 @@ pub struct Op<P> {
     pub payload: P,
 }
 
-/// Index container for the O(1) idempotency probe. `DotMap` chosen by
-/// measurement on the production fixture: packed lanes pad 1.3x (~2MB at 197k
-/// changesets) vs ~19MB HAMT-per-changeset; the crossover (~12 ops/changeset
-/// between first dots) is far above real keystroke-sized changesets.
-type CsByFirst = DotMap<u32>;
+/// Index container for the O(1) idempotency probe and owner lookups. `DotMap`
+/// chosen by measurement on the production fixture: packed lanes pad 1.3x
+/// (~2MB at 197k changesets) vs ~19MB HAMT-per-changeset. Lanes are dense
+/// over each actor's clock range anyway, so keying every sealed dot rather
+/// than only first dots costs no extra slots.
+type CsOwner = DotMap<u32>;
 
 /// Op-DAG storage. Immutable, structural-sharing append-only.
 #[derive(Clone, Debug)]
@@ -126,15 +152,15 @@ pub struct OpGraph<P> {
     /// (`debug_remove`); `add` and `receive_changeset` keep
     /// parents-before-children invariants so they always preserve the set.
     self_contained: DotMap<()>,
-    /// First-dot → ordinal in `changesets`. Derived index for the O(1)
-    /// idempotency probe; every stored dot belongs to exactly one sealed
-    /// changeset, so the first dot uniquely identifies its owner. `DotMap`
-    /// keeps the packed-lane footprint (a HAMT entry per changeset would
+    /// Sealed dot → ordinal of the `changesets` entry containing it.
+    /// Derived index for the O(1) idempotency probe and content-hash owner
+    /// lookups; every stored dot belongs to exactly one sealed changeset.
+    /// `DotMap` keeps the packed-lane footprint (a HAMT entry per op would
     /// bloat 200k-changeset browser graphs) and its lane map is imbl-backed,
     /// preserving `receive_changeset`'s O(1) `self.clone()`.
-    cs_by_first: CsByFirst,
+    cs_owner: CsOwner,
     /// Set by test-only `debug_remove` recovery flows that can seat two
-    /// descriptors under one first dot; degrades the probe to the full scan.
+    /// descriptors over one dot; degrades the probe to the full scan.
     index_degraded: bool,
 }
 
@@ -184,11 +210,16 @@ impl<P: Clone> OpGraph<P> {
             heads: FastSet::new(),
             children: DotMap::new(),
             self_contained: DotMap::new(),
-            cs_by_first: CsByFirst::new(),
+            cs_owner: CsOwner::new(),
             index_degraded: false,
         }
     }
 
+    /// The actor local writes are stamped with.
+    pub fn actor(&self) -> u64 {
+        self.actor
+    }
+
     pub fn current_heads(&self) -> impl Iterator<Item = &Dot> + '_ {
         self.heads.iter()
     }
@@ -245,6 +276,113 @@ impl<P: Clone> OpGraph<P> {
         &self.pending
     }
 
+    /// Content hash of the sealed changeset whose first op is `first`,
+    /// digested through `D` on first request and memoized together with the
+    /// ancestor hashes it chains over.
+    pub fn changeset_hash<D: ContentDigest<P>>(&self, first: &Dot) -> Option<ContentHash> {
+        let ix = if self.index_degraded {
+            self.changesets
+                .iter()
+                .position(|r| r.first().as_ref() == Some(first))?
+        } else {
+            self.owner_index(first)
+                .filter(|&ix| self.changesets[ix].first().as_ref() == Some(first))?
+        };
+        Some(self.hash_at::<D>(ix))
+    }
+
+    /// One value summarizing the sealed history: two replicas holding the
+    /// same changesets report the same heads-hash, and since every changeset
+    /// hash chains over its ancestors', equal values mean equal histories.
+    /// Combines the hashes of the changesets owning the current heads, so
+    /// once memoized the cost is `O(heads)`, not `O(history)`. Uncommitted
+    /// `pending` ops are not covered: heads they shadow are walked back to the
+    /// sealed frontier, so an open transaction never changes the value.
+    pub fn heads_hash<D: ContentDigest<P>>(&self) -> ContentHash {
+        let mut hashes: Vec<ContentHash> = Vec::with_capacity(self.heads.len());
+        let mut seen: HashSet<Dot> = HashSet::new();
+        let mut stack: Vec<Dot> = self.heads.iter().copied().collect();
+        while let Some(dot) = stack.pop() {
+            if !seen.insert(dot) {
+                continue;
+            }
+            // `pending` holds the newest local clocks, so one comparison
+            // classifies the dot without scanning.
+            let is_pending = self
+                .pending
+                .first()
+                .is_some_and(|first| dot.actor == self.actor && dot.clock >= first.id.clock);
+            if is_pending {
+                if let Some(op) = self.ops.get(&dot) {
+                    stack.extend(op.parents.iter().copied());
+                }
+            } else if let Some(ix) = self.owner_index(&dot) {
+                hashes.push(self.hash_at::<D>(ix));
+            }
+        }
+        content_hash::heads_hash(hashes)
+    }
+
+    /// Ordinal of the sealed changeset containing `dot`; degraded indexes
+    /// fall back to the full scan. `None` for pending ops.
+    fn owner_index(&self, dot: &Dot) -> Option<usize> {
+        if self.index_degraded {
+            return self.changesets.iter().position(|r| r.contains(dot));
+        }
+        self.cs_owner.get(dot).map(|&ix| ix as usize)
+    }
+
+    /// Memoized hash of `changesets[ix]`, filling in uncomputed ancestors
+    /// first. Parents inside the changeset are covered by their own entry;
+    /// every other parent contributes its owning changeset's hash, which is
+    /// what chains each hash to its history. Only owners sealed before `ix`
+    /// count: changesets seal parents-first, so outside degraded test graphs
+    /// that is every owner, and it keeps the walk acyclic.
+    fn hash_at<D: ContentDigest<P>>(&self, ix: usize) -> ContentHash {
+        let mut stack = vec![ix];
+        while let Some(&top) = stack.last() {
+            let cref = &self.changesets[top];
+            if cref.hash.get().is_some() {
+                stack.pop();
+                continue;
+            }
+            let ops: Vec<&Op<P>> = cref.dots().filter_map(|d| self.ops.get(&d)).collect();
+            let mut owners: Vec<usize> = ops
+                .iter()
+                .flat_map(|op| &op.parents)
+                .filter(|p| !cref.contains(p))
+                .filter_map(|p| self.owner_index(p))
+                .filter(|&owner| owner < top)
+                .collect();
+            owners.sort_unstable();
+            owners.dedup();
+            let unhashed = stack.len();
+            stack.extend(
+                owners
+                    .iter()
+                    .copied()
+                    .filter(|&owner| self.changesets[owner].hash.get().is_none()),
+            );
+            if stack.len() > unhashed {
+                continue;
+            }
+            let parent_hashes = owners
+                .iter()
+                .map(|&owner| self.changesets[owner].hash.get().copied())
+                .collect::<Option<Vec<_>>>()
+                .expect("owners are hashed before their dependents");
+            let _ = cref
+                .hash
+                .set(content_hash::changeset_hash::<P, D>(&ops, parent_hashes));
+            stack.pop();
+        }
+        self.changesets[ix]
+            .hash
+            .get()
+            .copied()
+            .expect("hashed by the walk above")
+    }
+
     /// Order is unstable across inserts; callers needing
     /// causality-respecting order should pass the result through
     /// [`OpGraph::topo_sort`].
@@ -457,11 +595,17 @@ impl<P: Clone> OpGraph<P> {
         if !self.pending.is_empty() {
             let ops = std::mem::take(&mut self.pending);
             let cref = ChangesetRef::from_ops(&ops);
-            if let Some(first) = cref.dots().next() {
-                self.cs_by_first.insert(first, self.changesets.len() as u32);
-            }
-            self.changesets.push_back(cref);
+            self.seal(cref);
+        }
+    }
+
+    /// Appends a sealed descriptor and points its dots at it in `cs_owner`.
+    fn seal(&mut self, cref: ChangesetRef) {
+        let ix = self.changesets.len() as u32;
+        for dot in cref.dots() {
+            self.cs_owner.insert(dot, ix);
         }
+        self.changesets.push_back(cref);
     }
 
     /// Atomicity invariant: each changeset in `self.changesets` is either
@@ -737,7 +881,7 @@ impl<P: Clone + Eq> OpGraph<P> {
                 && if self.index_degraded {
                     self.changesets.iter().any(|r| r.matches_ops(&cs.ops))
                 } else {
-                    self.cs_by_first
+                    self.cs_owner
                         .get(&cs.ops[0].id)
                         .and_then(|&ix| self.changesets.get(ix as usize))
                         .is_some_and(|r| r.matches_ops(&cs.ops))
@@ -772,10 +916,7 @@ impl<P: Clone + Eq> OpGraph<P> {
         for d in cref.dots() {
             self.try_promote_self_contained_mut(d, &mut scratch.promote_queue);
         }
-        if let Some(first) = cref.dots().next() {
-            self.cs_by_first.insert(first, self.changesets.len() as u32);
-        }
-        self.changesets.push_back(cref);
+        self.seal(cref);
         Ok(())
     }
 
@@ -2411,6 +2552,118 @@ mod tests {
         let all = g.missing_changesets_tolerant(&HashSet::new());
         assert_eq!(all.len(), 1);
     }
+
+    /// Test digest for `u32` payloads.
+    struct LeBytes;
+
+    impl ContentDigest<u32> for LeBytes {
+        fn digest_into(payload: &u32, out: &mut Vec<u8>) {
+            out.extend_from_slice(&payload.to_le_bytes());
+        }
+    }
+
+    #[test]
+    fn heads_hash_matches_across_replicas_with_same_history() {
+        let mut local: OpGraph<u32> = OpGraph::with_actor(1);
+        local.add_mut(1).unwrap();
+        local.add_mut(2).unwrap();
+        local.commit_mut();
+        local.add_mut(3).unwrap();
+        local.commit_mut();
+
+        let remote = OpGraph::from_changesets(local.changesets_as_vec()).unwrap();
+        assert_eq!(
+            remote.heads_hash::<LeBytes>(),
+            local.heads_hash::<LeBytes>()
+        );
+        for r in local.changesets() {
+            let first = r.first().unwrap();
+            assert_eq!(
+                remote.changeset_hash::<LeBytes>(&first),
+                local.changeset_hash::<LeBytes>(&first)
+            );
+        }
+
+        let mut diverged = local.clone();
+        diverged.add_mut(4).unwrap();
+        assert_eq!(
+            diverged.heads_hash::<LeBytes>(),
+            local.heads_hash::<LeBytes>(),
+            "pending ops are not covered"
+        );
+        diverged.commit_mut();
+        assert_ne!(
+            diverged.heads_hash::<LeBytes>(),
+            local.heads_hash::<LeBytes>()
+        );
+    }
+
+    #[test]
+    fn changeset_hash_chains_over_parent_changesets() {
+        let root_a = Op {
+            id: Dot::new(7, 0),
+            parents: vec![],
+            payload: 1,
+        };
+        let root_b = Op {
+            payload: 2,
+            ..root_a.clone()
+        };
+        let child = Op {
+            id: Dot::new(8, 0),
+            parents: vec![root_a.id],
+            payload: 3,
+        };
+        let a = OpGraph::from_changesets(vec![
+            crate::Changeset { ops: vec![root_a] },
+            crate::Changeset {
+                ops: vec![child.clone()],
+            },
+        ])
+        .unwrap();
+        let b = OpGraph::from_changesets(vec![
+            crate::Changeset { ops: vec![root_b] },
+            crate::Changeset { ops: vec![child] },
+        ])
+        .unwrap();
+        assert_ne!(
+            a.changeset_hash::<LeBytes>(&Dot::new(8, 0)),
+            b.changeset_hash::<LeBytes>(&Dot::new(8, 0)),
+            "identical ops over different parents hash differently"
+        );
+        assert_ne!(a.heads_hash::<LeBytes>(), b.heads_hash::<LeBytes>());
+    }
+
+    #[test]
+    fn owner_lookup_survives_degraded_index() {
+        let mut g: OpGraph<u32> = OpGraph::with_actor(1);
+        g.add_mut(1).unwrap();
+        g.add_mut(2).unwrap();
+        g.commit_mut();
+        let indexed = g.clone();
+        g.debug_force_index_degraded();
+        assert_eq!(g.heads_hash::<LeBytes>(), indexed.heads_hash::<LeBytes>());
+        assert_eq!(
+            g.changeset_hash::<LeBytes>(&Dot::new(1, 0)),
+            indexed.changeset_hash::<LeBytes>(&Dot::new(1, 0))
+        );
+    }
+
+    #[test]
+    fn owner_index_resolves_ops_inside_long_changesets() {
+        let mut g: OpGraph<u32> = OpGraph::with_actor(1);
+        for i in 0..10_000 {
+            g.add_mut(i).unwrap();
+        }
+        g.commit_mut();
+        g.add_mut(0).unwrap();
+        g.commit_mut();
+        assert_eq!(g.owner_index(&Dot::new(1, 9_999)), Some(0));
+        assert_eq!(g.owner_index(&Dot::new(1, 10_000)), Some(1));
+        let mut degraded = g.clone();
+        degraded.debug_force_index_degraded();
+        assert_eq!(g.heads_hash::<LeBytes>(), degraded.heads_hash::<LeBytes>());
+    }
 }
 
 #[cfg(test)]
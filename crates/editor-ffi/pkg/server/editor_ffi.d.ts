@@ -91,17 +91,62 @@ export type Bind = "left" | "right";
 /**
  *Auto-generated discriminant enum variants
  */
-export type ModifierType = "bold" | "italic" | "underline" | "strikethrough" | "font_size" | "font_family" | "font_weight" | "text_color" | "background_color" | "letter_spacing" | "link" | "ruby" | "line_height" | "block_gap" | "paragraph_indent" | "alignment";
+export type ModifierType = "bold" | "italic" | "underline" | "strikethrough" | "font_size" | "font_family" | "font_weight" | "text_color" | "background_color" | "letter_spacing" | "link" | "ruby" | "line_height" | "block_gap" | "paragraph_indent" | "alignment" | "direction";
 
 /**
  *Auto-generated discriminant enum variants
  */
-export type NodeType = "root" | "paragraph" | "blockquote" | "callout" | "text" | "bullet_list" | "ordered_list" | "list_item" | "fold" | "fold_title" | "fold_content" | "table" | "table_row" | "table_cell" | "image" | "file" | "embed" | "archived" | "hard_break" | "horizontal_rule" | "page_break" | "tab" | "unknown";
+export type NodeType = "root" | "paragraph" | "blockquote" | "callout" | "text" | "bullet_list" | "ordered_list" | "list_item" | "fold" | "fold_title" | "fold_content" | "table" | "table_row" | "table_cell" | "image" | "file" | "embed" | "archived" | "hard_break" | "horizontal_rule" | "page_break" | "tab" | "column_break" | "unknown";
+
+export interface AccessibilityNode {
+    id: Dot;
+    role: AccessibilityRole;
+    parent: Dot | undefined;
+    children: Dot[];
+    text: string;
+    label: string | undefined;
+    href: string | undefined;
+    bounds: PageRect[];
+}
+
+export interface AccessibilityTextPosition {
+    node: Dot;
+    offset: number;
+}
+
+/**
+ * A collapsed selection (anchor equal to focus) is the caret.
+ */
+export interface AccessibilityTextSelection {
+    anchor: AccessibilityTextPosition;
+    focus: AccessibilityTextPosition;
+}
+
+export interface AccessibilityTree {
+    root: Dot | undefined;
+    nodes: AccessibilityNode[];
+    selection: AccessibilityTextSelection | undefined;
+}
+
+/**
+ * Changes between two trees: nodes that are new or differ, nodes that are
+ * gone, and the current selection.
+ */
+export interface AccessibilityUpdate {
+    root: Dot | undefined;
+    updated: AccessibilityNode[];
+    removed: Dot[];
+    selection: AccessibilityTextSelection | undefined;
+}
 
 export interface AlignmentValue {
     value: Alignment;
 }
 
+export interface DirectionValue {
+    value: TextDirection;
+}
+
 export interface AnchorPaths {
     paths: number[][];
 }
@@ -148,6 +193,12 @@ export interface ChangesetEntry {
     bytes: Uint8Array;
 }
 
+export interface CharacterCount {
+    with_whitespace: number;
+    without_whitespace: number;
+    without_whitespace_and_punctuation: number;
+}
+
 export interface CharacterCounts {
     doc_with_whitespace: number;
     doc_without_whitespace: number;
@@ -166,6 +217,15 @@ export interface ClipboardPayload {
     text: string;
 }
 
+export interface DocxAttachment {
+    /**
+     * File name of the media part, e.g. `image1.png`.
+     */
+    name: string;
+    content_type: string;
+    data: Uint8Array;
+}
+
 export interface CollectResult {
     heads: Uint8Array;
     statuses: BundleStatus[];
@@ -190,6 +250,11 @@ export interface CursorMetrics {
     page_idx: number;
     caret: Rect;
     line: Rect;
+    /**
+     * Second caret at a direction boundary, where the opposite affinity's
+     * character sits.
+     */
+    secondary_caret: Rect | undefined;
 }
 
 export interface DecorationStyle {
@@ -199,6 +264,30 @@ export interface DecorationStyle {
     underline: Underline | undefined;
 }
 
+export interface DocxOptions {
+    title: string | undefined;
+    author: string | undefined;
+    language: string | undefined;
+    embedFonts?: boolean;
+}
+
+export interface EditorTelemetry {
+    ticks: TickTrace[];
+    frames: FrameTrace[];
+    /**
+     * Traces evicted from the rings since the last drain.
+     */
+    dropped: number;
+}
+
+export interface EpubMetadata {
+    title: string;
+    author: string | undefined;
+    language: string | undefined;
+    identifier: string | undefined;
+    modified: string | undefined;
+}
+
 export interface ExpansionAffordances {
     word: boolean;
     sentence: boolean;
@@ -257,6 +346,21 @@ export interface Fragment {
     children?: Fragment[];
 }
 
+export interface FrameTrace {
+    page: number;
+    revision: Revision;
+    /**
+     * Display-list build under the editor lock.
+     */
+    display_list_micros: number;
+    raster_micros: number;
+    damage_rects: number;
+    /**
+     * Damaged device pixels, overlapping rects counted once per rect.
+     */
+    damage_area: number;
+}
+
 export interface GraphWithAnchors {
     graph: Uint8Array;
     anchors: StableSelection[];
@@ -350,6 +454,7 @@ export interface ModifierState {
     block_gap: Tri<BlockGapValue>;
     paragraph_indent: Tri<ParagraphIndentValue>;
     alignment: Tri<AlignmentValue>;
+    direction: Tri<DirectionValue>;
     effective_bold: Tri<undefined>;
     cell_background_color: Tri<BackgroundColorValue> | undefined;
 }
@@ -368,6 +473,14 @@ export interface PartitionedChangesets {
     blocked: Uint8Array;
 }
 
+export interface PdfMetadata {
+    title: string | undefined;
+    author: string | undefined;
+    subject: string | undefined;
+    keywords: string | undefined;
+    creator: string | undefined;
+}
+
 export interface PlaceholderMetrics {
     page_idx: number;
     rect: Rect;
@@ -387,6 +500,8 @@ export interface PlainBlockquoteNode {
 
 export interface PlainBulletListNode {}
 
+export interface PlainColumnBreakNode {}
+
 export interface PlainCalloutNode {
     variant?: CalloutVariant;
 }
@@ -486,6 +601,15 @@ export interface Rect {
     height: number;
 }
 
+export interface ReplaySummary {
+    /**
+     * Inbound calls replayed, not counting the final state check.
+     */
+    entries: number;
+    ticks: number;
+    revision: Revision;
+}
+
 export interface RequestId {
     value: number;
 }
@@ -551,6 +675,12 @@ export interface StableSelection {
     head: StablePosition;
 }
 
+export interface StatisticsOptions {
+    exclude_fold_content?: boolean;
+    exclude_tables?: boolean;
+    exclude_blockquotes?: boolean;
+}
+
 export interface TableOverlay {
     table_id: Dot;
     page_idx: number;
@@ -594,12 +724,77 @@ export interface TextColorValue {
     value: string;
 }
 
+export interface ThemeColor {
+    token: string;
+    /**
+     * `#rrggbb` or `#rrggbbaa`.
+     */
+    value: string;
+}
+
+/**
+ * A host-supplied theme, typically the web app's design tokens.
+ */
+export interface ThemeDefinition {
+    /**
+     * Built-in variant supplying every token `colors` leaves out. Without
+     * one, `colors` must cover every token in [`theme_tokens`].
+     */
+    base: ThemeVariant | undefined;
+    colors: ThemeColor[];
+}
+
+export interface ThumbnailImage {
+    id: string;
+    width: number;
+    height: number;
+    rgba: Uint8Array;
+}
+
 export interface TickResult {
     revision: Revision;
     events: EditorEvent[];
     request_outcomes: RequestOutcome[];
 }
 
+export interface TickSpan {
+    kind: TickSpanKind;
+    micros: number;
+    /**
+     * Messages for a request, changesets for a coalesced remote batch, else 1.
+     */
+    count: number;
+}
+
+export interface TickTrace {
+    /**
+     * The revision the tick published, or `None` when it changed nothing.
+     */
+    revision: Revision | undefined;
+    total_micros: number;
+    /**
+     * Local requests, templates and resource updates.
+     */
+    apply_micros: number;
+    /**
+     * Remote changesets and document replacement.
+     */
+    projection_micros: number;
+    /**
+     * Measuring and layout reconciliation, pagination excluded.
+     */
+    layout_micros: number;
+    pagination_micros: number;
+    /**
+     * Tracked ranges, spellcheck, statistics, effects and events.
+     */
+    publish_micros: number;
+    blocks_remeasured: number;
+    pages_repaginated: number;
+    spans: TickSpan[];
+    dropped_spans: number;
+}
+
 export interface TrackedRange {
     id: string;
     group: string;
@@ -646,8 +841,39 @@ export interface ViewportAnchorPoint {
     y: number;
 }
 
+export interface WritingStatistics {
+    words: number;
+    sentences: number;
+    /**
+     * Textblocks with any visible text.
+     */
+    paragraphs: number;
+    /**
+     * 200-cell 원고지 pages, each paragraph starting on a fresh line.
+     */
+    manuscript_pages: number;
+    reading_seconds: number;
+}
+
+export interface WritingStatisticsReport {
+    doc: WritingStatistics;
+    selection: WritingStatistics;
+}
+
+export type AccessibilityRole = "document" | "paragraph" | "list" | "list_item" | "table" | "table_row" | "table_cell" | "link" | "image" | "separator" | "group";
+
+/**
+ * `Left` and `Right` name the paragraph's start and end edges, so they
+ * mirror in a right-to-left paragraph.
+ */
 export type Alignment = "left" | "center" | "right" | "justify";
 
+/**
+ * Base direction a paragraph's bidi resolution starts from. `Auto` takes it
+ * from the paragraph's first strong character, as `dir="auto"` does in HTML.
+ */
+export type TextDirection = "auto" | "ltr" | "rtl";
+
 export type ArchivedNodeAttr = { type: "id"; value: string | undefined };
 
 export type AttachmentPlaceholderKind = "image" | "file";
@@ -660,7 +886,7 @@ export type BlockquoteNodeAttr = { type: "variant"; value: BlockquoteVariant };
 
 export type BlockquoteVariant = "left_line" | "left_quote" | "message_sent" | "message_received";
 
-export type Break = "line" | "paragraph" | "page";
+export type Break = "line" | "paragraph" | "page" | "column";
 
 export type BulletListNodeAttr = void;
 
@@ -670,7 +896,9 @@ export type CalloutNodeAttr = { type: "variant"; value: CalloutVariant };
 
 export type CalloutVariant = "info" | "success" | "warning" | "danger";
 
-export type ClipboardOp = { type: "paste"; html: string | undefined; text: string } | { type: "repaste_as_text" } | { type: "cut" };
+export type ClipboardOp = { type: "paste"; html: string | undefined; text: string } | { type: "paste_docx"; request_id: string; data: Uint8Array } | { type: "repaste_as_text" } | { type: "cut" };
+
+export type ColumnBreakNodeAttr = void;
 
 export type CommandOutcome = { type: "applied" } | { type: "rejected"; reason: CommandRejection };
 
@@ -686,7 +914,7 @@ export type DndOp = { type: "start_internal_selection" } | { type: "enter_extern
 
 export type Dot = string;
 
-export type EditorEvent = { type: "state_changed"; fields: StateField[] } | { type: "render_invalidated" } | { type: "font_data_missing"; family: string; weight: number; required: FontData[]; prefetch: FontData[] } | { type: "cursor_exited_document_start" } | { type: "ime_resync_required" } | { type: "tracked_range_replace_result"; id: string; outcome: TrackedRangeReplaceOutcome } | { type: "tracked_ranges_stale"; ids: string[] } | { type: "prose_range_install_result"; outcome: ProseRangeInstallOutcome } | { type: "attachment_placeholders_inserted"; request_id: string; node_ids: Dot[] };
+export type EditorEvent = { type: "state_changed"; fields: StateField[] } | { type: "render_invalidated" } | { type: "font_data_missing"; family: string; weight: number; required: FontData[]; prefetch: FontData[] } | { type: "cursor_exited_document_start" } | { type: "ime_resync_required" } | { type: "tracked_range_replace_result"; id: string; outcome: TrackedRangeReplaceOutcome } | { type: "tracked_ranges_stale"; ids: string[] } | { type: "prose_range_install_result"; outcome: ProseRangeInstallOutcome } | { type: "attachment_placeholders_inserted"; request_id: string; node_ids: Dot[] } | { type: "typography_normalized"; replacements: number };
 
 export type Effect = { load_font: { family: string; weight: number; codepoints: number[] } };
 
@@ -730,7 +958,7 @@ export type InteractiveHit = { type: "fold_title"; id: Dot; text_rect: Rect | un
 
 export type Key = "enter" | "backspace" | "delete" | "tab" | "escape";
 
-export type LayoutMode = { type: "paginated"; page_width: number; page_height: number; page_margin_top: number; page_margin_bottom: number; page_margin_left: number; page_margin_right: number } | { type: "continuous"; max_width: number };
+export type LayoutMode = { type: "paginated"; page_width: number; page_height: number; page_margin_top: number; page_margin_bottom: number; page_margin_left: number; page_margin_right: number; columns?: number; column_gap?: number } | { type: "continuous"; max_width: number } | { type: "manuscript"; columns: number; rows: number; cell_size: number; row_gap: number; page_margin: number };
 
 export type ListItemNodeAttr = void;
 
@@ -738,9 +966,9 @@ export type ListKind = "bullet" | "ordered";
 
 export type ListOp = { type: "toggle_kind"; kind: ListKind } | { type: "indent" } | { type: "outdent" };
 
-export type Message = { type: "key"; event: KeyEvent } | { type: "insertion"; op: InsertionOp } | { type: "deletion"; op: DeletionOp } | { type: "selection"; op: SelectionOp } | { type: "modifier"; op: ModifierOp } | { type: "node"; op: NodeOp } | { type: "block"; op: BlockOp } | { type: "list"; op: ListOp } | { type: "view"; op: ViewOp } | { type: "clipboard"; op: ClipboardOp } | { type: "text_input"; ops: FlatImeOp[] } | { type: "dnd"; op: DndOp } | { type: "navigation"; op: NavigationOp } | { type: "history"; op: HistoryOp } | { type: "system"; event: SystemEvent } | { type: "tracked_range"; op: TrackedRangeOp };
+export type Message = { type: "key"; event: KeyEvent } | { type: "insertion"; op: InsertionOp } | { type: "deletion"; op: DeletionOp } | { type: "selection"; op: SelectionOp } | { type: "modifier"; op: ModifierOp } | { type: "node"; op: NodeOp } | { type: "block"; op: BlockOp } | { type: "list"; op: ListOp } | { type: "view"; op: ViewOp } | { type: "clipboard"; op: ClipboardOp } | { type: "text_input"; ops: FlatImeOp[] } | { type: "dnd"; op: DndOp } | { type: "navigation"; op: NavigationOp } | { type: "history"; op: HistoryOp } | { type: "system"; event: SystemEvent } | { type: "tracked_range"; op: TrackedRangeOp } | { type: "typography"; op: TypographyOp };
 
-export type Modifier = { type: "bold" } | { type: "italic" } | { type: "underline" } | { type: "strikethrough" } | { type: "font_size"; value: number } | { type: "font_family"; value: string } | { type: "font_weight"; value: number } | { type: "text_color"; value: string } | { type: "background_color"; value: string } | { type: "letter_spacing"; value: number } | { type: "link"; href: string } | { type: "ruby"; text: string } | { type: "line_height"; value: number } | { type: "block_gap"; value: number } | { type: "paragraph_indent"; value: number } | { type: "alignment"; value: Alignment };
+export type Modifier = { type: "bold" } | { type: "italic" } | { type: "underline" } | { type: "strikethrough" } | { type: "font_size"; value: number } | { type: "font_family"; value: string } | { type: "font_weight"; value: number } | { type: "text_color"; value: string } | { type: "background_color"; value: string } | { type: "letter_spacing"; value: number } | { type: "link"; href: string } | { type: "ruby"; text: string } | { type: "line_height"; value: number } | { type: "block_gap"; value: number } | { type: "paragraph_indent"; value: number } | { type: "alignment"; value: Alignment } | { type: "direction"; value: TextDirection };
 
 export type ModifierOp = { type: "toggle"; modifier_type: ModifierType } | { type: "set"; modifier: Modifier } | { type: "set_on_node"; id: Dot; modifier: Modifier } | { type: "edit"; modifier_type: ModifierType; modifier: Modifier | undefined } | { type: "clear_all" };
 
@@ -748,7 +976,7 @@ export type Movement = { type: "grapheme"; direction: Direction } | { type: "wor
 
 export type NavigationOp = { type: "move"; movement: Movement; extend: boolean };
 
-export type NodeAttr = { type: "root"; attr: RootNodeAttr } | { type: "paragraph"; attr: ParagraphNodeAttr } | { type: "blockquote"; attr: BlockquoteNodeAttr } | { type: "callout"; attr: CalloutNodeAttr } | { type: "text"; attr: TextNodeAttr } | { type: "bullet_list"; attr: BulletListNodeAttr } | { type: "ordered_list"; attr: OrderedListNodeAttr } | { type: "list_item"; attr: ListItemNodeAttr } | { type: "fold"; attr: FoldNodeAttr } | { type: "fold_title"; attr: FoldTitleNodeAttr } | { type: "fold_content"; attr: FoldContentNodeAttr } | { type: "table"; attr: TableNodeAttr } | { type: "table_row"; attr: TableRowNodeAttr } | { type: "table_cell"; attr: TableCellNodeAttr } | { type: "image"; attr: ImageNodeAttr } | { type: "file"; attr: FileNodeAttr } | { type: "embed"; attr: EmbedNodeAttr } | { type: "archived"; attr: ArchivedNodeAttr } | { type: "hard_break"; attr: HardBreakNodeAttr } | { type: "horizontal_rule"; attr: HorizontalRuleNodeAttr } | { type: "page_break"; attr: PageBreakNodeAttr } | { type: "tab"; attr: TabNodeAttr } | { type: "unknown"; tag: number; bytes: number[] };
+export type NodeAttr = { type: "root"; attr: RootNodeAttr } | { type: "paragraph"; attr: ParagraphNodeAttr } | { type: "blockquote"; attr: BlockquoteNodeAttr } | { type: "callout"; attr: CalloutNodeAttr } | { type: "text"; attr: TextNodeAttr } | { type: "bullet_list"; attr: BulletListNodeAttr } | { type: "ordered_list"; attr: OrderedListNodeAttr } | { type: "list_item"; attr: ListItemNodeAttr } | { type: "fold"; attr: FoldNodeAttr } | { type: "fold_title"; attr: FoldTitleNodeAttr } | { type: "fold_content"; attr: FoldContentNodeAttr } | { type: "table"; attr: TableNodeAttr } | { type: "table_row"; attr: TableRowNodeAttr } | { type: "table_cell"; attr: TableCellNodeAttr } | { type: "image"; attr: ImageNodeAttr } | { type: "file"; attr: FileNodeAttr } | { type: "embed"; attr: EmbedNodeAttr } | { type: "archived"; attr: ArchivedNodeAttr } | { type: "hard_break"; attr: HardBreakNodeAttr } | { type: "horizontal_rule"; attr: HorizontalRuleNodeAttr } | { type: "page_break"; attr: PageBreakNodeAttr } | { type: "tab"; attr: TabNodeAttr } | { type: "column_break"; attr: ColumnBreakNodeAttr } | { type: "unknown"; tag: number; bytes: number[] };
 
 export type NodeOp = { type: "delete"; id: Dot } | { type: "cycle_callout_variant"; id: Dot } | { type: "set_attr"; id: Dot; attr: NodeAttr } | { type: "set_attrs"; id: Dot; attrs: PlainNode } | { type: "unwrap"; id: Dot } | { type: "table"; id: Dot; op: TableOp };
 
@@ -762,12 +990,14 @@ export type PendingModifier = { type: "set"; modifier: Modifier } | { type: "uns
 
 export type PendingModifiers = PendingModifier[];
 
-export type PlainNode = ({ type: "root" } & PlainRootNode) | ({ type: "paragraph" } & PlainParagraphNode) | ({ type: "blockquote" } & PlainBlockquoteNode) | ({ type: "callout" } & PlainCalloutNode) | ({ type: "text" } & PlainTextNode) | ({ type: "bullet_list" } & PlainBulletListNode) | ({ type: "ordered_list" } & PlainOrderedListNode) | ({ type: "list_item" } & PlainListItemNode) | ({ type: "fold" } & PlainFoldNode) | ({ type: "fold_title" } & PlainFoldTitleNode) | ({ type: "fold_content" } & PlainFoldContentNode) | ({ type: "table" } & PlainTableNode) | ({ type: "table_row" } & PlainTableRowNode) | ({ type: "table_cell" } & PlainTableCellNode) | ({ type: "image" } & PlainImageNode) | ({ type: "file" } & PlainFileNode) | ({ type: "embed" } & PlainEmbedNode) | ({ type: "archived" } & PlainArchivedNode) | ({ type: "hard_break" } & PlainHardBreakNode) | ({ type: "horizontal_rule" } & PlainHorizontalRuleNode) | ({ type: "page_break" } & PlainPageBreakNode) | ({ type: "tab" } & PlainTabNode) | { type: "unknown" };
+export type PlainNode = ({ type: "root" } & PlainRootNode) | ({ type: "paragraph" } & PlainParagraphNode) | ({ type: "blockquote" } & PlainBlockquoteNode) | ({ type: "callout" } & PlainCalloutNode) | ({ type: "text" } & PlainTextNode) | ({ type: "bullet_list" } & PlainBulletListNode) | ({ type: "ordered_list" } & PlainOrderedListNode) | ({ type: "list_item" } & PlainListItemNode) | ({ type: "fold" } & PlainFoldNode) | ({ type: "fold_title" } & PlainFoldTitleNode) | ({ type: "fold_content" } & PlainFoldContentNode) | ({ type: "table" } & PlainTableNode) | ({ type: "table_row" } & PlainTableRowNode) | ({ type: "table_cell" } & PlainTableCellNode) | ({ type: "image" } & PlainImageNode) | ({ type: "file" } & PlainFileNode) | ({ type: "embed" } & PlainEmbedNode) | ({ type: "archived" } & PlainArchivedNode) | ({ type: "hard_break" } & PlainHardBreakNode) | ({ type: "horizontal_rule" } & PlainHorizontalRuleNode) | ({ type: "page_break" } & PlainPageBreakNode) | ({ type: "tab" } & PlainTabNode) | ({ type: "column_break" } & PlainColumnBreakNode) | { type: "unknown" };
 
 export type PointerStyle = "default" | "text" | "pointer";
 
 export type ProseRangeInstallOutcome = { type: "applied" } | { type: "text_mismatch" } | { type: "invalid_ranges"; indices: number[] } | { type: "invalid_request" };
 
+export type QuoteLocale = "english" | "korean";
+
 export type RootNodeAttr = { type: "layout_mode"; value: LayoutMode };
 
 export type SelectionExpansionUnit = "word" | "sentence" | "paragraph" | "all";
@@ -796,12 +1026,16 @@ export type TextNodeAttr = void;
 
 export type ThemeVariant = "dark-black" | "dark-charcoal" | "dark-espresso" | "dark-graphite" | "dark-midnight" | "dark-navy" | "dark-obsidian" | "dark-storm" | "light-butter" | "light-latte" | "light-lavender" | "light-mint" | "light-peach" | "light-rose" | "light-snow" | "light-white";
 
+export type TickSpanKind = "request" | "resource" | "remote" | "set_document" | "insert_template";
+
 export type TrackedRangeOp = { type: "add"; id: string; group: string; selection: Selection; metadata?: string; invalidate_on_text_change?: boolean } | { type: "add_frozen"; id: string; group: string; selection: StableSelection; metadata?: string } | { type: "remove"; id: string } | { type: "set_group"; id: string; group: string } | { type: "clear_group"; group: string } | { type: "replace_groups_from_prose"; expected_text: string; groups: string[]; ranges: ProseTrackedRangeRegistration[] } | { type: "invalidate"; id: string } | { type: "set_group_decoration"; group: string; style: DecorationStyle; enabled: boolean; z_index?: number } | { type: "remove_group_decoration"; group: string } | { type: "replace_text"; id: string; expected_text?: string | undefined; replacement: string };
 
 export type TrackedRangeReplaceOutcome = "replaced" | "unknown_id" | "invalid" | "text_mismatch" | "invalid_replacement";
 
 export type Tri<T> = { type: "absent" } | { type: "uniform"; value: T } | { type: "mixed" };
 
+export type TypographyOp = { type: "normalize"; locale: QuoteLocale; whole_document?: boolean };
+
 export type UnderlineStyle = "solid" | "dashed" | "wavy";
 
 export type ViewOp = { type: "toggle_fold"; id: Dot } | { type: "expand_folds_for_selection" } | { type: "expand_folds_for_tracked_range"; id: string };
@@ -817,6 +1051,16 @@ declare class Editor {
     private constructor();
     free(): void;
     [Symbol.dispose](): void;
+    /**
+     * Full accessibility snapshot. Later `accessibility_update` calls are
+     * relative to it.
+     */
+    accessibility_tree(): AccessibilityTree;
+    /**
+     * Accessibility nodes added, changed or removed since the last snapshot
+     * or update. Hosts call this after each tick.
+     */
+    accessibility_update(): AccessibilityUpdate;
     block_state(): BlockState | undefined;
     capture_selection_viewport_anchor(revision: Revision): CapturedViewportAnchor | undefined;
     capture_viewport_anchor_at(revision: Revision, point: ViewportAnchorPoint): CapturedViewportAnchor | undefined;
@@ -834,10 +1078,41 @@ declare class Editor {
     cursor_hit_rects(): PageRect[];
     cursor_hit_test(page: number, x: number, y: number): boolean;
     enqueue_request(messages: Message[]): RequestId;
+    /**
+     * Packages the document as a Word document, optionally with the fonts it
+     * uses embedded as subsets.
+     */
+    export_docx(options: DocxOptions): Uint8Array;
+    /**
+     * Packages the document as an EPUB 3 book, one chapter per page break,
+     * with the fonts it uses embedded as subsets.
+     */
+    export_epub(metadata: EpubMetadata): Uint8Array;
+    export_page_svg(page: number): string;
     export_page_vector(page: number, scale_factor: number): Uint8Array;
+    /**
+     * Lays out every page and writes them as one PDF with embedded font
+     * subsets, link annotations and a bookmark outline from the document's
+     * folds.
+     */
+    export_pdf(metadata: PdfMetadata): Uint8Array;
+    /**
+     * The selected area of each page it spans, stacked into one SVG document.
+     * `None` when nothing is selected.
+     */
+    export_selection_svg(): string | undefined;
     external_elements(): ExternalElement[];
     find_matches(query: string, options?: SearchOptions | null): Selection[];
+    /**
+     * Stops recording and returns the encoded session.
+     */
+    finish_recording(): Uint8Array;
     freeze_selection(selection: Selection): StableSelection | undefined;
+    /**
+     * Hex SHA-256 over the sealed history's heads. Two replicas reporting the
+     * same value hold the same changesets; uncommitted ops are not covered.
+     */
+    heads_hash(): string;
     ime(before_limit: number, after_limit: number): Ime | undefined;
     insert_template_fragment(changesets: Uint8Array): void;
     inspect_selection_as_slice_macro(): string | undefined;
@@ -881,14 +1156,37 @@ declare class Editor {
     selection_hit_rects(): PageRect[];
     selection_hit_test(page: number, x: number, y: number): boolean;
     set_doc(plain: PlainDoc): void;
+    /**
+     * Keeps traces of the last `capacity` ticks and page rasterizations for
+     * `take_telemetry`. `0` turns tracing off and drops collected traces.
+     */
+    set_telemetry_capacity(capacity: number): void;
+    /**
+     * Corrections for a range in the `misspelling` tracked-range group.
+     */
+    spelling_suggestions(id: string): string[];
     split_changesets(payload: Uint8Array): ChangesetEntry[];
+    /**
+     * Starts logging every inbound call so the session can be replayed with
+     * `EditorHost.replay_recording`.
+     */
+    start_recording(): void;
     table_overlays(): TableOverlay[];
+    /**
+     * Drains the traces collected since the last call, oldest first.
+     */
+    take_telemetry(): EditorTelemetry;
     tick(): TickResult | undefined;
     tick_through(request_id: RequestId): TickResult;
     tracked_range(id: string): TrackedRange | undefined;
     tracked_ranges(group?: string | null): TrackedRange[];
     tracked_ranges_at(page: number, x: number, y: number, group?: string | null): TrackedRangeHit[];
     tracked_ranges_containing_selection(selection: Selection, group?: string | null): TrackedRangeEndpoints[];
+    /**
+     * Word, sentence, paragraph, 원고지 page and reading-time counts for
+     * the document and the selection.
+     */
+    writing_statistics(options: StatisticsOptions): WritingStatisticsReport;
 }
 
 declare class EditorHost {
@@ -898,17 +1196,71 @@ declare class EditorHost {
     add_font_base(family: string, weight: number, data: Uint8Array): ResourceUpdate | undefined;
     add_font_chunk(family: string, weight: number, chunk_id: number, data: Uint8Array): ResourceUpdate | undefined;
     add_font_manifest(family: string, weight: number, data: Uint8Array): ResourceUpdate | undefined;
+    /**
+     * Registers zstd-compressed TeX hyphenation patterns for `language`.
+     * They take effect once `language` is the hyphenation language.
+     */
+    add_hyphenation_patterns(language: string, data: Uint8Array): ResourceUpdate;
+    /**
+     * Stores decoded straight-alpha RGBA8 pixels for the image nodes whose
+     * id is `id`, so exports and offline renders can draw them.
+     */
+    add_image(id: string, width: number, height: number, rgba: Uint8Array): ResourceUpdate | undefined;
+    /**
+     * Turns spellchecking off and drops every misspelling range.
+     */
+    clear_spellcheck_dictionary(): ResourceUpdate | undefined;
     static create(icu_data: Uint8Array): EditorHost;
     create_editor_from_doc(doc: PlainDoc, viewport: Viewport): Editor;
     create_editor_from_graph(changesets: Uint8Array, viewport: Viewport): Editor;
     create_editor_from_graph_with_pending(server: Uint8Array, pending_encoded: Uint8Array, viewport: Viewport): Editor;
+    /**
+     * The images of a Word document, in the order `ClipboardOp::PasteDocx`
+     * reports their placeholders, for the host to upload into them.
+     */
+    docx_attachments(data: Uint8Array): DocxAttachment[];
+    /**
+     * Loads `state!` macro text, e.g. from `Editor.inspect_state_as_macro`,
+     * with its selection and pending modifiers.
+     */
+    create_editor_from_state_macro(text: string, viewport: Viewport): Editor;
     extract_text_from_graph(changesets: Uint8Array): string;
     graph_heads(changesets: Uint8Array): Uint8Array;
+    /**
+     * Renders one page of a plain document to a PNG at `scale`, without
+     * creating an editor. Images not in `images` draw as placeholders.
+     */
+    remove_image(id: string): ResourceUpdate | undefined;
+    render_thumbnail_from_doc(doc: PlainDoc, viewport: Viewport, page: number, scale: number, images: ThumbnailImage[]): Uint8Array;
+    /**
+     * Renders one page of an encoded changeset stream to a PNG at `scale`.
+     */
+    render_thumbnail_from_graph(changesets: Uint8Array, viewport: Viewport, page: number, scale: number, images: ThumbnailImage[]): Uint8Array;
+    /**
+     * Replays a recording from `Editor.finish_recording` on a fresh editor
+     * using this host's resources, failing at the first tick that differs.
+     */
+    replay_recording(recording: Uint8Array): ReplaySummary;
     root_attrs_from_graph(changesets: Uint8Array): PlainRootNode;
     root_modifiers_from_graph(changesets: Uint8Array): Modifier[];
     set_auto_surround_enabled(enabled: boolean): ResourceUpdate | undefined;
     set_fonts(families: FontFamily[]): ResourceUpdate | undefined;
+    /**
+     * Hyphenates Latin-script words with `language`'s patterns; `None`
+     * leaves only soft hyphens as break points inside words.
+     */
+    set_hyphenation_language(language?: string | null): ResourceUpdate | undefined;
+    /**
+     * Checks spelling against zstd-compressed Hunspell `.aff`/`.dic` data.
+     * Misspellings appear as ranges in the `misspelling` tracked-range group.
+     */
+    set_spellcheck_dictionary(aff: Uint8Array, dic: Uint8Array): ResourceUpdate | undefined;
     set_text_replacement_rules(rules: RawTextReplacementRule[]): ResourceUpdate | undefined;
+    /**
+     * Replaces the theme with `definition`, e.g. the web app's design
+     * tokens. Fails without changing anything if a token or color is invalid.
+     */
+    set_theme(definition: ThemeDefinition): ResourceUpdate | undefined;
     set_theme_variant(variant: ThemeVariant): ResourceUpdate | undefined;
 }
 
@@ -0,0 +1,537 @@
+//! Native bulk maintenance over stored changeset streams — the engine behind
+//! the `editor-server` binary. Runs the same code paths as `EditorServer`
+//! (`materialize`, `zombie_dots`, `sweep`, `consolidate`, `revert`,
+//! `verify_plain`) without a wasm host, ICU data or network access, and yields
+//! one [`DocumentReport`] per input document.
+
+use std::fs::File;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::sync::mpsc;
+use std::thread;
+
+use serde::Serialize;
+
+use crate::prelude::*;
+use crate::server::{collect_zombie_dots, revert_bytes, sweep_bytes};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    /// Degraded projection, zombie count and the consolidation saving, read-only.
+    Report,
+    Materialize,
+    Verify,
+    Zombies,
+    Sweep,
+    Consolidate,
+    Revert,
+}
+
+impl Command {
+    pub fn parse(name: &str) -> Option<Self> {
+        Some(match name {
+            "report" => Self::Report,
+            "materialize" => Self::Materialize,
+            "verify" => Self::Verify,
+            "zombies" => Self::Zombies,
+            "sweep" => Self::Sweep,
+            "consolidate" => Self::Consolidate,
+            "revert" => Self::Revert,
+            _ => return None,
+        })
+    }
+}
+
+/// Where a document's changeset stream comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    File(PathBuf),
+    Stdin,
+}
+
+impl Source {
+    fn label(&self) -> String {
+        match self {
+            Self::File(path) => path.display().to_string(),
+            Self::Stdin => "-".to_owned(),
+        }
+    }
+
+    fn read(&self) -> io::Result<Vec<u8>> {
+        match self {
+            Self::File(path) => std::fs::read(path),
+            Self::Stdin => {
+                let mut bytes = Vec::new();
+                io::stdin().lock().read_to_end(&mut bytes)?;
+                Ok(bytes)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Job {
+    pub source: Source,
+    /// `revert` target; empty for every other command.
+    pub target_heads: Vec<editor_crdt::Dot>,
+}
+
+/// A job that cannot run, reported as that document's failure.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JobError {
+    pub spec: String,
+    pub message: String,
+}
+
+impl JobError {
+    fn new(spec: &str, message: impl Into<String>) -> Self {
+        Self {
+            spec: spec.to_owned(),
+            message: message.into(),
+        }
+    }
+
+    fn into_report(self) -> DocumentReport {
+        DocumentReport {
+            path: self.spec,
+            error: Some(self.message),
+            ..DocumentReport::default()
+        }
+    }
+}
+
+impl Job {
+    /// `<path>` or, for `revert`, `<path>@<dot>,<dot>...`. `-` reads stdin.
+    pub fn parse(spec: &str, command: Command) -> Result<Self, JobError> {
+        let (path, heads) = match (command, spec.rsplit_once('@')) {
+            (Command::Revert, Some((path, heads))) => (path, heads),
+            (Command::Revert, None) => {
+                return Err(JobError::new(spec, "revert needs <path>@<heads>"));
+            }
+            _ => (spec, ""),
+        };
+        let target_heads = heads
+            .split(',')
+            .filter(|s| !s.is_empty())
+            .map(|s| {
+                s.parse()
+                    .map_err(|_| JobError::new(spec, format!("bad head {s:?}")))
+            })
+            .collect::<Result<_, _>>()?;
+        let source = match path {
+            "-" => Source::Stdin,
+            _ => Source::File(PathBuf::from(path)),
+        };
+        Ok(Self {
+            source,
+            target_heads,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Options {
+    /// Rewrite files in place for `sweep`, `consolidate` and `revert`. Without
+    /// it those commands only report what they would change.
+    pub write: bool,
+    pub jobs: usize,
+}
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct DocumentReport {
+    pub path: String,
+    pub bytes: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub projection_degraded: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub zombie_count: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub zombie_dots: Option<Vec<String>>,
+    /// Stream bytes consolidation removes (negative if re-encoding grows it).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bytes_saved: Option<i64>,
+    /// Changeset bytes `sweep`/`revert` append.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bytes_appended: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub plain: Option<editor_model::PlainDoc>,
+    pub written: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Runs `command` over every job on `options.jobs` worker threads and hands each
+/// report to `sink` as it completes — completion order, not input order. Jobs
+/// are pulled lazily, so `jobs` may stream millions of paths. A failing
+/// document yields a report with `error` set; it never stops the batch. So do
+/// a spec that did not parse and any stdin job after the first, which would
+/// find stdin already drained.
+pub fn run(
+    command: Command,
+    options: Options,
+    jobs: impl Iterator<Item = Result<Job, JobError>> + Send,
+    mut sink: impl FnMut(DocumentReport),
+) {
+    let workers = options.jobs.max(1);
+    let jobs = Mutex::new(single_stdin(jobs));
+    let (tx, rx) = mpsc::sync_channel(workers * 2);
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            let tx = tx.clone();
+            let jobs = &jobs;
+            scope.spawn(move || {
+                loop {
+                    let Some(job) = jobs.lock().ok().and_then(|mut it| it.next()) else {
+                        break;
+                    };
+                    let report = match job {
+                        Ok(job) => run_one(command, options.write, &job),
+                        Err(e) => e.into_report(),
+                    };
+                    if tx.send(report).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        drop(tx);
+        for report in rx {
+            sink(report);
+        }
+    });
+}
+
+/// Fails every stdin job after the first: stdin can only be read once.
+fn single_stdin(
+    jobs: impl Iterator<Item = Result<Job, JobError>>,
+) -> impl Iterator<Item = Result<Job, JobError>> {
+    let mut stdin_taken = false;
+    jobs.map(move |job| match job {
+        Ok(job) if job.source == Source::Stdin => {
+            if std::mem::replace(&mut stdin_taken, true) {
+                Err(JobError::new(
+                    "-",
+                    "stdin is already read by an earlier job",
+                ))
+            } else {
+                Ok(job)
+            }
+        }
+        job => job,
+    })
+}
+
+/// Fails every stdin job, for batches whose job list is itself read from stdin.
+pub fn without_stdin(
+    jobs: impl Iterator<Item = Result<Job, JobError>>,
+) -> impl Iterator<Item = Result<Job, JobError>> {
+    jobs.map(|job| match job {
+        Ok(job) if job.source == Source::Stdin => {
+            Err(JobError::new("-", "stdin already holds the job list"))
+        }
+        job => job,
+    })
+}
+
+pub fn run_one(command: Command, write: bool, job: &Job) -> DocumentReport {
+    let mut report = DocumentReport {
+        path: job.source.label(),
+        ..DocumentReport::default()
+    };
+    let result = job
+        .source
+        .read()
+        .map_err(|e| format!("cannot read: {e}"))
+        .and_then(|bytes| {
+            report.bytes = bytes.len() as u64;
+            apply(command, write, job, &bytes, &mut report).map_err(|e| e.to_string())
+        });
+    if let Err(e) = result {
+        report.error = Some(e);
+    }
+    report
+}
+
+fn apply(
+    command: Command,
+    write: bool,
+    job: &Job,
+    bytes: &[u8],
+    report: &mut DocumentReport,
+) -> EditorResult<()> {
+    match command {
+        Command::Report => {
+            let state = build_state(bytes)?;
+            report.projection_degraded = Some(state.projection_degraded());
+            report.zombie_count = Some(collect_zombie_dots(&state).len() as u32);
+            report.bytes_saved = Some(consolidated(bytes)?.map_or(0, |(_, saved)| saved));
+        }
+        Command::Materialize => {
+            let state = build_state(bytes)?;
+            report.projection_degraded = Some(state.projection_degraded());
+            report.text = Some(editor_state::doc_plain_text(&state.view()));
+            report.plain = Some(state.to_plain());
+        }
+        Command::Verify => {
+            let state = build_state(bytes)?;
+            report.projection_degraded = Some(state.projection_degraded());
+            editor_state::State::from_plain(&state.to_plain()).map_err(|e| {
+                EditorError::General {
+                    msg: format!("{e:?}"),
+                }
+            })?;
+        }
+        Command::Zombies => {
+            let state = build_state(bytes)?;
+            let zombies = collect_zombie_dots(&state);
+            report.zombie_count = Some(zombies.len() as u32);
+            report.zombie_dots = Some(zombies.iter().map(|d| d.to_string()).collect());
+        }
+        Command::Sweep => {
+            let appended = sweep_bytes(bytes)?;
+            append(job, write, bytes, &appended, report)?;
+        }
+        Command::Consolidate => {
+            let Some((rewritten, saved)) = consolidated(bytes)? else {
+                report.bytes_saved = Some(0);
+                return Ok(());
+            };
+            report.bytes_saved = Some(saved);
+            if write {
+                replace(job, &rewritten)?;
+                report.written = true;
+            }
+        }
+        Command::Revert => {
+            let target: hashbrown::HashSet<editor_crdt::Dot> =
+                job.target_heads.iter().copied().collect();
+            let appended = revert_bytes(bytes, &target, &[])?;
+            append(job, write, bytes, &appended, report)?;
+        }
+    }
+    Ok(())
+}
+
+fn build_state(bytes: &[u8]) -> EditorResult<editor_state::State> {
+    let css = editor_codec::decode_changeset_stream(bytes)
+        .map_err(|e| FfiError::Deserialization(e.to_string()))?
+        .into_graph_input();
+    crate::graph::build_state_tolerant(css)
+}
+
+/// The whole stream with its consolidatable prefix replaced, and the bytes
+/// that saves. `None` when nothing consolidates.
+fn consolidated(bytes: &[u8]) -> EditorResult<Option<(Vec<u8>, i64)>> {
+    let Some(c) = editor_codec::consolidate_stream(bytes)
+        .map_err(|e| FfiError::Deserialization(e.to_string()))?
+    else {
+        return Ok(None);
+    };
+    let mut out = c.payload;
+    out.extend_from_slice(&bytes[c.consumed_bytes..]);
+    let saved = bytes.len() as i64 - out.len() as i64;
+    Ok(Some((out, saved)))
+}
+
+fn append(
+    job: &Job,
+    write: bool,
+    bytes: &[u8],
+    appended: &[u8],
+    report: &mut DocumentReport,
+) -> EditorResult<()> {
+    report.bytes_appended = Some(appended.len() as u64);
+    if write && !appended.is_empty() {
+        let mut out = bytes.to_vec();
+        out.extend_from_slice(appended);
+        replace(job, &out)?;
+        report.written = true;
+    }
+    Ok(())
+}
+
+/// Writes beside the original and renames over it, so a crash mid-batch never
+/// leaves a truncated document.
+fn replace(job: &Job, bytes: &[u8]) -> EditorResult<()> {
+    let Source::File(path) = &job.source else {
+        return Err(EditorError::General {
+            msg: "--write needs file inputs, not stdin".to_owned(),
+        });
+    };
+    write_atomic(path, bytes).map_err(|e| EditorError::General {
+        msg: format!("cannot write: {e}"),
+    })
+}
+
+/// The data reaches disk before the rename, so the rename can never publish
+/// a file whose contents a crash lost.
+fn write_atomic(path: &Path, bytes: &[u8]) -> io::Result<()> {
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    let mut file = File::create(&tmp)?;
+    file.write_all(bytes)?;
+    file.sync_all()?;
+    drop(file);
+    std::fs::rename(&tmp, path)?;
+    // Persist the rename itself; directories open as files only on Unix.
+    #[cfg(unix)]
+    {
+        let dir = path.parent().filter(|dir| !dir.as_os_str().is_empty());
+        File::open(dir.unwrap_or(Path::new(".")))?.sync_all()?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use editor_crdt::ListOp;
+    use editor_model::{EditOp, SeqItem};
+    use editor_state::ProjectedState;
+
+    use super::*;
+
+    fn typed_stream(texts: &[&str]) -> Vec<u8> {
+        let mut ps = ProjectedState::empty();
+        ps.commit();
+        let mut out = Vec::new();
+        let mut pos = 1;
+        for text in texts {
+            let heads: hashbrown::HashSet<editor_crdt::Dot> =
+                ps.graph().current_heads().copied().collect();
+            for c in text.chars() {
+                ps.apply(EditOp::Seq(ListOp::Ins {
+                    pos,
+                    item: SeqItem::Char(c),
+                }))
+                .unwrap();
+                pos += 1;
+            }
+            ps.commit();
+            let css = if out.is_empty() {
+                ps.graph().changesets_as_vec()
+            } else {
+                ps.graph().local_changesets_since(&heads).unwrap()
+            };
+            out.extend(
+                editor_codec::encode_changesets(
+                    editor_codec::ReencodableChangesets::from_local_ops(css),
+                )
+                .unwrap(),
+            );
+        }
+        out
+    }
+
+    fn temp_doc(name: &str, bytes: &[u8]) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("editor-batch-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(name);
+        std::fs::write(&path, bytes).unwrap();
+        path
+    }
+
+    #[test]
+    fn report_covers_projection_zombies_and_savings() {
+        let path = temp_doc("report", &typed_stream(&["ab", "cd", "ef"]));
+        let job = Job::parse(path.to_str().unwrap(), Command::Report).unwrap();
+        let report = run_one(Command::Report, false, &job);
+        assert_eq!(report.error, None);
+        assert_eq!(report.projection_degraded, Some(false));
+        assert_eq!(report.zombie_count, Some(0));
+        assert!(report.bytes_saved.is_some());
+        assert!(!report.written);
+    }
+
+    #[test]
+    fn consolidate_write_keeps_the_document_text() {
+        let bytes = typed_stream(&["ab", "cd", "ef"]);
+        let path = temp_doc("consolidate", &bytes);
+        let job = Job::parse(path.to_str().unwrap(), Command::Consolidate).unwrap();
+        let report = run_one(Command::Consolidate, true, &job);
+        assert!(report.written);
+
+        let rewritten = std::fs::read(&path).unwrap();
+        assert_eq!(
+            rewritten.len() as i64,
+            bytes.len() as i64 - report.bytes_saved.unwrap()
+        );
+        let text = |b: &[u8]| editor_state::doc_plain_text(&build_state(b).unwrap().view());
+        assert_eq!(text(&rewritten), text(&bytes));
+    }
+
+    #[test]
+    fn failures_are_reported_per_document() {
+        let good = temp_doc("good", &typed_stream(&["ok"]));
+        let bad = temp_doc("bad", b"not a stream");
+        let jobs = [good, bad].map(|p| Job {
+            source: Source::File(p),
+            target_heads: Vec::new(),
+        });
+        let mut reports = Vec::new();
+        let options = Options {
+            jobs: 2,
+            ..Options::default()
+        };
+        run(Command::Verify, options, jobs.into_iter().map(Ok), |r| {
+            reports.push(r)
+        });
+        reports.sort_by(|a, b| a.path.cmp(&b.path));
+        assert_eq!(reports.len(), 2);
+        assert!(reports[0].error.is_some(), "bad stream fails alone");
+        assert_eq!(reports[1].error, None);
+    }
+
+    #[test]
+    fn bad_specs_are_reported_without_stopping_the_batch() {
+        let path = temp_doc("spec-good", &typed_stream(&["ok"]));
+        let good = format!("{}@1_0", path.display());
+        let jobs = [good.as_str(), "no-heads"].map(|spec| Job::parse(spec, Command::Revert));
+        let mut reports = Vec::new();
+        run(Command::Revert, Options::default(), jobs.into_iter(), |r| {
+            reports.push(r)
+        });
+        assert_eq!(reports.len(), 2);
+        let bad = reports.iter().find(|r| r.path == "no-heads").unwrap();
+        assert_eq!(bad.error.as_deref(), Some("revert needs <path>@<heads>"));
+        assert!(reports.iter().any(|r| r.path == path.display().to_string()));
+    }
+
+    #[test]
+    fn only_the_first_stdin_job_runs() {
+        let jobs = ["-", "a", "-"].map(|spec| Job::parse(spec, Command::Report));
+        let checked: Vec<_> = single_stdin(jobs.into_iter()).collect();
+        assert_eq!(checked[0].as_ref().unwrap().source, Source::Stdin);
+        assert!(checked[1].is_ok());
+        assert_eq!(
+            checked[2].as_ref().unwrap_err().message,
+            "stdin is already read by an earlier job"
+        );
+    }
+
+    #[test]
+    fn stdin_jobs_fail_when_stdin_holds_the_list() {
+        let jobs = ["a", "-"].map(|spec| Job::parse(spec, Command::Report));
+        let checked: Vec<_> = without_stdin(jobs.into_iter()).collect();
+        assert!(checked[0].is_ok());
+        assert_eq!(
+            checked[1].as_ref().unwrap_err().message,
+            "stdin already holds the job list"
+        );
+    }
+
+    #[test]
+    fn revert_jobs_carry_their_target_heads() {
+        let job = Job::parse("doc.bin@1_0,2_a", Command::Revert).unwrap();
+        assert_eq!(job.source, Source::File(PathBuf::from("doc.bin")));
+        assert_eq!(job.target_heads.len(), 2);
+        assert!(Job::parse("doc.bin", Command::Revert).is_err());
+        assert_eq!(
+            Job::parse("a@b", Command::Sweep).unwrap().source,
+            Source::File(PathBuf::from("a@b"))
+        );
+    }
+}
@@ -0,0 +1,94 @@
+use std::env;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Write};
+use std::process;
+use std::thread;
+
+use editor_ffi::batch::{self, Command, Job, Options};
+
+const USAGE: &str = "Usage:
+  editor-server [--jobs <n>] [--write] <command> <input>...
+  editor-server [--jobs <n>] [--write] <command> --from <list>
+
+Commands: report, materialize, verify, zombies, sweep, consolidate, revert
+  <input>   changeset stream file, or - for stdin; revert takes <file>@<dot>,...
+  --from    newline-separated inputs from <list> (- for stdin)
+  --write   rewrite files in place for sweep, consolidate and revert
+
+Prints one JSON report per document. Exits 1 if any document failed.";
+
+fn main() {
+    let mut args = env::args().skip(1).peekable();
+    let mut options = Options {
+        jobs: thread::available_parallelism().map_or(1, |n| n.get()),
+        write: false,
+    };
+    while let Some(flag) = args.next_if(|a| a.starts_with("--")) {
+        match flag.as_str() {
+            "--write" => options.write = true,
+            "--jobs" => {
+                options.jobs = args
+                    .next()
+                    .and_then(|n| n.parse().ok())
+                    .unwrap_or_else(|| usage())
+            }
+            _ => usage(),
+        }
+    }
+    let command = args
+        .next()
+        .and_then(|c| Command::parse(&c))
+        .unwrap_or_else(|| usage());
+    let inputs: Vec<String> = args.collect();
+
+    let list_on_stdin =
+        matches!(inputs.as_slice(), [flag, list] if flag == "--from" && list == "-");
+    let specs: Box<dyn Iterator<Item = String> + Send> = match inputs.as_slice() {
+        [flag, list] if flag == "--from" => list_lines(list),
+        [] => usage(),
+        _ => Box::new(inputs.into_iter()),
+    };
+    let jobs = specs
+        .filter(|spec| !spec.trim().is_empty())
+        .map(move |spec| Job::parse(spec.trim(), command));
+    let jobs: Box<dyn Iterator<Item = _> + Send> = if list_on_stdin {
+        Box::new(batch::without_stdin(jobs))
+    } else {
+        Box::new(jobs)
+    };
+
+    let mut failed = false;
+    let mut stdout = io::stdout().lock();
+    batch::run(command, options, jobs, |report| {
+        failed |= report.error.is_some();
+        let line = serde_json::to_string(&report).expect("report serializes");
+        writeln!(stdout, "{line}").unwrap_or_else(|e| fail(&format!("stdout: {e}")));
+    });
+    if failed {
+        process::exit(1);
+    }
+}
+
+fn list_lines(path: &str) -> Box<dyn Iterator<Item = String> + Send> {
+    let reader: Box<dyn BufRead + Send> = match path {
+        "-" => Box::new(BufReader::new(io::stdin())),
+        _ => Box::new(BufReader::new(
+            File::open(path).unwrap_or_else(|e| fail(&format!("cannot read {path}: {e}"))),
+        )),
+    };
+    Box::new(
+        reader
+            .lines()
+            .map(|line| line.unwrap_or_else(|e| fail(&format!("input list: {e}")))),
+    )
+}
+
+fn usage() -> ! {
+    eprintln!("{USAGE}");
+    process::exit(1)
+}
+
+fn fail(msg: &str) -> ! {
+    eprintln!("ERROR: {msg}");
+    process::exit(1)
+}
@@ -2,6 +2,8 @@
 use hashbrown::HashMap;
 use std::sync::Mutex;
 
+#[cfg(not(feature = "wasm-server"))]
+use editor_common::time::Instant;
 use editor_macros::ffi;
 use serde::{Deserialize, Serialize};
 
@@ -123,6 +125,8 @@ struct RenderState {
     // fixed at the page's max height, so when content grows we force-damage the
     // newly revealed strip [prev, current) that no primitive diff would cover.
     last_content_height: HashMap<u32, i32>,
+    // Present only while the host has telemetry enabled.
+    frame_traces: Option<editor_core::TraceRing<editor_core::FrameTrace>>,
 }
 
 #[cfg(not(feature = "wasm-server"))]
@@ -184,6 +188,14 @@ pub struct CharacterCounts {
     pub selection_without_whitespace_and_punctuation: u32,
 }
 
+#[ffi]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct WritingStatisticsReport {
+    pub doc: editor_core::WritingStatistics,
+    pub selection: editor_core::WritingStatistics,
+}
+
 #[ffi]
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -330,6 +342,57 @@ impl Editor {
         self.with_tick(|inner| Ok(inner.editor.tick_through(request_id)?.into_ffi()?))
     }
 
+    /// Starts logging every inbound call so the session can be replayed with
+    /// `EditorHost.replay_recording`.
+    pub fn start_recording(&self) -> EditorResult<()> {
+        self.with_inner(|inner| Ok(inner.editor.start_recording()?))
+    }
+
+    /// Stops recording and returns the encoded session.
+    pub fn finish_recording(&self) -> EditorResult<Vec<u8>> {
+        self.with_inner(|inner| Ok(inner.editor.finish_recording()?))
+    }
+
+    /// Keeps traces of the last `capacity` ticks and page rasterizations for
+    /// `take_telemetry`. `0` turns tracing off and drops collected traces.
+    pub fn set_telemetry_capacity(&self, capacity: u32) -> EditorResult<()> {
+        #[cfg(not(feature = "wasm-server"))]
+        self.with_render(|render| {
+            render.frame_traces =
+                (capacity > 0).then(|| editor_core::TraceRing::new(capacity as usize));
+            Ok(())
+        })?;
+        self.with_inner(|inner| {
+            if capacity == 0 {
+                inner.editor.disable_telemetry();
+            } else {
+                inner.editor.enable_telemetry(capacity as usize);
+            }
+            Ok(())
+        })
+    }
+
+    /// Drains the traces collected since the last call, oldest first.
+    pub fn take_telemetry(&self) -> EditorResult<Complex<editor_core::EditorTelemetry>> {
+        #[cfg(not(feature = "wasm-server"))]
+        let (frames, dropped_frames) = self.with_render(|render| {
+            Ok(render
+                .frame_traces
+                .as_mut()
+                .map_or_else(Default::default, editor_core::TraceRing::drain))
+        })?;
+        #[cfg(feature = "wasm-server")]
+        let (frames, dropped_frames) = (Vec::new(), 0);
+        let (ticks, dropped_ticks) =
+            self.with_inner(|inner| Ok(inner.editor.take_tick_traces()))?;
+        Ok(editor_core::EditorTelemetry {
+            ticks,
+            frames,
+            dropped: dropped_ticks.saturating_add(dropped_frames),
+        }
+        .into_ffi()?)
+    }
+
     pub fn replace_viewport_anchor_presentation(
         &self,
         revision: Complex<editor_core::Revision>,
@@ -475,6 +538,19 @@ impl Editor {
         })
     }
 
+    /// Word, sentence, paragraph, 원고지 page and reading-time counts for
+    /// the document and the selection.
+    pub fn writing_statistics(
+        &self,
+        options: Complex<editor_core::StatisticsOptions>,
+    ) -> EditorResult<Complex<WritingStatisticsReport>> {
+        self.with_inner(|inner| {
+            let options: editor_core::StatisticsOptions = options.from_ffi()?;
+            let (doc, selection) = inner.editor.writing_statistics(options);
+            Ok(WritingStatisticsReport { doc, selection }.into_ffi()?)
+        })
+    }
+
     pub fn interactive_hit_test(
         &self,
         page: u32,
@@ -528,6 +604,18 @@ impl Editor {
         self.with_inner(|inner| Ok(inner.editor.cursor_hit_rects().into_ffi()?))
     }
 
+    /// Full accessibility snapshot. Later `accessibility_update` calls are
+    /// relative to it.
+    pub fn accessibility_tree(&self) -> EditorResult<Complex<editor_view::AccessibilityTree>> {
+        self.with_inner(|inner| Ok(inner.editor.accessibility_tree().into_ffi()?))
+    }
+
+    /// Accessibility nodes added, changed or removed since the last snapshot
+    /// or update. Hosts call this after each tick.
+    pub fn accessibility_update(&self) -> EditorResult<Complex<editor_view::AccessibilityUpdate>> {
+        self.with_inner(|inner| Ok(inner.editor.accessibility_update().into_ffi()?))
+    }
+
     pub fn interactive_regions(
         &self,
     ) -> EditorResult<Vec<Complex<editor_view::InteractiveRegion>>> {
@@ -673,7 +761,9 @@ impl Editor {
             let decoded = editor_codec::decode_changeset_stream(&payload[..])
                 .map_err(|e| FfiError::Deserialization(e.to_string()))?;
             let lossless = decoded.lossless();
+            let hashes = decoded.content_hashes().to_vec();
             let css = decoded.into_graph_input();
+            crate::graph::verify_content_hashes(inner.editor.state().graph(), &css, &hashes)?;
             stash_carriers(&css, &payload, lossless, &mut inner.carrier_bytes)?;
             for changeset in css {
                 inner.editor.receive_remote_changeset(changeset);
@@ -773,6 +863,19 @@ impl Editor {
         })
     }
 
+    /// Hex SHA-256 over the sealed history's heads. Two replicas reporting the
+    /// same value hold the same changesets; uncommitted ops are not covered.
+    pub fn heads_hash(&self) -> EditorResult<String> {
+        self.with_inner(|inner| {
+            Ok(inner
+                .editor
+                .state()
+                .graph()
+                .heads_hash::<editor_codec::DurableDigest>()
+                .to_string())
+        })
+    }
+
     /// The `id` of every local changeset (its first op's `actor:clock`), read straight
     /// from the graph — `O(#changesets)`. Callers that only need the id set must use
     /// this instead of `missing_changesets_tolerant(&[])` + `split_changesets`, which
@@ -934,10 +1037,25 @@ impl Editor {
         })
     }
 
+    /// Corrections for a range in the `misspelling` tracked-range group.
+    pub fn spelling_suggestions(&self, id: String) -> EditorResult<Vec<String>> {
+        self.with_inner(|inner| Ok(inner.editor.spelling_suggestions(&id)))
+    }
+
     pub fn export_page_vector(&self, page: u32, scale_factor: f64) -> EditorResult<Vec<u8>> {
         self.with_inner(|inner| Ok(inner.editor.export_page_vector(page, scale_factor as f32)))
     }
 
+    pub fn export_page_svg(&self, page: u32) -> EditorResult<String> {
+        self.with_inner(|inner| Ok(inner.editor.export_page_svg(page)))
+    }
+
+    /// The selected area of each page it spans, stacked into one SVG document.
+    /// `None` when nothing is selected.
+    pub fn export_selection_svg(&self) -> EditorResult<Option<String>> {
+        self.with_inner(|inner| Ok(inner.editor.export_selection_svg()))
+    }
+
     pub fn tracked_ranges_at(
         &self,
         page: u32,
@@ -1084,7 +1202,7 @@ impl Editor {
             // The editor lock is held only for the signature check and the
             // display-list build; the raster + present below run with the editor
             // free for concurrent ticks and reads.
-            let (sig, built) = {
+            let (sig, built, display_list_elapsed) = {
                 let mut inner = self.inner.lock().map_err(|_| FfiError::LockPoisoned)?;
                 inner.ensure_available()?;
                 // Skip the rebuild + incremental raster + present when nothing this page
@@ -1098,7 +1216,9 @@ impl Editor {
                 if let Some(frame_key) = render.reused_frame_key(page, sig) {
                     return Ok(Some(frame_key));
                 }
-                (sig, inner.editor.build_display_list(page, scale_factor))
+                let started = render.frame_traces.is_some().then(Instant::now);
+                let built = inner.editor.build_display_list(page, scale_factor);
+                (sig, built, started.map(|started| started.elapsed()))
             };
             // The page may have been removed by an edit this frame (the host's
             // surfaces outlive a shrinking document until it re-renders), in
@@ -1128,12 +1248,27 @@ impl Editor {
                 }
             }
             let frame_key = render.prepare_frame_key(page, sig);
+            let raster_started = display_list_elapsed.map(|_| Instant::now());
             let committed = render.surfaces.get_mut(&page).unwrap().apply_damage(
                 &dl,
                 &damage,
                 requested_revision.get(),
                 frame_key,
             );
+            if let (Some(traces), Some(display_list), Some(raster_started)) = (
+                render.frame_traces.as_mut(),
+                display_list_elapsed,
+                raster_started,
+            ) {
+                traces.push(editor_core::FrameTrace {
+                    page,
+                    revision: requested_revision,
+                    display_list_micros: display_list.as_micros() as u64,
+                    raster_micros: raster_started.elapsed().as_micros() as u64,
+                    damage_rects: damage.len() as u32,
+                    damage_area: damage.iter().map(|r| r.area().max(0) as u64).sum(),
+                });
+            }
             if committed {
                 render.prev_dl.insert(page, dl);
                 render.last_content_height.insert(page, page_bounds.y1);
@@ -1177,6 +1312,57 @@ impl Editor {
     }
 }
 
+#[cfg(feature = "wasm-server")]
+#[editor_macros::ffi_export(wasm)]
+impl Editor {
+    /// Lays out every page and writes them as one PDF with embedded font
+    /// subsets, link annotations and a bookmark outline from the document's
+    /// folds.
+    pub fn export_pdf(
+        &self,
+        metadata: Complex<editor_server::pdf::PdfMetadata>,
+    ) -> EditorResult<Vec<u8>> {
+        let metadata = metadata.from_ffi()?;
+        self.with_inner(|inner| {
+            let document = inner.editor.export_vector_document();
+            let resource = inner.editor.resource().lock().unwrap();
+            Ok(editor_server::pdf::write_pdf(
+                &document,
+                &metadata,
+                &resource.font_registry,
+            )?)
+        })
+    }
+
+    /// Packages the document as an EPUB 3 book, one chapter per page break,
+    /// with the fonts it uses embedded as subsets.
+    pub fn export_epub(
+        &self,
+        metadata: Complex<editor_server::epub::EpubMetadata>,
+    ) -> EditorResult<Vec<u8>> {
+        let metadata = metadata.from_ffi()?;
+        self.with_inner(|inner| {
+            let doc = inner.editor.state().to_plain();
+            let resource = inner.editor.resource().lock().unwrap();
+            Ok(editor_server::epub::build_epub(&doc, &metadata, &resource)?)
+        })
+    }
+
+    /// Packages the document as a Word document, optionally with the fonts it
+    /// uses embedded as subsets.
+    pub fn export_docx(
+        &self,
+        options: Complex<editor_server::docx::DocxOptions>,
+    ) -> EditorResult<Vec<u8>> {
+        let options = options.from_ffi()?;
+        self.with_inner(|inner| {
+            let doc = inner.editor.state().to_plain();
+            let resource = inner.editor.resource().lock().unwrap();
+            Ok(editor_server::docx::build_docx(&doc, &options, &resource)?)
+        })
+    }
+}
+
 impl Editor {
     pub(crate) fn new(core: editor_core::Editor, carrier_bytes: CarrierStash) -> Self {
         Self {
@@ -1192,6 +1378,7 @@ impl Editor {
                 next_frame_key: 1,
                 prev_dl: HashMap::new(),
                 last_content_height: HashMap::new(),
+                frame_traces: None,
             }),
         }
     }
@@ -1285,7 +1472,12 @@ mod tests {
     }
 
     fn local_theme(editor: &Editor) -> editor_resource::ThemeVariant {
-        local_resource(editor).lock().unwrap().theme().variant()
+        local_resource(editor)
+            .lock()
+            .unwrap()
+            .theme()
+            .variant()
+            .expect("built-in theme")
     }
 
     #[test]
@@ -1713,6 +1905,47 @@ mod tests {
         );
     }
 
+    #[test]
+    fn receive_rejects_bundle_with_mismatched_content_hash() {
+        let (initial, ..) = state! {
+            doc { root { p1: paragraph { text("") } } }
+            selection: (p1, 0)
+        };
+        let mut parents: Vec<_> = initial.graph().current_heads().copied().collect();
+        parents.sort();
+        let cs = editor_crdt::Changeset {
+            ops: vec![editor_crdt::Op {
+                id: editor_crdt::Dot::new(900, 0),
+                parents,
+                payload: editor_model::EditOp::Seq(editor_crdt::ListOp::Ins {
+                    pos: 0,
+                    item: editor_model::SeqItem::Char('x'),
+                }),
+            }],
+        };
+        let genuine = initial
+            .graph()
+            .receive_changeset(cs.clone())
+            .unwrap()
+            .changeset_hash::<editor_codec::DurableDigest>(&cs.ops[0].id)
+            .unwrap();
+        let hashed = |hash| {
+            editor_codec::encode_changesets_hashed(
+                editor_codec::ReencodableChangesets::from_local_ops(vec![cs.clone()]),
+                &[hash],
+            )
+            .unwrap()
+        };
+        let editor = make_ffi_editor(initial);
+
+        assert!(
+            editor
+                .receive_remote_changeset(hashed(editor_crdt::ContentHash([0; 32])))
+                .is_err()
+        );
+        editor.receive_remote_changeset(hashed(genuine)).unwrap();
+    }
+
     /// Hand-assembles a single-changeset bundle whose one op is an unrecognized
     /// (v-next) op tag, using only editor-codec's public low-level primitives —
     /// mirrors editor-codec's own `vnext.rs` synth pattern (the `test-util`-gated
@@ -17,6 +17,9 @@ pub enum FfiError {
     )]
     CausalOrderViolation { first_op: editor_crdt::Dot },
 
+    #[error("carried content hash for changeset {first:?} does not match the sealed hash")]
+    ChangesetHashMismatch { first: editor_crdt::Dot },
+
     #[error("surface creation failed: {0}")]
     Surface(String),
 
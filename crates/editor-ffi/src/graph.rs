@@ -65,6 +65,35 @@ pub(crate) fn state_from_changesets_with_pending(
     Ok((state, stash))
 }
 
+/// Checks every content hash a bundle carried against the hash its changeset
+/// seals to on top of `graph`, before anything is merged. A sender re-using a
+/// known dot for other content is already a `DotConflict`; this also catches
+/// forged or stale hashes over identical ops. Changesets that cannot land yet
+/// (parents neither local nor in the bundle) have nothing to hash against and
+/// pass unchecked, as does every changeset of a bundle that carried no hashes.
+pub(crate) fn verify_content_hashes(
+    graph: &editor_crdt::OpGraph<editor_model::EditOp>,
+    css: &[editor_crdt::Changeset<editor_model::EditOp>],
+    hashes: &[Option<editor_crdt::ContentHash>],
+) -> Result<(), FfiError> {
+    if hashes.iter().all(Option::is_none) {
+        return Ok(());
+    }
+    let (merged, _) = graph.receive_changesets_ordered(css.to_vec());
+    for (cs, hash) in css.iter().zip(hashes) {
+        let (Some(op), Some(hash)) = (cs.ops.first(), hash) else {
+            continue;
+        };
+        match merged.changeset_hash::<editor_codec::DurableDigest>(&op.id) {
+            Some(sealed) if sealed != *hash => {
+                return Err(FfiError::ChangesetHashMismatch { first: op.id });
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
 pub(crate) fn parse_sweep_tombstones(tombstones: &[String]) -> Vec<editor_crdt::Dot> {
     tombstones
         .iter()
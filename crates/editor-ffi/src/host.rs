@@ -123,6 +123,24 @@ impl EditorHost {
         )))
     }
 
+    /// Loads `state!` macro text, e.g. from `Editor.inspect_state_as_macro`,
+    /// with its selection and pending modifiers.
+    #[cfg(feature = "state-macro")]
+    pub fn create_editor_from_state_macro(
+        &self,
+        text: String,
+        viewport: Complex<editor_view::Viewport>,
+    ) -> EditorResult<Owned<crate::editor::Editor>> {
+        let parsed = editor_introspection::parse_state_macro(&text, 1)
+            .map_err(|e| EditorError::General { msg: e.to_string() })?;
+        let viewport = viewport.from_ffi()?;
+        let core = editor_core::Editor::new(parsed.state, viewport, self.new_local_resource()?);
+        Ok(into_owned(crate::editor::Editor::new(
+            core,
+            crate::editor::CarrierStash::default(),
+        )))
+    }
+
     pub fn create_editor_from_graph(
         &self,
         changesets: Vec<u8>,
@@ -148,6 +166,16 @@ impl EditorHost {
         Ok(into_owned(crate::editor::Editor::new(core, carrier_bytes)))
     }
 
+    /// Replays a recording from `Editor.finish_recording` on a fresh editor
+    /// using this host's resources, failing at the first tick that differs.
+    pub fn replay_recording(
+        &self,
+        recording: Vec<u8>,
+    ) -> EditorResult<Complex<editor_core::ReplaySummary>> {
+        let (_, summary) = editor_core::replay_session(&recording, self.new_local_resource()?)?;
+        Ok(summary.into_ffi()?)
+    }
+
     pub fn extract_text_from_graph(&self, changesets: Vec<u8>) -> EditorResult<String> {
         let (state, _) = crate::graph::state_from_changesets(changesets)?;
         let view = state.view();
@@ -175,6 +203,18 @@ impl EditorHost {
         Ok(crate::root::root_default_modifiers(&state).into_ffi()?)
     }
 
+    /// The images of a Word document, in the order `ClipboardOp::PasteDocx`
+    /// reports their placeholders, for the host to upload into them.
+    pub fn docx_attachments(
+        &self,
+        data: Vec<u8>,
+    ) -> EditorResult<Vec<Complex<editor_clipboard::docx::DocxAttachment>>> {
+        let resource = editor_resource::Resource::from_snapshot(self.lock_source()?.snapshot());
+        let import = editor_clipboard::docx::from_docx(&data, &resource)
+            .map_err(|e| EditorError::General { msg: e.to_string() })?;
+        Ok(import.attachments.into_ffi()?)
+    }
+
     pub fn set_fonts(
         &self,
         families: Vec<Complex<editor_resource::FontFamily>>,
@@ -264,6 +304,78 @@ impl EditorHost {
         Ok(snapshot.map(|snapshot| ResourceUpdate::new(snapshot, Vec::new())))
     }
 
+    /// Registers zstd-compressed TeX hyphenation patterns for `language`.
+    /// They take effect once `language` is the hyphenation language.
+    pub fn add_hyphenation_patterns(
+        &self,
+        language: String,
+        data: Vec<u8>,
+    ) -> EditorResult<Owned<ResourceUpdate>> {
+        let prepared = editor_resource::prepare_hyphenation(&data)?;
+        let snapshot = self
+            .lock_source()?
+            .add_hyphenation_patterns(&language, prepared);
+        let notices = if snapshot.hyphenation_language() == Some(language.as_str()) {
+            vec![editor_core::SystemEvent::HyphenationChanged]
+        } else {
+            Vec::new()
+        };
+        Ok(ResourceUpdate::new(snapshot, notices))
+    }
+
+    /// Hyphenates Latin-script words with `language`'s patterns; `None`
+    /// leaves only soft hyphens as break points inside words.
+    pub fn set_hyphenation_language(
+        &self,
+        language: Option<String>,
+    ) -> EditorResult<Option<Owned<ResourceUpdate>>> {
+        let snapshot = self.lock_source()?.set_hyphenation_language(language);
+        Ok(snapshot.map(|snapshot| {
+            ResourceUpdate::new(snapshot, vec![editor_core::SystemEvent::HyphenationChanged])
+        }))
+    }
+
+    /// Checks spelling against zstd-compressed Hunspell `.aff`/`.dic` data.
+    /// Misspellings appear as ranges in the `misspelling` tracked-range group.
+    pub fn set_spellcheck_dictionary(
+        &self,
+        aff: Vec<u8>,
+        dic: Vec<u8>,
+    ) -> EditorResult<Option<Owned<ResourceUpdate>>> {
+        let prepared = editor_resource::prepare_spellchecker(&aff, &dic)?;
+        let snapshot = self.lock_source()?.set_spellchecker(Some(prepared));
+        Ok(snapshot.map(|snapshot| {
+            ResourceUpdate::new(snapshot, vec![editor_core::SystemEvent::SpellcheckChanged])
+        }))
+    }
+
+    /// Turns spellchecking off and drops every misspelling range.
+    pub fn clear_spellcheck_dictionary(&self) -> EditorResult<Option<Owned<ResourceUpdate>>> {
+        let snapshot = self.lock_source()?.set_spellchecker(None);
+        Ok(snapshot.map(|snapshot| {
+            ResourceUpdate::new(snapshot, vec![editor_core::SystemEvent::SpellcheckChanged])
+        }))
+    }
+
+    /// Stores decoded straight-alpha RGBA8 pixels for the image nodes whose
+    /// id is `id`, so exports and offline renders can draw them.
+    pub fn add_image(
+        &self,
+        id: String,
+        width: u32,
+        height: u32,
+        rgba: Vec<u8>,
+    ) -> EditorResult<Option<Owned<ResourceUpdate>>> {
+        let prepared = editor_resource::prepare_image(width, height, rgba)?;
+        let snapshot = self.lock_source()?.insert_image(&id, prepared);
+        Ok(snapshot.map(|snapshot| ResourceUpdate::new(snapshot, Vec::new())))
+    }
+
+    pub fn remove_image(&self, id: String) -> EditorResult<Option<Owned<ResourceUpdate>>> {
+        let snapshot = self.lock_source()?.remove_image(&id);
+        Ok(snapshot.map(|snapshot| ResourceUpdate::new(snapshot, Vec::new())))
+    }
+
     pub fn graph_heads(&self, changesets: Vec<u8>) -> EditorResult<Vec<u8>> {
         let css: Vec<editor_crdt::Changeset<editor_model::EditOp>> =
             editor_codec::decode_changeset_stream(&changesets[..])
@@ -287,14 +399,69 @@ impl EditorHost {
         let variant = variant.from_ffi()?;
         let snapshot = self.lock_source()?.set_theme_variant(variant);
         Ok(snapshot.map(|snapshot| {
-            ResourceUpdate::new(
-                snapshot,
-                vec![editor_core::SystemEvent::ThemeVariantChanged],
-            )
+            ResourceUpdate::new(snapshot, vec![editor_core::SystemEvent::ThemeChanged])
+        }))
+    }
+
+    /// Replaces the theme with `definition`, e.g. the web app's design
+    /// tokens. Fails without changing anything if a token or color is invalid.
+    pub fn set_theme(
+        &self,
+        definition: Complex<editor_resource::ThemeDefinition>,
+    ) -> EditorResult<Option<Owned<ResourceUpdate>>> {
+        let prepared = editor_resource::prepare_theme(definition.from_ffi()?)?;
+        let snapshot = self.lock_source()?.set_theme(prepared);
+        Ok(snapshot.map(|snapshot| {
+            ResourceUpdate::new(snapshot, vec![editor_core::SystemEvent::ThemeChanged])
         }))
     }
 }
 
+#[cfg(feature = "wasm-server")]
+#[editor_macros::ffi_export(wasm)]
+impl EditorHost {
+    /// Renders one page of a plain document to a PNG at `scale`, without
+    /// creating an editor. Images not in `images` draw as placeholders.
+    pub fn render_thumbnail_from_doc(
+        &self,
+        doc: Complex<editor_model::PlainDoc>,
+        viewport: Complex<editor_view::Viewport>,
+        page: u32,
+        scale: f32,
+        images: Vec<Complex<editor_server::thumbnail::ThumbnailImage>>,
+    ) -> EditorResult<Vec<u8>> {
+        let plain: editor_model::PlainDoc = doc.from_ffi()?;
+        self.render_thumbnail(
+            editor_server::thumbnail::ThumbnailSource::Plain(&plain),
+            viewport.from_ffi()?,
+            page,
+            scale,
+            images.from_ffi()?,
+        )
+    }
+
+    /// Renders one page of an encoded changeset stream to a PNG at `scale`.
+    pub fn render_thumbnail_from_graph(
+        &self,
+        changesets: Vec<u8>,
+        viewport: Complex<editor_view::Viewport>,
+        page: u32,
+        scale: f32,
+        images: Vec<Complex<editor_server::thumbnail::ThumbnailImage>>,
+    ) -> EditorResult<Vec<u8>> {
+        let css = editor_codec::decode_changeset_stream(&changesets[..])
+            .map_err(|e| FfiError::Deserialization(e.to_string()))?
+            .into_graph_input();
+        self.render_thumbnail(
+            editor_server::thumbnail::ThumbnailSource::Changesets(css),
+            viewport.from_ffi()?,
+            page,
+            scale,
+            images.from_ffi()?,
+        )
+    }
+}
+
 impl EditorHost {
     fn lock_source(&self) -> EditorResult<MutexGuard<'_, editor_resource::ResourceSource>> {
         self.source
@@ -306,6 +473,25 @@ impl EditorHost {
         local_resource_from_source(&self.source)
     }
 
+    #[cfg(feature = "wasm-server")]
+    fn render_thumbnail(
+        &self,
+        source: editor_server::thumbnail::ThumbnailSource,
+        viewport: editor_view::Viewport,
+        page: u32,
+        scale: f32,
+        images: Vec<editor_server::thumbnail::ThumbnailImage>,
+    ) -> EditorResult<Vec<u8>> {
+        Ok(editor_server::thumbnail::render_page_png(
+            source,
+            viewport,
+            self.new_local_resource()?,
+            page as usize,
+            scale,
+            &images[..],
+        )?)
+    }
+
     #[cfg(test)]
     pub(crate) fn new_test() -> Self {
         Self {
@@ -358,6 +544,38 @@ mod tests {
         );
     }
 
+    #[test]
+    fn set_theme_notifies_once_and_rejects_unknown_tokens() {
+        let host = make_host();
+        let definition = |token: &str| editor_resource::ThemeDefinition {
+            base: Some(editor_resource::ThemeVariant::DarkBlack),
+            colors: vec![editor_resource::ThemeColor {
+                token: token.into(),
+                value: "#ffd400".into(),
+            }],
+        };
+
+        assert!(host.set_theme(definition("ui.nope")).is_err());
+        assert_eq!(
+            host.source.lock().unwrap().revision(),
+            editor_resource::ResourceRevision::INITIAL
+        );
+
+        let update = host
+            .set_theme(definition("ui.accent.brand.default"))
+            .unwrap()
+            .expect("custom theme must return an update");
+        assert_eq!(
+            update.inner.notices(),
+            &[editor_core::SystemEvent::ThemeChanged]
+        );
+        assert!(
+            host.set_theme(definition("ui.accent.brand.default"))
+                .unwrap()
+                .is_none()
+        );
+    }
+
     #[test]
     fn set_theme_variant_returns_exact_update_and_none_for_noop() {
         let host = make_host();
@@ -376,7 +594,7 @@ mod tests {
         assert!(Arc::ptr_eq(update.inner.snapshot(), &current));
         assert_eq!(
             update.inner.notices(),
-            &[editor_core::SystemEvent::ThemeVariantChanged]
+            &[editor_core::SystemEvent::ThemeChanged]
         );
         assert!(
             host.set_theme_variant(editor_resource::ThemeVariant::DarkBlack)
@@ -385,6 +603,67 @@ mod tests {
         );
     }
 
+    #[test]
+    fn hyphenation_updates_notify_only_when_layout_changes() {
+        let host = make_host();
+        let patterns = editor_resource::compress_zstd(b"1na");
+
+        let update = host
+            .add_hyphenation_patterns("en".into(), patterns.clone())
+            .unwrap();
+        assert!(update.inner.notices().is_empty());
+
+        let update = host
+            .set_hyphenation_language(Some("en".into()))
+            .unwrap()
+            .expect("changed language must return an update");
+        assert_eq!(
+            update.inner.notices(),
+            &[editor_core::SystemEvent::HyphenationChanged]
+        );
+        assert!(
+            host.set_hyphenation_language(Some("en".into()))
+                .unwrap()
+                .is_none()
+        );
+
+        let update = host
+            .add_hyphenation_patterns("en".into(), patterns)
+            .unwrap();
+        assert_eq!(
+            update.inner.notices(),
+            &[editor_core::SystemEvent::HyphenationChanged]
+        );
+    }
+
+    #[test]
+    fn spellcheck_dictionary_updates_notify_until_cleared() {
+        let host = make_host();
+        let aff = editor_resource::compress_zstd(b"SET UTF-8\n");
+        let dic = editor_resource::compress_zstd(b"1\nhello\n");
+
+        let update = host
+            .set_spellcheck_dictionary(aff, dic)
+            .unwrap()
+            .expect("installed dictionary must return an update");
+        assert!(update.inner.snapshot().spellchecker().is_some());
+        assert_eq!(
+            update.inner.notices(),
+            &[editor_core::SystemEvent::SpellcheckChanged]
+        );
+
+        let update = host
+            .clear_spellcheck_dictionary()
+            .unwrap()
+            .expect("clearing an installed dictionary must return an update");
+        assert!(update.inner.snapshot().spellchecker().is_none());
+        assert!(host.clear_spellcheck_dictionary().unwrap().is_none());
+        assert!(
+            host.set_spellcheck_dictionary(Vec::new(), Vec::new())
+                .is_err()
+        );
+    }
+
     #[test]
     fn root_modifiers_from_graph_returns_root_default_modifiers() {
         let host = make_host();
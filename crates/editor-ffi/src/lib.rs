@@ -6,10 +6,15 @@ compile_error!("features \"uniffi\" and \"wasm\" are mutually exclusive");
 #[cfg(all(feature = "wasm-browser", feature = "wasm-server"))]
 compile_error!("features \"wasm-browser\" and \"wasm-server\" are mutually exclusive");
 
-#[cfg(any(test, feature = "wasm-server"))]
+#[cfg(all(feature = "batch", any(feature = "uniffi", feature = "wasm")))]
+compile_error!("feature \"batch\" builds the standalone binary and excludes \"uniffi\"/\"wasm\"");
+
+#[cfg(any(test, feature = "wasm-server", feature = "batch"))]
 mod anchors;
+#[cfg(any(test, feature = "batch"))]
+pub mod batch;
 mod convert;
-#[cfg(any(test, feature = "wasm-server"))]
+#[cfg(any(test, feature = "wasm-server", feature = "batch"))]
 mod doc_builder;
 pub mod editor;
 mod error;
@@ -21,5 +26,5 @@ pub mod ingest;
 mod platform;
 mod prelude;
 mod root;
-#[cfg(any(test, feature = "wasm-server"))]
+#[cfg(any(test, feature = "wasm-server", feature = "batch"))]
 mod server;
@@ -1,6 +1,6 @@
 use editor_macros::ffi;
 use editor_renderer::RenderBackend;
-use editor_renderer::backend::cpu::CpuSink;
+use editor_renderer::backend::cpu::{CpuSink, TiledRasterizer};
 use editor_renderer::damage::IRect;
 
 use super::render_buffer::RenderBuffer;
@@ -12,6 +12,7 @@ pub type PlatformHandle = u64;
 
 pub struct SurfaceHandle {
     backend: RenderBackend,
+    tiles: TiledRasterizer,
     handle: PlatformHandle,
     width: u32,
     height: u32,
@@ -38,6 +39,7 @@ impl SurfaceHandle {
 
         Ok(Self {
             backend,
+            tiles: TiledRasterizer::with_available_parallelism(),
             handle,
             width: pw,
             height: ph,
@@ -60,13 +62,8 @@ impl SurfaceHandle {
         editor_revision: u64,
         frame_key: FrameKey,
     ) -> bool {
-        let sink = self.cpu_sink();
-        for &r in damage {
-            sink.clear_rect(r);
-            sink.set_clip(Some(r));
-            editor_renderer::diff::replay(dl, r, sink);
-        }
-        sink.set_clip(None);
+        self.tiles
+            .render_damage(dl, damage, self.backend.cpu_sink());
         self.present_damage(damage, editor_revision, frame_key)
     }
 
@@ -1,5 +1,5 @@
 use editor_renderer::RenderBackend;
-use editor_renderer::backend::cpu::{CpuSink, unpremultiply};
+use editor_renderer::backend::cpu::{CpuSink, TiledRasterizer, unpremultiply};
 use editor_renderer::damage::IRect;
 use wasm_bindgen::prelude::*;
 
@@ -11,6 +11,7 @@ pub type PlatformHandle = web_sys::HtmlCanvasElement;
 
 pub struct CpuPageSurface {
     backend: Option<RenderBackend>,
+    tiles: TiledRasterizer,
     handle: PlatformHandle,
     width: u32,
     height: u32,
@@ -44,6 +45,7 @@ impl CpuPageSurface {
 
         Ok(Self {
             backend,
+            tiles: TiledRasterizer::with_available_parallelism(),
             handle,
             width: w,
             height: h,
@@ -114,15 +116,10 @@ impl CpuPageSurface {
         };
         let clamped: Vec<IRect> = damage.iter().filter_map(|&r| r.intersect(bounds)).collect();
 
-        let Some(sink) = self.cpu_sink() else {
+        let Some(backend) = self.backend.as_mut() else {
             return false;
         };
-        for &r in &clamped {
-            sink.clear_rect(r);
-            sink.set_clip(Some(r));
-            editor_renderer::diff::replay(dl, r, sink);
-        }
-        sink.set_clip(None);
+        self.tiles.render_damage(dl, &clamped, backend.cpu_sink());
         self.present_damage(&clamped)
     }
 
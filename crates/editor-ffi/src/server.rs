@@ -457,43 +457,11 @@ impl EditorServer {
         target_heads: Vec<u8>,
         sweep_tombstones: Vec<String>,
     ) -> EditorResult<Vec<u8>> {
-        // Input graph is used only to build state (`into_graph_input`); the only
-        // thing ever reencoded is the revert transaction's own new local
-        // changesets below (`from_local_ops`) — the input graph is never
-        // value-reencoded.
-        let css: Vec<editor_crdt::Changeset<editor_model::EditOp>> =
-            editor_codec::decode_changeset_stream(&graph[..])
-                .map_err(|e| FfiError::Deserialization(e.to_string()))?
-                .into_graph_input();
         let target_vec = editor_codec::decode_dots(&target_heads[..])
             .map_err(|e| FfiError::Deserialization(e.to_string()))?;
         let target_set: hashbrown::HashSet<editor_crdt::Dot> = target_vec.into_iter().collect();
         let overlay = crate::graph::parse_sweep_tombstones(&sweep_tombstones);
-
-        let state = crate::graph::build_state_tolerant(css)
-            .map_err(|e| FfiError::RevertFailed(e.to_string()))?;
-        let current_heads: hashbrown::HashSet<editor_crdt::Dot> =
-            state.graph().current_heads().copied().collect();
-
-        let target_state = state_at_heads(state.graph(), &target_set, &overlay)?;
-        if target_state.projection_degraded() {
-            return Err(FfiError::RevertFailed("target projection is degraded".to_string()).into());
-        }
-
-        let tr = editor_transaction::build_revert_transaction(&state, &target_state)
-            .map_err(|e| FfiError::RevertFailed(e.to_string()))?;
-        let (new_state, ..) = tr.commit();
-
-        let revert_css = new_state.graph().local_changesets_since(&current_heads)?;
-        if revert_css.is_empty() {
-            return Ok(Vec::new());
-        }
-
-        let bytes = editor_codec::encode_changesets(
-            editor_codec::ReencodableChangesets::from_local_ops(revert_css),
-        )
-        .map_err(|e| FfiError::Serialization(e.to_string()))?;
-        Ok(bytes)
+        revert_bytes(&graph, &target_set, &overlay)
     }
 
     pub fn zombie_dots(&self, graph: Vec<u8>) -> EditorResult<Vec<String>> {
@@ -509,18 +477,7 @@ impl EditorServer {
     }
 
     pub fn sweep(&self, graph: Vec<u8>) -> EditorResult<Vec<u8>> {
-        let css = editor_codec::decode_changeset_stream(&graph[..])
-            .map_err(|e| FfiError::Deserialization(e.to_string()))?
-            .into_graph_input();
-        let sweep_css = sweep_impl(css)?;
-        if sweep_css.is_empty() {
-            return Ok(Vec::new());
-        }
-        let bytes = editor_codec::encode_changesets(
-            editor_codec::ReencodableChangesets::from_local_ops(sweep_css),
-        )
-        .map_err(|e| FfiError::Serialization(e.to_string()))?;
-        Ok(bytes)
+        sweep_bytes(&graph)
     }
 
     /// Returns the total ops count in a Changesets bundle. Used by push light validation.
@@ -633,7 +590,7 @@ impl EditorServer {
             let mut ins = 0u32;
             let mut del = 0u32;
             let status = match editor_codec::decode_changeset_stream(&bundle[..]) {
-                Ok(decoded) => match state.receive_remote_changesets(decoded.into_graph_input()) {
+                Ok(decoded) => match receive_checked(&state, decoded) {
                     Ok((next, ops)) if !ops.is_empty() => {
                         state = next;
                         for op in &ops {
@@ -699,6 +656,77 @@ impl EditorServer {
     }
 }
 
+/// `receive_remote_changesets` behind the bundle's carried content hashes.
+fn receive_checked(
+    state: &editor_state::State,
+    decoded: editor_codec::Decoded,
+) -> EditorResult<(
+    editor_state::State,
+    Vec<editor_crdt::Op<editor_model::EditOp>>,
+)> {
+    let hashes = decoded.content_hashes().to_vec();
+    let css = decoded.into_graph_input();
+    crate::graph::verify_content_hashes(state.graph(), &css, &hashes)?;
+    Ok(state.receive_remote_changesets(css)?)
+}
+
+/// `revert` over an encoded graph: the changesets that bring it back to
+/// `target_set`, or empty when it is already there.
+pub(crate) fn revert_bytes(
+    graph: &[u8],
+    target_set: &hashbrown::HashSet<editor_crdt::Dot>,
+    overlay: &[editor_crdt::Dot],
+) -> EditorResult<Vec<u8>> {
+    // Input graph is used only to build state (`into_graph_input`); the only
+    // thing ever reencoded is the revert transaction's own new local
+    // changesets below (`from_local_ops`) — the input graph is never
+    // value-reencoded.
+    let css: Vec<editor_crdt::Changeset<editor_model::EditOp>> =
+        editor_codec::decode_changeset_stream(graph)
+            .map_err(|e| FfiError::Deserialization(e.to_string()))?
+            .into_graph_input();
+
+    let state = crate::graph::build_state_tolerant(css)
+        .map_err(|e| FfiError::RevertFailed(e.to_string()))?;
+    let current_heads: hashbrown::HashSet<editor_crdt::Dot> =
+        state.graph().current_heads().copied().collect();
+
+    let target_state = state_at_heads(state.graph(), target_set, overlay)?;
+    if target_state.projection_degraded() {
+        return Err(FfiError::RevertFailed("target projection is degraded".to_string()).into());
+    }
+
+    let tr = editor_transaction::build_revert_transaction(&state, &target_state)
+        .map_err(|e| FfiError::RevertFailed(e.to_string()))?;
+    let (new_state, ..) = tr.commit();
+
+    let revert_css = new_state.graph().local_changesets_since(&current_heads)?;
+    if revert_css.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let bytes = editor_codec::encode_changesets(
+        editor_codec::ReencodableChangesets::from_local_ops(revert_css),
+    )
+    .map_err(|e| FfiError::Serialization(e.to_string()))?;
+    Ok(bytes)
+}
+
+pub(crate) fn sweep_bytes(graph: &[u8]) -> EditorResult<Vec<u8>> {
+    let css = editor_codec::decode_changeset_stream(graph)
+        .map_err(|e| FfiError::Deserialization(e.to_string()))?
+        .into_graph_input();
+    let sweep_css = sweep_impl(css)?;
+    if sweep_css.is_empty() {
+        return Ok(Vec::new());
+    }
+    let bytes = editor_codec::encode_changesets(
+        editor_codec::ReencodableChangesets::from_local_ops(sweep_css),
+    )
+    .map_err(|e| FfiError::Serialization(e.to_string()))?;
+    Ok(bytes)
+}
+
 /// Builds a `State` whose graph contains only the ops that are ancestors of
 /// (or equal to) `heads`. Used by `revert` to project the document at a past
 /// point without requiring a bespoke `from_op_graph_at` on the new model.
@@ -724,7 +752,7 @@ fn state_at_heads(
         .map_err(|e| FfiError::RevertFailed(e.to_string()))
 }
 
-fn collect_zombie_dots(state: &editor_state::State) -> Vec<editor_crdt::Dot> {
+pub(crate) fn collect_zombie_dots(state: &editor_state::State) -> Vec<editor_crdt::Dot> {
     let ps = &state.projected;
     let reachable: hashbrown::HashSet<editor_crdt::Dot> = ps
         .subtree_real_dots(editor_crdt::Dot::ROOT)
@@ -1210,6 +1238,48 @@ mod tests {
         );
     }
 
+    /// Content hashes carried on a bundle are checked against the hash the
+    /// merged graph seals: a forged hash fails the bundle without touching the
+    /// fold, the genuine one applies.
+    #[test]
+    fn collect_fold_rejects_bundle_with_mismatched_content_hash() {
+        let cs_a = Changeset::<EditOp> {
+            ops: vec![Op {
+                id: Dot::new(1, 0),
+                parents: vec![],
+                payload: dummy_payload(),
+            }],
+        };
+        let cs_b = Changeset::<EditOp> {
+            ops: vec![Op {
+                id: Dot::new(2, 0),
+                parents: vec![Dot::new(1, 0)],
+                payload: dummy_payload(),
+            }],
+        };
+        let sealed =
+            editor_crdt::OpGraph::from_changesets(vec![cs_a.clone(), cs_b.clone()]).unwrap();
+        let genuine = sealed
+            .changeset_hash::<editor_codec::DurableDigest>(&Dot::new(2, 0))
+            .unwrap();
+        let hashed = |hash| {
+            editor_codec::encode_changesets_hashed(
+                editor_codec::ReencodableChangesets::from_local_ops(vec![cs_b.clone()]),
+                &[hash],
+            )
+            .unwrap()
+        };
+        let server = EditorServer::new_test();
+        let existing = enc_css(std::slice::from_ref(&cs_a));
+        let packed = pack(&[hashed(editor_crdt::ContentHash([0; 32])), hashed(genuine)]);
+
+        let result = server.collect_fold(existing, packed).unwrap();
+        assert_eq!(
+            result.statuses,
+            vec![BundleStatus::Failed, BundleStatus::Applied]
+        );
+    }
+
     fn seq_char(actor: u64, counter: u64, parents: &[Dot], pos: usize, ch: char) -> Op<EditOp> {
         Op {
             id: Dot::new(actor, counter),
@@ -213,6 +213,8 @@ slice! {
                         page_margin_bottom: 20,
                         page_margin_left: 30,
                         page_margin_right: 40,
+                        columns: 2,
+                        column_gap: 24,
                     },
                 ) {}
                 table_cell(
@@ -227,7 +229,7 @@ slice! {
         let output = inspect_slice_as_macro(&slice);
         let expected = r#"slice! {
     content {
-        root(layout_mode: LayoutMode::Paginated { page_width: 800, page_height: 1200, page_margin_top: 10, page_margin_bottom: 20, page_margin_left: 30, page_margin_right: 40 }) {}
+        root(layout_mode: LayoutMode::Paginated { page_width: 800, page_height: 1200, page_margin_top: 10, page_margin_bottom: 20, page_margin_left: 30, page_margin_right: 40, columns: 2, column_gap: 24 }) {}
         table_cell(col_width: Some(320), background_color: Some("blue\"gray".to_string())) {}
     }
     open_start: 0
@@ -339,7 +339,9 @@ fn write_modifier_tree(m: &Modifier, output: &mut String) {
         }
         Modifier::Link { href } => write!(output, "{name}(href: \"{href}\")").unwrap(),
         Modifier::Ruby { text } => write!(output, "{name}(text: \"{text}\")").unwrap(),
-        Modifier::Alignment { value } => write!(output, "{name}({value:?})").unwrap(),
+        Modifier::Alignment { value } | Modifier::Direction { value } => {
+            write!(output, "{name}({value:?})").unwrap()
+        }
     }
 }
 
@@ -5,7 +5,11 @@ mod inspect_state;
 mod inspect_state_as_macro;
 mod labeler;
 mod macro_format;
+#[cfg(feature = "state-macro")]
+mod parse_state_macro;
 
 pub use inspect_slice_as_macro::*;
 pub use inspect_state::*;
 pub use inspect_state_as_macro::*;
+#[cfg(feature = "state-macro")]
+pub use parse_state_macro::*;
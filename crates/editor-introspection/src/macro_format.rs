@@ -92,6 +92,7 @@ pub(crate) fn write_node_attrs_macro(node: &PlainNode, output: &mut String) {
         | PlainNode::HardBreak(_)
         | PlainNode::PageBreak(_)
         | PlainNode::Tab(_)
+        | PlainNode::ColumnBreak(_)
         | PlainNode::Unknown => {}
     }
     if !attrs.is_empty() {
@@ -125,6 +126,9 @@ pub(crate) fn write_modifier_macro(m: &Modifier, output: &mut String) {
         Modifier::Alignment { value } => {
             write!(output, "{name}(Alignment::{value:?})").unwrap();
         }
+        Modifier::Direction { value } => {
+            write!(output, "{name}(TextDirection::{value:?})").unwrap();
+        }
     }
 }
 
@@ -185,13 +189,26 @@ fn layout_mode_expr(layout_mode: LayoutMode) -> String {
             page_margin_bottom,
             page_margin_left,
             page_margin_right,
+            columns,
+            column_gap,
         } => format!(
             "LayoutMode::Paginated {{ page_width: {page_width}, page_height: {page_height}, \
              page_margin_top: {page_margin_top}, page_margin_bottom: {page_margin_bottom}, \
-             page_margin_left: {page_margin_left}, page_margin_right: {page_margin_right} }}"
+             page_margin_left: {page_margin_left}, page_margin_right: {page_margin_right}, \
+             columns: {columns}, column_gap: {column_gap} }}"
         ),
         LayoutMode::Continuous { max_width } => {
             format!("LayoutMode::Continuous {{ max_width: {max_width} }}")
         }
+        LayoutMode::Manuscript {
+            columns,
+            rows,
+            cell_size,
+            row_gap,
+            page_margin,
+        } => format!(
+            "LayoutMode::Manuscript {{ columns: {columns}, rows: {rows}, \
+             cell_size: {cell_size}, row_gap: {row_gap}, page_margin: {page_margin} }}"
+        ),
     }
 }
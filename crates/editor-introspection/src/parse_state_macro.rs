@@ -0,0 +1,450 @@
+use std::collections::BTreeMap;
+
+use editor_crdt::Dot;
+use editor_macro_syntax::doc::{
+    CarryDef, DecorationDef, DecorationParams, FieldValue, NodeContent, NodeDef,
+};
+use editor_macro_syntax::state::{
+    AffinityKind, PendingModifierDef, PositionExpr, SelectionExpr, StateInput,
+};
+use editor_model::*;
+use editor_state::{
+    Affinity, PendingModifier, Position, Selection, State, TemplateError, build_state_from_template,
+};
+use heck::ToSnakeCase;
+use serde_json::{Map, Value};
+use syn::{Expr, Lit, UnOp};
+
+/// A document parsed from `state!` text, with the Dot behind every label.
+pub struct ParsedState {
+    pub state: State,
+    pub labels: BTreeMap<String, Dot>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ParseStateError {
+    #[error("syntax error: {0}")]
+    Syntax(#[from] syn::Error),
+
+    /// Attribute and modifier values are evaluated, not compiled: only
+    /// literals, `Some`/`None`, `.to_string()`, enum paths and struct
+    /// literals — everything `inspect_state_as_macro` prints — are accepted.
+    #[error("unsupported value `{expr}`")]
+    UnsupportedValue { expr: String },
+
+    #[error("invalid {what} `{name}`: {msg}")]
+    Invalid {
+        what: &'static str,
+        name: String,
+        msg: String,
+    },
+
+    #[error("unknown label `{label}`")]
+    UnknownLabel { label: String },
+
+    #[error(transparent)]
+    Template(#[from] TemplateError),
+
+    #[error("{0}")]
+    Document(String),
+}
+
+/// Parses the `state!` macro grammar at runtime, e.g. the output of
+/// [`crate::inspect_state_as_macro`]. The surrounding `state! { .. }` is
+/// optional. Dots are allocated exactly as the macro allocates them, so a
+/// reproduction parses to the same document and labels it compiles to when
+/// built under `actor` 1, the actor `state!` compiles with.
+pub fn parse_state_macro(text: &str, actor: u64) -> Result<ParsedState, ParseStateError> {
+    let input: StateInput = match syn::parse_str::<syn::Macro>(text) {
+        Ok(mac) if mac.path.is_ident("state") => mac.parse_body()?,
+        _ => syn::parse_str(text)?,
+    };
+
+    let mut builder = DocBuilder::default();
+    let root = builder
+        .entry(&input.doc_tree.root, &mut Vec::new(), &mut Vec::new())?
+        .expect("root is never synthetic");
+    let (mut state, handles) = build_state_from_template(PlainDoc { root }, actor)?;
+
+    let mut labels = BTreeMap::new();
+    for (label, path) in builder.plain_labels {
+        let dot = handles[&path];
+        labels.insert(label, dot);
+    }
+    for (label, path, node_type) in builder.synthetic {
+        let (dot, actual) = projected_element(&state, &path)
+            .ok_or_else(|| ParseStateError::Document(format!("no synthetic node at {path:?}")))?;
+        if !dot.is_synthetic() || actual != node_type {
+            return Err(ParseStateError::Document(format!(
+                "synthetic {node_type:?} at {path:?} resolved to {actual:?}"
+            )));
+        }
+        if let Some(label) = label {
+            labels.insert(label, dot);
+        }
+    }
+
+    state.selection = match &input.selection {
+        SelectionExpr::None => None,
+        SelectionExpr::Collapsed(pos) => Some(Selection::collapsed(position(pos, &labels)?)),
+        SelectionExpr::Range(anchor, head) => Some(Selection::new(
+            position(anchor, &labels)?,
+            position(head, &labels)?,
+        )),
+    };
+    state.pending_modifiers = input
+        .pending_modifiers
+        .iter()
+        .map(|def| match def {
+            PendingModifierDef::Set(dec) => Ok(PendingModifier::Set {
+                modifier: modifier(dec)?,
+            }),
+            PendingModifierDef::Unset(name) => Ok(PendingModifier::Unset {
+                ty: from_json("modifier", &name.to_string(), name.to_string().into())?,
+            }),
+        })
+        .collect::<Result<_, ParseStateError>>()?;
+
+    Ok(ParsedState { state, labels })
+}
+
+#[derive(Default)]
+struct DocBuilder {
+    plain_labels: Vec<(String, Vec<usize>)>,
+    synthetic: Vec<(Option<String>, Vec<usize>, NodeType)>,
+}
+
+impl DocBuilder {
+    // Mirrors `doc_macro::codegen::collect_node`: synthetic nodes are
+    // projection scaffolding, so they are left out of the plain document and
+    // addressed by their projected path instead.
+    fn entry(
+        &mut self,
+        node: &NodeDef,
+        plain_path: &mut Vec<usize>,
+        projected_path: &mut Vec<usize>,
+    ) -> Result<Option<PlainNodeEntry>, ParseStateError> {
+        if node.synthetic {
+            self.synthetic_node(node, projected_path)?;
+            return Ok(None);
+        }
+        if let Some(binding) = &node.binding {
+            self.plain_labels
+                .push((binding.to_string(), plain_path.clone()));
+        }
+
+        let mut children = Vec::new();
+        if let NodeContent::Children(defs) = &node.content {
+            for (projected_index, child) in defs.iter().enumerate() {
+                projected_path.push(projected_index);
+                plain_path.push(children.len());
+                if let Some(entry) = self.entry(child, plain_path, projected_path)? {
+                    children.push(entry);
+                }
+                plain_path.pop();
+                projected_path.pop();
+            }
+        }
+
+        Ok(Some(PlainNodeEntry {
+            node: plain_node(node)?,
+            modifiers: block_modifiers(node)?,
+            carry: carry(&node.carry)?,
+            children,
+        }))
+    }
+
+    fn synthetic_node(
+        &mut self,
+        node: &NodeDef,
+        projected_path: &mut Vec<usize>,
+    ) -> Result<(), ParseStateError> {
+        let type_name = node.node_type.to_string();
+        let node_type = from_json("node type", &type_name, type_name.clone().into())?;
+        self.synthetic.push((
+            node.binding.as_ref().map(ToString::to_string),
+            projected_path.clone(),
+            node_type,
+        ));
+        if let NodeContent::Children(children) = &node.content {
+            for (index, child) in children.iter().enumerate() {
+                projected_path.push(index);
+                self.synthetic_node(child, projected_path)?;
+                projected_path.pop();
+            }
+        }
+        Ok(())
+    }
+}
+
+fn plain_node(node: &NodeDef) -> Result<PlainNode, ParseStateError> {
+    let type_name = node.node_type.to_string();
+    if let NodeContent::Text(lit) = &node.content {
+        return Ok(PlainNode::Text(PlainTextNode { text: lit.value() }));
+    }
+    let node_type: NodeType = from_json("node type", &type_name, type_name.clone().into())?;
+    if node_type == NodeType::Unknown {
+        return Ok(PlainNode::Unknown);
+    }
+
+    // Start from the type's defaults so omitted attributes behave like the
+    // macro's `..Default::default()`.
+    let plain = Node::from_discriminant(node_type).to_plain();
+    let Ok(Value::Object(mut fields)) = serde_json::to_value(&plain) else {
+        unreachable!("plain nodes serialize to objects");
+    };
+    for param in &node.params {
+        let name = param.name.to_string();
+        if !fields.contains_key(&name) {
+            return Err(ParseStateError::Invalid {
+                what: "attribute",
+                name,
+                msg: format!("`{type_name}` has no such attribute"),
+            });
+        }
+        fields.insert(name, eval(&param.value)?);
+    }
+    from_json("node", &type_name, Value::Object(fields))
+}
+
+fn block_modifiers(node: &NodeDef) -> Result<BTreeMap<ModifierType, Modifier>, ParseStateError> {
+    let explicit = node
+        .modifiers
+        .iter()
+        .flatten()
+        .map(modifier)
+        .collect::<Result<Vec<_>, _>>()?;
+    // Like the macro, an unannotated root carries the schema defaults and an
+    // annotated one overrides them.
+    let modifiers = match (&node.modifiers, node.node_type == "root") {
+        (None, true) => default_modifiers(),
+        (Some(mods), true) if !mods.is_empty() => default_modifiers_with(explicit),
+        _ => explicit,
+    };
+    Ok(modifiers.into_iter().map(|m| (m.as_type(), m)).collect())
+}
+
+fn carry(carry: &Option<CarryDef>) -> Result<Vec<Modifier>, ParseStateError> {
+    carry
+        .iter()
+        .flat_map(|c| &c.modifiers)
+        .map(modifier)
+        .collect()
+}
+
+fn modifier(dec: &DecorationDef) -> Result<Modifier, ParseStateError> {
+    let name = dec.name.to_string();
+    let mut fields = Map::new();
+    fields.insert("type".into(), name.clone().into());
+    match &dec.params {
+        DecorationParams::None => {}
+        DecorationParams::Named(values) => {
+            for FieldValue { name, value } in values {
+                fields.insert(name.to_string(), eval(value)?);
+            }
+        }
+        DecorationParams::Positional(exprs) => {
+            let [value] = exprs.as_slice() else {
+                return Err(ParseStateError::Invalid {
+                    what: "modifier",
+                    name,
+                    msg: "positional shorthand takes exactly one argument".into(),
+                });
+            };
+            fields.insert("value".into(), eval(value)?);
+        }
+    }
+    from_json("modifier", &name, Value::Object(fields))
+}
+
+fn position(
+    pos: &PositionExpr,
+    labels: &BTreeMap<String, Dot>,
+) -> Result<Position, ParseStateError> {
+    let label = pos.node_ident.to_string();
+    let node = *labels
+        .get(&label)
+        .ok_or(ParseStateError::UnknownLabel { label })?;
+    Ok(Position {
+        node,
+        offset: pos.offset.base10_parse()?,
+        affinity: match pos.affinity {
+            Some(AffinityKind::Upstream) => Affinity::Upstream,
+            Some(AffinityKind::Downstream) | None => Affinity::Downstream,
+        },
+    })
+}
+
+fn projected_element(state: &State, path: &[usize]) -> Option<(Dot, NodeType)> {
+    let view = state.view();
+    let mut node = view.root()?;
+    let Some((last, parents)) = path.split_last() else {
+        return Some((node.id(), node.node_type()));
+    };
+    for &index in parents {
+        node = match node.child_at(index)? {
+            ChildView::Block(block) => block,
+            ChildView::Leaf(_) => return None,
+        };
+    }
+    Some(match node.child_at(*last)? {
+        ChildView::Block(block) => (block.id(), block.node_type()),
+        ChildView::Leaf(leaf) => (leaf.dot(), leaf.node_type()),
+    })
+}
+
+/// Evaluates an attribute or modifier value into the JSON shape serde reads
+/// back into the model types.
+fn eval(expr: &Expr) -> Result<Value, ParseStateError> {
+    let unsupported = || ParseStateError::UnsupportedValue {
+        expr: quote::ToTokens::to_token_stream(expr).to_string(),
+    };
+    Ok(match expr {
+        Expr::Lit(lit) => match &lit.lit {
+            Lit::Str(s) => s.value().into(),
+            Lit::Bool(b) => b.value.into(),
+            Lit::Int(i) => Value::Number(i.base10_digits().parse().map_err(|_| unsupported())?),
+            Lit::Float(f) => Value::Number(f.base10_digits().parse().map_err(|_| unsupported())?),
+            _ => return Err(unsupported()),
+        },
+        Expr::Unary(unary) if matches!(unary.op, UnOp::Neg(_)) => match eval(&unary.expr)? {
+            Value::Number(n) => Value::Number(format!("-{n}").parse().map_err(|_| unsupported())?),
+            _ => return Err(unsupported()),
+        },
+        Expr::Paren(paren) => eval(&paren.expr)?,
+        Expr::Group(group) => eval(&group.expr)?,
+        Expr::MethodCall(call)
+            if call.args.is_empty()
+                && ["to_string", "to_owned", "into"]
+                    .contains(&call.method.to_string().as_str()) =>
+        {
+            eval(&call.receiver)?
+        }
+        Expr::Call(call) => {
+            let Expr::Path(func) = &*call.func else {
+                return Err(unsupported());
+            };
+            let segments: Vec<String> = func
+                .path
+                .segments
+                .iter()
+                .map(|segment| segment.ident.to_string())
+                .collect();
+            match (segments.as_slice(), call.args.len()) {
+                ([some], 1) if some == "Some" => eval(&call.args[0])?,
+                ([ty, from], 1) if ty == "String" && from == "from" => eval(&call.args[0])?,
+                _ => return Err(unsupported()),
+            }
+        }
+        Expr::Path(path) if path.path.is_ident("None") => Value::Null,
+        // `Enum::Variant` of a unit enum, serialized in snake_case.
+        Expr::Path(path) if path.path.segments.len() == 2 => {
+            snake_variant(&path.path).ok_or_else(unsupported)?.into()
+        }
+        // `Enum::Variant { .. }` of an internally tagged enum.
+        Expr::Struct(lit) if lit.rest.is_none() => {
+            let mut fields = Map::new();
+            fields.insert(
+                "type".into(),
+                snake_variant(&lit.path).ok_or_else(unsupported)?.into(),
+            );
+            for field in &lit.fields {
+                let syn::Member::Named(name) = &field.member else {
+                    return Err(unsupported());
+                };
+                fields.insert(name.to_string(), eval(&field.expr)?);
+            }
+            Value::Object(fields)
+        }
+        _ => return Err(unsupported()),
+    })
+}
+
+fn snake_variant(path: &syn::Path) -> Option<String> {
+    path.segments
+        .last()
+        .map(|segment| segment.ident.to_string().to_snake_case())
+}
+
+fn from_json<T: serde::de::DeserializeOwned>(
+    what: &'static str,
+    name: &str,
+    value: Value,
+) -> Result<T, ParseStateError> {
+    serde_json::from_value(value).map_err(|e| ParseStateError::Invalid {
+        what,
+        name: name.to_string(),
+        msg: e.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use editor_macros::state;
+
+    use crate::{inspect_state_as_macro, parse_state_macro};
+
+    fn round_trip(state: &editor_state::State) {
+        let text = inspect_state_as_macro(state);
+        let parsed = parse_state_macro(&text, 1).unwrap();
+        assert_eq!(parsed.state.to_plain(), state.to_plain());
+        assert_eq!(inspect_state_as_macro(&parsed.state), text);
+    }
+
+    #[test]
+    fn parses_inspected_state_back() {
+        let (state, ..) = state! {
+            doc { root {
+                p1: paragraph [alignment(Alignment::Center)] {
+                    text("Hello ")
+                    text("world") [bold, text_color("#ff0000".to_string())]
+                }
+                blockquote {
+                    p2: paragraph { text("quoted") }
+                }
+            } }
+            selection: (p1, 2) -> (p2, 3)
+        };
+        round_trip(&state);
+    }
+
+    #[test]
+    fn keeps_labels_affinity_and_pending_modifiers() {
+        let (state, p1) = state! {
+            doc { root { p1: paragraph { text("Hi") } } }
+            selection: (p1, 2, <)
+            pending_modifiers: [italic, !bold]
+        };
+        let parsed = parse_state_macro(&inspect_state_as_macro(&state), 1).unwrap();
+        assert_eq!(parsed.labels["p1"], p1);
+        assert_eq!(parsed.state.selection, state.selection);
+        assert_eq!(parsed.state.pending_modifiers, state.pending_modifiers);
+    }
+
+    #[test]
+    fn accepts_the_bare_grammar() {
+        let parsed = parse_state_macro(
+            "doc { root(layout_mode: LayoutMode::Continuous { max_width: 600 }) { \
+             p1: paragraph { text(\"x\") } } } selection: none",
+            1,
+        )
+        .unwrap();
+        assert!(parsed.state.selection.is_none());
+        assert!(parsed.labels.contains_key("p1"));
+    }
+
+    #[test]
+    fn rejects_unknown_labels_and_values() {
+        let unknown = parse_state_macro("doc { root { paragraph {} } } selection: (p9, 0)", 1);
+        assert!(unknown.is_err_and(|e| e.to_string() == "unknown label `p9`"));
+
+        let value = parse_state_macro(
+            "doc { root { image(id: Some(load())) } } selection: none",
+            1,
+        );
+        assert!(matches!(
+            value,
+            Err(super::ParseStateError::UnsupportedValue { .. })
+        ));
+    }
+}
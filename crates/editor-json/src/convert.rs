@@ -0,0 +1,584 @@
+use std::collections::BTreeMap;
+
+use editor_model::{
+    Alignment, BlockquoteVariant, CalloutVariant, HorizontalRuleVariant, LayoutMode, Modifier,
+    ModifierType, PlainArchivedNode, PlainBlockquoteNode, PlainBulletListNode, PlainCalloutNode,
+    PlainColumnBreakNode, PlainDoc, PlainEmbedNode, PlainFileNode, PlainFoldContentNode,
+    PlainFoldNode, PlainFoldTitleNode, PlainHardBreakNode, PlainHorizontalRuleNode, PlainImageNode,
+    PlainListItemNode, PlainNode, PlainNodeEntry, PlainOrderedListNode, PlainPageBreakNode,
+    PlainParagraphNode, PlainRootNode, PlainTabNode, PlainTableCellNode, PlainTableNode,
+    PlainTableRowNode, PlainTextNode, TableBorderStyle, TextDirection,
+};
+use serde::Deserialize;
+
+use crate::{
+    DOCUMENT_VERSION, JsonAlignment, JsonBlockquoteVariant, JsonCalloutVariant, JsonDocument,
+    JsonError, JsonHorizontalRuleVariant, JsonLayoutMode, JsonMark, JsonNode, JsonNodeEntry,
+    JsonTableBorderStyle, JsonTextDirection,
+};
+
+/// Serializes `doc` in the current document version.
+pub fn export_json(doc: &PlainDoc) -> String {
+    serde_json::to_string(&JsonDocument::from_plain(doc)).expect("document serializes")
+}
+
+/// Parses a document written in [`DOCUMENT_VERSION`], the only version so
+/// far; any other version is rejected. The version is read first so a newer
+/// document fails as unsupported rather than as malformed.
+pub fn import_json(json: &str) -> Result<PlainDoc, JsonError> {
+    #[derive(Deserialize)]
+    struct Header {
+        version: u32,
+    }
+
+    let Header { version } = serde_json::from_str(json)?;
+    check_version(version)?;
+    serde_json::from_str::<JsonDocument>(json)?.into_plain()
+}
+
+fn check_version(version: u32) -> Result<(), JsonError> {
+    if version == DOCUMENT_VERSION {
+        Ok(())
+    } else {
+        Err(JsonError::UnsupportedVersion {
+            found: version,
+            supported: DOCUMENT_VERSION,
+        })
+    }
+}
+
+impl JsonDocument {
+    pub fn from_plain(doc: &PlainDoc) -> Self {
+        Self {
+            version: DOCUMENT_VERSION,
+            root: JsonNodeEntry::from_plain(&doc.root),
+        }
+    }
+
+    pub fn into_plain(self) -> Result<PlainDoc, JsonError> {
+        check_version(self.version)?;
+        if !matches!(self.root.node, JsonNode::Root { .. }) {
+            return Err(JsonError::InvalidRoot);
+        }
+        Ok(PlainDoc {
+            root: self.root.into_plain()?,
+        })
+    }
+}
+
+impl JsonNodeEntry {
+    fn from_plain(entry: &PlainNodeEntry) -> Self {
+        Self {
+            node: JsonNode::from_plain(&entry.node),
+            marks: entry.modifiers.values().map(JsonMark::from_plain).collect(),
+            carry: entry.carry.iter().map(JsonMark::from_plain).collect(),
+            children: entry.children.iter().map(Self::from_plain).collect(),
+        }
+    }
+
+    fn into_plain(self) -> Result<PlainNodeEntry, JsonError> {
+        let mut modifiers = BTreeMap::new();
+        for mark in self.marks {
+            let modifier = mark.into_plain()?;
+            modifiers.insert(modifier.as_type(), modifier);
+        }
+        Ok(PlainNodeEntry {
+            node: self.node.into_plain(),
+            modifiers,
+            carry: self
+                .carry
+                .into_iter()
+                .map(JsonMark::into_plain)
+                .collect::<Result<_, _>>()?,
+            children: self
+                .children
+                .into_iter()
+                .map(Self::into_plain)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl JsonNode {
+    fn from_plain(node: &PlainNode) -> Self {
+        match node {
+            PlainNode::Root(n) => Self::Root {
+                layout_mode: n.layout_mode.into(),
+            },
+            PlainNode::Paragraph(_) => Self::Paragraph,
+            PlainNode::Blockquote(n) => Self::Blockquote {
+                variant: n.variant.into(),
+            },
+            PlainNode::Callout(n) => Self::Callout {
+                variant: n.variant.into(),
+            },
+            PlainNode::Text(n) => Self::Text {
+                text: n.text.clone(),
+            },
+            PlainNode::BulletList(_) => Self::BulletList,
+            PlainNode::OrderedList(_) => Self::OrderedList,
+            PlainNode::ListItem(_) => Self::ListItem,
+            PlainNode::Fold(_) => Self::Fold,
+            PlainNode::FoldTitle(_) => Self::FoldTitle,
+            PlainNode::FoldContent(_) => Self::FoldContent,
+            PlainNode::Table(n) => Self::Table {
+                border_style: n.border_style.into(),
+                proportion: n.proportion,
+            },
+            PlainNode::TableRow(_) => Self::TableRow,
+            PlainNode::TableCell(n) => Self::TableCell {
+                col_width: n.col_width,
+                background_color: n.background_color.clone(),
+            },
+            PlainNode::Image(n) => Self::Image {
+                id: n.id.clone(),
+                proportion: n.proportion,
+            },
+            PlainNode::File(n) => Self::File { id: n.id.clone() },
+            PlainNode::Embed(n) => Self::Embed { id: n.id.clone() },
+            PlainNode::Archived(n) => Self::Archived { id: n.id.clone() },
+            PlainNode::HardBreak(_) => Self::HardBreak,
+            PlainNode::HorizontalRule(n) => Self::HorizontalRule {
+                variant: n.variant.into(),
+            },
+            PlainNode::PageBreak(_) => Self::PageBreak,
+            PlainNode::Tab(_) => Self::Tab,
+            PlainNode::ColumnBreak(_) => Self::ColumnBreak,
+            PlainNode::Unknown => Self::Unknown,
+        }
+    }
+
+    fn into_plain(self) -> PlainNode {
+        match self {
+            Self::Root { layout_mode } => PlainNode::Root(PlainRootNode {
+                layout_mode: layout_mode.into(),
+            }),
+            Self::Paragraph => PlainNode::Paragraph(PlainParagraphNode {}),
+            Self::Blockquote { variant } => PlainNode::Blockquote(PlainBlockquoteNode {
+                variant: variant.into(),
+            }),
+            Self::Callout { variant } => PlainNode::Callout(PlainCalloutNode {
+                variant: variant.into(),
+            }),
+            Self::Text { text } => PlainNode::Text(PlainTextNode { text }),
+            Self::BulletList => PlainNode::BulletList(PlainBulletListNode {}),
+            Self::OrderedList => PlainNode::OrderedList(PlainOrderedListNode {}),
+            Self::ListItem => PlainNode::ListItem(PlainListItemNode {}),
+            Self::Fold => PlainNode::Fold(PlainFoldNode {}),
+            Self::FoldTitle => PlainNode::FoldTitle(PlainFoldTitleNode {}),
+            Self::FoldContent => PlainNode::FoldContent(PlainFoldContentNode {}),
+            Self::Table {
+                border_style,
+                proportion,
+            } => PlainNode::Table(PlainTableNode {
+                border_style: border_style.into(),
+                proportion,
+            }),
+            Self::TableRow => PlainNode::TableRow(PlainTableRowNode {}),
+            Self::TableCell {
+                col_width,
+                background_color,
+            } => PlainNode::TableCell(PlainTableCellNode {
+                col_width,
+                background_color,
+            }),
+            Self::Image { id, proportion } => PlainNode::Image(PlainImageNode { id, proportion }),
+            Self::File { id } => PlainNode::File(PlainFileNode { id }),
+            Self::Embed { id } => PlainNode::Embed(PlainEmbedNode { id }),
+            Self::Archived { id } => PlainNode::Archived(PlainArchivedNode { id }),
+            Self::HardBreak => PlainNode::HardBreak(PlainHardBreakNode {}),
+            Self::HorizontalRule { variant } => {
+                PlainNode::HorizontalRule(PlainHorizontalRuleNode {
+                    variant: variant.into(),
+                })
+            }
+            Self::PageBreak => PlainNode::PageBreak(PlainPageBreakNode {}),
+            Self::Tab => PlainNode::Tab(PlainTabNode {}),
+            Self::ColumnBreak => PlainNode::ColumnBreak(PlainColumnBreakNode {}),
+            Self::Unknown => PlainNode::Unknown,
+        }
+    }
+}
+
+impl JsonMark {
+    fn from_plain(modifier: &Modifier) -> Self {
+        match modifier {
+            Modifier::Bold => Self::Bold,
+            Modifier::Italic => Self::Italic,
+            Modifier::Underline => Self::Underline,
+            Modifier::Strikethrough => Self::Strikethrough,
+            Modifier::FontSize { value } => Self::FontSize { value: *value },
+            Modifier::FontFamily { value } => Self::FontFamily {
+                value: value.clone(),
+            },
+            Modifier::FontWeight { value } => Self::FontWeight { value: *value },
+            Modifier::TextColor { value } => Self::TextColor {
+                value: value.clone(),
+            },
+            Modifier::BackgroundColor { value } => Self::BackgroundColor {
+                value: value.clone(),
+            },
+            Modifier::LetterSpacing { value } => Self::LetterSpacing { value: *value },
+            Modifier::Link { href } => Self::Link { href: href.clone() },
+            Modifier::Ruby { text } => Self::Ruby { text: text.clone() },
+            Modifier::LineHeight { value } => Self::LineHeight { value: *value },
+            Modifier::BlockGap { value } => Self::BlockGap { value: *value },
+            Modifier::ParagraphIndent { value } => Self::ParagraphIndent { value: *value },
+            Modifier::Alignment { value } => Self::Alignment {
+                value: (*value).into(),
+            },
+            Modifier::Direction { value } => Self::Direction {
+                value: (*value).into(),
+            },
+        }
+    }
+
+    /// Out-of-range values are rejected here rather than left for the editor
+    /// to silently drop.
+    fn into_plain(self) -> Result<Modifier, JsonError> {
+        let modifier = match self {
+            Self::Bold => Modifier::Bold,
+            Self::Italic => Modifier::Italic,
+            Self::Underline => Modifier::Underline,
+            Self::Strikethrough => Modifier::Strikethrough,
+            Self::FontSize { value } => Modifier::FontSize { value },
+            Self::FontFamily { value } => Modifier::FontFamily { value },
+            Self::FontWeight { value } => Modifier::FontWeight { value },
+            Self::TextColor { value } => Modifier::TextColor { value },
+            Self::BackgroundColor { value } => Modifier::BackgroundColor { value },
+            Self::LetterSpacing { value } => Modifier::LetterSpacing { value },
+            Self::Link { href } => Modifier::Link { href },
+            Self::Ruby { text } => Modifier::Ruby { text },
+            Self::LineHeight { value } => Modifier::LineHeight { value },
+            Self::BlockGap { value } => Modifier::BlockGap { value },
+            Self::ParagraphIndent { value } => Modifier::ParagraphIndent { value },
+            Self::Alignment { value } => Modifier::Alignment {
+                value: value.into(),
+            },
+            Self::Direction { value } => Modifier::Direction {
+                value: value.into(),
+            },
+        };
+        if modifier.is_valid() {
+            Ok(modifier)
+        } else {
+            Err(JsonError::InvalidMark(ModifierType::from(&modifier).into()))
+        }
+    }
+}
+
+impl Default for JsonLayoutMode {
+    fn default() -> Self {
+        LayoutMode::default().into()
+    }
+}
+
+impl From<LayoutMode> for JsonLayoutMode {
+    fn from(mode: LayoutMode) -> Self {
+        match mode {
+            LayoutMode::Paginated {
+                page_width,
+                page_height,
+                page_margin_top,
+                page_margin_bottom,
+                page_margin_left,
+                page_margin_right,
+                columns,
+                column_gap,
+            } => Self::Paginated {
+                page_width,
+                page_height,
+                page_margin_top,
+                page_margin_bottom,
+                page_margin_left,
+                page_margin_right,
+                columns,
+                column_gap,
+            },
+            LayoutMode::Continuous { max_width } => Self::Continuous { max_width },
+            LayoutMode::Manuscript {
+                columns,
+                rows,
+                cell_size,
+                row_gap,
+                page_margin,
+            } => Self::Manuscript {
+                columns,
+                rows,
+                cell_size,
+                row_gap,
+                page_margin,
+            },
+        }
+    }
+}
+
+impl From<JsonLayoutMode> for LayoutMode {
+    fn from(mode: JsonLayoutMode) -> Self {
+        match mode {
+            JsonLayoutMode::Paginated {
+                page_width,
+                page_height,
+                page_margin_top,
+                page_margin_bottom,
+                page_margin_left,
+                page_margin_right,
+                columns,
+                column_gap,
+            } => Self::Paginated {
+                page_width,
+                page_height,
+                page_margin_top,
+                page_margin_bottom,
+                page_margin_left,
+                page_margin_right,
+                columns,
+                column_gap,
+            },
+            JsonLayoutMode::Continuous { max_width } => Self::Continuous { max_width },
+            JsonLayoutMode::Manuscript {
+                columns,
+                rows,
+                cell_size,
+                row_gap,
+                page_margin,
+            } => Self::Manuscript {
+                columns,
+                rows,
+                cell_size,
+                row_gap,
+                page_margin,
+            },
+        }
+    }
+}
+
+/// Both directions of a one-to-one mapping between fieldless enums.
+macro_rules! mirror_enum {
+    ($model:ident <=> $json:ident { $($variant:ident),* $(,)? }) => {
+        impl From<$model> for $json {
+            fn from(value: $model) -> Self {
+                match value {
+                    $($model::$variant => Self::$variant,)*
+                }
+            }
+        }
+
+        impl From<$json> for $model {
+            fn from(value: $json) -> Self {
+                match value {
+                    $($json::$variant => Self::$variant,)*
+                }
+            }
+        }
+    };
+}
+
+mirror_enum!(Alignment <=> JsonAlignment { Left, Center, Right, Justify });
+mirror_enum!(TextDirection <=> JsonTextDirection { Auto, Ltr, Rtl });
+mirror_enum!(BlockquoteVariant <=> JsonBlockquoteVariant {
+    LeftLine,
+    LeftQuote,
+    MessageSent,
+    MessageReceived,
+});
+mirror_enum!(CalloutVariant <=> JsonCalloutVariant {
+    Info,
+    Success,
+    Warning,
+    Danger,
+});
+mirror_enum!(TableBorderStyle <=> JsonTableBorderStyle {
+    Solid,
+    Dashed,
+    Dotted,
+    None,
+});
+mirror_enum!(HorizontalRuleVariant <=> JsonHorizontalRuleVariant {
+    Line,
+    DashedLine,
+    CircleLine,
+    DiamondLine,
+    Circle,
+    Diamond,
+    ThreeCircles,
+    ThreeDiamonds,
+    Zigzag,
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(node: PlainNode, children: Vec<PlainNodeEntry>) -> PlainNodeEntry {
+        PlainNodeEntry {
+            node,
+            modifiers: BTreeMap::new(),
+            carry: Vec::new(),
+            children,
+        }
+    }
+
+    fn text(text: &str, modifiers: Vec<Modifier>) -> PlainNodeEntry {
+        PlainNodeEntry {
+            modifiers: modifiers.into_iter().map(|m| (m.as_type(), m)).collect(),
+            ..entry(
+                PlainNode::Text(PlainTextNode {
+                    text: text.to_owned(),
+                }),
+                Vec::new(),
+            )
+        }
+    }
+
+    fn sample() -> PlainDoc {
+        PlainDoc {
+            root: entry(
+                PlainNode::Root(PlainRootNode {
+                    layout_mode: LayoutMode::Manuscript {
+                        columns: 20,
+                        rows: 10,
+                        cell_size: 32,
+                        row_gap: 12,
+                        page_margin: 48,
+                    },
+                }),
+                vec![
+                    PlainNodeEntry {
+                        carry: vec![Modifier::Italic],
+                        modifiers: BTreeMap::from([(
+                            ModifierType::Alignment,
+                            Modifier::Alignment {
+                                value: Alignment::Center,
+                            },
+                        )]),
+                        ..entry(
+                            PlainNode::Paragraph(PlainParagraphNode {}),
+                            vec![
+                                text("hello ", vec![Modifier::Bold]),
+                                text(
+                                    "world",
+                                    vec![
+                                        Modifier::Link {
+                                            href: "https://example.com".to_owned(),
+                                        },
+                                        Modifier::FontSize { value: 1600 },
+                                    ],
+                                ),
+                            ],
+                        )
+                    },
+                    entry(
+                        PlainNode::Table(PlainTableNode {
+                            border_style: TableBorderStyle::Dashed,
+                            proportion: 80,
+                        }),
+                        vec![entry(
+                            PlainNode::TableRow(PlainTableRowNode {}),
+                            vec![entry(
+                                PlainNode::TableCell(PlainTableCellNode {
+                                    col_width: Some(120),
+                                    background_color: None,
+                                }),
+                                vec![entry(PlainNode::Paragraph(PlainParagraphNode {}), vec![])],
+                            )],
+                        )],
+                    ),
+                    entry(
+                        PlainNode::Archived(PlainArchivedNode {
+                            id: Some("archived-1".to_owned()),
+                        }),
+                        vec![],
+                    ),
+                    entry(PlainNode::Unknown, vec![]),
+                ],
+            ),
+        }
+    }
+
+    #[test]
+    fn plain_document_round_trips() {
+        let doc = sample();
+        assert_eq!(import_json(&export_json(&doc)).unwrap(), doc);
+    }
+
+    #[test]
+    fn export_uses_stable_names_and_omits_empty_lists() {
+        let json: serde_json::Value = serde_json::from_str(&export_json(&sample())).unwrap();
+        assert_eq!(json["version"], DOCUMENT_VERSION);
+        assert_eq!(json["root"]["node"]["layout_mode"]["type"], "manuscript");
+
+        let paragraph = &json["root"]["children"][0];
+        assert_eq!(
+            paragraph["node"],
+            serde_json::json!({ "type": "paragraph" })
+        );
+        assert_eq!(
+            paragraph["carry"],
+            serde_json::json!([{ "type": "italic" }])
+        );
+        assert_eq!(
+            paragraph["children"][1]["marks"],
+            serde_json::json!([
+                { "type": "font_size", "value": 1600 },
+                { "type": "link", "href": "https://example.com" },
+            ])
+        );
+        assert!(paragraph["children"][0].get("children").is_none());
+    }
+
+    #[test]
+    fn unknown_node_types_import_as_placeholders_keeping_children() {
+        let json = r#"{
+            "version": 1,
+            "root": {
+                "node": { "type": "root" },
+                "children": [{
+                    "node": { "type": "math_block", "latex": "x^2" },
+                    "children": [{ "node": { "type": "paragraph" } }]
+                }]
+            }
+        }"#;
+
+        let doc = import_json(json).unwrap();
+        assert_eq!(
+            doc.root.node,
+            PlainNode::Root(PlainRootNode {
+                layout_mode: LayoutMode::default(),
+            })
+        );
+        let unknown = &doc.root.children[0];
+        assert_eq!(unknown.node, PlainNode::Unknown);
+        assert_eq!(
+            unknown.children[0].node,
+            PlainNode::Paragraph(PlainParagraphNode {})
+        );
+    }
+
+    #[test]
+    fn rejects_unsupported_versions_invalid_roots_and_marks() {
+        let newer = r#"{ "version": 2, "root": { "shape": "changed" } }"#;
+        assert!(matches!(
+            import_json(newer),
+            Err(JsonError::UnsupportedVersion {
+                found: 2,
+                supported: 1
+            })
+        ));
+
+        let not_root = r#"{ "version": 1, "root": { "node": { "type": "paragraph" } } }"#;
+        assert!(matches!(import_json(not_root), Err(JsonError::InvalidRoot)));
+
+        let bad_mark = r#"{
+            "version": 1,
+            "root": {
+                "node": { "type": "root" },
+                "marks": [{ "type": "font_weight", "value": 450 }]
+            }
+        }"#;
+        assert!(matches!(
+            import_json(bad_mark),
+            Err(JsonError::InvalidMark("font_weight"))
+        ));
+    }
+}
@@ -0,0 +1,264 @@
+//! The public JSON document format. These types mirror `PlainDoc` but are
+//! owned by this crate: `editor-model` may rename or restructure its nodes,
+//! while this shape only changes together with [`DOCUMENT_VERSION`].
+
+use editor_macros::ffi;
+use serde::{Deserialize, Serialize};
+
+/// Bumped on any change that an older reader could not accept.
+pub const DOCUMENT_VERSION: u32 = 1;
+
+#[ffi]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JsonDocument {
+    pub version: u32,
+    pub root: JsonNodeEntry,
+}
+
+#[ffi]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JsonNodeEntry {
+    pub node: JsonNode,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub marks: Vec<JsonMark>,
+    /// Marks an empty paragraph keeps for the text typed into it next.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub carry: Vec<JsonMark>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<JsonNodeEntry>,
+}
+
+#[ffi]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum JsonNode {
+    Root {
+        #[serde(default)]
+        layout_mode: JsonLayoutMode,
+    },
+    Paragraph,
+    Blockquote {
+        #[serde(default)]
+        variant: JsonBlockquoteVariant,
+    },
+    Callout {
+        #[serde(default)]
+        variant: JsonCalloutVariant,
+    },
+    Text {
+        text: String,
+    },
+    BulletList,
+    OrderedList,
+    ListItem,
+    Fold,
+    FoldTitle,
+    FoldContent,
+    Table {
+        #[serde(default)]
+        border_style: JsonTableBorderStyle,
+        #[ffi(default = "100")]
+        #[serde(default = "default_proportion")]
+        proportion: u32,
+    },
+    TableRow,
+    TableCell {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        col_width: Option<u32>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        background_color: Option<String>,
+    },
+    Image {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        id: Option<String>,
+        #[ffi(default = "100")]
+        #[serde(default = "default_proportion")]
+        proportion: u32,
+    },
+    File {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        id: Option<String>,
+    },
+    Embed {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        id: Option<String>,
+    },
+    /// A node removed from the editable tree but kept for its content.
+    Archived {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        id: Option<String>,
+    },
+    HardBreak,
+    HorizontalRule {
+        #[serde(default)]
+        variant: JsonHorizontalRuleVariant,
+    },
+    PageBreak,
+    Tab,
+    ColumnBreak,
+    /// Any `type` this version does not know. It is read as a placeholder
+    /// whose attributes are dropped and whose children are kept.
+    #[serde(other)]
+    Unknown,
+}
+
+fn default_proportion() -> u32 {
+    100
+}
+
+#[ffi]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum JsonMark {
+    Bold,
+    Italic,
+    Underline,
+    Strikethrough,
+    /// pt x 100 (e.g. 16pt -> 1600)
+    FontSize {
+        value: u32,
+    },
+    FontFamily {
+        value: String,
+    },
+    FontWeight {
+        value: u16,
+    },
+    TextColor {
+        value: String,
+    },
+    BackgroundColor {
+        value: String,
+    },
+    /// em x 100 (e.g. 0.05em -> 5)
+    LetterSpacing {
+        value: i32,
+    },
+    Link {
+        href: String,
+    },
+    Ruby {
+        text: String,
+    },
+    /// % (e.g. 160 -> 160%)
+    LineHeight {
+        value: u32,
+    },
+    /// x 100 (e.g. 100% -> 100)
+    BlockGap {
+        value: u32,
+    },
+    /// x 100 (e.g. 100% -> 100)
+    ParagraphIndent {
+        value: u32,
+    },
+    Alignment {
+        value: JsonAlignment,
+    },
+    Direction {
+        value: JsonTextDirection,
+    },
+}
+
+#[ffi]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum JsonLayoutMode {
+    Paginated {
+        page_width: u32,
+        page_height: u32,
+        page_margin_top: u32,
+        page_margin_bottom: u32,
+        page_margin_left: u32,
+        page_margin_right: u32,
+        #[ffi(default = "1")]
+        #[serde(default = "default_columns")]
+        columns: u32,
+        #[serde(default)]
+        column_gap: u32,
+    },
+    Continuous {
+        max_width: u32,
+    },
+    Manuscript {
+        columns: u32,
+        rows: u32,
+        cell_size: u32,
+        row_gap: u32,
+        page_margin: u32,
+    },
+}
+
+fn default_columns() -> u32 {
+    1
+}
+
+#[ffi]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JsonAlignment {
+    #[default]
+    Left,
+    Center,
+    Right,
+    Justify,
+}
+
+#[ffi]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JsonTextDirection {
+    #[default]
+    Auto,
+    Ltr,
+    Rtl,
+}
+
+#[ffi]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JsonBlockquoteVariant {
+    #[default]
+    LeftLine,
+    LeftQuote,
+    MessageSent,
+    MessageReceived,
+}
+
+#[ffi]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JsonCalloutVariant {
+    #[default]
+    Info,
+    Success,
+    Warning,
+    Danger,
+}
+
+#[ffi]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JsonTableBorderStyle {
+    #[default]
+    Solid,
+    Dashed,
+    Dotted,
+    None,
+}
+
+#[ffi]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JsonHorizontalRuleVariant {
+    #[default]
+    Line,
+    DashedLine,
+    CircleLine,
+    DiamondLine,
+    Circle,
+    Diamond,
+    ThreeCircles,
+    ThreeDiamonds,
+    Zigzag,
+}
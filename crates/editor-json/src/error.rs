@@ -0,0 +1,14 @@
+#[derive(Debug, thiserror::Error)]
+pub enum JsonError {
+    #[error("malformed document: {0}")]
+    Malformed(#[from] serde_json::Error),
+
+    #[error("unsupported document version {found} (supported: {supported})")]
+    UnsupportedVersion { found: u32, supported: u32 },
+
+    #[error("document root must be a `root` node")]
+    InvalidRoot,
+
+    #[error("invalid `{0}` mark")]
+    InvalidMark(&'static str),
+}
@@ -0,0 +1,9 @@
+editor_macros::preamble!();
+
+mod convert;
+mod document;
+mod error;
+
+pub use convert::*;
+pub use document::*;
+pub use error::*;
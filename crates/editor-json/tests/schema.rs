@@ -0,0 +1,40 @@
+use std::path::PathBuf;
+
+use editor_bindgen::json_schema;
+use editor_bindgen::meta::FfiMeta;
+use editor_bindgen::reader::read_ffi_meta;
+
+/// Every `FFI_META_*` static of the crate, read from its rlib as the bindgen
+/// binaries read a built library. The test binary itself keeps only the
+/// statics it references, so it cannot enumerate them.
+fn metas() -> Vec<FfiMeta> {
+    let exe = std::env::current_exe().unwrap();
+    let deps = exe.parent().unwrap();
+    let rlib: PathBuf = std::fs::read_dir(deps)
+        .unwrap()
+        .filter_map(Result::ok)
+        .filter(|entry| {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            name.starts_with("libeditor_json-") && name.ends_with(".rlib")
+        })
+        .max_by_key(|entry| entry.metadata().and_then(|meta| meta.modified()).ok())
+        .expect("editor-json rlib next to the test binary")
+        .path();
+    read_ffi_meta(&rlib)
+}
+
+#[test]
+fn schema_file_matches_types() {
+    let rendered = json_schema::generate(&metas(), "JsonDocument");
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/schema/document.schema.json");
+    if std::env::var("UPDATE_SCHEMA").is_ok() {
+        std::fs::write(path, &rendered).unwrap();
+    }
+    let on_disk = std::fs::read_to_string(path)
+        .expect("schema/document.schema.json must be checked in — generate with UPDATE_SCHEMA=1");
+    assert_eq!(
+        on_disk, rendered,
+        "document types changed: regenerate the schema with UPDATE_SCHEMA=1 and bump DOCUMENT_VERSION if older readers break"
+    );
+}
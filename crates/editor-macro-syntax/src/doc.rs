@@ -61,7 +61,7 @@ impl Parse for DocTree {
     }
 }
 
-pub(crate) fn parse_node_list(input: ParseStream) -> Result<Vec<NodeDef>> {
+pub fn parse_node_list(input: ParseStream) -> Result<Vec<NodeDef>> {
     let mut nodes = Vec::new();
     while !input.is_empty() {
         nodes.push(parse_node_def(input)?);
@@ -181,7 +181,7 @@ fn parse_text_content(input: ParseStream) -> Result<LitStr> {
     content.parse()
 }
 
-pub(crate) fn parse_modifier_list(input: ParseStream) -> Result<Vec<DecorationDef>> {
+pub fn parse_modifier_list(input: ParseStream) -> Result<Vec<DecorationDef>> {
     let content;
     bracketed!(content in input);
 
@@ -212,7 +212,7 @@ pub(crate) fn parse_modifier_list(input: ParseStream) -> Result<Vec<DecorationDe
     Ok(modifiers)
 }
 
-pub(crate) fn parse_decoration_params(input: ParseStream) -> Result<DecorationParams> {
+pub fn parse_decoration_params(input: ParseStream) -> Result<DecorationParams> {
     let content;
     parenthesized!(content in input);
 
@@ -0,0 +1,6 @@
+//! The grammar of the `state!`/`slice!` document macros, shared by the proc
+//! macros in `editor-macros` and by `editor-introspection`, which parses the
+//! same text at runtime.
+
+pub mod doc;
+pub mod state;
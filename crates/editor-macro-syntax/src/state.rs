@@ -3,7 +3,7 @@ use syn::ext::IdentExt;
 use syn::parse::{Parse, ParseStream};
 use syn::{LitInt, Result, Token, braced, bracketed, parenthesized, token};
 
-use crate::doc_macro::parse::{DecorationDef, DecorationParams, DocTree, parse_decoration_params};
+use crate::doc::{DecorationDef, DecorationParams, DocTree, parse_decoration_params};
 
 pub struct StateInput {
     pub doc_tree: DocTree,
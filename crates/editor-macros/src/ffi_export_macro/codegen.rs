@@ -13,6 +13,7 @@ pub fn generate(mode: FfiExportMode, mut item: ItemImpl) -> TokenStream {
     let attr = match mode {
         FfiExportMode::Uniffi => quote! { #[::uniffi::export] },
         FfiExportMode::Wasm => quote! { #[::wasm_bindgen::prelude::wasm_bindgen] },
+        FfiExportMode::Python => quote! { #[::pyo3::pymethods] },
     };
 
     quote! {
@@ -78,7 +79,7 @@ impl VisitMut for ComplexRewriter {
             };
 
             *ty = match self.mode {
-                FfiExportMode::Uniffi => syn::parse_quote! { String },
+                FfiExportMode::Uniffi | FfiExportMode::Python => syn::parse_quote! { String },
                 FfiExportMode::Wasm => syn::parse_quote! { ::tsify::Ts<#inner> },
             };
         }
@@ -78,8 +78,8 @@ fn extract_param_name(pat: &syn::Pat) -> String {
 
 fn has_constructor_attr(attrs: &[syn::Attribute]) -> bool {
     for attr in attrs {
-        // Direct: #[uniffi::constructor]
-        if path_matches(attr.path(), &["uniffi", "constructor"]) {
+        // Direct: #[uniffi::constructor] / pyo3 #[new]
+        if is_constructor_path(attr.path()) {
             return true;
         }
         // Via cfg_attr: #[cfg_attr(predicate, uniffi::constructor)]
@@ -93,7 +93,7 @@ fn has_constructor_attr(attrs: &[syn::Attribute]) -> bool {
                 Ok(path)
             });
             if let Ok(path) = result
-                && path_matches(&path, &["uniffi", "constructor"])
+                && is_constructor_path(&path)
             {
                 return true;
             }
@@ -102,6 +102,10 @@ fn has_constructor_attr(attrs: &[syn::Attribute]) -> bool {
     false
 }
 
+fn is_constructor_path(path: &syn::Path) -> bool {
+    path_matches(path, &["uniffi", "constructor"]) || path.is_ident("new")
+}
+
 fn path_matches(path: &syn::Path, segments: &[&str]) -> bool {
     if path.segments.len() != segments.len() {
         return false;
@@ -325,6 +329,21 @@ mod tests {
         );
     }
 
+    #[test]
+    fn pyo3_new_marks_a_constructor() {
+        let method: syn::ImplItemFn = syn::parse_quote! {
+            #[cfg_attr(feature = "python", new)]
+            pub fn create(icu_data: Vec<u8>) -> EditorResult<Owned<Self>> {
+                todo!()
+            }
+        };
+
+        let extracted = extract_method(&method, "Corpus");
+
+        assert!(extracted.is_constructor);
+        assert_eq!(extracted.return_type, FfiReturnType::Owned("Corpus".into()));
+    }
+
     #[test]
     fn complex_param_preserves_generic_type_arguments() {
         let ty: syn::Type = syn::parse_quote! {
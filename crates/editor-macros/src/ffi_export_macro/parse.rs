@@ -4,6 +4,7 @@ use syn::{Ident, parse::Parse};
 pub enum FfiExportMode {
     Uniffi,
     Wasm,
+    Python,
 }
 
 impl Parse for FfiExportMode {
@@ -12,9 +13,10 @@ impl Parse for FfiExportMode {
         match ident.to_string().as_str() {
             "uniffi" => Ok(Self::Uniffi),
             "wasm" => Ok(Self::Wasm),
+            "python" => Ok(Self::Python),
             other => Err(syn::Error::new(
                 ident.span(),
-                format!("expected `uniffi` or `wasm`, found `{other}`"),
+                format!("expected `uniffi`, `wasm` or `python`, found `{other}`"),
             )),
         }
     }
@@ -1,2 +1,2 @@
 pub mod codegen;
-pub mod parse;
+pub use editor_macro_syntax::state as parse;
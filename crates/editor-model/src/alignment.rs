@@ -1,6 +1,8 @@
 use editor_macros::ffi;
 use serde::{Deserialize, Serialize};
 
+/// `Left` and `Right` name the paragraph's start and end edges, so they
+/// mirror in a right-to-left paragraph.
 #[ffi]
 #[derive(
     Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize,
@@ -13,3 +15,17 @@ pub enum Alignment {
     Right,
     Justify,
 }
+
+/// Base direction a paragraph's bidi resolution starts from. `Auto` takes it
+/// from the paragraph's first strong character, as `dir="auto"` does in HTML.
+#[ffi]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum TextDirection {
+    #[default]
+    Auto,
+    Ltr,
+    Rtl,
+}
@@ -1,4 +1,4 @@
-use crate::alignment::Alignment;
+use crate::alignment::{Alignment, TextDirection};
 use editor_common::Tri;
 use editor_macros::ffi;
 use enum_map::Enum;
@@ -97,6 +97,10 @@ pub enum Modifier {
     Alignment {
         value: Alignment,
     },
+
+    Direction {
+        value: TextDirection,
+    },
 }
 
 impl Modifier {
@@ -121,7 +125,7 @@ impl Modifier {
             Modifier::LineHeight { value } => (50..=400).contains(value),
             Modifier::BlockGap { value } => (0..=400).contains(value),
             Modifier::ParagraphIndent { value } => (0..=400).contains(value),
-            Modifier::Alignment { .. } => true,
+            Modifier::Alignment { .. } | Modifier::Direction { .. } => true,
         }
     }
 }
@@ -238,6 +242,11 @@ impl ModifierState {
                     value: AlignmentValue { value: *value },
                 }
             }
+            Modifier::Direction { value } => {
+                self.direction = Tri::Uniform {
+                    value: DirectionValue { value: *value },
+                }
+            }
         }
     }
 
@@ -259,6 +268,7 @@ impl ModifierState {
             ModifierType::BlockGap => self.block_gap = Tri::Mixed,
             ModifierType::ParagraphIndent => self.paragraph_indent = Tri::Mixed,
             ModifierType::Alignment => self.alignment = Tri::Mixed,
+            ModifierType::Direction => self.direction = Tri::Mixed,
         }
     }
 }
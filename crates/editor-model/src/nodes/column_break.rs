@@ -0,0 +1,4 @@
+use editor_macros::NodeAttr;
+
+#[derive(Debug, Clone, PartialEq, NodeAttr)]
+pub struct ColumnBreakNode {}
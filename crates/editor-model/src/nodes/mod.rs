@@ -2,6 +2,7 @@ mod archived;
 mod blockquote;
 mod bullet_list;
 mod callout;
+mod column_break;
 mod embed;
 mod file;
 mod fold;
@@ -26,6 +27,7 @@ pub use archived::*;
 pub use blockquote::*;
 pub use bullet_list::*;
 pub use callout::*;
+pub use column_break::*;
 pub use embed::*;
 pub use file::*;
 pub use fold::*;
@@ -95,6 +97,7 @@ pub enum Node {
     HorizontalRule(HorizontalRuleNode),
     PageBreak(PageBreakNode),
     Tab(TabNode),
+    ColumnBreak(ColumnBreakNode),
     Unknown(UnknownNode),
 }
 
@@ -14,9 +14,58 @@ pub enum LayoutMode {
         page_margin_bottom: u32,
         page_margin_left: u32,
         page_margin_right: u32,
+        /// Number of text columns the page body is divided into. Content flows
+        /// top-to-bottom through each column before moving to the next.
+        #[ffi(default = "1")]
+        #[serde(default = "default_columns")]
+        columns: u32,
+        /// Horizontal gap between adjacent columns.
+        #[serde(default)]
+        column_gap: u32,
     },
     #[serde(rename_all = "snake_case")]
     Continuous { max_width: u32 },
+    /// 원고지 manuscript paper: pages of `columns` × `rows` square cells,
+    /// one character per cell.
+    #[serde(rename_all = "snake_case")]
+    Manuscript {
+        columns: u32,
+        rows: u32,
+        /// Side length of one cell.
+        cell_size: u32,
+        /// Blank band above each row of cells.
+        row_gap: u32,
+        page_margin: u32,
+    },
+}
+
+/// Upper bounds on a manuscript grid. Layout and drawing cost grow with
+/// `columns × rows` per page, and the values come straight from the document.
+pub const MANUSCRIPT_MAX_COLUMNS: u32 = 100;
+pub const MANUSCRIPT_MAX_ROWS: u32 = 100;
+pub const MANUSCRIPT_MAX_CELL_SIZE: u32 = 256;
+
+impl LayoutMode {
+    /// The mode with its manuscript grid clamped to at least one cell and at
+    /// most the `MANUSCRIPT_MAX_*` bounds; other modes are returned as is.
+    pub fn normalized(self) -> Self {
+        match self {
+            Self::Manuscript {
+                columns,
+                rows,
+                cell_size,
+                row_gap,
+                page_margin,
+            } => Self::Manuscript {
+                columns: columns.clamp(1, MANUSCRIPT_MAX_COLUMNS),
+                rows: rows.clamp(1, MANUSCRIPT_MAX_ROWS),
+                cell_size: cell_size.clamp(1, MANUSCRIPT_MAX_CELL_SIZE),
+                row_gap,
+                page_margin,
+            },
+            mode => mode,
+        }
+    }
 }
 
 impl Default for LayoutMode {
@@ -25,6 +74,10 @@ impl Default for LayoutMode {
     }
 }
 
+fn default_columns() -> u32 {
+    1
+}
+
 #[derive(Debug, Clone, PartialEq, NodeAttr)]
 pub struct RootNode {
     pub layout_mode: LwwReg<LayoutMode>,
@@ -56,4 +109,55 @@ mod tests {
         let parsed: LayoutMode = serde_json::from_str(&json).unwrap();
         assert_eq!(m, parsed);
     }
+
+    #[test]
+    fn manuscript_serde_roundtrip() {
+        let m = LayoutMode::Manuscript {
+            columns: 20,
+            rows: 10,
+            cell_size: 32,
+            row_gap: 12,
+            page_margin: 48,
+        };
+        let json = serde_json::to_string(&m).unwrap();
+        assert!(json.contains(r#""type":"manuscript""#));
+        let parsed: LayoutMode = serde_json::from_str(&json).unwrap();
+        assert_eq!(m, parsed);
+    }
+
+    #[test]
+    fn manuscript_grid_is_clamped_when_normalized() {
+        let m = LayoutMode::Manuscript {
+            columns: u32::MAX,
+            rows: 0,
+            cell_size: u32::MAX,
+            row_gap: 12,
+            page_margin: 48,
+        };
+        assert_eq!(
+            m.normalized(),
+            LayoutMode::Manuscript {
+                columns: MANUSCRIPT_MAX_COLUMNS,
+                rows: 1,
+                cell_size: MANUSCRIPT_MAX_CELL_SIZE,
+                row_gap: 12,
+                page_margin: 48,
+            }
+        );
+        assert_eq!(LayoutMode::default().normalized(), LayoutMode::default());
+    }
+
+    #[test]
+    fn paginated_without_columns_deserializes_as_single_column() {
+        let json = r#"{"type":"paginated","page_width":794,"page_height":1123,"page_margin_top":40,"page_margin_bottom":40,"page_margin_left":30,"page_margin_right":30}"#;
+        let parsed: LayoutMode = serde_json::from_str(json).unwrap();
+        assert!(matches!(
+            parsed,
+            LayoutMode::Paginated {
+                columns: 1,
+                column_gap: 0,
+                ..
+            }
+        ));
+    }
 }
@@ -6,6 +6,13 @@ impl NodeType {
     pub fn spec(self) -> &'static NodeSpec {
         Schema::node_spec(self)
     }
+
+    /// Leaves that may only occupy a paragraph's trailing slot and force the
+    /// flow past them — a page break advances to the next page, a column
+    /// break to the next column.
+    pub fn is_terminal_break(self) -> bool {
+        matches!(self, NodeType::PageBreak | NodeType::ColumnBreak)
+    }
 }
 
 impl Node {
@@ -83,6 +90,7 @@ mod tests {
             ModifierType::BlockGap,
             ModifierType::ParagraphIndent,
             ModifierType::Alignment,
+            ModifierType::Direction,
         ] {
             assert!(!ty.is_carry_kind(), "{ty:?} must not be a carry kind");
         }
@@ -40,7 +40,7 @@ impl Default for SchemaInner {
                     ..Default::default()
                 },
                 NodeType::Paragraph => NodeSpec {
-                    content: content_expr!((Text | HardBreak | Tab)*, PageBreak?),
+                    content: content_expr!((Text | HardBreak | Tab)*, (PageBreak | ColumnBreak)?),
                     ..Default::default()
                 },
                 NodeType::Text => NodeSpec {
@@ -76,6 +76,11 @@ impl Default for SchemaInner {
                     inline: true,
                     ..Default::default()
                 },
+                NodeType::ColumnBreak => NodeSpec {
+                    context: context_expr!(Root > Paragraph > &),
+                    inline: true,
+                    ..Default::default()
+                },
                 NodeType::HorizontalRule => NodeSpec {
                     selectable: true,
                     ..Default::default()
@@ -226,6 +231,11 @@ impl Default for SchemaInner {
                     target: context_expr!(Paragraph | Image | Table),
                     ..Default::default()
                 },
+                ModifierType::Direction => ModifierSpec {
+                    context: context_expr!(Paragraph),
+                    target: context_expr!(Paragraph),
+                    inheritable: false,
+                },
             },
         }
     }
@@ -31,6 +31,7 @@ pub enum AtomLeaf {
     HardBreak,
     Tab,
     PageBreak,
+    ColumnBreak,
     HorizontalRule {
         variant: crate::nodes::HorizontalRuleVariant,
     },
@@ -84,6 +85,7 @@ impl AtomLeaf {
             AtomLeaf::HardBreak => NodeType::HardBreak,
             AtomLeaf::Tab => NodeType::Tab,
             AtomLeaf::PageBreak => NodeType::PageBreak,
+            AtomLeaf::ColumnBreak => NodeType::ColumnBreak,
             AtomLeaf::HorizontalRule { .. } => NodeType::HorizontalRule,
             AtomLeaf::Image { .. } => NodeType::Image,
             AtomLeaf::File { .. } => NodeType::File,
@@ -97,11 +99,12 @@ impl AtomLeaf {
         !self.node_type().spec().inline
     }
 
-    pub fn node_type_set() -> [NodeType; 8] {
+    pub fn node_type_set() -> [NodeType; 9] {
         [
             NodeType::HardBreak,
             NodeType::Tab,
             NodeType::PageBreak,
+            NodeType::ColumnBreak,
             NodeType::HorizontalRule,
             NodeType::Image,
             NodeType::File,
@@ -112,11 +115,14 @@ impl AtomLeaf {
 
     pub fn into_node(self) -> crate::Node {
         use crate::Node;
-        use crate::nodes::{HardBreakNode, HorizontalRuleNode, PageBreakNode, TabNode};
+        use crate::nodes::{
+            ColumnBreakNode, HardBreakNode, HorizontalRuleNode, PageBreakNode, TabNode,
+        };
         match self {
             AtomLeaf::HardBreak => Node::HardBreak(HardBreakNode {}),
             AtomLeaf::Tab => Node::Tab(TabNode {}),
             AtomLeaf::PageBreak => Node::PageBreak(PageBreakNode {}),
+            AtomLeaf::ColumnBreak => Node::ColumnBreak(ColumnBreakNode {}),
             AtomLeaf::HorizontalRule { variant } => Node::HorizontalRule(HorizontalRuleNode {
                 variant: editor_crdt::LwwReg::with_value(variant),
             }),
@@ -134,6 +140,7 @@ impl AtomLeaf {
             Node::HardBreak(_) => AtomLeaf::HardBreak,
             Node::Tab(_) => AtomLeaf::Tab,
             Node::PageBreak(_) => AtomLeaf::PageBreak,
+            Node::ColumnBreak(_) => AtomLeaf::ColumnBreak,
             Node::HorizontalRule(n) => AtomLeaf::HorizontalRule {
                 variant: *n.variant.get(),
             },
@@ -235,6 +242,7 @@ mod tests {
         assert!(!AtomLeaf::HardBreak.is_block_level());
         assert!(!AtomLeaf::Tab.is_block_level());
         assert!(!AtomLeaf::PageBreak.is_block_level());
+        assert!(!AtomLeaf::ColumnBreak.is_block_level());
         assert!(
             AtomLeaf::HorizontalRule {
                 variant: HorizontalRuleVariant::default()
@@ -247,6 +255,7 @@ mod tests {
                 NodeType::HardBreak => AtomLeaf::HardBreak,
                 NodeType::Tab => AtomLeaf::Tab,
                 NodeType::PageBreak => AtomLeaf::PageBreak,
+                NodeType::ColumnBreak => AtomLeaf::ColumnBreak,
                 NodeType::HorizontalRule => AtomLeaf::HorizontalRule {
                     variant: HorizontalRuleVariant::default(),
                 },
@@ -471,8 +471,9 @@ fn complete_root_trailing_editable_paragraph(node: &mut RawNode) {
         return;
     };
     if paragraph.node_type != NodeType::Paragraph
-        || last_known_child(&paragraph.children).and_then(RawChild::as_child_type)
-            != Some(NodeType::PageBreak)
+        || !last_known_child(&paragraph.children)
+            .and_then(RawChild::as_child_type)
+            .is_some_and(NodeType::is_terminal_break)
     {
         return;
     }
@@ -1024,6 +1025,36 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn normalize_root_adds_trailing_paragraph_after_column_break() {
+        let tree = RawTree {
+            roots: vec![RawNode {
+                attrs: vec![],
+                id: Dot::ROOT,
+                node_type: NodeType::Root,
+                children: vec![RawChild::Block(RawNode {
+                    attrs: vec![],
+                    id: Dot::new(1, 1),
+                    node_type: NodeType::Paragraph,
+                    children: vec![RawChild::Leaf {
+                        id: Dot::new(1, 2),
+                        item: super::super::SeqItem::Atom(AtomLeaf::ColumnBreak),
+                    }],
+                })],
+            }],
+        };
+
+        let normalized = normalize(tree);
+        let root = &normalized.roots[0];
+
+        assert_eq!(root.children.len(), 2);
+        assert!(matches!(
+            &root.children[1],
+            RawChild::Block(paragraph)
+                if paragraph.node_type == NodeType::Paragraph && paragraph.id.is_synthetic()
+        ));
+    }
+
     #[test]
     fn normalize_content_shallow_adds_trailing_paragraph_after_page_break() {
         let mut root = RawNode {
@@ -2357,6 +2388,9 @@ mod tests {
                 Just(Shape::Leaf(super::super::super::SeqItem::Atom(
                     AtomLeaf::PageBreak
                 ))),
+                Just(Shape::Leaf(super::super::super::SeqItem::Atom(
+                    AtomLeaf::ColumnBreak
+                ))),
             ]
         }
 
@@ -2369,7 +2403,11 @@ mod tests {
                 .filter(|t| {
                     !matches!(
                         t,
-                        NodeType::HardBreak | NodeType::Tab | NodeType::PageBreak | NodeType::Root
+                        NodeType::HardBreak
+                            | NodeType::Tab
+                            | NodeType::PageBreak
+                            | NodeType::ColumnBreak
+                            | NodeType::Root
                     )
                 })
                 .collect();
@@ -1,4 +1,6 @@
-use crate::{Alignment, Modifier};
+use std::collections::BTreeMap;
+
+use crate::{Alignment, Modifier, PlainNode, PlainNodeEntry};
 
 pub fn default_modifiers() -> Vec<Modifier> {
     vec![
@@ -26,3 +28,13 @@ pub fn default_modifiers_with(overrides: Vec<Modifier>) -> Vec<Modifier> {
     mods.extend(overrides);
     mods
 }
+
+/// A plain node without modifiers or carry.
+pub fn plain_entry(node: PlainNode, children: Vec<PlainNodeEntry>) -> PlainNodeEntry {
+    PlainNodeEntry {
+        node,
+        modifiers: BTreeMap::new(),
+        carry: Vec::new(),
+        children,
+    }
+}
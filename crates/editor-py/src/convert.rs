@@ -0,0 +1,42 @@
+use cfg_if::cfg_if;
+
+use crate::error::EditorError;
+
+#[allow(clippy::wrong_self_convention)]
+pub trait FromFfi<T> {
+    fn from_ffi(self) -> Result<T, EditorError>;
+}
+
+pub trait IntoFfi<R> {
+    fn into_ffi(self) -> Result<R, EditorError>;
+}
+
+cfg_if! {
+    if #[cfg(feature = "python")] {
+        // `ffi_export(python)` rewrites `Complex<T>` to `String`: JSON text the
+        // generated Python wrapper `json.loads`/`json.dumps` on its side.
+        impl<T: serde::de::DeserializeOwned> FromFfi<T> for String {
+            fn from_ffi(self) -> Result<T, EditorError> {
+                serde_json::from_str(&self).map_err(|e| EditorError::Deserialization(e.to_string()))
+            }
+        }
+
+        impl<T: serde::Serialize> IntoFfi<String> for T {
+            fn into_ffi(self) -> Result<String, EditorError> {
+                serde_json::to_string(&self).map_err(|e| EditorError::Serialization(e.to_string()))
+            }
+        }
+    } else {
+        impl<T> FromFfi<T> for T {
+            fn from_ffi(self) -> Result<T, EditorError> { Ok(self) }
+        }
+
+        impl<T> IntoFfi<T> for T {
+            fn into_ffi(self) -> Result<T, EditorError> { Ok(self) }
+        }
+
+        pub type Complex<T> = T;
+    }
+}
+
+pub type Owned<T> = T;
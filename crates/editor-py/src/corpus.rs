@@ -0,0 +1,148 @@
+use editor_resource::CharacterCount;
+
+use crate::convert::*;
+use crate::error::{EditorError, EditorResult};
+
+/// Read-side document tooling for corpus analysis: exported changeset streams
+/// in, `PlainDoc` JSON / text / counts out, and back. Holds only the ICU data
+/// the character counts segment with.
+#[cfg_attr(feature = "python", pyo3::pyclass(frozen))]
+pub struct Corpus {
+    icu: editor_resource::IcuResources,
+}
+
+#[cfg_attr(feature = "python", editor_macros::ffi_export(python))]
+impl Corpus {
+    #[cfg_attr(feature = "python", new)]
+    pub fn create(icu_data: Vec<u8>) -> EditorResult<Owned<Self>> {
+        let icu = editor_resource::IcuResources::from_icu_data(&icu_data)?;
+        Ok(Self { icu })
+    }
+
+    /// Decodes a changeset stream and materializes the document it converges to.
+    pub fn materialize(&self, stream: Vec<u8>) -> EditorResult<Complex<editor_model::PlainDoc>> {
+        state_from_stream(&stream)?.to_plain().into_ffi()
+    }
+
+    /// Proofreading-grade text: the same `prose` projection the editor feeds to
+    /// spellcheck and statistics, not the raw block dump.
+    pub fn prose_text(&self, plain: Complex<editor_model::PlainDoc>) -> EditorResult<String> {
+        let state = state_from_plain(&plain.from_ffi()?)?;
+        Ok(editor_state::prose(&state.view()).text().to_string())
+    }
+
+    pub fn character_stats(
+        &self,
+        plain: Complex<editor_model::PlainDoc>,
+    ) -> EditorResult<Complex<CharacterCount>> {
+        let state = state_from_plain(&plain.from_ffi()?)?;
+        let text = editor_state::doc_plain_text(&state.view());
+        editor_resource::count_text(
+            &text,
+            &self.icu.segmenters.grapheme,
+            &self.icu.general_category,
+        )
+        .into_ffi()
+    }
+
+    /// Encodes `plain` as a fresh changeset stream (empty for an empty document).
+    pub fn to_changesets(&self, plain: Complex<editor_model::PlainDoc>) -> EditorResult<Vec<u8>> {
+        let state = state_from_plain(&plain.from_ffi()?)?;
+        let changesets = state.graph().changesets_as_vec();
+        if changesets.is_empty() {
+            return Ok(Vec::new());
+        }
+        editor_codec::encode_changesets(editor_codec::ReencodableChangesets::from_local_ops(
+            changesets,
+        ))
+        .map_err(|e| EditorError::Serialization(e.to_string()))
+    }
+}
+
+fn state_from_stream(stream: &[u8]) -> EditorResult<editor_state::State> {
+    let css = editor_codec::decode_changeset_stream(stream)
+        .map_err(|e| EditorError::Deserialization(e.to_string()))?
+        .into_graph_input();
+    Ok(editor_state::State::from_changesets(css, None)?)
+}
+
+fn state_from_plain(plain: &editor_model::PlainDoc) -> EditorResult<editor_state::State> {
+    editor_state::State::from_plain(plain)
+        .map_err(|e| EditorError::InvalidDocument(format!("{e:?}")))
+}
+
+#[cfg(test)]
+impl Corpus {
+    fn new_test() -> Self {
+        Self {
+            icu: editor_resource::IcuResources::new_test(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use editor_model::{
+        PlainDoc, PlainNode, PlainNodeEntry, PlainParagraphNode, PlainRootNode, PlainTextNode,
+    };
+
+    use super::*;
+
+    fn paragraph(text: &str) -> PlainDoc {
+        fn entry(node: PlainNode, children: Vec<PlainNodeEntry>) -> PlainNodeEntry {
+            PlainNodeEntry {
+                node,
+                modifiers: BTreeMap::new(),
+                carry: Vec::new(),
+                children,
+            }
+        }
+
+        PlainDoc {
+            root: entry(
+                PlainNode::Root(PlainRootNode::default()),
+                vec![entry(
+                    PlainNode::Paragraph(PlainParagraphNode {}),
+                    vec![entry(
+                        PlainNode::Text(PlainTextNode { text: text.into() }),
+                        vec![],
+                    )],
+                )],
+            ),
+        }
+    }
+
+    #[test]
+    fn stream_round_trips_through_plain() {
+        let corpus = Corpus::new_test();
+        let plain = paragraph("hello, world");
+        let stream = corpus.to_changesets(plain.clone()).unwrap();
+        let back = corpus.materialize(stream).unwrap();
+        assert_eq!(corpus.prose_text(back).unwrap(), "hello, world");
+    }
+
+    #[test]
+    fn character_stats_match_editor_counts() {
+        let corpus = Corpus::new_test();
+        let stats = corpus.character_stats(paragraph("a b, c")).unwrap();
+        assert_eq!(
+            stats,
+            CharacterCount {
+                with_whitespace: 6,
+                without_whitespace: 4,
+                without_whitespace_and_punctuation: 3,
+            }
+        );
+    }
+
+    #[test]
+    fn malformed_stream_is_a_deserialization_error() {
+        let corpus = Corpus::new_test();
+        assert!(matches!(
+            corpus.materialize(b"nope".to_vec()),
+            Err(EditorError::Deserialization(_))
+        ));
+    }
+}
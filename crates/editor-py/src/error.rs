@@ -0,0 +1,26 @@
+#[derive(Debug, thiserror::Error)]
+pub enum EditorError {
+    #[error("deserialization failed: {0}")]
+    Deserialization(String),
+
+    #[error("serialization failed: {0}")]
+    Serialization(String),
+
+    #[error("invalid document: {0}")]
+    InvalidDocument(String),
+
+    #[error(transparent)]
+    Resource(#[from] editor_resource::ResourceError),
+
+    #[error(transparent)]
+    State(#[from] editor_state::StateError),
+}
+
+pub type EditorResult<T> = Result<T, EditorError>;
+
+#[cfg(feature = "python")]
+impl From<EditorError> for pyo3::PyErr {
+    fn from(e: EditorError) -> Self {
+        pyo3::exceptions::PyValueError::new_err(e.to_string())
+    }
+}
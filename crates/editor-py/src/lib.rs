@@ -0,0 +1,16 @@
+editor_macros::preamble!();
+
+mod convert;
+mod corpus;
+mod error;
+
+pub use corpus::Corpus;
+pub use editor_resource::CharacterCount;
+pub use error::{EditorError, EditorResult};
+
+#[cfg(feature = "python")]
+#[pyo3::pymodule]
+fn _native(m: &pyo3::Bound<'_, pyo3::types::PyModule>) -> pyo3::PyResult<()> {
+    use pyo3::types::PyModuleMethods;
+    m.add_class::<Corpus>()
+}
@@ -1,4 +1,7 @@
+mod pool;
 pub(crate) mod raster;
 mod sink;
+mod tiles;
 
 pub use sink::{CpuSink, unpremultiply};
+pub use tiles::TiledRasterizer;
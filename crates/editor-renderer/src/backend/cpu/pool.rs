@@ -0,0 +1,161 @@
+//! Long-lived raster threads. Spawning a scoped thread per worker on every
+//! repaint put a thread create/join on each frame's critical path; these
+//! threads start once per process and park on the job queue between frames.
+
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Condvar, Mutex, OnceLock, PoisonError};
+
+type Job = Box<dyn FnOnce() + Send + 'static>;
+
+pub(crate) struct WorkerPool {
+    queue: Sender<Job>,
+}
+
+impl WorkerPool {
+    /// The process-wide pool, one thread per available core, started on
+    /// first use.
+    pub(crate) fn shared() -> &'static WorkerPool {
+        static POOL: OnceLock<WorkerPool> = OnceLock::new();
+        POOL.get_or_init(|| {
+            WorkerPool::new(std::thread::available_parallelism().map_or(1, |n| n.get()))
+        })
+    }
+
+    fn new(threads: usize) -> Self {
+        let (queue, jobs) = mpsc::channel::<Job>();
+        let jobs = Arc::new(Mutex::new(jobs));
+        for i in 0..threads.max(1) {
+            let jobs = Arc::clone(&jobs);
+            std::thread::Builder::new()
+                .name(format!("editor-raster-{i}"))
+                .spawn(move || work(&jobs))
+                .expect("failed to spawn raster worker");
+        }
+        Self { queue }
+    }
+
+    /// Runs every job on the pool and returns once all of them have finished,
+    /// so jobs may borrow from the caller. A panic in any job is re-raised
+    /// here after the others complete; the worker threads survive it.
+    pub(crate) fn run<'a>(&self, jobs: Vec<Box<dyn FnOnce() + Send + 'a>>) {
+        let latch = Arc::new(Latch::new(jobs.len()));
+        // Declared before any job is queued so it also waits when unwinding.
+        let wait = Wait(&latch);
+        for job in jobs {
+            // SAFETY: `wait` blocks until every queued job has run or been
+            // dropped, so nothing the job borrows for `'a` is released first.
+            let job: Job =
+                unsafe { std::mem::transmute::<Box<dyn FnOnce() + Send + 'a>, Job>(job) };
+            let arrival = Arrival(Arc::clone(&latch));
+            let task: Job = Box::new(move || {
+                if let Err(payload) = panic::catch_unwind(AssertUnwindSafe(job)) {
+                    arrival.0.record_panic(payload);
+                }
+                drop(arrival);
+            });
+            // A failed send hands the task back and drops it, which arrives.
+            let _ = self.queue.send(task);
+        }
+        drop(wait);
+        if let Some(payload) = latch.take_panic() {
+            panic::resume_unwind(payload);
+        }
+    }
+}
+
+fn work(jobs: &Mutex<Receiver<Job>>) {
+    loop {
+        let job = jobs.lock().unwrap_or_else(PoisonError::into_inner).recv();
+        match job {
+            Ok(job) => job(),
+            Err(_) => return,
+        }
+    }
+}
+
+struct Latch {
+    state: Mutex<(usize, Option<Box<dyn Any + Send>>)>,
+    done: Condvar,
+}
+
+impl Latch {
+    fn new(count: usize) -> Self {
+        Self {
+            state: Mutex::new((count, None)),
+            done: Condvar::new(),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, (usize, Option<Box<dyn Any + Send>>)> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn record_panic(&self, payload: Box<dyn Any + Send>) {
+        let mut state = self.lock();
+        if state.1.is_none() {
+            state.1 = Some(payload);
+        }
+    }
+
+    fn take_panic(&self) -> Option<Box<dyn Any + Send>> {
+        self.lock().1.take()
+    }
+}
+
+/// Counts one job down when dropped, whether it ran, panicked or was never
+/// scheduled.
+struct Arrival(Arc<Latch>);
+
+impl Drop for Arrival {
+    fn drop(&mut self) {
+        let mut state = self.0.lock();
+        state.0 -= 1;
+        if state.0 == 0 {
+            self.0.done.notify_all();
+        }
+    }
+}
+
+struct Wait<'a>(&'a Latch);
+
+impl Drop for Wait<'_> {
+    fn drop(&mut self) {
+        let mut state = self.0.lock();
+        while state.0 > 0 {
+            state = self
+                .0
+                .done
+                .wait(state)
+                .unwrap_or_else(PoisonError::into_inner);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn jobs_borrow_from_the_caller_and_panics_propagate() {
+        let pool = WorkerPool::new(2);
+        let mut slots = [0u32; 5];
+        let jobs = slots
+            .iter_mut()
+            .enumerate()
+            .map(|(i, slot)| Box::new(move || *slot = i as u32 + 1) as Box<dyn FnOnce() + Send>)
+            .collect();
+        pool.run(jobs);
+        assert_eq!(slots, [1, 2, 3, 4, 5]);
+
+        let panicked = panic::catch_unwind(AssertUnwindSafe(|| {
+            pool.run(vec![Box::new(|| panic!("raster job"))]);
+        }));
+        assert!(panicked.is_err());
+
+        let mut after = 0;
+        pool.run(vec![Box::new(|| after = 7)]);
+        assert_eq!(after, 7, "workers survive a panicking job");
+    }
+}
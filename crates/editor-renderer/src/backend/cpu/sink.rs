@@ -81,6 +81,11 @@ impl CpuSink {
         }
     }
 
+    /// The pixel buffer in chunks of `rows` whole rows, top to bottom.
+    pub(crate) fn rows_mut(&mut self, rows: usize) -> std::slice::ChunksMut<'_, u8> {
+        self.buf.chunks_mut(rows * self.width as usize * 4)
+    }
+
     pub fn set_clip(&mut self, clip: Option<IRect>) {
         self.clip = clip;
     }
@@ -200,6 +205,93 @@ impl CpuSink {
         }
     }
 
+    /// Draws `image` stretched over `rect` under `transform`, sampling the
+    /// premultiplied source bilinearly at each covered pixel center.
+    fn blit_image(&mut self, image: &Image, rect: Rect, transform: Transform) {
+        let iw = image.width as usize;
+        let ih = image.height as usize;
+        if iw == 0 || ih == 0 || rect.width <= 0.0 || rect.height <= 0.0 {
+            return;
+        }
+        if image.data.len() < iw * ih * 4 {
+            return;
+        }
+        let affine = kurbo::Affine::from(transform);
+        if affine.determinant().abs() < f64::EPSILON {
+            return;
+        }
+        let inverse = affine.inverse();
+        let bounds = affine.transform_rect_bbox(kurbo::Rect::new(
+            rect.x as f64,
+            rect.y as f64,
+            rect.right() as f64,
+            rect.bottom() as f64,
+        ));
+        let mut r = IRect {
+            x0: bounds.x0.floor() as i32,
+            y0: bounds.y0.floor() as i32,
+            x1: bounds.x1.ceil() as i32,
+            y1: bounds.y1.ceil() as i32,
+        };
+        if let Some(c) = self.clip {
+            r = match r.intersect(c) {
+                Some(r) => r,
+                None => return,
+            };
+        }
+        let Some(r) = r.intersect(self.sink_bounds()) else {
+            return;
+        };
+
+        let pitch = self.width as usize * 4;
+        let src = &image.data;
+        let fetch = |x: usize, y: usize| -> [f32; 4] {
+            let i = (y * iw + x) * 4;
+            [
+                src[i] as f32,
+                src[i + 1] as f32,
+                src[i + 2] as f32,
+                src[i + 3] as f32,
+            ]
+        };
+        for y in r.y0..r.y1 {
+            for x in r.x0..r.x1 {
+                let local = inverse * kurbo::Point::new(x as f64 + 0.5, y as f64 + 0.5);
+                let u = (local.x as f32 - rect.x) / rect.width;
+                let v = (local.y as f32 - rect.y) / rect.height;
+                if !(0.0..1.0).contains(&u) || !(0.0..1.0).contains(&v) {
+                    continue;
+                }
+                let sx = (u * iw as f32 - 0.5).clamp(0.0, (iw - 1) as f32);
+                let sy = (v * ih as f32 - 0.5).clamp(0.0, (ih - 1) as f32);
+                let (x0, y0) = (sx as usize, sy as usize);
+                let (x1, y1) = ((x0 + 1).min(iw - 1), (y0 + 1).min(ih - 1));
+                let (fx, fy) = (sx - x0 as f32, sy - y0 as f32);
+                let (p00, p10, p01, p11) =
+                    (fetch(x0, y0), fetch(x1, y0), fetch(x0, y1), fetch(x1, y1));
+                let px: [u8; 4] = std::array::from_fn(|c| {
+                    let top = p00[c] + (p10[c] - p00[c]) * fx;
+                    let bottom = p01[c] + (p11[c] - p01[c]) * fx;
+                    (top + (bottom - top) * fy).round() as u8
+                });
+                let sa = px[3] as u32;
+                if sa == 0 {
+                    continue;
+                }
+                let di = y as usize * pitch + x as usize * 4;
+                let dst = &mut self.buf[di..di + 4];
+                if sa == 255 {
+                    dst.copy_from_slice(&px);
+                } else {
+                    let inv = 255 - sa;
+                    for (d, s) in dst.iter_mut().zip(px) {
+                        *d = (s as u32 + ((*d as u32 * inv) >> 8)).min(255) as u8;
+                    }
+                }
+            }
+        }
+    }
+
     fn blit_mask_at(&mut self, placement: zeno::Placement, color: Color) {
         let iw = placement.width as i32;
         let ih = placement.height as i32;
@@ -280,7 +372,9 @@ impl RenderSink for CpuSink {
         self.blit_mask_at(placement, color);
     }
 
-    fn draw_image(&mut self, _image: &Image, _rect: Rect, _transform: Transform) {}
+    fn draw_image(&mut self, image: &Image, rect: Rect, transform: Transform) {
+        self.blit_image(image, rect, transform);
+    }
 
     fn draw_glyph(&mut self, image: &Image, dst_x: i32, dst_y: i32) {
         self.blit_premul_at(
@@ -686,6 +780,28 @@ mod tests {
         }
     }
 
+    #[test]
+    fn draw_image_scales_into_the_transformed_rect() {
+        let red = [255u8, 0, 0, 255];
+        let image = Image {
+            data: red.repeat(4).into(),
+            width: 2,
+            height: 2,
+            glyph: None,
+        };
+        let mut sink = CpuSink::new(8, 8);
+        sink.draw_image(
+            &image,
+            Rect::from_xywh(1.0, 1.0, 2.0, 2.0),
+            Transform::scale(2.0),
+        );
+        let px = |x: usize, y: usize| &sink.pixels()[(y * 8 + x) * 4..][..4];
+        assert_eq!(px(2, 2), red);
+        assert_eq!(px(5, 5), red);
+        assert_eq!(px(1, 1), [0, 0, 0, 0]);
+        assert_eq!(px(6, 6), [0, 0, 0, 0]);
+    }
+
     #[test]
     fn unpremultiply_converts_translucent_and_keeps_edges() {
         let mut px = [49u8, 74, 99, 127, 0, 0, 0, 0, 10, 20, 30, 255, 1, 1, 1, 2];
@@ -0,0 +1,140 @@
+//! Display-list replay split across the shared raster pool. The sink is cut
+//! into horizontal bands of whole rows, so every worker owns a disjoint slice
+//! of the pixel buffer. Each worker rasters the damage inside its bands through
+//! its own scratch `CpuSink` (and with it its own `RasterScratch`), then
+//! copies the result into its slice. Glyph bitmaps are already baked into the
+//! display list as shared `Arc` buffers, so workers read them without a lock.
+
+use crate::backend::cpu::CpuSink;
+use crate::backend::cpu::pool::WorkerPool;
+use crate::damage::IRect;
+use crate::diff::raster_rect;
+use crate::display_list::DisplayList;
+use crate::sink::RenderSink;
+
+/// Rows per band. Small enough to spread a single damage rect over every
+/// worker, large enough that per-band replay overhead stays negligible.
+const BAND_ROWS: u16 = 64;
+
+/// Band work below this many pixels runs on the calling thread; handing it to
+/// the pool costs more than it saves for caret-sized damage.
+const PARALLEL_MIN_PIXELS: i64 = 256 * 256;
+
+pub struct TiledRasterizer {
+    scratches: Vec<CpuSink>,
+}
+
+impl TiledRasterizer {
+    /// A rasterizer splitting damage into `workers` jobs, at least one. The
+    /// jobs run on the process-wide pool, so rasterizers are cheap to keep
+    /// per surface.
+    pub fn new(workers: usize) -> Self {
+        Self {
+            scratches: (0..workers.max(1)).map(|_| CpuSink::new(1, 1)).collect(),
+        }
+    }
+
+    /// One worker per available core. Targets without threads get a single
+    /// worker and replay serially.
+    pub fn with_available_parallelism() -> Self {
+        #[cfg(target_arch = "wasm32")]
+        let workers = 1;
+        #[cfg(not(target_arch = "wasm32"))]
+        let workers = std::thread::available_parallelism().map_or(1, |n| n.get());
+        Self::new(workers)
+    }
+
+    pub fn workers(&self) -> usize {
+        self.scratches.len()
+    }
+
+    /// Clears and re-rasters every `damage` rect of `sink` from `dl`. The
+    /// pixels are identical to clearing and replaying each rect serially.
+    pub fn render_damage(&mut self, dl: &DisplayList, damage: &[IRect], sink: &mut CpuSink) {
+        let (width, height) = sink.pixel_size();
+        let bounds = IRect {
+            x0: 0,
+            y0: 0,
+            x1: width as i32,
+            y1: height as i32,
+        };
+        let damage: Vec<IRect> = damage.iter().filter_map(|r| r.intersect(bounds)).collect();
+        if damage.is_empty() {
+            return;
+        }
+
+        let pitch = width as usize * 4;
+        let mut bands: Vec<Band<'_>> = sink
+            .rows_mut(BAND_ROWS as usize)
+            .enumerate()
+            .filter_map(|(i, rows)| {
+                let y0 = i as i32 * BAND_ROWS as i32;
+                let band = IRect {
+                    x0: 0,
+                    y0,
+                    x1: width as i32,
+                    y1: y0 + (rows.len() / pitch) as i32,
+                };
+                let rects: Vec<IRect> = damage.iter().filter_map(|r| r.intersect(band)).collect();
+                (!rects.is_empty()).then_some(Band { y0, rows, rects })
+            })
+            .collect();
+
+        let area: i64 = bands.iter().flat_map(|b| &b.rects).map(|r| r.area()).sum();
+        let mut workers = self.scratches.len().min(bands.len());
+        if area < PARALLEL_MIN_PIXELS {
+            workers = 1;
+        }
+        for scratch in &mut self.scratches[..workers] {
+            scratch.resize(width as u16, BAND_ROWS.min(height as u16));
+        }
+        if workers == 1 {
+            let scratch = &mut self.scratches[0];
+            for band in &mut bands {
+                band.raster(dl, scratch, pitch);
+            }
+            return;
+        }
+
+        // Deal bands round-robin so damage concentrated in one region still
+        // spreads across workers.
+        let mut groups: Vec<Vec<Band<'_>>> = (0..workers).map(|_| Vec::new()).collect();
+        for (i, band) in bands.into_iter().enumerate() {
+            groups[i % workers].push(band);
+        }
+        let jobs = self
+            .scratches
+            .iter_mut()
+            .zip(groups)
+            .map(|(scratch, group)| {
+                Box::new(move || {
+                    for mut band in group {
+                        band.raster(dl, scratch, pitch);
+                    }
+                }) as Box<dyn FnOnce() + Send + '_>
+            })
+            .collect();
+        WorkerPool::shared().run(jobs);
+    }
+}
+
+struct Band<'a> {
+    y0: i32,
+    rows: &'a mut [u8],
+    rects: Vec<IRect>,
+}
+
+impl Band<'_> {
+    fn raster(&mut self, dl: &DisplayList, scratch: &mut CpuSink, pitch: usize) {
+        let scratch_pitch = scratch.pixel_size().0 as usize * 4;
+        for &r in &self.rects {
+            raster_rect(dl, r, scratch);
+            let len = r.width() as usize * 4;
+            for y in 0..r.height() as usize {
+                let src = &scratch.pixels()[y * scratch_pitch..][..len];
+                let dst = (r.y0 - self.y0) as usize + y;
+                self.rows[dst * pitch + r.x0 as usize * 4..][..len].copy_from_slice(src);
+            }
+        }
+    }
+}
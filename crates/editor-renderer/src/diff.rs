@@ -1,4 +1,4 @@
-use crate::backend::cpu::CpuSink;
+use crate::backend::cpu::{CpuSink, TiledRasterizer};
 use crate::damage::{IRect, merge_damage};
 use crate::display_list::DisplayList;
 use crate::sink::RenderSink;
@@ -110,6 +110,23 @@ pub fn render_incremental(
     damage
 }
 
+/// `render_incremental` with the damage replayed on `tiles`' worker pool.
+/// The pixels are identical to the serial path.
+pub fn render_incremental_tiled(
+    prev: Option<&DisplayList>,
+    new: &DisplayList,
+    sink: &mut CpuSink,
+    full: IRect,
+    tiles: &mut TiledRasterizer,
+) -> Vec<IRect> {
+    let damage = match prev {
+        None => vec![full],
+        Some(prev) => diff(prev, new, full),
+    };
+    tiles.render_damage(new, &damage, sink);
+    damage
+}
+
 /// Rasters the display-list content of device rect `r` into `scratch` at origin.
 /// `scratch` must be at least `r.width() x r.height()`; offsets are integral so
 /// the result is byte-identical to the same subregion of a full-page raster.
@@ -1,5 +1,8 @@
+use editor_common::{Color, Rect};
+
 use crate::icon_data;
-use crate::types::IconData;
+use crate::sink::RenderSink;
+use crate::types::{IconData, IconElement, Path, Stroke, Transform};
 
 pub struct IconRegistry;
 
@@ -10,3 +13,46 @@ impl IconRegistry {
         icon_data::ICONS.get(name)
     }
 }
+
+/// Draws `icon` scaled to fit and centered in a box of `rect`'s size at the
+/// transform's origin. `stroke_width` is in the transform's units,
+/// independent of the icon's viewport.
+pub fn draw_icon(
+    sink: &mut dyn RenderSink,
+    icon: &'static IconData,
+    color: Color,
+    rect: Rect,
+    base_transform: Transform,
+    stroke_width: f32,
+) {
+    let s = (rect.width / icon.viewport.0).min(rect.height / icon.viewport.1);
+    let dx = (rect.width - icon.viewport.0 * s) / 2.0;
+    let dy = (rect.height - icon.viewport.1 * s) / 2.0;
+    let icon_t = base_transform.translate(dx, dy).post_scale(s);
+
+    for elem in icon.elements {
+        match *elem {
+            IconElement::Fill { path, .. } => {
+                let p = Path {
+                    elements: path.to_vec(),
+                };
+                sink.fill_path(&p, color, icon_t);
+            }
+            IconElement::Stroke {
+                path,
+                stroke_cap,
+                stroke_join,
+            } => {
+                let p = Path {
+                    elements: path.to_vec(),
+                };
+                let stroke = Stroke {
+                    width: stroke_width / s,
+                    cap: stroke_cap,
+                    join: stroke_join,
+                };
+                sink.stroke_path(&p, color, &stroke, icon_t);
+            }
+        }
+    }
+}
@@ -3,8 +3,8 @@ use editor_model::{DocView, Modifier, ModifierType, Node, TableBorderStyle};
 use editor_resource::{Resource, Theme};
 use editor_view::style::DecorationData;
 use editor_view::{
-    Edges, LineMetrics, PageFragmentAtom, PageFragmentBox, PageFragmentDecoration,
-    PageFragmentLine, PageFragmentNode, PageRect, PageVisitor,
+    Edges, LineMetrics, ManuscriptPageGrid, PageFragmentAtom, PageFragmentBox,
+    PageFragmentDecoration, PageFragmentLine, PageFragmentNode, PageRect, PageVisitor,
 };
 use std::sync::{Arc, Mutex};
 
@@ -12,14 +12,15 @@ use crate::glyph::{
     BakedGlyphCache, Content, GlyphCache, GlyphKey, PositionedGlyph, PositionedSvgPathGlyph,
     ScaleContext, SvgPathGlyphCache,
 };
-use crate::icons::ICONS;
+use crate::icons::{ICONS, draw_icon};
 use crate::sink::RenderSink;
 use crate::types::{
-    Color, CornerRadii, IconData, IconElement, Image, Path, PathElement, Stroke, StrokeCap,
-    StrokeJoin, Transform,
+    Color, CornerRadii, IconData, Image, Path, PathElement, Stroke, StrokeCap, StrokeJoin,
+    Transform,
 };
 use crate::vector::codec::encode_vector_page;
 use crate::vector::export::VectorSink;
+use crate::vector::types::VectorPage;
 
 #[cfg(test)]
 thread_local! {
@@ -192,6 +193,11 @@ const MESSAGE_BORDER_RADIUS: f32 = 18.0;
 const MESSAGE_TAIL_SIZE: f32 = 10.0;
 const BULLET_RADIUS_RATIO: f32 = 0.125;
 const TEXT_DECORATION_THICKNESS: f32 = 1.0;
+const PLACEHOLDER_ICON_SIZE: f32 = 32.0;
+const CARD_BORDER_RADIUS: f32 = 8.0;
+const CARD_BORDER_WIDTH: f32 = 1.0;
+const CARD_ICON_SIZE: f32 = 20.0;
+const CARD_PADDING: f32 = 16.0;
 
 fn build_partial_border(r: Rect, radii: CornerRadii, edges: &Edges<bool>) -> Path {
     let CornerRadii {
@@ -527,6 +533,7 @@ pub struct Renderer {
     pub(crate) glyph_cache: GlyphCache,
     pub(crate) svg_path_glyph_cache: SvgPathGlyphCache,
     pub(crate) baked_glyph_cache: BakedGlyphCache,
+    pub(crate) draw_external_elements: bool,
 }
 
 impl Renderer {
@@ -537,9 +544,16 @@ impl Renderer {
             glyph_cache: GlyphCache::new(),
             svg_path_glyph_cache: SvgPathGlyphCache::new(),
             baked_glyph_cache: BakedGlyphCache::new(),
+            draw_external_elements: false,
         }
     }
 
+    /// Draws images, files and embeds in `render_page` instead of leaving
+    /// them to the host's overlays. Vector exports always draw them.
+    pub fn set_draw_external_elements(&mut self, draw: bool) {
+        self.draw_external_elements = draw;
+    }
+
     pub fn reset_font_data_caches(&mut self) {
         self.scale_ctx = ScaleContext::new();
         self.glyph_cache = GlyphCache::new();
@@ -628,7 +642,7 @@ impl Renderer {
         scale_factor: f32,
         marks: &[Mark],
     ) {
-        let theme = *self.resource.lock().unwrap().theme();
+        let theme = self.resource.lock().unwrap().theme().clone();
 
         view.visit_page(
             page_idx,
@@ -639,6 +653,14 @@ impl Renderer {
                 LayerSet::of(&[RenderLayer::Background]),
             ),
         );
+        if let Some(grid) = view.manuscript_grid() {
+            draw_manuscript_grid(
+                sink,
+                grid,
+                theme.color("ui.border.default"),
+                Transform::scale(scale_factor),
+            );
+        }
 
         self.draw_marks(
             sink,
@@ -676,13 +698,48 @@ impl Renderer {
         page_idx: usize,
         scale_factor: f32,
     ) -> Vec<u8> {
+        let page = self.export_page_outline_vector(doc, view, page_idx, scale_factor);
+        encode_vector_page(&page)
+    }
+
+    /// The page's vector display list with text drawn as outline paths, for
+    /// exporters whose output cannot carry fonts.
+    pub fn export_page_outline_vector(
+        &mut self,
+        doc: &DocView,
+        view: &editor_view::View,
+        page_idx: usize,
+        scale_factor: f32,
+    ) -> VectorPage {
+        self.vector_page(VectorSink::new(), doc, view, page_idx, scale_factor)
+    }
+
+    /// The page's vector display list with text left as glyph runs, for
+    /// exporters that embed the fonts instead of drawing outlines.
+    pub fn export_page_glyph_vector(
+        &mut self,
+        doc: &DocView,
+        view: &editor_view::View,
+        page_idx: usize,
+        scale_factor: f32,
+    ) -> VectorPage {
+        self.vector_page(VectorSink::with_glyphs(), doc, view, page_idx, scale_factor)
+    }
+
+    fn vector_page(
+        &mut self,
+        mut sink: VectorSink,
+        doc: &DocView,
+        view: &editor_view::View,
+        page_idx: usize,
+        scale_factor: f32,
+    ) -> VectorPage {
         let (width, height) = view
             .pages()
             .get(page_idx)
             .map(|p| (p.size.width, p.size.height))
             .unwrap_or((0.0, 0.0));
 
-        let mut sink = VectorSink::new();
         view.visit_page(
             page_idx,
             &mut self.vector_page_visitor(
@@ -692,6 +749,15 @@ impl Renderer {
                 LayerSet::of(&[RenderLayer::Background]),
             ),
         );
+        if let Some(grid) = view.manuscript_grid() {
+            let color = self
+                .resource
+                .lock()
+                .unwrap()
+                .theme()
+                .color("ui.border.default");
+            draw_manuscript_grid(&mut sink, grid, color, Transform::scale(scale_factor));
+        }
         view.visit_page(
             page_idx,
             &mut self.vector_page_visitor(
@@ -702,8 +768,7 @@ impl Renderer {
             ),
         );
 
-        let page = sink.into_page(width, height);
-        encode_vector_page(&page)
+        sink.into_page(width, height)
     }
 
     fn page_visitor<'a>(
@@ -740,7 +805,7 @@ impl Renderer {
         active: LayerSet,
         text_mode: TextRenderMode,
     ) -> RenderVisitor<'a> {
-        let theme = *self.resource.lock().unwrap().theme();
+        let theme = self.resource.lock().unwrap().theme().clone();
         RenderVisitor {
             renderer: self,
             sink,
@@ -778,6 +843,10 @@ impl RenderVisitor<'_> {
     fn on(&self, layer: RenderLayer) -> bool {
         self.active.contains(layer)
     }
+
+    fn draws_external_elements(&self) -> bool {
+        self.text_mode == TextRenderMode::VectorExport || self.renderer.draw_external_elements
+    }
 }
 
 impl<'a> RenderVisitor<'a> {
@@ -789,35 +858,93 @@ impl<'a> RenderVisitor<'a> {
         base_transform: Transform,
         stroke_width: f32,
     ) {
-        let s = (rect.width / icon.viewport.0).min(rect.height / icon.viewport.1);
-        let dx = (rect.width - icon.viewport.0 * s) / 2.0;
-        let dy = (rect.height - icon.viewport.1 * s) / 2.0;
-        let icon_t = base_transform.translate(dx, dy).post_scale(s);
-
-        for elem in icon.elements {
-            match *elem {
-                IconElement::Fill { path, .. } => {
-                    let p = Path {
-                        elements: path.to_vec(),
-                    };
-                    self.sink.fill_path(&p, color, icon_t);
-                }
-                IconElement::Stroke {
-                    path,
-                    stroke_cap,
-                    stroke_join,
-                } => {
-                    let p = Path {
-                        elements: path.to_vec(),
-                    };
-                    let stroke = Stroke {
-                        width: stroke_width / s,
-                        cap: stroke_cap,
-                        join: stroke_join,
-                    };
-                    self.sink.stroke_path(&p, color, &stroke, icon_t);
-                }
-            }
+        draw_icon(self.sink, icon, color, rect, base_transform, stroke_width);
+    }
+
+    /// Draws the image's pixels at `proportion` percent of the atom's width,
+    /// centered, or the placeholder glyph while the host has not supplied
+    /// them.
+    fn render_image(&mut self, id: Option<&str>, proportion: u32, rect: Rect, t: Transform) {
+        let width = rect.width * (proportion as f32 / 100.0);
+        let rect = Rect::from_xywh(
+            rect.x + (rect.width - width) / 2.0,
+            rect.y,
+            width,
+            rect.height,
+        );
+        let pixels = id.and_then(|id| {
+            let resource = self.renderer.resource.lock().unwrap();
+            resource.image(id).cloned()
+        });
+        if let Some(pixels) = pixels {
+            let image = Image {
+                data: Arc::clone(&pixels.data),
+                width: pixels.width,
+                height: pixels.height,
+                glyph: None,
+            };
+            self.sink.draw_image(&image, rect, t);
+            return;
+        }
+
+        self.sink
+            .fill_rect(rect, self.theme.color("ui.surface.muted"), t);
+        if let Some(icon) = ICONS.resolve("lucide/images") {
+            let size = PLACEHOLDER_ICON_SIZE.min(rect.width).min(rect.height);
+            let icon_t = t.translate(
+                rect.x + (rect.width - size) / 2.0,
+                rect.y + (rect.height - size) / 2.0,
+            );
+            let color = self.theme.color("ui.text.faint");
+            self.render_icon(
+                icon,
+                color,
+                Rect::from_xywh(0.0, 0.0, size, size),
+                icon_t,
+                ICON_STROKE_WIDTH,
+            );
+        }
+    }
+
+    /// A bordered card with the attachment's icon, standing in for the
+    /// host's file and embed widgets.
+    fn render_card(&mut self, icon_name: &str, rect: Rect, t: Transform) {
+        let radii = CornerRadii {
+            top_left: CARD_BORDER_RADIUS,
+            top_right: CARD_BORDER_RADIUS,
+            bottom_left: CARD_BORDER_RADIUS,
+            bottom_right: CARD_BORDER_RADIUS,
+        };
+        let half = CARD_BORDER_WIDTH / 2.0;
+        let inset = Rect::from_xywh(
+            rect.x + half,
+            rect.y + half,
+            (rect.width - CARD_BORDER_WIDTH).max(0.0),
+            (rect.height - CARD_BORDER_WIDTH).max(0.0),
+        );
+        let path = Path::rrect(inset, radii);
+        self.sink
+            .fill_path(&path, self.theme.color("ui.surface.muted"), t);
+        self.sink.stroke_path(
+            &path,
+            self.theme.color("ui.border.default"),
+            &Stroke::new(CARD_BORDER_WIDTH),
+            t,
+        );
+        if let Some(icon) = ICONS.resolve(icon_name) {
+            let size = CARD_ICON_SIZE.min(rect.height);
+            let icon_t = t.translate(
+                rect.x + CARD_PADDING.min(rect.width / 2.0),
+                rect.y + (rect.height - size) / 2.0,
+            );
+            let color = self.theme.color("ui.text.muted");
+            self.render_icon(
+                icon,
+                color,
+                Rect::from_xywh(0.0, 0.0, size, size),
+                icon_t,
+                ICON_STROKE_WIDTH,
+            );
         }
     }
 
@@ -891,6 +1018,7 @@ impl<'a> RenderVisitor<'a> {
                     graphemes: Vec::<GraphemeSpan>::new(),
                     cursor_ascent: 0.0,
                     cursor_descent: 0.0,
+                    rtl: false,
                 };
 
                 if self.text_mode == TextRenderMode::VectorExport {
@@ -933,6 +1061,34 @@ impl<'a> RenderVisitor<'a> {
 }
 
 const TABLE_BORDER_WIDTH: f32 = 1.0;
+const MANUSCRIPT_LINE_WIDTH: f32 = 1.0;
+
+/// Outlines each row of 원고지 cells. Rows are drawn separately since the
+/// band between them is blank.
+fn draw_manuscript_grid(
+    sink: &mut dyn RenderSink,
+    grid: ManuscriptPageGrid,
+    color: Color,
+    t: Transform,
+) {
+    let lw = MANUSCRIPT_LINE_WIDTH;
+    let cell = grid.cell_size;
+    let row_width = grid.columns as f32 * cell;
+    for row in 0..grid.rows {
+        let top = grid.y + row as f32 * (cell + grid.row_gap) + grid.row_gap;
+        for y in [top, top + cell - lw] {
+            sink.fill_path(
+                &Path::rect(Rect::from_xywh(grid.x, y, row_width, lw)),
+                color,
+                t,
+            );
+        }
+        for column in 0..=grid.columns {
+            let x = (grid.x + column as f32 * cell).min(grid.x + row_width - lw);
+            sink.fill_path(&Path::rect(Rect::from_xywh(x, top, lw, cell)), color, t);
+        }
+    }
+}
 
 fn draw_table_grid(
     sink: &mut dyn RenderSink,
@@ -1482,6 +1638,16 @@ impl<'a> PageVisitor for RenderVisitor<'a> {
                     }
                 }
             }
+            Some(Node::Image(image)) if self.draws_external_elements() => {
+                let proportion = *image.proportion.get();
+                self.render_image(image.id.get().as_deref(), proportion, inner_rect, t);
+            }
+            Some(Node::File(_)) if self.draws_external_elements() => {
+                self.render_card("lucide/paperclip", inner_rect, t);
+            }
+            Some(Node::Embed(_)) if self.draws_external_elements() => {
+                self.render_card("lucide/globe", inner_rect, t);
+            }
             Some(Node::Image(_) | Node::File(_) | Node::Embed(_) | Node::Archived(_)) => {}
             _ => {}
         }
@@ -2239,6 +2405,7 @@ mod tests {
             graphemes: vec![],
             cursor_ascent: 0.0,
             cursor_descent: 0.0,
+            rtl: false,
         };
 
         let line_node = fragment_line(
@@ -2262,7 +2429,11 @@ mod tests {
     }
 
     fn export_page_to_vector_page(state: &State) -> VectorPage {
-        let resource = Arc::new(Mutex::new(Resource::new_test()));
+        export_page_to_vector_page_with(state, Resource::new_test())
+    }
+
+    fn export_page_to_vector_page_with(state: &State, resource: Resource) -> VectorPage {
+        let resource = Arc::new(Mutex::new(resource));
         let mut view = editor_view::View::new_test();
         view.layout(state);
         let doc = state.view();
@@ -2329,6 +2500,51 @@ mod tests {
         );
     }
 
+    #[test]
+    fn stored_images_export_as_image_ops_and_attachments_as_cards() {
+        use editor_macros::state;
+
+        let (state,) = state! {
+            doc {
+                root {
+                    image(id: Some("stored".to_string()), proportion: 50)
+                    image(id: Some("missing".to_string()))
+                    file
+                    embed
+                }
+            }
+            selection: none
+        };
+        let mut source = ResourceSource::new_test();
+        let pixels = editor_resource::prepare_image(1, 1, vec![0, 0, 255, 255]).unwrap();
+        source.insert_image("stored", pixels);
+
+        let page =
+            export_page_to_vector_page_with(&state, Resource::from_snapshot(source.snapshot()));
+
+        let images: Vec<_> = page
+            .ops
+            .iter()
+            .filter(|op| matches!(op, VectorOp::Image { .. }))
+            .collect();
+        assert_eq!(images.len(), 1, "only the stored image has pixels");
+        let strokes = page
+            .ops
+            .iter()
+            .filter(|op| matches!(op, VectorOp::StrokePath { .. }))
+            .count();
+        assert!(strokes >= 2, "file and embed cards draw bordered outlines");
+
+        let empty = export_page_to_vector_page(&state);
+        assert!(
+            !empty
+                .ops
+                .iter()
+                .any(|op| matches!(op, VectorOp::Image { .. })),
+            "images missing from the store draw as placeholders"
+        );
+    }
+
     #[test]
     fn horizontal_rule_pattern_page_is_vectorized() {
         // horizontal rule 패턴이 페이지 export 결과에서 벡터 path op로 나타나는지 확인한다.
@@ -2523,6 +2739,7 @@ mod tests {
                 graphemes: vec![],
                 cursor_ascent: 0.0,
                 cursor_descent: 0.0,
+                rtl: false,
             };
             fragment_line(
                 Rect::from_xywh(0.0, 0.0, 100.0, 20.0),
@@ -25,9 +25,16 @@ pub fn encode_vector_page(page: &VectorPage) -> Vec<u8> {
     write_u32(&mut out, MAGIC);
     write_f32(&mut out, page.width);
     write_f32(&mut out, page.height);
-    write_u32(&mut out, page.ops.len() as u32);
+    // Glyph ops reference fonts by registry id, which the TVE1 format has no
+    // place for; only glyph-mode sinks record them, so they are dropped here.
+    let ops: Vec<&VectorOp> = page
+        .ops
+        .iter()
+        .filter(|op| !matches!(op, VectorOp::Glyphs { .. }))
+        .collect();
+    write_u32(&mut out, ops.len() as u32);
 
-    for op in &page.ops {
+    for op in ops {
         match op {
             VectorOp::FillPath {
                 path,
@@ -94,6 +101,7 @@ pub fn encode_vector_page(page: &VectorPage) -> Vec<u8> {
                 write_u32(&mut out, data.len() as u32);
                 out.extend_from_slice(data);
             }
+            VectorOp::Glyphs { .. } => {}
         }
     }
 
@@ -168,7 +176,7 @@ fn write_path_commands(out: &mut Vec<u8>, path: &[VectorPathCommand]) {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::vector::types::{TextOp, VectorPage};
+    use crate::vector::types::{TextOp, VectorGlyph, VectorPage};
 
     #[test]
     fn encode_starts_with_magic() {
@@ -290,4 +298,28 @@ mod tests {
         assert!((size - 3.0).abs() < 0.001);
         assert_eq!(text, "Hi");
     }
+
+    #[test]
+    fn glyph_ops_are_left_out_of_the_op_count() {
+        let page = VectorPage {
+            width: 10.0,
+            height: 20.0,
+            ops: vec![VectorOp::Glyphs {
+                family_id: 0,
+                weight: 400,
+                size: 16.0,
+                color: [0, 0, 0, 255],
+                glyphs: vec![VectorGlyph {
+                    id: 3,
+                    x: 0.0,
+                    y: 0.0,
+                }],
+                text: "A".to_string(),
+            }],
+            text_ops: vec![],
+        };
+        let bytes = encode_vector_page(&page);
+        assert_eq!(u32::from_le_bytes(bytes[12..16].try_into().unwrap()), 0);
+        assert_eq!(bytes.len(), 16);
+    }
 }
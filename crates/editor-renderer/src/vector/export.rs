@@ -6,12 +6,14 @@ use skrifa::{FontRef, GlyphId, MetadataProvider};
 use crate::sink::RenderSink;
 use crate::types::{Color, Image, Path, PathElement, Stroke, StrokeCap, StrokeJoin, Transform};
 use crate::vector::types::{
-    TextOp, VectorFillRule, VectorLineCap, VectorLineJoin, VectorOp, VectorPage, VectorPathCommand,
+    TextOp, VectorFillRule, VectorGlyph, VectorLineCap, VectorLineJoin, VectorOp, VectorPage,
+    VectorPathCommand,
 };
 
 pub struct VectorSink {
     ops: Vec<VectorOp>,
     text_ops: Vec<TextOp>,
+    glyphs: bool,
 }
 
 impl Default for VectorSink {
@@ -25,6 +27,16 @@ impl VectorSink {
         Self {
             ops: Vec::new(),
             text_ops: Vec::new(),
+            glyphs: false,
+        }
+    }
+
+    /// A sink that records glyph runs as [`VectorOp::Glyphs`] instead of
+    /// outlines, for exporters that embed the fonts themselves.
+    pub fn with_glyphs() -> Self {
+        Self {
+            glyphs: true,
+            ..Self::new()
         }
     }
 
@@ -36,6 +48,75 @@ impl VectorSink {
             text_ops: self.text_ops,
         }
     }
+
+    fn push_glyphs(
+        &mut self,
+        run: &editor_view::glyph_run::GlyphRun,
+        rgba: [u8; 4],
+        base_transform: Transform,
+    ) {
+        let glyphs: Vec<VectorGlyph> = run
+            .glyphs
+            .iter()
+            .filter(|g| g.id != 0)
+            .map(|g| {
+                let (x, y) = map_point(base_transform, g.x, g.y);
+                VectorGlyph { id: g.id, x, y }
+            })
+            .collect();
+        if glyphs.is_empty() {
+            return;
+        }
+        self.ops.push(VectorOp::Glyphs {
+            family_id: run.family_id,
+            weight: run.weight,
+            size: run.font_size * base_transform.m[0],
+            color: rgba,
+            glyphs,
+            text: run.text.clone(),
+        });
+    }
+
+    fn push_glyph_outlines(
+        &mut self,
+        run: &editor_view::glyph_run::GlyphRun,
+        rgba: [u8; 4],
+        base_transform: Transform,
+        fonts: &editor_resource::FontRegistry,
+    ) {
+        let Some(font_data) = fonts.font_data(run.family_id, run.weight) else {
+            return;
+        };
+        let Ok(font) = FontRef::from_index(font_data, 0) else {
+            return;
+        };
+        let outlines = font.outline_glyphs();
+        let coords: &[NormalizedCoord] = &[];
+        let size = Size::new(run.font_size);
+
+        for g in &run.glyphs {
+            if g.id == 0 {
+                continue;
+            }
+            let Some(og) = outlines.get(GlyphId::new(g.id)) else {
+                continue;
+            };
+            let glyph_t = base_transform.translate(g.x, g.y);
+            let mut writer = GlyphOutlineWriter {
+                cmds: Vec::new(),
+                transform: glyph_t,
+            };
+            let settings = DrawSettings::unhinted(size, LocationRef::new(coords));
+            if og.draw(settings, &mut writer).is_err() || writer.cmds.is_empty() {
+                continue;
+            }
+            self.ops.push(VectorOp::FillPath {
+                path: writer.cmds,
+                color: rgba,
+                fill_rule: VectorFillRule::Winding,
+            });
+        }
+    }
 }
 
 impl RenderSink for VectorSink {
@@ -101,38 +182,11 @@ impl RenderSink for VectorSink {
         base_transform: Transform,
         fonts: &editor_resource::FontRegistry,
     ) {
-        let Some(font_data) = fonts.font_data(run.family_id, run.weight) else {
-            return;
-        };
-        let Ok(font) = FontRef::from_index(font_data, 0) else {
-            return;
-        };
-        let outlines = font.outline_glyphs();
-        let coords: &[NormalizedCoord] = &[];
-        let size = Size::new(run.font_size);
         let rgba = color_to_rgba(color);
-
-        for g in &run.glyphs {
-            if g.id == 0 {
-                continue;
-            }
-            let Some(og) = outlines.get(GlyphId::new(g.id)) else {
-                continue;
-            };
-            let glyph_t = base_transform.translate(g.x, g.y);
-            let mut writer = GlyphOutlineWriter {
-                cmds: Vec::new(),
-                transform: glyph_t,
-            };
-            let settings = DrawSettings::unhinted(size, LocationRef::new(coords));
-            if og.draw(settings, &mut writer).is_err() || writer.cmds.is_empty() {
-                continue;
-            }
-            self.ops.push(VectorOp::FillPath {
-                path: writer.cmds,
-                color: rgba,
-                fill_rule: VectorFillRule::Winding,
-            });
+        if self.glyphs {
+            self.push_glyphs(run, rgba, base_transform);
+        } else {
+            self.push_glyph_outlines(run, rgba, base_transform, fonts);
         }
 
         if !run.text.is_empty() {
@@ -436,6 +490,7 @@ mod tests {
             graphemes: vec![],
             cursor_ascent: 0.0,
             cursor_descent: 0.0,
+            rtl: false,
         };
 
         let mut sink = VectorSink::new();
@@ -479,6 +534,7 @@ mod tests {
             graphemes: vec![],
             cursor_ascent: 0.0,
             cursor_descent: 0.0,
+            rtl: false,
         };
 
         let mut sink = VectorSink::new();
@@ -522,6 +578,7 @@ mod tests {
             graphemes: vec![],
             cursor_ascent: 0.0,
             cursor_descent: 0.0,
+            rtl: false,
         };
         let mut sink = VectorSink::new();
         sink.draw_glyph_run(&run, red(), Transform::IDENTITY, &resource.font_registry);
@@ -531,6 +588,70 @@ mod tests {
         assert_eq!(page.text_ops[0].size, 16.0);
     }
 
+    #[test]
+    fn glyph_sink_records_positioned_glyphs_instead_of_outlines() {
+        use editor_view::glyph_run::{Glyph, GlyphRun, Synthesis, TextDecoration};
+        const TEST_FONT: &[u8] = include_bytes!("../../../../assets/Pretendard-Regular.ttf");
+        let resource = resource_with_test_font(TEST_FONT);
+        let family_id = resource.font_registry.intern_id("test").unwrap();
+        let run = GlyphRun {
+            family_id,
+            weight: 400,
+            font_size: 16.0,
+            synthesis: Synthesis::default(),
+            color: "text.black".to_string(),
+            background_color: None,
+            glyphs: vec![
+                Glyph {
+                    id: 0,
+                    x: 0.0,
+                    y: 8.0,
+                },
+                Glyph {
+                    id: 3,
+                    x: 5.0,
+                    y: 8.0,
+                },
+            ],
+            decoration: TextDecoration::default(),
+            offset_range: 0..0,
+            link: None,
+            text: "A".to_string(),
+            x: 0.0,
+            width: 10.0,
+            graphemes: vec![],
+            cursor_ascent: 0.0,
+            cursor_descent: 0.0,
+            rtl: false,
+        };
+        let mut sink = VectorSink::with_glyphs();
+        sink.draw_glyph_run(&run, red(), Transform::scale(2.0), &resource.font_registry);
+        let page = sink.into_page(100.0, 100.0);
+
+        assert_eq!(page.ops.len(), 1);
+        match &page.ops[0] {
+            VectorOp::Glyphs {
+                family_id: id,
+                weight,
+                size,
+                color,
+                glyphs,
+                text,
+            } => {
+                assert_eq!(*id, family_id);
+                assert_eq!(*weight, 400);
+                assert_eq!(*size, 32.0);
+                assert_eq!(*color, [255, 0, 0, 255]);
+                assert_eq!(glyphs.len(), 1);
+                assert_eq!(glyphs[0].id, 3);
+                assert_eq!((glyphs[0].x, glyphs[0].y), (10.0, 16.0));
+                assert_eq!(text, "A");
+            }
+            other => panic!("expected Glyphs op, got {other:?}"),
+        }
+        assert_eq!(page.text_ops.len(), 1);
+    }
+
     #[test]
     fn draw_glyph_run_text_op_uses_first_glyph_origin_and_transform() {
         use editor_view::glyph_run::{Glyph, GlyphRun, Synthesis, TextDecoration};
@@ -558,6 +679,7 @@ mod tests {
             graphemes: vec![],
             cursor_ascent: 0.0,
             cursor_descent: 0.0,
+            rtl: false,
         };
         let mut sink = VectorSink::new();
         sink.draw_glyph_run(
@@ -611,6 +733,7 @@ mod tests {
             graphemes: vec![],
             cursor_ascent: 0.0,
             cursor_descent: 0.0,
+            rtl: false,
         };
 
         let mut sink = VectorSink::new();
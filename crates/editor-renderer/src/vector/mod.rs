@@ -1,3 +1,4 @@
 pub mod codec;
 pub mod export;
+pub mod svg;
 pub mod types;
@@ -0,0 +1,446 @@
+use std::fmt::Write;
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use editor_common::Rect;
+use zune_png::PngEncoder;
+use zune_png::zune_core::bit_depth::BitDepth;
+use zune_png::zune_core::colorspace::ColorSpace;
+use zune_png::zune_core::options::EncoderOptions;
+
+use crate::vector::types::{
+    TextOp, VectorFillRule, VectorLineCap, VectorLineJoin, VectorLink, VectorOp, VectorPage,
+    VectorPathCommand,
+};
+
+/// One area of a page placed in an SVG document. `rect` is in the page's
+/// layout coordinates; `links` are the page's link areas.
+pub struct SvgRegion<'a> {
+    pub page: &'a VectorPage,
+    pub links: &'a [VectorLink],
+    pub rect: Rect,
+}
+
+/// Writes the regions stacked top to bottom as one standalone SVG document.
+/// Text is drawn from the outline paths, with the page's text ops laid over it
+/// as invisible `<text>` so it can still be selected and searched.
+///
+/// The output depends only on the input: numbers are rounded to a fixed
+/// precision and elements follow display-list order.
+pub fn write_svg(regions: &[SvgRegion<'_>]) -> String {
+    let width = regions.iter().map(|r| r.rect.width).fold(0.0, f32::max);
+    let height: f32 = regions.iter().map(|r| r.rect.height).sum();
+
+    let mut out = String::new();
+    let _ = write!(
+        out,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#,
+        w = num(width),
+        h = num(height)
+    );
+    let mut offset = 0.0;
+    for region in regions {
+        write_region(&mut out, region, offset);
+        offset += region.rect.height;
+    }
+    out.push_str("</svg>");
+    out
+}
+
+fn write_region(out: &mut String, region: &SvgRegion<'_>, offset: f32) {
+    let rect = region.rect;
+    // A nested viewport clips everything outside the region.
+    let _ = write!(
+        out,
+        r#"<svg y="{}" width="{w}" height="{h}" viewBox="{} {} {w} {h}">"#,
+        num(offset),
+        num(rect.x),
+        num(rect.y),
+        w = num(rect.width),
+        h = num(rect.height)
+    );
+    for op in &region.page.ops {
+        write_op(out, op);
+    }
+
+    let text_ops: Vec<&TextOp> = region
+        .page
+        .text_ops
+        .iter()
+        .filter(|op| op.y >= rect.y && op.y - op.size <= rect.y + rect.height)
+        .collect();
+    if !text_ops.is_empty() {
+        out.push_str(r#"<g fill-opacity="0" font-family="sans-serif">"#);
+        for op in text_ops {
+            let _ = write!(
+                out,
+                r#"<text x="{}" y="{}" font-size="{}" xml:space="preserve">{}</text>"#,
+                num(op.x),
+                num(op.y),
+                num(op.size),
+                escape(&op.text)
+            );
+        }
+        out.push_str("</g>");
+    }
+
+    for link in region.links {
+        let _ = write!(
+            out,
+            r#"<a href="{}"><rect x="{}" y="{}" width="{}" height="{}" fill-opacity="0"/></a>"#,
+            escape(&link.href),
+            num(link.rect.x),
+            num(link.rect.y),
+            num(link.rect.width),
+            num(link.rect.height)
+        );
+    }
+    out.push_str("</svg>");
+}
+
+fn write_op(out: &mut String, op: &VectorOp) {
+    match op {
+        VectorOp::FillPath {
+            path,
+            color,
+            fill_rule,
+        } => {
+            let _ = write!(out, r#"<path d="{}""#, path_data(path));
+            write_paint(out, "fill", *color);
+            if matches!(fill_rule, VectorFillRule::EvenOdd) {
+                out.push_str(r#" fill-rule="evenodd""#);
+            }
+            out.push_str("/>");
+        }
+        VectorOp::StrokePath {
+            path,
+            color,
+            width,
+            line_cap,
+            line_join,
+        } => {
+            let _ = write!(
+                out,
+                r#"<path d="{}" fill="none" stroke-width="{}""#,
+                path_data(path),
+                num(*width)
+            );
+            write_paint(out, "stroke", *color);
+            out.push_str(match line_cap {
+                VectorLineCap::Butt => "",
+                VectorLineCap::Round => r#" stroke-linecap="round""#,
+                VectorLineCap::Square => r#" stroke-linecap="square""#,
+            });
+            out.push_str(match line_join {
+                VectorLineJoin::Miter => "",
+                VectorLineJoin::Round => r#" stroke-linejoin="round""#,
+                VectorLineJoin::Bevel => r#" stroke-linejoin="bevel""#,
+            });
+            out.push_str("/>");
+        }
+        VectorOp::Image {
+            data,
+            width,
+            height,
+            x,
+            y,
+            render_width,
+            render_height,
+        } => {
+            let Some(png) = encode_png(data, *width, *height) else {
+                return;
+            };
+            let _ = write!(
+                out,
+                r#"<image x="{}" y="{}" width="{}" height="{}" preserveAspectRatio="none" href="data:image/png;base64,{}"/>"#,
+                num(*x),
+                num(*y),
+                num(*render_width),
+                num(*render_height),
+                STANDARD.encode(png)
+            );
+        }
+        // Outline sinks draw text as fill paths; glyph runs have no font to
+        // reference in a standalone document.
+        VectorOp::Glyphs { .. } => {}
+    }
+}
+
+fn write_paint(out: &mut String, property: &str, [r, g, b, a]: [u8; 4]) {
+    let _ = write!(out, r##" {property}="#{r:02x}{g:02x}{b:02x}""##);
+    if a < 255 {
+        let opacity = (f32::from(a) / 255.0 * 1000.0).round() / 1000.0;
+        let _ = write!(out, r#" {property}-opacity="{opacity}""#);
+    }
+}
+
+fn path_data(path: &[VectorPathCommand]) -> String {
+    let mut d = String::new();
+    for cmd in path {
+        let _ = match *cmd {
+            VectorPathCommand::MoveTo { x, y } => write!(d, "M{} {}", num(x), num(y)),
+            VectorPathCommand::LineTo { x, y } => write!(d, "L{} {}", num(x), num(y)),
+            VectorPathCommand::QuadTo { cx, cy, x, y } => {
+                write!(d, "Q{} {} {} {}", num(cx), num(cy), num(x), num(y))
+            }
+            VectorPathCommand::CubicTo {
+                c1x,
+                c1y,
+                c2x,
+                c2y,
+                x,
+                y,
+            } => write!(
+                d,
+                "C{} {} {} {} {} {}",
+                num(c1x),
+                num(c1y),
+                num(c2x),
+                num(c2y),
+                num(x),
+                num(y)
+            ),
+            VectorPathCommand::ClosePath => d.write_str("Z"),
+        };
+    }
+    d
+}
+
+/// Vector images hold premultiplied RGBA; PNG wants straight alpha.
+fn encode_png(data: &[u8], width: u32, height: u32) -> Option<Vec<u8>> {
+    if width == 0 || height == 0 || data.len() != width as usize * height as usize * 4 {
+        return None;
+    }
+    let mut rgba = data.to_vec();
+    for px in rgba.chunks_exact_mut(4) {
+        let a = u32::from(px[3]);
+        if a == 0 {
+            px[..3].fill(0);
+        } else if a < 255 {
+            for c in &mut px[..3] {
+                *c = ((u32::from(*c) * 255 + a / 2) / a).min(255) as u8;
+            }
+        }
+    }
+    let options = EncoderOptions::new(
+        width as usize,
+        height as usize,
+        ColorSpace::RGBA,
+        BitDepth::Eight,
+    );
+    let mut png = Vec::new();
+    PngEncoder::new(&rgba, options).encode(&mut png).ok()?;
+    Some(png)
+}
+
+/// Coordinates rounded to a hundredth of a pixel, without a negative zero.
+fn num(v: f32) -> f32 {
+    if !v.is_finite() {
+        return 0.0;
+    }
+    let rounded = (v * 100.0).round() / 100.0;
+    if rounded == 0.0 { 0.0 } else { rounded }
+}
+
+/// Escapes text and attribute content, dropping characters XML cannot hold.
+fn escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for ch in s.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\t' | '\n' | '\r' => out.push(ch),
+            c if c < ' ' || matches!(c, '\u{FFFE}' | '\u{FFFF}') => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+
+    fn page(ops: Vec<VectorOp>, text_ops: Vec<TextOp>) -> VectorPage {
+        VectorPage {
+            width: 200.0,
+            height: 100.0,
+            ops,
+            text_ops,
+        }
+    }
+
+    fn full(page: &VectorPage) -> Rect {
+        Rect::from_xywh(0.0, 0.0, page.width, page.height)
+    }
+
+    #[test]
+    fn paths_keep_color_alpha_and_fill_rule() {
+        let page = page(
+            vec![
+                VectorOp::FillPath {
+                    path: vec![
+                        VectorPathCommand::MoveTo { x: 0.0, y: 0.0 },
+                        VectorPathCommand::LineTo {
+                            x: 10.004,
+                            y: -0.001,
+                        },
+                        VectorPathCommand::ClosePath,
+                    ],
+                    color: [255, 0, 16, 128],
+                    fill_rule: VectorFillRule::EvenOdd,
+                },
+                VectorOp::StrokePath {
+                    path: vec![VectorPathCommand::MoveTo { x: 1.0, y: 2.0 }],
+                    color: [0, 0, 0, 255],
+                    width: 1.5,
+                    line_cap: VectorLineCap::Round,
+                    line_join: VectorLineJoin::Miter,
+                },
+            ],
+            vec![],
+        );
+        let svg = write_svg(&[SvgRegion {
+            page: &page,
+            links: &[],
+            rect: full(&page),
+        }]);
+
+        assert!(svg.starts_with(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="200" height="100" viewBox="0 0 200 100">"#
+        ));
+        assert!(svg.contains(
+            r##"<path d="M0 0L10 0Z" fill="#ff0010" fill-opacity="0.502" fill-rule="evenodd"/>"##
+        ));
+        assert!(svg.contains(
+            r##"<path d="M1 2" fill="none" stroke-width="1.5" stroke="#000000" stroke-linecap="round"/>"##
+        ));
+        assert!(svg.ends_with("</svg></svg>"));
+    }
+
+    #[test]
+    fn text_and_links_are_escaped_overlays() {
+        let page = page(
+            vec![],
+            vec![TextOp {
+                text: "a < b & \"c\"\u{0}".to_string(),
+                x: 10.0,
+                y: 20.0,
+                size: 16.0,
+            }],
+        );
+        let links = [VectorLink {
+            page_idx: 0,
+            rect: Rect::from_xywh(10.0, 4.0, 30.0, 20.0),
+            href: "https://typie.co/?a=1&b=2".to_string(),
+        }];
+        let svg = write_svg(&[SvgRegion {
+            page: &page,
+            links: &links,
+            rect: full(&page),
+        }]);
+
+        assert!(svg.contains(
+            r#"<text x="10" y="20" font-size="16" xml:space="preserve">a &lt; b &amp; &quot;c&quot;</text>"#
+        ));
+        assert!(svg.contains(r#"<a href="https://typie.co/?a=1&amp;b=2"><rect x="10" y="4""#));
+    }
+
+    #[test]
+    fn images_are_embedded_as_png_data_uris() {
+        let page = page(
+            vec![VectorOp::Image {
+                data: Arc::from(vec![128, 0, 0, 128]),
+                width: 1,
+                height: 1,
+                x: 5.0,
+                y: 6.0,
+                render_width: 7.0,
+                render_height: 8.0,
+            }],
+            vec![],
+        );
+        let svg = write_svg(&[SvgRegion {
+            page: &page,
+            links: &[],
+            rect: full(&page),
+        }]);
+
+        let prefix = "href=\"data:image/png;base64,";
+        let start = svg.find(prefix).unwrap() + prefix.len();
+        let end = start + svg[start..].find('"').unwrap();
+        let png = STANDARD.decode(&svg[start..end]).unwrap();
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+        assert!(svg.contains(r#"<image x="5" y="6" width="7" height="8""#));
+    }
+
+    #[test]
+    fn regions_are_stacked_and_cropped() {
+        let first = page(
+            vec![],
+            vec![
+                TextOp {
+                    text: "inside".to_string(),
+                    x: 0.0,
+                    y: 30.0,
+                    size: 10.0,
+                },
+                TextOp {
+                    text: "outside".to_string(),
+                    x: 0.0,
+                    y: 90.0,
+                    size: 10.0,
+                },
+            ],
+        );
+        let second = page(vec![], vec![]);
+        let svg = write_svg(&[
+            SvgRegion {
+                page: &first,
+                links: &[],
+                rect: Rect::from_xywh(10.0, 20.0, 50.0, 30.0),
+            },
+            SvgRegion {
+                page: &second,
+                links: &[],
+                rect: Rect::from_xywh(0.0, 0.0, 80.0, 40.0),
+            },
+        ]);
+
+        assert!(svg.contains(r#"width="80" height="70" viewBox="0 0 80 70""#));
+        assert!(svg.contains(r#"<svg y="0" width="50" height="30" viewBox="10 20 50 30">"#));
+        assert!(svg.contains(r#"<svg y="30" width="80" height="40" viewBox="0 0 80 40">"#));
+        assert!(svg.contains("inside"));
+        assert!(!svg.contains("outside"));
+    }
+
+    #[test]
+    fn output_is_deterministic() {
+        let page = page(
+            vec![VectorOp::FillPath {
+                path: vec![VectorPathCommand::CubicTo {
+                    c1x: 0.1,
+                    c1y: 0.2,
+                    c2x: 1.0 / 3.0,
+                    c2y: 2.0 / 3.0,
+                    x: 1.0,
+                    y: 1.0,
+                }],
+                color: [1, 2, 3, 255],
+                fill_rule: VectorFillRule::Winding,
+            }],
+            vec![],
+        );
+        let region = [SvgRegion {
+            page: &page,
+            links: &[],
+            rect: full(&page),
+        }];
+        assert_eq!(write_svg(&region), write_svg(&region));
+        assert!(write_svg(&region).contains(r#"d="C0.1 0.2 0.33 0.67 1 1""#));
+    }
+}
@@ -68,6 +68,23 @@ pub enum VectorOp {
         render_width: f32,
         render_height: f32,
     },
+    /// Text kept as positioned glyphs of a registered font instead of outlines,
+    /// recorded only by [`VectorSink::with_glyphs`](crate::vector::export::VectorSink::with_glyphs).
+    Glyphs {
+        family_id: u16,
+        weight: u16,
+        size: f32,
+        color: [u8; 4],
+        glyphs: Vec<VectorGlyph>,
+        text: String,
+    },
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct VectorGlyph {
+    pub id: u32,
+    pub x: f32,
+    pub y: f32,
 }
 
 #[derive(Debug, Clone)]
@@ -85,3 +102,27 @@ pub struct VectorPage {
     pub ops: Vec<VectorOp>,
     pub text_ops: Vec<TextOp>,
 }
+
+#[derive(Debug, Clone)]
+pub struct VectorLink {
+    pub page_idx: usize,
+    pub rect: editor_common::Rect,
+    pub href: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct VectorOutlineItem {
+    pub title: String,
+    pub page_idx: usize,
+    pub y: f32,
+    pub children: Vec<VectorOutlineItem>,
+}
+
+/// Every page of a document together with the navigation that sits outside the
+/// display lists: link areas and the outline.
+#[derive(Debug, Clone, Default)]
+pub struct VectorDocument {
+    pub pages: Vec<VectorPage>,
+    pub links: Vec<VectorLink>,
+    pub outline: Vec<VectorOutlineItem>,
+}
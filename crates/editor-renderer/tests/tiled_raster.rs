@@ -0,0 +1,149 @@
+//! Banded CPU rasterization against the serial path: bit-identical output,
+//! and a perf gate for a full 3x-density page repaint (theme change, zoom).
+//! Perf run: cargo test -p editor-renderer --release perf_tiled -- --ignored --nocapture
+
+use std::time::Instant;
+
+use editor_common::Rect;
+use editor_renderer::RenderSink;
+use editor_renderer::backend::cpu::{CpuSink, TiledRasterizer};
+use editor_renderer::damage::IRect;
+use editor_renderer::diff::{render_incremental, render_incremental_tiled, replay};
+use editor_renderer::display_list::{DisplayList, DisplayListRecorder};
+use editor_renderer::types::{Color, Path, PathElement, Stroke, Transform};
+
+/// `shapes` overlapping translucent curves and rects scattered over the page.
+fn scene(width: i32, height: i32, shapes: usize) -> DisplayList {
+    let full = IRect {
+        x0: 0,
+        y0: 0,
+        x1: width,
+        y1: height,
+    };
+    let mut rec = DisplayListRecorder::new(full);
+    let mut seed = 0x2545_f491_u32;
+    let mut next = |max: f32| {
+        seed ^= seed << 13;
+        seed ^= seed >> 17;
+        seed ^= seed << 5;
+        (seed % 10_000) as f32 / 10_000.0 * max
+    };
+    for i in 0..shapes {
+        let (x, y) = (next(width as f32), next(height as f32));
+        let (w, h) = (8.0 + next(120.0), 8.0 + next(120.0));
+        let color = Color::new((i * 37) as u8, (i * 91) as u8, 160, 128 + (i % 128) as u8);
+        let path = Path {
+            elements: vec![
+                PathElement::MoveTo { x, y },
+                PathElement::CurveTo {
+                    x1: x + w,
+                    y1: y - h / 2.0,
+                    x2: x + w * 1.5,
+                    y2: y + h,
+                    x: x + w / 3.0,
+                    y: y + h,
+                },
+                PathElement::Close,
+            ],
+        };
+        if i % 3 == 0 {
+            rec.stroke_path(&path, color, &Stroke::new(2.5), Transform::IDENTITY);
+        } else {
+            rec.fill_path(&path, color, Transform::IDENTITY);
+        }
+        rec.fill_rect(
+            Rect::from_xywh(x, y, w / 4.0, h / 4.0),
+            color,
+            Transform::IDENTITY,
+        );
+    }
+    rec.into_list()
+}
+
+fn serial(dl: &DisplayList, width: u16, height: u16, damage: &[IRect]) -> CpuSink {
+    let mut sink = CpuSink::new(width, height);
+    render_incremental(None, dl, &mut sink, damage[0]);
+    for &r in &damage[1..] {
+        sink.clear_rect(r);
+        sink.set_clip(Some(r));
+        replay(dl, r, &mut sink);
+    }
+    sink.set_clip(None);
+    sink
+}
+
+#[test]
+fn tiled_output_is_bit_identical_to_serial() {
+    let (width, height) = (300u16, 450u16);
+    let dl = scene(width as i32, height as i32, 120);
+    let damage = [
+        IRect {
+            x0: 0,
+            y0: 0,
+            x1: width as i32,
+            y1: height as i32,
+        },
+        IRect {
+            x0: 17,
+            y0: 50,
+            x1: 211,
+            y1: 333,
+        },
+    ];
+    let expected = serial(&dl, width, height, &damage);
+
+    for workers in [1, 3, 8] {
+        let mut sink = CpuSink::new(width, height);
+        sink.fill_rect(
+            Rect::from_xywh(0.0, 0.0, width as f32, height as f32),
+            Color::new(9, 9, 9, 255),
+            Transform::IDENTITY,
+        );
+        TiledRasterizer::new(workers).render_damage(&dl, &damage, &mut sink);
+        assert!(
+            sink.pixels() == expected.pixels(),
+            "{workers} workers must match the serial raster"
+        );
+    }
+}
+
+#[test]
+#[ignore]
+fn perf_tiled_full_page_repaint() {
+    let (width, height) = (2400u16, 3400u16);
+    let dl = scene(width as i32, height as i32, 6_000);
+    let full = IRect {
+        x0: 0,
+        y0: 0,
+        x1: width as i32,
+        y1: height as i32,
+    };
+
+    let mut tiles = TiledRasterizer::with_available_parallelism();
+    // Warm-up: starts the shared pool and sizes the band scratches.
+    let mut tiled_sink = CpuSink::new(width, height);
+    render_incremental_tiled(None, &dl, &mut tiled_sink, full, &mut tiles);
+
+    let mut serial_sink = CpuSink::new(width, height);
+    let t = Instant::now();
+    render_incremental(None, &dl, &mut serial_sink, full);
+    let serial = t.elapsed();
+
+    let mut tiled_sink = CpuSink::new(width, height);
+    let t = Instant::now();
+    render_incremental_tiled(None, &dl, &mut tiled_sink, full, &mut tiles);
+    let tiled = t.elapsed();
+
+    eprintln!(
+        "perf_tiled_full_page_repaint: serial {serial:?}, {} workers {tiled:?}, {:.2}x",
+        tiles.workers(),
+        serial.as_secs_f64() / tiled.as_secs_f64()
+    );
+    assert!(serial_sink.pixels() == tiled_sink.pixels());
+    if tiles.workers() >= 4 {
+        assert!(
+            tiled * 2 < serial,
+            "tiled repaint should be at least twice as fast: {tiled:?} vs {serial:?}"
+        );
+    }
+}
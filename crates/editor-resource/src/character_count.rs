@@ -1,7 +1,11 @@
+use editor_macros::ffi;
 use icu_properties::CodePointMapData;
 use icu_properties::props::GeneralCategory;
 use icu_segmenter::GraphemeClusterSegmenter;
+use serde::{Deserialize, Serialize};
 
+#[ffi]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct CharacterCount {
     pub with_whitespace: u32,
     pub without_whitespace: u32,
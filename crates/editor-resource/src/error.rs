@@ -23,4 +23,16 @@ pub enum ResourceError {
 
     #[error("invalid font data: {0}")]
     InvalidFont(String),
+
+    #[error("invalid hyphenation patterns: {0}")]
+    InvalidHyphenation(String),
+
+    #[error("invalid image: {0}")]
+    InvalidImage(String),
+
+    #[error("invalid theme: {0}")]
+    InvalidTheme(String),
+
+    #[error("invalid spellcheck dictionary: {0}")]
+    InvalidSpellcheck(String),
 }
@@ -0,0 +1,186 @@
+use hashbrown::HashMap;
+use std::sync::Arc;
+
+use crate::error::ResourceError;
+use crate::zstd::decompress_zstd_capped;
+
+const PATTERNS_MAX_BYTES: usize = 4 * 1024 * 1024;
+
+/// Liang's hyphenation patterns for one language, parsed from the plain-text
+/// files TeX distributes (`hyph-en-us.pat.txt` and friends): whitespace
+/// separated patterns, `%` comments, and words spelled with `-` as exceptions.
+#[derive(Debug, Default)]
+pub struct Hyphenator {
+    /// Pattern letters → priority before each letter and after the last.
+    patterns: HashMap<Box<str>, Box<[u8]>>,
+    /// Lowercased word → char indices a hyphen may precede.
+    exceptions: HashMap<Box<str>, Box<[usize]>>,
+    /// Longest pattern, in chars.
+    max_len: usize,
+    left_min: usize,
+    right_min: usize,
+}
+
+impl Hyphenator {
+    pub fn parse(source: &str) -> Self {
+        let mut hyphenator = Self {
+            left_min: 2,
+            right_min: 3,
+            ..Self::default()
+        };
+        for token in source
+            .lines()
+            .map(|line| line.split('%').next().unwrap_or_default())
+            .flat_map(str::split_whitespace)
+        {
+            if token.contains('-') {
+                hyphenator.insert_exception(token);
+            } else {
+                hyphenator.insert_pattern(token);
+            }
+        }
+        hyphenator
+    }
+
+    fn insert_pattern(&mut self, token: &str) {
+        let mut letters = String::new();
+        let mut priorities = vec![0u8];
+        for c in token.chars() {
+            match c.to_digit(10) {
+                Some(digit) => *priorities.last_mut().unwrap() = digit as u8,
+                None => {
+                    letters.push(c);
+                    priorities.push(0);
+                }
+            }
+        }
+        if letters.is_empty() {
+            return;
+        }
+        self.max_len = self.max_len.max(letters.chars().count());
+        self.patterns
+            .insert(letters.into_boxed_str(), priorities.into_boxed_slice());
+    }
+
+    fn insert_exception(&mut self, token: &str) {
+        let mut word = String::new();
+        let mut points = Vec::new();
+        let mut len = 0;
+        for c in token.chars() {
+            if c == '-' {
+                points.push(len);
+            } else {
+                word.push(lowercase(c));
+                len += 1;
+            }
+        }
+        self.exceptions
+            .insert(word.into_boxed_str(), points.into_boxed_slice());
+    }
+
+    /// Char indices into `word` a hyphen may be inserted before, ascending.
+    /// Leaves at least two chars before and three after every point, as
+    /// TeX's English defaults do.
+    pub fn hyphenate(&self, word: &str) -> Vec<usize> {
+        let lower: String = word.chars().map(lowercase).collect();
+        let len = lower.chars().count();
+        if len < self.left_min + self.right_min {
+            return Vec::new();
+        }
+        let allowed = self.left_min..=len - self.right_min;
+        if let Some(points) = self.exceptions.get(lower.as_str()) {
+            return points
+                .iter()
+                .copied()
+                .filter(|k| allowed.contains(k))
+                .collect();
+        }
+
+        let dotted = format!(".{lower}.");
+        let bounds: Vec<usize> = dotted
+            .char_indices()
+            .map(|(i, _)| i)
+            .chain([dotted.len()])
+            .collect();
+        let chars = bounds.len() - 1;
+        let mut priorities = vec![0u8; chars + 1];
+        for start in 0..chars {
+            for end in start + 1..=chars.min(start + self.max_len) {
+                let Some(pattern) = self.patterns.get(&dotted[bounds[start]..bounds[end]]) else {
+                    continue;
+                };
+                for (k, &priority) in pattern.iter().enumerate() {
+                    priorities[start + k] = priorities[start + k].max(priority);
+                }
+            }
+        }
+
+        // The gap before word char `k` is the gap before dotted char `k + 1`.
+        allowed.filter(|&k| priorities[k + 1] % 2 == 1).collect()
+    }
+}
+
+/// Exceptions and words are matched on the same single-char lowercase folding.
+fn lowercase(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+#[derive(Clone)]
+pub struct PreparedHyphenation {
+    pub(crate) hyphenator: Arc<Hyphenator>,
+}
+
+/// Decompresses and parses a zstd-compressed pattern file, shipped the same
+/// way as the ICU data blob.
+pub fn prepare_hyphenation(data: &[u8]) -> Result<PreparedHyphenation, ResourceError> {
+    let raw = decompress_zstd_capped(data, PATTERNS_MAX_BYTES)?;
+    let source =
+        String::from_utf8(raw).map_err(|e| ResourceError::InvalidHyphenation(e.to_string()))?;
+    Ok(PreparedHyphenation {
+        hyphenator: Arc::new(Hyphenator::parse(&source)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::zstd::compress_zstd;
+
+    // Liang's worked example for "hyphenation".
+    const PATTERNS: &str = "% example\nhy3ph he2n hena4 hen5at 1na n2at 1tio 2io o2n\n";
+
+    #[test]
+    fn patterns_pick_odd_priorities() {
+        let hyphenator = Hyphenator::parse(PATTERNS);
+        assert_eq!(hyphenator.hyphenate("hyphenation"), vec![2, 6]);
+        assert_eq!(hyphenator.hyphenate("Hyphenation"), vec![2, 6]);
+    }
+
+    #[test]
+    fn short_words_and_edges_are_left_alone() {
+        let hyphenator = Hyphenator::parse("1a1 1b1");
+        assert!(hyphenator.hyphenate("abab").is_empty());
+        assert_eq!(hyphenator.hyphenate("ababab"), vec![2, 3]);
+    }
+
+    #[test]
+    fn exceptions_override_patterns() {
+        let hyphenator = Hyphenator::parse(&format!("{PATTERNS} hyphen-ation"));
+        assert_eq!(hyphenator.hyphenate("hyphenation"), vec![6]);
+    }
+
+    #[test]
+    fn exceptions_respect_the_edges_and_ignore_case() {
+        let hyphenator = Hyphenator::parse("-ta-bles- Hy-phen-ation");
+        assert_eq!(hyphenator.hyphenate("tables"), vec![2]);
+        assert_eq!(hyphenator.hyphenate("hyphenation"), vec![2, 6]);
+        assert_eq!(hyphenator.hyphenate("HYPHENATION"), vec![2, 6]);
+    }
+
+    #[test]
+    fn prepared_patterns_are_decompressed() {
+        let prepared = prepare_hyphenation(&compress_zstd(PATTERNS.as_bytes())).unwrap();
+        assert_eq!(prepared.hyphenator.hyphenate("hyphenation"), vec![2, 6]);
+        assert!(prepare_hyphenation(b"not zstd").is_err());
+    }
+}
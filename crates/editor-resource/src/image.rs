@@ -0,0 +1,94 @@
+use std::sync::Arc;
+
+use crate::error::ResourceError;
+
+/// Largest side accepted from a host, in pixels.
+const IMAGE_MAX_SIDE: u32 = 16_384;
+
+/// Decoded pixels of one host-supplied image, premultiplied RGBA8 so every
+/// renderer backend can draw them without converting again.
+#[derive(Debug)]
+pub struct ImagePixels {
+    pub width: u32,
+    pub height: u32,
+    pub data: Arc<[u8]>,
+    hash: u64,
+}
+
+impl ImagePixels {
+    /// Height over width, the ratio a layout keeps when it scales the image.
+    pub fn aspect_ratio(&self) -> f32 {
+        self.height as f32 / self.width as f32
+    }
+}
+
+pub struct PreparedImage {
+    pub(crate) pixels: Arc<ImagePixels>,
+}
+
+impl PreparedImage {
+    pub fn pixels(&self) -> &Arc<ImagePixels> {
+        &self.pixels
+    }
+}
+
+/// Validates straight-alpha RGBA8 pixels decoded by the host and
+/// premultiplies them ahead of taking the `ResourceSource` lock.
+pub fn prepare_image(
+    width: u32,
+    height: u32,
+    mut rgba: Vec<u8>,
+) -> Result<PreparedImage, ResourceError> {
+    if width == 0 || height == 0 || width > IMAGE_MAX_SIDE || height > IMAGE_MAX_SIDE {
+        return Err(ResourceError::InvalidImage(format!(
+            "unsupported size {width}x{height}"
+        )));
+    }
+    if rgba.len() != width as usize * height as usize * 4 {
+        return Err(ResourceError::InvalidImage(format!(
+            "expected {} bytes for {width}x{height}, got {}",
+            width as usize * height as usize * 4,
+            rgba.len()
+        )));
+    }
+    let hash = rapidhash::v3::rapidhash_v3(&rgba);
+    for px in rgba.chunks_exact_mut(4) {
+        let a = u32::from(px[3]);
+        for c in &mut px[..3] {
+            *c = ((u32::from(*c) * a + 127) / 255) as u8;
+        }
+    }
+    Ok(PreparedImage {
+        pixels: Arc::new(ImagePixels {
+            width,
+            height,
+            data: rgba.into(),
+            hash,
+        }),
+    })
+}
+
+pub(crate) fn same_pixels(a: &ImagePixels, b: &ImagePixels) -> bool {
+    a.width == b.width && a.height == b.height && a.hash == b.hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prepare_image_premultiplies_and_rejects_bad_lengths() {
+        let prepared = prepare_image(1, 1, vec![255, 0, 100, 128]).unwrap();
+        assert_eq!(&prepared.pixels().data[..], &[128, 0, 50, 128]);
+        assert_eq!(prepared.pixels().aspect_ratio(), 1.0);
+
+        assert!(matches!(
+            prepare_image(2, 1, vec![0; 4]),
+            Err(ResourceError::InvalidImage(_))
+        ));
+        assert!(matches!(
+            prepare_image(0, 1, Vec::new()),
+            Err(ResourceError::InvalidImage(_))
+        ));
+    }
+}
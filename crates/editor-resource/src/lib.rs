@@ -4,9 +4,13 @@ mod brush;
 mod character_count;
 mod error;
 mod font;
+mod hyphenation;
+mod image;
 mod resource;
 mod segmentation;
+mod spellcheck;
 mod text_replacement;
+mod text_statistics;
 mod theme;
 mod theme_data;
 mod zstd;
@@ -15,9 +19,13 @@ pub use brush::*;
 pub use character_count::*;
 pub use error::*;
 pub use font::*;
+pub use hyphenation::*;
+pub use image::*;
 pub use resource::*;
 pub use segmentation::*;
+pub use spellcheck::*;
 pub use text_replacement::*;
+pub use text_statistics::*;
 pub use theme::*;
 pub use theme_data::*;
 pub use zstd::*;
@@ -12,9 +12,12 @@ use icu_properties::props::GeneralCategory;
 use crate::brush::TextBrush;
 use crate::error::ResourceError;
 use crate::font::{FontData, FontFamily, FontManifest, FontRegistry};
+use crate::hyphenation::{Hyphenator, PreparedHyphenation};
+use crate::image::{ImagePixels, PreparedImage, same_pixels};
 use crate::segmentation::{IcuResources, TextSegmenters};
+use crate::spellcheck::{PreparedSpellchecker, Spellchecker};
 use crate::text_replacement::{PreparedTextReplacementRules, TextReplacementRule};
-use crate::theme::Theme;
+use crate::theme::{PreparedTheme, Theme};
 use crate::theme_data::ThemeVariant;
 use crate::zstd::decompress_zstd_capped;
 
@@ -90,6 +93,10 @@ pub struct ResourceSnapshot {
     general_category: Arc<CodePointMapData<GeneralCategory>>,
     text_replacement_rules: Arc<[TextReplacementRule]>,
     auto_surround_enabled: bool,
+    hyphenation_patterns: Arc<BTreeMap<String, Arc<Hyphenator>>>,
+    hyphenation_language: Option<String>,
+    images: Arc<BTreeMap<String, Arc<ImagePixels>>>,
+    spellchecker: Option<Arc<Spellchecker>>,
 }
 
 impl ResourceSnapshot {
@@ -104,6 +111,10 @@ impl ResourceSnapshot {
             general_category: icu.general_category,
             text_replacement_rules: Arc::from([]),
             auto_surround_enabled: true,
+            hyphenation_patterns: Arc::new(BTreeMap::new()),
+            hyphenation_language: None,
+            images: Arc::new(BTreeMap::new()),
+            spellchecker: None,
         }
     }
 
@@ -135,6 +146,40 @@ impl ResourceSnapshot {
         self.auto_surround_enabled
     }
 
+    pub fn hyphenation_language(&self) -> Option<&str> {
+        self.hyphenation_language.as_deref()
+    }
+
+    /// Patterns for the active hyphenation language. `None` while
+    /// hyphenation is off or that language's patterns have not arrived.
+    pub fn hyphenator(&self) -> Option<&Arc<Hyphenator>> {
+        self.hyphenation_patterns
+            .get(self.hyphenation_language.as_deref()?)
+    }
+
+    /// Pixels the host supplied for the image node with this id.
+    pub fn image(&self, id: &str) -> Option<&Arc<ImagePixels>> {
+        self.images.get(id)
+    }
+
+    /// The dictionary the editor checks spelling with, if the host loaded one.
+    pub fn spellchecker(&self) -> Option<&Arc<Spellchecker>> {
+        self.spellchecker.as_ref()
+    }
+
+    /// A detached copy with `images` added, for one-off renders that bring
+    /// their own pixels without publishing them through a `ResourceSource`.
+    pub fn with_images(
+        &self,
+        images: impl IntoIterator<Item = (String, PreparedImage)>,
+    ) -> ResourceSnapshot {
+        let mut next = self.with_revision_from();
+        let mut store = next.images.as_ref().clone();
+        store.extend(images.into_iter().map(|(id, p)| (id, p.pixels)));
+        next.images = Arc::new(store);
+        next
+    }
+
     fn with_revision_from(&self) -> Self {
         Self {
             revision: self.revision.next(),
@@ -144,6 +189,10 @@ impl ResourceSnapshot {
             general_category: Arc::clone(&self.general_category),
             text_replacement_rules: Arc::clone(&self.text_replacement_rules),
             auto_surround_enabled: self.auto_surround_enabled,
+            hyphenation_patterns: Arc::clone(&self.hyphenation_patterns),
+            hyphenation_language: self.hyphenation_language.clone(),
+            images: Arc::clone(&self.images),
+            spellchecker: self.spellchecker.clone(),
         }
     }
 }
@@ -174,7 +223,7 @@ impl ResourceSource {
     }
 
     pub fn set_theme_variant(&mut self, variant: ThemeVariant) -> Option<Arc<ResourceSnapshot>> {
-        if self.current.theme.variant() == variant {
+        if self.current.theme.variant() == Some(variant) {
             return None;
         }
         let mut next = self.current.with_revision_from();
@@ -182,6 +231,18 @@ impl ResourceSource {
         Some(self.commit(next))
     }
 
+    /// Replaces the theme with a custom one from [`prepare_theme`].
+    ///
+    /// [`prepare_theme`]: crate::prepare_theme
+    pub fn set_theme(&mut self, prepared: PreparedTheme) -> Option<Arc<ResourceSnapshot>> {
+        if *self.current.theme == prepared.theme {
+            return None;
+        }
+        let mut next = self.current.with_revision_from();
+        next.theme = Arc::new(prepared.theme);
+        Some(self.commit(next))
+    }
+
     pub fn set_text_replacement_rules(
         &mut self,
         prepared: PreparedTextReplacementRules,
@@ -203,6 +264,46 @@ impl ResourceSource {
         Some(self.commit(next))
     }
 
+    pub fn add_hyphenation_patterns(
+        &mut self,
+        language: &str,
+        prepared: PreparedHyphenation,
+    ) -> Arc<ResourceSnapshot> {
+        let mut patterns = self.current.hyphenation_patterns.as_ref().clone();
+        patterns.insert(language.to_owned(), prepared.hyphenator);
+        let mut next = self.current.with_revision_from();
+        next.hyphenation_patterns = Arc::new(patterns);
+        self.commit(next)
+    }
+
+    /// Selects the language whose patterns hyphenate text, or turns
+    /// hyphenation off with `None`.
+    pub fn set_hyphenation_language(
+        &mut self,
+        language: Option<String>,
+    ) -> Option<Arc<ResourceSnapshot>> {
+        if self.current.hyphenation_language == language {
+            return None;
+        }
+        let mut next = self.current.with_revision_from();
+        next.hyphenation_language = language;
+        Some(self.commit(next))
+    }
+
+    /// Installs the dictionary the editor checks spelling with, or turns
+    /// spellchecking off with `None`.
+    pub fn set_spellchecker(
+        &mut self,
+        prepared: Option<PreparedSpellchecker>,
+    ) -> Option<Arc<ResourceSnapshot>> {
+        if prepared.is_none() && self.current.spellchecker.is_none() {
+            return None;
+        }
+        let mut next = self.current.with_revision_from();
+        next.spellchecker = prepared.map(|p| p.spellchecker);
+        Some(self.commit(next))
+    }
+
     pub fn set_fonts(&mut self, prepared: PreparedFonts) -> Option<Arc<ResourceSnapshot>> {
         let mut fonts = self.current.fonts.as_ref().clone();
         if !fonts.set_fonts(prepared.families) {
@@ -275,6 +376,34 @@ impl ResourceSource {
         next.fonts = Arc::new(fonts);
         Some(self.commit(next))
     }
+
+    pub fn insert_image(
+        &mut self,
+        id: &str,
+        prepared: PreparedImage,
+    ) -> Option<Arc<ResourceSnapshot>> {
+        if let Some(existing) = self.current.images.get(id)
+            && same_pixels(existing, &prepared.pixels)
+        {
+            return None;
+        }
+        let mut images = self.current.images.as_ref().clone();
+        images.insert(id.to_owned(), prepared.pixels);
+        let mut next = self.current.with_revision_from();
+        next.images = Arc::new(images);
+        Some(self.commit(next))
+    }
+
+    pub fn remove_image(&mut self, id: &str) -> Option<Arc<ResourceSnapshot>> {
+        if !self.current.images.contains_key(id) {
+            return None;
+        }
+        let mut images = self.current.images.as_ref().clone();
+        images.remove(id);
+        let mut next = self.current.with_revision_from();
+        next.images = Arc::new(images);
+        Some(self.commit(next))
+    }
 }
 
 pub struct PreparedFonts {
@@ -442,6 +571,18 @@ impl Resource {
         self.snapshot.auto_surround_enabled()
     }
 
+    pub fn hyphenator(&self) -> Option<&Arc<Hyphenator>> {
+        self.snapshot.hyphenator()
+    }
+
+    pub fn image(&self, id: &str) -> Option<&Arc<ImagePixels>> {
+        self.snapshot.image(id)
+    }
+
+    pub fn spellchecker(&self) -> Option<&Arc<Spellchecker>> {
+        self.snapshot.spellchecker()
+    }
+
     pub fn apply_update(
         &mut self,
         snapshot: Arc<ResourceSnapshot>,
@@ -541,6 +682,43 @@ mod tests {
         assert!(Arc::ptr_eq(before.fonts(), after.fonts()));
     }
 
+    #[test]
+    fn resource_snapshot_hyphenator_follows_the_active_language() {
+        let mut source = ResourceSource::new_test();
+        let patterns = crate::zstd::compress_zstd(b"1na");
+        source.add_hyphenation_patterns(
+            "en",
+            crate::hyphenation::prepare_hyphenation(&patterns).unwrap(),
+        );
+        assert!(source.snapshot().hyphenator().is_none());
+
+        let enabled = source
+            .set_hyphenation_language(Some("en".into()))
+            .expect("language changed");
+        assert!(enabled.hyphenator().is_some());
+        assert!(Arc::ptr_eq(&enabled, &source.snapshot()));
+        assert!(source.set_hyphenation_language(Some("en".into())).is_none());
+
+        let unknown = source
+            .set_hyphenation_language(Some("de".into()))
+            .expect("language changed");
+        assert!(unknown.hyphenator().is_none());
+    }
+
+    #[test]
+    fn resource_snapshot_images_commit_only_on_change() {
+        let mut source = ResourceSource::new_test();
+        let red = || crate::image::prepare_image(1, 1, vec![255, 0, 0, 255]).unwrap();
+        let added = source.insert_image("img", red()).expect("image added");
+        assert_eq!(added.image("img").map(|p| p.width), Some(1));
+        assert!(Arc::ptr_eq(added.theme(), source.snapshot().theme()));
+        assert!(source.insert_image("img", red()).is_none());
+
+        let removed = source.remove_image("img").expect("image removed");
+        assert!(removed.image("img").is_none());
+        assert!(source.remove_image("img").is_none());
+    }
+
     #[test]
     fn resource_snapshot_local_resources_have_distinct_mutable_contexts() {
         let source = ResourceSource::new_test();
@@ -563,6 +741,34 @@ mod tests {
         assert_eq!(source.revision(), ResourceRevision::INITIAL);
     }
 
+    #[test]
+    fn custom_theme_commits_once_and_yields_to_builtin_variant() {
+        let sepia = || {
+            crate::prepare_theme(crate::ThemeDefinition {
+                base: Some(ThemeVariant::LightWhite),
+                colors: vec![crate::ThemeColor {
+                    token: "ui.surface.default".into(),
+                    value: "#f4ecd8".into(),
+                }],
+            })
+            .unwrap()
+        };
+        let mut source = ResourceSource::new_test();
+
+        let committed = source.set_theme(sepia()).expect("theme changed");
+        assert_eq!(committed.theme().variant(), None);
+        assert_eq!(
+            committed.theme().color("ui.surface.default"),
+            editor_common::Color::rgb(0xf4, 0xec, 0xd8)
+        );
+        assert!(source.set_theme(sepia()).is_none());
+
+        let restored = source
+            .set_theme_variant(ThemeVariant::LightWhite)
+            .expect("custom theme replaced");
+        assert_eq!(restored.theme().variant(), Some(ThemeVariant::LightWhite));
+    }
+
     #[test]
     fn resource_update_preparation_failure_does_not_change_source() {
         let source = ResourceSource::new_test();
@@ -610,7 +816,7 @@ mod tests {
             .set_text_replacement_rules(prepared_rules)
             .expect("rules changed");
 
-        assert_eq!(committed.theme().variant(), ThemeVariant::DarkBlack);
+        assert_eq!(committed.theme().variant(), Some(ThemeVariant::DarkBlack));
         assert_eq!(committed.text_replacement_rules().len(), 1);
         assert_eq!(committed.revision(), ResourceRevision::new(2));
     }
@@ -902,7 +1108,7 @@ mod tests {
     #[test]
     fn new_initializes_with_light_white_theme() {
         let resource = Resource::new_test();
-        assert_eq!(resource.theme().variant(), ThemeVariant::LightWhite);
+        assert_eq!(resource.theme().variant(), Some(ThemeVariant::LightWhite));
     }
 
     #[test]
@@ -914,7 +1120,29 @@ mod tests {
             .expect("theme changed");
 
         assert_eq!(resource.apply_update(snapshot), Ok(true));
-        assert_eq!(resource.theme().variant(), ThemeVariant::DarkBlack);
+        assert_eq!(resource.theme().variant(), Some(ThemeVariant::DarkBlack));
+    }
+
+    #[test]
+    fn resource_applies_source_spellchecker_update() {
+        let mut source = ResourceSource::new_test();
+        let mut resource = Resource::from_snapshot(source.snapshot());
+        assert!(source.set_spellchecker(None).is_none());
+
+        let prepared = crate::spellcheck::prepare_spellchecker(
+            &crate::zstd::compress_zstd(b"SET UTF-8\n"),
+            &crate::zstd::compress_zstd(b"1\nhello\n"),
+        )
+        .unwrap();
+        let snapshot = source
+            .set_spellchecker(Some(prepared))
+            .expect("dictionary installed");
+        assert_eq!(resource.apply_update(snapshot), Ok(true));
+        assert!(resource.spellchecker().is_some_and(|c| c.check("hello")));
+
+        let snapshot = source.set_spellchecker(None).expect("dictionary cleared");
+        assert_eq!(resource.apply_update(snapshot), Ok(true));
+        assert!(resource.spellchecker().is_none());
     }
 
     #[test]
@@ -0,0 +1,710 @@
+use hashbrown::{HashMap, HashSet};
+use icu_segmenter::WordSegmenter;
+use std::ops::Range;
+use std::sync::Arc;
+
+use crate::error::ResourceError;
+use crate::zstd::decompress_zstd_capped;
+
+const AFF_MAX_BYTES: usize = 4 * 1024 * 1024;
+const DIC_MAX_BYTES: usize = 32 * 1024 * 1024;
+
+type Flag = u32;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FlagFormat {
+    /// One character per flag, the Hunspell default (`FLAG UTF-8` too).
+    Char,
+    /// Two characters per flag (`FLAG long`).
+    Long,
+    /// Comma-separated decimal numbers (`FLAG num`).
+    Num,
+}
+
+impl FlagFormat {
+    fn parse(self, flags: &str) -> Vec<Flag> {
+        match self {
+            FlagFormat::Char => flags.chars().map(|c| c as Flag).collect(),
+            FlagFormat::Long => flags
+                .chars()
+                .collect::<Vec<_>>()
+                .chunks(2)
+                .map(|pair| {
+                    pair.iter()
+                        .fold(0, |acc, &c| (acc << 16) | (c as Flag & 0xffff))
+                })
+                .collect(),
+            FlagFormat::Num => flags
+                .split(',')
+                .filter_map(|n| n.trim().parse().ok())
+                .collect(),
+        }
+    }
+
+    fn parse_one(self, flag: &str) -> Option<Flag> {
+        self.parse(flag).first().copied()
+    }
+}
+
+#[derive(Debug)]
+enum CondUnit {
+    Any,
+    Char(char),
+    Set { negated: bool, chars: Box<[char]> },
+}
+
+impl CondUnit {
+    fn matches(&self, c: char) -> bool {
+        match self {
+            CondUnit::Any => true,
+            CondUnit::Char(expected) => c == *expected,
+            CondUnit::Set { negated, chars } => chars.contains(&c) != *negated,
+        }
+    }
+}
+
+fn parse_condition(condition: &str) -> Box<[CondUnit]> {
+    let mut units = Vec::new();
+    let mut chars = condition.chars();
+    while let Some(c) = chars.next() {
+        units.push(match c {
+            '.' => CondUnit::Any,
+            '[' => {
+                let mut set: Vec<char> = chars.by_ref().take_while(|&c| c != ']').collect();
+                let negated = set.first() == Some(&'^');
+                if negated {
+                    set.remove(0);
+                }
+                CondUnit::Set {
+                    negated,
+                    chars: set.into_boxed_slice(),
+                }
+            }
+            c => CondUnit::Char(c),
+        });
+    }
+    if units.len() == 1 && matches!(units[0], CondUnit::Any) {
+        units.clear();
+    }
+    units.into_boxed_slice()
+}
+
+/// One `PFX`/`SFX` rule: strip `strip` from the stem, then add `append`,
+/// provided the stem's edge matches `condition`.
+#[derive(Debug)]
+struct AffixRule {
+    flag: Flag,
+    cross_product: bool,
+    strip: Box<str>,
+    append: Box<str>,
+    condition: Box<[CondUnit]>,
+}
+
+impl AffixRule {
+    fn matches_suffix_stem(&self, stem: &str) -> bool {
+        let mut tail = stem.chars().rev();
+        self.condition
+            .iter()
+            .rev()
+            .all(|unit| tail.next().is_some_and(|c| unit.matches(c)))
+    }
+
+    fn matches_prefix_stem(&self, stem: &str) -> bool {
+        let mut head = stem.chars();
+        self.condition
+            .iter()
+            .all(|unit| head.next().is_some_and(|c| unit.matches(c)))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Casing {
+    Lower,
+    Initial,
+    All,
+    Mixed,
+}
+
+fn casing(word: &str) -> Casing {
+    let mut letters = word.chars().filter(|c| c.is_alphabetic());
+    let Some(first) = letters.next() else {
+        return Casing::Lower;
+    };
+    let (mut upper, mut lower) = (0usize, 0usize);
+    for c in letters {
+        if c.is_uppercase() {
+            upper += 1;
+        } else if c.is_lowercase() {
+            lower += 1;
+        }
+    }
+    match (first.is_uppercase(), upper, lower) {
+        (false, 0, _) => Casing::Lower,
+        (true, 0, _) => Casing::Initial,
+        (true, _, 0) => Casing::All,
+        _ => Casing::Mixed,
+    }
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    chars
+        .next()
+        .map(|first| {
+            first
+                .to_uppercase()
+                .chain(chars.flat_map(char::to_lowercase))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Lookup {
+    Found,
+    Forbidden,
+    Missing,
+}
+
+/// A Hunspell dictionary: the `.dic` word list with the `.aff` prefix and
+/// suffix rules, `TRY`/`REP` suggestion hints and the `FORBIDDENWORD`,
+/// `NOSUGGEST`, `NEEDAFFIX` and `KEEPCASE` flags. Compounding and
+/// morphological fields are not supported.
+#[derive(Debug, Default)]
+pub struct Spellchecker {
+    /// Stem → the flag sets of each homonym.
+    words: HashMap<Box<str>, Vec<Box<[Flag]>>>,
+    prefixes: Vec<AffixRule>,
+    suffixes: Vec<AffixRule>,
+    /// Affix text → indices into `prefixes`/`suffixes` adding it.
+    prefix_index: HashMap<Box<str>, Vec<usize>>,
+    suffix_index: HashMap<Box<str>, Vec<usize>>,
+    /// Longest affix, in chars.
+    max_affix_len: usize,
+    forbidden: Option<Flag>,
+    no_suggest: Option<Flag>,
+    need_affix: Option<Flag>,
+    keep_case: Option<Flag>,
+    /// Characters tried by insertion and replacement suggestions.
+    try_chars: Box<[char]>,
+    replacements: Vec<(Box<str>, Box<str>)>,
+    /// Lowercase letters the dictionary spells with. Words using any other
+    /// letter belong to another language and are not checked.
+    alphabet: HashSet<char>,
+}
+
+impl Spellchecker {
+    pub fn parse(aff: &str, dic: &str) -> Self {
+        let mut checker = Self::default();
+        let flags = checker.parse_aff(aff);
+        checker.parse_dic(dic, flags);
+        checker.index_affixes();
+        if checker.try_chars.is_empty() {
+            let mut letters: Vec<char> = checker.alphabet.iter().copied().collect();
+            letters.sort_unstable();
+            checker.try_chars = letters.into_boxed_slice();
+        }
+        checker
+    }
+
+    fn parse_aff(&mut self, aff: &str) -> FlagFormat {
+        let mut format = FlagFormat::Char;
+        // Rules still expected under the last `PFX`/`SFX` header.
+        let mut pending = 0usize;
+        let mut cross_product = false;
+        for line in aff.lines() {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let Some(&directive) = fields.first() else {
+                continue;
+            };
+            match (directive, &fields[1..]) {
+                ("FLAG", [kind, ..]) => {
+                    format = match *kind {
+                        "long" => FlagFormat::Long,
+                        "num" => FlagFormat::Num,
+                        _ => FlagFormat::Char,
+                    }
+                }
+                ("TRY", [chars, ..]) => {
+                    self.try_chars = chars.chars().collect();
+                    self.alphabet
+                        .extend(chars.chars().flat_map(char::to_lowercase));
+                }
+                ("REP", [from, to, ..]) => self
+                    .replacements
+                    .push((rep_pattern(from).into(), rep_pattern(to).into())),
+                ("FORBIDDENWORD", [flag, ..]) => self.forbidden = format.parse_one(flag),
+                ("NOSUGGEST", [flag, ..]) => self.no_suggest = format.parse_one(flag),
+                ("NEEDAFFIX" | "PSEUDOROOT", [flag, ..]) => {
+                    self.need_affix = format.parse_one(flag)
+                }
+                ("KEEPCASE", [flag, ..]) => self.keep_case = format.parse_one(flag),
+                ("PFX" | "SFX", [flag, strip, append, rest @ ..]) => {
+                    if pending == 0 {
+                        cross_product = *strip == "Y";
+                        pending = append.parse().unwrap_or(0);
+                        continue;
+                    }
+                    pending -= 1;
+                    let Some(flag) = format.parse_one(flag) else {
+                        continue;
+                    };
+                    let append = append.split('/').next().unwrap_or_default();
+                    let rule = AffixRule {
+                        flag,
+                        cross_product,
+                        strip: affix_text(strip).into(),
+                        append: affix_text(append).into(),
+                        condition: parse_condition(rest.first().copied().unwrap_or(".")),
+                    };
+                    self.alphabet
+                        .extend(rule.append.chars().flat_map(char::to_lowercase));
+                    if directive == "PFX" {
+                        self.prefixes.push(rule);
+                    } else {
+                        self.suffixes.push(rule);
+                    }
+                }
+                _ => {}
+            }
+        }
+        format
+    }
+
+    fn parse_dic(&mut self, dic: &str, format: FlagFormat) {
+        let mut lines = dic.lines().peekable();
+        if lines
+            .peek()
+            .is_some_and(|first| first.trim().parse::<usize>().is_ok())
+        {
+            lines.next();
+        }
+        for line in lines {
+            let Some(entry) = line.split(['\t', ' ']).next().filter(|e| !e.is_empty()) else {
+                continue;
+            };
+            let (word, flags) = match entry.split_once('/') {
+                Some((word, flags)) if !word.is_empty() => (word, format.parse(flags)),
+                _ => (entry, Vec::new()),
+            };
+            self.alphabet.extend(
+                word.chars()
+                    .filter(|c| c.is_alphabetic())
+                    .flat_map(char::to_lowercase),
+            );
+            self.words
+                .entry(word.into())
+                .or_default()
+                .push(flags.into_boxed_slice());
+        }
+    }
+
+    fn index_affixes(&mut self) {
+        for (i, rule) in self.prefixes.iter().enumerate() {
+            self.prefix_index
+                .entry(rule.append.clone())
+                .or_default()
+                .push(i);
+            self.max_affix_len = self.max_affix_len.max(rule.append.chars().count());
+        }
+        for (i, rule) in self.suffixes.iter().enumerate() {
+            self.suffix_index
+                .entry(rule.append.clone())
+                .or_default()
+                .push(i);
+            self.max_affix_len = self.max_affix_len.max(rule.append.chars().count());
+        }
+    }
+
+    /// Whether `word` is spelled correctly. Words with digits or with letters
+    /// outside the dictionary's alphabet are accepted unchecked.
+    pub fn check(&self, word: &str) -> bool {
+        let word = word.replace('\u{2019}', "'");
+        if !self.is_checkable(&word) {
+            return true;
+        }
+        match self.lookup(&word, false) {
+            Lookup::Found => return true,
+            Lookup::Forbidden => return false,
+            Lookup::Missing => {}
+        }
+        match casing(&word) {
+            Casing::Initial => self.lookup(&word.to_lowercase(), true) == Lookup::Found,
+            Casing::All => {
+                self.lookup(&word.to_lowercase(), true) == Lookup::Found
+                    || self.lookup(&capitalize(&word), true) == Lookup::Found
+            }
+            Casing::Lower | Casing::Mixed => false,
+        }
+    }
+
+    fn is_checkable(&self, word: &str) -> bool {
+        !word.chars().any(char::is_numeric)
+            && word
+                .chars()
+                .filter(|c| c.is_alphabetic())
+                .flat_map(char::to_lowercase)
+                .all(|c| self.alphabet.contains(&c))
+    }
+
+    fn lookup(&self, word: &str, case_folded: bool) -> Lookup {
+        if let Some(homonyms) = self.words.get(word) {
+            if homonyms.iter().any(|flags| self.has(flags, self.forbidden)) {
+                return Lookup::Forbidden;
+            }
+            if homonyms.iter().any(|flags| {
+                !self.has(flags, self.need_affix)
+                    && !(case_folded && self.has(flags, self.keep_case))
+            }) {
+                return Lookup::Found;
+            }
+        }
+        if self.suffixed(word, case_folded, None) || self.prefixed(word, case_folded) {
+            Lookup::Found
+        } else {
+            Lookup::Missing
+        }
+    }
+
+    fn has(&self, flags: &[Flag], flag: Option<Flag>) -> bool {
+        flag.is_some_and(|flag| flags.contains(&flag))
+    }
+
+    /// Whether `stem` is a dictionary word carrying `flag` (and `also`, for a
+    /// prefix combined with a suffix).
+    fn stem_has(&self, stem: &str, flag: Flag, also: Option<Flag>, case_folded: bool) -> bool {
+        self.words.get(stem).is_some_and(|homonyms| {
+            homonyms.iter().any(|flags| {
+                flags.contains(&flag)
+                    && also.is_none_or(|also| flags.contains(&also))
+                    && !self.has(flags, self.forbidden)
+                    && !(case_folded && self.has(flags, self.keep_case))
+            })
+        })
+    }
+
+    /// Byte offsets splitting `word` into an edge of at most `max_affix_len`
+    /// chars and a non-empty remainder, from the shortest edge.
+    fn split_points(&self, word: &str, from_end: bool) -> Vec<usize> {
+        let mut points: Vec<usize> = word
+            .char_indices()
+            .map(|(i, _)| i)
+            .chain(std::iter::once(word.len()))
+            .collect();
+        if from_end {
+            points.reverse();
+            points.retain(|&i| i > 0);
+        } else {
+            points.retain(|&i| i < word.len());
+        }
+        points.truncate(self.max_affix_len + 1);
+        points
+    }
+
+    fn suffixed(&self, word: &str, case_folded: bool, prefix: Option<&AffixRule>) -> bool {
+        self.split_points(word, true).into_iter().any(|i| {
+            let (base, tail) = word.split_at(i);
+            self.suffix_index.get(tail).is_some_and(|rules| {
+                rules.iter().map(|&r| &self.suffixes[r]).any(|rule| {
+                    if prefix.is_some() && !rule.cross_product {
+                        return false;
+                    }
+                    let stem = format!("{base}{}", rule.strip);
+                    rule.matches_suffix_stem(&stem)
+                        && self.stem_has(&stem, rule.flag, prefix.map(|p| p.flag), case_folded)
+                })
+            })
+        })
+    }
+
+    fn prefixed(&self, word: &str, case_folded: bool) -> bool {
+        self.split_points(word, false).into_iter().any(|i| {
+            let (head, rest) = word.split_at(i);
+            self.prefix_index.get(head).is_some_and(|rules| {
+                rules.iter().map(|&r| &self.prefixes[r]).any(|rule| {
+                    let stem = format!("{}{rest}", rule.strip);
+                    rule.matches_prefix_stem(&stem)
+                        && (self.stem_has(&stem, rule.flag, None, case_folded)
+                            || (rule.cross_product
+                                && self.suffixed(&stem, case_folded, Some(rule))))
+                })
+            })
+        })
+    }
+
+    /// Char ranges of the misspelled words in `text`, split into words by
+    /// `segmenter`.
+    pub fn misspellings(&self, text: &str, segmenter: &WordSegmenter) -> Vec<Range<usize>> {
+        let mut out = Vec::new();
+        let segmenter = segmenter.as_borrowed();
+        let mut segments = segmenter.segment_str(text);
+        let mut start = segments.next().unwrap_or_default();
+        let mut char_start = 0;
+        while let Some(end) = segments.next() {
+            let word = &text[start..end];
+            let len = word.chars().count();
+            if segments.is_word_like() && !self.check(word) {
+                out.push(char_start..char_start + len);
+            }
+            start = end;
+            char_start += len;
+        }
+        out
+    }
+
+    /// Up to `limit` corrections for `word`, best first: `REP` table hits,
+    /// then single-character edits, then splits into two words.
+    pub fn suggest(&self, word: &str, limit: usize) -> Vec<String> {
+        let word = word.replace('\u{2019}', "'");
+        let case = casing(&word);
+        let lower = if matches!(case, Casing::Initial | Casing::All) {
+            word.to_lowercase()
+        } else {
+            word.clone()
+        };
+
+        let mut candidates: Vec<String> = vec![lower.clone(), capitalize(&lower)];
+        for (from, to) in &self.replacements {
+            for (i, _) in lower.match_indices(&**from) {
+                candidates.push(format!("{}{to}{}", &lower[..i], &lower[i + from.len()..]));
+            }
+        }
+        let chars: Vec<char> = lower.chars().collect();
+        let spell = |chars: &[char]| chars.iter().collect::<String>();
+        for i in 1..chars.len() {
+            let mut swapped = chars.clone();
+            swapped.swap(i - 1, i);
+            candidates.push(spell(&swapped));
+        }
+        for i in 0..chars.len() {
+            let mut replaced = chars.clone();
+            for &t in self.try_chars.iter().filter(|&&t| t != chars[i]) {
+                replaced[i] = t;
+                candidates.push(spell(&replaced));
+            }
+        }
+        for i in 0..chars.len() {
+            let mut deleted = chars.clone();
+            deleted.remove(i);
+            candidates.push(spell(&deleted));
+        }
+        for i in 0..=chars.len() {
+            for &t in self.try_chars.iter() {
+                let mut inserted = chars.clone();
+                inserted.insert(i, t);
+                candidates.push(spell(&inserted));
+            }
+        }
+        for i in 1..chars.len() {
+            candidates.push(format!("{} {}", spell(&chars[..i]), spell(&chars[i..])));
+        }
+
+        let mut out: Vec<String> = Vec::new();
+        for candidate in candidates {
+            if out.len() == limit {
+                break;
+            }
+            if candidate == word || !candidate.split(' ').all(|w| self.suggestable(w)) {
+                continue;
+            }
+            let candidate = match case {
+                Casing::Initial => capitalize(&candidate),
+                Casing::All => candidate.to_uppercase(),
+                Casing::Lower | Casing::Mixed => candidate,
+            };
+            if !out.contains(&candidate) {
+                out.push(candidate);
+            }
+        }
+        out
+    }
+
+    fn suggestable(&self, word: &str) -> bool {
+        if word.is_empty() {
+            return false;
+        }
+        let no_suggest = self.words.get(word).is_some_and(|homonyms| {
+            homonyms
+                .iter()
+                .all(|flags| self.has(flags, self.no_suggest))
+        });
+        !no_suggest && self.is_checkable(word) && self.check(word)
+    }
+}
+
+/// `0` stands for an empty strip or append.
+fn affix_text(text: &str) -> &str {
+    if text == "0" { "" } else { text }
+}
+
+/// `REP` patterns spell spaces as `_`; anchors are ignored.
+fn rep_pattern(pattern: &str) -> String {
+    pattern
+        .trim_start_matches('^')
+        .trim_end_matches('$')
+        .replace('_', " ")
+}
+
+/// Decodes `.aff`/`.dic` bytes in the encoding the `.aff` names with `SET`.
+fn decode(bytes: Vec<u8>, encoding: &str) -> Result<String, ResourceError> {
+    match encoding.to_ascii_uppercase().as_str() {
+        "UTF-8" | "UTF8" => {
+            String::from_utf8(bytes).map_err(|e| ResourceError::InvalidSpellcheck(e.to_string()))
+        }
+        "ISO8859-1" | "ISO-8859-1" | "LATIN1" => Ok(bytes.into_iter().map(char::from).collect()),
+        other => Err(ResourceError::InvalidSpellcheck(format!(
+            "unsupported encoding: {other}"
+        ))),
+    }
+}
+
+pub struct PreparedSpellchecker {
+    pub(crate) spellchecker: Arc<Spellchecker>,
+}
+
+/// Decompresses and parses zstd-compressed Hunspell `.aff` and `.dic` files,
+/// shipped the same way as hyphenation patterns.
+pub fn prepare_spellchecker(aff: &[u8], dic: &[u8]) -> Result<PreparedSpellchecker, ResourceError> {
+    let aff = decompress_zstd_capped(aff, AFF_MAX_BYTES)?;
+    let dic = decompress_zstd_capped(dic, DIC_MAX_BYTES)?;
+    // `SET` is plain ASCII, so it can be read before the encoding is known.
+    let encoding = aff
+        .split(|&b| b == b'\n')
+        .find_map(|line| {
+            let line = std::str::from_utf8(line).ok()?;
+            line.trim()
+                .strip_prefix("SET ")
+                .map(|e| e.trim().to_owned())
+        })
+        .unwrap_or_else(|| "UTF-8".to_owned());
+    let aff = decode(aff, &encoding)?;
+    let dic = decode(dic, &encoding)?;
+    Ok(PreparedSpellchecker {
+        spellchecker: Arc::new(Spellchecker::parse(&aff, &dic)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::segmentation::TextSegmenters;
+    use crate::zstd::compress_zstd;
+
+    const AFF: &str = "\
+SET UTF-8
+TRY esianrtolcdugmphbyfvkwzESIANRTOLCDUGMPHBYFVKWZ'
+REP 1
+REP f ph
+NOSUGGEST !
+FORBIDDENWORD X
+KEEPCASE K
+
+PFX U Y 1
+PFX U   0     un         .
+
+SFX S Y 3
+SFX S   y     ies        [^aeiou]y
+SFX S   0     s          [aeiou]y
+SFX S   0     s          [^y]
+
+SFX D Y 2
+SFX D   0     ed         [^e]
+SFX D   0     d          e
+";
+
+    const DIC: &str = "\
+9
+hello
+world/S
+city/S
+play/SDU
+bake/D
+happy/U
+graph/S
+damn/!
+NASA/K
+";
+
+    fn checker() -> Spellchecker {
+        Spellchecker::parse(AFF, DIC)
+    }
+
+    #[test]
+    fn accepts_stems_and_affixed_forms() {
+        let checker = checker();
+        for word in [
+            "hello", "worlds", "cities", "plays", "played", "baked", "unhappy", "unplays", "graphs",
+        ] {
+            assert!(checker.check(word), "{word}");
+        }
+        for word in ["helo", "citys", "bakeed", "unhello", "unbaked", "wordls"] {
+            assert!(!checker.check(word), "{word}");
+        }
+    }
+
+    #[test]
+    fn folds_sentence_and_all_caps_case_but_keeps_keepcase_words() {
+        let checker = checker();
+        assert!(checker.check("Hello"));
+        assert!(checker.check("HELLO"));
+        assert!(!checker.check("hELLO"));
+        assert!(checker.check("NASA"));
+        assert!(!checker.check("nasa"));
+        assert!(!checker.check("Nasa"));
+    }
+
+    #[test]
+    fn skips_words_outside_the_dictionary_alphabet() {
+        let checker = checker();
+        assert!(checker.check("안녕하세요"));
+        assert!(checker.check("h3llo"));
+        assert!(checker.check("hellq"), "q is in neither the words nor TRY");
+        assert!(!checker.check("hellz"));
+    }
+
+    #[test]
+    fn misspellings_reports_char_ranges_of_word_segments() {
+        let checker = checker();
+        let segmenters = TextSegmenters::new_test();
+        let text = "Hello — helo wrld, 안녕 cities!";
+        let ranges = checker.misspellings(text, &segmenters.word);
+        let words: Vec<String> = ranges
+            .iter()
+            .map(|r| text.chars().skip(r.start).take(r.len()).collect())
+            .collect();
+        assert_eq!(words, vec!["helo", "wrld"]);
+    }
+
+    #[test]
+    fn suggest_ranks_rep_and_edits_and_preserves_case() {
+        let checker = checker();
+        assert_eq!(checker.suggest("helo", 3), vec!["hello"]);
+        assert_eq!(checker.suggest("Wrold", 1), vec!["World"]);
+        assert_eq!(checker.suggest("grafs", 1), vec!["graphs"]);
+        assert!(
+            checker
+                .suggest("helloworld", 5)
+                .contains(&"hello world".to_owned())
+        );
+        assert!(!checker.suggest("damm", 5).contains(&"damn".to_owned()));
+    }
+
+    #[test]
+    fn forbidden_words_are_rejected_even_when_affixes_derive_them() {
+        let checker = Spellchecker::parse(AFF, "3\nplay/S\nplays/X\nhello\n");
+        assert!(checker.check("play"));
+        assert!(!checker.check("plays"));
+    }
+
+    #[test]
+    fn prepare_spellchecker_decodes_latin1_and_rejects_bad_input() {
+        let aff = compress_zstd(b"SET ISO8859-1\n");
+        let dic = compress_zstd(b"1\ncaf\xe9\n");
+        let prepared = prepare_spellchecker(&aff, &dic).unwrap();
+        assert!(prepared.spellchecker.check("café"));
+
+        assert!(prepare_spellchecker(b"not zstd", &dic).is_err());
+        let koi8 = compress_zstd(b"SET KOI8-R\n");
+        assert!(prepare_spellchecker(&koi8, &dic).is_err());
+    }
+}
@@ -0,0 +1,102 @@
+use crate::segmentation::TextSegmenters;
+
+/// Cells on one line of 200-cell 원고지 manuscript paper.
+pub const MANUSCRIPT_COLUMNS: u32 = 20;
+/// Lines on one page of 200-cell 원고지 manuscript paper.
+pub const MANUSCRIPT_ROWS: u32 = 10;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TextStatistics {
+    pub words: u32,
+    pub sentences: u32,
+    /// Manuscript-paper lines the text fills written as one paragraph: an
+    /// indent cell, then one cell per grapheme.
+    pub manuscript_lines: u32,
+}
+
+impl TextStatistics {
+    pub fn is_empty(&self) -> bool {
+        self.words == 0 && self.sentences == 0 && self.manuscript_lines == 0
+    }
+}
+
+/// Word, sentence and manuscript-line tallies for one paragraph of text.
+/// Whitespace-only text counts as nothing rather than as a blank paragraph.
+pub fn count_statistics(text: &str, segmenters: &TextSegmenters) -> TextStatistics {
+    let stripped;
+    let text = if text.contains('\u{200B}') {
+        stripped = text.replace('\u{200B}', "");
+        stripped.as_str()
+    } else {
+        text
+    };
+    let text = text.trim_end();
+    if text.trim_start().is_empty() {
+        return TextStatistics::default();
+    }
+
+    let word = segmenters.word.as_borrowed();
+    let mut segments = word.segment_str(text);
+    segments.next();
+    let mut words: u32 = 0;
+    while segments.next().is_some() {
+        if segments.is_word_like() {
+            words += 1;
+        }
+    }
+
+    let mut sentences: u32 = 0;
+    let mut prev = 0usize;
+    for end in segmenters.sentence.as_borrowed().segment_str(text).skip(1) {
+        if !text[prev..end].trim().is_empty() {
+            sentences += 1;
+        }
+        prev = end;
+    }
+
+    let graphemes = segmenters.grapheme.as_borrowed().segment_str(text).count() as u32 - 1;
+    TextStatistics {
+        words,
+        sentences,
+        manuscript_lines: (1 + graphemes).div_ceil(MANUSCRIPT_COLUMNS),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn count(text: &str) -> (u32, u32, u32) {
+        let s = count_statistics(text, &TextSegmenters::new_test());
+        (s.words, s.sentences, s.manuscript_lines)
+    }
+
+    #[test]
+    fn blank_text_is_all_zero() {
+        assert_eq!(count(""), (0, 0, 0));
+        assert_eq!(count("  \t"), (0, 0, 0));
+        assert_eq!(count("\u{200B}"), (0, 0, 0));
+    }
+
+    #[test]
+    fn words_skip_spaces_and_punctuation() {
+        assert_eq!(count("Hello, world!").0, 2);
+        assert_eq!(count("it's a well-known fact").0, 5);
+        assert_eq!(count("안녕하세요 반갑습니다.").0, 2);
+    }
+
+    #[test]
+    fn sentences_ignore_trailing_whitespace() {
+        assert_eq!(count("One. Two? Three!").1, 3);
+        assert_eq!(count("No terminator").1, 1);
+        assert_eq!(count("첫 문장입니다. 둘째 문장입니다.  ").1, 2);
+    }
+
+    #[test]
+    fn manuscript_lines_include_the_indent_cell() {
+        assert_eq!(count("가나다").2, 1);
+        assert_eq!(count(&"가".repeat(19)).2, 1);
+        assert_eq!(count(&"가".repeat(20)).2, 2);
+        assert_eq!(count("👍🏽 ok").2, 1);
+    }
+}
@@ -1,50 +1,144 @@
-use crate::theme_data::ThemeVariant;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
 use editor_common::Color;
+use editor_macros::ffi;
+use serde::{Deserialize, Serialize};
+
+use crate::error::ResourceError;
+use crate::theme_data::ThemeVariant;
+
+/// A host-supplied theme, typically the web app's design tokens.
+#[ffi]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThemeDefinition {
+    /// Built-in variant supplying every token `colors` leaves out. Without
+    /// one, `colors` must cover every token in [`theme_tokens`].
+    pub base: Option<ThemeVariant>,
+    pub colors: Vec<ThemeColor>,
+}
+
+#[ffi]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThemeColor {
+    pub token: String,
+    /// `#rrggbb` or `#rrggbbaa`.
+    pub value: String,
+}
+
+pub struct PreparedTheme {
+    pub(crate) theme: Theme,
+}
+
+/// Resolves `definition` against its base variant, rejecting tokens the
+/// renderer does not know, malformed colors and, without a base, gaps.
+pub fn prepare_theme(definition: ThemeDefinition) -> Result<PreparedTheme, ResourceError> {
+    let mut colors: BTreeMap<&'static str, Color> = match definition.base {
+        Some(base) => base.colors().entries().map(|(k, v)| (*k, *v)).collect(),
+        None => BTreeMap::new(),
+    };
+    for ThemeColor { token, value } in definition.colors {
+        let (token, _) = ThemeVariant::LightWhite
+            .colors()
+            .get_entry(token.as_str())
+            .ok_or_else(|| ResourceError::InvalidTheme(format!("unknown token: {token}")))?;
+        let color = parse_hex_color(&value).ok_or_else(|| {
+            ResourceError::InvalidTheme(format!("invalid color for {token}: {value}"))
+        })?;
+        colors.insert(*token, color);
+    }
+    if let Some(missing) = theme_tokens().find(|t| !colors.contains_key(t)) {
+        return Err(ResourceError::InvalidTheme(format!(
+            "missing token without a base variant: {missing}"
+        )));
+    }
+    Ok(PreparedTheme {
+        theme: Theme {
+            palette: Palette::Custom(Arc::new(colors)),
+        },
+    })
+}
+
+/// Every token the renderer reads. All built-in variants define the same set.
+pub fn theme_tokens() -> impl Iterator<Item = &'static str> {
+    ThemeVariant::LightWhite.colors().keys().copied()
+}
+
+fn parse_hex_color(value: &str) -> Option<Color> {
+    let hex = value.strip_prefix('#')?;
+    if !hex.is_ascii() || !matches!(hex.len(), 6 | 8) {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    let a = if hex.len() == 8 { channel(6)? } else { 255 };
+    Some(Color::new(channel(0)?, channel(2)?, channel(4)?, a))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Palette {
+    Builtin(ThemeVariant),
+    Custom(Arc<BTreeMap<&'static str, Color>>),
+}
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Theme {
-    variant: ThemeVariant,
-    colors: &'static phf::Map<&'static str, Color>,
+    palette: Palette,
 }
 
 impl Theme {
     pub fn new(variant: ThemeVariant) -> Self {
         Self {
-            variant,
-            colors: variant.colors(),
+            palette: Palette::Builtin(variant),
         }
     }
 
-    pub fn variant(&self) -> ThemeVariant {
-        self.variant
+    /// The built-in variant, or `None` for a custom theme.
+    pub fn variant(&self) -> Option<ThemeVariant> {
+        match self.palette {
+            Palette::Builtin(variant) => Some(variant),
+            Palette::Custom(_) => None,
+        }
     }
 
     pub fn color(&self, token: &str) -> Color {
-        self.colors.get(token).copied().unwrap_or(Color::BLACK)
+        self.try_color(token).unwrap_or(Color::BLACK)
     }
 
     pub fn try_color(&self, token: &str) -> Option<Color> {
-        self.colors.get(token).copied()
+        match &self.palette {
+            Palette::Builtin(variant) => variant.colors().get(token).copied(),
+            Palette::Custom(colors) => colors.get(token).copied(),
+        }
     }
 
     pub fn color_with_alpha(&self, token: &str, alpha: u8) -> Color {
         self.color(token).with_alpha(alpha)
     }
 
+    fn entries(&self) -> Box<dyn Iterator<Item = (&'static str, Color)> + '_> {
+        match &self.palette {
+            Palette::Builtin(variant) => {
+                Box::new(variant.colors().entries().map(|(k, v)| (*k, *v)))
+            }
+            Palette::Custom(colors) => Box::new(colors.iter().map(|(k, v)| (*k, *v))),
+        }
+    }
+
     pub fn text_paste_palette(&self) -> impl Iterator<Item = (&'static str, Color)> + '_ {
         const DENY: &[&str] = &["bright"];
-        self.colors.entries().filter_map(|(token, color)| {
+        self.entries().filter_map(|(token, color)| {
             token
                 .strip_prefix("text.")
                 .filter(|suffix| !DENY.contains(suffix))
-                .map(|suffix| (suffix, *color))
+                .map(|suffix| (suffix, color))
         })
     }
 
     pub fn bg_paste_palette(&self) -> impl Iterator<Item = (&'static str, Color)> + '_ {
-        self.colors
-            .entries()
-            .filter_map(|(token, color)| token.strip_prefix("bg.").map(|suffix| (suffix, *color)))
+        self.entries()
+            .filter_map(|(token, color)| token.strip_prefix("bg.").map(|suffix| (suffix, color)))
     }
 }
 
@@ -55,7 +149,7 @@ mod tests {
     #[test]
     fn new_initializes_with_given_variant() {
         let theme = Theme::new(ThemeVariant::LightWhite);
-        assert_eq!(theme.variant(), ThemeVariant::LightWhite);
+        assert_eq!(theme.variant(), Some(ThemeVariant::LightWhite));
     }
 
     #[test]
@@ -140,4 +234,80 @@ mod tests {
             w
         );
     }
+
+    fn definition(base: Option<ThemeVariant>, colors: &[(&str, &str)]) -> ThemeDefinition {
+        ThemeDefinition {
+            base,
+            colors: colors
+                .iter()
+                .map(|(token, value)| ThemeColor {
+                    token: (*token).into(),
+                    value: (*value).into(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn every_variant_defines_the_same_tokens() {
+        let tokens: Vec<&str> = theme_tokens().collect();
+        for variant in [ThemeVariant::LightWhite, ThemeVariant::DarkBlack] {
+            let mut keys: Vec<&str> = variant.colors().keys().copied().collect();
+            let mut expected = tokens.clone();
+            keys.sort_unstable();
+            expected.sort_unstable();
+            assert_eq!(keys, expected, "{variant:?}");
+        }
+    }
+
+    #[test]
+    fn prepare_theme_overrides_base_variant_tokens() {
+        let prepared = prepare_theme(definition(
+            Some(ThemeVariant::LightWhite),
+            &[
+                ("ui.accent.brand.default", "#704214"),
+                ("bg.red", "#ff000080"),
+            ],
+        ))
+        .unwrap();
+        let theme = prepared.theme;
+        let base = Theme::new(ThemeVariant::LightWhite);
+        assert_eq!(theme.variant(), None);
+        assert_eq!(
+            theme.color("ui.accent.brand.default"),
+            Color::rgb(0x70, 0x42, 0x14)
+        );
+        assert_eq!(theme.color("bg.red"), Color::new(255, 0, 0, 0x80));
+        assert_eq!(
+            theme.color("ui.text.default"),
+            base.color("ui.text.default")
+        );
+        assert_eq!(
+            theme.bg_paste_palette().count(),
+            base.bg_paste_palette().count()
+        );
+    }
+
+    #[test]
+    fn prepare_theme_rejects_unknown_tokens_and_bad_colors() {
+        let unknown = definition(Some(ThemeVariant::LightWhite), &[("ui.nope", "#000000")]);
+        assert!(prepare_theme(unknown).is_err());
+        for value in ["000000", "#00000", "#gg0000", "#0000000000"] {
+            let bad = definition(
+                Some(ThemeVariant::LightWhite),
+                &[("ui.text.default", value)],
+            );
+            assert!(prepare_theme(bad).is_err(), "{value}");
+        }
+    }
+
+    #[test]
+    fn prepare_theme_without_base_requires_every_token() {
+        let partial = definition(None, &[("ui.text.default", "#111111")]);
+        assert!(prepare_theme(partial).is_err());
+
+        let tokens: Vec<(&str, &str)> = theme_tokens().map(|t| (t, "#223344")).collect();
+        let theme = prepare_theme(definition(None, &tokens)).unwrap().theme;
+        assert_eq!(theme.color("ui.text.default"), Color::rgb(0x22, 0x33, 0x44));
+    }
 }
@@ -0,0 +1,669 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+
+use editor_clipboard::html::serialize::html_escape;
+use editor_model::{
+    Alignment, DEFAULT_FONT_FAMILY, DEFAULT_FONT_SIZE, DEFAULT_FONT_WEIGHT, Modifier, ModifierType,
+    PlainNode, PlainNodeEntry, TableBorderStyle, TextDirection,
+};
+use editor_resource::{Resource, find_bold_target};
+
+pub(super) type Modifiers = BTreeMap<ModifierType, Modifier>;
+
+/// The characters each `(family, weight)` face has to cover.
+pub(super) type UsedText = BTreeMap<(String, u16), BTreeSet<char>>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum ListKind {
+    Bullet,
+    Ordered,
+}
+
+/// One `w:num` instance. Every list gets its own so ordered lists restart.
+pub(super) struct List {
+    pub kind: ListKind,
+    pub level: u8,
+}
+
+/// `word/document.xml`'s body content, without the section properties, and
+/// what the other parts need to reference from it.
+pub(super) struct Body {
+    pub xml: String,
+    /// External link targets; the `n`th is relationship `rIdLink{n}`.
+    pub links: Vec<String>,
+    /// The `n`th list is `w:numId` `n + 1`.
+    pub lists: Vec<List>,
+    pub used: UsedText,
+}
+
+/// Writes the root's children. `defaults` are the modifiers the styles part
+/// declares as document defaults; only differences from them are written.
+pub(super) fn body(
+    root: &PlainNodeEntry,
+    defaults: &Modifiers,
+    content_width: u32,
+    language: &str,
+    resource: &Resource,
+) -> Body {
+    let mut writer = BodyWriter {
+        resource,
+        defaults,
+        content_width,
+        language,
+        out: String::new(),
+        links: Vec::new(),
+        lists: Vec::new(),
+        used: UsedText::new(),
+    };
+    for child in &root.children {
+        writer.block(child, defaults, 0);
+    }
+    if writer.out.is_empty() {
+        writer.out.push_str("<w:p/>");
+    }
+    Body {
+        xml: writer.out,
+        links: writer.links,
+        lists: writer.lists,
+        used: writer.used,
+    }
+}
+
+struct BodyWriter<'a> {
+    resource: &'a Resource,
+    defaults: &'a Modifiers,
+    /// Twips available to a table at full proportion.
+    content_width: u32,
+    language: &'a str,
+    out: String,
+    links: Vec<String>,
+    lists: Vec<List>,
+    used: UsedText,
+}
+
+impl BodyWriter<'_> {
+    /// `depth` is the list nesting level a list at this position gets.
+    fn block(&mut self, entry: &PlainNodeEntry, inherited: &Modifiers, depth: u8) {
+        let modifiers = merged(inherited, &entry.modifiers);
+        match &entry.node {
+            PlainNode::Paragraph(_) => self.paragraph(entry, inherited, None),
+            PlainNode::BulletList(_) => self.list(entry, &modifiers, ListKind::Bullet, depth),
+            PlainNode::OrderedList(_) => self.list(entry, &modifiers, ListKind::Ordered, depth),
+            PlainNode::Table(_) => self.table(entry, &modifiers),
+            PlainNode::HorizontalRule(_) => self.out.push_str(
+                r#"<w:p><w:pPr><w:pBdr><w:bottom w:val="single" w:sz="6" w:space="1" w:color="auto"/></w:pBdr></w:pPr></w:p>"#,
+            ),
+            // Top-level breaks from older documents sit between paragraphs.
+            PlainNode::PageBreak(_) => self
+                .out
+                .push_str(r#"<w:p><w:r><w:br w:type="page"/></w:r></w:p>"#),
+            PlainNode::ColumnBreak(_) => self
+                .out
+                .push_str(r#"<w:p><w:r><w:br w:type="column"/></w:r></w:p>"#),
+            // WordprocessingML has no counterpart for these containers; their
+            // content is kept in order.
+            PlainNode::Root(_)
+            | PlainNode::Blockquote(_)
+            | PlainNode::Callout(_)
+            | PlainNode::Fold(_)
+            | PlainNode::FoldTitle(_)
+            | PlainNode::FoldContent(_)
+            | PlainNode::ListItem(_)
+            | PlainNode::TableRow(_)
+            | PlainNode::TableCell(_)
+            | PlainNode::Unknown => {
+                for child in &entry.children {
+                    self.block(child, &modifiers, depth);
+                }
+            }
+            // Media nodes only carry upload ids; their bytes are not part of the
+            // document.
+            PlainNode::Image(_)
+            | PlainNode::Embed(_)
+            | PlainNode::File(_)
+            | PlainNode::Archived(_)
+            | PlainNode::Text(_)
+            | PlainNode::HardBreak(_)
+            | PlainNode::Tab(_) => {}
+        }
+    }
+
+    /// A list's items become numbered paragraphs: the first paragraph of each
+    /// item carries the numbering, nested lists go one level deeper.
+    fn list(&mut self, entry: &PlainNodeEntry, inherited: &Modifiers, kind: ListKind, depth: u8) {
+        let level = depth.min(8);
+        self.lists.push(List { kind, level });
+        let num_id = self.lists.len() as u32;
+        for item in &entry.children {
+            let modifiers = merged(inherited, &item.modifiers);
+            let mut numbered = false;
+            for child in &item.children {
+                match &child.node {
+                    PlainNode::Paragraph(_) if !numbered => {
+                        numbered = true;
+                        self.paragraph(child, &modifiers, Some((num_id, level)));
+                    }
+                    _ => self.block(child, &modifiers, depth + 1),
+                }
+            }
+        }
+    }
+
+    fn paragraph(
+        &mut self,
+        entry: &PlainNodeEntry,
+        inherited: &Modifiers,
+        numbering: Option<(u32, u8)>,
+    ) {
+        let modifiers = merged(inherited, &entry.modifiers);
+        self.out.push_str("<w:p>");
+        let mut ppr = String::new();
+        if let Some((num_id, level)) = numbering {
+            let _ = write!(
+                ppr,
+                r#"<w:numPr><w:ilvl w:val="{level}"/><w:numId w:val="{num_id}"/></w:numPr>"#
+            );
+        }
+        ppr.push_str(&paragraph_properties(&modifiers, self.defaults));
+        if !entry.carry.is_empty() {
+            let carry = merged(
+                &modifiers,
+                &entry
+                    .carry
+                    .iter()
+                    .map(|m| (m.as_type(), m.clone()))
+                    .collect(),
+            );
+            ppr.push_str(&self.rpr(&carry));
+        }
+        if !ppr.is_empty() {
+            let _ = write!(self.out, "<w:pPr>{ppr}</w:pPr>");
+        }
+
+        let mut link: Option<String> = None;
+        for child in &entry.children {
+            let own = merged(&modifiers, &child.modifiers);
+            match &child.node {
+                PlainNode::Text(text) => {
+                    let href = match own.get(&ModifierType::Link) {
+                        Some(Modifier::Link { href }) => Some(href.clone()),
+                        _ => None,
+                    };
+                    if href != link {
+                        if link.is_some() {
+                            self.out.push_str("</w:hyperlink>");
+                        }
+                        if let Some(href) = &href {
+                            self.links.push(href.clone());
+                            let _ = write!(
+                                self.out,
+                                r#"<w:hyperlink r:id="rIdLink{}" w:history="1">"#,
+                                self.links.len()
+                            );
+                        }
+                        link = href;
+                    }
+                    self.text(&text.text, &own);
+                }
+                PlainNode::HardBreak(_) => self.out.push_str("<w:r><w:br/></w:r>"),
+                PlainNode::Tab(_) => self.out.push_str("<w:r><w:tab/></w:r>"),
+                PlainNode::PageBreak(_) => self.out.push_str(r#"<w:r><w:br w:type="page"/></w:r>"#),
+                PlainNode::ColumnBreak(_) => {
+                    self.out.push_str(r#"<w:r><w:br w:type="column"/></w:r>"#)
+                }
+                _ => {}
+            }
+        }
+        if link.is_some() {
+            self.out.push_str("</w:hyperlink>");
+        }
+        self.out.push_str("</w:p>");
+    }
+
+    fn text(&mut self, text: &str, modifiers: &Modifiers) {
+        let ruby = match modifiers.get(&ModifierType::Ruby) {
+            Some(Modifier::Ruby { text }) => Some(text.as_str()),
+            _ => None,
+        };
+        self.record(text, ruby, modifiers);
+
+        let rpr = self.rpr(modifiers);
+        let Some(ruby) = ruby else {
+            let _ = write!(
+                self.out,
+                r#"<w:r>{rpr}<w:t xml:space="preserve">{}</w:t></w:r>"#,
+                xml_text(text)
+            );
+            return;
+        };
+        let base = half_points(modifiers);
+        let annotation = (base / 2).max(1);
+        let mut small = modifiers.clone();
+        small.insert(
+            ModifierType::FontSize,
+            Modifier::FontSize {
+                value: annotation * 50,
+            },
+        );
+        let _ = write!(
+            self.out,
+            concat!(
+                r#"<w:r>{rpr}<w:ruby><w:rubyPr><w:rubyAlign w:val="center"/>"#,
+                r#"<w:hps w:val="{annotation}"/><w:hpsRaise w:val="{base}"/>"#,
+                r#"<w:hpsBaseText w:val="{base}"/><w:lid w:val="{lang}"/></w:rubyPr>"#,
+                r#"<w:rt><w:r>{small}<w:t xml:space="preserve">{ruby}</w:t></w:r></w:rt>"#,
+                r#"<w:rubyBase><w:r>{rpr}<w:t xml:space="preserve">{text}</w:t></w:r></w:rubyBase>"#,
+                "</w:ruby></w:r>"
+            ),
+            rpr = rpr,
+            annotation = annotation,
+            base = base,
+            lang = html_escape(self.language),
+            small = self.rpr(&small),
+            ruby = xml_text(ruby),
+            text = xml_text(text),
+        );
+    }
+
+    /// `w:rPr` for what differs from the document defaults, or nothing.
+    fn rpr(&self, modifiers: &Modifiers) -> String {
+        let rpr = run_properties(modifiers, self.defaults, self.resource);
+        if rpr.is_empty() {
+            rpr
+        } else {
+            format!("<w:rPr>{rpr}</w:rPr>")
+        }
+    }
+
+    /// Notes the face a run is drawn with, for font embedding.
+    fn record(&mut self, text: &str, ruby: Option<&str>, modifiers: &Modifiers) {
+        let family = match modifiers.get(&ModifierType::FontFamily) {
+            Some(Modifier::FontFamily { value }) => value.clone(),
+            _ => DEFAULT_FONT_FAMILY.to_string(),
+        };
+        let mut weight = match modifiers.get(&ModifierType::FontWeight) {
+            Some(Modifier::FontWeight { value }) => *value,
+            _ => DEFAULT_FONT_WEIGHT,
+        };
+        if modifiers.contains_key(&ModifierType::Bold) {
+            weight = self
+                .resource
+                .font_registry
+                .weights(&family)
+                .and_then(|available| find_bold_target(weight, available))
+                .unwrap_or(700);
+        }
+        let glyphs = self.used.entry((family, weight)).or_default();
+        glyphs.extend(text.chars());
+        glyphs.extend(ruby.into_iter().flat_map(str::chars));
+    }
+
+    fn table(&mut self, entry: &PlainNodeEntry, modifiers: &Modifiers) {
+        let PlainNode::Table(table) = &entry.node else {
+            return;
+        };
+        let rows: Vec<&PlainNodeEntry> = entry
+            .children
+            .iter()
+            .filter(|row| matches!(row.node, PlainNode::TableRow(_)))
+            .collect();
+        let Some(first) = rows.first() else {
+            return;
+        };
+
+        let total = self.content_width * table.proportion.clamp(1, 100) / 100;
+        let widths = column_widths(first, total);
+        let border = match table.border_style {
+            TableBorderStyle::Solid => "single",
+            TableBorderStyle::Dashed => "dashed",
+            TableBorderStyle::Dotted => "dotted",
+            TableBorderStyle::None => "nil",
+        };
+        let _ = write!(
+            self.out,
+            r#"<w:tbl><w:tblPr><w:tblW w:w="{total}" w:type="dxa"/><w:tblBorders>"#
+        );
+        for side in ["top", "left", "bottom", "right", "insideH", "insideV"] {
+            let _ = write!(
+                self.out,
+                r#"<w:{side} w:val="{border}" w:sz="4" w:space="0" w:color="auto"/>"#
+            );
+        }
+        self.out
+            .push_str(r#"</w:tblBorders><w:tblLayout w:type="fixed"/></w:tblPr><w:tblGrid>"#);
+        for width in &widths {
+            let _ = write!(self.out, r#"<w:gridCol w:w="{width}"/>"#);
+        }
+        self.out.push_str("</w:tblGrid>");
+
+        for row in rows {
+            let row_modifiers = merged(modifiers, &row.modifiers);
+            self.out.push_str("<w:tr>");
+            let cells = row
+                .children
+                .iter()
+                .filter(|cell| matches!(cell.node, PlainNode::TableCell(_)));
+            for (idx, cell) in cells.enumerate() {
+                let width = widths.get(idx).or(widths.last()).copied().unwrap_or(0);
+                let _ = write!(
+                    self.out,
+                    r#"<w:tc><w:tcPr><w:tcW w:w="{width}" w:type="dxa"/>"#
+                );
+                let mut cell_modifiers = cell.modifiers.clone();
+                // Cell shading belongs to the cell, not to the runs inside it.
+                if let Some(Modifier::BackgroundColor { value }) =
+                    cell_modifiers.remove(&ModifierType::BackgroundColor)
+                    && let Some(fill) = hex_color(&value, "bg", self.resource)
+                {
+                    let _ = write!(
+                        self.out,
+                        r#"<w:shd w:val="clear" w:color="auto" w:fill="{fill}"/>"#
+                    );
+                }
+                self.out.push_str("</w:tcPr>");
+                let inner = merged(&row_modifiers, &cell_modifiers);
+                let start = self.out.len();
+                for child in &cell.children {
+                    self.block(child, &inner, 0);
+                }
+                // A cell must end with a paragraph.
+                if !self.out[start..].ends_with("</w:p>") {
+                    self.out.push_str("<w:p/>");
+                }
+                self.out.push_str("</w:tc>");
+            }
+            self.out.push_str("</w:tr>");
+        }
+        self.out.push_str("</w:tbl>");
+    }
+}
+
+/// Grid widths in twips. Cell widths are ratios, as the editor lays them out;
+/// without a width on every cell of the first row the columns are even.
+fn column_widths(first_row: &PlainNodeEntry, total: u32) -> Vec<u32> {
+    let ratios: Vec<Option<u32>> = first_row
+        .children
+        .iter()
+        .filter_map(|cell| match &cell.node {
+            PlainNode::TableCell(cell) => Some(cell.col_width),
+            _ => None,
+        })
+        .collect();
+    let count = ratios.len().max(1) as u64;
+    let custom: Option<Vec<u32>> = ratios.iter().copied().collect();
+    match custom {
+        Some(ratios) if ratios.iter().any(|&r| r > 0) => {
+            let sum: u64 = ratios.iter().map(|&r| u64::from(r)).sum();
+            ratios
+                .iter()
+                .map(|&r| (u64::from(r) * u64::from(total) / sum) as u32)
+                .collect()
+        }
+        _ => vec![(u64::from(total) / count) as u32; count as usize],
+    }
+}
+
+fn merged(inherited: &Modifiers, own: &Modifiers) -> Modifiers {
+    let mut modifiers = inherited.clone();
+    modifiers.extend(own.iter().map(|(k, v)| (*k, v.clone())));
+    modifiers
+}
+
+/// Font size in half-points, Word's unit for `w:sz`.
+pub(super) fn half_points(modifiers: &Modifiers) -> u32 {
+    let size = match modifiers.get(&ModifierType::FontSize) {
+        Some(Modifier::FontSize { value }) => *value,
+        _ => DEFAULT_FONT_SIZE,
+    };
+    (size / 50).max(1)
+}
+
+/// The `w:rPr` children for the modifiers that differ from `base`.
+pub(super) fn run_properties(
+    modifiers: &Modifiers,
+    base: &Modifiers,
+    resource: &Resource,
+) -> String {
+    let changed = |ty: ModifierType| {
+        modifiers
+            .get(&ty)
+            .filter(|modifier| base.get(&ty) != Some(*modifier))
+    };
+    let mut rpr = String::new();
+    if let Some(Modifier::FontFamily { value }) = changed(ModifierType::FontFamily) {
+        let name = html_escape(value);
+        let _ = write!(
+            rpr,
+            r#"<w:rFonts w:ascii="{name}" w:hAnsi="{name}" w:eastAsia="{name}" w:cs="{name}"/>"#
+        );
+    }
+    let heavy = matches!(
+        changed(ModifierType::FontWeight),
+        Some(Modifier::FontWeight { value }) if *value >= 600
+    );
+    if modifiers.contains_key(&ModifierType::Bold) || heavy {
+        rpr.push_str("<w:b/>");
+    }
+    if modifiers.contains_key(&ModifierType::Italic) {
+        rpr.push_str("<w:i/>");
+    }
+    if modifiers.contains_key(&ModifierType::Strikethrough) {
+        rpr.push_str("<w:strike/>");
+    }
+    if let Some(Modifier::TextColor { value }) = changed(ModifierType::TextColor)
+        && let Some(color) = hex_color(value, "text", resource)
+    {
+        let _ = write!(rpr, r#"<w:color w:val="{color}"/>"#);
+    }
+    let half_points = half_points(modifiers);
+    let size_changed = changed(ModifierType::FontSize).is_some();
+    // Letter spacing is relative to the font size, so it follows a size change.
+    if let Some(Modifier::LetterSpacing { value }) = modifiers.get(&ModifierType::LetterSpacing)
+        && ((size_changed && *value != 0) || changed(ModifierType::LetterSpacing).is_some())
+    {
+        let _ = write!(
+            rpr,
+            r#"<w:spacing w:val="{}"/>"#,
+            value * half_points as i32 / 10
+        );
+    }
+    if size_changed {
+        let _ = write!(
+            rpr,
+            r#"<w:sz w:val="{half_points}"/><w:szCs w:val="{half_points}"/>"#
+        );
+    }
+    if modifiers.contains_key(&ModifierType::Underline) {
+        rpr.push_str(r#"<w:u w:val="single"/>"#);
+    }
+    if let Some(Modifier::BackgroundColor { value }) = changed(ModifierType::BackgroundColor)
+        && let Some(fill) = hex_color(value, "bg", resource)
+    {
+        let _ = write!(
+            rpr,
+            r#"<w:shd w:val="clear" w:color="auto" w:fill="{fill}"/>"#
+        );
+    }
+    rpr
+}
+
+/// The `w:pPr` children for the block modifiers that differ from `base`.
+/// Lengths relative to the font size are converted at the paragraph's size.
+pub(super) fn paragraph_properties(modifiers: &Modifiers, base: &Modifiers) -> String {
+    let changed = |ty: ModifierType| {
+        modifiers
+            .get(&ty)
+            .filter(|modifier| base.get(&ty) != Some(*modifier))
+    };
+    let half_points = half_points(modifiers);
+    let size_changed = changed(ModifierType::FontSize).is_some();
+    let mut ppr = String::new();
+    if let Some(Modifier::Direction { value }) = changed(ModifierType::Direction) {
+        match value {
+            TextDirection::Rtl => ppr.push_str("<w:bidi/>"),
+            TextDirection::Ltr => ppr.push_str(r#"<w:bidi w:val="0"/>"#),
+            TextDirection::Auto => {}
+        }
+    }
+    let mut spacing = String::new();
+    if let Some(Modifier::BlockGap { value }) = modifiers.get(&ModifierType::BlockGap)
+        && ((size_changed && *value != 0) || changed(ModifierType::BlockGap).is_some())
+    {
+        let _ = write!(spacing, r#" w:after="{}""#, value * half_points / 10);
+    }
+    if let Some(Modifier::LineHeight { value }) = changed(ModifierType::LineHeight) {
+        let _ = write!(
+            spacing,
+            r#" w:line="{}" w:lineRule="auto""#,
+            value * 240 / 100
+        );
+    }
+    if !spacing.is_empty() {
+        let _ = write!(ppr, "<w:spacing{spacing}/>");
+    }
+    if let Some(Modifier::ParagraphIndent { value }) = modifiers.get(&ModifierType::ParagraphIndent)
+        && ((size_changed && *value != 0) || changed(ModifierType::ParagraphIndent).is_some())
+    {
+        // Word prefers the character count; the twips are for other readers.
+        let _ = write!(
+            ppr,
+            r#"<w:ind w:firstLine="{}" w:firstLineChars="{value}"/>"#,
+            value * half_points / 10
+        );
+    }
+    if let Some(Modifier::Alignment { value }) = changed(ModifierType::Alignment) {
+        let jc = match value {
+            Alignment::Left => "left",
+            Alignment::Center => "center",
+            Alignment::Right => "right",
+            Alignment::Justify => "both",
+        };
+        let _ = write!(ppr, r#"<w:jc w:val="{jc}"/>"#);
+    }
+    ppr
+}
+
+/// A theme color token, or a literal `#rrggbb`, as `RRGGBB`. `none` has no
+/// color to write.
+fn hex_color(value: &str, token_prefix: &str, resource: &Resource) -> Option<String> {
+    if value == "none" {
+        return None;
+    }
+    if let Some(c) = resource
+        .theme()
+        .try_color(&format!("{token_prefix}.{value}"))
+    {
+        return Some(format!("{:02X}{:02X}{:02X}", c.r, c.g, c.b));
+    }
+    let hex = value.strip_prefix('#')?;
+    (hex.len() == 6 && hex.bytes().all(|b| b.is_ascii_hexdigit())).then(|| hex.to_ascii_uppercase())
+}
+
+/// Escapes text for XML and drops the control characters XML 1.0 cannot
+/// carry.
+fn xml_text(text: &str) -> String {
+    let kept: String = text
+        .chars()
+        .filter(|&ch| !ch.is_control() || matches!(ch, '\t' | '\n' | '\r'))
+        .collect();
+    html_escape(&kept)
+}
+
+#[cfg(test)]
+mod tests {
+    use editor_model::{PlainParagraphNode, PlainRootNode, PlainTextNode};
+
+    use super::*;
+
+    fn modifiers(list: &[Modifier]) -> Modifiers {
+        list.iter().map(|m| (m.as_type(), m.clone())).collect()
+    }
+
+    #[test]
+    fn run_properties_follow_the_schema_order() {
+        let base = modifiers(&[Modifier::FontSize { value: 1000 }]);
+        let run = modifiers(&[
+            Modifier::Underline,
+            Modifier::FontSize { value: 1600 },
+            Modifier::LetterSpacing { value: 5 },
+            Modifier::Bold,
+            Modifier::TextColor {
+                value: "#12ab34".into(),
+            },
+        ]);
+        assert_eq!(
+            run_properties(&run, &base, &Resource::new_test()),
+            concat!(
+                r#"<w:b/><w:color w:val="12AB34"/><w:spacing w:val="16"/>"#,
+                r#"<w:sz w:val="32"/><w:szCs w:val="32"/><w:u w:val="single"/>"#
+            )
+        );
+        assert_eq!(run_properties(&base, &base, &Resource::new_test()), "");
+    }
+
+    #[test]
+    fn paragraph_lengths_scale_with_the_font_size() {
+        let base = modifiers(&[
+            Modifier::FontSize { value: 1000 },
+            Modifier::LineHeight { value: 160 },
+        ]);
+        let paragraph = modifiers(&[
+            Modifier::FontSize { value: 1000 },
+            Modifier::LineHeight { value: 200 },
+            Modifier::ParagraphIndent { value: 100 },
+            Modifier::Alignment {
+                value: Alignment::Justify,
+            },
+        ]);
+        assert_eq!(
+            paragraph_properties(&paragraph, &base),
+            concat!(
+                r#"<w:spacing w:line="480" w:lineRule="auto"/>"#,
+                r#"<w:ind w:firstLine="200" w:firstLineChars="100"/><w:jc w:val="both"/>"#
+            )
+        );
+    }
+
+    #[test]
+    fn consecutive_linked_runs_share_a_hyperlink() {
+        let link = Modifier::Link {
+            href: "https://example.com/?a=1&b=2".into(),
+        };
+        let text = |value: &str, list: &[Modifier]| PlainNodeEntry {
+            node: PlainNode::Text(PlainTextNode { text: value.into() }),
+            modifiers: modifiers(list),
+            carry: Vec::new(),
+            children: Vec::new(),
+        };
+        let paragraph = PlainNodeEntry {
+            node: PlainNode::Paragraph(PlainParagraphNode::default()),
+            modifiers: Modifiers::new(),
+            carry: Vec::new(),
+            children: vec![
+                text("a", std::slice::from_ref(&link)),
+                text("b", &[link.clone(), Modifier::Italic]),
+                text("c", &[]),
+            ],
+        };
+        let root = PlainNodeEntry {
+            node: PlainNode::Root(PlainRootNode::default()),
+            modifiers: Modifiers::new(),
+            carry: Vec::new(),
+            children: vec![paragraph],
+        };
+        let body = body(
+            &root,
+            &Modifiers::new(),
+            9000,
+            "ko-KR",
+            &Resource::new_test(),
+        );
+
+        assert_eq!(body.links, ["https://example.com/?a=1&b=2"]);
+        assert_eq!(body.xml.matches("<w:hyperlink ").count(), 1);
+        assert!(body.xml.contains(concat!(
+            r#"<w:t xml:space="preserve">b</w:t></w:r></w:hyperlink>"#,
+            r#"<w:r><w:t xml:space="preserve">c</w:t></w:r>"#
+        )));
+    }
+}
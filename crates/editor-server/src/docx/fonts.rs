@@ -0,0 +1,150 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use editor_resource::Resource;
+use hashbrown::HashSet;
+use skrifa::{FontRef, MetadataProvider};
+
+use super::body::UsedText;
+use crate::font::subset_font;
+
+/// Weights from this one up fill a family's bold slot.
+const BOLD_THRESHOLD: u16 = 600;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(super) enum Slot {
+    Regular,
+    Bold,
+}
+
+impl Slot {
+    pub(super) fn element(self) -> &'static str {
+        match self {
+            Slot::Regular => "embedRegular",
+            Slot::Bold => "embedBold",
+        }
+    }
+}
+
+/// A subset face, obfuscated as ECMA-376 Part 2 §15.2.13 requires of
+/// embedded fonts.
+pub(super) struct EmbeddedFont {
+    pub family: String,
+    pub slot: Slot,
+    /// `w:fontKey`, the GUID the data is obfuscated with.
+    pub key: String,
+    pub data: Vec<u8>,
+}
+
+/// Word embeds at most a regular and a bold face per family; every weight
+/// the text uses folds into one of the two, drawn with the loaded face
+/// nearest 400 or 700. Faces that are not loaded or cannot be subset are left
+/// to the reader's fallback.
+pub(super) fn embedded_fonts(used: &UsedText, resource: &Resource) -> Vec<EmbeddedFont> {
+    let registry = &resource.font_registry;
+    let mut slots: BTreeMap<(&str, Slot), (Vec<u16>, BTreeSet<char>)> = BTreeMap::new();
+    for ((family, weight), chars) in used {
+        let slot = if *weight >= BOLD_THRESHOLD {
+            Slot::Bold
+        } else {
+            Slot::Regular
+        };
+        let (weights, glyphs) = slots.entry((family.as_str(), slot)).or_default();
+        weights.push(*weight);
+        glyphs.extend(chars);
+    }
+
+    let mut fonts = Vec::new();
+    for ((family, slot), (weights, chars)) in slots {
+        let Some(id) = registry.intern_id(family) else {
+            continue;
+        };
+        let target = match slot {
+            Slot::Regular => 400,
+            Slot::Bold => 700,
+        };
+        let Some(data) = weights
+            .iter()
+            .copied()
+            .filter(|&weight| registry.font_data(id, weight).is_some())
+            .min_by_key(|&weight| weight.abs_diff(target))
+            .and_then(|weight| registry.font_data(id, weight))
+        else {
+            continue;
+        };
+        let Ok(font) = FontRef::new(data) else {
+            continue;
+        };
+        let charmap = font.charmap();
+        let gids: HashSet<u16> = chars
+            .iter()
+            .filter_map(|&ch| charmap.map(ch))
+            .filter_map(|gid| u16::try_from(gid.to_u32()).ok())
+            .collect();
+        let Ok(mut subset) = subset_font(data, &gids) else {
+            continue;
+        };
+        let guid = font_guid(family, slot, &subset);
+        obfuscate(&mut subset, &guid);
+        fonts.push(EmbeddedFont {
+            family: family.to_owned(),
+            slot,
+            key: format_guid(&guid),
+            data: subset,
+        });
+    }
+    fonts
+}
+
+/// Derived from the content so the same document always builds the same file.
+fn font_guid(family: &str, slot: Slot, data: &[u8]) -> [u8; 16] {
+    let mut seed = format!("{family}:{slot:?}:").into_bytes();
+    seed.extend_from_slice(data);
+    let high = rapidhash::v3::rapidhash_v3(&seed);
+    let low = rapidhash::v3::rapidhash_v3(&high.to_le_bytes());
+    let mut guid = [0; 16];
+    guid[..8].copy_from_slice(&high.to_be_bytes());
+    guid[8..].copy_from_slice(&low.to_be_bytes());
+    guid
+}
+
+/// `{XXXXXXXX-XXXX-XXXX-XXXX-XXXXXXXXXXXX}`, the bytes in order.
+fn format_guid(guid: &[u8; 16]) -> String {
+    let hex: String = guid.iter().map(|b| format!("{b:02X}")).collect();
+    format!(
+        "{{{}-{}-{}-{}-{}}}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    )
+}
+
+/// XORs the first 32 bytes with the key, which is the GUID's bytes read from
+/// the end of its string form.
+fn obfuscate(data: &mut [u8], guid: &[u8; 16]) {
+    for (idx, byte) in data.iter_mut().take(32).enumerate() {
+        *byte ^= guid[15 - idx % 16];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn obfuscation_uses_the_reversed_guid_and_undoes_itself() {
+        let guid: [u8; 16] = std::array::from_fn(|idx| idx as u8 + 1);
+        assert_eq!(format_guid(&guid), "{01020304-0506-0708-090A-0B0C0D0E0F10}");
+
+        let original = vec![0u8; 40];
+        let mut data = original.clone();
+        obfuscate(&mut data, &guid);
+        assert_eq!(data[0], 0x10);
+        assert_eq!(data[15], 0x01);
+        assert_eq!(data[16], 0x10);
+        assert_eq!(&data[32..], &original[32..]);
+        obfuscate(&mut data, &guid);
+        assert_eq!(data, original);
+    }
+}
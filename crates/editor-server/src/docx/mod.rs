@@ -0,0 +1,724 @@
+mod body;
+mod fonts;
+
+use std::collections::BTreeSet;
+use std::fmt::Write;
+
+use editor_clipboard::html::serialize::html_escape;
+use editor_macros::ffi;
+use editor_model::{
+    LayoutMode, Modifier, ModifierType, PlainDoc, PlainNode, text_style_default_modifier,
+};
+use editor_resource::Resource;
+use editor_zip::ZipWriter;
+use serde::{Deserialize, Serialize};
+
+use self::body::{Body, ListKind, Modifiers, body, paragraph_properties, run_properties};
+use self::fonts::{EmbeddedFont, embedded_fonts};
+use crate::ServerError;
+
+/// Twips per layout px: 1px is 0.75pt and a twip is 1/20pt.
+const TWIPS_PER_PX: u32 = 15;
+
+/// Word's largest page side and margin, 22in.
+const MAX_TWIPS: u32 = 22 * 1440;
+
+/// A4 with 1in margins, for documents that have no page.
+const DEFAULT_PAGE: PageSetup = PageSetup {
+    width: 11906,
+    height: 16838,
+    margin_top: 1440,
+    margin_bottom: 1440,
+    margin_left: 1440,
+    margin_right: 1440,
+    columns: 1,
+    column_gap: 0,
+};
+
+#[ffi]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DocxOptions {
+    pub title: Option<String>,
+    pub author: Option<String>,
+    /// BCP 47 tag for the text and ruby; `ko-KR` when unset.
+    pub language: Option<String>,
+    /// Embeds subsets of the font faces the text uses that the resource has
+    /// loaded. Fonts are otherwise referenced by family name only.
+    #[serde(default)]
+    pub embed_fonts: bool,
+}
+
+/// Builds a WordprocessingML document from a document: span modifiers become
+/// run properties over the document defaults in the styles part, block
+/// modifiers paragraph properties, lists numbering instances, and the page
+/// layout the section's page size, margins and columns.
+pub fn build_docx(
+    doc: &PlainDoc,
+    options: &DocxOptions,
+    resource: &Resource,
+) -> Result<Vec<u8>, ServerError> {
+    let language = options.language.as_deref().unwrap_or("ko-KR");
+    let defaults = document_defaults(doc);
+    let page = match &doc.root.node {
+        PlainNode::Root(root) => PageSetup::from_layout(&root.layout_mode),
+        _ => DEFAULT_PAGE,
+    };
+    let body = body(
+        &doc.root,
+        &defaults,
+        page.column_width(),
+        language,
+        resource,
+    );
+    let fonts = if options.embed_fonts {
+        embedded_fonts(&body.used, resource)
+    } else {
+        Vec::new()
+    };
+
+    let mut zip = ZipWriter::new();
+    zip.deflated("[Content_Types].xml", content_types(&fonts).as_bytes());
+    zip.deflated("_rels/.rels", PACKAGE_RELS.as_bytes());
+    zip.deflated("docProps/core.xml", core_properties(options).as_bytes());
+    zip.deflated("word/document.xml", document(&body, &page).as_bytes());
+    zip.deflated(
+        "word/_rels/document.xml.rels",
+        document_rels(&body.links).as_bytes(),
+    );
+    zip.deflated(
+        "word/styles.xml",
+        styles(&defaults, language, resource).as_bytes(),
+    );
+    zip.deflated("word/settings.xml", settings(!fonts.is_empty()).as_bytes());
+    zip.deflated("word/numbering.xml", numbering(&body).as_bytes());
+    zip.deflated(
+        "word/fontTable.xml",
+        font_table(&defaults, &body, &fonts).as_bytes(),
+    );
+    if !fonts.is_empty() {
+        zip.deflated(
+            "word/_rels/fontTable.xml.rels",
+            font_table_rels(&fonts).as_bytes(),
+        );
+        for (idx, font) in fonts.iter().enumerate() {
+            zip.deflated(&font_part(idx), &font.data);
+        }
+    }
+    Ok(zip.finish())
+}
+
+const XML_DECLARATION: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#;
+
+const W_NS: &str = "http://schemas.openxmlformats.org/wordprocessingml/2006/main";
+const R_NS: &str = "http://schemas.openxmlformats.org/officeDocument/2006/relationships";
+const REL_NS: &str = "http://schemas.openxmlformats.org/package/2006/relationships";
+const REL_TYPE: &str = "http://schemas.openxmlformats.org/officeDocument/2006/relationships";
+
+const PACKAGE_RELS: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="word/document.xml"/><Relationship Id="rId2" Type="http://schemas.openxmlformats.org/package/2006/relationships/metadata/core-properties" Target="docProps/core.xml"/></Relationships>
+"#;
+
+/// Page geometry in twips.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct PageSetup {
+    width: u32,
+    height: u32,
+    margin_top: u32,
+    margin_bottom: u32,
+    margin_left: u32,
+    margin_right: u32,
+    columns: u32,
+    column_gap: u32,
+}
+
+impl PageSetup {
+    /// Manuscript pages are sized to their grid; the grid itself has no
+    /// counterpart and is left out. Document sizes are unbounded, so every
+    /// length is clamped to what Word accepts.
+    fn from_layout(layout: &LayoutMode) -> Self {
+        match layout.normalized() {
+            LayoutMode::Paginated {
+                page_width,
+                page_height,
+                page_margin_top,
+                page_margin_bottom,
+                page_margin_left,
+                page_margin_right,
+                columns,
+                column_gap,
+            } => Self {
+                width: twips(page_width),
+                height: twips(page_height),
+                margin_top: twips(page_margin_top),
+                margin_bottom: twips(page_margin_bottom),
+                margin_left: twips(page_margin_left),
+                margin_right: twips(page_margin_right),
+                columns: columns.max(1),
+                column_gap: twips(column_gap),
+            },
+            LayoutMode::Manuscript {
+                columns,
+                rows,
+                cell_size,
+                row_gap,
+                page_margin,
+            } => {
+                let margin = twips(page_margin);
+                let margins = page_margin.saturating_mul(2);
+                Self {
+                    width: twips(columns.saturating_mul(cell_size).saturating_add(margins)),
+                    height: twips(
+                        rows.saturating_mul(cell_size.saturating_add(row_gap))
+                            .saturating_add(margins),
+                    ),
+                    margin_top: margin,
+                    margin_bottom: margin,
+                    margin_left: margin,
+                    margin_right: margin,
+                    columns: 1,
+                    column_gap: 0,
+                }
+            }
+            LayoutMode::Continuous { .. } => DEFAULT_PAGE,
+        }
+    }
+
+    /// Width of one text column, what a full-width table spans.
+    fn column_width(&self) -> u32 {
+        let content = self
+            .width
+            .saturating_sub(self.margin_left.saturating_add(self.margin_right));
+        let gaps = self.column_gap.saturating_mul(self.columns - 1);
+        (content.saturating_sub(gaps) / self.columns).max(1)
+    }
+
+    fn section_properties(&self) -> String {
+        let orient = if self.width > self.height {
+            r#" w:orient="landscape""#
+        } else {
+            ""
+        };
+        format!(
+            concat!(
+                r#"<w:sectPr><w:pgSz w:w="{}" w:h="{}"{}/>"#,
+                r#"<w:pgMar w:top="{}" w:right="{}" w:bottom="{}" w:left="{}" w:header="0" w:footer="0" w:gutter="0"/>"#,
+                r#"<w:cols w:num="{}" w:space="{}"/></w:sectPr>"#
+            ),
+            self.width,
+            self.height,
+            orient,
+            self.margin_top,
+            self.margin_right,
+            self.margin_bottom,
+            self.margin_left,
+            self.columns,
+            self.column_gap
+        )
+    }
+}
+
+fn twips(px: u32) -> u32 {
+    px.saturating_mul(TWIPS_PER_PX).min(MAX_TWIPS)
+}
+
+/// The model's text defaults with the root's modifiers on top. The styles
+/// part declares these, so runs and paragraphs only carry what differs.
+fn document_defaults(doc: &PlainDoc) -> Modifiers {
+    let mut defaults: Modifiers = [
+        ModifierType::FontFamily,
+        ModifierType::FontSize,
+        ModifierType::FontWeight,
+        ModifierType::LetterSpacing,
+        ModifierType::LineHeight,
+        ModifierType::Alignment,
+        ModifierType::BlockGap,
+        ModifierType::ParagraphIndent,
+    ]
+    .into_iter()
+    .filter_map(|ty| Some((ty, text_style_default_modifier(ty)?)))
+    .collect();
+    defaults.extend(
+        doc.root
+            .modifiers
+            .iter()
+            .map(|(ty, modifier)| (*ty, modifier.clone())),
+    );
+    defaults
+}
+
+fn content_types(fonts: &[EmbeddedFont]) -> String {
+    let mut xml = format!(
+        r#"{XML_DECLARATION}
+<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types"><Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/><Default Extension="xml" ContentType="application/xml"/>"#
+    );
+    if !fonts.is_empty() {
+        xml.push_str(r#"<Default Extension="odttf" ContentType="application/vnd.openxmlformats-officedocument.obfuscatedFont"/>"#);
+    }
+    for (part, content_type) in [
+        ("/word/document.xml", "document.main"),
+        ("/word/styles.xml", "styles"),
+        ("/word/settings.xml", "settings"),
+        ("/word/numbering.xml", "numbering"),
+        ("/word/fontTable.xml", "fontTable"),
+    ] {
+        let _ = write!(
+            xml,
+            r#"<Override PartName="{part}" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.{content_type}+xml"/>"#
+        );
+    }
+    xml.push_str(r#"<Override PartName="/docProps/core.xml" ContentType="application/vnd.openxmlformats-package.core-properties+xml"/></Types>"#);
+    xml.push('\n');
+    xml
+}
+
+fn core_properties(options: &DocxOptions) -> String {
+    let mut xml = format!(
+        r#"{XML_DECLARATION}
+<cp:coreProperties xmlns:cp="http://schemas.openxmlformats.org/package/2006/metadata/core-properties" xmlns:dc="http://purl.org/dc/elements/1.1/">"#
+    );
+    if let Some(title) = &options.title {
+        let _ = write!(xml, "<dc:title>{}</dc:title>", html_escape(title));
+    }
+    if let Some(author) = &options.author {
+        let _ = write!(xml, "<dc:creator>{}</dc:creator>", html_escape(author));
+    }
+    xml.push_str("</cp:coreProperties>\n");
+    xml
+}
+
+fn document(body: &Body, page: &PageSetup) -> String {
+    format!(
+        r#"{XML_DECLARATION}
+<w:document xmlns:w="{W_NS}" xmlns:r="{R_NS}"><w:body>{}{}</w:body></w:document>
+"#,
+        body.xml,
+        page.section_properties()
+    )
+}
+
+fn document_rels(links: &[String]) -> String {
+    let mut xml = format!(
+        r#"{XML_DECLARATION}
+<Relationships xmlns="{REL_NS}">"#
+    );
+    for (id, target) in [
+        ("rIdStyles", "styles"),
+        ("rIdSettings", "settings"),
+        ("rIdNumbering", "numbering"),
+        ("rIdFontTable", "fontTable"),
+    ] {
+        let _ = write!(
+            xml,
+            r#"<Relationship Id="{id}" Type="{REL_TYPE}/{target}" Target="{target}.xml"/>"#
+        );
+    }
+    for (idx, href) in links.iter().enumerate() {
+        let _ = write!(
+            xml,
+            r#"<Relationship Id="rIdLink{}" Type="{REL_TYPE}/hyperlink" Target="{}" TargetMode="External"/>"#,
+            idx + 1,
+            html_escape(href)
+        );
+    }
+    xml.push_str("</Relationships>\n");
+    xml
+}
+
+fn styles(defaults: &Modifiers, language: &str, resource: &Resource) -> String {
+    let none = Modifiers::new();
+    let lang = html_escape(language);
+    format!(
+        concat!(
+            r#"{}
+<w:styles xmlns:w="{}"><w:docDefaults><w:rPrDefault><w:rPr>{}<w:lang w:val="{lang}" w:eastAsia="{lang}"/></w:rPr></w:rPrDefault>"#,
+            r#"<w:pPrDefault><w:pPr>{}</w:pPr></w:pPrDefault></w:docDefaults>"#,
+            r#"<w:style w:type="paragraph" w:default="1" w:styleId="Normal"><w:name w:val="Normal"/><w:qFormat/></w:style>"#,
+            "</w:styles>\n"
+        ),
+        XML_DECLARATION,
+        W_NS,
+        run_properties(defaults, &none, resource),
+        paragraph_properties(defaults, &none),
+        lang = lang
+    )
+}
+
+fn settings(embed_fonts: bool) -> String {
+    let embedding = if embed_fonts {
+        "<w:embedTrueTypeFonts/><w:saveSubsetFonts/>"
+    } else {
+        ""
+    };
+    format!(
+        r#"{XML_DECLARATION}
+<w:settings xmlns:w="{W_NS}">{embedding}<w:defaultTabStop w:val="720"/><w:compat><w:compatSetting w:name="compatibilityMode" w:uri="http://schemas.microsoft.com/office/word" w:val="15"/></w:compat></w:settings>
+"#
+    )
+}
+
+/// One abstract definition per list kind, nine levels each; every list is an
+/// instance of its kind, and ordered instances restart at their level.
+fn numbering(body: &Body) -> String {
+    let mut xml = format!(
+        r#"{XML_DECLARATION}
+<w:numbering xmlns:w="{W_NS}">"#
+    );
+    for (id, format, text) in [(0, "bullet", "•"), (1, "decimal", "")] {
+        let _ = write!(
+            xml,
+            r#"<w:abstractNum w:abstractNumId="{id}"><w:multiLevelType w:val="hybridMultilevel"/>"#
+        );
+        for level in 0..9u32 {
+            let text = if text.is_empty() {
+                format!("%{}.", level + 1)
+            } else {
+                text.to_owned()
+            };
+            let _ = write!(
+                xml,
+                concat!(
+                    r#"<w:lvl w:ilvl="{level}"><w:start w:val="1"/><w:numFmt w:val="{format}"/>"#,
+                    r#"<w:lvlText w:val="{text}"/><w:lvlJc w:val="left"/>"#,
+                    r#"<w:pPr><w:ind w:left="{left}" w:hanging="360"/></w:pPr></w:lvl>"#
+                ),
+                level = level,
+                format = format,
+                text = text,
+                left = 720 * (level + 1)
+            );
+        }
+        xml.push_str("</w:abstractNum>");
+    }
+    for (idx, list) in body.lists.iter().enumerate() {
+        let _ = write!(xml, r#"<w:num w:numId="{}">"#, idx + 1);
+        match list.kind {
+            ListKind::Bullet => xml.push_str(r#"<w:abstractNumId w:val="0"/>"#),
+            ListKind::Ordered => {
+                let _ = write!(
+                    xml,
+                    r#"<w:abstractNumId w:val="1"/><w:lvlOverride w:ilvl="{}"><w:startOverride w:val="1"/></w:lvlOverride>"#,
+                    list.level
+                );
+            }
+        }
+        xml.push_str("</w:num>");
+    }
+    xml.push_str("</w:numbering>\n");
+    xml
+}
+
+/// Every family the document names, with its embedded faces.
+fn font_table(defaults: &Modifiers, body: &Body, fonts: &[EmbeddedFont]) -> String {
+    let mut families: BTreeSet<&str> = body
+        .used
+        .keys()
+        .map(|(family, _)| family.as_str())
+        .collect();
+    if let Some(Modifier::FontFamily { value }) = defaults.get(&ModifierType::FontFamily) {
+        families.insert(value);
+    }
+    let mut xml = format!(
+        r#"{XML_DECLARATION}
+<w:fonts xmlns:w="{W_NS}" xmlns:r="{R_NS}">"#
+    );
+    for family in families {
+        let _ = write!(xml, r#"<w:font w:name="{}">"#, html_escape(family));
+        for (idx, font) in fonts.iter().enumerate() {
+            if font.family == family {
+                let _ = write!(
+                    xml,
+                    r#"<w:{} r:id="rIdFont{}" w:fontKey="{}"/>"#,
+                    font.slot.element(),
+                    idx + 1,
+                    font.key
+                );
+            }
+        }
+        xml.push_str("</w:font>");
+    }
+    xml.push_str("</w:fonts>\n");
+    xml
+}
+
+fn font_table_rels(fonts: &[EmbeddedFont]) -> String {
+    let mut xml = format!(
+        r#"{XML_DECLARATION}
+<Relationships xmlns="{REL_NS}">"#
+    );
+    for idx in 0..fonts.len() {
+        let _ = write!(
+            xml,
+            r#"<Relationship Id="rIdFont{}" Type="{REL_TYPE}/font" Target="fonts/font{}.odttf"/>"#,
+            idx + 1,
+            idx + 1
+        );
+    }
+    xml.push_str("</Relationships>\n");
+    xml
+}
+
+fn font_part(idx: usize) -> String {
+    format!("word/fonts/font{}.odttf", idx + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use editor_model::{
+        PlainNodeEntry, PlainPageBreakNode, PlainParagraphNode, PlainRootNode, PlainTableCellNode,
+        PlainTableNode, PlainTableRowNode, PlainTextNode, TableBorderStyle, plain_entry,
+    };
+    use editor_resource::{ResourceSource, compress_zstd, prepare_font_base};
+    use editor_zip::ZipArchive;
+    use skrifa::FontRef;
+
+    use super::*;
+
+    const TEXT_FONT: &[u8] = include_bytes!("../../../../assets/Pretendard-Regular.ttf");
+
+    fn resource_with_font() -> Resource {
+        let prepared =
+            prepare_font_base(&compress_zstd(TEXT_FONT)).expect("test font must be valid");
+        let mut source = ResourceSource::new_test();
+        source
+            .insert_font_base(editor_model::DEFAULT_FONT_FAMILY, 400, prepared)
+            .expect("test font must change resources");
+        Resource::from_snapshot(source.snapshot())
+    }
+
+    fn text(text: &str, modifiers: &[Modifier]) -> PlainNodeEntry {
+        PlainNodeEntry {
+            modifiers: modifiers.iter().map(|m| (m.as_type(), m.clone())).collect(),
+            ..plain_entry(
+                PlainNode::Text(PlainTextNode {
+                    text: text.to_string(),
+                }),
+                vec![],
+            )
+        }
+    }
+
+    fn paragraph(children: Vec<PlainNodeEntry>) -> PlainNodeEntry {
+        plain_entry(
+            PlainNode::Paragraph(PlainParagraphNode::default()),
+            children,
+        )
+    }
+
+    fn doc(layout_mode: LayoutMode, children: Vec<PlainNodeEntry>) -> PlainDoc {
+        PlainDoc {
+            root: plain_entry(PlainNode::Root(PlainRootNode { layout_mode }), children),
+        }
+    }
+
+    fn entry_text<'a>(entries: &'a [(String, Vec<u8>)], name: &str) -> &'a str {
+        let (_, data) = entries.iter().find(|(n, _)| n == name).unwrap();
+        std::str::from_utf8(data).unwrap()
+    }
+
+    #[test]
+    fn paginated_layout_sets_the_section() {
+        let doc = doc(
+            LayoutMode::Paginated {
+                page_width: 794,
+                page_height: 1123,
+                page_margin_top: 96,
+                page_margin_bottom: 96,
+                page_margin_left: 72,
+                page_margin_right: 72,
+                columns: 2,
+                column_gap: 24,
+            },
+            vec![paragraph(vec![text("본문", &[])])],
+        );
+        let docx = build_docx(&doc, &DocxOptions::default(), &Resource::new_test()).unwrap();
+        let entries = ZipArchive::new(&docx).unwrap().read_all().unwrap();
+        let names: Vec<&str> = entries.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "[Content_Types].xml",
+                "_rels/.rels",
+                "docProps/core.xml",
+                "word/document.xml",
+                "word/_rels/document.xml.rels",
+                "word/styles.xml",
+                "word/settings.xml",
+                "word/numbering.xml",
+                "word/fontTable.xml",
+            ]
+        );
+
+        let document = entry_text(&entries, "word/document.xml");
+        assert!(document.contains(concat!(
+            r#"<w:pgSz w:w="11910" w:h="16845"/>"#,
+            r#"<w:pgMar w:top="1440" w:right="1080" w:bottom="1440" w:left="1080" w:header="0" w:footer="0" w:gutter="0"/>"#,
+            r#"<w:cols w:num="2" w:space="360"/>"#
+        )));
+        let styles = entry_text(&entries, "word/styles.xml");
+        assert!(styles.contains(r#"<w:sz w:val="24"/>"#));
+        assert!(styles.contains(r#"<w:lang w:val="ko-KR" w:eastAsia="ko-KR"/>"#));
+        assert!(
+            entry_text(&entries, "word/fontTable.xml").contains(r#"<w:font w:name="Pretendard">"#)
+        );
+    }
+
+    #[test]
+    fn oversized_pages_are_clamped_to_word_limits() {
+        let page = PageSetup::from_layout(&LayoutMode::Paginated {
+            page_width: u32::MAX,
+            page_height: 300_000_000,
+            page_margin_top: 96,
+            page_margin_bottom: 96,
+            page_margin_left: u32::MAX,
+            page_margin_right: u32::MAX,
+            columns: u32::MAX,
+            column_gap: u32::MAX,
+        });
+        assert_eq!((page.width, page.height), (MAX_TWIPS, MAX_TWIPS));
+        assert_eq!((page.margin_left, page.column_gap), (MAX_TWIPS, MAX_TWIPS));
+        assert_eq!(page.column_width(), 1);
+
+        let page = PageSetup::from_layout(&LayoutMode::Manuscript {
+            columns: u32::MAX,
+            rows: u32::MAX,
+            cell_size: u32::MAX,
+            row_gap: u32::MAX,
+            page_margin: u32::MAX,
+        });
+        assert_eq!((page.width, page.height), (MAX_TWIPS, MAX_TWIPS));
+        assert!(page.column_width() >= 1);
+    }
+
+    #[test]
+    fn round_trips_through_the_importer() {
+        let doc = doc(
+            LayoutMode::default(),
+            vec![
+                paragraph(vec![
+                    text("first", &[Modifier::FontSize { value: 1600 }]),
+                    plain_entry(PlainNode::PageBreak(PlainPageBreakNode::default()), vec![]),
+                ]),
+                paragraph(vec![text("second", &[Modifier::Bold, Modifier::Italic])]),
+            ],
+        );
+        let docx = build_docx(&doc, &DocxOptions::default(), &Resource::new_test()).unwrap();
+        let import = editor_clipboard::docx::from_docx(&docx, &Resource::new_test()).unwrap();
+
+        let [first, second] = import.slice.content.as_slice() else {
+            panic!("expected two paragraphs: {:?}", import.slice.content);
+        };
+        assert!(matches!(first.children[0].node, PlainNode::Text(ref t) if t.text == "first"));
+        assert!(
+            first.children[0]
+                .modifiers
+                .contains(&Modifier::FontSize { value: 1600 })
+        );
+        assert!(matches!(
+            first.children.last().unwrap().node,
+            PlainNode::PageBreak(_)
+        ));
+        assert!(matches!(second.children[0].node, PlainNode::Text(ref t) if t.text == "second"));
+        assert!(second.children[0].modifiers.contains(&Modifier::Bold));
+        assert!(second.children[0].modifiers.contains(&Modifier::Italic));
+    }
+
+    #[test]
+    fn tables_keep_border_style_and_column_widths() {
+        let cell = |width: u32, content: &str| {
+            plain_entry(
+                PlainNode::TableCell(PlainTableCellNode {
+                    col_width: Some(width),
+                    background_color: None,
+                }),
+                vec![paragraph(vec![text(content, &[])])],
+            )
+        };
+        let table = plain_entry(
+            PlainNode::Table(PlainTableNode {
+                border_style: TableBorderStyle::Dashed,
+                proportion: 50,
+            }),
+            vec![plain_entry(
+                PlainNode::TableRow(PlainTableRowNode::default()),
+                vec![cell(100, "a"), cell(300, "b")],
+            )],
+        );
+        let doc = doc(LayoutMode::default(), vec![table]);
+        let docx = build_docx(&doc, &DocxOptions::default(), &Resource::new_test()).unwrap();
+        let document = entry_text(
+            &ZipArchive::new(&docx).unwrap().read_all().unwrap(),
+            "word/document.xml",
+        )
+        .to_owned();
+
+        // Half of A4's 9026tw text width, split 1:3.
+        assert!(document.contains(r#"<w:tblW w:w="4513" w:type="dxa"/>"#));
+        assert!(document.contains(r#"<w:gridCol w:w="1128"/><w:gridCol w:w="3384"/>"#));
+        assert_eq!(document.matches(r#"w:val="dashed""#).count(), 6);
+    }
+
+    #[test]
+    fn ruby_becomes_w_ruby() {
+        let doc = doc(
+            LayoutMode::default(),
+            vec![paragraph(vec![text(
+                "漢字",
+                &[Modifier::Ruby {
+                    text: "한자".into(),
+                }],
+            )])],
+        );
+        let docx = build_docx(&doc, &DocxOptions::default(), &Resource::new_test()).unwrap();
+        let document = entry_text(
+            &ZipArchive::new(&docx).unwrap().read_all().unwrap(),
+            "word/document.xml",
+        )
+        .to_owned();
+
+        assert!(document.contains(r#"<w:hps w:val="12"/><w:hpsRaise w:val="24"/>"#));
+        assert!(document.contains(
+            r#"<w:rt><w:r><w:rPr><w:sz w:val="12"/><w:szCs w:val="12"/></w:rPr><w:t xml:space="preserve">한자</w:t></w:r></w:rt>"#
+        ));
+        assert!(document.contains(
+            r#"<w:rubyBase><w:r><w:t xml:space="preserve">漢字</w:t></w:r></w:rubyBase>"#
+        ));
+    }
+
+    #[test]
+    fn embedded_fonts_are_obfuscated_subsets() {
+        let doc = doc(
+            LayoutMode::default(),
+            vec![paragraph(vec![text("가나다", &[])])],
+        );
+        let options = DocxOptions {
+            embed_fonts: true,
+            ..Default::default()
+        };
+        let docx = build_docx(&doc, &options, &resource_with_font()).unwrap();
+        let entries = ZipArchive::new(&docx).unwrap().read_all().unwrap();
+
+        assert!(entry_text(&entries, "word/settings.xml").contains("<w:embedTrueTypeFonts/>"));
+        let table = entry_text(&entries, "word/fontTable.xml");
+        let key_start = table.find(r#"w:fontKey="{"#).unwrap() + r#"w:fontKey="{"#.len();
+        let key: Vec<u8> = table[key_start..key_start + 36]
+            .replace('-', "")
+            .as_bytes()
+            .chunks(2)
+            .map(|hex| u8::from_str_radix(std::str::from_utf8(hex).unwrap(), 16).unwrap())
+            .rev()
+            .collect();
+        assert!(table.contains(r#"<w:embedRegular r:id="rIdFont1""#));
+
+        let (_, data) = entries
+            .iter()
+            .find(|(name, _)| name == "word/fonts/font1.odttf")
+            .unwrap();
+        assert!(data.len() < TEXT_FONT.len());
+        let mut font = data.clone();
+        for (idx, byte) in font.iter_mut().take(32).enumerate() {
+            *byte ^= key[idx % 16];
+        }
+        assert!(FontRef::new(&font).is_ok());
+    }
+}
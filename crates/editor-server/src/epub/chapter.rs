@@ -0,0 +1,449 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+
+use editor_clipboard::html::serialize::{html_escape, serialize_text, style_declarations};
+use editor_model::{
+    Alignment, DEFAULT_FONT_FAMILY, DEFAULT_FONT_WEIGHT, Modifier, ModifierType, PlainDoc,
+    PlainNode, PlainNodeEntry, TextDirection,
+};
+use editor_resource::Resource;
+
+/// One XHTML content document of the book.
+pub(super) struct Chapter {
+    pub title: String,
+    pub body: String,
+    /// Fold titles in the chapter, nested as the folds are.
+    pub sections: Vec<Section>,
+}
+
+pub(super) struct Section {
+    pub title: String,
+    pub anchor: String,
+    pub children: Vec<Section>,
+}
+
+/// The characters each `(family, weight)` face has to cover.
+pub(super) type UsedText = BTreeMap<(String, u16), BTreeSet<char>>;
+
+/// Splits the document into chapters after each top-level paragraph that ends
+/// in a page break; the paragraph's text stays in the chapter it closes. The
+/// schema has no heading node, so a chapter is titled by its first non-empty
+/// paragraph and fold titles become its sub-headings.
+pub(super) fn chapters(doc: &PlainDoc, resource: &Resource) -> (Vec<Chapter>, UsedText) {
+    let mut writer = ChapterWriter {
+        resource,
+        used: UsedText::new(),
+        next_anchor: 0,
+    };
+    let inherited = doc.root.modifiers.clone();
+
+    let mut groups: Vec<Vec<&PlainNodeEntry>> = vec![Vec::new()];
+    for child in &doc.root.children {
+        if let Some(group) = groups.last_mut() {
+            group.push(child);
+        }
+        if ends_with_page_break(child) {
+            groups.push(Vec::new());
+        }
+    }
+    // Consecutive breaks leave groups holding only break paragraphs.
+    groups.retain(|group| !group.iter().all(|entry| is_bare_page_break(entry)));
+    if groups.is_empty() {
+        groups.push(Vec::new());
+    }
+
+    let chapters = groups
+        .into_iter()
+        .enumerate()
+        .map(|(idx, group)| {
+            let mut body = String::new();
+            let mut sections = Vec::new();
+            for entry in &group {
+                writer.block(entry, &inherited, &mut body, &mut sections, 2);
+            }
+            let title = group
+                .iter()
+                .find_map(|entry| {
+                    let text = plain_text(entry);
+                    let text = text.trim();
+                    (!text.is_empty()).then(|| truncate(text, 80))
+                })
+                .unwrap_or_else(|| format!("Chapter {}", idx + 1));
+            Chapter {
+                title,
+                body,
+                sections,
+            }
+        })
+        .collect();
+    (chapters, writer.used)
+}
+
+struct ChapterWriter<'a> {
+    resource: &'a Resource,
+    used: UsedText,
+    next_anchor: usize,
+}
+
+impl ChapterWriter<'_> {
+    /// `level` is the heading level a fold title at this depth gets.
+    fn block(
+        &mut self,
+        entry: &PlainNodeEntry,
+        inherited: &BTreeMap<ModifierType, Modifier>,
+        out: &mut String,
+        sections: &mut Vec<Section>,
+        level: u8,
+    ) {
+        let mut modifiers = inherited.clone();
+        modifiers.extend(entry.modifiers.iter().map(|(k, v)| (*k, v.clone())));
+
+        match &entry.node {
+            PlainNode::Text(t) => self.text(&t.text, &entry.modifiers, &modifiers, out),
+            PlainNode::HardBreak(_) => out.push_str("<br/>"),
+            PlainNode::Tab(_) => out.push('\t'),
+            PlainNode::Paragraph(_) => {
+                let _ = write!(out, "<p{}>", block_style(&entry.modifiers, self.resource));
+                self.children(entry, &modifiers, out, sections, level);
+                out.push_str("</p>");
+            }
+            PlainNode::BulletList(_) => {
+                self.container("ul", entry, &modifiers, out, sections, level)
+            }
+            PlainNode::OrderedList(_) => {
+                self.container("ol", entry, &modifiers, out, sections, level)
+            }
+            PlainNode::ListItem(_) => self.container("li", entry, &modifiers, out, sections, level),
+            PlainNode::Blockquote(_) => {
+                self.container("blockquote", entry, &modifiers, out, sections, level)
+            }
+            PlainNode::Callout(_) => {
+                self.container("aside", entry, &modifiers, out, sections, level)
+            }
+            PlainNode::Fold(_) => {
+                let anchor = format!("section-{}", self.next_anchor);
+                self.next_anchor += 1;
+                let title = entry
+                    .children
+                    .iter()
+                    .find(|child| matches!(child.node, PlainNode::FoldTitle(_)))
+                    .map(|child| plain_text(child).trim().to_string())
+                    .unwrap_or_default();
+                let mut section = Section {
+                    title,
+                    anchor: anchor.clone(),
+                    children: Vec::new(),
+                };
+                let _ = write!(out, r#"<section id="{anchor}">"#);
+                for child in &entry.children {
+                    self.block(child, &modifiers, out, &mut section.children, level + 1);
+                }
+                out.push_str("</section>");
+                sections.push(section);
+            }
+            PlainNode::FoldTitle(_) => {
+                // `level` is already one deeper than the fold's own depth.
+                let tag = format!("h{}", (level - 1).min(6));
+                self.container(&tag, entry, &modifiers, out, sections, level)
+            }
+            PlainNode::FoldContent(_) | PlainNode::Root(_) => {
+                self.children(entry, &modifiers, out, sections, level)
+            }
+            PlainNode::Table(_) => self.container("table", entry, &modifiers, out, sections, level),
+            PlainNode::TableRow(_) => self.container("tr", entry, &modifiers, out, sections, level),
+            PlainNode::TableCell(_) => {
+                self.container("td", entry, &modifiers, out, sections, level)
+            }
+            PlainNode::HorizontalRule(_) => out.push_str("<hr/>"),
+            // Media nodes only carry upload ids; their bytes are not part of the
+            // document.
+            PlainNode::Image(_)
+            | PlainNode::Embed(_)
+            | PlainNode::File(_)
+            | PlainNode::Archived(_)
+            | PlainNode::PageBreak(_)
+            | PlainNode::ColumnBreak(_)
+            | PlainNode::Unknown => {}
+        }
+    }
+
+    fn container(
+        &mut self,
+        tag: &str,
+        entry: &PlainNodeEntry,
+        modifiers: &BTreeMap<ModifierType, Modifier>,
+        out: &mut String,
+        sections: &mut Vec<Section>,
+        level: u8,
+    ) {
+        let _ = write!(out, "<{tag}>");
+        self.children(entry, modifiers, out, sections, level);
+        let _ = write!(out, "</{tag}>");
+    }
+
+    fn children(
+        &mut self,
+        entry: &PlainNodeEntry,
+        modifiers: &BTreeMap<ModifierType, Modifier>,
+        out: &mut String,
+        sections: &mut Vec<Section>,
+        level: u8,
+    ) {
+        for child in &entry.children {
+            self.block(child, modifiers, out, sections, level);
+        }
+    }
+
+    /// Inline markup comes from the clipboard HTML mapping; ruby, which it has
+    /// no element for, wraps the run.
+    fn text(
+        &mut self,
+        text: &str,
+        own: &BTreeMap<ModifierType, Modifier>,
+        effective: &BTreeMap<ModifierType, Modifier>,
+        out: &mut String,
+    ) {
+        let family = match effective.get(&ModifierType::FontFamily) {
+            Some(Modifier::FontFamily { value }) => value.clone(),
+            _ => DEFAULT_FONT_FAMILY.to_string(),
+        };
+        let weight = match effective.get(&ModifierType::FontWeight) {
+            Some(Modifier::FontWeight { value }) => *value,
+            _ => DEFAULT_FONT_WEIGHT,
+        };
+        let ruby = match own.get(&ModifierType::Ruby) {
+            Some(Modifier::Ruby { text }) => Some(text.as_str()),
+            _ => None,
+        };
+        let glyphs = self.used.entry((family, weight)).or_default();
+        glyphs.extend(text.chars());
+        glyphs.extend(ruby.into_iter().flat_map(str::chars));
+
+        let modifiers: Vec<Modifier> = own.values().cloned().collect();
+        match ruby {
+            Some(ruby) => {
+                out.push_str("<ruby>");
+                serialize_text(text, &modifiers, self.resource, out);
+                let _ = write!(out, "<rt>{}</rt></ruby>", html_escape(ruby));
+            }
+            None => serialize_text(text, &modifiers, self.resource, out),
+        }
+    }
+}
+
+/// Block modifiers as an inline `style` attribute, or nothing.
+fn block_style(modifiers: &BTreeMap<ModifierType, Modifier>, resource: &Resource) -> String {
+    let (mut declarations, text) = paragraph_declarations(modifiers);
+    declarations.extend(style_declarations(&text, resource));
+    if declarations.is_empty() {
+        String::new()
+    } else {
+        format!(r#" style="{}""#, html_escape(&declarations.join(";")))
+    }
+}
+
+/// CSS for the paragraph-level modifiers, which the clipboard mapping leaves
+/// out, and the modifiers left for it.
+pub(super) fn paragraph_declarations(
+    modifiers: &BTreeMap<ModifierType, Modifier>,
+) -> (Vec<String>, Vec<Modifier>) {
+    let mut declarations = Vec::new();
+    let mut rest = Vec::new();
+    for modifier in modifiers.values() {
+        match modifier {
+            Modifier::Alignment { value } => declarations.push(format!(
+                "text-align:{}",
+                match value {
+                    Alignment::Left => "left",
+                    Alignment::Center => "center",
+                    Alignment::Right => "right",
+                    Alignment::Justify => "justify",
+                }
+            )),
+            Modifier::Direction { value } => declarations.push(
+                match value {
+                    TextDirection::Auto => "unicode-bidi:plaintext",
+                    TextDirection::Ltr => "direction:ltr",
+                    TextDirection::Rtl => "direction:rtl",
+                }
+                .to_owned(),
+            ),
+            Modifier::LineHeight { value } => {
+                declarations.push(format!("line-height:{}", *value as f32 / 100.0))
+            }
+            Modifier::ParagraphIndent { value } => {
+                declarations.push(format!("text-indent:{}em", *value as f32 / 100.0))
+            }
+            Modifier::BlockGap { value } => {
+                declarations.push(format!("margin-bottom:{}em", *value as f32 / 100.0))
+            }
+            other => rest.push(other.clone()),
+        }
+    }
+    (declarations, rest)
+}
+
+fn plain_text(entry: &PlainNodeEntry) -> String {
+    let mut out = String::new();
+    collect_text(entry, &mut out);
+    out
+}
+
+fn collect_text(entry: &PlainNodeEntry, out: &mut String) {
+    match &entry.node {
+        PlainNode::Text(t) => out.push_str(&t.text),
+        PlainNode::HardBreak(_) | PlainNode::Tab(_) => out.push(' '),
+        _ => {
+            for child in &entry.children {
+                collect_text(child, out);
+            }
+        }
+    }
+}
+
+fn ends_with_page_break(entry: &PlainNodeEntry) -> bool {
+    matches!(entry.node, PlainNode::Paragraph(_))
+        && entry
+            .children
+            .last()
+            .is_some_and(|child| matches!(child.node, PlainNode::PageBreak(_)))
+}
+
+fn is_bare_page_break(entry: &PlainNodeEntry) -> bool {
+    ends_with_page_break(entry) && entry.children.len() == 1
+}
+
+fn truncate(text: &str, max_chars: usize) -> String {
+    match text.char_indices().nth(max_chars) {
+        Some((idx, _)) => format!("{}…", &text[..idx]),
+        None => text.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use editor_model::{PlainFoldNode, PlainFoldTitleNode, PlainPageBreakNode, PlainRootNode};
+
+    use super::*;
+
+    fn entry(node: PlainNode, children: Vec<PlainNodeEntry>) -> PlainNodeEntry {
+        PlainNodeEntry {
+            node,
+            modifiers: BTreeMap::new(),
+            carry: Vec::new(),
+            children,
+        }
+    }
+
+    fn text(value: &str, modifiers: Vec<Modifier>) -> PlainNodeEntry {
+        PlainNodeEntry {
+            modifiers: modifiers.into_iter().map(|m| (m.as_type(), m)).collect(),
+            ..entry(
+                PlainNode::Text(editor_model::PlainTextNode {
+                    text: value.to_string(),
+                }),
+                vec![],
+            )
+        }
+    }
+
+    fn paragraph(children: Vec<PlainNodeEntry>) -> PlainNodeEntry {
+        entry(PlainNode::Paragraph(Default::default()), children)
+    }
+
+    fn doc(children: Vec<PlainNodeEntry>) -> PlainDoc {
+        PlainDoc {
+            root: entry(PlainNode::Root(PlainRootNode::default()), children),
+        }
+    }
+
+    #[test]
+    fn page_breaks_split_chapters_titled_by_their_first_paragraph() {
+        let page_break = || entry(PlainNode::PageBreak(PlainPageBreakNode::default()), vec![]);
+        let doc = doc(vec![
+            paragraph(vec![text("  One  ", vec![])]),
+            paragraph(vec![text("body", vec![]), page_break()]),
+            paragraph(vec![page_break()]),
+            paragraph(vec![]),
+            paragraph(vec![text("Two", vec![])]),
+        ]);
+        let (chapters, _) = chapters(&doc, &Resource::new_test());
+
+        let titles: Vec<&str> = chapters.iter().map(|c| c.title.as_str()).collect();
+        assert_eq!(titles, ["One", "Two"]);
+        assert_eq!(chapters[0].body, "<p>  One  </p><p>body</p>");
+    }
+
+    #[test]
+    fn ruby_wraps_the_clipboard_markup() {
+        let doc = doc(vec![paragraph(vec![text(
+            "漢字",
+            vec![
+                Modifier::Bold,
+                Modifier::Ruby {
+                    text: "かんじ".to_string(),
+                },
+            ],
+        )])]);
+        let (chapters, used) = chapters(&doc, &Resource::new_test());
+
+        assert_eq!(
+            chapters[0].body,
+            "<p><ruby><strong>漢字</strong><rt>かんじ</rt></ruby></p>"
+        );
+        let glyphs = &used[&(DEFAULT_FONT_FAMILY.to_string(), DEFAULT_FONT_WEIGHT)];
+        assert!(glyphs.contains(&'漢') && glyphs.contains(&'か'));
+    }
+
+    #[test]
+    fn folds_become_anchored_sections_with_headings() {
+        let fold = |title: &str, content: Vec<PlainNodeEntry>| {
+            entry(
+                PlainNode::Fold(PlainFoldNode::default()),
+                vec![
+                    entry(
+                        PlainNode::FoldTitle(PlainFoldTitleNode::default()),
+                        vec![text(title, vec![])],
+                    ),
+                    entry(PlainNode::FoldContent(Default::default()), content),
+                ],
+            )
+        };
+        let doc = doc(vec![fold("Outer", vec![fold("Inner", vec![])])]);
+        let (chapters, _) = chapters(&doc, &Resource::new_test());
+
+        let chapter = &chapters[0];
+        assert_eq!(chapter.sections[0].title, "Outer");
+        assert_eq!(chapter.sections[0].children[0].title, "Inner");
+        assert!(
+            chapter
+                .body
+                .starts_with(r#"<section id="section-0"><h2>Outer</h2>"#)
+        );
+        assert!(
+            chapter
+                .body
+                .contains(r#"<section id="section-1"><h3>Inner</h3>"#)
+        );
+    }
+
+    #[test]
+    fn paragraph_modifiers_become_inline_css() {
+        let modifiers = BTreeMap::from([
+            (
+                ModifierType::Alignment,
+                Modifier::Alignment {
+                    value: Alignment::Center,
+                },
+            ),
+            (
+                ModifierType::LineHeight,
+                Modifier::LineHeight { value: 180 },
+            ),
+        ]);
+        assert_eq!(
+            block_style(&modifiers, &Resource::new_test()),
+            r#" style="line-height:1.8;text-align:center""#
+        );
+    }
+}
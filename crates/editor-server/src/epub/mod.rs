@@ -0,0 +1,451 @@
+mod chapter;
+
+use std::fmt::Write;
+
+use editor_clipboard::html::serialize::{html_escape, style_declarations};
+use editor_macros::ffi;
+use editor_model::{DEFAULT_FONT_FAMILY, PlainDoc};
+use editor_resource::Resource;
+use editor_zip::ZipWriter;
+use hashbrown::HashSet;
+use serde::{Deserialize, Serialize};
+use skrifa::{FontRef, MetadataProvider};
+
+use self::chapter::{Chapter, Section, UsedText, chapters, paragraph_declarations};
+use crate::ServerError;
+use crate::font::subset_font;
+
+#[ffi]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EpubMetadata {
+    pub title: String,
+    pub author: Option<String>,
+    /// BCP 47 tag; `ko` when unset.
+    pub language: Option<String>,
+    /// Unique identifier of the book. Derived from the content when unset.
+    pub identifier: Option<String>,
+    /// `dcterms:modified` as `CCYY-MM-DDThh:mm:ssZ`. The epoch when unset, so
+    /// the same document always builds the same file.
+    pub modified: Option<String>,
+}
+
+/// Builds an EPUB 3 book from a document: one XHTML chapter per page-break
+/// section, a navigation document, a stylesheet carrying the document's
+/// paragraph and text defaults, and subsets of every font face the text uses
+/// that the resource has loaded.
+pub fn build_epub(
+    doc: &PlainDoc,
+    metadata: &EpubMetadata,
+    resource: &Resource,
+) -> Result<Vec<u8>, ServerError> {
+    let (chapters, used) = chapters(doc, resource);
+    let fonts = embedded_fonts(&used, resource);
+
+    let identifier = metadata.identifier.clone().unwrap_or_else(|| {
+        let mut seed = metadata.title.clone();
+        for chapter in &chapters {
+            seed.push_str(&chapter.body);
+        }
+        format!(
+            "urn:typie:{:016x}",
+            rapidhash::v3::rapidhash_v3(seed.as_bytes())
+        )
+    });
+    let language = metadata.language.as_deref().unwrap_or("ko");
+
+    let mut zip = ZipWriter::new();
+    zip.stored("mimetype", b"application/epub+zip");
+    zip.deflated("META-INF/container.xml", CONTAINER_XML.as_bytes());
+    zip.deflated(
+        "OEBPS/content.opf",
+        package_document(metadata, &identifier, language, &chapters, &fonts).as_bytes(),
+    );
+    zip.deflated(
+        "OEBPS/nav.xhtml",
+        nav_document(&metadata.title, language, &chapters).as_bytes(),
+    );
+    zip.deflated(
+        "OEBPS/style.css",
+        stylesheet(doc, resource, &fonts).as_bytes(),
+    );
+    for (idx, chapter) in chapters.iter().enumerate() {
+        zip.deflated(
+            &format!("OEBPS/{}", chapter_href(idx)),
+            chapter_document(chapter, language).as_bytes(),
+        );
+    }
+    for font in &fonts {
+        zip.deflated(&format!("OEBPS/{}", font.href), &font.data);
+    }
+    Ok(zip.finish())
+}
+
+const CONTAINER_XML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<container version="1.0" xmlns="urn:oasis:names:tc:opendocument:xmlns:container">
+  <rootfiles>
+    <rootfile full-path="OEBPS/content.opf" media-type="application/oebps-package+xml"/>
+  </rootfiles>
+</container>
+"#;
+
+struct EmbeddedFont {
+    family: String,
+    weight: u16,
+    href: String,
+    data: Vec<u8>,
+}
+
+/// Faces that are not loaded or cannot be subset are left to the reading
+/// system's fallback.
+fn embedded_fonts(used: &UsedText, resource: &Resource) -> Vec<EmbeddedFont> {
+    let registry = &resource.font_registry;
+    let mut fonts = Vec::new();
+    for ((family, weight), chars) in used {
+        let Some(data) = registry
+            .intern_id(family)
+            .and_then(|id| registry.font_data(id, *weight))
+        else {
+            continue;
+        };
+        let Ok(font) = FontRef::new(data) else {
+            continue;
+        };
+        let charmap = font.charmap();
+        let gids: HashSet<u16> = chars
+            .iter()
+            .filter_map(|&ch| charmap.map(ch))
+            .filter_map(|gid| u16::try_from(gid.to_u32()).ok())
+            .collect();
+        let Ok(subset) = subset_font(data, &gids) else {
+            continue;
+        };
+        fonts.push(EmbeddedFont {
+            family: family.clone(),
+            weight: *weight,
+            href: format!("fonts/font-{}.ttf", fonts.len()),
+            data: subset,
+        });
+    }
+    fonts
+}
+
+fn chapter_href(idx: usize) -> String {
+    format!("chapter-{}.xhtml", idx + 1)
+}
+
+fn package_document(
+    metadata: &EpubMetadata,
+    identifier: &str,
+    language: &str,
+    chapters: &[Chapter],
+    fonts: &[EmbeddedFont],
+) -> String {
+    let mut opf = String::from(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<package xmlns="http://www.idpf.org/2007/opf" version="3.0" unique-identifier="book-id">
+  <metadata xmlns:dc="http://purl.org/dc/elements/1.1/">
+"#,
+    );
+    let _ = writeln!(
+        opf,
+        r#"    <dc:identifier id="book-id">{}</dc:identifier>"#,
+        html_escape(identifier)
+    );
+    let _ = writeln!(
+        opf,
+        "    <dc:title>{}</dc:title>",
+        html_escape(&metadata.title)
+    );
+    let _ = writeln!(
+        opf,
+        "    <dc:language>{}</dc:language>",
+        html_escape(language)
+    );
+    if let Some(author) = &metadata.author {
+        let _ = writeln!(opf, "    <dc:creator>{}</dc:creator>", html_escape(author));
+    }
+    let modified = metadata
+        .modified
+        .as_deref()
+        .unwrap_or("1970-01-01T00:00:00Z");
+    let _ = writeln!(
+        opf,
+        r#"    <meta property="dcterms:modified">{}</meta>"#,
+        html_escape(modified)
+    );
+    opf.push_str("  </metadata>\n  <manifest>\n");
+    opf.push_str(
+        r#"    <item id="nav" href="nav.xhtml" media-type="application/xhtml+xml" properties="nav"/>
+    <item id="style" href="style.css" media-type="text/css"/>
+"#,
+    );
+    for idx in 0..chapters.len() {
+        let _ = writeln!(
+            opf,
+            r#"    <item id="chapter-{}" href="{}" media-type="application/xhtml+xml"/>"#,
+            idx + 1,
+            chapter_href(idx)
+        );
+    }
+    for (idx, font) in fonts.iter().enumerate() {
+        let _ = writeln!(
+            opf,
+            r#"    <item id="font-{idx}" href="{}" media-type="font/ttf"/>"#,
+            font.href
+        );
+    }
+    opf.push_str("  </manifest>\n  <spine>\n");
+    for idx in 0..chapters.len() {
+        let _ = writeln!(opf, r#"    <itemref idref="chapter-{}"/>"#, idx + 1);
+    }
+    opf.push_str("  </spine>\n</package>\n");
+    opf
+}
+
+fn nav_document(title: &str, language: &str, chapters: &[Chapter]) -> String {
+    let mut nav = xhtml_head(title, language);
+    nav.push_str(r#"<nav epub:type="toc" id="toc"><ol>"#);
+    for (idx, chapter) in chapters.iter().enumerate() {
+        let href = chapter_href(idx);
+        let _ = write!(
+            nav,
+            r#"<li><a href="{href}">{}</a>"#,
+            html_escape(&chapter.title)
+        );
+        nav_sections(&mut nav, &href, &chapter.sections);
+        nav.push_str("</li>");
+    }
+    nav.push_str("</ol></nav></body></html>\n");
+    nav
+}
+
+fn nav_sections(nav: &mut String, href: &str, sections: &[Section]) {
+    let mut items = String::new();
+    nav_items(&mut items, href, sections);
+    if !items.is_empty() {
+        let _ = write!(nav, "<ol>{items}</ol>");
+    }
+}
+
+/// Untitled folds have nothing to show in a table of contents; their titled
+/// descendants are listed in their place.
+fn nav_items(items: &mut String, href: &str, sections: &[Section]) {
+    for section in sections {
+        if section.title.is_empty() {
+            nav_items(items, href, &section.children);
+            continue;
+        }
+        let _ = write!(
+            items,
+            r##"<li><a href="{href}#{}">{}</a>"##,
+            section.anchor,
+            html_escape(&section.title)
+        );
+        nav_sections(items, href, &section.children);
+        items.push_str("</li>");
+    }
+}
+
+fn chapter_document(chapter: &Chapter, language: &str) -> String {
+    let mut xhtml = xhtml_head(&chapter.title, language);
+    xhtml.push_str(&chapter.body);
+    xhtml.push_str("</body></html>\n");
+    xhtml
+}
+
+fn xhtml_head(title: &str, language: &str) -> String {
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE html>
+<html xmlns="http://www.w3.org/1999/xhtml" xmlns:epub="http://www.idpf.org/2007/ops" xml:lang="{lang}" lang="{lang}">
+<head><meta charset="UTF-8"/><title>{}</title><link rel="stylesheet" type="text/css" href="style.css"/></head>
+<body>"#,
+        html_escape(title),
+        lang = html_escape(language)
+    )
+}
+
+/// `@font-face` rules for the embedded subsets, then the document defaults:
+/// the root's text modifiers on `body` and its paragraph modifiers on `p`.
+fn stylesheet(doc: &PlainDoc, resource: &Resource, fonts: &[EmbeddedFont]) -> String {
+    let mut css = String::new();
+    for font in fonts {
+        let _ = writeln!(
+            css,
+            "@font-face {{ font-family: \"{}\"; font-weight: {}; src: url(\"{}\"); }}",
+            css_string(&font.family),
+            font.weight,
+            font.href
+        );
+    }
+
+    let (paragraph, text) = paragraph_declarations(&doc.root.modifiers);
+    let mut body = vec![format!("font-family:\"{DEFAULT_FONT_FAMILY}\"")];
+    body.extend(style_declarations(&text, resource));
+    let _ = writeln!(css, "body {{ {}; }}", body.join("; "));
+    if !paragraph.is_empty() {
+        let _ = writeln!(css, "p {{ {}; }}", paragraph.join("; "));
+    }
+    css.push_str("ruby rt { font-size: 0.5em; }\n");
+    css
+}
+
+fn css_string(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use editor_model::{
+        Modifier, PlainNode, PlainNodeEntry, PlainPageBreakNode, PlainParagraphNode, PlainRootNode,
+        PlainTextNode,
+    };
+    use editor_resource::{ResourceSource, compress_zstd, prepare_font_base};
+    use editor_zip::ZipArchive;
+
+    use super::*;
+
+    const TEXT_FONT: &[u8] = include_bytes!("../../../../assets/Pretendard-Regular.ttf");
+
+    fn resource_with_font() -> Resource {
+        let prepared =
+            prepare_font_base(&compress_zstd(TEXT_FONT)).expect("test font must be valid");
+        let mut source = ResourceSource::new_test();
+        source
+            .insert_font_base(DEFAULT_FONT_FAMILY, 400, prepared)
+            .expect("test font must change resources");
+        Resource::from_snapshot(source.snapshot())
+    }
+
+    fn entry(node: PlainNode, children: Vec<PlainNodeEntry>) -> PlainNodeEntry {
+        PlainNodeEntry {
+            node,
+            modifiers: BTreeMap::new(),
+            carry: Vec::new(),
+            children,
+        }
+    }
+
+    fn paragraph(text: &str) -> PlainNodeEntry {
+        entry(
+            PlainNode::Paragraph(PlainParagraphNode::default()),
+            vec![entry(
+                PlainNode::Text(PlainTextNode {
+                    text: text.to_string(),
+                }),
+                vec![],
+            )],
+        )
+    }
+
+    fn sample_doc() -> PlainDoc {
+        let mut first = paragraph("First");
+        first.children.push(entry(
+            PlainNode::PageBreak(PlainPageBreakNode::default()),
+            vec![],
+        ));
+        let mut root = entry(
+            PlainNode::Root(PlainRootNode::default()),
+            vec![first, paragraph("Second & more")],
+        );
+        root.modifiers.insert(
+            editor_model::ModifierType::LineHeight,
+            Modifier::LineHeight { value: 180 },
+        );
+        PlainDoc { root }
+    }
+
+    fn entry_text<'a>(entries: &'a [(String, Vec<u8>)], name: &str) -> &'a str {
+        let (_, data) = entries.iter().find(|(n, _)| n == name).unwrap();
+        std::str::from_utf8(data).unwrap()
+    }
+
+    #[test]
+    fn container_starts_with_an_uncompressed_mimetype() {
+        let metadata = EpubMetadata {
+            title: "Book".to_string(),
+            ..Default::default()
+        };
+        let epub = build_epub(&sample_doc(), &metadata, &Resource::new_test()).unwrap();
+
+        assert_eq!(&epub[30..38], b"mimetype");
+        assert_eq!(&epub[38..58], b"application/epub+zip");
+        let entries = ZipArchive::new(&epub).unwrap().read_all().unwrap();
+        let names: Vec<&str> = entries.iter().map(|(n, _)| n.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "mimetype",
+                "META-INF/container.xml",
+                "OEBPS/content.opf",
+                "OEBPS/nav.xhtml",
+                "OEBPS/style.css",
+                "OEBPS/chapter-1.xhtml",
+                "OEBPS/chapter-2.xhtml",
+            ]
+        );
+    }
+
+    #[test]
+    fn package_lists_chapters_in_reading_order_with_a_nav() {
+        let metadata = EpubMetadata {
+            title: "Book".to_string(),
+            author: Some("Writer".to_string()),
+            ..Default::default()
+        };
+        let epub = build_epub(&sample_doc(), &metadata, &Resource::new_test()).unwrap();
+        let entries = ZipArchive::new(&epub).unwrap().read_all().unwrap();
+
+        let opf = entry_text(&entries, "OEBPS/content.opf");
+        assert!(opf.contains("<dc:title>Book</dc:title>"));
+        assert!(opf.contains("<dc:creator>Writer</dc:creator>"));
+        assert!(opf.contains("<dc:language>ko</dc:language>"));
+        assert!(opf.contains(r#"<dc:identifier id="book-id">urn:typie:"#));
+        assert!(opf.contains("<itemref idref=\"chapter-1\"/>\n    <itemref idref=\"chapter-2\"/>"));
+
+        let nav = entry_text(&entries, "OEBPS/nav.xhtml");
+        assert!(nav.contains(r#"<li><a href="chapter-1.xhtml">First</a></li>"#));
+        assert!(nav.contains(r#"<li><a href="chapter-2.xhtml">Second &amp; more</a></li>"#));
+
+        assert!(entry_text(&entries, "OEBPS/chapter-1.xhtml").contains("<p>First</p>"));
+        let chapter = entry_text(&entries, "OEBPS/chapter-2.xhtml");
+        assert!(chapter.contains("<title>Second &amp; more</title>"));
+        assert!(chapter.contains("<p>Second &amp; more</p>"));
+
+        let css = entry_text(&entries, "OEBPS/style.css");
+        assert!(css.contains("p { line-height:1.8; }"));
+
+        assert_eq!(
+            epub,
+            build_epub(&sample_doc(), &metadata, &Resource::new_test()).unwrap()
+        );
+    }
+
+    #[test]
+    fn used_fonts_are_subset_and_declared() {
+        let metadata = EpubMetadata {
+            title: "Book".to_string(),
+            ..Default::default()
+        };
+        let epub = build_epub(&sample_doc(), &metadata, &resource_with_font()).unwrap();
+        let entries = ZipArchive::new(&epub).unwrap().read_all().unwrap();
+
+        let (_, font) = entries
+            .iter()
+            .find(|(n, _)| n == "OEBPS/fonts/font-0.ttf")
+            .expect("the default face is embedded");
+        assert!(font.len() < TEXT_FONT.len());
+        let subset = FontRef::new(font).unwrap();
+        assert!(subset.charmap().map('F').is_some());
+
+        let css = entry_text(&entries, "OEBPS/style.css");
+        assert!(css.contains(
+            r#"@font-face { font-family: "Pretendard"; font-weight: 400; src: url("fonts/font-0.ttf"); }"#
+        ));
+        let opf = entry_text(&entries, "OEBPS/content.opf");
+        assert!(opf.contains(r#"href="fonts/font-0.ttf" media-type="font/ttf""#));
+    }
+}
@@ -5,4 +5,10 @@ pub enum ServerError {
 
     #[error("encoding failed: {0}")]
     EncodingFailed(String),
+
+    #[error("invalid document: {0}")]
+    InvalidDocument(String),
+
+    #[error("page {0} is out of range")]
+    PageOutOfRange(usize),
 }
@@ -54,16 +54,7 @@ pub fn build_font(
     let has_cbdt = font.table_data(Tag::new(b"CBDT")).is_some()
         && font.table_data(Tag::new(b"CBLC")).is_some();
 
-    if font.table_data(Tag::new(b"CFF2")).is_some() {
-        return Err(ServerError::InvalidFont(
-            "unsplittable font: CFF2 unsupported".into(),
-        ));
-    }
-    if font.table_data(Tag::new(b"VARC")).is_some() {
-        return Err(ServerError::InvalidFont(
-            "unsplittable font: VARC unsupported".into(),
-        ));
-    }
+    reject_unsplittable(&font)?;
 
     if !has_glyf && !has_cbdt {
         if font.table_data(Tag::new(b"CFF ")).is_none() {
@@ -287,6 +278,103 @@ pub fn build_font(
     })
 }
 
+/// A standalone TrueType font that keeps only `gids`, `.notdef` and the
+/// components of composite glyphs, for embedding into exported documents.
+/// Glyph ids are preserved so shaped runs and the original cmap index it
+/// unchanged; every other glyph is left empty. CFF fonts go through the same glyf conversion as
+/// [`build_font`].
+pub(crate) fn subset_font(font_data: &[u8], gids: &HashSet<u16>) -> Result<Vec<u8>, ServerError> {
+    let font = FontRef::new(font_data).map_err(|e| ServerError::InvalidFont(e.to_string()))?;
+    reject_unsplittable(&font)?;
+
+    if font.glyf().is_err() {
+        if font.table_data(Tag::new(b"CFF ")).is_none() {
+            return Err(ServerError::InvalidFont(
+                "unsplittable font: no glyf/CFF table".into(),
+            ));
+        }
+        let converted = convert_to_glyf(&font)?;
+        return subset_font(&converted, gids);
+    }
+
+    let num_glyphs = font
+        .maxp()
+        .map_err(|e| ServerError::InvalidFont(e.to_string()))?
+        .num_glyphs();
+    let loca = font
+        .loca(None)
+        .map_err(|e| ServerError::InvalidFont(e.to_string()))?;
+    let glyf = font
+        .glyf()
+        .map_err(|e| ServerError::InvalidFont(e.to_string()))?;
+    let glyf_raw = font
+        .table_data(Tag::new(b"glyf"))
+        .ok_or_else(|| ServerError::InvalidFont("glyf missing".into()))?;
+    let glyf_bytes = glyf_raw.as_ref();
+
+    let mut keep: HashSet<u16> = gids.iter().copied().filter(|&g| g < num_glyphs).collect();
+    keep.insert(0);
+    let mut deps: HashSet<u16> = HashSet::new();
+    for &gid in &keep {
+        deps.extend(resolve_composite_deps(&loca, &glyf, gid, num_glyphs));
+    }
+    keep.extend(deps);
+
+    let mut subset_glyf: Vec<u8> = Vec::new();
+    let mut subset_loca: Vec<u8> = Vec::with_capacity((usize::from(num_glyphs) + 1) * 4);
+    for gid in 0..num_glyphs {
+        subset_loca.extend_from_slice(&(subset_glyf.len() as u32).to_be_bytes());
+        if !keep.contains(&gid) {
+            continue;
+        }
+        let start = loca.get_raw(gid as usize).unwrap_or(0) as usize;
+        let end = loca.get_raw(gid as usize + 1).unwrap_or(0) as usize;
+        if start < end && end <= glyf_bytes.len() {
+            subset_glyf.extend_from_slice(&glyf_bytes[start..end]);
+            subset_glyf.resize(subset_glyf.len().next_multiple_of(4), 0);
+        }
+    }
+    subset_loca.extend_from_slice(&(subset_glyf.len() as u32).to_be_bytes());
+
+    // The rebuilt loca always uses long offsets.
+    let mut head = font
+        .table_data(Tag::new(b"head"))
+        .ok_or_else(|| ServerError::InvalidFont("head missing".into()))?
+        .as_ref()
+        .to_vec();
+    if head.len() < 54 {
+        return Err(ServerError::InvalidFont("head too short".into()));
+    }
+    head[50..52].copy_from_slice(&1i16.to_be_bytes());
+
+    let mut builder = FontBuilder::new();
+    builder.add_raw(Tag::new(b"head"), head);
+    builder.add_raw(Tag::new(b"glyf"), subset_glyf);
+    builder.add_raw(Tag::new(b"loca"), subset_loca);
+    for tag in [
+        b"hhea", b"hmtx", b"maxp", b"cmap", b"OS/2", b"name", b"post", b"cvt ", b"fpgm", b"prep",
+    ] {
+        if let Some(data) = font.table_data(Tag::new(tag)) {
+            builder.add_raw(Tag::new(tag), data.as_ref());
+        }
+    }
+    Ok(builder.build())
+}
+
+fn reject_unsplittable(font: &FontRef) -> Result<(), ServerError> {
+    if font.table_data(Tag::new(b"CFF2")).is_some() {
+        return Err(ServerError::InvalidFont(
+            "unsplittable font: CFF2 unsupported".into(),
+        ));
+    }
+    if font.table_data(Tag::new(b"VARC")).is_some() {
+        return Err(ServerError::InvalidFont(
+            "unsplittable font: VARC unsupported".into(),
+        ));
+    }
+    Ok(())
+}
+
 const MANIFEST_MAX_BYTES: usize = 1024 * 1024;
 
 pub fn build_font_manifest(coverages: &[Vec<u32>]) -> Result<Vec<u8>, ServerError> {
@@ -748,10 +836,15 @@ mod tests {
     }
 
     fn draw_points(data: &[u8], ch: char) -> Vec<(u8, f32, f32)> {
+        let font = FontRef::new(data).unwrap();
+        let gid = font.charmap().map(ch).unwrap();
+        draw_gid_points(data, gid)
+    }
+
+    fn draw_gid_points(data: &[u8], gid: GlyphId) -> Vec<(u8, f32, f32)> {
         use skrifa::instance::{LocationRef, Size};
         use skrifa::outline::DrawSettings;
         let font = FontRef::new(data).unwrap();
-        let gid = font.charmap().map(ch).unwrap();
         let glyph = font.outline_glyphs().get(gid).unwrap();
         let mut pen = RecordingPen::default();
         glyph
@@ -832,11 +925,39 @@ mod tests {
         assert_eq!(a.hash, b.hash);
     }
 
+    #[test]
+    fn subset_font_keeps_only_requested_glyphs_at_their_ids() {
+        let converted = convert_to_glyf(&FontRef::new(CFF_FIXTURE).unwrap()).unwrap();
+        let font = FontRef::new(&converted).unwrap();
+        let a = font.charmap().map('A').unwrap();
+        let b = font.charmap().map('B').unwrap();
+
+        let subset = subset_font(CFF_FIXTURE, &HashSet::from([a.to_u32() as u16])).unwrap();
+        assert!(subset.len() < converted.len());
+        let subset_ref = FontRef::new(&subset).unwrap();
+        assert_eq!(
+            subset_ref.maxp().unwrap().num_glyphs(),
+            font.maxp().unwrap().num_glyphs()
+        );
+        assert_eq!(draw_gid_points(&subset, a), draw_points(&converted, 'A'));
+        assert!(draw_gid_points(&subset, b).is_empty());
+        assert_eq!(subset_ref.charmap().map('A'), Some(a));
+    }
+
     const CFF2_FIXTURE: &[u8] = include_bytes!(concat!(
         env!("CARGO_MANIFEST_DIR"),
         "/assets/SourceSans3VF-Upright.otf"
     ));
 
+    #[test]
+    fn subset_font_rejects_cff2() {
+        let result = subset_font(CFF2_FIXTURE, &HashSet::from([1]));
+        assert!(
+            matches!(&result, Err(ServerError::InvalidFont(msg)) if msg.contains("unsplittable font")),
+            "{result:?}"
+        );
+    }
+
     #[test]
     fn build_font_rejects_cff2() {
         let result = build_font(CFF2_FIXTURE, &[vec![0x41]]);
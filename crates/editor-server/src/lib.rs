@@ -1,6 +1,10 @@
 editor_macros::preamble!();
 
+pub mod docx;
+pub mod epub;
 mod error;
 pub mod font;
+pub mod pdf;
+pub mod thumbnail;
 
 pub use error::*;
@@ -0,0 +1,363 @@
+use std::collections::BTreeSet;
+use std::fmt::Write;
+use std::sync::Arc;
+
+use editor_renderer::vector::types::{
+    VectorFillRule, VectorGlyph, VectorLineCap, VectorLineJoin, VectorOp, VectorPathCommand,
+};
+use editor_resource::FontRegistry;
+use skrifa::instance::{LocationRef, Size};
+use skrifa::outline::{DrawSettings, OutlinePen};
+use skrifa::{FontRef, GlyphId, MetadataProvider};
+
+use super::PT_PER_PX;
+use super::font::FontSet;
+use super::object::num;
+
+/// One page's content stream and the resources it names: fonts as
+/// `/F{font set index}`, images as `/Im{position}` and constant alpha states
+/// as `/GS{alpha}`.
+pub(super) struct PageContent<'a> {
+    pub data: String,
+    pub fonts: BTreeSet<usize>,
+    pub images: Vec<PageImage<'a>>,
+    pub alphas: BTreeSet<u8>,
+}
+
+pub(super) struct PageImage<'a> {
+    pub data: &'a Arc<[u8]>,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Display-list coordinates are layout pixels with y pointing down; the
+/// content stream starts by mapping them onto the page's points.
+pub(super) fn page_content<'a>(
+    ops: &'a [VectorOp],
+    page_height: f32,
+    fonts: &FontSet,
+    registry: &FontRegistry,
+) -> PageContent<'a> {
+    let mut content = PageContent {
+        data: String::new(),
+        fonts: BTreeSet::new(),
+        images: Vec::new(),
+        alphas: BTreeSet::new(),
+    };
+    let _ = writeln!(
+        content.data,
+        "{} 0 0 {} 0 {} cm",
+        num(PT_PER_PX),
+        num(-PT_PER_PX),
+        num(page_height * PT_PER_PX)
+    );
+    for op in ops {
+        content.op(op, fonts, registry);
+    }
+    content
+}
+
+impl<'a> PageContent<'a> {
+    fn op(&mut self, op: &'a VectorOp, fonts: &FontSet, registry: &FontRegistry) {
+        match op {
+            VectorOp::FillPath {
+                path,
+                color,
+                fill_rule,
+            } => {
+                self.begin(color[3]);
+                self.color(*color, "rg");
+                write_path(&mut self.data, path);
+                self.data.push_str(match fill_rule {
+                    VectorFillRule::Winding => "f\n",
+                    VectorFillRule::EvenOdd => "f*\n",
+                });
+                self.end(color[3]);
+            }
+            VectorOp::StrokePath {
+                path,
+                color,
+                width,
+                line_cap,
+                line_join,
+            } => {
+                let cap = match line_cap {
+                    VectorLineCap::Butt => 0,
+                    VectorLineCap::Round => 1,
+                    VectorLineCap::Square => 2,
+                };
+                let join = match line_join {
+                    VectorLineJoin::Miter => 0,
+                    VectorLineJoin::Round => 1,
+                    VectorLineJoin::Bevel => 2,
+                };
+                self.begin(color[3]);
+                self.color(*color, "RG");
+                let _ = writeln!(self.data, "{} w {cap} J {join} j", num(*width));
+                write_path(&mut self.data, path);
+                self.data.push_str("S\n");
+                self.end(color[3]);
+            }
+            VectorOp::Image {
+                data,
+                width,
+                height,
+                x,
+                y,
+                render_width,
+                render_height,
+            } => {
+                let len = *width as usize * *height as usize * 4;
+                if len == 0 || data.len() != len {
+                    return;
+                }
+                let index = self.images.len();
+                self.images.push(PageImage {
+                    data,
+                    width: *width,
+                    height: *height,
+                });
+                // Image space has its first row at the top of the unit square, so
+                // the square is flipped back against the page's y-down mapping.
+                let _ = writeln!(
+                    self.data,
+                    "q {} 0 0 {} {} {} cm /Im{index} Do Q",
+                    num(*render_width),
+                    num(-render_height),
+                    num(*x),
+                    num(y + render_height)
+                );
+            }
+            VectorOp::Glyphs {
+                family_id,
+                weight,
+                size,
+                color,
+                glyphs,
+                ..
+            } => {
+                self.begin(color[3]);
+                self.color(*color, "rg");
+                match fonts.index(*family_id, *weight) {
+                    Some(font) => self.text(font, *size, glyphs),
+                    None => self.glyph_outlines(registry, *family_id, *weight, *size, glyphs),
+                }
+                self.end(color[3]);
+            }
+        }
+    }
+
+    fn begin(&mut self, alpha: u8) {
+        if alpha < 255 {
+            self.alphas.insert(alpha);
+            let _ = writeln!(self.data, "q /GS{alpha} gs");
+        }
+    }
+
+    fn end(&mut self, alpha: u8) {
+        if alpha < 255 {
+            self.data.push_str("Q\n");
+        }
+    }
+
+    fn color(&mut self, [r, g, b, _]: [u8; 4], operator: &str) {
+        let channel = |c: u8| num(f32::from(c) / 255.0);
+        let _ = writeln!(
+            self.data,
+            "{} {} {} {operator}",
+            channel(r),
+            channel(g),
+            channel(b)
+        );
+    }
+
+    /// Each glyph gets its own text matrix, so shaped positions are kept exactly
+    /// and the font's advances never matter. The matrix flips y back so glyphs
+    /// stand upright under the page's y-down mapping.
+    fn text(&mut self, font: usize, size: f32, glyphs: &[VectorGlyph]) {
+        self.fonts.insert(font);
+        let _ = writeln!(self.data, "BT /F{font} 1 Tf");
+        for glyph in glyphs {
+            let Ok(gid) = u16::try_from(glyph.id) else {
+                continue;
+            };
+            let _ = writeln!(
+                self.data,
+                "{} 0 0 {} {} {} Tm <{gid:04X}> Tj",
+                num(size),
+                num(-size),
+                num(glyph.x),
+                num(glyph.y)
+            );
+        }
+        self.data.push_str("ET\n");
+    }
+
+    fn glyph_outlines(
+        &mut self,
+        registry: &FontRegistry,
+        family_id: u16,
+        weight: u16,
+        size: f32,
+        glyphs: &[VectorGlyph],
+    ) {
+        let Some(data) = registry.font_data(family_id, weight) else {
+            return;
+        };
+        let Ok(font) = FontRef::from_index(data, 0) else {
+            return;
+        };
+        let outlines = font.outline_glyphs();
+        let mut pen = GlyphPen {
+            out: &mut self.data,
+            origin: (0.0, 0.0),
+            current: (0.0, 0.0),
+            drawn: false,
+        };
+        for glyph in glyphs {
+            let Some(outline) = outlines.get(GlyphId::new(glyph.id)) else {
+                continue;
+            };
+            pen.origin = (glyph.x, glyph.y);
+            let settings = DrawSettings::unhinted(Size::new(size), LocationRef::default());
+            let _ = outline.draw(settings, &mut pen);
+        }
+        if pen.drawn {
+            self.data.push_str("f\n");
+        }
+    }
+}
+
+fn write_path(out: &mut String, path: &[VectorPathCommand]) {
+    let mut current = (0.0, 0.0);
+    let mut start = (0.0, 0.0);
+    for cmd in path {
+        match *cmd {
+            VectorPathCommand::MoveTo { x, y } => {
+                push_op(out, &[(x, y)], "m");
+                current = (x, y);
+                start = current;
+            }
+            VectorPathCommand::LineTo { x, y } => {
+                push_op(out, &[(x, y)], "l");
+                current = (x, y);
+            }
+            VectorPathCommand::QuadTo { cx, cy, x, y } => {
+                let (c1, c2) = quad_to_cubic(current, (cx, cy), (x, y));
+                push_op(out, &[c1, c2, (x, y)], "c");
+                current = (x, y);
+            }
+            VectorPathCommand::CubicTo {
+                c1x,
+                c1y,
+                c2x,
+                c2y,
+                x,
+                y,
+            } => {
+                push_op(out, &[(c1x, c1y), (c2x, c2y), (x, y)], "c");
+                current = (x, y);
+            }
+            VectorPathCommand::ClosePath => {
+                out.push_str("h\n");
+                current = start;
+            }
+        }
+    }
+}
+
+fn push_op(out: &mut String, points: &[(f32, f32)], operator: &str) {
+    for (x, y) in points {
+        let _ = write!(out, "{} {} ", num(*x), num(*y));
+    }
+    out.push_str(operator);
+    out.push('\n');
+}
+
+/// PDF paths have no quadratic segments; the cubic with control points two
+/// thirds of the way to the quadratic one traces the same curve.
+fn quad_to_cubic(
+    from: (f32, f32),
+    control: (f32, f32),
+    to: (f32, f32),
+) -> ((f32, f32), (f32, f32)) {
+    let toward = |p: (f32, f32)| {
+        (
+            p.0 + (control.0 - p.0) * 2.0 / 3.0,
+            p.1 + (control.1 - p.1) * 2.0 / 3.0,
+        )
+    };
+    (toward(from), toward(to))
+}
+
+/// Writes a glyph outline in font space (y up, scaled to the run size) into
+/// page space at the glyph origin.
+struct GlyphPen<'a> {
+    out: &'a mut String,
+    origin: (f32, f32),
+    current: (f32, f32),
+    drawn: bool,
+}
+
+impl GlyphPen<'_> {
+    fn point(&self, x: f32, y: f32) -> (f32, f32) {
+        (self.origin.0 + x, self.origin.1 - y)
+    }
+}
+
+impl OutlinePen for GlyphPen<'_> {
+    fn move_to(&mut self, x: f32, y: f32) {
+        let p = self.point(x, y);
+        push_op(self.out, &[p], "m");
+        self.current = p;
+        self.drawn = true;
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        let p = self.point(x, y);
+        push_op(self.out, &[p], "l");
+        self.current = p;
+    }
+
+    fn quad_to(&mut self, cx0: f32, cy0: f32, x: f32, y: f32) {
+        let p = self.point(x, y);
+        let (c1, c2) = quad_to_cubic(self.current, self.point(cx0, cy0), p);
+        push_op(self.out, &[c1, c2, p], "c");
+        self.current = p;
+    }
+
+    fn curve_to(&mut self, cx0: f32, cy0: f32, cx1: f32, cy1: f32, x: f32, y: f32) {
+        let p = self.point(x, y);
+        let (c1, c2) = (self.point(cx0, cy0), self.point(cx1, cy1));
+        push_op(self.out, &[c1, c2, p], "c");
+        self.current = p;
+    }
+
+    fn close(&mut self) {
+        self.out.push_str("h\n");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quads_become_equivalent_cubics() {
+        let mut out = String::new();
+        write_path(
+            &mut out,
+            &[
+                VectorPathCommand::MoveTo { x: 0.0, y: 0.0 },
+                VectorPathCommand::QuadTo {
+                    cx: 3.0,
+                    cy: 3.0,
+                    x: 6.0,
+                    y: 0.0,
+                },
+                VectorPathCommand::ClosePath,
+            ],
+        );
+        assert_eq!(out, "0 0 m\n2 2 4 2 6 0 c\nh\n");
+    }
+}
@@ -0,0 +1,281 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use editor_renderer::vector::types::{VectorDocument, VectorOp};
+use editor_resource::FontRegistry;
+use hashbrown::{HashMap, HashSet};
+use skrifa::instance::{LocationRef, Size};
+use skrifa::string::StringId;
+use skrifa::{FontRef, GlyphId, MetadataProvider};
+
+use super::object::{ObjectWriter, Ref, name, num, utf16_hex};
+use crate::font::subset_font;
+
+/// The fonts behind a document's glyph runs that could be subset for
+/// embedding, each written once as a CID-keyed TrueType font. Runs in any
+/// other font are drawn as outlines instead.
+pub(super) struct FontSet {
+    fonts: Vec<(Ref, EmbeddedFont)>,
+    index: HashMap<(u16, u16), usize>,
+}
+
+struct EmbeddedFont {
+    base_font: String,
+    subset: Vec<u8>,
+    /// Text each glyph stands for, for the ToUnicode map.
+    glyphs: BTreeMap<u16, String>,
+    widths: Vec<(u16, f32)>,
+    ascent: f32,
+    descent: f32,
+    cap_height: f32,
+    bbox: [f32; 4],
+}
+
+impl FontSet {
+    pub(super) fn new(
+        document: &VectorDocument,
+        registry: &FontRegistry,
+        writer: &mut ObjectWriter,
+    ) -> Self {
+        let mut used: BTreeMap<(u16, u16), BTreeMap<u16, String>> = BTreeMap::new();
+        let mut reverse_cmaps: HashMap<(u16, u16), HashMap<u16, char>> = HashMap::new();
+        for page in &document.pages {
+            for op in &page.ops {
+                let VectorOp::Glyphs {
+                    family_id,
+                    weight,
+                    glyphs,
+                    text,
+                    ..
+                } = op
+                else {
+                    continue;
+                };
+                let key = (*family_id, *weight);
+                let Some(data) = registry.font_data(key.0, key.1) else {
+                    continue;
+                };
+                let cmap = reverse_cmaps
+                    .entry(key)
+                    .or_insert_with(|| reverse_cmap(data));
+                let entries = used.entry(key).or_default();
+                for glyph in glyphs {
+                    let Ok(gid) = u16::try_from(glyph.id) else {
+                        continue;
+                    };
+                    // Glyphs outside the cmap (ligatures, alternates) can only be
+                    // attributed when they make up the whole run.
+                    let unicode = match cmap.get(&gid) {
+                        Some(ch) => ch.to_string(),
+                        None if glyphs.len() == 1 => text.clone(),
+                        None => String::new(),
+                    };
+                    let slot = entries.entry(gid).or_default();
+                    if slot.is_empty() {
+                        *slot = unicode;
+                    }
+                }
+            }
+        }
+
+        let mut fonts = Vec::new();
+        let mut index = HashMap::new();
+        for ((family_id, weight), glyphs) in used {
+            let Some(data) = registry.font_data(family_id, weight) else {
+                continue;
+            };
+            let gids: HashSet<u16> = glyphs.keys().copied().collect();
+            let Ok(subset) = subset_font(data, &gids) else {
+                continue;
+            };
+            let Some(font) = EmbeddedFont::new(
+                subset_tag(fonts.len()),
+                registry.family_name(family_id),
+                subset,
+                glyphs,
+            ) else {
+                continue;
+            };
+            index.insert((family_id, weight), fonts.len());
+            fonts.push((writer.alloc(), font));
+        }
+
+        Self { fonts, index }
+    }
+
+    /// The resource index of an embedded font, named `/F{index}` in content.
+    pub(super) fn index(&self, family_id: u16, weight: u16) -> Option<usize> {
+        self.index.get(&(family_id, weight)).copied()
+    }
+
+    pub(super) fn font_ref(&self, index: usize) -> Ref {
+        self.fonts[index].0
+    }
+
+    pub(super) fn write(&self, writer: &mut ObjectWriter) {
+        for (font_ref, font) in &self.fonts {
+            font.write(*font_ref, writer);
+        }
+    }
+}
+
+impl EmbeddedFont {
+    fn new(
+        tag: String,
+        family_name: &str,
+        subset: Vec<u8>,
+        glyphs: BTreeMap<u16, String>,
+    ) -> Option<Self> {
+        let font = FontRef::new(&subset).ok()?;
+        let metrics = font.metrics(Size::unscaled(), LocationRef::default());
+        let scale = 1000.0 / f32::from(metrics.units_per_em.max(1));
+        let glyph_metrics = font.glyph_metrics(Size::unscaled(), LocationRef::default());
+        let widths = glyphs
+            .keys()
+            .map(|&gid| {
+                let advance = glyph_metrics
+                    .advance_width(GlyphId::new(u32::from(gid)))
+                    .unwrap_or(0.0);
+                (gid, advance * scale)
+            })
+            .collect();
+        let ascent = metrics.ascent * scale;
+        let descent = metrics.descent * scale;
+        let bbox = metrics.bounds.map_or([0.0, descent, 1000.0, ascent], |b| {
+            [
+                b.x_min * scale,
+                b.y_min * scale,
+                b.x_max * scale,
+                b.y_max * scale,
+            ]
+        });
+        let postscript_name = font
+            .localized_strings(StringId::POSTSCRIPT_NAME)
+            .english_or_first()
+            .map(|s| name(&s.to_string()))
+            .filter(|s| !s.is_empty())
+            .unwrap_or_else(|| name(family_name));
+
+        Some(Self {
+            base_font: format!("{tag}+{postscript_name}"),
+            widths,
+            ascent,
+            descent,
+            cap_height: metrics.cap_height.map_or(ascent, |h| h * scale),
+            bbox,
+            subset,
+            glyphs,
+        })
+    }
+
+    fn write(&self, font_ref: Ref, writer: &mut ObjectWriter) {
+        let cid_ref = writer.alloc();
+        let descriptor_ref = writer.alloc();
+        let file_ref = writer.alloc();
+        let to_unicode_ref = writer.alloc();
+        let base_font = &self.base_font;
+
+        writer.object(
+            font_ref,
+            &format!(
+                "<< /Type /Font /Subtype /Type0 /BaseFont /{base_font} /Encoding /Identity-H \
+                 /DescendantFonts [{cid_ref}] /ToUnicode {to_unicode_ref} >>"
+            ),
+        );
+
+        let mut widths = String::new();
+        for (gid, width) in &self.widths {
+            let _ = write!(widths, " {gid} [{}]", num(*width));
+        }
+        writer.object(
+            cid_ref,
+            &format!(
+                "<< /Type /Font /Subtype /CIDFontType2 /BaseFont /{base_font} \
+                 /CIDSystemInfo << /Registry (Adobe) /Ordering (Identity) /Supplement 0 >> \
+                 /FontDescriptor {descriptor_ref} /CIDToGIDMap /Identity /W [{widths} ] >>"
+            ),
+        );
+
+        let [x_min, y_min, x_max, y_max] = self.bbox.map(num);
+        writer.object(
+            descriptor_ref,
+            &format!(
+                "<< /Type /FontDescriptor /FontName /{base_font} /Flags 4 \
+                 /FontBBox [{x_min} {y_min} {x_max} {y_max}] /ItalicAngle 0 /Ascent {} \
+                 /Descent {} /CapHeight {} /StemV 80 /FontFile2 {file_ref} >>",
+                num(self.ascent),
+                num(self.descent),
+                num(self.cap_height)
+            ),
+        );
+
+        writer.stream(
+            file_ref,
+            &format!("/Length1 {}", self.subset.len()),
+            &self.subset,
+        );
+        writer.stream(to_unicode_ref, "", self.to_unicode().as_bytes());
+    }
+
+    fn to_unicode(&self) -> String {
+        let mut cmap = String::from(
+            "/CIDInit /ProcSet findresource begin\n12 dict begin\nbegincmap\n\
+             /CIDSystemInfo << /Registry (Adobe) /Ordering (UCS) /Supplement 0 >> def\n\
+             /CMapName /Adobe-Identity-UCS def\n/CMapType 2 def\n\
+             1 begincodespacerange\n<0000> <FFFF>\nendcodespacerange\n",
+        );
+        let mapped: Vec<(&u16, &String)> = self
+            .glyphs
+            .iter()
+            .filter(|(_, text)| !text.is_empty())
+            .collect();
+        // A bfchar block holds at most 100 entries.
+        for block in mapped.chunks(100) {
+            let _ = writeln!(cmap, "{} beginbfchar", block.len());
+            for (gid, text) in block {
+                let _ = writeln!(cmap, "<{gid:04X}> <{}>", utf16_hex(text));
+            }
+            cmap.push_str("endbfchar\n");
+        }
+        cmap.push_str("endcmap\nCMapName currentdict /CMap defineresource pop\nend\nend\n");
+        cmap
+    }
+}
+
+/// The first codepoint mapped to each glyph.
+fn reverse_cmap(data: &[u8]) -> HashMap<u16, char> {
+    let mut map = HashMap::new();
+    let Ok(font) = FontRef::from_index(data, 0) else {
+        return map;
+    };
+    for (cp, gid) in font.charmap().mappings() {
+        let (Ok(gid), Some(ch)) = (u16::try_from(gid.to_u32()), char::from_u32(cp)) else {
+            continue;
+        };
+        map.entry(gid).or_insert(ch);
+    }
+    map
+}
+
+/// The six-letter prefix marking a subset font, unique within the document.
+fn subset_tag(index: usize) -> String {
+    let mut tag = [b'A'; 6];
+    let mut n = index;
+    for slot in tag.iter_mut().rev() {
+        *slot = b'A' + (n % 26) as u8;
+        n /= 26;
+    }
+    String::from_utf8_lossy(&tag).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn subset_tags_are_distinct_uppercase() {
+        assert_eq!(subset_tag(0), "AAAAAA");
+        assert_eq!(subset_tag(1), "AAAAAB");
+        assert_eq!(subset_tag(27), "AAAABB");
+    }
+}
@@ -0,0 +1,470 @@
+mod content;
+mod font;
+mod object;
+
+use std::fmt::Write;
+
+use editor_macros::ffi;
+use editor_renderer::vector::types::{VectorDocument, VectorLink, VectorOutlineItem, VectorPage};
+use editor_resource::FontRegistry;
+use hashbrown::HashMap;
+use serde::{Deserialize, Serialize};
+
+use self::content::{PageImage, page_content};
+use self::font::FontSet;
+use self::object::{ObjectWriter, Ref, num, text_string, uri_string};
+use crate::ServerError;
+
+/// PDF points per layout pixel (72pt and 96px per inch).
+const PT_PER_PX: f32 = 0.75;
+
+#[ffi]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PdfMetadata {
+    pub title: Option<String>,
+    pub author: Option<String>,
+    pub subject: Option<String>,
+    pub keywords: Option<String>,
+    pub creator: Option<String>,
+}
+
+/// Writes a document's vector pages as one PDF. Glyph runs embed subsets of
+/// their fonts so text stays selectable; runs whose font cannot be subset are
+/// drawn as outlines.
+pub fn write_pdf(
+    document: &VectorDocument,
+    metadata: &PdfMetadata,
+    fonts: &FontRegistry,
+) -> Result<Vec<u8>, ServerError> {
+    if document.pages.is_empty() {
+        return Err(ServerError::EncodingFailed("document has no pages".into()));
+    }
+
+    let mut writer = ObjectWriter::new();
+    let catalog = writer.alloc();
+    let page_tree = writer.alloc();
+    let info = writer.alloc();
+    let page_refs: Vec<Ref> = document.pages.iter().map(|_| writer.alloc()).collect();
+    let font_set = FontSet::new(document, fonts, &mut writer);
+    let mut image_refs: HashMap<(usize, usize), Ref> = HashMap::new();
+
+    for (page_idx, page) in document.pages.iter().enumerate() {
+        let content = page_content(&page.ops, page.height, &font_set, fonts);
+        let content_ref = writer.alloc();
+        writer.stream(content_ref, "", content.data.as_bytes());
+
+        let mut resources = String::new();
+        if !content.fonts.is_empty() {
+            resources.push_str(" /Font <<");
+            for &font in &content.fonts {
+                let _ = write!(resources, " /F{font} {}", font_set.font_ref(font));
+            }
+            resources.push_str(" >>");
+        }
+        if !content.images.is_empty() {
+            resources.push_str(" /XObject <<");
+            for (index, image) in content.images.iter().enumerate() {
+                let key = (image.data.as_ptr() as usize, image.data.len());
+                let image_ref = *image_refs
+                    .entry(key)
+                    .or_insert_with(|| write_image(&mut writer, image));
+                let _ = write!(resources, " /Im{index} {image_ref}");
+            }
+            resources.push_str(" >>");
+        }
+        if !content.alphas.is_empty() {
+            resources.push_str(" /ExtGState <<");
+            for &alpha in &content.alphas {
+                let value = num(f32::from(alpha) / 255.0);
+                let _ = write!(resources, " /GS{alpha} << /ca {value} /CA {value} >>");
+            }
+            resources.push_str(" >>");
+        }
+
+        let links: Vec<Ref> = document
+            .links
+            .iter()
+            .filter(|link| link.page_idx == page_idx)
+            .map(|link| write_link(&mut writer, link, page.height))
+            .collect();
+        let mut annots = String::new();
+        if !links.is_empty() {
+            annots.push_str(" /Annots [");
+            for link in &links {
+                let _ = write!(annots, " {link}");
+            }
+            annots.push_str(" ]");
+        }
+
+        writer.object(
+            page_refs[page_idx],
+            &format!(
+                "<< /Type /Page /Parent {page_tree} /MediaBox [0 0 {} {}] \
+                 /Resources <<{resources} >> /Contents {content_ref}{annots} >>",
+                num(page.width * PT_PER_PX),
+                num(page.height * PT_PER_PX)
+            ),
+        );
+    }
+
+    font_set.write(&mut writer);
+
+    let mut kids = String::new();
+    for page_ref in &page_refs {
+        let _ = write!(kids, " {page_ref}");
+    }
+    writer.object(
+        page_tree,
+        &format!(
+            "<< /Type /Pages /Kids [{kids} ] /Count {} >>",
+            page_refs.len()
+        ),
+    );
+
+    let outline = write_outline(&mut writer, &document.outline, &page_refs, &document.pages);
+    let mut catalog_dict = format!("<< /Type /Catalog /Pages {page_tree}");
+    if let Some(outline) = outline {
+        let _ = write!(catalog_dict, " /Outlines {outline} /PageMode /UseOutlines");
+    }
+    catalog_dict.push_str(" >>");
+    writer.object(catalog, &catalog_dict);
+
+    let mut info_dict = String::from("<< /Producer (Typie)");
+    for (key, value) in [
+        ("Title", &metadata.title),
+        ("Author", &metadata.author),
+        ("Subject", &metadata.subject),
+        ("Keywords", &metadata.keywords),
+        ("Creator", &metadata.creator),
+    ] {
+        if let Some(value) = value {
+            let _ = write!(info_dict, " /{key} {}", text_string(value));
+        }
+    }
+    info_dict.push_str(" >>");
+    writer.object(info, &info_dict);
+
+    Ok(writer.finish(catalog, info))
+}
+
+/// Images arrive as premultiplied RGBA; PDF wants straight color with the
+/// alpha as a separate soft mask.
+fn write_image(writer: &mut ObjectWriter, image: &PageImage) -> Ref {
+    let pixels = image.width as usize * image.height as usize;
+    let mut rgb = Vec::with_capacity(pixels * 3);
+    let mut alpha = Vec::with_capacity(pixels);
+    for px in image.data.chunks_exact(4) {
+        let a = px[3];
+        for &c in &px[..3] {
+            rgb.push(match a {
+                0 => 0,
+                _ => ((u32::from(c) * 255 + u32::from(a) / 2) / u32::from(a)).min(255) as u8,
+            });
+        }
+        alpha.push(a);
+    }
+
+    let image_ref = writer.alloc();
+    let mut dict = format!(
+        "/Type /XObject /Subtype /Image /Width {} /Height {} /ColorSpace /DeviceRGB \
+         /BitsPerComponent 8",
+        image.width, image.height
+    );
+    if alpha.iter().any(|&a| a < 255) {
+        let mask_ref = writer.alloc();
+        writer.stream(
+            mask_ref,
+            &format!(
+                "/Type /XObject /Subtype /Image /Width {} /Height {} /ColorSpace /DeviceGray \
+                 /BitsPerComponent 8",
+                image.width, image.height
+            ),
+            &alpha,
+        );
+        let _ = write!(dict, " /SMask {mask_ref}");
+    }
+    writer.stream(image_ref, &dict, &rgb);
+    image_ref
+}
+
+fn write_link(writer: &mut ObjectWriter, link: &VectorLink, page_height: f32) -> Ref {
+    let rect = link.rect;
+    let link_ref = writer.alloc();
+    writer.object(
+        link_ref,
+        &format!(
+            "<< /Type /Annot /Subtype /Link /Rect [{} {} {} {}] /Border [0 0 0] \
+             /A << /S /URI /URI {} >> >>",
+            num(rect.x * PT_PER_PX),
+            num((page_height - rect.y - rect.height) * PT_PER_PX),
+            num((rect.x + rect.width) * PT_PER_PX),
+            num((page_height - rect.y) * PT_PER_PX),
+            uri_string(&link.href)
+        ),
+    );
+    link_ref
+}
+
+fn write_outline(
+    writer: &mut ObjectWriter,
+    items: &[VectorOutlineItem],
+    page_refs: &[Ref],
+    pages: &[VectorPage],
+) -> Option<Ref> {
+    if !items.iter().any(|item| item.page_idx < page_refs.len()) {
+        return None;
+    }
+    let root = writer.alloc();
+    let (first, last, count) = write_outline_level(writer, items, root, page_refs, pages)?;
+    writer.object(
+        root,
+        &format!("<< /Type /Outlines /First {first} /Last {last} /Count {count} >>"),
+    );
+    Some(root)
+}
+
+/// Writes one level of sibling outline items, all open, and returns the first
+/// and last item with the number of items visible below `parent`.
+fn write_outline_level(
+    writer: &mut ObjectWriter,
+    items: &[VectorOutlineItem],
+    parent: Ref,
+    page_refs: &[Ref],
+    pages: &[VectorPage],
+) -> Option<(Ref, Ref, usize)> {
+    let items: Vec<&VectorOutlineItem> = items
+        .iter()
+        .filter(|item| item.page_idx < page_refs.len())
+        .collect();
+    if items.is_empty() {
+        return None;
+    }
+
+    let refs: Vec<Ref> = items.iter().map(|_| writer.alloc()).collect();
+    let mut count = items.len();
+    for (idx, item) in items.iter().enumerate() {
+        let mut dict = format!("<< /Title {} /Parent {parent}", text_string(&item.title));
+        if idx > 0 {
+            let _ = write!(dict, " /Prev {}", refs[idx - 1]);
+        }
+        if let Some(next) = refs.get(idx + 1) {
+            let _ = write!(dict, " /Next {next}");
+        }
+        if let Some((first, last, descendants)) =
+            write_outline_level(writer, &item.children, refs[idx], page_refs, pages)
+        {
+            let _ = write!(dict, " /First {first} /Last {last} /Count {descendants}");
+            count += descendants;
+        }
+        let top = (pages[item.page_idx].height - item.y) * PT_PER_PX;
+        let _ = write!(
+            dict,
+            " /Dest [{} /XYZ 0 {} null] >>",
+            page_refs[item.page_idx],
+            num(top)
+        );
+        writer.object(refs[idx], &dict);
+    }
+
+    Some((refs[0], refs[refs.len() - 1], count))
+}
+
+#[cfg(test)]
+mod tests {
+    use editor_common::Rect;
+    use editor_renderer::vector::types::{
+        VectorFillRule, VectorGlyph, VectorOp, VectorPathCommand,
+    };
+    use editor_resource::{Resource, ResourceSource, compress_zstd, prepare_font_base};
+
+    use super::*;
+
+    const TEXT_FONT: &[u8] = include_bytes!("../../../../assets/Pretendard-Regular.ttf");
+    const BITMAP_FONT: &[u8] = include_bytes!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/assets/Mona12ColorEmoji.ttf"
+    ));
+
+    fn resource_with_font(font: &[u8]) -> (Resource, u16) {
+        let prepared = prepare_font_base(&compress_zstd(font)).expect("test font must be valid");
+        let mut source = ResourceSource::new_test();
+        source
+            .insert_font_base("test", 400, prepared)
+            .expect("test font must change resources");
+        let resource = Resource::from_snapshot(source.snapshot());
+        let family_id = resource.font_registry.intern_id("test").unwrap();
+        (resource, family_id)
+    }
+
+    fn page(ops: Vec<VectorOp>) -> VectorPage {
+        VectorPage {
+            width: 800.0,
+            height: 1000.0,
+            ops,
+            text_ops: vec![],
+        }
+    }
+
+    fn glyphs(family_id: u16, ids: &[u32]) -> VectorOp {
+        VectorOp::Glyphs {
+            family_id,
+            weight: 400,
+            size: 16.0,
+            color: [0, 0, 0, 255],
+            glyphs: ids
+                .iter()
+                .enumerate()
+                .map(|(i, &id)| VectorGlyph {
+                    id,
+                    x: 10.0 + i as f32 * 10.0,
+                    y: 20.0,
+                })
+                .collect(),
+            text: "A".to_string(),
+        }
+    }
+
+    /// The decompressed bodies of every stream in the file.
+    fn streams(pdf: &[u8]) -> Vec<Vec<u8>> {
+        let mut out = Vec::new();
+        let mut rest = pdf;
+        while let Some(start) = rest.windows(7).position(|w| w == b"stream\n") {
+            let body = &rest[start + 7..];
+            let end = body.windows(10).position(|w| w == b"\nendstream").unwrap();
+            out.push(miniz_oxide::inflate::decompress_to_vec_zlib(&body[..end]).unwrap());
+            rest = &body[end..];
+        }
+        out
+    }
+
+    fn contains(haystack: &[u8], needle: &str) -> bool {
+        haystack
+            .windows(needle.len())
+            .any(|w| w == needle.as_bytes())
+    }
+
+    #[test]
+    fn empty_document_is_rejected() {
+        let (resource, _) = resource_with_font(TEXT_FONT);
+        let result = write_pdf(
+            &VectorDocument::default(),
+            &PdfMetadata::default(),
+            &resource.font_registry,
+        );
+        assert!(matches!(result, Err(ServerError::EncodingFailed(_))));
+    }
+
+    #[test]
+    fn pages_are_sized_in_points_with_flipped_content() {
+        let (resource, _) = resource_with_font(TEXT_FONT);
+        let document = VectorDocument {
+            pages: vec![page(vec![VectorOp::FillPath {
+                path: vec![
+                    VectorPathCommand::MoveTo { x: 0.0, y: 0.0 },
+                    VectorPathCommand::LineTo { x: 10.0, y: 0.0 },
+                    VectorPathCommand::LineTo { x: 10.0, y: 10.0 },
+                    VectorPathCommand::ClosePath,
+                ],
+                color: [255, 0, 0, 128],
+                fill_rule: VectorFillRule::EvenOdd,
+            }])],
+            ..Default::default()
+        };
+        let pdf = write_pdf(&document, &PdfMetadata::default(), &resource.font_registry).unwrap();
+
+        assert!(pdf.starts_with(b"%PDF-1.7"));
+        assert!(pdf.ends_with(b"%%EOF\n"));
+        assert!(contains(&pdf, "/MediaBox [0 0 600 750]"));
+        assert!(contains(&pdf, "/GS128 << /ca 0.502 /CA 0.502 >>"));
+        let content = String::from_utf8(streams(&pdf).remove(0)).unwrap();
+        assert!(content.starts_with("0.75 0 0 -0.75 0 750 cm\n"));
+        assert!(content.contains("q /GS128 gs\n1 0 0 rg\n0 0 m\n10 0 l\n10 10 l\nh\nf*\nQ\n"));
+    }
+
+    #[test]
+    fn glyph_runs_embed_a_subset_font_with_unicode_mapping() {
+        let (resource, family_id) = resource_with_font(TEXT_FONT);
+        // Pretendard-Regular 'A' (U+0041) is glyph id 3.
+        let document = VectorDocument {
+            pages: vec![page(vec![glyphs(family_id, &[3])])],
+            ..Default::default()
+        };
+        let pdf = write_pdf(&document, &PdfMetadata::default(), &resource.font_registry).unwrap();
+
+        assert!(contains(&pdf, "/Subtype /Type0"));
+        assert!(contains(&pdf, "/Encoding /Identity-H"));
+        assert!(contains(&pdf, "/FontFile2"));
+        assert!(contains(&pdf, "/BaseFont /AAAAAA+"));
+
+        let streams = streams(&pdf);
+        assert!(
+            streams
+                .iter()
+                .any(|s| contains(s, "BT /F0 1 Tf\n16 0 0 -16 10 20 Tm <0003> Tj\nET\n"))
+        );
+        assert!(streams.iter().any(|s| contains(s, "<0003> <0041>")));
+        let subset = streams
+            .iter()
+            .find(|s| s.starts_with(&[0, 1, 0, 0]))
+            .expect("embedded TrueType font");
+        assert!(subset.len() < TEXT_FONT.len());
+    }
+
+    #[test]
+    fn fonts_that_cannot_be_subset_are_not_embedded() {
+        let (resource, family_id) = resource_with_font(BITMAP_FONT);
+        let document = VectorDocument {
+            pages: vec![page(vec![glyphs(family_id, &[1])])],
+            ..Default::default()
+        };
+        let pdf = write_pdf(&document, &PdfMetadata::default(), &resource.font_registry).unwrap();
+
+        assert!(!contains(&pdf, "/FontFile2"));
+        assert!(!contains(&pdf, "/Font <<"));
+        let content = String::from_utf8(streams(&pdf).remove(0)).unwrap();
+        assert!(!content.contains(" Tf"));
+    }
+
+    #[test]
+    fn links_outline_and_metadata_are_written() {
+        let (resource, _) = resource_with_font(TEXT_FONT);
+        let document = VectorDocument {
+            pages: vec![page(vec![]), page(vec![])],
+            links: vec![VectorLink {
+                page_idx: 1,
+                rect: Rect::from_xywh(100.0, 200.0, 40.0, 20.0),
+                href: "https://typie.co".to_string(),
+            }],
+            outline: vec![VectorOutlineItem {
+                title: "A".to_string(),
+                page_idx: 1,
+                y: 200.0,
+                children: vec![VectorOutlineItem {
+                    title: "B".to_string(),
+                    page_idx: 1,
+                    y: 400.0,
+                    children: vec![],
+                }],
+            }],
+        };
+        let metadata = PdfMetadata {
+            title: Some("제목".to_string()),
+            ..Default::default()
+        };
+        let pdf = write_pdf(&document, &metadata, &resource.font_registry).unwrap();
+
+        assert!(contains(
+            &pdf,
+            "/Subtype /Link /Rect [75 585 105 600] /Border [0 0 0] \
+             /A << /S /URI /URI (https://typie.co) >>"
+        ));
+        assert!(contains(&pdf, "/PageMode /UseOutlines"));
+        assert!(contains(&pdf, "/Type /Outlines"));
+        assert!(contains(&pdf, "/Title <FEFF0041>"));
+        assert!(contains(&pdf, "/Title <FEFF0042>"));
+        assert!(contains(&pdf, "/XYZ 0 600 null"));
+        assert!(contains(&pdf, "/XYZ 0 450 null"));
+        assert!(contains(&pdf, "/Title <FEFFC81CBAA9>"));
+    }
+}
@@ -0,0 +1,191 @@
+use std::fmt::{self, Write};
+
+/// An indirect object reference, `N 0 R` in the file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) struct Ref(u32);
+
+impl fmt::Display for Ref {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} 0 R", self.0)
+    }
+}
+
+/// Appends numbered objects to the file body and records their offsets for the
+/// cross-reference table. Object numbers are handed out up front with
+/// [`alloc`](Self::alloc) so objects can point at ones written later.
+pub(super) struct ObjectWriter {
+    buf: Vec<u8>,
+    offsets: Vec<Option<usize>>,
+}
+
+impl ObjectWriter {
+    pub(super) fn new() -> Self {
+        let mut buf = Vec::new();
+        buf.extend_from_slice(b"%PDF-1.7\n%\xE2\xE3\xCF\xD3\n");
+        Self {
+            buf,
+            offsets: Vec::new(),
+        }
+    }
+
+    pub(super) fn alloc(&mut self) -> Ref {
+        self.offsets.push(None);
+        Ref(self.offsets.len() as u32)
+    }
+
+    pub(super) fn object(&mut self, r: Ref, body: &str) {
+        self.begin(r);
+        self.buf.extend_from_slice(body.as_bytes());
+        self.buf.extend_from_slice(b"\nendobj\n");
+    }
+
+    /// Writes `data` Flate-compressed. `dict` holds any entries beyond the
+    /// filter and length.
+    pub(super) fn stream(&mut self, r: Ref, dict: &str, data: &[u8]) {
+        let compressed = miniz_oxide::deflate::compress_to_vec_zlib(data, 6);
+        let mut header = String::from("<<");
+        if !dict.is_empty() {
+            header.push(' ');
+            header.push_str(dict);
+        }
+        let _ = write!(
+            header,
+            " /Filter /FlateDecode /Length {} >>\nstream\n",
+            compressed.len()
+        );
+
+        self.begin(r);
+        self.buf.extend_from_slice(header.as_bytes());
+        self.buf.extend_from_slice(&compressed);
+        self.buf.extend_from_slice(b"\nendstream\nendobj\n");
+    }
+
+    pub(super) fn finish(mut self, root: Ref, info: Ref) -> Vec<u8> {
+        let xref_offset = self.buf.len();
+        let mut xref = format!("xref\n0 {}\n0000000000 65535 f \n", self.offsets.len() + 1);
+        for offset in &self.offsets {
+            match offset {
+                Some(offset) => {
+                    let _ = write!(xref, "{offset:010} 00000 n \n");
+                }
+                None => xref.push_str("0000000000 65535 f \n"),
+            }
+        }
+        let _ = write!(
+            xref,
+            "trailer\n<< /Size {} /Root {root} /Info {info} >>\nstartxref\n{xref_offset}\n%%EOF\n",
+            self.offsets.len() + 1
+        );
+        self.buf.extend_from_slice(xref.as_bytes());
+        self.buf
+    }
+
+    fn begin(&mut self, r: Ref) {
+        self.offsets[r.0 as usize - 1] = Some(self.buf.len());
+        self.buf
+            .extend_from_slice(format!("{} 0 obj\n", r.0).as_bytes());
+    }
+}
+
+/// A real number in the shortest form PDF readers accept: at most three
+/// decimals, no exponent, no trailing zeros.
+pub(super) fn num(v: f32) -> String {
+    if !v.is_finite() {
+        return "0".to_string();
+    }
+    let s = format!("{v:.3}");
+    let s = s.trim_end_matches('0').trim_end_matches('.');
+    if s == "-0" {
+        "0".to_string()
+    } else {
+        s.to_string()
+    }
+}
+
+/// A text string (titles, metadata) as UTF-16BE hex with a byte order mark.
+pub(super) fn text_string(s: &str) -> String {
+    format!("<FEFF{}>", utf16_hex(s))
+}
+
+pub(super) fn utf16_hex(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() * 4);
+    for unit in s.encode_utf16() {
+        let _ = write!(out, "{unit:04X}");
+    }
+    out
+}
+
+/// A URI as a literal string. URIs are 7-bit ASCII in PDF, so anything else is
+/// percent-encoded first.
+pub(super) fn uri_string(uri: &str) -> String {
+    let mut out = String::from("(");
+    for byte in uri.bytes() {
+        match byte {
+            b'\\' | b'(' | b')' => {
+                out.push('\\');
+                out.push(byte as char);
+            }
+            0x21..=0x7E => out.push(byte as char),
+            _ => {
+                let _ = write!(out, "%{byte:02X}");
+            }
+        }
+    }
+    out.push(')');
+    out
+}
+
+/// A PDF name body keeping only characters that need no escaping.
+pub(super) fn name(s: &str) -> String {
+    s.chars()
+        .filter(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn num_trims_trailing_zeros_and_negative_zero() {
+        assert_eq!(num(10.0), "10");
+        assert_eq!(num(0.75), "0.75");
+        assert_eq!(num(-0.0001), "0");
+        assert_eq!(num(1.23456), "1.235");
+        assert_eq!(num(f32::NAN), "0");
+    }
+
+    #[test]
+    fn text_string_is_utf16_with_bom() {
+        assert_eq!(text_string("A가"), "<FEFF0041AC00>");
+        assert_eq!(text_string("😀"), "<FEFFD83DDE00>");
+    }
+
+    #[test]
+    fn uri_string_escapes_delimiters_and_non_ascii() {
+        assert_eq!(uri_string("https://a.com/(x)"), "(https://a.com/\\(x\\))");
+        assert_eq!(
+            uri_string("https://a.com/가 b"),
+            "(https://a.com/%EA%B0%80%20b)"
+        );
+    }
+
+    #[test]
+    fn xref_offsets_point_at_objects() {
+        let mut writer = ObjectWriter::new();
+        let root = writer.alloc();
+        let info = writer.alloc();
+        writer.object(info, "<< >>");
+        writer.object(root, "<< /Type /Catalog >>");
+        let bytes = writer.finish(root, info);
+
+        let xref = bytes.windows(6).rposition(|w| w == b"\nxref\n").unwrap();
+        let table = std::str::from_utf8(&bytes[xref + 1..]).unwrap();
+        let entries: Vec<&str> = table.lines().skip(3).take(2).collect();
+        for (idx, entry) in entries.iter().enumerate() {
+            let offset: usize = entry[..10].parse().unwrap();
+            assert!(bytes[offset..].starts_with(format!("{} 0 obj", idx + 1).as_bytes()));
+        }
+        assert!(table.ends_with("%%EOF\n"));
+    }
+}
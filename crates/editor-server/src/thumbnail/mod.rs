@@ -0,0 +1,256 @@
+mod png;
+
+use std::sync::{Arc, Mutex};
+
+use editor_common::Rect;
+use editor_crdt::Changeset;
+use editor_macros::ffi;
+use editor_model::{EditOp, PlainDoc};
+use editor_renderer::backend::cpu::{CpuSink, unpremultiply};
+use editor_renderer::{RenderSink, Renderer, Transform};
+use editor_resource::{PreparedImage, Resource, prepare_image};
+use editor_state::State;
+use editor_view::{ExternalElementData, View, Viewport};
+use hashbrown::HashMap;
+use serde::{Deserialize, Serialize};
+
+use crate::ServerError;
+
+/// Height over width for images neither the resource nor the resolver has.
+const PLACEHOLDER_ASPECT: f32 = 9.0 / 16.0;
+
+/// Supplies decoded pixels for image nodes missing from the resource's image
+/// store, keyed by `ImageNode.id`.
+pub trait ImageResolver {
+    /// The image's pixels, or `None` to draw the placeholder.
+    fn resolve(&self, id: &str) -> Option<PreparedImage>;
+}
+
+/// Resolves nothing; images outside the resource draw as the placeholder.
+pub struct NoImages;
+
+impl ImageResolver for NoImages {
+    fn resolve(&self, _id: &str) -> Option<PreparedImage> {
+        None
+    }
+}
+
+impl<F: Fn(&str) -> Option<PreparedImage>> ImageResolver for F {
+    fn resolve(&self, id: &str) -> Option<PreparedImage> {
+        self(id)
+    }
+}
+
+/// A decoded image handed over by the host, in straight-alpha RGBA8.
+#[ffi]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThumbnailImage {
+    pub id: String,
+    pub width: u32,
+    pub height: u32,
+    #[serde(with = "serde_bytes")]
+    #[cfg_attr(feature = "wasm", tsify(type = "Uint8Array"))]
+    pub rgba: Vec<u8>,
+}
+
+impl ImageResolver for [ThumbnailImage] {
+    fn resolve(&self, id: &str) -> Option<PreparedImage> {
+        let image = self.iter().find(|image| image.id == id)?;
+        prepare_image(image.width, image.height, image.rgba.clone()).ok()
+    }
+}
+
+/// The document to render: a plain tree or a full changeset stream.
+pub enum ThumbnailSource<'a> {
+    Plain(&'a PlainDoc),
+    Changesets(Vec<Changeset<EditOp>>),
+}
+
+/// Lays out the document and rasterizes one page at `scale` into a PNG.
+/// Fonts and images come from `resource`, with `images` filling in the
+/// images it lacks; the same input always yields the same bytes.
+pub fn render_page_png(
+    source: ThumbnailSource,
+    viewport: Viewport,
+    resource: Arc<Mutex<Resource>>,
+    page_idx: usize,
+    scale: f32,
+    images: &dyn ImageResolver,
+) -> Result<Vec<u8>, ServerError> {
+    let state = match source {
+        ThumbnailSource::Plain(plain) => {
+            State::from_plain(plain).map_err(|e| ServerError::InvalidDocument(format!("{e:?}")))?
+        }
+        ThumbnailSource::Changesets(changesets) => State::from_changesets(changesets, None)
+            .map_err(|e| ServerError::InvalidDocument(format!("{e:?}")))?,
+    };
+    let mut view = View::new(viewport, Arc::clone(&resource));
+    view.layout(&state);
+    let resolved = size_images(&mut view, &state, &resource, images);
+
+    let page = view
+        .pages()
+        .get(page_idx)
+        .ok_or(ServerError::PageOutOfRange(page_idx))?;
+    let width = (page.size.width * scale).round();
+    let height = (page.size.height * scale).round();
+    if !(1.0..=f32::from(u16::MAX)).contains(&width)
+        || !(1.0..=f32::from(u16::MAX)).contains(&height)
+    {
+        return Err(ServerError::EncodingFailed(format!(
+            "page size {width}x{height} is out of range"
+        )));
+    }
+    let mut sink = CpuSink::try_new(width as u16, height as u16)
+        .ok_or_else(|| ServerError::EncodingFailed("page buffer allocation failed".into()))?;
+
+    let theme = resource.lock().unwrap().theme().clone();
+    sink.fill_rect(
+        Rect::from_xywh(0.0, 0.0, page.size.width, page.size.height),
+        theme.color("ui.surface.default"),
+        Transform::scale(scale),
+    );
+
+    // Layout never reads image pixels, so only the renderer needs a resource
+    // that also holds the resolved ones.
+    let render_resource = if resolved.is_empty() {
+        resource
+    } else {
+        let snapshot = resource.lock().unwrap().snapshot().with_images(resolved);
+        Arc::new(Mutex::new(Resource::from_snapshot(Arc::new(snapshot))))
+    };
+    let mut renderer = Renderer::new(render_resource);
+    renderer.set_draw_external_elements(true);
+    renderer.render_page(&mut sink, &state.view(), &view, page_idx, scale, &[]);
+
+    let mut pixels = sink.pixels().to_vec();
+    unpremultiply(&mut pixels);
+    Ok(png::encode_rgba(width as u32, height as u32, &pixels))
+}
+
+/// Gives each image atom the height its aspect ratio needs at its displayed
+/// width, as a host would after loading it. Returns the images `images`
+/// supplied for ids the resource's store lacks.
+fn size_images(
+    view: &mut View,
+    state: &State,
+    resource: &Mutex<Resource>,
+    images: &dyn ImageResolver,
+) -> HashMap<String, PreparedImage> {
+    let mut resolved = HashMap::new();
+    for element in view.external_elements(state, None) {
+        let ExternalElementData::Image { id, proportion } = &element.data else {
+            continue;
+        };
+        let stored = id.as_deref().and_then(|id| {
+            let resource = resource.lock().unwrap();
+            resource.image(id).map(|pixels| pixels.aspect_ratio())
+        });
+        if stored.is_none()
+            && let Some(id) = id
+            && !resolved.contains_key(id)
+            && let Some(image) = images.resolve(id)
+        {
+            resolved.insert(id.clone(), image);
+        }
+        let aspect = stored
+            .or_else(|| {
+                let image: &PreparedImage = resolved.get(id.as_ref()?)?;
+                Some(image.pixels().aspect_ratio())
+            })
+            .unwrap_or(PLACEHOLDER_ASPECT);
+        let width = element.bounds.width * (*proportion as f32 / 100.0);
+        view.set_external_height(state, element.node, width * aspect);
+    }
+    resolved
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use editor_model::{PlainImageNode, PlainNode, PlainNodeEntry, PlainTextNode};
+
+    use super::*;
+
+    fn entry(node: PlainNode, children: Vec<PlainNodeEntry>) -> PlainNodeEntry {
+        PlainNodeEntry {
+            node,
+            modifiers: BTreeMap::new(),
+            carry: Vec::new(),
+            children,
+        }
+    }
+
+    fn doc(children: Vec<PlainNodeEntry>) -> PlainDoc {
+        let mut doc = PlainDoc::default();
+        doc.root.children = children;
+        doc
+    }
+
+    fn render(doc: &PlainDoc, images: &dyn ImageResolver) -> Vec<u8> {
+        let resource = Arc::new(Mutex::new(Resource::new_test()));
+        render_page_png(
+            ThumbnailSource::Plain(doc),
+            Viewport::new(400.0, 600.0, 1.0),
+            resource,
+            0,
+            0.5,
+            images,
+        )
+        .unwrap()
+    }
+
+    fn image_doc() -> PlainDoc {
+        doc(vec![entry(
+            PlainNode::Image(PlainImageNode {
+                id: Some("cover".to_owned()),
+                proportion: 100,
+            }),
+            Vec::new(),
+        )])
+    }
+
+    #[test]
+    fn rendering_is_deterministic() {
+        let text = PlainNode::Text(PlainTextNode {
+            text: "thumbnail".to_owned(),
+        });
+        let doc = doc(vec![entry(
+            PlainNode::Paragraph(Default::default()),
+            vec![entry(text, Vec::new())],
+        )]);
+        let first = render(&doc, &NoImages);
+        assert!(first.starts_with(b"\x89PNG"));
+        assert_eq!(first, render(&doc, &NoImages));
+    }
+
+    #[test]
+    fn resolved_images_replace_the_placeholder() {
+        let doc = image_doc();
+        let images = [ThumbnailImage {
+            id: "cover".to_owned(),
+            width: 2,
+            height: 1,
+            rgba: [200u8, 10, 10, 255].repeat(2),
+        }];
+        let placeholder = render(&doc, &NoImages);
+        let drawn = render(&doc, &images[..]);
+        assert_ne!(placeholder, drawn);
+    }
+
+    #[test]
+    fn out_of_range_pages_are_rejected() {
+        let resource = Arc::new(Mutex::new(Resource::new_test()));
+        let result = render_page_png(
+            ThumbnailSource::Plain(&image_doc()),
+            Viewport::new(400.0, 600.0, 1.0),
+            resource,
+            5,
+            1.0,
+            &NoImages,
+        );
+        assert!(matches!(result, Err(ServerError::PageOutOfRange(5))));
+    }
+}
@@ -0,0 +1,72 @@
+use editor_zip::crc32;
+
+const SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1A, b'\n'];
+const COLOR_TYPE_RGBA: u8 = 6;
+const FILTER_NONE: u8 = 0;
+
+/// Encodes straight-alpha RGBA8 rows as a PNG. Rows are stored unfiltered
+/// and no timestamp or text chunks are written, so equal pixels give equal
+/// bytes.
+pub(super) fn encode_rgba(width: u32, height: u32, pixels: &[u8]) -> Vec<u8> {
+    let stride = width as usize * 4;
+    debug_assert_eq!(pixels.len(), stride * height as usize);
+
+    let mut raw = Vec::with_capacity((stride + 1) * height as usize);
+    for row in pixels.chunks_exact(stride) {
+        raw.push(FILTER_NONE);
+        raw.extend_from_slice(row);
+    }
+
+    let mut header = Vec::with_capacity(13);
+    header.extend_from_slice(&width.to_be_bytes());
+    header.extend_from_slice(&height.to_be_bytes());
+    header.extend_from_slice(&[8, COLOR_TYPE_RGBA, 0, 0, 0]);
+
+    let mut out = SIGNATURE.to_vec();
+    chunk(&mut out, b"IHDR", &header);
+    chunk(
+        &mut out,
+        b"IDAT",
+        &miniz_oxide::deflate::compress_to_vec_zlib(&raw, 6),
+    );
+    chunk(&mut out, b"IEND", &[]);
+    out
+}
+
+fn chunk(out: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    out.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let start = out.len();
+    out.extend_from_slice(kind);
+    out.extend_from_slice(data);
+    let crc = crc32(&out[start..]);
+    out.extend_from_slice(&crc.to_be_bytes());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chunks_carry_length_and_crc() {
+        let png = encode_rgba(1, 1, &[255, 0, 0, 255]);
+        assert_eq!(&png[..8], &SIGNATURE);
+        assert_eq!(&png[8..12], &13u32.to_be_bytes());
+        assert_eq!(&png[12..16], b"IHDR");
+        assert_eq!(&png[16..24], &[0, 0, 0, 1, 0, 0, 0, 1]);
+        assert_eq!(
+            &png[png.len() - 12..],
+            &[0, 0, 0, 0, b'I', b'E', b'N', b'D', 0xAE, 0x42, 0x60, 0x82]
+        );
+    }
+
+    #[test]
+    fn image_data_inflates_to_filtered_rows() {
+        let pixels = [1, 2, 3, 4, 5, 6, 7, 8];
+        let png = encode_rgba(1, 2, &pixels);
+        let idat = png.windows(4).position(|w| w == b"IDAT").unwrap();
+        let len = u32::from_be_bytes(png[idat - 4..idat].try_into().unwrap()) as usize;
+        let raw =
+            miniz_oxide::inflate::decompress_to_vec_zlib(&png[idat + 4..idat + 4 + len]).unwrap();
+        assert_eq!(raw, [0, 1, 2, 3, 4, 0, 5, 6, 7, 8]);
+    }
+}
@@ -34,6 +34,7 @@ mod selection_expansion;
 mod stable_position;
 mod stable_selection;
 mod state;
+mod template;
 #[cfg(any(test, feature = "test-utils"))]
 #[doc(hidden)]
 pub mod test_utils;
@@ -95,6 +96,7 @@ pub use stable_position::{
 };
 pub use stable_selection::{StableSelection, remap_selection};
 pub use state::*;
+pub use template::{TemplateError, TemplateHandles, build_state_from_template};
 pub use to_plain::to_plain;
 pub use traversal::{
     LeafGroup, blocks_in_range, document_content_selection, first_cursor_position,
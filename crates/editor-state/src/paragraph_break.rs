@@ -1,4 +1,4 @@
-use editor_model::{AtomLeaf, ChildView, DocView, NodeType, NodeView};
+use editor_model::{ChildView, DocView, NodeType, NodeView};
 
 use crate::affinity::Affinity;
 
@@ -30,8 +30,8 @@ fn paragraph_is_empty(p: &NodeView) -> bool {
     p.node_type() == NodeType::Paragraph && p.children().count() == 0
 }
 
-fn has_trailing_page_break(p: &NodeView) -> bool {
-    matches!(p.last_child(), Some(ChildView::Leaf(l)) if matches!(l.as_atom(), Some(AtomLeaf::PageBreak)))
+fn has_trailing_terminal_break(p: &NodeView) -> bool {
+    matches!(p.last_child(), Some(ChildView::Leaf(l)) if l.node_type().is_terminal_break())
 }
 
 fn child_node_type(c: &ChildView) -> NodeType {
@@ -62,7 +62,7 @@ fn after_node_position(node: &NodeView) -> Option<Position> {
 }
 
 fn trailing_break_for_paragraph(p: &NodeView, _view: &DocView) -> Option<Selection> {
-    if p.node_type() != NodeType::Paragraph || has_trailing_page_break(p) {
+    if p.node_type() != NodeType::Paragraph || has_trailing_terminal_break(p) {
         return None;
     }
     let next = p.parent()?.child_at(p.index()? + 1)?;
@@ -54,7 +54,7 @@ fn resolve_selection_expansion<'a>(
 fn is_inline_unit_atom(l: &editor_model::LeafView) -> bool {
     matches!(
         l.as_atom(),
-        Some(AtomLeaf::HardBreak | AtomLeaf::Tab | AtomLeaf::PageBreak)
+        Some(AtomLeaf::HardBreak | AtomLeaf::Tab | AtomLeaf::PageBreak | AtomLeaf::ColumnBreak)
     )
 }
 
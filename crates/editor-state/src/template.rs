@@ -0,0 +1,286 @@
+//! Builds a [`State`] from a [`PlainDoc`] template, the shape the `state!`
+//! macro emits, allocating `Dot`s exactly as the macro does so a template
+//! parsed at runtime lands on the same identities it compiles to.
+
+use std::collections::BTreeMap;
+
+use editor_crdt::{Dot, ListOp, OpGraph};
+use editor_model::{
+    Anchor, AtomLeaf, Bias, EditOp, Modifier, ModifierAttrOp, ModifierType, NodeAttrOp, NodeType,
+    PlainDoc, PlainNode, PlainNodeEntry, SeqClass, SeqItem, SpanOp, classify,
+};
+use hashbrown::HashMap;
+
+use crate::{ProjectedState, SpineError, State};
+
+/// Each template node's child-index path from the root (the root is the empty
+/// path) mapped to its projected `Dot`. Text nodes map to their containing
+/// block, since text has no projected identity.
+pub type TemplateHandles = HashMap<Vec<usize>, Dot>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum TemplateError {
+    #[error("{0:?} cannot be a leaf")]
+    UnsupportedNode(NodeType),
+    #[error("template does not project: {0:?}")]
+    Projection(SpineError),
+    #[error(
+        "template does not survive projection: schema-invalid content was dropped or normalized"
+    )]
+    NotPreserved,
+}
+
+/// Builds the template's document under `actor`, rejecting a template whose
+/// content projection would drop or normalize.
+pub fn build_state_from_template(
+    plain: PlainDoc,
+    actor: u64,
+) -> Result<(State, TemplateHandles), TemplateError> {
+    let (graph, handles) = emit_template(&plain, actor)?;
+    let projected = ProjectedState::from_graph(graph).map_err(TemplateError::Projection)?;
+
+    let mut want = plain;
+    canonicalize_entry(&mut want.root);
+    if crate::to_plain::to_plain_authored(projected.projected()) != want {
+        return Err(TemplateError::NotPreserved);
+    }
+
+    Ok((State::new(projected, None), handles))
+}
+
+pub(crate) fn emit_template(
+    plain: &PlainDoc,
+    actor: u64,
+) -> Result<(OpGraph<EditOp>, TemplateHandles), TemplateError> {
+    let mut graph = OpGraph::<EditOp>::with_actor(actor);
+    let mut handles = TemplateHandles::new();
+    let mut seq_pos: usize = 0;
+
+    emit_node(
+        &plain.root,
+        &[],
+        &mut Vec::new(),
+        &mut graph,
+        &mut handles,
+        &mut seq_pos,
+    )?;
+
+    graph.commit_mut();
+    Ok((graph, handles))
+}
+
+/// Rewrites a template entry into the canonical shape `to_plain` emits — empty
+/// text entries dropped, adjacent equal-modifier text entries merged, carry
+/// sorted by type — so the round-trip check flags only real content loss.
+pub(crate) fn canonicalize_entry(entry: &mut PlainNodeEntry) {
+    entry.carry.sort_by_key(Modifier::as_type);
+    let mut out: Vec<PlainNodeEntry> = Vec::new();
+    for mut child in std::mem::take(&mut entry.children) {
+        canonicalize_entry(&mut child);
+        if let PlainNode::Text(t) = &child.node {
+            if t.text.is_empty() {
+                continue;
+            }
+            if let Some(prev) = out.last_mut()
+                && let PlainNode::Text(prev_text) = &mut prev.node
+                && prev.modifiers == child.modifiers
+            {
+                prev_text.text.push_str(&t.text);
+                continue;
+            }
+        }
+        out.push(child);
+    }
+    entry.children = out;
+}
+
+fn emit_node(
+    entry: &PlainNodeEntry,
+    parents: &[Dot],
+    path: &mut Vec<usize>,
+    graph: &mut OpGraph<EditOp>,
+    handles: &mut TemplateHandles,
+    seq_pos: &mut usize,
+) -> Result<(), TemplateError> {
+    let node_type = entry.node.as_type();
+
+    match classify(node_type) {
+        SeqClass::Block => {
+            // The root is implicit (Dot::ROOT): no Block op in the seq, children
+            // parent to Dot::ROOT, and its overlays target Dot::ROOT.
+            let is_root = matches!(entry.node, PlainNode::Root(_));
+            let dot = if is_root {
+                Dot::ROOT
+            } else {
+                let d = graph
+                    .add_mut(EditOp::Seq(ListOp::Ins {
+                        pos: *seq_pos,
+                        item: SeqItem::Block {
+                            node_type,
+                            parents: parents.to_vec(),
+                            attrs: entry.node.to_attrs(),
+                        },
+                    }))
+                    .expect("local seq block insert never conflicts")
+                    .id;
+                *seq_pos += 1;
+                d
+            };
+            handles.insert(path.clone(), dot);
+
+            for modifier in entry.modifiers.values() {
+                graph
+                    .add_mut(EditOp::BlockModifier(ModifierAttrOp::SetModifier {
+                        target: dot,
+                        modifier: modifier.clone(),
+                    }))
+                    .expect("local block modifier never conflicts");
+            }
+            let mut carry_by_type: BTreeMap<ModifierType, Modifier> = BTreeMap::new();
+            for m in &entry.carry {
+                if m.as_type().is_carry_kind() {
+                    carry_by_type.insert(m.as_type(), m.clone());
+                }
+            }
+            for modifier in carry_by_type.into_values() {
+                graph
+                    .add_mut(EditOp::NodeCarry(ModifierAttrOp::SetModifier {
+                        target: dot,
+                        modifier,
+                    }))
+                    .expect("local node carry never conflicts");
+            }
+            if is_root {
+                for attr in entry.node.to_attrs() {
+                    graph
+                        .add_mut(EditOp::NodeAttr(NodeAttrOp { target: dot, attr }))
+                        .expect("local node attr never conflicts");
+                }
+            }
+
+            let mut child_parents = parents.to_vec();
+            child_parents.push(dot);
+            for (i, child) in entry.children.iter().enumerate() {
+                path.push(i);
+                emit_node(child, &child_parents, path, graph, handles, seq_pos)?;
+                path.pop();
+            }
+        }
+        SeqClass::Text => {
+            if let PlainNode::Text(text_node) = &entry.node {
+                let mut char_dots = Vec::with_capacity(text_node.text.chars().count());
+                for ch in text_node.text.chars() {
+                    let d = graph
+                        .add_mut(EditOp::Seq(ListOp::Ins {
+                            pos: *seq_pos,
+                            item: SeqItem::Char(ch),
+                        }))
+                        .expect("local char insert never conflicts")
+                        .id;
+                    *seq_pos += 1;
+                    char_dots.push(d);
+                }
+                if let (Some(&first), Some(&last)) = (char_dots.first(), char_dots.last()) {
+                    for modifier in entry.modifiers.values() {
+                        graph
+                            .add_mut(EditOp::Span(SpanOp::AddSpan {
+                                start: Anchor {
+                                    id: first,
+                                    bias: Bias::Before,
+                                },
+                                end: Anchor {
+                                    id: last,
+                                    bias: Bias::After,
+                                },
+                                modifier: modifier.clone(),
+                            }))
+                            .expect("local span never conflicts");
+                    }
+                }
+            }
+            if let Some(parent_dot) = parents.last() {
+                handles.insert(path.clone(), *parent_dot);
+            }
+        }
+        SeqClass::Atom => {
+            let leaf = AtomLeaf::from_plain_node(&entry.node)
+                .ok_or(TemplateError::UnsupportedNode(node_type))?;
+            let item = if leaf.is_block_level() {
+                SeqItem::BlockAtom {
+                    leaf,
+                    parents: parents.to_vec(),
+                }
+            } else {
+                SeqItem::Atom(leaf)
+            };
+            let dot = graph
+                .add_mut(EditOp::Seq(ListOp::Ins {
+                    pos: *seq_pos,
+                    item,
+                }))
+                .expect("local seq atom insert never conflicts")
+                .id;
+            *seq_pos += 1;
+            handles.insert(path.clone(), dot);
+
+            for modifier in entry.modifiers.values() {
+                graph
+                    .add_mut(EditOp::Span(SpanOp::AddSpan {
+                        start: Anchor {
+                            id: dot,
+                            bias: Bias::Before,
+                        },
+                        end: Anchor {
+                            id: dot,
+                            bias: Bias::After,
+                        },
+                        modifier: modifier.clone(),
+                    }))
+                    .expect("local atom span never conflicts");
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use editor_macros::state;
+    use editor_model::{AtomLeaf, PlainNodeEntry};
+
+    use super::{TemplateError, build_state_from_template};
+
+    #[test]
+    fn allocates_under_the_given_actor_with_handles() {
+        let (template, ..) = state! {
+            doc { root { p: paragraph { text("ab") } } }
+            selection: (p, 0)
+        };
+        let (state, handles) = build_state_from_template(template.to_plain(), 7).unwrap();
+        let para = handles[&vec![0]];
+        assert_eq!(para.actor, 7);
+        assert_eq!(handles[&vec![0, 0]], para);
+        assert_eq!(state.view().node(para).unwrap().inline_text(), "ab");
+    }
+
+    #[test]
+    fn reports_content_projection_drops() {
+        let (template, ..) = state! {
+            doc { root { p: paragraph { text("가") } } }
+            selection: (p, 0)
+        };
+        let mut plain = template.to_plain();
+        // A page break must end its paragraph, so one ahead of text is dropped.
+        plain.root.children[0].children.insert(
+            0,
+            PlainNodeEntry {
+                node: AtomLeaf::PageBreak.into_node().to_plain(),
+                modifiers: Default::default(),
+                carry: Vec::new(),
+                children: Vec::new(),
+            },
+        );
+        let result = build_state_from_template(plain, 1);
+        assert!(matches!(result, Err(TemplateError::NotPreserved)));
+    }
+}
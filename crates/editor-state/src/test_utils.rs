@@ -1,12 +1,9 @@
 use hashbrown::HashMap;
-use std::collections::BTreeMap;
 
-use editor_crdt::{Dot, ListOp, OpGraph};
-use editor_model::{
-    Anchor, AtomLeaf, Bias, EditOp, Modifier, ModifierAttrOp, ModifierType, NodeAttrOp, PlainDoc,
-    PlainNode, PlainNodeEntry, SeqClass, SeqItem, SpanOp, classify,
-};
+use editor_crdt::Dot;
+use editor_model::PlainDoc;
 
+use crate::template::{canonicalize_entry, emit_template};
 use crate::{ProjectedState, State};
 
 /// Builds a projected [`State`] from a [`PlainDoc`] template (the shape emitted
@@ -26,20 +23,7 @@ pub fn build_state_from_plain_with_actor(
     plain: PlainDoc,
     actor: u64,
 ) -> (State, HashMap<Vec<usize>, Dot>) {
-    let mut graph = OpGraph::<EditOp>::with_actor(actor);
-    let mut handles: HashMap<Vec<usize>, Dot> = HashMap::new();
-    let mut seq_pos: usize = 0;
-
-    emit_node(
-        &plain.root,
-        &[],
-        &mut Vec::new(),
-        &mut graph,
-        &mut handles,
-        &mut seq_pos,
-    );
-
-    graph.commit_mut();
+    let (graph, handles) = emit_template(&plain, actor).expect("template atoms convert");
     let projected = ProjectedState::from_graph(graph).expect("template always projects");
 
     let mut want = plain;
@@ -53,178 +37,6 @@ pub fn build_state_from_plain_with_actor(
     (State::new(projected, None), handles)
 }
 
-/// Rewrites a template entry into the canonical shape `to_plain` emits — empty
-/// text entries dropped, adjacent equal-modifier text entries merged, carry
-/// sorted by type — so the round-trip check flags only real content loss.
-fn canonicalize_entry(entry: &mut PlainNodeEntry) {
-    entry.carry.sort_by_key(Modifier::as_type);
-    let mut out: Vec<PlainNodeEntry> = Vec::new();
-    for mut child in std::mem::take(&mut entry.children) {
-        canonicalize_entry(&mut child);
-        if let PlainNode::Text(t) = &child.node {
-            if t.text.is_empty() {
-                continue;
-            }
-            if let Some(prev) = out.last_mut()
-                && let PlainNode::Text(prev_text) = &mut prev.node
-                && prev.modifiers == child.modifiers
-            {
-                prev_text.text.push_str(&t.text);
-                continue;
-            }
-        }
-        out.push(child);
-    }
-    entry.children = out;
-}
-
-fn emit_node(
-    entry: &PlainNodeEntry,
-    parents: &[Dot],
-    path: &mut Vec<usize>,
-    graph: &mut OpGraph<EditOp>,
-    handles: &mut HashMap<Vec<usize>, Dot>,
-    seq_pos: &mut usize,
-) {
-    let node_type = entry.node.as_type();
-
-    match classify(node_type) {
-        SeqClass::Block => {
-            // The root is implicit (Dot::ROOT): no Block op in the seq, children
-            // parent to Dot::ROOT, and its overlays target Dot::ROOT.
-            let is_root = matches!(entry.node, PlainNode::Root(_));
-            let dot = if is_root {
-                Dot::ROOT
-            } else {
-                let d = graph
-                    .add_mut(EditOp::Seq(ListOp::Ins {
-                        pos: *seq_pos,
-                        item: SeqItem::Block {
-                            node_type,
-                            parents: parents.to_vec(),
-                            attrs: entry.node.to_attrs(),
-                        },
-                    }))
-                    .expect("local seq block insert never conflicts")
-                    .id;
-                *seq_pos += 1;
-                d
-            };
-            handles.insert(path.clone(), dot);
-
-            for modifier in entry.modifiers.values() {
-                graph
-                    .add_mut(EditOp::BlockModifier(ModifierAttrOp::SetModifier {
-                        target: dot,
-                        modifier: modifier.clone(),
-                    }))
-                    .expect("local block modifier never conflicts");
-            }
-            let mut carry_by_type: BTreeMap<ModifierType, Modifier> = BTreeMap::new();
-            for m in &entry.carry {
-                if m.as_type().is_carry_kind() {
-                    carry_by_type.insert(m.as_type(), m.clone());
-                }
-            }
-            for modifier in carry_by_type.into_values() {
-                graph
-                    .add_mut(EditOp::NodeCarry(ModifierAttrOp::SetModifier {
-                        target: dot,
-                        modifier,
-                    }))
-                    .expect("local node carry never conflicts");
-            }
-            if is_root {
-                for attr in entry.node.to_attrs() {
-                    graph
-                        .add_mut(EditOp::NodeAttr(NodeAttrOp { target: dot, attr }))
-                        .expect("local node attr never conflicts");
-                }
-            }
-
-            let mut child_parents = parents.to_vec();
-            child_parents.push(dot);
-            for (i, child) in entry.children.iter().enumerate() {
-                path.push(i);
-                emit_node(child, &child_parents, path, graph, handles, seq_pos);
-                path.pop();
-            }
-        }
-        SeqClass::Text => {
-            if let PlainNode::Text(text_node) = &entry.node {
-                let mut char_dots = Vec::with_capacity(text_node.text.chars().count());
-                for ch in text_node.text.chars() {
-                    let d = graph
-                        .add_mut(EditOp::Seq(ListOp::Ins {
-                            pos: *seq_pos,
-                            item: SeqItem::Char(ch),
-                        }))
-                        .expect("local char insert never conflicts")
-                        .id;
-                    *seq_pos += 1;
-                    char_dots.push(d);
-                }
-                if let (Some(&first), Some(&last)) = (char_dots.first(), char_dots.last()) {
-                    for modifier in entry.modifiers.values() {
-                        graph
-                            .add_mut(EditOp::Span(SpanOp::AddSpan {
-                                start: Anchor {
-                                    id: first,
-                                    bias: Bias::Before,
-                                },
-                                end: Anchor {
-                                    id: last,
-                                    bias: Bias::After,
-                                },
-                                modifier: modifier.clone(),
-                            }))
-                            .expect("local span never conflicts");
-                    }
-                }
-            }
-            if let Some(parent_dot) = parents.last() {
-                handles.insert(path.clone(), *parent_dot);
-            }
-        }
-        SeqClass::Atom => {
-            let leaf = AtomLeaf::from_plain_node(&entry.node).expect("atom plain node converts");
-            let item = if leaf.is_block_level() {
-                SeqItem::BlockAtom {
-                    leaf,
-                    parents: parents.to_vec(),
-                }
-            } else {
-                SeqItem::Atom(leaf)
-            };
-            let dot = graph
-                .add_mut(EditOp::Seq(ListOp::Ins {
-                    pos: *seq_pos,
-                    item,
-                }))
-                .expect("local seq atom insert never conflicts")
-                .id;
-            *seq_pos += 1;
-            handles.insert(path.clone(), dot);
-
-            for modifier in entry.modifiers.values() {
-                graph
-                    .add_mut(EditOp::Span(SpanOp::AddSpan {
-                        start: Anchor {
-                            id: dot,
-                            bias: Bias::Before,
-                        },
-                        end: Anchor {
-                            id: dot,
-                            bias: Bias::After,
-                        },
-                        modifier: modifier.clone(),
-                    }))
-                    .expect("local atom span never conflicts");
-            }
-        }
-    }
-}
-
 // ── assert_state_eq ──────────────────────────────────────────────────────────
 // Structural state equality for tests: compares the projected tree (node types,
 // inline content, effective/own modifiers, carries) ignoring the concrete
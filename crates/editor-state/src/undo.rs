@@ -511,6 +511,7 @@ fn node_type_of_attr(attr: &NodeAttr) -> NodeType {
         NodeAttr::HorizontalRule { .. } => NodeType::HorizontalRule,
         NodeAttr::PageBreak { .. } => NodeType::PageBreak,
         NodeAttr::Tab { .. } => NodeType::Tab,
+        NodeAttr::ColumnBreak { .. } => NodeType::ColumnBreak,
         NodeAttr::Unknown { .. } => unreachable!(),
     }
 }
@@ -720,6 +720,8 @@ mod tests {
                 page_margin_bottom: 20,
                 page_margin_left: 20,
                 page_margin_right: 20,
+                columns: 1,
+                column_gap: 0,
             },
         });
         let step = Step::SetNode {
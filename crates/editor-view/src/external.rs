@@ -62,12 +62,7 @@ pub(crate) fn page_external_elements(
         elements.push(ExternalElement {
             page_idx,
             node: atom.node,
-            bounds: Rect::from_xywh(
-                entry.rect.x,
-                entry.rect.y - page.y_start,
-                entry.rect.width,
-                entry.rect.height,
-            ),
+            bounds: page.to_page_rect(entry.rect),
             is_selected,
             data,
         });
@@ -41,9 +41,12 @@ pub struct GlyphRun {
     pub text: String,
     pub x: f32,
     pub width: f32,
+    /// Graphemes in logical order; an RTL run lays them out from its right
+    /// edge, while `glyphs` stay in visual order.
     pub graphemes: Vec<GraphemeSpan>,
     pub cursor_ascent: f32,
     pub cursor_descent: f32,
+    pub rtl: bool,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -92,6 +95,7 @@ impl GlyphRun {
             graphemes,
             cursor_ascent: 0.0,
             cursor_descent: 0.0,
+            rtl: false,
         }
     }
 }
@@ -17,7 +17,7 @@ mod view_state;
 mod viewport;
 
 pub use dnd::*;
-pub use external::ExternalElement;
+pub use external::{ExternalElement, ExternalElementData};
 pub use measure::text::measure::TabGap;
 pub use measure::text::ruby::ruby_extra_top;
 pub use page::*;
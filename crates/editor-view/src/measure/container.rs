@@ -3,7 +3,7 @@ use std::sync::{Arc, LazyLock};
 
 use editor_model::{
     Alignment, ChildView, DEFAULT_BLOCK_GAP, DEFAULT_PARAGRAPH_INDENT, Modifier, ModifierType,
-    NodeType, NodeView,
+    NodeType, NodeView, TextDirection,
 };
 use editor_resource::Resource;
 
@@ -76,6 +76,7 @@ fn make_gap_phantom_block(
         &base_style,
         width,
         Alignment::Left,
+        TextDirection::Auto,
         indent,
         index..index,
         resource,
@@ -114,6 +115,10 @@ pub(crate) fn layout_vertical<'a>(
         }
         let gap_after = resolve_gap_after(child_effective(node, i, &child));
         let m = measure_child(child, width, ctx, resource);
+        let gap_after = match ctx.manuscript {
+            Some(grid) => grid.snap_gap(m.height),
+            None => gap_after,
+        };
         blocks.push((m, gap_after));
     }
     if gap_phantom_index == Some(n_children) {
@@ -1,3 +1,4 @@
+use crate::measure::text::manuscript::ManuscriptGrid;
 use crate::measure::text::measure::LineStrutExpansion;
 use crate::view_state::{GapPhantom, PendingOverlay};
 use editor_crdt::Dot;
@@ -10,6 +11,9 @@ pub(crate) struct MeasureContext {
     pub gap_phantom: Option<GapPhantom>,
     pub pending_overlay: Option<PendingOverlay>,
     pub pending_caret_expansion: Option<LineStrutExpansion>,
+    /// Set in manuscript layout mode: paragraphs go on the cell grid and
+    /// blocks start on row boundaries.
+    pub manuscript: Option<ManuscriptGrid>,
 }
 
 impl MeasureContext {
@@ -57,6 +61,7 @@ pub(crate) fn measure_context(vs: &crate::view_state::ViewState) -> MeasureConte
         }),
         pending_overlay: vs.pending_overlay.clone(),
         pending_caret_expansion: None,
+        manuscript: None,
     }
 }
 
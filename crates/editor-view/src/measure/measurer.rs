@@ -11,6 +11,8 @@ use crate::measure::types::MeasuredNode;
 pub(crate) struct Measurer {
     cache: MeasureCache,
     pub(crate) seg_cache: crate::measure::text::seg_cache::SegmentCache,
+    // Cache misses since construction; survives `clear` so callers can diff it.
+    measured: u64,
 }
 
 impl Measurer {
@@ -18,6 +20,7 @@ impl Measurer {
         Self {
             cache: MeasureCache::new(),
             seg_cache: Default::default(),
+            measured: 0,
         }
     }
 
@@ -37,11 +40,16 @@ impl Measurer {
         if let Some(cached) = self.cache.get(id, width) {
             return cached.clone();
         }
+        self.measured += 1;
         let measured = Arc::new(measure_node(self, node, width, ctx, resource));
         self.cache.insert(id, width, measured.clone());
         measured
     }
 
+    pub(crate) fn measured_count(&self) -> u64 {
+        self.measured
+    }
+
     pub(crate) fn invalidate_with_ancestors(&mut self, node: &NodeView) -> bool {
         let mut invalidated = self.cache.invalidate(node.id());
         for anc in node.ancestors() {
@@ -168,7 +168,9 @@ pub(crate) fn measured_intrinsic_width(node: &MeasuredNode) -> f32 {
                 + b.style.border.right
         }
         MeasuredContent::Atom(_) => node.width,
-        MeasuredContent::Spacing(_) | MeasuredContent::PageBreak => 0.0,
+        MeasuredContent::Spacing(_) | MeasuredContent::PageBreak | MeasuredContent::ColumnBreak => {
+            0.0
+        }
     }
 }
 
@@ -456,6 +458,7 @@ mod tests {
             graphemes: vec![],
             cursor_ascent: 0.0,
             cursor_descent: 0.0,
+            rtl: false,
         };
 
         let line = MeasuredLine {
@@ -98,6 +98,11 @@ pub(crate) fn measure_child(
                 height: 0.0,
                 content: MeasuredContent::PageBreak,
             }),
+            NodeType::ColumnBreak => Arc::new(MeasuredNode {
+                width,
+                height: 0.0,
+                content: MeasuredContent::ColumnBreak,
+            }),
             _ => Arc::new(MeasuredNode {
                 width,
                 height: 0.0,
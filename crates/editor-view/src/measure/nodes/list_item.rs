@@ -130,6 +130,7 @@ fn shape_marker(
                 }],
                 cursor_ascent: 0.0,
                 cursor_descent: 0.0,
+                rtl: false,
             });
         }
     }
@@ -8,6 +8,7 @@ use editor_model::{
 use editor_resource::Resource;
 
 use crate::measure::PageBreakPolicy;
+use crate::measure::text::manuscript::layout_manuscript_paragraph;
 use crate::measure::text::measure::measure_paragraph;
 use crate::style::{BorderMode, BoxStyle, Direction};
 
@@ -57,30 +58,46 @@ pub(crate) fn measure_paragraph_block(
     };
 
     let pending = ctx.pending_for(&node.id());
-    let (lines, total_height) = measure_paragraph(
-        node,
-        width,
-        align,
-        indent,
-        pending,
-        ctx.pending_caret_for(&node.id()),
-        Some(&mut measurer.seg_cache),
-        resource,
-    );
+    let (lines, total_height) = match ctx.manuscript {
+        Some(grid) => layout_manuscript_paragraph(
+            node,
+            grid,
+            width,
+            pending,
+            Some(&mut measurer.seg_cache),
+            resource,
+        ),
+        None => measure_paragraph(
+            node,
+            width,
+            align,
+            indent,
+            pending,
+            ctx.pending_caret_for(&node.id()),
+            Some(&mut measurer.seg_cache),
+            resource,
+        ),
+    };
 
     let mut children: Vec<Arc<MeasuredNode>> = lines
         .into_iter()
         .map(|l| Arc::new(MeasuredNode::from_line(width, l)))
         .collect();
 
-    let has_trailing_page_break = node
-        .last_child()
-        .is_some_and(|c| matches!(c, ChildView::Leaf(lv) if lv.node_type() == NodeType::PageBreak));
-    if has_trailing_page_break {
+    let trailing_break = match node.last_child() {
+        Some(ChildView::Leaf(lv)) if lv.node_type() == NodeType::PageBreak => {
+            Some(MeasuredContent::PageBreak)
+        }
+        Some(ChildView::Leaf(lv)) if lv.node_type() == NodeType::ColumnBreak => {
+            Some(MeasuredContent::ColumnBreak)
+        }
+        _ => None,
+    };
+    if let Some(content) = trailing_break {
         children.push(Arc::new(MeasuredNode {
             width: 0.0,
             height: 0.0,
-            content: MeasuredContent::PageBreak,
+            content,
         }));
     }
 
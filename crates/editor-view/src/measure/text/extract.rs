@@ -14,6 +14,7 @@ use parley::Layout;
 
 use editor_resource::TextBrush;
 
+use super::hyphenate::LayoutText;
 use super::inline::{TabMark, TextRun};
 use super::strut::StrutMetrics;
 use super::style_run::StyleRun;
@@ -133,7 +134,7 @@ fn resolve_line_typography_metrics(
 }
 
 pub(crate) fn extract_lines(
-    text: &str,
+    text: &LayoutText,
     layout: &Layout<TextBrush>,
     style_runs: &[StyleRun],
     runs: &[TextRun],
@@ -219,11 +220,16 @@ pub(crate) fn extract_lines(
 
                 // Every cluster of the run must land in exactly one extracted
                 // GlyphRun or its codepoints vanish from offset accounting.
+                // Hyphenation inserts are the exception: their glyphs are drawn
+                // but they own no document offset.
                 // Glyphless clusters (ZWJ, variation selectors, ligature
                 // continuations) carry no style of their own, so attribute them
                 // to the nearest glyph-bearing cluster: the previous one, or the
-                // next one at the run head.
-                let raw: Vec<(Range<usize>, f32, Option<usize>)> = run
+                // next one at the run head. Clusters come in visual order; an
+                // RTL run's are reversed back to logical order so the run text,
+                // graphemes and offsets read the way the document stores them.
+                let rtl = run.is_rtl();
+                let mut raw: Vec<(Range<usize>, f32, Option<usize>)> = run
                     .visual_clusters()
                     .map(|c| {
                         (
@@ -233,6 +239,9 @@ pub(crate) fn extract_lines(
                         )
                     })
                     .collect();
+                if rtl {
+                    raw.reverse();
+                }
                 let mut styles: Vec<Option<usize>> = raw.iter().map(|(_, _, s)| *s).collect();
                 let mut carrier = None;
                 for style in styles.iter_mut() {
@@ -254,23 +263,31 @@ pub(crate) fn extract_lines(
                 let mut first_byte_start = None;
 
                 for ((cluster_range, advance, _), style) in raw.iter().zip(&styles) {
-                    if *style != target_style_index || cluster_range.is_empty() {
+                    if *style != target_style_index
+                        || cluster_range.is_empty()
+                        || text.is_inserted(cluster_range)
+                    {
                         continue;
                     }
                     if first_byte_start.is_none() {
-                        first_byte_start = Some(cluster_range.start);
+                        first_byte_start = Some(text.to_source(cluster_range.start));
                     }
                     let local_start = run_text.len();
-                    run_text.push_str(&text[cluster_range.clone()]);
+                    run_text.push_str(&text.as_str()[cluster_range.clone()]);
                     clusters.push((local_start..run_text.len(), *advance));
                 }
 
                 let graphemes = segment_run_graphemes(&run_text, &clusters, grapheme_segmenter);
 
-                let byte_start = first_byte_start.unwrap_or(0);
+                // A run holding only a hyphenation insert sits at its source
+                // position with an empty offset range.
+                let byte_start = first_byte_start.unwrap_or_else(|| {
+                    raw.first()
+                        .map_or(0, |(range, _, _)| text.to_source(range.start))
+                });
                 let run_index = glyph_run.style().brush.run_index;
                 let src = &runs[run_index];
-                let char_offset = text[src.byte_range.start..byte_start].char_count();
+                let char_offset = text.source()[src.byte_range.start..byte_start].char_count();
                 let run_char_count = run_text.chars().count();
                 let offset_range: Range<usize> = (src.offset_range.start + char_offset)
                     ..(src.offset_range.start + char_offset + run_char_count);
@@ -287,6 +304,11 @@ pub(crate) fn extract_lines(
                     .unwrap_or((0, 400));
 
                 let run_advance = glyph_run.advance();
+                // The base direction mark shapes to an empty, zero-width run of
+                // its own when its direction differs from the text after it.
+                if clusters.is_empty() && run_advance == 0.0 {
+                    continue;
+                }
 
                 glyph_runs.push(GlyphRun {
                     family_id,
@@ -305,6 +327,7 @@ pub(crate) fn extract_lines(
                     graphemes,
                     cursor_ascent: run_cursor_ascent,
                     cursor_descent: run_cursor_descent,
+                    rtl,
                 });
             }
         }
@@ -635,7 +658,7 @@ mod tests {
             })
             .collect();
         let layout = build_layout(
-            &text,
+            &LayoutText::plain(&text),
             &style_runs,
             Alignment::Left,
             0.0,
@@ -646,7 +669,7 @@ mod tests {
         let segmenters = Arc::clone(resource.segmenters());
         drop(resource);
         extract_lines(
-            &text,
+            &LayoutText::plain(&text),
             &layout,
             &style_runs,
             &runs,
@@ -722,6 +745,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn rtl_run_extracted_in_logical_order() {
+        let l = build_logs(vec![ch('a'), ch(' '), ch('ש'), ch('ל'), ch('ו'), ch('ם')]);
+        let lines = pipeline_extract(&l);
+        let grs = glyph_runs(&lines);
+
+        let hebrew = grs
+            .iter()
+            .find(|g| g.text.contains('ש'))
+            .expect("Hebrew run");
+        assert!(hebrew.rtl);
+        assert_eq!(hebrew.text, "שלום");
+        assert_eq!(hebrew.offset_range, 2..6);
+        assert!(grs.iter().filter(|g| g.text.contains('a')).all(|g| !g.rtl));
+    }
+
     #[test]
     fn render_fields_from_effective() {
         let mut l = build_logs(vec![ch('a')]);
@@ -0,0 +1,365 @@
+use std::borrow::Cow;
+use std::ops::Range;
+
+use editor_model::{Alignment, TextDirection};
+use editor_resource::{Hyphenator, Resource, TextBrush};
+use parley::Layout;
+
+use super::inline::TabMark;
+use super::layout::build_layout;
+use super::style_run::StyleRun;
+
+const SOFT_HYPHEN: char = '\u{AD}';
+const WORD_JOINER: char = '\u{2060}';
+const HYPHEN: char = '-';
+const LEFT_TO_RIGHT_MARK: char = '\u{200E}';
+const RIGHT_TO_LEFT_MARK: char = '\u{200F}';
+/// Layouts tried while settling which opportunities end a line; each pass
+/// only moves opportunities forward through `Allowed → Hyphen → Suppressed`.
+const MAX_PASSES: usize = 4;
+
+/// A segment's text as parley sees it: the source text plus the break
+/// controls, visible hyphens and base direction mark measurement inserted.
+/// Inserted chars occupy no document offset, so clusters inside them are
+/// skipped when extracting runs.
+pub(crate) struct LayoutText<'a> {
+    source: &'a str,
+    text: Cow<'a, str>,
+    /// Source byte offset of each insertion and the layout bytes it occupies,
+    /// ascending.
+    inserted: Vec<(usize, Range<usize>)>,
+}
+
+impl<'a> LayoutText<'a> {
+    pub(crate) fn plain(source: &'a str) -> Self {
+        Self {
+            source,
+            text: Cow::Borrowed(source),
+            inserted: Vec::new(),
+        }
+    }
+
+    /// A leading strong mark pins the paragraph's base direction, which parley
+    /// otherwise takes from the first strong character.
+    fn with_insertions(
+        source: &'a str,
+        direction: TextDirection,
+        opportunities: &[Opportunity],
+    ) -> Self {
+        let mut text = String::with_capacity(source.len() + opportunities.len() * 3 + 3);
+        let mut inserted = Vec::new();
+        let mark = match direction {
+            TextDirection::Auto => None,
+            TextDirection::Ltr => Some(LEFT_TO_RIGHT_MARK),
+            TextDirection::Rtl => Some(RIGHT_TO_LEFT_MARK),
+        };
+        if let Some(mark) = mark {
+            text.push(mark);
+            inserted.push((0, 0..text.len()));
+        }
+        let mut copied = 0;
+        for opportunity in opportunities {
+            let Some(c) = opportunity.inserted_char() else {
+                continue;
+            };
+            text.push_str(&source[copied..opportunity.at]);
+            copied = opportunity.at;
+            let start = text.len();
+            text.push(c);
+            inserted.push((opportunity.at, start..text.len()));
+        }
+        if inserted.is_empty() {
+            return Self::plain(source);
+        }
+        text.push_str(&source[copied..]);
+        Self {
+            source,
+            text: Cow::Owned(text),
+            inserted,
+        }
+    }
+
+    pub(crate) fn source(&self) -> &'a str {
+        self.source
+    }
+
+    pub(crate) fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    /// Layout offset of a source offset. Insertions at `pos` come before it,
+    /// so they belong to whatever precedes `pos`.
+    pub(crate) fn to_layout(&self, pos: usize) -> usize {
+        let i = self.inserted.partition_point(|(at, _)| *at <= pos);
+        match i.checked_sub(1).map(|i| &self.inserted[i]) {
+            Some((at, range)) => pos + (range.end - at),
+            None => pos,
+        }
+    }
+
+    /// The range a source range covers in the layout text. Insertions at the
+    /// text's very start belong to the range beginning there, so the base
+    /// direction mark takes the first run's style.
+    pub(crate) fn to_layout_range(&self, range: &Range<usize>) -> Range<usize> {
+        let start = match range.start {
+            0 => 0,
+            start => self.to_layout(start),
+        };
+        start..self.to_layout(range.end)
+    }
+
+    /// Source offset of a layout offset outside the inserted chars.
+    pub(crate) fn to_source(&self, pos: usize) -> usize {
+        let i = self.inserted.partition_point(|(_, range)| range.end <= pos);
+        match i.checked_sub(1).map(|i| &self.inserted[i]) {
+            Some((at, range)) => pos - (range.end - at),
+            None => pos,
+        }
+    }
+
+    pub(crate) fn is_inserted(&self, range: &Range<usize>) -> bool {
+        let i = self
+            .inserted
+            .partition_point(|(_, inserted)| inserted.end <= range.start);
+        self.inserted
+            .get(i)
+            .is_some_and(|(_, inserted)| inserted.start <= range.start && range.end <= inserted.end)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Break {
+    /// An invisible break opportunity.
+    Allowed,
+    /// A line ended here, so the break shows a hyphen.
+    Hyphen,
+    /// The hyphen did not stay at a line end; the word is kept whole here.
+    Suppressed,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Opportunity {
+    /// Source byte offset the line would break before.
+    at: usize,
+    /// Written by the author as U+00AD rather than found by the patterns.
+    soft_hyphen: bool,
+    state: Break,
+}
+
+impl Opportunity {
+    fn inserted_char(&self) -> Option<char> {
+        match (self.state, self.soft_hyphen) {
+            (Break::Allowed, true) | (Break::Suppressed, false) => None,
+            (Break::Allowed, false) => Some(SOFT_HYPHEN),
+            (Break::Hyphen, _) => Some(HYPHEN),
+            (Break::Suppressed, true) => Some(WORD_JOINER),
+        }
+    }
+}
+
+fn is_latin_letter(c: char) -> bool {
+    c.is_alphabetic() && matches!(c as u32, 0x41..=0x24F | 0x1E00..=0x1EFF)
+}
+
+/// Break opportunities inside words: after every soft hyphen, and where the
+/// patterns allow one in Latin words the author did not hyphenate by hand.
+fn find_opportunities(text: &str, hyphenator: Option<&Hyphenator>) -> Vec<Opportunity> {
+    let mut out = Vec::new();
+    let mut word_start = None;
+    for (i, c) in text.char_indices().chain([(text.len(), ' ')]) {
+        if c.is_alphabetic() || c == SOFT_HYPHEN {
+            word_start.get_or_insert(i);
+            continue;
+        }
+        let Some(start) = word_start.take() else {
+            continue;
+        };
+        let word = &text[start..i];
+        let opportunity = |at, soft_hyphen| Opportunity {
+            at: start + at,
+            soft_hyphen,
+            state: Break::Allowed,
+        };
+        if word.contains(SOFT_HYPHEN) {
+            let len = SOFT_HYPHEN.len_utf8();
+            out.extend(
+                word.match_indices(SOFT_HYPHEN)
+                    .filter(|&(at, _)| at > 0 && at + len < word.len())
+                    .map(|(at, _)| opportunity(at + len, true)),
+            );
+        } else if let Some(hyphenator) = hyphenator
+            && word.chars().all(is_latin_letter)
+        {
+            let bounds: Vec<usize> = word.char_indices().map(|(at, _)| at).collect();
+            out.extend(
+                hyphenator
+                    .hyphenate(word)
+                    .into_iter()
+                    .map(|k| opportunity(bounds[k], false)),
+            );
+        }
+    }
+    out
+}
+
+/// Lays the segment out with hyphenation and the paragraph's base direction.
+/// Soft hyphens are always honored; pattern hyphenation applies while the
+/// resource has an active language.
+///
+/// A break opportunity that ends a line becomes a real hyphen in the laid-out
+/// text, so it is measured, justified and drawn like any other glyph while
+/// carrying no document offset. Adding the hyphen can push its word fragment
+/// to the next line; such an opportunity is then suppressed and the paragraph
+/// rebroken, a few passes at most.
+pub(crate) fn build_hyphenated_layout<'a>(
+    text: &'a str,
+    style_runs: &[StyleRun],
+    align: Alignment,
+    direction: TextDirection,
+    indent: f32,
+    width: f32,
+    resource: &mut Resource,
+    tabs: &[(TabMark, f32)],
+) -> (LayoutText<'a>, Layout<TextBrush>) {
+    let hyphenator = resource.hyphenator().cloned();
+    let mut opportunities = find_opportunities(text, hyphenator.as_deref());
+    let mut layout_text = LayoutText::with_insertions(text, direction, &opportunities);
+    let mut layout = build_layout(
+        &layout_text,
+        style_runs,
+        align,
+        indent,
+        width,
+        resource,
+        tabs,
+    );
+
+    for _ in 1..MAX_PASSES {
+        let line_ends: Vec<usize> = layout.lines().map(|line| line.text_range().end).collect();
+        let mut changed = false;
+        for opportunity in &mut opportunities {
+            let ends_line = line_ends
+                .binary_search(&layout_text.to_layout(opportunity.at))
+                .is_ok();
+            let next = match (opportunity.state, ends_line) {
+                (Break::Allowed, true) => Break::Hyphen,
+                (Break::Hyphen, false) => Break::Suppressed,
+                (state, _) => state,
+            };
+            changed |= next != opportunity.state;
+            opportunity.state = next;
+        }
+        if !changed {
+            break;
+        }
+        layout_text = LayoutText::with_insertions(text, direction, &opportunities);
+        layout = build_layout(
+            &layout_text,
+            style_runs,
+            align,
+            indent,
+            width,
+            resource,
+            tabs,
+        );
+    }
+
+    (layout_text, layout)
+}
+
+#[cfg(test)]
+mod tests {
+    use editor_resource::{ResourceSource, compress_zstd, prepare_hyphenation};
+
+    use super::*;
+
+    const PATTERNS: &str = "hy3ph he2n hena4 hen5at 1na n2at 1tio 2io o2n";
+
+    fn hyphenating_resource() -> Resource {
+        let mut source = ResourceSource::new_test();
+        let prepared = prepare_hyphenation(&compress_zstd(PATTERNS.as_bytes())).unwrap();
+        source.add_hyphenation_patterns("en", prepared);
+        source.set_hyphenation_language(Some("en".into()));
+        Resource::from_snapshot(source.snapshot())
+    }
+
+    fn style_run(len: usize, resource: &Resource) -> StyleRun {
+        StyleRun {
+            run_index: 0,
+            byte_range: 0..len,
+            family: resource.font_registry.placeholder_family_id().unwrap(),
+            weight: 400,
+            font_size: 16.0,
+            letter_spacing: 0.0,
+            line_height: 1.6,
+        }
+    }
+
+    #[test]
+    fn offsets_round_trip_around_insertions() {
+        let opportunities = [
+            Opportunity {
+                at: 2,
+                soft_hyphen: false,
+                state: Break::Allowed,
+            },
+            Opportunity {
+                at: 6,
+                soft_hyphen: false,
+                state: Break::Hyphen,
+            },
+        ];
+        let text = LayoutText::with_insertions("hyphenation", TextDirection::Auto, &opportunities);
+        assert_eq!(text.as_str(), "hy\u{AD}phen-ation");
+        assert_eq!(text.to_layout(1), 1);
+        assert_eq!(text.to_layout(2), 4);
+        assert_eq!(text.to_layout(6), 9);
+        assert_eq!(text.to_source(9), 6);
+        assert_eq!(text.to_source(4), 2);
+        assert!(text.is_inserted(&(2..4)));
+        assert!(text.is_inserted(&(8..9)));
+        assert!(!text.is_inserted(&(4..5)));
+    }
+
+    #[test]
+    fn soft_hyphens_are_opportunities_and_block_patterns() {
+        let hyphenator = Hyphenator::parse(PATTERNS);
+        let found = find_opportunities("hyphen\u{AD}ation hyphenation", Some(&hyphenator));
+        let ats: Vec<(usize, bool)> = found.iter().map(|o| (o.at, o.soft_hyphen)).collect();
+        assert_eq!(ats, vec![(8, true), (16, false), (20, false)]);
+        assert!(find_opportunities("hyphenation", None).is_empty());
+        assert!(find_opportunities("하이픈네이션", Some(&hyphenator)).is_empty());
+    }
+
+    #[test]
+    fn hyphenation_breaks_words_with_a_visible_hyphen() {
+        let mut resource = hyphenating_resource();
+        assert!(resource.hyphenator().is_some());
+        let text = "hyphenation hyphenation";
+        let runs = [style_run(text.len(), &resource)];
+        // The placeholder advances every glyph by 8px, so 7 chars fit a line.
+        let (layout_text, layout) = build_hyphenated_layout(
+            text,
+            &runs,
+            Alignment::Left,
+            TextDirection::Auto,
+            0.0,
+            60.0,
+            &mut resource,
+            &[],
+        );
+
+        let lines: Vec<&str> = layout
+            .lines()
+            .map(|line| &layout_text.as_str()[line.text_range()])
+            .collect();
+        assert!(lines.len() > 2, "{lines:?}");
+        assert!(lines[0].ends_with(HYPHEN), "{lines:?}");
+        let visible: String = lines
+            .concat()
+            .chars()
+            .filter(|&c| c != HYPHEN && c != SOFT_HYPHEN)
+            .collect();
+        assert_eq!(visible, text);
+    }
+}
@@ -10,11 +10,15 @@ use parley::{
     Layout, OverflowWrap, WordBreak,
 };
 
+use super::hyphenate::LayoutText;
 use super::inline::TabMark;
 use super::style_run::StyleRun;
 
+/// `style_runs` and `tabs` are in source offsets; they are mapped onto the
+/// layout text here. With a hyphenation language active, words only break at
+/// their hyphenation opportunities unless one alone overflows the line.
 pub(crate) fn build_layout(
-    text: &str,
+    text: &LayoutText,
     style_runs: &[StyleRun],
     align: Alignment,
     indent: f32,
@@ -22,10 +26,17 @@ pub(crate) fn build_layout(
     resource: &mut Resource,
     tabs: &[(TabMark, f32)],
 ) -> Layout<TextBrush> {
-    let mut builder =
-        resource
-            .layout_context
-            .style_run_builder(&mut resource.font_context, text, 1.0, true);
+    let word_break = if resource.hyphenator().is_some() {
+        WordBreak::Normal
+    } else {
+        WordBreak::BreakAll
+    };
+    let mut builder = resource.layout_context.style_run_builder(
+        &mut resource.font_context,
+        text.as_str(),
+        1.0,
+        true,
+    );
 
     let family_names: Vec<String> = style_runs
         .iter()
@@ -51,26 +62,26 @@ pub(crate) fn build_layout(
             font_features: FontFeatures::Source(Cow::Borrowed(
                 "\"ss05\" 1, \"cv12\" 1, \"ss18\" 1",
             )),
-            word_break: WordBreak::BreakAll,
+            word_break,
             overflow_wrap: OverflowWrap::Anywhere,
             ..TextStyle::default()
         };
 
         let idx = builder.push_style(style);
-        builder.push_style_run(idx, style_run.byte_range.clone());
+        builder.push_style_run(idx, text.to_layout_range(&style_run.byte_range));
     }
 
     for (i, (tab, placeholder)) in tabs.iter().enumerate() {
         builder.push_inline_box(InlineBox {
             id: i as u64,
             kind: InlineBoxKind::InFlow,
-            index: tab.byte_offset,
+            index: text.to_layout(tab.byte_offset),
             width: *placeholder,
             height: 0.0,
         });
     }
 
-    let mut layout = builder.build(text);
+    let mut layout = builder.build(text.as_str());
 
     if indent > 0.0 {
         layout.set_text_indent(indent, IndentOptions::default());
@@ -155,7 +166,7 @@ mod tests {
         let fam = resource.font_registry.placeholder_family_id().unwrap();
         let style_runs = vec![style_run(0, 0..1, fam, 16.0), style_run(1, 1..2, fam, 32.0)];
         let layout = build_layout(
-            "AB",
+            &LayoutText::plain("AB"),
             &style_runs,
             Alignment::Left,
             0.0,
@@ -175,7 +186,7 @@ mod tests {
         let fam = resource.font_registry.placeholder_family_id().unwrap();
         let style_runs = vec![style_run(0, 0..5, fam, 16.0)];
         let layout = build_layout(
-            "hello",
+            &LayoutText::plain("hello"),
             &style_runs,
             Alignment::Left,
             0.0,
@@ -208,7 +219,7 @@ mod tests {
         };
         let tab_w = tab_px(&tab.style, &mut resource);
         let layout = build_layout(
-            "ab",
+            &LayoutText::plain("ab"),
             &style_runs,
             Alignment::Left,
             0.0,
@@ -236,7 +247,7 @@ mod tests {
         let text = "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
         let style_runs = vec![style_run(0, 0..text.len(), fam, 16.0)];
         let layout = build_layout(
-            text,
+            &LayoutText::plain(text),
             &style_runs,
             Alignment::Left,
             0.0,
@@ -254,7 +265,15 @@ mod tests {
         let fam = resource.font_registry.placeholder_family_id().unwrap();
         let style_runs = vec![style_run(0, 0..2, fam, 16.0)];
         let measure = |align: Alignment, resource: &mut Resource| -> (f32, f32) {
-            let layout = build_layout("ab", &style_runs, align, 0.0, W, resource, &[]);
+            let layout = build_layout(
+                &LayoutText::plain("ab"),
+                &style_runs,
+                align,
+                0.0,
+                W,
+                resource,
+                &[],
+            );
             // layout_max_advance() returns the container width (W), not the text content advance.
             // Use the first line's advance minus trailing whitespace as the actual content width.
             let content_width = layout
@@ -293,7 +312,7 @@ mod tests {
         let fam = resource.font_registry.placeholder_family_id().unwrap();
         let style_runs = vec![style_run(0, 0..3, fam, 16.0)];
         let layout = build_layout(
-            "xyz",
+            &LayoutText::plain("xyz"),
             &style_runs,
             Alignment::Left,
             0.0,
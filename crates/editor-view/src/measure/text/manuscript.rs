@@ -0,0 +1,580 @@
+//! 원고지 (manuscript paper) layout: paragraph text placed one grapheme per
+//! square cell on a fixed grid, following the usual manuscript conventions.
+//! Shaping is reused from [`measure_paragraph`]; only placement differs.
+
+use editor_model::{Alignment, LayoutMode, NodeView};
+use editor_resource::Resource;
+
+use super::measure::{MeasuredLine, TabGap, measure_paragraph};
+use super::seg_cache::SegmentCache;
+use crate::glyph_run::{Glyph, GlyphRun, GraphemeSpan};
+
+/// Wide enough that shaping never wraps, so each source line is a whole
+/// hard-break segment.
+const UNWRAPPED_WIDTH: f32 = 1.0e7;
+
+/// Tolerance when snapping block heights to whole rows.
+const ROW_EPSILON: f32 = 1e-3;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct ManuscriptGrid {
+    pub columns: usize,
+    pub cell_size: f32,
+    pub row_gap: f32,
+}
+
+impl ManuscriptGrid {
+    /// `mode` is expected to be [`LayoutMode::normalized`].
+    pub(crate) fn from_layout_mode(mode: LayoutMode) -> Option<Self> {
+        match mode {
+            LayoutMode::Manuscript {
+                columns,
+                cell_size,
+                row_gap,
+                ..
+            } => Some(Self {
+                columns: columns as usize,
+                cell_size: cell_size as f32,
+                row_gap: row_gap as f32,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Height of one row: the blank band above it plus the cell.
+    pub(crate) fn row_pitch(&self) -> f32 {
+        self.cell_size + self.row_gap
+    }
+
+    /// Space to leave after a block of `height` so the next block starts on
+    /// a row boundary.
+    pub(crate) fn snap_gap(&self, height: f32) -> f32 {
+        let pitch = self.row_pitch();
+        let rows = ((height - ROW_EPSILON) / pitch).ceil().max(0.0);
+        (rows * pitch - height).max(0.0)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CellClass {
+    /// Takes a cell of its own, collapsing at the start of a row.
+    Space,
+    /// Latin letters and digits, two to a cell.
+    Narrow,
+    /// Never starts a row: shares the last cell when the row is full.
+    Closing,
+    Wide,
+    /// Zero-width marks that occupy no cell.
+    Empty,
+}
+
+fn classify(text: &str, advance: f32) -> CellClass {
+    let mut chars = text.chars();
+    let Some(c) = chars.next() else {
+        return CellClass::Empty;
+    };
+    if advance == 0.0 {
+        CellClass::Empty
+    } else if c.is_whitespace() {
+        CellClass::Space
+    } else if chars.next().is_none() && c.is_ascii_alphanumeric() {
+        CellClass::Narrow
+    } else if is_closing(c) {
+        CellClass::Closing
+    } else {
+        CellClass::Wide
+    }
+}
+
+fn is_closing(c: char) -> bool {
+    matches!(
+        c,
+        '.' | ','
+            | '!'
+            | '?'
+            | ':'
+            | ';'
+            | ')'
+            | ']'
+            | '}'
+            | '\u{201D}'
+            | '\u{2019}'
+            | '\u{300D}'
+            | '\u{300F}'
+            | '\u{300B}'
+            | '\u{3009}'
+            | '\u{3001}'
+            | '\u{3002}'
+            | '\u{2026}'
+    )
+}
+
+/// A closing quote written into the same cell as the sentence mark before it.
+fn shares_with(prev: &str, text: &str) -> bool {
+    matches!(prev, "." | "," | "!" | "?")
+        && matches!(
+            text,
+            "\u{201D}" | "\u{2019}" | "\"" | "'" | "\u{300D}" | "\u{300F}"
+        )
+}
+
+/// One grapheme lifted out of a shaped source line.
+struct Unit {
+    run: usize,
+    text: String,
+    offset: usize,
+    codepoints: u8,
+    x: f32,
+    advance: f32,
+    glyphs: Vec<Glyph>,
+    class: CellClass,
+}
+
+enum Item {
+    Grapheme(Unit),
+    Tab(TabGap),
+}
+
+impl Item {
+    fn offset(&self) -> usize {
+        match self {
+            Item::Grapheme(unit) => unit.offset,
+            Item::Tab(gap) => gap.offset_index,
+        }
+    }
+}
+
+fn collect_items(line: &MeasuredLine) -> Vec<Item> {
+    let mut items = Vec::new();
+    for (run_index, run) in line.glyph_runs.iter().enumerate() {
+        let mut chars = run.text.chars();
+        let mut offset = run.offset_range.start;
+        let mut cumulative = 0.0;
+        let mut units: Vec<Unit> = Vec::with_capacity(run.graphemes.len());
+        for span in &run.graphemes {
+            let text: String = chars.by_ref().take(span.codepoints as usize).collect();
+            let x = if run.rtl {
+                run.x + run.width - cumulative - span.advance
+            } else {
+                run.x + cumulative
+            };
+            cumulative += span.advance;
+            units.push(Unit {
+                run: run_index,
+                class: classify(&text, span.advance),
+                text,
+                offset,
+                codepoints: span.codepoints,
+                x,
+                advance: span.advance,
+                glyphs: Vec::new(),
+            });
+            offset += span.codepoints as usize;
+        }
+        for glyph in &run.glyphs {
+            let owner = units
+                .iter_mut()
+                .filter(|unit| unit.x <= glyph.x + 0.01)
+                .max_by(|a, b| a.x.total_cmp(&b.x));
+            if let Some(unit) = owner {
+                unit.glyphs.push(*glyph);
+            } else if let Some(unit) = units.first_mut() {
+                unit.glyphs.push(*glyph);
+            }
+        }
+        items.extend(units.into_iter().map(Item::Grapheme));
+    }
+    items.extend(line.tab_gaps.iter().cloned().map(Item::Tab));
+    items.sort_by_key(Item::offset);
+    items
+}
+
+struct Placed {
+    item: usize,
+    x: f32,
+    advance: f32,
+}
+
+/// Fills rows of `columns` cells, tracking occupancy in half cells so Latin
+/// pairs can share one.
+struct CellCursor {
+    columns: usize,
+    cell: f32,
+    rows: Vec<Vec<Placed>>,
+    half: usize,
+}
+
+impl CellCursor {
+    fn half_width(&self) -> f32 {
+        self.cell / 2.0
+    }
+
+    fn row(&mut self) -> &mut Vec<Placed> {
+        self.rows.last_mut().expect("cursor always has a row")
+    }
+
+    fn new_row(&mut self) {
+        self.rows.push(Vec::new());
+        self.half = 0;
+    }
+
+    /// Aligns to the next whole cell, wrapping when `halves` no longer fit,
+    /// and widens the last placement over any skipped half so a run's
+    /// graphemes stay contiguous.
+    fn claim(&mut self, halves: usize) -> f32 {
+        let mut start = self.half.div_ceil(2) * 2;
+        if start + halves > self.columns * 2 && !self.row().is_empty() {
+            self.new_row();
+            start = 0;
+        }
+        let skipped = (start - self.half) as f32 * self.half_width();
+        if let Some(last) = self.row().last_mut() {
+            last.advance += skipped;
+        }
+        self.half = start + halves;
+        start as f32 * self.half_width()
+    }
+
+    fn row_full(&self) -> bool {
+        self.half.div_ceil(2) * 2 + 2 > self.columns * 2
+    }
+
+    /// Splits the last cell between its current occupant and the new item.
+    fn share_last(&mut self, item: usize) -> bool {
+        let Some(last) = self.row().last_mut() else {
+            return false;
+        };
+        last.advance /= 2.0;
+        let x = last.x + last.advance;
+        let advance = last.advance;
+        self.row().push(Placed { item, x, advance });
+        true
+    }
+
+    fn place(&mut self, item: usize, halves: usize) {
+        let x = self.claim(halves);
+        let advance = halves as f32 * self.half_width();
+        self.row().push(Placed { item, x, advance });
+    }
+}
+
+fn place_items(items: &[Item], columns: usize, cell: f32, indent: bool) -> Vec<Vec<Placed>> {
+    let mut cursor = CellCursor {
+        columns,
+        cell,
+        rows: vec![Vec::new()],
+        half: if indent { 2 } else { 0 },
+    };
+    for (index, item) in items.iter().enumerate() {
+        let unit = match item {
+            Item::Tab(_) => {
+                cursor.place(index, 2);
+                continue;
+            }
+            Item::Grapheme(unit) => unit,
+        };
+        let prev = cursor.row().last().and_then(|p| match &items[p.item] {
+            Item::Grapheme(prev) => Some(prev),
+            Item::Tab(_) => None,
+        });
+        match unit.class {
+            CellClass::Empty => {
+                let x = cursor.half as f32 * cursor.half_width();
+                cursor.row().push(Placed {
+                    item: index,
+                    x,
+                    advance: 0.0,
+                });
+            }
+            CellClass::Space if cursor.row().is_empty() || cursor.row_full() => {
+                let x = if cursor.row().is_empty() {
+                    cursor.half as f32 * cursor.half_width()
+                } else {
+                    cursor.new_row();
+                    0.0
+                };
+                cursor.row().push(Placed {
+                    item: index,
+                    x,
+                    advance: 0.0,
+                });
+            }
+            CellClass::Narrow
+                if cursor.half % 2 == 1 && prev.is_some_and(|p| p.class == CellClass::Narrow) =>
+            {
+                let x = cursor.half as f32 * cursor.half_width();
+                let advance = cursor.half_width();
+                cursor.half += 1;
+                cursor.row().push(Placed {
+                    item: index,
+                    x,
+                    advance,
+                });
+            }
+            CellClass::Narrow => cursor.place(index, 1),
+            _ if prev.is_some_and(|p| shares_with(&p.text, &unit.text))
+                && cursor.share_last(index) => {}
+            CellClass::Closing if cursor.row_full() && cursor.share_last(index) => {}
+            CellClass::Space | CellClass::Closing | CellClass::Wide => cursor.place(index, 2),
+        }
+    }
+    cursor.rows
+}
+
+/// Lays out a paragraph on the manuscript grid: an indent cell opens the
+/// paragraph, then each grapheme takes a cell of `grid.cell_size`. Rows are
+/// `grid.row_pitch()` tall with the cell at the bottom. Ruby annotations are
+/// dropped; the grid has no room above the cells for them.
+pub(crate) fn layout_manuscript_paragraph(
+    node: &NodeView,
+    grid: ManuscriptGrid,
+    width: f32,
+    pending: Option<&editor_state::PendingModifiers>,
+    seg_cache: Option<&mut SegmentCache>,
+    resource: &mut Resource,
+) -> (Vec<MeasuredLine>, f32) {
+    let (source, _) = measure_paragraph(
+        node,
+        UNWRAPPED_WIDTH,
+        Alignment::Left,
+        0.0,
+        pending,
+        None,
+        seg_cache,
+        resource,
+    );
+    let cell = grid.cell_size;
+    let pitch = grid.row_pitch();
+    let columns = grid.columns.min((width / cell).floor() as usize).max(1);
+
+    let mut lines = Vec::new();
+    for (line_index, line) in source.iter().enumerate() {
+        let items = collect_items(line);
+        let rows = place_items(&items, columns, cell, line_index == 0);
+        let baseline = grid.row_gap + cell / 2.0 + (line.ascent - line.descent) / 2.0;
+        let cursor_ascent = baseline - grid.row_gap;
+        let cursor_descent = grid.row_gap + cell - baseline;
+        let n = rows.len();
+        for (row_index, row) in rows.into_iter().enumerate() {
+            let offset_range = line.offset_range.clone().and_then(|range| {
+                if n == 1 {
+                    Some(range)
+                } else if row_index == 0 {
+                    Some(range.start..range.start)
+                } else if row_index + 1 == n {
+                    Some(range.end..range.end)
+                } else {
+                    None
+                }
+            });
+            let mut glyph_runs: Vec<GlyphRun> = Vec::new();
+            let mut tab_gaps = Vec::new();
+            let mut open_run = None;
+            for placed in row {
+                let unit = match &items[placed.item] {
+                    Item::Tab(gap) => {
+                        tab_gaps.push(TabGap {
+                            x: placed.x,
+                            width: placed.advance,
+                            ..gap.clone()
+                        });
+                        open_run = None;
+                        continue;
+                    }
+                    Item::Grapheme(unit) => unit,
+                };
+                if open_run != Some(unit.run) {
+                    let source_run = &line.glyph_runs[unit.run];
+                    glyph_runs.push(GlyphRun {
+                        glyphs: Vec::new(),
+                        offset_range: unit.offset..unit.offset,
+                        text: String::new(),
+                        x: placed.x,
+                        width: 0.0,
+                        graphemes: Vec::new(),
+                        cursor_ascent,
+                        cursor_descent,
+                        rtl: false,
+                        ..source_run.clone()
+                    });
+                    open_run = Some(unit.run);
+                }
+                let run = glyph_runs.last_mut().expect("a run was just opened");
+                let shift = placed.x + (placed.advance - unit.advance) / 2.0 - unit.x;
+                run.glyphs.extend(unit.glyphs.iter().map(|glyph| Glyph {
+                    id: glyph.id,
+                    x: glyph.x + shift,
+                    y: glyph.y - line.baseline + baseline,
+                }));
+                run.offset_range.end = unit.offset + unit.codepoints as usize;
+                run.text.push_str(&unit.text);
+                run.width += placed.advance;
+                run.graphemes.push(GraphemeSpan {
+                    advance: placed.advance,
+                    codepoints: unit.codepoints,
+                });
+            }
+            lines.push(MeasuredLine {
+                node: line.node,
+                height: pitch,
+                baseline,
+                ascent: baseline,
+                descent: pitch - baseline,
+                cursor_ascent,
+                cursor_descent,
+                glyph_runs,
+                ruby_annotations: Vec::new(),
+                empty_caret_x: if line_index == 0 && row_index == 0 {
+                    cell
+                } else {
+                    0.0
+                },
+                offset_range,
+                tab_gaps,
+                is_phantom: line.is_phantom,
+                content_edge_x: None,
+            });
+        }
+    }
+    let total_height = lines.len() as f32 * pitch;
+    (lines, total_height)
+}
+
+#[cfg(test)]
+mod tests {
+    use editor_crdt::{Dot, InputEvent, ListOp, build_oplog};
+    use editor_model::{
+        AliasLog, DocLogs, DocView, ModifierAttrLog, NodeAttrLog, NodeType, SeqItem, SpanLog,
+        project_document,
+    };
+
+    use super::*;
+
+    const GRID: ManuscriptGrid = ManuscriptGrid {
+        columns: 5,
+        cell_size: 20.0,
+        row_gap: 8.0,
+    };
+
+    fn paragraph(text: &str) -> DocLogs {
+        let mut items = vec![(
+            Dot::new(1, 1),
+            SeqItem::Block {
+                node_type: NodeType::Paragraph,
+                parents: vec![Dot::ROOT],
+                attrs: vec![],
+            },
+        )];
+        for (i, c) in text.chars().enumerate() {
+            items.push((Dot::new(1, 2 + i as u64), SeqItem::Char(c)));
+        }
+        let mut ev = Vec::new();
+        let mut prev: Option<Dot> = None;
+        for (i, (id, item)) in items.into_iter().enumerate() {
+            ev.push(InputEvent {
+                id,
+                parents: prev.into_iter().collect(),
+                op: ListOp::Ins { pos: i, item },
+            });
+            prev = Some(id);
+        }
+        DocLogs {
+            seq: build_oplog(&ev),
+            spans: SpanLog::new(),
+            block_modifiers: ModifierAttrLog::new(),
+            node_attrs: NodeAttrLog::new(),
+            node_carries: ModifierAttrLog::new(),
+            aliases: AliasLog::new(),
+        }
+    }
+
+    /// `(x, advance)` of every grapheme, row by row.
+    fn cells(text: &str) -> Vec<Vec<(f32, f32)>> {
+        let doc = paragraph(text);
+        let pd = project_document(&doc).unwrap();
+        let view = DocView::new(&pd);
+        let para = view.root().unwrap().child_blocks().next().unwrap();
+        let (lines, height) =
+            layout_manuscript_paragraph(&para, GRID, 1000.0, None, None, &mut Resource::new_test());
+        assert_eq!(height, lines.len() as f32 * GRID.row_pitch());
+        lines
+            .iter()
+            .map(|line| {
+                line.glyph_runs
+                    .iter()
+                    .flat_map(|run| {
+                        let mut x = run.x;
+                        run.graphemes.iter().map(move |g| {
+                            let cell = (x, g.advance);
+                            x += g.advance;
+                            cell
+                        })
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn indent_cell_then_one_grapheme_per_cell() {
+        assert_eq!(
+            cells("가나다라마바"),
+            vec![
+                vec![(20.0, 20.0), (40.0, 20.0), (60.0, 20.0), (80.0, 20.0)],
+                vec![(0.0, 20.0), (20.0, 20.0)],
+            ]
+        );
+    }
+
+    #[test]
+    fn latin_letters_pair_up_in_a_cell() {
+        assert_eq!(
+            cells("abc가"),
+            vec![vec![(20.0, 10.0), (30.0, 10.0), (40.0, 20.0), (60.0, 20.0)]],
+            "the lone `c` widens over its cell's empty half"
+        );
+    }
+
+    #[test]
+    fn closing_punctuation_shares_the_last_cell_instead_of_starting_a_row() {
+        assert_eq!(
+            cells("가나다라."),
+            vec![vec![
+                (20.0, 20.0),
+                (40.0, 20.0),
+                (60.0, 20.0),
+                (80.0, 10.0),
+                (90.0, 10.0),
+            ]]
+        );
+        assert_eq!(cells("가.\u{201D}")[0][1..], [(40.0, 10.0), (50.0, 10.0)]);
+    }
+
+    #[test]
+    fn space_at_row_start_collapses() {
+        let rows = cells("가나다라 마");
+        assert_eq!(rows[1], vec![(0.0, 0.0), (0.0, 20.0)]);
+    }
+
+    #[test]
+    fn rows_are_pitch_tall_with_the_caret_spanning_the_cell() {
+        let doc = paragraph("가");
+        let pd = project_document(&doc).unwrap();
+        let view = DocView::new(&pd);
+        let para = view.root().unwrap().child_blocks().next().unwrap();
+        let (lines, _) =
+            layout_manuscript_paragraph(&para, GRID, 1000.0, None, None, &mut Resource::new_test());
+        let line = &lines[0];
+        assert_eq!(line.height, GRID.row_pitch());
+        assert!((line.baseline - line.cursor_ascent - GRID.row_gap).abs() < 1e-4);
+        assert!((line.cursor_ascent + line.cursor_descent - GRID.cell_size).abs() < 1e-4);
+        assert_eq!(line.empty_caret_x, GRID.cell_size);
+    }
+
+    #[test]
+    fn snap_gap_rounds_up_to_whole_rows() {
+        assert_eq!(GRID.snap_gap(56.0), 0.0);
+        assert_eq!(GRID.snap_gap(30.0), 26.0);
+        assert_eq!(GRID.snap_gap(0.0), 0.0);
+    }
+}
@@ -1,8 +1,26 @@
-pub(crate) fn empty_caret_x_for(align: Alignment, indent: f32, width: f32) -> f32 {
-    match align {
-        Alignment::Left | Alignment::Justify => indent,
-        Alignment::Center => width / 2.0,
-        Alignment::Right => width,
+/// Alignment follows the base direction, so a right-to-left paragraph's
+/// `Left` caret rests at the right edge, inset by the indent.
+pub(crate) fn empty_caret_x_for(
+    align: Alignment,
+    direction: TextDirection,
+    indent: f32,
+    width: f32,
+) -> f32 {
+    match (align, direction) {
+        (Alignment::Center, _) => width / 2.0,
+        (Alignment::Left | Alignment::Justify, TextDirection::Rtl) => width - indent,
+        (Alignment::Right, TextDirection::Rtl) => 0.0,
+        (Alignment::Left | Alignment::Justify, _) => indent,
+        (Alignment::Right, _) => width,
+    }
+}
+
+/// The paragraph's base direction. `Direction` never inherits, so only the
+/// paragraph's own modifier counts.
+fn paragraph_direction(node: &NodeView) -> TextDirection {
+    match node.effective().get(&ModifierType::Direction) {
+        Some(Modifier::Direction { value }) => *value,
+        _ => TextDirection::default(),
     }
 }
 
@@ -10,7 +28,7 @@ use std::ops::Range;
 use std::sync::Arc;
 
 use editor_crdt::Dot;
-use editor_model::{Alignment, ChildView, Modifier, NodeView};
+use editor_model::{Alignment, ChildView, Modifier, ModifierType, NodeView, TextDirection};
 use editor_resource::Resource;
 use editor_state::{Affinity, Position};
 
@@ -18,10 +36,10 @@ use crate::glyph_run::RubyAnnotation;
 
 use super::extract::LineHeightConfig;
 use super::extract::{ExtractedLine, extract_lines, resolve_link};
+use super::hyphenate::build_hyphenated_layout;
 use super::inline::{
     RubyGroup, Segment, TabMark, TextRun, collect_text_runs, identify_ruby_groups, split_segments,
 };
-use super::layout::build_layout;
 use super::resolve::{ResolvedTextStyle, apply_pending_to_style, style_from_effective_modifiers};
 use super::ruby::build_ruby_annotations;
 use super::ruby::ruby_extra_top;
@@ -152,6 +170,7 @@ pub(crate) fn build_strut_only_line(
     base_style: &ResolvedTextStyle,
     width: f32,
     align: Alignment,
+    direction: TextDirection,
     indent: f32,
     offset_range: Range<usize>,
     resource: &mut Resource,
@@ -174,7 +193,7 @@ pub(crate) fn build_strut_only_line(
         cursor_descent: strut.descent,
         glyph_runs: vec![],
         ruby_annotations: vec![],
-        empty_caret_x: empty_caret_x_for(align, indent, width),
+        empty_caret_x: empty_caret_x_for(align, direction, indent, width),
         offset_range: Some(offset_range),
         tab_gaps: vec![],
         is_phantom: false,
@@ -191,6 +210,7 @@ fn measure_segment<'a>(
     tabs: &[TabMark<'a>],
     width: f32,
     align: Alignment,
+    direction: TextDirection,
     indent: f32,
     base_style: &ResolvedTextStyle,
     ruby_groups: &[RubyGroup],
@@ -204,6 +224,7 @@ fn measure_segment<'a>(
                 base_style,
                 width,
                 align,
+                direction,
                 indent,
                 offset_range.clone(),
                 resource,
@@ -245,6 +266,7 @@ fn measure_segment<'a>(
             base_style,
             width,
             align,
+            direction,
             indent,
             seg_off.clone(),
             resource,
@@ -267,10 +289,11 @@ fn measure_segment<'a>(
             (t, px)
         })
         .collect();
-    let layout = build_layout(
+    let (layout_text, layout) = build_hyphenated_layout(
         seg_text,
         &style_runs,
         align,
+        direction,
         indent,
         width,
         resource,
@@ -278,7 +301,7 @@ fn measure_segment<'a>(
     );
     let segmenters = Arc::clone(resource.segmenters());
     let lines = extract_lines(
-        seg_text,
+        &layout_text,
         &layout,
         &style_runs,
         &seg_runs,
@@ -366,7 +389,7 @@ fn measure_segment<'a>(
                 cursor_descent: strut.descent,
                 glyph_runs,
                 ruby_annotations,
-                empty_caret_x: empty_caret_x_for(align, indent, width),
+                empty_caret_x: empty_caret_x_for(align, direction, indent, width),
                 offset_range: line_offset_range,
                 tab_gaps,
                 is_phantom: line.is_phantom,
@@ -414,6 +437,7 @@ pub(crate) fn measure_paragraph(
     // (that made a paragraph with `S` hard-break segments `O(S · paragraph)`).
     let ruby_groups = identify_ruby_groups(node);
 
+    let direction = paragraph_direction(node);
     let node_id = node.id();
     let mut lines: Vec<MeasuredLine> = Vec::new();
     for (i, seg) in segments.iter().enumerate() {
@@ -434,6 +458,7 @@ pub(crate) fn measure_paragraph(
                 &tabs,
                 width,
                 align,
+                direction,
                 seg_indent,
                 &base_style,
             );
@@ -452,6 +477,7 @@ pub(crate) fn measure_paragraph(
                 &tabs,
                 width,
                 align,
+                direction,
                 seg_indent,
                 &base_style,
                 &ruby_groups,
@@ -470,6 +496,7 @@ pub(crate) fn measure_paragraph(
                 &tabs,
                 width,
                 align,
+                direction,
                 seg_indent,
                 &base_style,
                 &ruby_groups,
@@ -514,8 +541,16 @@ mod tests {
     fn strut_only_line_has_height_and_no_glyphs() {
         let mut res = Resource::new_test();
         let node = Dot::new(1, 1);
-        let line =
-            build_strut_only_line(node, &base(), 100.0, Alignment::Left, 0.0, 0..0, &mut res);
+        let line = build_strut_only_line(
+            node,
+            &base(),
+            100.0,
+            Alignment::Left,
+            TextDirection::Auto,
+            0.0,
+            0..0,
+            &mut res,
+        );
         assert!(line.height > 0.0);
         assert!(line.glyph_runs.is_empty());
         assert!(line.ruby_annotations.is_empty());
@@ -770,6 +805,37 @@ mod tests {
         assert!(!lines[0].is_phantom);
     }
 
+    fn with_direction(mut l: DocLogs, value: TextDirection) -> DocLogs {
+        l.block_modifiers = ModifierAttrLog::new()
+            .apply(
+                Dot::new(60, 1),
+                ModifierAttrOp::SetModifier {
+                    target: Dot::new(1, 1),
+                    modifier: Modifier::Direction { value },
+                },
+            )
+            .unwrap();
+        l
+    }
+
+    #[test]
+    fn rtl_paragraph_mirrors_empty_caret_and_forces_base_direction() {
+        let (lines, _) = measure(
+            &with_direction(build_logs(vec![]), TextDirection::Rtl),
+            200.0,
+        );
+        assert_eq!(lines[0].empty_caret_x, 200.0);
+
+        // The base direction mark is shaped but owns no offset.
+        let l = with_direction(build_logs(vec![ch('a'), ch('b')]), TextDirection::Rtl);
+        let (lines, _) = measure(&l, 1.0e6);
+        let grs = glyph_runs(&lines);
+        assert_eq!(grs.iter().map(|g| g.offset_range.len()).sum::<usize>(), 2);
+        assert!(grs.iter().all(|g| !g.text.contains('\u{200F}')));
+        let (lines, _) = measure(&build_logs(vec![ch('a'), ch('b')]), 1.0e6);
+        assert!(glyph_runs(&lines).iter().all(|g| !g.rtl));
+    }
+
     #[test]
     fn hard_break_two_segments_offsets_absolute() {
         let (lines, _h) = measure(&build_logs(vec![ch('a'), hb(), ch('b')]), 1.0e6);
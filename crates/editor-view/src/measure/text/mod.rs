@@ -1,6 +1,8 @@
 pub(crate) mod extract;
+pub(crate) mod hyphenate;
 pub(crate) mod inline;
 pub(crate) mod layout;
+pub(crate) mod manuscript;
 pub(crate) mod measure;
 pub(crate) mod resolve;
 pub(crate) mod ruby;
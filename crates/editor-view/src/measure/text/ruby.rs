@@ -339,6 +339,7 @@ mod tests {
             }],
             cursor_ascent: 0.0,
             cursor_descent: 0.0,
+            rtl: false,
         }
     }
 
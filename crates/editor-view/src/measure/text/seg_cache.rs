@@ -3,7 +3,7 @@ use std::hash::{Hash, Hasher};
 use std::ops::Range;
 
 use editor_crdt::Dot;
-use editor_model::Alignment;
+use editor_model::{Alignment, TextDirection};
 use hashbrown::HashMap;
 
 use super::inline::{TabMark, TextRun};
@@ -114,7 +114,7 @@ fn hash_style<H: Hasher>(s: &ResolvedTextStyle, h: &mut H) {
 }
 
 /// Content hash capturing everything a segment's measured output depends on: its
-/// text, width/alignment/indent, paragraph base style, inline text runs, and tab
+/// text, width/alignment/direction/indent, paragraph base style, inline text runs, and tab
 /// marks. Text runs hash relative byte/offset ranges and style/modifier state;
 /// tabs hash relative offset and style/modifier state because their measured gap
 /// and link rectangles are part of the cached `MeasuredLine`.
@@ -130,6 +130,7 @@ pub(crate) fn segment_hash(
     tabs: &[TabMark],
     width: f32,
     align: Alignment,
+    direction: TextDirection,
     indent: f32,
     base_style: &ResolvedTextStyle,
 ) -> u64 {
@@ -137,6 +138,7 @@ pub(crate) fn segment_hash(
     seg_text.hash(&mut h);
     width.to_bits().hash(&mut h);
     (align as u8).hash(&mut h);
+    (direction as u8).hash(&mut h);
     indent.to_bits().hash(&mut h);
     hash_style(base_style, &mut h);
     for r in runs
@@ -235,6 +237,7 @@ mod tests {
                 &[],
                 100.0,
                 Alignment::Left,
+                TextDirection::Auto,
                 0.0,
                 &style(),
             )
@@ -286,6 +289,7 @@ mod tests {
                 &tabs,
                 100.0,
                 Alignment::Left,
+                TextDirection::Auto,
                 0.0,
                 &style(),
             )
@@ -3,6 +3,7 @@ use std::collections::BTreeMap;
 use editor_model::{Alignment, Modifier, ModifierType, OwnModifier};
 use editor_resource::Resource;
 
+use super::hyphenate::LayoutText;
 use super::inline::TextRun;
 use super::layout::build_layout;
 use super::resolve::ResolvedTextStyle;
@@ -29,7 +30,7 @@ pub fn tab_px(style: &ResolvedTextStyle, resource: &mut Resource) -> f32 {
         &grapheme_segmenter.grapheme,
     );
     let layout = build_layout(
-        space,
+        &LayoutText::plain(space),
         &style_runs,
         Alignment::Left,
         0.0,
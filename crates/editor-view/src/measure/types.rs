@@ -96,6 +96,7 @@ pub(crate) enum MeasuredContent {
     Atom(MeasuredAtom),
     Spacing(f32),
     PageBreak,
+    ColumnBreak,
 }
 
 impl MeasuredNode {
@@ -116,7 +117,9 @@ impl MeasuredNode {
         match &self.content {
             MeasuredContent::Box(b) => b.page_break_policy,
             MeasuredContent::Line(_) | MeasuredContent::Atom(_) => PageBreakPolicy::Avoid,
-            MeasuredContent::Spacing(_) | MeasuredContent::PageBreak => PageBreakPolicy::Auto,
+            MeasuredContent::Spacing(_)
+            | MeasuredContent::PageBreak
+            | MeasuredContent::ColumnBreak => PageBreakPolicy::Auto,
         }
     }
 }
@@ -3,6 +3,11 @@ use editor_macros::ffi;
 use serde::{Deserialize, Serialize};
 
 /// A y-range window into the LayoutTree produced by the two-pass layout.
+///
+/// A multi-column page keeps its columns stacked one after another in
+/// document space (the "galley"): the LayoutTree is laid out at a single
+/// column's width, and each [`PageColumn`] translates its own slice of the
+/// page's y-range to its place on the physical page.
 #[derive(Debug, Clone, PartialEq)]
 pub struct LayoutPage {
     /// Physical page top in document coordinates.
@@ -14,6 +19,57 @@ pub struct LayoutPage {
     /// Bottom of the drawable content window in document coordinates.
     pub content_y_end: f32,
     pub size: Size,
+    /// Column slices in flow order; never empty. A single-column page has one
+    /// column spanning the whole page with no horizontal offset.
+    pub columns: Vec<PageColumn>,
+}
+
+/// One column's slice of a page's document-space y-range and the translation
+/// that places it on the page.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PageColumn {
+    /// Slice top in document coordinates. The first column starts at the page
+    /// top so the top margin belongs to it.
+    pub y_start: f32,
+    /// Slice bottom in document coordinates. The last column ends at the page
+    /// bottom so the bottom margin belongs to it.
+    pub y_end: f32,
+    /// Top of the column's drawable content window in document coordinates.
+    pub content_y_start: f32,
+    /// Bottom of the column's drawable content window in document coordinates.
+    pub content_y_end: f32,
+    /// Left edge of the column in page coordinates.
+    pub x_start: f32,
+    /// Right edge of the column in page coordinates.
+    pub x_end: f32,
+    /// Document-to-page x translation.
+    pub dx: f32,
+    /// Document-to-page y translation.
+    pub dy: f32,
+}
+
+impl PageColumn {
+    pub fn to_page_x(&self, x: f32) -> f32 {
+        x + self.dx
+    }
+
+    pub fn to_page_y(&self, y: f32) -> f32 {
+        y + self.dy
+    }
+
+    pub fn to_page_rect(&self, rect: Rect) -> Rect {
+        Rect::from_xywh(rect.x + self.dx, rect.y + self.dy, rect.width, rect.height)
+    }
+
+    fn x_distance(&self, page_x: f32) -> f32 {
+        if page_x < self.x_start {
+            self.x_start - page_x
+        } else if page_x > self.x_end {
+            page_x - self.x_end
+        } else {
+            0.0
+        }
+    }
 }
 
 impl LayoutPage {
@@ -28,15 +84,96 @@ impl LayoutPage {
         content_y_end: f32,
         size: Size,
     ) -> Self {
-        debug_assert!(y_start.is_finite() && y_end.is_finite());
-        Self {
+        let column = PageColumn {
             y_start,
             y_end,
             content_y_start,
             content_y_end,
+            x_start: 0.0,
+            x_end: size.width,
+            dx: 0.0,
+            dy: -y_start,
+        };
+        Self::with_columns(y_start, y_end, size, vec![column])
+    }
+
+    pub fn with_columns(y_start: f32, y_end: f32, size: Size, columns: Vec<PageColumn>) -> Self {
+        debug_assert!(y_start.is_finite() && y_end.is_finite());
+        debug_assert!(!columns.is_empty());
+        Self {
+            y_start,
+            y_end,
+            content_y_start: columns.first().map_or(y_start, |c| c.content_y_start),
+            content_y_end: columns.last().map_or(y_end, |c| c.content_y_end),
             size,
+            columns,
         }
     }
+
+    /// Index of the column whose slice holds document `y`, clamped to the
+    /// first or last column outside the page.
+    pub fn column_index_at_y(&self, y: f32) -> usize {
+        self.columns
+            .iter()
+            .position(|column| y < column.y_end)
+            .unwrap_or(self.columns.len() - 1)
+    }
+
+    pub fn column_at_y(&self, y: f32) -> &PageColumn {
+        &self.columns[self.column_index_at_y(y)]
+    }
+
+    /// The column nearest to page-space `page_x`; a point in a gutter goes to
+    /// the closer neighbour.
+    pub fn column_at_page_x(&self, page_x: f32) -> &PageColumn {
+        self.columns
+            .iter()
+            .min_by(|a, b| a.x_distance(page_x).total_cmp(&b.x_distance(page_x)))
+            .unwrap_or_else(|| self.last_column())
+    }
+
+    /// Translates a document rect to page coordinates through the column
+    /// holding its top edge.
+    pub fn to_page_rect(&self, rect: Rect) -> Rect {
+        self.column_at_y(rect.y).to_page_rect(rect)
+    }
+
+    /// Clips the document y-range `top..bottom` to each column slice it
+    /// overlaps, yielding the column and the clipped document range.
+    pub fn column_slices(
+        &self,
+        top: f32,
+        bottom: f32,
+    ) -> impl Iterator<Item = (&PageColumn, f32, f32)> + '_ {
+        self.columns.iter().filter_map(move |column| {
+            if bottom <= column.y_start || top >= column.y_end {
+                return None;
+            }
+            Some((column, top.max(column.y_start), bottom.min(column.y_end)))
+        })
+    }
+
+    pub fn is_multi_column(&self) -> bool {
+        self.columns.len() > 1
+    }
+
+    fn last_column(&self) -> &PageColumn {
+        self.columns.last().expect("a page has at least one column")
+    }
+}
+
+/// The 원고지 cell grid every page carries in manuscript layout mode, in page
+/// coordinates.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ManuscriptPageGrid {
+    /// Top-left of the first row, including its blank band.
+    pub x: f32,
+    pub y: f32,
+    pub columns: usize,
+    pub rows: usize,
+    pub cell_size: f32,
+    /// Blank band above each row of cells.
+    pub row_gap: f32,
 }
 
 #[ffi]
@@ -91,4 +228,68 @@ mod tests {
         assert_eq!(stripped.rect, Rect::from_xywh(10.0, 20.0, 100.0, 50.0));
         assert_eq!(stripped.meta, ());
     }
+
+    fn two_column_page() -> LayoutPage {
+        // 200x100 page, margins 10, gutter 20: each column is 80 wide and 80
+        // tall; the page spans 10 + 80 + 80 + 10 = 180 in document space.
+        let columns = vec![
+            PageColumn {
+                y_start: 0.0,
+                y_end: 90.0,
+                content_y_start: 10.0,
+                content_y_end: 90.0,
+                x_start: 10.0,
+                x_end: 90.0,
+                dx: 0.0,
+                dy: 0.0,
+            },
+            PageColumn {
+                y_start: 90.0,
+                y_end: 180.0,
+                content_y_start: 90.0,
+                content_y_end: 170.0,
+                x_start: 110.0,
+                x_end: 190.0,
+                dx: 100.0,
+                dy: -80.0,
+            },
+        ];
+        LayoutPage::with_columns(0.0, 180.0, Size::new(200.0, 100.0), columns)
+    }
+
+    #[test]
+    fn single_column_page_translates_by_page_top() {
+        let page = LayoutPage::with_content(100.0, 200.0, 110.0, 190.0, Size::new(50.0, 100.0));
+        assert!(!page.is_multi_column());
+        let rect = page.to_page_rect(Rect::from_xywh(5.0, 120.0, 10.0, 10.0));
+        assert_eq!(rect, Rect::from_xywh(5.0, 20.0, 10.0, 10.0));
+    }
+
+    #[test]
+    fn second_column_rect_moves_right_and_up() {
+        let page = two_column_page();
+        assert_eq!(page.content_y_start, 10.0);
+        assert_eq!(page.content_y_end, 170.0);
+        let rect = page.to_page_rect(Rect::from_xywh(10.0, 95.0, 30.0, 5.0));
+        assert_eq!(rect, Rect::from_xywh(110.0, 15.0, 30.0, 5.0));
+    }
+
+    #[test]
+    fn column_at_page_x_picks_nearest_column() {
+        let page = two_column_page();
+        assert_eq!(page.column_at_page_x(50.0).dx, 0.0);
+        assert_eq!(page.column_at_page_x(150.0).dx, 100.0);
+        assert_eq!(page.column_at_page_x(95.0).dx, 0.0);
+        assert_eq!(page.column_at_page_x(105.0).dx, 100.0);
+    }
+
+    #[test]
+    fn column_slices_split_a_range_at_the_column_boundary() {
+        let page = two_column_page();
+        let slices: Vec<_> = page
+            .column_slices(50.0, 120.0)
+            .map(|(column, top, bottom)| (column.dx, top, bottom))
+            .collect();
+        assert_eq!(slices, vec![(0.0, 50.0, 90.0), (100.0, 90.0, 120.0)]);
+    }
 }
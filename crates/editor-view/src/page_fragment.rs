@@ -6,7 +6,7 @@ use editor_crdt::Dot;
 use crate::glyph_run::GlyphRun;
 use crate::glyph_run::RubyAnnotation;
 use crate::measure::text::measure::TabGap;
-use crate::page::LayoutPage;
+use crate::page::{LayoutPage, PageColumn};
 use crate::paginate::types::{
     ChildAttachment, LayoutAtom, LayoutContent, LayoutLine, LayoutNode, LayoutTree,
 };
@@ -16,7 +16,9 @@ use crate::style::{BoxStyle, DecorationData};
 #[derive(Debug, Clone)]
 pub struct PageFragmentTree {
     pub page_idx: usize,
-    pub root: Option<PageFragmentNode>,
+    /// The document root clipped to each column's content window, in column
+    /// order. Columns that show nothing contribute no root.
+    pub roots: Vec<PageFragmentNode>,
 }
 
 #[derive(Debug, Clone)]
@@ -85,15 +87,19 @@ pub(crate) fn build_page_fragment_tree(
 ) -> PageFragmentTree {
     PageFragmentTree {
         page_idx,
-        root: fragment_node(&tree.root, page),
+        roots: page
+            .columns
+            .iter()
+            .filter_map(|column| fragment_node(&tree.root, column))
+            .collect(),
     }
 }
 
-fn fragment_node(node: &LayoutNode, page: &LayoutPage) -> Option<PageFragmentNode> {
+fn fragment_node(node: &LayoutNode, column: &PageColumn) -> Option<PageFragmentNode> {
     let node_top = node.rect.y;
     let node_bottom = node.rect.bottom();
-    let visible_top = page.content_y_start;
-    let visible_bottom = page.content_y_end;
+    let visible_top = column.content_y_start;
+    let visible_bottom = column.content_y_end;
 
     if node_bottom <= visible_top || node_top >= visible_bottom {
         return None;
@@ -104,8 +110,8 @@ fn fragment_node(node: &LayoutNode, page: &LayoutPage) -> Option<PageFragmentNod
             let fragment_top = node_top.max(visible_top);
             let fragment_bottom = node_bottom.min(visible_bottom);
             let rect = Rect::from_xywh(
-                node.rect.x,
-                fragment_top - page.y_start,
+                column.to_page_x(node.rect.x),
+                column.to_page_y(fragment_top),
                 node.rect.width,
                 fragment_bottom - fragment_top,
             );
@@ -122,12 +128,12 @@ fn fragment_node(node: &LayoutNode, page: &LayoutPage) -> Option<PageFragmentNod
                     .style
                     .decorations
                     .iter()
-                    .filter_map(|dec| fragment_decoration(node, dec, page))
+                    .filter_map(|dec| fragment_decoration(node, dec, column))
                     .collect(),
                 children: b
                     .children
                     .iter()
-                    .filter_map(|child| fragment_node(child, page))
+                    .filter_map(|child| fragment_node(child, column))
                     .collect(),
                 attachment: b.attachment.clone(),
             });
@@ -136,7 +142,7 @@ fn fragment_node(node: &LayoutNode, page: &LayoutPage) -> Option<PageFragmentNod
         LayoutContent::Line(l) => {
             debug_assert!(
                 node_top >= visible_top && node_bottom <= visible_bottom,
-                "line layout node should be contained by its page content window"
+                "line layout node should be contained by its column content window"
             );
             PageFragmentContent::Line(fragment_line(l))
         }
@@ -144,8 +150,8 @@ fn fragment_node(node: &LayoutNode, page: &LayoutPage) -> Option<PageFragmentNod
             let fragment_top = node_top.max(visible_top);
             let fragment_bottom = node_bottom.min(visible_bottom);
             let rect = Rect::from_xywh(
-                node.rect.x,
-                fragment_top - page.y_start,
+                column.to_page_x(node.rect.x),
+                column.to_page_y(fragment_top),
                 node.rect.width,
                 fragment_bottom - fragment_top,
             );
@@ -158,31 +164,26 @@ fn fragment_node(node: &LayoutNode, page: &LayoutPage) -> Option<PageFragmentNod
         }
     };
 
-    let rect = Rect::from_xywh(
-        node.rect.x,
-        node.rect.y - page.y_start,
-        node.rect.width,
-        node.rect.height,
-    );
+    let rect = column.to_page_rect(node.rect);
     Some(PageFragmentNode { rect, content })
 }
 
 fn fragment_decoration(
     node: &LayoutNode,
     dec: &crate::style::Decoration,
-    page: &LayoutPage,
+    column: &PageColumn,
 ) -> Option<PageFragmentDecoration> {
     let dec_abs_y = node.rect.y + dec.rect.y;
     let dec_abs_bottom = dec_abs_y + dec.rect.height;
 
-    if dec_abs_y < page.content_y_start || dec_abs_bottom > page.content_y_end {
+    if dec_abs_y < column.content_y_start || dec_abs_bottom > column.content_y_end {
         return None;
     }
 
     Some(PageFragmentDecoration {
         rect: Rect::from_xywh(
-            node.rect.x + dec.rect.x,
-            dec_abs_y - page.y_start,
+            column.to_page_x(node.rect.x + dec.rect.x),
+            column.to_page_y(dec_abs_y),
             dec.rect.width,
             dec.rect.height,
         ),
@@ -344,7 +345,11 @@ mod tests {
         let fragment = build_page_fragment_tree(&tree, 0, &pg);
 
         assert_eq!(fragment.page_idx, 0);
-        let root_frag = fragment.root.expect("root must be present");
+        let root_frag = fragment
+            .roots
+            .into_iter()
+            .next()
+            .expect("root must be present");
         let root_box = root_frag.as_box().expect("root must be a box");
 
         assert_eq!(
@@ -372,7 +377,7 @@ mod tests {
         let pg = page(0.0, 100.0);
 
         let fragment = build_page_fragment_tree(&tree, 0, &pg);
-        let root_frag = fragment.root.unwrap();
+        let root_frag = fragment.roots.into_iter().next().unwrap();
         let root_box = root_frag.as_box().unwrap();
 
         assert_eq!(root_box.children.len(), 1);
@@ -396,7 +401,7 @@ mod tests {
         let pg = page(0.0, 200.0);
 
         let fragment = build_page_fragment_tree(&tree, 0, &pg);
-        let root_frag = fragment.root.unwrap();
+        let root_frag = fragment.roots.into_iter().next().unwrap();
         let root_box = root_frag.as_box().unwrap();
         assert_eq!(root_box.node, root_id, "box node Dot must be carried");
 
@@ -417,6 +422,61 @@ mod tests {
         }
     }
 
+    #[test]
+    fn second_column_fragment_is_translated_beside_the_first() {
+        use crate::page::PageColumn;
+
+        let root_id = elem(5, 0);
+        let first_id = elem(5, 1);
+        let second_id = elem(5, 2);
+
+        let first = line_node(first_id, 10.0, 20.0, 80.0, 20.0, Some(0..5));
+        let second = line_node(second_id, 10.0, 100.0, 80.0, 20.0, Some(5..10));
+        let root = box_node(root_id, 10.0, 10.0, 80.0, 160.0, vec![first, second], None);
+        let tree = LayoutTree { root };
+        let column = |y_start, y_end, content_y_start, content_y_end, x_start, dx, dy| PageColumn {
+            y_start,
+            y_end,
+            content_y_start,
+            content_y_end,
+            x_start,
+            x_end: x_start + 80.0,
+            dx,
+            dy,
+        };
+        let pg = LayoutPage::with_columns(
+            0.0,
+            180.0,
+            Size::new(200.0, 100.0),
+            vec![
+                column(0.0, 90.0, 10.0, 90.0, 10.0, 0.0, 0.0),
+                column(90.0, 180.0, 90.0, 170.0, 110.0, 100.0, -80.0),
+            ],
+        );
+
+        let fragment = build_page_fragment_tree(&tree, 0, &pg);
+        assert_eq!(fragment.roots.len(), 2, "each column gets its own root");
+
+        let first_box = fragment.roots[0].as_box().unwrap();
+        assert_eq!(first_box.children.len(), 1);
+        assert_eq!(first_box.children[0].rect, rect(10.0, 20.0, 80.0, 20.0));
+        assert!(
+            !first_box.edges.bottom,
+            "root continues into the next column"
+        );
+
+        let second_root = &fragment.roots[1];
+        assert_eq!(second_root.rect, rect(110.0, 10.0, 80.0, 80.0));
+        let second_box = second_root.as_box().unwrap();
+        assert!(!second_box.edges.top);
+        assert_eq!(second_box.children.len(), 1);
+        match &second_box.children[0].content {
+            PageFragmentContent::Line(l) => assert_eq!(l.node, second_id),
+            _ => panic!("expected Line"),
+        }
+        assert_eq!(second_box.children[0].rect, rect(110.0, 20.0, 80.0, 20.0));
+    }
+
     #[test]
     fn decoration_geometry_preserved() {
         use crate::style::{Decoration, DecorationData};
@@ -450,7 +510,7 @@ mod tests {
         let pg = page(0.0, 100.0);
 
         let fragment = build_page_fragment_tree(&tree, 0, &pg);
-        let root_frag = fragment.root.unwrap();
+        let root_frag = fragment.roots.into_iter().next().unwrap();
         let root_box = root_frag.as_box().unwrap();
 
         assert_eq!(
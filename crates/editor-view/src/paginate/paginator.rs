@@ -5,17 +5,24 @@ use editor_state::Position;
 
 use crate::measure::PageBreakPolicy;
 use crate::measure::types::{MeasuredBox, MeasuredContent, MeasuredNode, MeasuredTree};
-use crate::page::LayoutPage;
+use crate::page::{LayoutPage, PageColumn};
 use crate::style::*;
 
 use super::types::*;
 
+/// Upper bound on columns per page.
+const MAX_COLUMNS: usize = 16;
+
 pub(crate) struct Paginator {
     paginated: bool,
     page_width: f32,
     page_height: f32,
     content_height: f32,
     margins: EdgeInsets,
+    columns: usize,
+    column_width: f32,
+    column_gap: f32,
+    column_index: usize,
     accumulated_y: f32,
     current_x: f32,
     current_width: f32,
@@ -34,6 +41,10 @@ impl Paginator {
             page_height,
             content_height,
             margins,
+            columns: 1,
+            column_width: content_width,
+            column_gap: 0.0,
+            column_index: 0,
             accumulated_y: margins.top,
             current_x: margins.left,
             current_width: content_width,
@@ -43,6 +54,34 @@ impl Paginator {
         }
     }
 
+    /// Split each page's content area into `columns` columns separated by
+    /// `gap`. Content fills a column top to bottom before flowing into the
+    /// next; the last column of a page flows into the first of the next page.
+    /// Only meaningful for paginated layout. The count comes from the document,
+    /// so it is clamped to `MAX_COLUMNS` and to what leaves every column at
+    /// least one pixel wide.
+    pub fn with_columns(mut self, columns: usize, gap: f32) -> Self {
+        debug_assert!(self.paginated || columns <= 1);
+        let content_width = self.page_width - self.margins.left - self.margins.right;
+        let gap = gap.max(0.0);
+        // Saturating cast: a negative or NaN width fits no more than one column.
+        let fitting = ((content_width + gap) / (1.0 + gap)).floor() as usize;
+        let columns = columns.clamp(1, MAX_COLUMNS.min(fitting).max(1));
+        let gap = if columns > 1 { gap } else { 0.0 };
+        self.columns = columns;
+        self.column_gap = gap;
+        self.column_width =
+            ((content_width - gap * (columns - 1) as f32) / columns as f32).max(0.0);
+        self.current_width = self.column_width;
+        self
+    }
+
+    /// Width every top-level block is measured against — a single column's
+    /// width when the page is split into columns.
+    pub fn column_width(&self) -> f32 {
+        self.column_width
+    }
+
     pub fn continuous(page_width: f32, max_content_height: f32, margins: EdgeInsets) -> Self {
         let content_width = page_width - margins.left - margins.right;
         Self {
@@ -51,6 +90,10 @@ impl Paginator {
             page_height: 0.0,
             content_height: max_content_height,
             margins,
+            columns: 1,
+            column_width: content_width,
+            column_gap: 0.0,
+            column_index: 0,
             accumulated_y: margins.top,
             current_x: margins.left,
             current_width: content_width,
@@ -64,7 +107,8 @@ impl Paginator {
     /// context the full walk had when it reached the subtree: `accumulated_y`
     /// is the subtree's document-space top, `current_x`/`current_width` the
     /// parent box's content origin/width, and the page window that of the page
-    /// containing the top. Page advances past the seeded window evolve exactly
+    /// containing the top (`column_index` names the window's column on a
+    /// multi-column page). Page advances past the seeded window evolve exactly
     /// as in the full walk (they depend only on the window and the page
     /// stride), so a subtree whose geometry is unchanged reproduces its old
     /// placement bit-for-bit; the caller verifies that and falls back to a
@@ -79,12 +123,14 @@ impl Paginator {
         current_width: f32,
         page_content_top: f32,
         page_content_bottom: f32,
+        column_index: usize,
     ) -> LayoutNode {
         self.accumulated_y = accumulated_y;
         self.current_x = current_x;
         self.current_width = current_width;
         self.page_content_top = page_content_top;
         self.page_content_bottom = page_content_bottom;
+        self.column_index = column_index;
         self.place_node(node, parent, child_index, 0.0)
     }
 
@@ -159,7 +205,7 @@ impl Paginator {
                     }),
                 }
             }
-            MeasuredContent::PageBreak => LayoutNode {
+            MeasuredContent::PageBreak | MeasuredContent::ColumnBreak => LayoutNode {
                 rect: Rect::from_xywh(0.0, self.accumulated_y, 0.0, 0.0),
                 content: LayoutContent::Spacing(SpacingKind::Gap {
                     position: Position::new(parent, child_index),
@@ -226,14 +272,26 @@ impl Paginator {
                 self.accumulated_y -= overlap;
             }
 
-            // 3. PageBreak -> forced break
-            if matches!(child.content, MeasuredContent::PageBreak) {
-                if self.is_paginated() && !self.is_at_page_start() {
-                    self.break_page(&mut children);
+            // 3. PageBreak / ColumnBreak -> forced break. A column break
+            //    advances one column; a page break also skips the page's
+            //    remaining columns.
+            if matches!(
+                child.content,
+                MeasuredContent::PageBreak | MeasuredContent::ColumnBreak
+            ) {
+                if self.is_paginated() {
+                    if !self.is_at_page_start() {
+                        self.break_page(&mut children);
+                    }
+                    if matches!(child.content, MeasuredContent::PageBreak) {
+                        while self.column_index != 0 {
+                            self.break_page(&mut children);
+                        }
+                    }
                 }
                 prev_border_bottom = None;
                 child_index += 1;
-                continue; // PageBreak consumed, not added to output
+                continue; // Break consumed, not added to output
             }
 
             let child_terminal_chrome_after = if Some(raw_child_index) == terminal_child_index {
@@ -413,16 +471,14 @@ impl Paginator {
 
     fn start_new_page(&mut self) {
         if self.paginated {
-            let page_start = self.page_content_top - self.margins.top;
-            let page_end = self.page_content_bottom + self.margins.bottom;
-            self.pages.push(LayoutPage::with_content(
-                page_start,
-                page_end,
-                self.page_content_top,
-                self.page_content_bottom,
-                Size::new(self.page_width(), self.page_height),
-            ));
-            self.page_content_top = page_end + self.margins.top;
+            if self.column_index + 1 < self.columns {
+                self.column_index += 1;
+                self.page_content_top = self.page_content_bottom;
+            } else {
+                let page_end = self.push_paginated_page();
+                self.column_index = 0;
+                self.page_content_top = page_end + self.margins.top;
+            }
             self.page_content_bottom = self.page_content_top + self.content_height;
             self.accumulated_y = self.page_content_top;
         } else {
@@ -443,18 +499,53 @@ impl Paginator {
         }
     }
 
+    /// Push the page the current column belongs to and return its document
+    /// bottom. The page always spans every column, however many were filled.
+    fn push_paginated_page(&mut self) -> f32 {
+        let first_column_top =
+            self.page_content_top - self.column_index as f32 * self.content_height;
+        let page_start = first_column_top - self.margins.top;
+        let page_end =
+            first_column_top + self.columns as f32 * self.content_height + self.margins.bottom;
+        let size = Size::new(self.page_width(), self.page_height);
+        let page = if self.columns == 1 {
+            LayoutPage::with_content(
+                page_start,
+                page_end,
+                first_column_top,
+                first_column_top + self.content_height,
+                size,
+            )
+        } else {
+            let stride = self.column_width + self.column_gap;
+            let last = self.columns - 1;
+            let columns = (0..self.columns)
+                .map(|k| {
+                    let content_top = first_column_top + k as f32 * self.content_height;
+                    let content_bottom = content_top + self.content_height;
+                    let x_start = self.margins.left + k as f32 * stride;
+                    PageColumn {
+                        y_start: if k == 0 { page_start } else { content_top },
+                        y_end: if k == last { page_end } else { content_bottom },
+                        content_y_start: content_top,
+                        content_y_end: content_bottom,
+                        x_start,
+                        x_end: x_start + self.column_width,
+                        dx: k as f32 * stride,
+                        dy: -(page_start + k as f32 * self.content_height),
+                    }
+                })
+                .collect();
+            LayoutPage::with_columns(page_start, page_end, size, columns)
+        };
+        self.pages.push(page);
+        page_end
+    }
+
     fn finish(mut self) -> Vec<LayoutPage> {
-        if self.accumulated_y > self.page_content_top {
+        if self.accumulated_y > self.page_content_top || self.column_index > 0 {
             if self.paginated {
-                let page_start = self.page_content_top - self.margins.top;
-                let page_end = self.page_content_top + self.content_height + self.margins.bottom;
-                self.pages.push(LayoutPage::with_content(
-                    page_start,
-                    page_end,
-                    self.page_content_top,
-                    self.page_content_top + self.content_height,
-                    Size::new(self.page_width(), self.page_height),
-                ));
+                self.push_paginated_page();
             } else {
                 let is_first_page = self.pages.is_empty();
                 let page_start = if is_first_page {
@@ -471,13 +562,7 @@ impl Paginator {
             }
         } else if self.pages.is_empty() {
             if self.paginated {
-                self.pages.push(LayoutPage::with_content(
-                    0.0,
-                    self.page_height,
-                    self.margins.top,
-                    self.page_height - self.margins.bottom,
-                    Size::new(self.page_width(), self.page_height),
-                ));
+                self.push_paginated_page();
             } else {
                 self.pages.push(LayoutPage::new(
                     0.0,
@@ -491,6 +576,10 @@ impl Paginator {
             page.y_end += self.margins.bottom;
             page.content_y_end += self.margins.bottom;
             page.size.height += self.margins.bottom;
+            for column in &mut page.columns {
+                column.y_end += self.margins.bottom;
+                column.content_y_end += self.margins.bottom;
+            }
         }
         self.pages
     }
@@ -533,7 +622,7 @@ fn terminal_child_index(b: &MeasuredBox) -> Option<usize> {
     for (i, child) in b.children.iter().enumerate() {
         if !matches!(
             child.content,
-            MeasuredContent::Spacing(_) | MeasuredContent::PageBreak
+            MeasuredContent::Spacing(_) | MeasuredContent::PageBreak | MeasuredContent::ColumnBreak
         ) {
             last = Some(i);
         }
@@ -654,7 +743,7 @@ fn place_node_at(
                 position: Position::new(parent, child_index),
             }),
         },
-        MeasuredContent::PageBreak => LayoutNode {
+        MeasuredContent::PageBreak | MeasuredContent::ColumnBreak => LayoutNode {
             rect: Rect::from_xywh(x, y, 0.0, 0.0),
             content: LayoutContent::Spacing(SpacingKind::Gap {
                 position: Position::new(parent, child_index),
@@ -1126,6 +1215,97 @@ mod tests {
         assert!(!has_fill(&continuous.tree.root));
     }
 
+    fn two_paragraphs_split_by(leaf: AtomLeaf) -> DocLogs {
+        let root = Dot::ROOT;
+        logs(&[
+            (
+                Dot::new(1, 1),
+                SeqItem::Block {
+                    node_type: NodeType::Paragraph,
+                    parents: vec![root],
+                    attrs: vec![],
+                },
+            ),
+            (Dot::new(1, 3), SeqItem::Char('A')),
+            (Dot::new(1, 4), SeqItem::Atom(leaf)),
+            (
+                Dot::new(1, 2),
+                SeqItem::Block {
+                    node_type: NodeType::Paragraph,
+                    parents: vec![root],
+                    attrs: vec![],
+                },
+            ),
+            (Dot::new(1, 5), SeqItem::Char('B')),
+        ])
+    }
+
+    fn paginate_two_columns(doc: &DocLogs) -> PaginatedLayout {
+        let paginator =
+            Paginator::paginated(400.0, 1000.0, EdgeInsets::all(10.0)).with_columns(2, 20.0);
+        assert_eq!(paginator.column_width(), 180.0);
+        let (_, tree) = measure_doc(doc, paginator.column_width());
+        paginator.paginate(tree)
+    }
+
+    #[test]
+    fn single_column_page_spans_the_page_width() {
+        let doc = build_root_two_paragraphs_gap(None);
+        let (_, tree) = measure_doc(&doc, 380.0);
+        let layout = Paginator::paginated(400.0, 1000.0, EdgeInsets::all(10.0)).paginate(tree);
+
+        assert_eq!(layout.pages.len(), 1);
+        assert_eq!(
+            layout.pages[0],
+            LayoutPage::with_content(0.0, 1000.0, 10.0, 990.0, Size::new(400.0, 1000.0))
+        );
+    }
+
+    #[test]
+    fn column_count_is_clamped_to_the_page() {
+        let margins = EdgeInsets::all(10.0);
+        let paginator =
+            Paginator::paginated(400.0, 1000.0, margins).with_columns(4_000_000_000, 0.0);
+        assert_eq!(paginator.columns, MAX_COLUMNS);
+
+        // 380px of content fits 13 columns of at least 1px with 30px gaps.
+        let paginator = Paginator::paginated(400.0, 1000.0, margins).with_columns(15, 30.0);
+        assert_eq!(paginator.columns, 13);
+        assert!(paginator.column_width() >= 1.0);
+
+        let paginator = Paginator::paginated(20.0, 1000.0, margins).with_columns(2, 20.0);
+        assert_eq!((paginator.columns, paginator.column_width()), (1, 0.0));
+    }
+
+    #[test]
+    fn column_break_moves_to_next_column() {
+        let layout = paginate_two_columns(&two_paragraphs_split_by(AtomLeaf::ColumnBreak));
+
+        assert_eq!(layout.pages.len(), 1);
+        let page = &layout.pages[0];
+        assert_eq!(page.columns.len(), 2);
+        assert_eq!((page.y_start, page.y_end), (0.0, 1970.0));
+
+        let second = page.columns[1];
+        assert_eq!((second.x_start, second.x_end), (210.0, 390.0));
+        assert_eq!((second.dx, second.dy), (200.0, -980.0));
+
+        let p2 = find_box(&layout.tree.root, Dot::new(1, 2)).expect("second paragraph");
+        assert_eq!(p2.rect.y, second.content_y_start);
+        assert_eq!(p2.rect.width, 180.0);
+        assert_eq!(second.to_page_y(p2.rect.y), 10.0);
+    }
+
+    #[test]
+    fn page_break_skips_remaining_columns() {
+        let layout = paginate_two_columns(&two_paragraphs_split_by(AtomLeaf::PageBreak));
+
+        assert_eq!(layout.pages.len(), 2);
+        let p2 = find_box(&layout.tree.root, Dot::new(1, 2)).expect("second paragraph");
+        assert_eq!(p2.rect.y, layout.pages[1].content_y_start);
+        assert_eq!(layout.pages[1].column_index_at_y(p2.rect.y), 0);
+    }
+
     fn find_box(node: &LayoutNode, id: Dot) -> Option<&LayoutNode> {
         match &node.content {
             LayoutContent::Box(b) => {
@@ -0,0 +1,790 @@
+//! Accessibility tree for screen reader bridges. Nodes follow document order;
+//! a text node's `text` holds one char per inline slot (atoms become U+FFFC,
+//! tabs and hard breaks their control chars), so selection offsets index it
+//! directly.
+
+use editor_common::Rect;
+use editor_crdt::Dot;
+use editor_macros::ffi;
+use editor_model::{
+    ChildView, DocView, InlineItem, InlineKind, LeafView, Node, NodeType, NodeView,
+};
+use editor_state::{Position, Selection};
+use hashbrown::{HashMap, HashSet};
+use serde::{Deserialize, Serialize};
+
+use crate::measure::text::extract::resolve_link;
+use crate::page::PageRect;
+use crate::paginate::types::LayoutContent;
+
+use super::layout_index::LayoutIndex;
+
+const OBJECT_REPLACEMENT: char = '\u{FFFC}';
+
+#[ffi]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AccessibilityRole {
+    Document,
+    Paragraph,
+    List,
+    ListItem,
+    Table,
+    TableRow,
+    TableCell,
+    Link,
+    Image,
+    Separator,
+    Group,
+}
+
+#[ffi]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct AccessibilityNode {
+    pub id: Dot,
+    pub role: AccessibilityRole,
+    pub parent: Option<Dot>,
+    pub children: Vec<Dot>,
+    pub text: String,
+    pub label: Option<String>,
+    pub href: Option<String>,
+    pub bounds: Vec<PageRect>,
+}
+
+#[ffi]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct AccessibilityTextPosition {
+    pub node: Dot,
+    pub offset: usize,
+}
+
+/// A collapsed selection (anchor equal to focus) is the caret.
+#[ffi]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct AccessibilityTextSelection {
+    pub anchor: AccessibilityTextPosition,
+    pub focus: AccessibilityTextPosition,
+}
+
+#[ffi]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct AccessibilityTree {
+    pub root: Option<Dot>,
+    pub nodes: Vec<AccessibilityNode>,
+    pub selection: Option<AccessibilityTextSelection>,
+}
+
+/// Changes between two trees: nodes that are new or differ, nodes that are
+/// gone, and the current selection.
+#[ffi]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct AccessibilityUpdate {
+    pub root: Option<Dot>,
+    pub updated: Vec<AccessibilityNode>,
+    pub removed: Vec<Dot>,
+    pub selection: Option<AccessibilityTextSelection>,
+}
+
+impl AccessibilityUpdate {
+    pub fn is_empty(&self) -> bool {
+        self.updated.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// The tree as last handed to the host, so an update after a content splice
+/// rebuilds only the spliced blocks and diffs them against what the host holds.
+#[derive(Debug, Default)]
+pub(crate) struct RetainedAccessibility {
+    root: Option<Dot>,
+    nodes: HashMap<Dot, AccessibilityNode>,
+    /// False until the first snapshot; before it there is nothing to patch.
+    seeded: bool,
+}
+
+impl RetainedAccessibility {
+    pub(crate) fn reset(&mut self, tree: &AccessibilityTree) {
+        self.root = tree.root;
+        self.nodes = tree
+            .nodes
+            .iter()
+            .map(|node| (node.id, node.clone()))
+            .collect();
+        self.seeded = true;
+    }
+
+    /// Diffs a freshly built whole tree against the retained one.
+    pub(crate) fn replace(&mut self, tree: AccessibilityTree) -> AccessibilityUpdate {
+        let stale = self.root.map(|root| self.subtree(root)).unwrap_or_default();
+        let mut update = AccessibilityUpdate {
+            root: tree.root,
+            updated: Vec::new(),
+            removed: Vec::new(),
+            selection: tree.selection,
+        };
+        self.apply(tree.nodes, stale, &mut update);
+        self.root = tree.root;
+        self.seeded = true;
+        update
+    }
+
+    /// Rebuilds just the subtrees of `blocks`, which must have kept their
+    /// place in the tree. `None` when nothing is retained to patch yet.
+    pub(crate) fn update_blocks(
+        &mut self,
+        layout_index: &LayoutIndex,
+        view: &DocView,
+        blocks: impl IntoIterator<Item = Dot>,
+        selection: Option<&Selection>,
+    ) -> Option<AccessibilityUpdate> {
+        if !self.seeded {
+            return None;
+        }
+        let mut update = AccessibilityUpdate {
+            root: self.root,
+            updated: Vec::new(),
+            removed: Vec::new(),
+            selection: text_selection(selection),
+        };
+        // Blocks the host never saw, such as those inside a collapsed fold,
+        // have nothing to patch. Resolve every block before touching the
+        // retained tree so a `None` leaves it intact for the full rebuild.
+        let mut targets = Vec::new();
+        for id in blocks {
+            if let Some(node) = self.nodes.get(&id) {
+                targets.push((view.node(id)?, node.parent));
+            }
+        }
+        for (block, parent) in targets {
+            let stale = self.subtree(block.id());
+            let mut built = Vec::new();
+            build_block(layout_index, &block, parent, &mut built);
+            self.apply(built, stale, &mut update);
+        }
+        Some(update)
+    }
+
+    /// `id` and its retained descendants, in document order.
+    fn subtree(&self, id: Dot) -> Vec<Dot> {
+        let mut out = Vec::new();
+        let mut stack = vec![id];
+        while let Some(id) = stack.pop() {
+            let Some(node) = self.nodes.get(&id) else {
+                continue;
+            };
+            out.push(id);
+            stack.extend(node.children.iter().rev());
+        }
+        out
+    }
+
+    fn apply(
+        &mut self,
+        built: Vec<AccessibilityNode>,
+        stale: Vec<Dot>,
+        update: &mut AccessibilityUpdate,
+    ) {
+        let kept: HashSet<Dot> = built.iter().map(|node| node.id).collect();
+        for node in built {
+            if self.nodes.get(&node.id) != Some(&node) {
+                update.updated.push(node.clone());
+                self.nodes.insert(node.id, node);
+            }
+        }
+        for id in stale {
+            if !kept.contains(&id) {
+                self.nodes.remove(&id);
+                update.removed.push(id);
+            }
+        }
+    }
+}
+
+pub(crate) fn accessibility_tree(
+    layout_index: &LayoutIndex,
+    view: &DocView,
+    selection: Option<&Selection>,
+) -> AccessibilityTree {
+    let Some(root) = view.root() else {
+        return AccessibilityTree::default();
+    };
+    let mut nodes = Vec::new();
+    build_block(layout_index, &root, None, &mut nodes);
+    AccessibilityTree {
+        root: Some(root.id()),
+        nodes,
+        selection: text_selection(selection),
+    }
+}
+
+fn text_selection(selection: Option<&Selection>) -> Option<AccessibilityTextSelection> {
+    selection.map(|selection| AccessibilityTextSelection {
+        anchor: text_position(&selection.anchor),
+        focus: text_position(&selection.head),
+    })
+}
+
+fn text_position(pos: &Position) -> AccessibilityTextPosition {
+    AccessibilityTextPosition {
+        node: pos.node,
+        offset: pos.offset,
+    }
+}
+
+fn block_role(node: &NodeView) -> AccessibilityRole {
+    match node.node() {
+        Node::Root(_) => AccessibilityRole::Document,
+        Node::Paragraph(_) | Node::FoldTitle(_) => AccessibilityRole::Paragraph,
+        Node::BulletList(_) | Node::OrderedList(_) => AccessibilityRole::List,
+        Node::ListItem(_) => AccessibilityRole::ListItem,
+        Node::Table(_) => AccessibilityRole::Table,
+        Node::TableRow(_) => AccessibilityRole::TableRow,
+        Node::TableCell(_) => AccessibilityRole::TableCell,
+        _ => AccessibilityRole::Group,
+    }
+}
+
+/// Emits `block` and its subtree, returning its id. Blocks without layout,
+/// such as the content of a collapsed fold, are left out.
+fn build_block(
+    layout_index: &LayoutIndex,
+    block: &NodeView,
+    parent: Option<Dot>,
+    out: &mut Vec<AccessibilityNode>,
+) -> Option<Dot> {
+    let bounds = layout_index.content_page_rects(&block.id());
+    if bounds.is_empty() && parent.is_some() {
+        return None;
+    }
+    let slot = out.len();
+    out.push(AccessibilityNode {
+        id: block.id(),
+        role: block_role(block),
+        parent,
+        children: Vec::new(),
+        text: String::new(),
+        label: None,
+        href: None,
+        bounds,
+    });
+
+    let mut children = Vec::new();
+    if block.spec().is_textblock() {
+        out[slot].text = build_inline(layout_index, block, &mut children, out);
+    } else {
+        for child in block.children() {
+            let id = match child {
+                ChildView::Block(node) => build_block(layout_index, &node, Some(block.id()), out),
+                ChildView::Leaf(leaf) => build_atom(layout_index, &leaf, block.id(), out),
+            };
+            children.extend(id);
+        }
+    }
+    out[slot].children = children;
+    Some(block.id())
+}
+
+fn build_atom(
+    layout_index: &LayoutIndex,
+    leaf: &LeafView,
+    parent: Dot,
+    out: &mut Vec<AccessibilityNode>,
+) -> Option<Dot> {
+    let (role, label) = match leaf.node()? {
+        Node::Image(_) => (AccessibilityRole::Image, None),
+        Node::HorizontalRule(_) => (AccessibilityRole::Separator, None),
+        Node::File(_) | Node::Embed(_) => (AccessibilityRole::Group, None),
+        _ => return None,
+    };
+    out.push(AccessibilityNode {
+        id: leaf.dot(),
+        role,
+        parent: Some(parent),
+        children: Vec::new(),
+        text: String::new(),
+        label,
+        href: None,
+        bounds: layout_index.content_page_rects(&leaf.dot()),
+    });
+    Some(leaf.dot())
+}
+
+/// Returns the block's text, pushing its links as children. Textblocks hold
+/// only breaks and tabs besides text, so their atoms live in the text alone.
+fn build_inline(
+    layout_index: &LayoutIndex,
+    block: &NodeView,
+    children: &mut Vec<Dot>,
+    out: &mut Vec<AccessibilityNode>,
+) -> String {
+    let chars: Vec<char> = block.inline_text().chars().collect();
+    let mut text = String::new();
+    let mut link: Option<(usize, usize)> = None;
+    let items = block.inline();
+    for (offset, item) in items.iter().enumerate() {
+        let href = resolve_link(item.own_modifiers);
+        let continues = link.is_some_and(|(start, _)| {
+            href.is_some() && href == resolve_link(items[start].own_modifiers)
+        });
+        if !continues {
+            if let Some(range) = link.take() {
+                children.push(push_link(layout_index, block, &items, range, &chars, out));
+            }
+            if href.is_some() {
+                link = Some((offset, offset));
+            }
+        }
+        if let Some(range) = link.as_mut() {
+            range.1 = offset + 1;
+        }
+
+        text.push(inline_char(&item.kind, &chars));
+    }
+    if let Some(range) = link {
+        children.push(push_link(layout_index, block, &items, range, &chars, out));
+    }
+    text
+}
+
+fn inline_char(kind: &InlineKind, chars: &[char]) -> char {
+    match kind {
+        InlineKind::Char { char_index, .. } => chars[*char_index],
+        InlineKind::Atom(NodeType::Tab) => '\t',
+        InlineKind::Atom(NodeType::HardBreak) => '\n',
+        InlineKind::Atom(_) => OBJECT_REPLACEMENT,
+    }
+}
+
+fn push_link(
+    layout_index: &LayoutIndex,
+    block: &NodeView,
+    items: &[InlineItem],
+    (start, end): (usize, usize),
+    chars: &[char],
+    out: &mut Vec<AccessibilityNode>,
+) -> Dot {
+    let href = resolve_link(items[start].own_modifiers);
+    let text = items[start..end]
+        .iter()
+        .map(|item| inline_char(&item.kind, chars))
+        .collect();
+    let id = items[start].dot;
+    out.push(AccessibilityNode {
+        id,
+        role: AccessibilityRole::Link,
+        parent: Some(block.id()),
+        children: Vec::new(),
+        text,
+        label: None,
+        bounds: link_bounds(layout_index, block.id(), start..end, href.as_deref()),
+        href,
+    });
+    id
+}
+
+/// One rect per line fragment of the link: its glyph runs and tab gaps.
+fn link_bounds(
+    layout_index: &LayoutIndex,
+    block: Dot,
+    range: std::ops::Range<usize>,
+    href: Option<&str>,
+) -> Vec<PageRect> {
+    let mut rects = Vec::new();
+    for entry in layout_index.entries_for_node(&block) {
+        let Some(LayoutContent::Line(line)) = entry.content(layout_index) else {
+            continue;
+        };
+        let runs = line
+            .glyph_runs
+            .iter()
+            .filter(|run| {
+                run.link.as_deref() == href
+                    && run.offset_range.start < range.end
+                    && range.start < run.offset_range.end
+            })
+            .map(|run| (run.x, run.width));
+        let gaps = line
+            .tab_gaps
+            .iter()
+            .filter(|gap| range.contains(&gap.offset_index))
+            .map(|gap| (gap.x, gap.width));
+        let spans: Vec<(f32, f32)> = runs.chain(gaps).collect();
+        let Some(left) = spans.iter().map(|(x, _)| *x).min_by(f32::total_cmp) else {
+            continue;
+        };
+        let right = spans
+            .iter()
+            .map(|(x, width)| x + width)
+            .max_by(f32::total_cmp)
+            .unwrap_or(left);
+        let rect = Rect::from_xywh(
+            entry.rect.x + left,
+            entry.rect.y,
+            right - left,
+            entry.rect.height,
+        );
+        rects.extend(layout_index.page_rect(rect));
+    }
+    rects
+}
+
+#[cfg(test)]
+mod tests {
+    use editor_common::EdgeInsets;
+    use editor_crdt::{Dot, InputEvent, ListOp, build_oplog};
+    use editor_model::{
+        AliasLog, Anchor, AtomLeaf, Bias, DocLogs, DocView, HorizontalRuleVariant, ImageNode,
+        Modifier, ModifierAttrLog, NodeAttrLog, NodeType, SeqItem, SpanLog, SpanOp,
+        project_document,
+    };
+    use editor_resource::Resource;
+
+    use crate::measure::context::MeasureContext;
+    use crate::measure::nodes::dispatch::measure_node;
+    use crate::measure::types::MeasuredTree;
+    use crate::paginate::paginator::Paginator;
+
+    use super::*;
+
+    fn logs(items: &[(Dot, SeqItem)]) -> DocLogs {
+        let mut ev = Vec::new();
+        let mut prev: Option<Dot> = None;
+        for (i, (id, item)) in items.iter().enumerate() {
+            ev.push(InputEvent {
+                id: *id,
+                parents: prev.into_iter().collect(),
+                op: ListOp::Ins {
+                    pos: i,
+                    item: item.clone(),
+                },
+            });
+            prev = Some(*id);
+        }
+        DocLogs {
+            seq: build_oplog(&ev),
+            spans: SpanLog::new(),
+            block_modifiers: ModifierAttrLog::new(),
+            node_attrs: NodeAttrLog::new(),
+            node_carries: ModifierAttrLog::new(),
+            aliases: AliasLog::new(),
+        }
+    }
+
+    fn with_layout<R>(doc: &DocLogs, f: impl FnOnce(&LayoutIndex, &DocView) -> R) -> R {
+        let pd = project_document(doc).unwrap();
+        let view = DocView::new(&pd);
+        let root = view.root().unwrap();
+        let mut res = Resource::new_test();
+        let measured = measure_node(
+            &mut crate::measure::Measurer::new(),
+            &root,
+            400.0,
+            &MeasureContext::default(),
+            &mut res,
+        );
+        let layout = Paginator::continuous(400.0, 100_000.0, EdgeInsets::all(0.0))
+            .paginate(MeasuredTree { root: measured });
+        let index = LayoutIndex::new(layout.tree, &layout.pages);
+        f(&index, &view)
+    }
+
+    fn tree(items: &[(Dot, SeqItem)], selection: Option<&Selection>) -> AccessibilityTree {
+        with_layout(&logs(items), |index, view| {
+            accessibility_tree(index, view, selection)
+        })
+    }
+
+    fn node(tree: &AccessibilityTree, id: Dot) -> &AccessibilityNode {
+        tree.nodes.iter().find(|n| n.id == id).unwrap()
+    }
+
+    fn block(id: Dot, node_type: NodeType, parents: Vec<Dot>) -> (Dot, SeqItem) {
+        (
+            id,
+            SeqItem::Block {
+                node_type,
+                parents,
+                attrs: vec![],
+            },
+        )
+    }
+
+    fn paragraph(id: Dot, text: &str) -> Vec<(Dot, SeqItem)> {
+        let mut items = vec![(
+            id,
+            SeqItem::Block {
+                node_type: NodeType::Paragraph,
+                parents: vec![Dot::ROOT],
+                attrs: vec![],
+            },
+        )];
+        for (i, ch) in text.chars().enumerate() {
+            items.push((
+                Dot::new(id.actor, id.clock + 1 + i as u64),
+                SeqItem::Char(ch),
+            ));
+        }
+        items
+    }
+
+    fn hr(id: Dot) -> (Dot, SeqItem) {
+        (
+            id,
+            SeqItem::BlockAtom {
+                leaf: AtomLeaf::HorizontalRule {
+                    variant: HorizontalRuleVariant::default(),
+                },
+                parents: vec![Dot::ROOT],
+            },
+        )
+    }
+
+    #[test]
+    fn paragraphs_and_rules_map_to_roles_with_text_and_bounds() {
+        let para = Dot::new(1, 1);
+        let rule = Dot::new(2, 1);
+        let mut items = paragraph(para, "Hello");
+        items.push(hr(rule));
+        let caret = Selection::new(Position::new(para, 2), Position::new(para, 2));
+        let tree = tree(&items, Some(&caret));
+
+        let root = &tree.nodes[0];
+        assert_eq!(root.role, AccessibilityRole::Document);
+        assert_eq!(tree.root, Some(root.id));
+        assert_eq!(root.children, vec![para, rule]);
+
+        let p = tree.nodes.iter().find(|n| n.id == para).unwrap();
+        assert_eq!(p.role, AccessibilityRole::Paragraph);
+        assert_eq!(p.text, "Hello");
+        assert_eq!(p.parent, Some(root.id));
+        assert_eq!(p.bounds.len(), 1);
+        assert_eq!(p.bounds[0].page_idx, 0);
+
+        let r = tree.nodes.iter().find(|n| n.id == rule).unwrap();
+        assert_eq!(r.role, AccessibilityRole::Separator);
+        assert!(!r.bounds.is_empty());
+
+        let selection = tree.selection.unwrap();
+        assert_eq!(selection.anchor, selection.focus);
+        assert_eq!(selection.focus.node, para);
+        assert_eq!(selection.focus.offset, 2);
+    }
+
+    #[test]
+    fn replace_reports_only_changed_and_removed_nodes() {
+        let first = Dot::new(1, 1);
+        let second = Dot::new(2, 1);
+        let rule = Dot::new(3, 1);
+        let mut before = paragraph(first, "Same");
+        before.extend(paragraph(second, "Old"));
+        before.push(hr(rule));
+        let mut after = paragraph(first, "Same");
+        after.extend(paragraph(second, "New"));
+
+        let mut retained = RetainedAccessibility::default();
+        retained.reset(&tree(&before, None));
+        let current = tree(&after, None);
+        let update = retained.replace(current.clone());
+
+        let updated: Vec<Dot> = update.updated.iter().map(|n| n.id).collect();
+        assert!(updated.contains(&second));
+        assert!(updated.contains(&current.root.unwrap()));
+        assert!(!updated.contains(&first));
+        assert_eq!(update.removed, vec![rule]);
+        assert!(retained.replace(current).is_empty());
+    }
+
+    #[test]
+    fn update_blocks_rebuilds_only_the_given_blocks() {
+        let first = Dot::new(1, 1);
+        let second = Dot::new(2, 1);
+        let mut before = paragraph(first, "Same");
+        before.extend(paragraph(second, "Old"));
+        let mut after = paragraph(first, "Same");
+        after.extend(paragraph(second, "New"));
+        let doc = logs(&after);
+
+        let mut retained = RetainedAccessibility::default();
+        let unseeded = with_layout(&doc, |index, view| {
+            retained.update_blocks(index, view, [second], None)
+        });
+        assert!(unseeded.is_none());
+
+        retained.reset(&tree(&before, None));
+        let update = with_layout(&doc, |index, view| {
+            retained.update_blocks(index, view, [second], None)
+        })
+        .unwrap();
+        assert_eq!(update.updated.len(), 1);
+        assert_eq!(update.updated[0].id, second);
+        assert_eq!(update.updated[0].text, "New");
+        assert!(update.removed.is_empty());
+
+        let again = with_layout(&doc, |index, view| {
+            retained.update_blocks(index, view, [second], None)
+        })
+        .unwrap();
+        assert!(again.is_empty());
+    }
+
+    #[test]
+    fn links_group_runs_into_one_node_with_href_and_text() {
+        let para = Dot::new(1, 1);
+        let items = paragraph(para, "go here now");
+        let char_at = |i: u64| Dot::new(1, 2 + i);
+        let mut doc = logs(&items);
+        doc.spans = SpanLog::new()
+            .apply(
+                Dot::new(9, 1),
+                SpanOp::AddSpan {
+                    start: Anchor {
+                        id: char_at(3),
+                        bias: Bias::Before,
+                    },
+                    end: Anchor {
+                        id: char_at(6),
+                        bias: Bias::After,
+                    },
+                    modifier: Modifier::Link {
+                        href: "https://example.com".to_string(),
+                    },
+                },
+            )
+            .unwrap()
+            // Bold over half the link splits it into two glyph runs.
+            .apply(
+                Dot::new(9, 2),
+                SpanOp::AddSpan {
+                    start: Anchor {
+                        id: char_at(3),
+                        bias: Bias::Before,
+                    },
+                    end: Anchor {
+                        id: char_at(4),
+                        bias: Bias::After,
+                    },
+                    modifier: Modifier::Bold,
+                },
+            )
+            .unwrap();
+        let tree = with_layout(&doc, |index, view| accessibility_tree(index, view, None));
+
+        let p = node(&tree, para);
+        assert_eq!(p.text, "go here now");
+        assert_eq!(p.children, vec![char_at(3)]);
+
+        let link = node(&tree, char_at(3));
+        assert_eq!(link.role, AccessibilityRole::Link);
+        assert_eq!(link.parent, Some(para));
+        assert_eq!(link.text, "here");
+        assert_eq!(link.href.as_deref(), Some("https://example.com"));
+        assert_eq!(link.bounds.len(), 1);
+        assert!(link.bounds[0].rect.width < p.bounds[0].rect.width);
+    }
+
+    #[test]
+    fn inline_atoms_keep_text_offsets_aligned() {
+        let para = Dot::new(1, 1);
+        let items = vec![
+            block(para, NodeType::Paragraph, vec![Dot::ROOT]),
+            (Dot::new(1, 2), SeqItem::Char('a')),
+            (Dot::new(1, 3), SeqItem::Atom(AtomLeaf::Tab)),
+            (Dot::new(1, 4), SeqItem::Char('b')),
+            (Dot::new(1, 5), SeqItem::Atom(AtomLeaf::HardBreak)),
+            (Dot::new(1, 6), SeqItem::Char('c')),
+        ];
+        let mut doc = logs(&items);
+        doc.spans = SpanLog::new()
+            .apply(
+                Dot::new(9, 1),
+                SpanOp::AddSpan {
+                    start: Anchor {
+                        id: Dot::new(1, 6),
+                        bias: Bias::Before,
+                    },
+                    end: Anchor {
+                        id: Dot::new(1, 6),
+                        bias: Bias::After,
+                    },
+                    modifier: Modifier::Link {
+                        href: "https://c".to_string(),
+                    },
+                },
+            )
+            .unwrap();
+        let tree = with_layout(&doc, |index, view| accessibility_tree(index, view, None));
+
+        let p = node(&tree, para);
+        assert_eq!(p.text, "a\tb\nc");
+        assert_eq!(p.children, vec![Dot::new(1, 6)]);
+        assert_eq!(node(&tree, Dot::new(1, 6)).text, "c");
+    }
+
+    #[test]
+    fn block_images_are_unlabelled_image_nodes() {
+        let image = Dot::new(1, 1);
+        let mut items = vec![(
+            image,
+            SeqItem::BlockAtom {
+                leaf: AtomLeaf::Image {
+                    node: ImageNode::default(),
+                },
+                parents: vec![Dot::ROOT],
+            },
+        )];
+        items.extend(paragraph(Dot::new(2, 1), "after"));
+        let tree = tree(&items, None);
+
+        assert_eq!(tree.nodes[0].children[0], image);
+        let img = node(&tree, image);
+        assert_eq!(img.role, AccessibilityRole::Image);
+        assert_eq!(img.parent, tree.root);
+        assert_eq!(img.label, None);
+        assert!(img.text.is_empty());
+        assert!(img.children.is_empty());
+    }
+
+    #[test]
+    fn lists_and_tables_map_to_item_and_cell_roles() {
+        let root = Dot::ROOT;
+        let list = Dot::new(1, 1);
+        let item = Dot::new(1, 2);
+        let item_para = Dot::new(1, 3);
+        let table = Dot::new(2, 1);
+        let row = Dot::new(2, 2);
+        let cell = Dot::new(2, 3);
+        let cell_para = Dot::new(2, 4);
+        let items = vec![
+            block(list, NodeType::BulletList, vec![root]),
+            block(item, NodeType::ListItem, vec![root, list]),
+            block(item_para, NodeType::Paragraph, vec![root, list, item]),
+            (Dot::new(1, 4), SeqItem::Char('a')),
+            block(table, NodeType::Table, vec![root]),
+            block(row, NodeType::TableRow, vec![root, table]),
+            block(cell, NodeType::TableCell, vec![root, table, row]),
+            block(cell_para, NodeType::Paragraph, vec![root, table, row, cell]),
+            (Dot::new(2, 5), SeqItem::Char('b')),
+        ];
+        let tree = tree(&items, None);
+
+        assert_eq!(node(&tree, list).role, AccessibilityRole::List);
+        let list_item = node(&tree, item);
+        assert_eq!(list_item.role, AccessibilityRole::ListItem);
+        assert_eq!(list_item.parent, Some(list));
+        assert_eq!(list_item.children, vec![item_para]);
+        assert_eq!(node(&tree, item_para).text, "a");
+
+        assert_eq!(node(&tree, table).role, AccessibilityRole::Table);
+        assert_eq!(node(&tree, row).role, AccessibilityRole::TableRow);
+        let table_cell = node(&tree, cell);
+        assert_eq!(table_cell.role, AccessibilityRole::TableCell);
+        assert_eq!(table_cell.parent, Some(row));
+        assert_eq!(table_cell.children, vec![cell_para]);
+        assert_eq!(node(&tree, cell_para).text, "b");
+        assert!(!table_cell.bounds.is_empty());
+    }
+}
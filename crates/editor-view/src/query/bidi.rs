@@ -0,0 +1,221 @@
+//! Caret geometry for lines holding right-to-left runs. Runs sit in visual
+//! order but keep their graphemes in logical order, so one offset can map to
+//! two x positions at a direction boundary; the affinity picks between them.
+//! Pure left-to-right lines never reach this module.
+
+use std::ops::Range;
+
+use editor_state::{Affinity, Position};
+
+use crate::glyph_run::GlyphRun;
+use crate::paginate::types::LayoutLine;
+
+/// Carets closer than this are drawn as one.
+const SPLIT_EPS: f32 = 0.5;
+
+#[derive(Clone, Copy, Debug)]
+struct Stop {
+    x: f32,
+    offset: usize,
+    affinity: Affinity,
+    /// Left and right edge of the run or tab gap the stop belongs to.
+    span: (f32, f32),
+}
+
+pub(crate) fn is_bidi(line: &LayoutLine) -> bool {
+    line.glyph_runs.iter().any(|run| run.rtl)
+}
+
+/// Grapheme boundaries of `run` in logical order, each with its caret x. An
+/// RTL run advances leftwards from its right edge.
+fn boundaries(run: &GlyphRun) -> Vec<(usize, f32)> {
+    let edge = |advance: f32| {
+        if run.rtl {
+            run.x + run.width - advance
+        } else {
+            run.x + advance
+        }
+    };
+    let mut offset = run.offset_range.start;
+    let mut advance = 0.0;
+    let mut out = Vec::with_capacity(run.graphemes.len() + 1);
+    out.push((offset, edge(advance)));
+    for g in &run.graphemes {
+        offset += g.codepoints as usize;
+        advance += g.advance;
+        out.push((offset, edge(advance)));
+    }
+    out
+}
+
+/// Every caret stop on the line, left to right. A run's end offset attaches
+/// upstream to the run, every other boundary downstream.
+fn caret_stops(line: &LayoutLine) -> Vec<Stop> {
+    let mut stops = Vec::new();
+    for run in &line.glyph_runs {
+        let span = (run.x, run.x + run.width);
+        let bounds = boundaries(run);
+        let last = bounds.len() - 1;
+        for (i, (offset, x)) in bounds.into_iter().enumerate() {
+            let affinity = if i == last && i > 0 {
+                Affinity::Upstream
+            } else {
+                Affinity::Downstream
+            };
+            stops.push(Stop {
+                x,
+                offset,
+                affinity,
+                span,
+            });
+        }
+    }
+    for gap in &line.tab_gaps {
+        let span = (gap.x, gap.x + gap.width);
+        stops.push(Stop {
+            x: gap.x,
+            offset: gap.offset_index,
+            affinity: Affinity::Downstream,
+            span,
+        });
+        stops.push(Stop {
+            x: gap.x + gap.width,
+            offset: gap.offset_index + 1,
+            affinity: Affinity::Upstream,
+            span,
+        });
+    }
+    stops.sort_by(|a, b| a.x.total_cmp(&b.x));
+    stops
+}
+
+/// The run a caret at `offset` draws against: the one holding the char on the
+/// affinity's side, else the one holding the char on the other side.
+fn caret_run(line: &LayoutLine, offset: usize, affinity: Affinity) -> Option<&GlyphRun> {
+    let following = |run: &&GlyphRun| run.offset_range.contains(&offset);
+    let preceding = |run: &&GlyphRun| offset > 0 && run.offset_range.contains(&(offset - 1));
+    match affinity {
+        Affinity::Downstream => line
+            .glyph_runs
+            .iter()
+            .find(following)
+            .or_else(|| line.glyph_runs.iter().find(preceding)),
+        Affinity::Upstream => line
+            .glyph_runs
+            .iter()
+            .find(preceding)
+            .or_else(|| line.glyph_runs.iter().find(following)),
+    }
+}
+
+pub(crate) fn x_at_offset(line: &LayoutLine, pos: &Position) -> Option<f32> {
+    if pos.node != line.node {
+        return None;
+    }
+    let run = caret_run(line, pos.offset, pos.affinity)?;
+    boundaries(run)
+        .into_iter()
+        .find(|(offset, _)| *offset >= pos.offset)
+        .map(|(_, x)| x)
+}
+
+/// The second caret drawn at a direction boundary: where the opposite
+/// affinity would put it, when that is elsewhere on the line.
+pub(crate) fn secondary_x(line: &LayoutLine, pos: &Position) -> Option<f32> {
+    let primary = x_at_offset(line, pos)?;
+    let opposite = Position {
+        affinity: match pos.affinity {
+            Affinity::Downstream => Affinity::Upstream,
+            Affinity::Upstream => Affinity::Downstream,
+        },
+        ..*pos
+    };
+    let secondary = x_at_offset(line, &opposite)?;
+    ((secondary - primary).abs() > SPLIT_EPS).then_some(secondary)
+}
+
+pub(crate) fn first_position(line: &LayoutLine) -> Option<Position> {
+    let offset = caret_stops(line).iter().map(|s| s.offset).min()?;
+    Some(Position::new(line.node, offset))
+}
+
+pub(crate) fn last_position(line: &LayoutLine) -> Option<Position> {
+    let offset = caret_stops(line).iter().map(|s| s.offset).max()?;
+    Some(Position {
+        node: line.node,
+        offset,
+        affinity: Affinity::Upstream,
+    })
+}
+
+/// The stop nearest `local_x`. At a shared edge the stop whose run lies under
+/// `local_x` wins, so a click lands on the grapheme it hit.
+pub(crate) fn position_at_x(line: &LayoutLine, local_x: f32) -> Option<Position> {
+    let stop = caret_stops(line).into_iter().min_by(|a, b| {
+        let inside = |s: &Stop| s.span.0 <= local_x && local_x <= s.span.1;
+        (a.x - local_x)
+            .abs()
+            .total_cmp(&(b.x - local_x).abs())
+            .then_with(|| inside(b).cmp(&inside(a)))
+    })?;
+    Some(Position {
+        node: line.node,
+        offset: stop.offset,
+        affinity: stop.affinity,
+    })
+}
+
+/// The next caret stop to the right (or left) of `pos`, or `None` at the
+/// line's visual edge. Of several stops at one x, the one on the side just
+/// crossed is taken.
+pub(crate) fn move_visual(line: &LayoutLine, pos: &Position, rightward: bool) -> Option<Position> {
+    let x = super::grapheme::x_at_offset(line, pos);
+    let stops = caret_stops(line);
+    let stop = if rightward {
+        stops.iter().find(|s| s.x > x + SPLIT_EPS)
+    } else {
+        stops.iter().rev().find(|s| s.x < x - SPLIT_EPS)
+    }?;
+    Some(Position {
+        node: line.node,
+        offset: stop.offset,
+        affinity: stop.affinity,
+    })
+}
+
+/// Offset of the stop at the line's right (or left) visual edge.
+pub(crate) fn edge_offset(line: &LayoutLine, right: bool) -> Option<usize> {
+    let stops = caret_stops(line);
+    let stop = if right { stops.last() } else { stops.first() }?;
+    Some(stop.offset)
+}
+
+/// Horizontal spans covering the graphemes and tab gaps in `range`, merged
+/// where they touch. A logical range splits into several spans once it
+/// crosses a direction boundary.
+pub(crate) fn range_spans(line: &LayoutLine, range: Range<usize>) -> Vec<(f32, f32)> {
+    let mut spans = Vec::new();
+    for run in &line.glyph_runs {
+        for pair in boundaries(run).windows(2) {
+            let ((start, x0), (end, x1)) = (pair[0], pair[1]);
+            if range.start <= start && end <= range.end && start < end {
+                spans.push((x0.min(x1), x0.max(x1)));
+            }
+        }
+    }
+    for gap in &line.tab_gaps {
+        if range.contains(&gap.offset_index) {
+            spans.push((gap.x, gap.x + gap.width));
+        }
+    }
+    spans.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let mut merged: Vec<(f32, f32)> = Vec::with_capacity(spans.len());
+    for (start, end) in spans {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 + SPLIT_EPS => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    merged
+}
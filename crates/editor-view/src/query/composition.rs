@@ -121,12 +121,16 @@ fn visit_line(
     }
 
     if let Some(page_idx) = page_for_y(pages, node.rect.y) {
-        let underline_y =
-            node.rect.y - pages[page_idx].y_start + line.baseline + line.descent * 0.5;
+        let underline_y = node.rect.y + line.baseline + line.descent * 0.5;
 
         rects.push(PageRect::new(
             page_idx,
-            Rect::from_xywh(node.rect.x + x_start, underline_y, width, 1.0),
+            pages[page_idx].to_page_rect(Rect::from_xywh(
+                node.rect.x + x_start,
+                underline_y,
+                width,
+                1.0,
+            )),
         ));
     }
 }
@@ -8,13 +8,16 @@ pub struct CursorMetrics {
     pub page_idx: usize,
     pub caret: Rect,
     pub line: Rect,
+    /// Second caret at a direction boundary, where the opposite affinity's
+    /// character sits.
+    pub secondary_caret: Option<Rect>,
 }
 
 use editor_common::Rect;
 use editor_state::Position;
 
-use super::grapheme;
 use super::layout_index::LayoutIndex;
+use super::{bidi, grapheme};
 use crate::paginate::types::{LayoutContent, LayoutLine};
 
 pub(crate) fn cursor_metrics(
@@ -45,16 +48,24 @@ pub(crate) fn cursor_metrics(
                 }
             };
             let cursor_height = cursor_ascent + cursor_descent;
-            let caret = Rect::from_xywh(
-                entry.rect.x + x,
-                page_rect.rect.y + l.baseline - cursor_ascent,
-                1.0,
-                cursor_height,
-            );
+            let caret_at = |x: f32| {
+                Rect::from_xywh(
+                    entry.rect.x + x,
+                    page_rect.rect.y + l.baseline - cursor_ascent,
+                    1.0,
+                    cursor_height,
+                )
+            };
+            let secondary_caret = if bidi::is_bidi(l) {
+                bidi::secondary_x(l, pos).map(caret_at)
+            } else {
+                None
+            };
             Some(CursorMetrics {
                 page_idx: page_rect.page_idx,
-                caret,
+                caret: caret_at(x),
                 line: page_rect.rect,
+                secondary_caret,
             })
         }
         LayoutContent::Atom(_) => None,
@@ -139,26 +139,26 @@ fn box_edge_position(
         return None;
     }
 
-    let page = layout_index.page(point.page_idx)?;
-    if point.y < page.content_y_start {
+    let column = point.column;
+    if point.y < column.content_y_start {
         let first = drop_children_in_y_range(
             layout_index,
             view,
             &b.node,
-            page.content_y_start,
-            page.content_y_end,
+            column.content_y_start,
+            column.content_y_end,
         )
         .into_iter()
         .next()?;
         return Some(Position::new(b.node, first.offset));
     }
-    if point.y > page.content_y_end {
+    if point.y > column.content_y_end {
         let last = drop_children_in_y_range(
             layout_index,
             view,
             &b.node,
-            page.content_y_start,
-            page.content_y_end,
+            column.content_y_start,
+            column.content_y_end,
         )
         .into_iter()
         .last()?;
@@ -304,11 +304,11 @@ fn block_drop_indicator(layout_index: &LayoutIndex, position: Position) -> Optio
             .unwrap_or(node_rect.y),
     };
     let page_idx = layout_index.page_idx_for_y(y_abs)?;
-    let page_y_start = layout_index.page_y_start(page_idx)?;
+    let column = layout_index.page_column(page_idx, y_abs)?;
     Some(DropIndicator::Block {
         page_idx,
-        x,
-        y: y_abs - page_y_start,
+        x: column.to_page_x(x),
+        y: column.to_page_y(y_abs),
         width,
     })
 }
@@ -764,8 +764,7 @@ mod tests {
         let atom_rect = atom_entry.rect;
 
         let mid_x = atom_rect.x + atom_rect.width * 0.5;
-        let mid_y_page =
-            atom_rect.y + atom_rect.height * 0.5 - index.page_y_start(0).unwrap_or(0.0);
+        let mid_y_page = atom_rect.y + atom_rect.height * 0.5 - index.pages()[0].y_start;
 
         let result = drop_target_at(&index, &view, 0, mid_x, mid_y_page);
         let (position, _) = result.expect("drop target on atom must be Some");
@@ -3,6 +3,8 @@ use editor_state::Position;
 
 use crate::paginate::types::LayoutLine;
 
+use super::bidi;
+
 type VisualEdge = Option<(f32, usize)>;
 
 fn visual_bounds(line: &LayoutLine) -> (VisualEdge, VisualEdge) {
@@ -35,6 +37,11 @@ fn visual_bounds(line: &LayoutLine) -> (VisualEdge, VisualEdge) {
 }
 
 pub(crate) fn last_position_in_line(line: &LayoutLine) -> Position {
+    if bidi::is_bidi(line)
+        && let Some(position) = bidi::last_position(line)
+    {
+        return position;
+    }
     let (_, last) = visual_bounds(line);
     if let Some((_, offset)) = last {
         return Position {
@@ -59,6 +66,11 @@ pub(crate) fn last_position_in_line(line: &LayoutLine) -> Position {
 }
 
 pub(crate) fn first_position_in_line(line: &LayoutLine) -> Position {
+    if bidi::is_bidi(line)
+        && let Some(position) = bidi::first_position(line)
+    {
+        return position;
+    }
     let (first, _) = visual_bounds(line);
     if let Some((_, offset)) = first {
         return Position::new(line.node, offset);
@@ -91,6 +103,12 @@ fn x_at_offset_raw(line: &LayoutLine, pos: &Position) -> f32 {
         }
     }
 
+    if bidi::is_bidi(line)
+        && let Some(x) = bidi::x_at_offset(line, pos)
+    {
+        return x;
+    }
+
     if pos.node == line.node {
         for run in &line.glyph_runs {
             let local_offset = pos.offset.saturating_sub(run.offset_range.start);
@@ -177,6 +195,12 @@ pub(crate) fn position_at_x(line: &LayoutLine, local_x: f32) -> Position {
         return Position::new(line.node, offset);
     }
 
+    if bidi::is_bidi(line)
+        && let Some(position) = bidi::position_at_x(line, local_x)
+    {
+        return position;
+    }
+
     if local_x <= line_content_start_x(line) {
         return first_position_in_line(line);
     }
@@ -249,6 +273,7 @@ mod tests {
             graphemes,
             cursor_ascent: 0.0,
             cursor_descent: 0.0,
+            rtl: false,
         }
     }
 
@@ -499,4 +524,64 @@ mod tests {
         assert_eq!(pos.node, n);
         assert_eq!(pos.offset, 5);
     }
+
+    /// "ab" followed by a three-grapheme RTL run: offsets 2..5 sit at x 50..20.
+    fn mixed_line(n: Dot) -> LayoutLine {
+        let mut rtl = run(2..5, 20.0, vec![gs(10.0, 1); 3]);
+        rtl.rtl = true;
+        line(
+            n,
+            None,
+            vec![run(0..2, 0.0, vec![gs(10.0, 1); 2]), rtl],
+            vec![],
+            0.0,
+            None,
+        )
+    }
+
+    #[test]
+    fn bidi_caret_follows_affinity_at_direction_boundary() {
+        let n = node();
+        let l = mixed_line(n);
+        let upstream = Position {
+            node: n,
+            offset: 2,
+            affinity: Affinity::Upstream,
+        };
+        assert_eq!(x_at_offset(&l, &upstream), 20.0);
+        assert_eq!(x_at_offset(&l, &Position::new(n, 2)), 50.0);
+        assert_eq!(x_at_offset(&l, &Position::new(n, 3)), 40.0);
+        assert_eq!(bidi::secondary_x(&l, &upstream), Some(50.0));
+        assert_eq!(bidi::secondary_x(&l, &Position::new(n, 1)), None);
+    }
+
+    #[test]
+    fn bidi_position_at_x_hits_grapheme_under_pointer() {
+        let n = node();
+        let l = mixed_line(n);
+        assert_eq!(position_at_x(&l, 47.0), Position::new(n, 2));
+        let pos = position_at_x(&l, 22.0);
+        assert_eq!((pos.offset, pos.affinity), (5, Affinity::Upstream));
+        let pos = position_at_x(&l, 18.0);
+        assert_eq!((pos.offset, pos.affinity), (2, Affinity::Upstream));
+        assert_eq!(first_position_in_line(&l).offset, 0);
+        assert_eq!(last_position_in_line(&l).offset, 5);
+    }
+
+    #[test]
+    fn bidi_moves_visually_and_splits_selection() {
+        let n = node();
+        let l = mixed_line(n);
+        let step = |pos: Position| bidi::move_visual(&l, &pos, true).unwrap();
+        let p = step(Position::new(n, 1));
+        assert_eq!((p.offset, p.affinity), (2, Affinity::Upstream));
+        assert_eq!(step(p).offset, 4);
+        assert_eq!(bidi::move_visual(&l, &Position::new(n, 2), true), None);
+
+        assert_eq!(
+            bidi::range_spans(&l, 1..3),
+            vec![(10.0, 20.0), (40.0, 50.0)]
+        );
+        assert_eq!(bidi::range_spans(&l, 0..5), vec![(0.0, 50.0)]);
+    }
 }
@@ -38,12 +38,13 @@ pub(crate) fn drag_selection_for_entry(
 ) -> Option<Selection> {
     let hard_break = hard_break_occurrence_for_entry(layout_index, view, entry)?;
     let rect = hard_break.geometry.rect.rect;
-    let page_y = point.y - point.page_y_start;
+    let page_x = point.column.to_page_x(point.x);
+    let page_y = point.column.to_page_y(point.y);
     let x_mid = rect.x + rect.width / 2.0;
     if hard_break.geometry.rect.page_idx == point.page_idx
         && page_y >= rect.y
         && page_y <= rect.bottom()
-        && point.x >= x_mid
+        && page_x >= x_mid
         && point.x <= hard_break.geometry.line_right
     {
         Some(hard_break.range)
@@ -98,12 +99,7 @@ fn geometry_for_line_rect(
     Some(HardBreakGeometry {
         rect: PageRect::new(
             page_idx,
-            Rect::from_xywh(
-                x,
-                line_rect.y - pages[page_idx].y_start,
-                width,
-                line_rect.height,
-            ),
+            pages[page_idx].to_page_rect(Rect::from_xywh(x, line_rect.y, width, line_rect.height)),
         ),
         line_right: line_rect.right(),
     })
@@ -315,12 +311,12 @@ mod tests {
 
         let rect = &geom.rect.rect;
         let mid_x = rect.x + rect.width / 2.0 + 1.0;
-        let page_y_start = index.pages()[geom.rect.page_idx].y_start;
+        let column = index.pages()[geom.rect.page_idx].columns[0];
         let point_inside = LayoutPoint {
             page_idx: geom.rect.page_idx,
             x: mid_x,
-            y: page_y_start + rect.y + rect.height / 2.0,
-            page_y_start,
+            y: rect.y - column.dy + rect.height / 2.0,
+            column,
         };
         let hit = drag_selection_for_entry(&index, &view, entry, point_inside);
         assert!(hit.is_some(), "point inside glyph rect must hit");
@@ -328,8 +324,8 @@ mod tests {
         let point_outside = LayoutPoint {
             page_idx: geom.rect.page_idx,
             x: rect.x - 10.0,
-            y: page_y_start + rect.y + rect.height / 2.0,
-            page_y_start,
+            y: rect.y - column.dy + rect.height / 2.0,
+            column,
         };
         let miss = drag_selection_for_entry(&index, &view, entry, point_outside);
         assert!(miss.is_none(), "point outside glyph rect must miss");
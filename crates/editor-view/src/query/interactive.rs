@@ -54,6 +54,13 @@ pub(crate) fn interactive_regions(
 ) -> Vec<InteractiveRegion> {
     let pages = layout_index.pages();
     let mut scored: Vec<(f32, InteractiveRegion)> = Vec::new();
+    let columns_of = |rect: &Rect| {
+        let (top, bottom) = (rect.y, rect.bottom());
+        pages.iter().enumerate().flat_map(move |(page_idx, page)| {
+            page.column_slices(top, bottom)
+                .map(move |(column, _, _)| (page_idx, *column))
+        })
+    };
     for entry in layout_index.entries() {
         let Some(LayoutContent::Box(b)) = entry.content(layout_index) else {
             continue;
@@ -62,9 +69,6 @@ pub(crate) fn interactive_regions(
             continue;
         };
         let area = entry.rect.width * entry.rect.height;
-        let to_local = |rect: &Rect, y_start: f32| {
-            Rect::from_xywh(rect.x, rect.y - y_start, rect.width, rect.height)
-        };
         match node_ref.node() {
             Node::Callout(callout) => {
                 // A callout without its icon decoration still owns its entry area in
@@ -78,16 +82,13 @@ pub(crate) fn interactive_regions(
                     ),
                     None => Rect::from_xywh(0.0, 0.0, -1.0, -1.0),
                 };
-                for (page_idx, page) in pages.iter().enumerate() {
-                    if entry.rect.y >= page.y_end || entry.rect.bottom() <= page.y_start {
-                        continue;
-                    }
+                for (page_idx, column) in columns_of(&entry.rect) {
                     scored.push((
                         area,
                         InteractiveRegion {
                             page_idx,
-                            entry_rect: to_local(&entry.rect, page.y_start),
-                            effective_rect: to_local(&icon, page.y_start),
+                            entry_rect: column.to_page_rect(entry.rect),
+                            effective_rect: column.to_page_rect(icon),
                             hit: InteractiveHit::CalloutIcon {
                                 id: b.node,
                                 next_variant: callout.variant.get().next(),
@@ -100,11 +101,8 @@ pub(crate) fn interactive_regions(
                 let Some(parent) = node_ref.parent() else {
                     continue;
                 };
-                for (page_idx, page) in pages.iter().enumerate() {
-                    if entry.rect.y >= page.y_end || entry.rect.bottom() <= page.y_start {
-                        continue;
-                    }
-                    let local_entry = to_local(&entry.rect, page.y_start);
+                for (page_idx, column) in columns_of(&entry.rect) {
+                    let local_entry = column.to_page_rect(entry.rect);
                     scored.push((
                         area,
                         InteractiveRegion {
@@ -114,9 +112,7 @@ pub(crate) fn interactive_regions(
                             hit: InteractiveHit::FoldTitle {
                                 id: parent.id(),
                                 text_rect: navigable_union_in(layout_index, page_idx, &entry.rect)
-                                    .map(|r| {
-                                        Rect::from_xywh(r.x, r.y - page.y_start, r.width, r.height)
-                                    }),
+                                    .map(|r| column.to_page_rect(r)),
                             },
                         },
                     ));
@@ -168,7 +164,7 @@ fn interactive_hit_for_entry(
         Node::FoldTitle(_) => Some(InteractiveHit::FoldTitle {
             id: node_ref.parent()?.id(),
             text_rect: navigable_union_in(layout_index, point.page_idx, &entry.rect)
-                .map(|r| Rect::from_xywh(r.x, r.y - point.page_y_start, r.width, r.height)),
+                .map(|r| point.column.to_page_rect(r)),
         }),
         _ => None,
     }
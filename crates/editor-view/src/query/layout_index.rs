@@ -7,7 +7,7 @@ use rstar::{AABB, RTree, RTreeObject};
 use smallvec::SmallVec;
 use std::sync::{Arc, OnceLock};
 
-use crate::page::{LayoutPage, PageRect};
+use crate::page::{LayoutPage, PageColumn, PageRect};
 use crate::paginate::types::{LayoutContent, LayoutLine, LayoutNode, LayoutTree, SpacingKind};
 
 type LayoutEntryId = usize;
@@ -51,7 +51,9 @@ pub(crate) struct LayoutPoint {
     pub(crate) page_idx: usize,
     pub(crate) x: f32,
     pub(crate) y: f32,
-    pub(crate) page_y_start: f32,
+    /// The page column the point was taken in; maps document geometry back
+    /// to page space.
+    pub(crate) column: PageColumn,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -175,12 +177,12 @@ impl LayoutIndex {
 
     pub(crate) fn point(&self, page_idx: usize, x: f32, page_y: f32) -> Option<LayoutPoint> {
         let page = self.pages.get(page_idx)?;
-        let y = page_y + page.y_start;
+        let column = *page.column_at_page_x(x);
         Some(LayoutPoint {
             page_idx,
-            x,
-            y,
-            page_y_start: page.y_start,
+            x: x - column.dx,
+            y: page_y - column.dy,
+            column,
         })
     }
 
@@ -248,10 +250,9 @@ impl LayoutIndex {
             .pages
             .iter()
             .position(|page| rect.y >= page.y_start && rect.y < page.y_end)?;
-        let y_start = self.pages[page_idx].y_start;
         Some(PageRect::new(
             page_idx,
-            Rect::from_xywh(rect.x, rect.y - y_start, rect.width, rect.height),
+            self.pages[page_idx].to_page_rect(rect),
         ))
     }
 
@@ -261,8 +262,8 @@ impl LayoutIndex {
             .position(|page| y >= page.y_start && y <= page.y_end)
     }
 
-    pub(crate) fn page_y_start(&self, page_idx: usize) -> Option<f32> {
-        self.pages.get(page_idx).map(|page| page.y_start)
+    pub(crate) fn page_column(&self, page_idx: usize, y: f32) -> Option<&PageColumn> {
+        self.pages.get(page_idx).map(|page| page.column_at_y(y))
     }
 
     pub(crate) fn box_entry(&self, node: &Dot) -> Option<&LayoutEntry> {
@@ -278,29 +279,38 @@ impl LayoutIndex {
     pub(crate) fn box_page_rects(&self, ids: &[Dot]) -> Vec<PageRect> {
         let mut rects = Vec::new();
         for id in ids {
-            let Some(entry) = self.box_entry(id) else {
-                continue;
-            };
-            let node_top = entry.rect.y;
-            let node_bottom = entry.rect.bottom();
-            for (page_idx, page) in self.pages.iter().enumerate() {
-                if node_bottom <= page.y_start || node_top >= page.y_end {
-                    continue;
-                }
-                let top = node_top.max(page.y_start);
-                let bottom = node_bottom.min(page.y_end);
+            if let Some(entry) = self.box_entry(id) {
+                self.push_entry_page_rects(entry, &mut rects);
+            }
+        }
+        rects
+    }
+
+    /// Page rects of a box or atom, one per page column it crosses.
+    pub(crate) fn content_page_rects(&self, node: &Dot) -> Vec<PageRect> {
+        let mut rects = Vec::new();
+        if let Some(entry) = self.entry_for_content_node(node) {
+            self.push_entry_page_rects(entry, &mut rects);
+        }
+        rects
+    }
+
+    fn push_entry_page_rects(&self, entry: &LayoutEntry, rects: &mut Vec<PageRect>) {
+        let node_top = entry.rect.y;
+        let node_bottom = entry.rect.bottom();
+        for (page_idx, page) in self.pages.iter().enumerate() {
+            for (column, top, bottom) in page.column_slices(node_top, node_bottom) {
                 rects.push(PageRect::new(
                     page_idx,
                     Rect::from_xywh(
-                        entry.rect.x,
-                        top - page.y_start,
+                        column.to_page_x(entry.rect.x),
+                        column.to_page_y(top),
                         entry.rect.width,
                         bottom - top,
                     ),
                 ));
             }
         }
-        rects
     }
 
     pub(crate) fn nearest_box(&self, point: LayoutPoint, ids: &[Dot]) -> Option<Dot> {
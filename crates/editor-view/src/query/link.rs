@@ -25,24 +25,24 @@ pub(crate) fn page_link_rects(layout_index: &LayoutIndex, page_idx: usize) -> Ve
                 let Some(ref href) = run.link else {
                     continue;
                 };
-                let rect = Rect::from_xywh(
+                let rect = page.to_page_rect(Rect::from_xywh(
                     entry.rect.x + run.x,
-                    entry.rect.y - page.y_start,
+                    entry.rect.y,
                     run.width,
                     entry.rect.height,
-                );
+                ));
                 push_rect(&mut out, page_idx, href.clone(), rect);
             }
             for gap in &line.tab_gaps {
                 let Some(ref href) = gap.link else {
                     continue;
                 };
-                let rect = Rect::from_xywh(
+                let rect = page.to_page_rect(Rect::from_xywh(
                     entry.rect.x + gap.x,
-                    entry.rect.y - page.y_start,
+                    entry.rect.y,
                     gap.width,
                     entry.rect.height,
-                );
+                ));
                 push_rect(&mut out, page_idx, href.clone(), rect);
             }
         }
@@ -118,6 +118,7 @@ mod tests {
             graphemes,
             cursor_ascent: 0.0,
             cursor_descent: 0.0,
+            rtl: false,
         }
     }
 
@@ -1,3 +1,5 @@
+pub(crate) mod accessibility;
+pub(crate) mod bidi;
 pub(crate) mod common;
 pub(crate) mod composition;
 pub(crate) mod cursor;
@@ -17,6 +19,10 @@ pub(crate) mod selection;
 pub(crate) mod viewport_anchor;
 pub(crate) mod visit;
 
+pub use accessibility::{
+    AccessibilityNode, AccessibilityRole, AccessibilityTextPosition, AccessibilityTextSelection,
+    AccessibilityTree, AccessibilityUpdate,
+};
 pub use composition::CompositionRect;
 pub use cursor::CursorMetrics;
 pub use hit_test::{ExtendingHit, ExtendingHitSource};
@@ -7,6 +7,7 @@ use editor_state::{Position, Selection};
 use crate::paginate::types::{ChildAttachment, LayoutBox, LayoutContent, LayoutLine};
 use crate::viewport::Viewport;
 
+use super::bidi;
 use super::cursor::x_at_offset;
 use super::grapheme::{first_position_in_line, last_position_in_line};
 use super::layout_index::{LayoutEntry, LayoutIndex};
@@ -26,10 +27,10 @@ pub(crate) fn resolve_movement(
     match movement {
         Movement::Grapheme {
             direction: Direction::Forward,
-        } => (move_grapheme_forward(layout_index, pos), None),
+        } => (move_grapheme_right(layout_index, pos), None),
         Movement::Grapheme {
             direction: Direction::Backward,
-        } => (move_grapheme_backward(layout_index, pos), None),
+        } => (move_grapheme_left(layout_index, pos), None),
         Movement::Word {
             direction: Direction::Forward,
         } => (
@@ -85,6 +86,56 @@ pub(crate) fn resolve_movement(
     }
 }
 
+fn move_grapheme_right(layout_index: &LayoutIndex, pos: &Position) -> Option<Selection> {
+    match bidi_line(layout_index, pos) {
+        Some((entry, line)) => move_grapheme_visual(layout_index, entry, line, pos, true),
+        None => move_grapheme_forward(layout_index, pos),
+    }
+}
+
+fn move_grapheme_left(layout_index: &LayoutIndex, pos: &Position) -> Option<Selection> {
+    match bidi_line(layout_index, pos) {
+        Some((entry, line)) => move_grapheme_visual(layout_index, entry, line, pos, false),
+        None => move_grapheme_backward(layout_index, pos),
+    }
+}
+
+fn bidi_line<'a>(
+    layout_index: &'a LayoutIndex,
+    pos: &Position,
+) -> Option<(&'a LayoutEntry, &'a LayoutLine)> {
+    let entry = layout_index.entry_for_position(pos)?;
+    match entry.content(layout_index)? {
+        LayoutContent::Line(line) if line.node == pos.node && bidi::is_bidi(line) => {
+            Some((entry, line))
+        }
+        _ => None,
+    }
+}
+
+/// Arrow keys move visually on lines holding right-to-left text. Past the
+/// line's visual edge the caret leaves through whichever logical end sits
+/// there: the start of an RTL line is its right edge.
+fn move_grapheme_visual(
+    layout_index: &LayoutIndex,
+    entry: &LayoutEntry,
+    line: &LayoutLine,
+    pos: &Position,
+    rightward: bool,
+) -> Option<Selection> {
+    if let Some(target) = bidi::move_visual(line, pos, rightward) {
+        return Some(Selection::collapsed(target));
+    }
+    let edge = bidi::edge_offset(line, rightward)?;
+    if edge == first_position_in_line(line).offset {
+        let prev = prev_navigable_entry(layout_index, entry)?;
+        Some(landed_entry(layout_index, prev, true, false))
+    } else {
+        let next = next_navigable_entry(layout_index, entry)?;
+        Some(landed_entry(layout_index, next, false, true))
+    }
+}
+
 fn move_grapheme_forward(layout_index: &LayoutIndex, pos: &Position) -> Option<Selection> {
     let entry = layout_index.entry_for_position(pos)?;
 
@@ -1074,6 +1125,7 @@ mod tests {
             graphemes,
             cursor_ascent: 0.0,
             cursor_descent: 0.0,
+            rtl: false,
         }
     }
 
@@ -61,12 +61,13 @@ pub(crate) fn drag_selection_for_entry(
     match entry.content(layout_index)? {
         LayoutContent::Line(_) => {
             let rect = paragraph_break.geometry.rect.rect;
-            let page_y = point.y - point.page_y_start;
+            let page_x = point.column.to_page_x(point.x);
+            let page_y = point.column.to_page_y(point.y);
             let x_mid = rect.x + rect.width / 2.0;
             if paragraph_break.geometry.rect.page_idx == point.page_idx
                 && page_y >= rect.y
                 && page_y <= rect.bottom()
-                && point.x >= x_mid
+                && page_x >= x_mid
                 && point.x <= paragraph_break.geometry.line_right
             {
                 Some(paragraph_break.range)
@@ -161,12 +162,12 @@ fn geometry_for_line_entry(
     Some(ParagraphBreakGeometry {
         rect: PageRect::new(
             page_idx,
-            Rect::from_xywh(
+            pages[page_idx].to_page_rect(Rect::from_xywh(
                 x,
-                entry.rect.y - pages[page_idx].y_start,
+                entry.rect.y,
                 width,
                 entry.rect.height,
-            ),
+            )),
         ),
         line_right: entry.rect.right(),
     })
@@ -468,7 +469,7 @@ mod tests {
                 page_idx: 0,
                 x: 0.0,
                 y: gap_entry.rect.y + gap_entry.rect.height / 2.0,
-                page_y_start: 0.0,
+                column: index.pages()[0].columns[0],
             };
 
             let result = drag_selection_for_entry(&index, &view, &anchor_rp, gap_entry, point);
@@ -78,7 +78,8 @@ fn atom_pointer_style(view: &DocView, id: editor_crdt::Dot) -> PointerStyle {
             | Node::Embed(_)
             | Node::Archived(_)
             | Node::HorizontalRule(_)
-            | Node::PageBreak(_) => PointerStyle::Default,
+            | Node::PageBreak(_)
+            | Node::ColumnBreak(_) => PointerStyle::Default,
             _ => PointerStyle::Text,
         };
     }
@@ -89,7 +90,8 @@ fn atom_pointer_style(view: &DocView, id: editor_crdt::Dot) -> PointerStyle {
             | NodeType::Embed
             | NodeType::Archived
             | NodeType::HorizontalRule
-            | NodeType::PageBreak => PointerStyle::Default,
+            | NodeType::PageBreak
+            | NodeType::ColumnBreak => PointerStyle::Default,
             _ => PointerStyle::Text,
         };
     }
@@ -105,7 +107,8 @@ fn box_pointer_style(node: &Node) -> PointerStyle {
         | Node::Embed(_)
         | Node::Archived(_)
         | Node::HorizontalRule(_)
-        | Node::PageBreak(_) => PointerStyle::Default,
+        | Node::PageBreak(_)
+        | Node::ColumnBreak(_) => PointerStyle::Default,
         _ => PointerStyle::Text,
     }
 }
@@ -447,6 +447,7 @@ mod tests {
             graphemes,
             cursor_ascent: 0.0,
             cursor_descent: 0.0,
+            rtl: false,
         }
     }
 
@@ -18,6 +18,7 @@ use serde::{Deserialize, Serialize};
 use crate::page::{LayoutPage, PageRect};
 use crate::paginate::types::{LayoutAtom, LayoutBox, LayoutContent, LayoutLine, LayoutNode};
 
+use super::bidi;
 use super::common::{Phase, line_end_x, line_start_x, page_for_y};
 use super::grapheme;
 use super::layout_index::{LayoutEntry, LayoutIndex};
@@ -305,25 +306,26 @@ fn entry_edge_endpoint(
     let mut trailing = None;
 
     for (page_idx, page) in layout_index.pages().iter().enumerate() {
-        if node_bottom <= page.y_start || node_top >= page.y_end {
-            continue;
-        }
-
-        let top = node_top.max(page.y_start);
-        let bottom = node_bottom.min(page.y_end);
-        let x = match edge {
-            EndpointEdge::Leading => entry.rect.x,
-            EndpointEdge::Trailing => entry.rect.x + entry.rect.width,
-        };
-        let endpoint = PageRect::new(
-            page_idx,
-            Rect::from_xywh(x, top - page.y_start, 0.0, bottom - top),
-        );
+        for (column, top, bottom) in page.column_slices(node_top, node_bottom) {
+            let x = match edge {
+                EndpointEdge::Leading => entry.rect.x,
+                EndpointEdge::Trailing => entry.rect.x + entry.rect.width,
+            };
+            let endpoint = PageRect::new(
+                page_idx,
+                Rect::from_xywh(
+                    column.to_page_x(x),
+                    column.to_page_y(top),
+                    0.0,
+                    bottom - top,
+                ),
+            );
 
-        if edge == EndpointEdge::Leading {
-            return Some(endpoint);
+            if edge == EndpointEdge::Leading {
+                return Some(endpoint);
+            }
+            trailing = Some(endpoint);
         }
-        trailing = Some(endpoint);
     }
 
     trailing
@@ -439,20 +441,17 @@ fn selected_external_atom_rects(
             continue;
         }
         for (page_idx, page) in pages.iter().enumerate() {
-            if entry.rect.y >= page.y_end || entry.rect.bottom() <= page.y_start {
-                continue;
+            for (column, top, bottom) in page.column_slices(entry.rect.y, entry.rect.bottom()) {
+                out.push(PageRect::new(
+                    page_idx,
+                    Rect::from_xywh(
+                        column.to_page_x(entry.rect.x),
+                        column.to_page_y(top),
+                        entry.rect.width,
+                        bottom - top,
+                    ),
+                ));
             }
-            let top = entry.rect.y.max(page.y_start);
-            let bottom = entry.rect.bottom().min(page.y_end);
-            out.push(PageRect::new(
-                page_idx,
-                Rect::from_xywh(
-                    entry.rect.x,
-                    top - page.y_start,
-                    entry.rect.width,
-                    bottom - top,
-                ),
-            ));
         }
     }
     out
@@ -462,12 +461,7 @@ fn atom_selection_rect(pages: &[LayoutPage], rect: Rect) -> Option<SelectionRect
     let page_idx = page_for_y(pages, rect.y)?;
     Some(PageRect::with_meta(
         page_idx,
-        Rect::from_xywh(
-            rect.x,
-            rect.y - pages[page_idx].y_start,
-            rect.width,
-            rect.height,
-        ),
+        pages[page_idx].to_page_rect(rect),
         SelectionRectKind::Atom,
     ))
 }
@@ -634,27 +628,47 @@ fn visit_line(
         _ => return,
     };
 
-    let width = if x_end > x_start {
-        x_end - x_start
+    // Once a line mixes directions, one logical range can cover several
+    // visually disjoint spans.
+    let spans = if bidi::is_bidi(line) {
+        let start = if contains_from {
+            from.offset
+        } else {
+            grapheme::first_position_in_line(line).offset
+        };
+        let end = if contains_to {
+            to.offset
+        } else {
+            grapheme::last_position_in_line(line).offset
+        };
+        bidi::range_spans(line, start..end)
+            .into_iter()
+            .map(|(x0, x1)| (x0, x1 - x0))
+            .collect()
+    } else if x_end > x_start {
+        vec![(x_start, x_end - x_start)]
     } else if strut_line_has_selectable_child_range(line) {
-        placeholder_width
+        vec![(x_start, placeholder_width)]
     } else {
         return;
     };
 
-    if let Some(page_idx) = page_for_y(pages, node.rect.y) {
-        let band = ruby_band(line);
-        let box_height = (node.rect.height - band).max(0.0);
-        let x = node.rect.x + x_start;
-        let box_top = node.rect.y + band - pages[page_idx].y_start;
+    let Some(page_idx) = page_for_y(pages, node.rect.y) else {
+        return;
+    };
+    let band = ruby_band(line);
+    let box_height = (node.rect.height - band).max(0.0);
+    let column = pages[page_idx].column_at_y(node.rect.y);
+    let box_top = column.to_page_y(node.rect.y + band);
+    let text_height = text_area_height(line);
+    let text_top = box_top + (box_height - text_height).max(0.0) * 0.5;
+    for (x_start, width) in spans {
+        let x = column.to_page_x(node.rect.x + x_start);
         let line_box = PageRect::with_meta(
             page_idx,
             Rect::from_xywh(x, box_top, width, box_height),
             SelectionRectKind::Text,
         );
-
-        let text_height = text_area_height(line);
-        let text_top = box_top + (box_height - text_height).max(0.0) * 0.5;
         let text = PageRect::with_meta(
             page_idx,
             Rect::from_xywh(x, text_top, width, text_height),
@@ -788,22 +802,19 @@ fn visit_box(
         let node_top = node.rect.y;
         let node_bottom = node_top + node.rect.height;
         for (page_idx, page) in pages.iter().enumerate() {
-            if node_bottom <= page.y_start || node_top >= page.y_end {
-                continue;
+            for (column, top, bottom) in page.column_slices(node_top, node_bottom) {
+                let rect = PageRect::with_meta(
+                    page_idx,
+                    Rect::from_xywh(
+                        column.to_page_x(node.rect.x),
+                        column.to_page_y(top),
+                        node.rect.width,
+                        bottom - top,
+                    ),
+                    SelectionRectKind::Block,
+                );
+                rects.push_same(rect);
             }
-            let top = node_top.max(page.y_start);
-            let bottom = node_bottom.min(page.y_end);
-            let rect = PageRect::with_meta(
-                page_idx,
-                Rect::from_xywh(
-                    node.rect.x,
-                    top - page.y_start,
-                    node.rect.width,
-                    bottom - top,
-                ),
-                SelectionRectKind::Block,
-            );
-            rects.push_same(rect);
         }
     }
 }
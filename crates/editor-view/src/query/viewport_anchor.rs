@@ -219,15 +219,15 @@ fn resolve_node(
     let Some(page_idx) = layout_index.page_idx_for_y(y) else {
         return ViewportAnchorResolution::NotLaidOut;
     };
-    let Some(page_y_start) = layout_index.page_y_start(page_idx) else {
+    let Some(column) = layout_index.page_column(page_idx, y) else {
         return ViewportAnchorResolution::NotLaidOut;
     };
     ViewportAnchorResolution::Resolved {
         geometry: ResolvedViewportAnchor {
             point: ViewportAnchorPoint {
                 page_idx,
-                x: entry.rect.x + offset_x,
-                y: y - page_y_start,
+                x: column.to_page_x(entry.rect.x + offset_x),
+                y: column.to_page_y(y),
             },
             rect: None,
         },
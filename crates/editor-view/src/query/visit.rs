@@ -27,7 +27,7 @@ pub trait PageVisitor {
 }
 
 pub fn visit_page(tree: &PageFragmentTree, visitor: &mut impl PageVisitor) {
-    if let Some(root) = &tree.root {
+    for root in &tree.roots {
         visit_node(root, visitor);
     }
 }
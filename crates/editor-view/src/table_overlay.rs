@@ -84,7 +84,7 @@ pub(crate) fn page_table_overlays(
     content_width: f32,
 ) -> Vec<TableOverlay> {
     let mut overlays = Vec::new();
-    if let Some(root) = &fragment_tree.root {
+    for root in &fragment_tree.roots {
         collect_table_overlays(
             root,
             fragment_tree.page_idx,
@@ -1,5 +1,6 @@
 use std::sync::{Arc, Mutex, OnceLock};
 
+use editor_common::time::{Duration, Instant};
 use editor_common::{EdgeInsets, Movement};
 use editor_crdt::Dot;
 use editor_model::{LayoutMode, Node, NodeView};
@@ -12,14 +13,16 @@ use editor_state::{
 use crate::measure::Measurer;
 use crate::measure::context::{MeasureContext, measure_context};
 use crate::measure::nodes::dispatch::content_remeasurement_target;
+use crate::measure::text::manuscript::ManuscriptGrid;
 use crate::measure::text::measure::LineStrutExpansion;
 use crate::measure::text::resolve::style_from_effective_modifiers;
 use crate::measure::text::strut::compute_strut;
 use crate::measure::types::MeasuredTree;
-use crate::page::LayoutPage;
+use crate::page::{LayoutPage, ManuscriptPageGrid};
 use crate::page_fragment::{PageFragmentTree, build_page_fragment_tree};
 use crate::paginate::paginator::Paginator;
 use crate::paginate::types::LayoutContent;
+use crate::query::accessibility::RetainedAccessibility;
 use crate::query::cursor::CursorMetrics;
 use crate::query::hit_test::ExtendingHit;
 use crate::query::layout_index::LayoutIndex;
@@ -36,6 +39,7 @@ fn measure_context_with_pending_caret(
     resource: &mut Resource,
 ) -> MeasureContext {
     let mut context = measure_context(view_state);
+    context.manuscript = ManuscriptGrid::from_layout_mode(View::doc_layout_mode(state));
     context.pending_caret_expansion = view_state.pending_overlay.as_ref().and_then(|pending| {
         let modifiers =
             resolve_caret_modifiers(&state.projected, &pending.position, &pending.modifiers)
@@ -61,6 +65,24 @@ pub struct View {
     viewport: Viewport,
     view_state: ViewState,
     measurer: Measurer,
+    layout_stats: Option<LayoutStats>,
+    measured_baseline: u64,
+    accessibility: RetainedAccessibility,
+    /// Layout changes since the last accessibility snapshot or update: the
+    /// blocks spliced in place, or `Full` once anything repaginated.
+    accessibility_dirty: LayoutDirty,
+}
+
+/// Layout work since the last [`View::take_layout_stats`]. Collected only
+/// while enabled, so a disabled view never reads the clock.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct LayoutStats {
+    /// Blocks measured afresh rather than served from the measure cache.
+    pub blocks_remeasured: u32,
+    /// Pages produced by full repagination. A content splice that keeps the
+    /// retained geometry repaginates nothing.
+    pub pages_repaginated: u32,
+    pub pagination: Duration,
 }
 
 struct LayoutResult {
@@ -86,9 +108,31 @@ impl View {
             layout_state: None,
             fingerprint: None,
             measurer: Measurer::new(),
+            layout_stats: None,
+            measured_baseline: 0,
+            accessibility: RetainedAccessibility::default(),
+            accessibility_dirty: LayoutDirty::Full,
         }
     }
 
+    pub fn set_collect_layout_stats(&mut self, enabled: bool) {
+        self.layout_stats = enabled.then(LayoutStats::default);
+        self.measured_baseline = self.measurer.measured_count();
+    }
+
+    /// Returns and resets the collected stats; all zero while collection is off.
+    pub fn take_layout_stats(&mut self) -> LayoutStats {
+        let Some(stats) = self.layout_stats.as_mut() else {
+            return LayoutStats::default();
+        };
+        let measured = self.measurer.measured_count();
+        let mut taken = std::mem::take(stats);
+        taken.blocks_remeasured =
+            u32::try_from(measured - self.measured_baseline).unwrap_or(u32::MAX);
+        self.measured_baseline = measured;
+        taken
+    }
+
     pub fn layout(&mut self, state: &State) {
         self.view_state.pending_overlay = None;
         self.view_state.gap_phantom = None;
@@ -189,6 +233,9 @@ impl View {
             && !targets.is_empty()
             && self.try_splice_content(state, targets)
         {
+            for target in targets {
+                self.accessibility_dirty.mark_content(*target);
+            }
             self.layout_state = Some(state.clone());
             if pending_changed {
                 self.view_state.preferred_x = None;
@@ -235,6 +282,7 @@ impl View {
             content_width: f32,
             page_top: f32,
             page_bottom: f32,
+            column_index: usize,
         }
 
         let mut seeds: Vec<SpliceSeed> = Vec::new();
@@ -294,15 +342,17 @@ impl View {
                 if !(y >= page.y_start && y < page.y_end) {
                     return false;
                 }
-                let (page_top, page_bottom) = if continuous {
+                let (page_top, page_bottom, column_index) = if continuous {
                     let top = if pi == 0 {
                         page.y_start + CONTINUOUS_MARGIN_X
                     } else {
                         page.y_start
                     };
-                    (top, top + CONTINUOUS_CONTENT_CAP)
+                    (top, top + CONTINUOUS_CONTENT_CAP, 0)
                 } else {
-                    (page.content_y_start, page.content_y_end)
+                    let column_index = page.column_index_at_y(y);
+                    let column = &page.columns[column_index];
+                    (column.content_y_start, column.content_y_end, column_index)
                 };
                 seeds.push(SpliceSeed {
                     dot: *dot,
@@ -314,6 +364,7 @@ impl View {
                     content_width: w,
                     page_top,
                     page_bottom,
+                    column_index,
                 });
             }
         }
@@ -341,6 +392,7 @@ impl View {
                 seed.content_width,
                 seed.page_top,
                 seed.page_bottom,
+                seed.column_index,
             );
             let Some(old) = self
                 .layout
@@ -381,9 +433,11 @@ impl View {
         self.measurer.clear();
     }
 
+    /// The document's layout mode, normalized once here so pagination,
+    /// measuring and the drawn manuscript grid all see the same bounds.
     fn doc_layout_mode(state: &State) -> LayoutMode {
         match state.view().root().map(|r| r.node()) {
-            Some(Node::Root(r)) => *r.layout_mode.get(),
+            Some(Node::Root(r)) => r.layout_mode.get().normalized(),
             _ => LayoutMode::default(),
         }
     }
@@ -398,6 +452,8 @@ impl View {
                 page_margin_bottom,
                 page_margin_left,
                 page_margin_right,
+                columns,
+                column_gap,
             } => {
                 let margins = EdgeInsets {
                     top: page_margin_top as f32,
@@ -405,9 +461,10 @@ impl View {
                     left: page_margin_left as f32,
                     right: page_margin_right as f32,
                 };
-                let content_width = page_width as f32 - margins.left - margins.right;
                 let paginator =
-                    Paginator::paginated(page_width as f32, page_height as f32, margins);
+                    Paginator::paginated(page_width as f32, page_height as f32, margins)
+                        .with_columns(columns as usize, column_gap as f32);
+                let content_width = paginator.column_width();
                 (
                     paginator,
                     content_width,
@@ -435,6 +492,53 @@ impl View {
                     },
                 )
             }
+            LayoutMode::Manuscript {
+                columns,
+                rows,
+                cell_size,
+                row_gap,
+                page_margin,
+            } => {
+                let margin = page_margin as f32;
+                let cell = cell_size as f32;
+                let content_width = columns as f32 * cell;
+                let content_height = rows as f32 * (cell + row_gap as f32);
+                let paginator = Paginator::paginated(
+                    content_width + 2.0 * margin,
+                    content_height + 2.0 * margin,
+                    EdgeInsets::all(margin),
+                );
+                (
+                    paginator,
+                    content_width,
+                    LayoutFingerprint {
+                        layout_mode,
+                        effective_viewport_width: 0.0,
+                    },
+                )
+            }
+        }
+    }
+
+    /// The cell grid to draw on every page, when the document is laid out
+    /// on manuscript paper.
+    pub fn manuscript_grid(&self) -> Option<ManuscriptPageGrid> {
+        match self.fingerprint.as_ref()?.layout_mode {
+            LayoutMode::Manuscript {
+                columns,
+                rows,
+                cell_size,
+                row_gap,
+                page_margin,
+            } => Some(ManuscriptPageGrid {
+                x: page_margin as f32,
+                y: page_margin as f32,
+                columns: columns as usize,
+                rows: rows as usize,
+                cell_size: cell_size as f32,
+                row_gap: row_gap as f32,
+            }),
+            _ => None,
         }
     }
 
@@ -528,6 +632,7 @@ impl View {
         let view = state.view();
         let Some(root) = view.root() else {
             self.layout = None;
+            self.accessibility_dirty.mark_full();
             return;
         };
         let measured = {
@@ -538,7 +643,12 @@ impl View {
                 .measure(&root, content_width, &ctx, &mut resource);
             Arc::unwrap_or_clone(root_arc)
         };
+        let paginate_started = self.layout_stats.is_some().then(Instant::now);
         let paginated = paginator.paginate(MeasuredTree { root: measured });
+        if let (Some(stats), Some(started)) = (self.layout_stats.as_mut(), paginate_started) {
+            stats.pagination += started.elapsed();
+            stats.pages_repaginated += paginated.pages.len() as u32;
+        }
         let pages = paginated.pages;
         let prev = self.layout.take();
 
@@ -561,6 +671,14 @@ impl View {
         } else {
             false
         };
+        match content_targets {
+            Some(targets) if reusable => {
+                for target in targets {
+                    self.accessibility_dirty.mark_content(*target);
+                }
+            }
+            _ => self.accessibility_dirty.mark_full(),
+        }
         let layout_index = if reusable {
             prev.expect("checked above")
                 .layout_index
@@ -657,6 +775,52 @@ impl View {
         crate::query::interactive::interactive_regions(&result.layout_index, &state.view())
     }
 
+    /// Full accessibility snapshot; later [`View::accessibility_update`]
+    /// calls are relative to it.
+    pub fn accessibility_tree(&mut self, state: &State) -> crate::query::AccessibilityTree {
+        let tree = match self.layout.as_ref() {
+            Some(result) => crate::query::accessibility::accessibility_tree(
+                &result.layout_index,
+                &state.view(),
+                state.selection.as_ref(),
+            ),
+            None => crate::query::AccessibilityTree::default(),
+        };
+        self.accessibility.reset(&tree);
+        self.accessibility_dirty = LayoutDirty::empty();
+        tree
+    }
+
+    /// Accessibility changes since the last snapshot or update. Blocks the
+    /// layout spliced in place are rebuilt alone; a repagination can move
+    /// every block, so it rebuilds the whole tree.
+    pub fn accessibility_update(&mut self, state: &State) -> crate::query::AccessibilityUpdate {
+        let dirty = std::mem::replace(&mut self.accessibility_dirty, LayoutDirty::empty());
+        let Some(result) = self.layout.as_ref() else {
+            return self
+                .accessibility
+                .replace(crate::query::AccessibilityTree::default());
+        };
+        let view = state.view();
+        let selection = state.selection.as_ref();
+        if let LayoutDirty::Incremental { content, .. } = &dirty
+            && let Some(update) = self.accessibility.update_blocks(
+                &result.layout_index,
+                &view,
+                content.iter().copied(),
+                selection,
+            )
+        {
+            return update;
+        }
+        self.accessibility
+            .replace(crate::query::accessibility::accessibility_tree(
+                &result.layout_index,
+                &view,
+                selection,
+            ))
+    }
+
     pub fn cursor_hit_rects(&self, state: &State) -> Vec<crate::page::PageRect> {
         let Some(result) = self.layout.as_ref() else {
             return Vec::new();
@@ -976,7 +1140,7 @@ impl View {
                 selection,
                 result.content_width,
             ),
-            LayoutMode::Paginated { .. } => {
+            LayoutMode::Paginated { .. } | LayoutMode::Manuscript { .. } => {
                 let mut overlays = Vec::new();
                 for page_idx in 0..result.pages.len() {
                     overlays.extend(self.page_table_overlays(state, page_idx, selection));
@@ -2070,6 +2234,48 @@ mod incremental_tests {
         }
     }
 
+    #[test]
+    fn accessibility_update_after_content_edit_rebuilds_only_the_edited_block() {
+        let mut g = OpGraph::<EditOp>::with_actor(1);
+        let root = Dot::ROOT;
+        let mut pos = 0;
+        for _ in 0..3 {
+            g.add_mut(seq_block(pos, NodeType::Paragraph, vec![root]))
+                .unwrap();
+            pos += 1;
+            for ch in "mmmm".chars() {
+                g.add_mut(seq_char(pos, ch)).unwrap();
+                pos += 1;
+            }
+        }
+        g.commit_mut();
+        let base = ProjectedState::from_graph(g).unwrap();
+        let pre = State::new(base.clone(), None);
+        let mut view = make_view(800.0);
+        view.layout(&pre);
+        let snapshot = view.accessibility_tree(&pre);
+        assert!(view.accessibility_update(&pre).is_empty());
+
+        let mut ed = base;
+        let _ = ed.take_layout_dirty();
+        ed.apply(seq_char(1, 'X')).unwrap();
+        let dirty = ed.take_layout_dirty();
+        let post = State::new(ed, None);
+        view.reconcile(&post, dirty, None, None);
+
+        let first = snapshot.nodes[0].children[0];
+        let update = view.accessibility_update(&post);
+        let updated: Vec<Dot> = update.updated.iter().map(|n| n.id).collect();
+        assert_eq!(updated, vec![first]);
+        assert_eq!(update.updated[0].text, "Xmmmm");
+        assert!(update.removed.is_empty());
+        assert!(view.accessibility_update(&post).is_empty());
+
+        // A full layout rebuilds and diffs the whole tree, which is unchanged.
+        view.layout(&post);
+        assert!(view.accessibility_update(&post).is_empty());
+    }
+
     #[test]
     fn fold_toggle_remeasures_only_the_fold_subtree() {
         let mut g = OpGraph::<EditOp>::with_actor(1);
@@ -0,0 +1,11 @@
+#[derive(Debug, thiserror::Error)]
+pub enum ZipError {
+    #[error("not a zip archive")]
+    NotZip,
+
+    #[error("unsupported zip entry `{0}`")]
+    UnsupportedEntry(String),
+
+    #[error("corrupt zip entry `{0}`")]
+    CorruptEntry(String),
+}
@@ -0,0 +1,16 @@
+//! ZIP archives as OOXML and EPUB packages use them: stored and deflated
+//! entries without ZIP64, encryption or multiple disks.
+
+mod error;
+mod read;
+mod write;
+
+pub use error::ZipError;
+pub use read::ZipArchive;
+pub use write::{ZipWriter, crc32};
+
+const LOCAL_HEADER: u32 = 0x0403_4b50;
+const CENTRAL_HEADER: u32 = 0x0201_4b50;
+const END_OF_CENTRAL_DIRECTORY: u32 = 0x0605_4b50;
+const METHOD_STORED: u16 = 0;
+const METHOD_DEFLATED: u16 = 8;
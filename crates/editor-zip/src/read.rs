@@ -0,0 +1,214 @@
+use crate::{
+    CENTRAL_HEADER, END_OF_CENTRAL_DIRECTORY, LOCAL_HEADER, METHOD_DEFLATED, METHOD_STORED,
+    ZipError, crc32,
+};
+
+const END_OF_CENTRAL_DIRECTORY_LEN: usize = 22;
+const CENTRAL_HEADER_LEN: usize = 46;
+const LOCAL_HEADER_LEN: usize = 30;
+/// Inflated size cap per entry. Declared sizes are untrusted, so the limit is
+/// enforced while inflating.
+const MAX_ENTRY_BYTES: usize = 256 * 1024 * 1024;
+
+/// Read-only view of a ZIP archive. Entries are kept in central directory
+/// order.
+pub struct ZipArchive<'a> {
+    bytes: &'a [u8],
+    entries: Vec<Entry>,
+}
+
+struct Entry {
+    name: String,
+    method: u16,
+    crc: u32,
+    compressed_size: usize,
+    size: usize,
+    local_offset: usize,
+}
+
+impl<'a> ZipArchive<'a> {
+    pub fn new(bytes: &'a [u8]) -> Result<Self, ZipError> {
+        let eocd = find_end_of_central_directory(bytes).ok_or(ZipError::NotZip)?;
+        let count = read_u16(bytes, eocd + 10).ok_or(ZipError::NotZip)? as usize;
+        let mut offset = read_u32(bytes, eocd + 16).ok_or(ZipError::NotZip)? as usize;
+
+        // Every record takes at least its fixed header, so a count the
+        // directory cannot hold is rejected before reserving for it.
+        let mut entries = Vec::with_capacity(count.min(bytes.len() / CENTRAL_HEADER_LEN));
+        for _ in 0..count {
+            if read_u32(bytes, offset) != Some(CENTRAL_HEADER) {
+                return Err(ZipError::NotZip);
+            }
+            let at = |field: usize| offset.checked_add(field).ok_or(ZipError::NotZip);
+            let field = |field: usize| read_u16(bytes, at(field)?).ok_or(ZipError::NotZip);
+            let wide = |field: usize| read_u32(bytes, at(field)?).ok_or(ZipError::NotZip);
+            let flags = field(8)?;
+            let method = field(10)?;
+            let crc = wide(16)?;
+            let compressed_size = wide(20)?;
+            let size = wide(24)?;
+            let name_len = field(28)? as usize;
+            let extra_len = field(30)? as usize;
+            let comment_len = field(32)? as usize;
+            let local_offset = wide(42)?;
+            let name_start = at(CENTRAL_HEADER_LEN)?;
+            let name_end = name_start.checked_add(name_len).ok_or(ZipError::NotZip)?;
+            let name = bytes.get(name_start..name_end).ok_or(ZipError::NotZip)?;
+            let name = String::from_utf8_lossy(name).into_owned();
+            offset = name_end
+                .checked_add(extra_len + comment_len)
+                .ok_or(ZipError::NotZip)?;
+
+            if flags & 1 != 0 {
+                return Err(ZipError::UnsupportedEntry(name));
+            }
+            if [compressed_size, size, local_offset].contains(&u32::MAX) {
+                return Err(ZipError::UnsupportedEntry(name));
+            }
+            entries.push(Entry {
+                name,
+                method,
+                crc,
+                compressed_size: compressed_size as usize,
+                size: size as usize,
+                local_offset: local_offset as usize,
+            });
+        }
+        Ok(Self { bytes, entries })
+    }
+
+    /// Entry names in central directory order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().map(|entry| entry.name.as_str())
+    }
+
+    /// Inflates the entry called `name`. An exact match wins; otherwise names
+    /// are matched case-insensitively, as OPC requires.
+    pub fn read(&self, name: &str) -> Result<Option<Vec<u8>>, ZipError> {
+        let entry = self
+            .entries
+            .iter()
+            .find(|entry| entry.name == name)
+            .or_else(|| {
+                self.entries
+                    .iter()
+                    .find(|entry| entry.name.eq_ignore_ascii_case(name))
+            });
+        entry.map(|entry| self.inflate(entry)).transpose()
+    }
+
+    /// Every entry, inflated, in central directory order.
+    pub fn read_all(&self) -> Result<Vec<(String, Vec<u8>)>, ZipError> {
+        self.entries
+            .iter()
+            .map(|entry| Ok((entry.name.clone(), self.inflate(entry)?)))
+            .collect()
+    }
+
+    fn inflate(&self, entry: &Entry) -> Result<Vec<u8>, ZipError> {
+        let corrupt = || ZipError::CorruptEntry(entry.name.clone());
+
+        let offset = entry.local_offset;
+        if read_u32(self.bytes, offset) != Some(LOCAL_HEADER) {
+            return Err(corrupt());
+        }
+        let name_len = read_u16(self.bytes, offset + 26).ok_or_else(corrupt)? as usize;
+        let extra_len = read_u16(self.bytes, offset + 28).ok_or_else(corrupt)? as usize;
+        let start = offset
+            .checked_add(LOCAL_HEADER_LEN + name_len + extra_len)
+            .ok_or_else(corrupt)?;
+        let end = start
+            .checked_add(entry.compressed_size)
+            .ok_or_else(corrupt)?;
+        let body = self.bytes.get(start..end).ok_or_else(corrupt)?;
+
+        let data = match entry.method {
+            METHOD_STORED => body.to_vec(),
+            METHOD_DEFLATED => {
+                miniz_oxide::inflate::decompress_to_vec_with_limit(body, MAX_ENTRY_BYTES)
+                    .map_err(|_| corrupt())?
+            }
+            _ => return Err(ZipError::UnsupportedEntry(entry.name.clone())),
+        };
+        if data.len() != entry.size || crc32(&data) != entry.crc {
+            return Err(corrupt());
+        }
+        Ok(data)
+    }
+}
+
+fn find_end_of_central_directory(bytes: &[u8]) -> Option<usize> {
+    let last = bytes.len().checked_sub(END_OF_CENTRAL_DIRECTORY_LEN)?;
+    // The record is followed by a comment of at most `u16::MAX` bytes.
+    let first = last.saturating_sub(usize::from(u16::MAX));
+    (first..=last)
+        .rev()
+        .find(|&at| read_u32(bytes, at) == Some(END_OF_CENTRAL_DIRECTORY))
+}
+
+fn read_u16(bytes: &[u8], at: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(
+        bytes.get(at..at.checked_add(2)?)?.try_into().ok()?,
+    ))
+}
+
+fn read_u32(bytes: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(
+        bytes.get(at..at.checked_add(4)?)?.try_into().ok()?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ZipWriter;
+
+    #[test]
+    fn reads_what_the_writer_wrote() {
+        let text = b"hello ".repeat(64);
+        let mut zip = ZipWriter::new();
+        zip.stored("a.txt", b"plain");
+        zip.deflated("word/B.xml", &text);
+        let bytes = zip.finish();
+        let archive = ZipArchive::new(&bytes).unwrap();
+
+        assert_eq!(archive.names().collect::<Vec<_>>(), ["a.txt", "word/B.xml"]);
+        assert_eq!(archive.read("a.txt").unwrap(), Some(b"plain".to_vec()));
+        assert_eq!(archive.read("word/b.xml").unwrap(), Some(text));
+        assert_eq!(archive.read("missing").unwrap(), None);
+    }
+
+    #[test]
+    fn rejects_non_zip_input() {
+        assert!(matches!(
+            ZipArchive::new(b"<html></html>"),
+            Err(ZipError::NotZip)
+        ));
+    }
+
+    #[test]
+    fn rejects_offsets_past_the_end_and_bad_checksums() {
+        let mut zip = ZipWriter::new();
+        zip.stored("a.txt", b"plain");
+        let bytes = zip.finish();
+        let eocd = bytes.len() - END_OF_CENTRAL_DIRECTORY_LEN;
+        let central = u32::from_le_bytes(bytes[eocd + 16..eocd + 20].try_into().unwrap()) as usize;
+
+        let mut huge = bytes.clone();
+        huge[central + 20..central + 24].copy_from_slice(&(u32::MAX - 1).to_le_bytes());
+        let archive = ZipArchive::new(&huge).unwrap();
+        assert!(matches!(
+            archive.read("a.txt"),
+            Err(ZipError::CorruptEntry(name)) if name == "a.txt"
+        ));
+
+        let mut flipped = bytes;
+        let body = LOCAL_HEADER_LEN + "a.txt".len();
+        flipped[body] ^= 1;
+        let archive = ZipArchive::new(&flipped).unwrap();
+        assert!(matches!(
+            archive.read("a.txt"),
+            Err(ZipError::CorruptEntry(_))
+        ));
+    }
+}
@@ -0,0 +1,172 @@
+use crate::{
+    CENTRAL_HEADER, END_OF_CENTRAL_DIRECTORY, LOCAL_HEADER, METHOD_DEFLATED, METHOD_STORED,
+};
+
+/// Writes a ZIP archive entry by entry. Every entry carries the same DOS
+/// timestamp so identical input produces identical bytes.
+#[derive(Default)]
+pub struct ZipWriter {
+    buf: Vec<u8>,
+    central: Vec<u8>,
+    entries: u16,
+}
+
+const VERSION: u16 = 20;
+/// 1980-01-01 00:00, the earliest DOS date.
+const DOS_DATE: u16 = (1 << 5) | 1;
+const DOS_TIME: u16 = 0;
+
+impl ZipWriter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an entry without compression. The EPUB `mimetype` entry must be
+    /// written this way, first.
+    pub fn stored(&mut self, name: &str, data: &[u8]) {
+        self.entry(name, data, data, METHOD_STORED);
+    }
+
+    pub fn deflated(&mut self, name: &str, data: &[u8]) {
+        let compressed = miniz_oxide::deflate::compress_to_vec(data, 6);
+        self.entry(name, data, &compressed, METHOD_DEFLATED);
+    }
+
+    pub fn finish(mut self) -> Vec<u8> {
+        let central_offset = self.buf.len() as u32;
+        let central_size = self.central.len() as u32;
+        self.buf.extend_from_slice(&self.central);
+        put_u32(&mut self.buf, END_OF_CENTRAL_DIRECTORY);
+        put_u16(&mut self.buf, 0);
+        put_u16(&mut self.buf, 0);
+        put_u16(&mut self.buf, self.entries);
+        put_u16(&mut self.buf, self.entries);
+        put_u32(&mut self.buf, central_size);
+        put_u32(&mut self.buf, central_offset);
+        put_u16(&mut self.buf, 0);
+        self.buf
+    }
+
+    fn entry(&mut self, name: &str, data: &[u8], body: &[u8], method: u16) {
+        let offset = self.buf.len() as u32;
+        let fields = RecordFields {
+            method,
+            crc: crc32(data),
+            compressed_size: body.len() as u32,
+            size: data.len() as u32,
+            name_len: name.len() as u16,
+        };
+
+        put_u32(&mut self.buf, LOCAL_HEADER);
+        fields.put(&mut self.buf);
+        self.buf.extend_from_slice(name.as_bytes());
+        self.buf.extend_from_slice(body);
+
+        put_u32(&mut self.central, CENTRAL_HEADER);
+        put_u16(&mut self.central, VERSION);
+        fields.put(&mut self.central);
+        // Comment length, disk number, internal and external attributes.
+        put_u16(&mut self.central, 0);
+        put_u16(&mut self.central, 0);
+        put_u16(&mut self.central, 0);
+        put_u32(&mut self.central, 0);
+        put_u32(&mut self.central, offset);
+        self.central.extend_from_slice(name.as_bytes());
+        self.entries += 1;
+    }
+}
+
+/// The header fields local and central records share, from the version
+/// needed through the extra field length.
+struct RecordFields {
+    method: u16,
+    crc: u32,
+    compressed_size: u32,
+    size: u32,
+    name_len: u16,
+}
+
+impl RecordFields {
+    fn put(&self, out: &mut Vec<u8>) {
+        put_u16(out, VERSION);
+        put_u16(out, 0);
+        put_u16(out, self.method);
+        put_u16(out, DOS_TIME);
+        put_u16(out, DOS_DATE);
+        put_u32(out, self.crc);
+        put_u32(out, self.compressed_size);
+        put_u32(out, self.size);
+        put_u16(out, self.name_len);
+        put_u16(out, 0);
+    }
+}
+
+fn put_u16(out: &mut Vec<u8>, v: u16) {
+    out.extend_from_slice(&v.to_le_bytes());
+}
+
+fn put_u32(out: &mut Vec<u8>, v: u32) {
+    out.extend_from_slice(&v.to_le_bytes());
+}
+
+/// CRC-32 (IEEE 802.3), the checksum ZIP and PNG share.
+pub fn crc32(data: &[u8]) -> u32 {
+    const TABLE: [u32; 256] = {
+        let mut table = [0u32; 256];
+        let mut i = 0;
+        while i < 256 {
+            let mut c = i as u32;
+            let mut k = 0;
+            while k < 8 {
+                c = if c & 1 != 0 {
+                    0xEDB8_8320 ^ (c >> 1)
+                } else {
+                    c >> 1
+                };
+                k += 1;
+            }
+            table[i] = c;
+            i += 1;
+        }
+        table
+    };
+    let mut crc = !0u32;
+    for &byte in data {
+        crc = TABLE[((crc ^ u32::from(byte)) & 0xFF) as usize] ^ (crc >> 8);
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crc32_matches_the_check_value() {
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+    }
+
+    #[test]
+    fn entries_are_listed_in_the_central_directory() {
+        let mut zip = ZipWriter::new();
+        zip.stored("mimetype", b"application/epub+zip");
+        zip.deflated("a.txt", &[b'a'; 100]);
+        let bytes = zip.finish();
+
+        assert!(bytes.starts_with(&LOCAL_HEADER.to_le_bytes()));
+        assert_eq!(&bytes[30..38], b"mimetype");
+        assert_eq!(&bytes[38..58], b"application/epub+zip");
+
+        let eocd = bytes.len() - 22;
+        assert_eq!(
+            bytes[eocd..eocd + 4],
+            END_OF_CENTRAL_DIRECTORY.to_le_bytes()
+        );
+        assert_eq!(u16::from_le_bytes([bytes[eocd + 10], bytes[eocd + 11]]), 2);
+        let central = u32::from_le_bytes(bytes[eocd + 16..eocd + 20].try_into().unwrap());
+        assert_eq!(
+            bytes[central as usize..central as usize + 4],
+            CENTRAL_HEADER.to_le_bytes()
+        );
+    }
+}